@@ -0,0 +1,42 @@
+//! Benchmarks for the auction NFT module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+const CURRENCY: u32 = 1;
+
+benchmarks! {
+	where_clause { where T::ClassData: Default, T::TokenData: Default, CurrencyIdOf<T>: From<u32> }
+
+	set_royalty {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let class_id = orml_nft::Pallet::<T>::create_class(&owner, vec![], Default::default())?;
+		let royalty = T::MaxRoyalty::get();
+	}: _(RawOrigin::Signed(owner), class_id, royalty)
+	verify {
+		assert_eq!(Pallet::<T>::royalties(class_id), royalty);
+	}
+
+	create_auction {
+		let seller: T::AccountId = account("seller", 0, SEED);
+		let class_id = orml_nft::Pallet::<T>::create_class(&seller, vec![], Default::default())?;
+		let token_id = orml_nft::Pallet::<T>::mint(&seller, class_id, vec![], Default::default())?;
+		let now = frame_system::Pallet::<T>::block_number();
+	}: _(
+		RawOrigin::Signed(seller),
+		(class_id, token_id),
+		CURRENCY.into(),
+		1u32.into(),
+		now,
+		now + 10u32.into()
+	)
+	verify {
+		assert!(orml_nft::Pallet::<T>::is_owner(&Pallet::<T>::account_id(), (class_id, token_id)));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::default().build(), crate::mock::Runtime);
@@ -0,0 +1,318 @@
+//! # Auction NFT
+//!
+//! ## Overview
+//!
+//! This module sells `orml-nft` tokens in auctions, and implements
+//! `AuctionHandler` to be used as the `Handler` of the auction module.
+//!
+//! The owner of a token creates an auction with a minimum price and the
+//! currency to bid in, and the token is escrowed in the pallet account until
+//! the auction ends. Bids are reserved from the bidders and the previous bid
+//! is unreserved when outbid. When the auction ends, the token is transferred
+//! to the winner and the winning bid to the seller, less the royalty of the
+//! class paid to the class owner. The settlement is atomic: if any part of it
+//! fails, the winning bid is refunded and the token returned to the seller.
+//! Without bids, the token is returned to the seller.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{
+	pallet_prelude::*,
+	storage::{with_transaction, TransactionOutcome},
+	traits::BalanceStatus,
+	transactional, PalletId,
+};
+use frame_system::pallet_prelude::*;
+use orml_traits::{Auction, AuctionHandler, Change, MultiCurrency, MultiReservableCurrency, OnNewBidResult};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AccountIdConversion, Saturating, Zero},
+	DispatchError, DispatchResult, Perbill, RuntimeDebug,
+};
+
+mod benchmarking;
+mod mock;
+mod tests;
+mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// A token sold in an auction.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct AuctionItem<AccountId, ClassId, TokenId, CurrencyId, Balance> {
+	/// The owner of the token before the auction.
+	pub seller: AccountId,
+	/// The token sold.
+	pub token: (ClassId, TokenId),
+	/// The currency bids are reserved in.
+	pub currency_id: CurrencyId,
+	/// The minimum bid.
+	pub min_price: Balance,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	pub(crate) type BalanceOf<T> =
+		<<T as Config>::BidCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub(crate) type CurrencyIdOf<T> =
+		<<T as Config>::BidCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
+	pub(crate) type AuctionItemOf<T> = AuctionItem<
+		<T as frame_system::Config>::AccountId,
+		<T as orml_nft::Config>::ClassId,
+		<T as orml_nft::Config>::TokenId,
+		CurrencyIdOf<T>,
+		BalanceOf<T>,
+	>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + orml_nft::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The auction ID type.
+		type AuctionId: Parameter + Member + Copy + MaxEncodedLen;
+
+		/// The auction module selling the tokens, its `Handler` must be this
+		/// module.
+		type Auction: Auction<
+			Self::AccountId,
+			Self::BlockNumber,
+			AuctionId = Self::AuctionId,
+			Balance = BalanceOf<Self>,
+		>;
+
+		/// The currency bids are reserved in.
+		type BidCurrency: MultiReservableCurrency<Self::AccountId>;
+
+		/// The maximum royalty of a class.
+		#[pallet::constant]
+		type MaxRoyalty: Get<Perbill>;
+
+		/// The pallet ID, its account holds the tokens in auction.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Weight information for extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The class doesn't exist.
+		ClassNotFound,
+		/// The caller is not the owner of the class.
+		NoPermission,
+		/// The royalty exceeds `MaxRoyalty`.
+		RoyaltyTooHigh,
+		/// The auction doesn't end after it starts and after the current
+		/// block.
+		InvalidAuctionPeriod,
+		/// The winning bid is not fully reserved.
+		BidNotReserved,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The royalty of a class is set. [class_id, royalty]
+		RoyaltySet(T::ClassId, Perbill),
+		/// A token is put up for auction. [auction_id, seller, class_id,
+		/// token_id]
+		AuctionCreated(T::AuctionId, T::AccountId, T::ClassId, T::TokenId),
+		/// An auction is settled. [auction_id, winner, price, royalty]
+		AuctionSettled(T::AuctionId, T::AccountId, BalanceOf<T>, BalanceOf<T>),
+		/// An auction ended without bids, the token is returned to the
+		/// seller. [auction_id]
+		AuctionClosed(T::AuctionId),
+		/// The settlement of an auction failed, the winning bid is refunded
+		/// and the token returned to the seller. [auction_id]
+		SettlementFailed(T::AuctionId),
+	}
+
+	/// The tokens in auction.
+	///
+	/// map AuctionId => Option<AuctionItem>
+	#[pallet::storage]
+	#[pallet::getter(fn auction_items)]
+	pub type AuctionItems<T: Config> = StorageMap<_, Twox64Concat, T::AuctionId, AuctionItemOf<T>, OptionQuery>;
+
+	/// The royalty of each class, paid to the class owner from the winning
+	/// bids.
+	///
+	/// map ClassId => Perbill
+	#[pallet::storage]
+	#[pallet::getter(fn royalties)]
+	pub type Royalties<T: Config> = StorageMap<_, Twox64Concat, T::ClassId, Perbill, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the royalty of a class.
+		///
+		/// The dispatch origin for this call must be `Signed` by the class
+		/// owner.
+		#[pallet::weight(<T as Config>::WeightInfo::set_royalty())]
+		pub fn set_royalty(origin: OriginFor<T>, class_id: T::ClassId, royalty: Perbill) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let class = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassNotFound)?;
+			ensure!(class.owner == who, Error::<T>::NoPermission);
+			ensure!(royalty <= T::MaxRoyalty::get(), Error::<T>::RoyaltyTooHigh);
+
+			Royalties::<T>::insert(class_id, royalty);
+			Self::deposit_event(Event::RoyaltySet(class_id, royalty));
+			Ok(())
+		}
+
+		/// Put a token up for auction from `start` to `end`, for bids of at
+		/// least `min_price` in `currency_id`. The token is escrowed until
+		/// the auction ends.
+		///
+		/// The dispatch origin for this call must be `Signed` by the token
+		/// owner.
+		#[pallet::weight(<T as Config>::WeightInfo::create_auction())]
+		#[transactional]
+		pub fn create_auction(
+			origin: OriginFor<T>,
+			token: (T::ClassId, T::TokenId),
+			currency_id: CurrencyIdOf<T>,
+			min_price: BalanceOf<T>,
+			start: T::BlockNumber,
+			end: T::BlockNumber,
+		) -> DispatchResult {
+			let seller = ensure_signed(origin)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(end > start && end > now, Error::<T>::InvalidAuctionPeriod);
+
+			orml_nft::Pallet::<T>::transfer(&seller, &Self::account_id(), token)?;
+			let auction_id = T::Auction::new_auction(start, Some(end))?;
+			AuctionItems::<T>::insert(
+				auction_id,
+				AuctionItem {
+					seller: seller.clone(),
+					token,
+					currency_id,
+					min_price,
+				},
+			);
+
+			Self::deposit_event(Event::AuctionCreated(auction_id, seller, token.0, token.1));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account holding the tokens in auction.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// Transfer the token to `winner`, and the winning bid to the seller and
+	/// the royalty to the class owner. Returns the royalty.
+	fn settle(
+		item: &AuctionItemOf<T>,
+		winner: &T::AccountId,
+		price: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		orml_nft::Pallet::<T>::transfer(&Self::account_id(), winner, item.token)?;
+
+		let mut royalty = Zero::zero();
+		if let Some(class) = orml_nft::Pallet::<T>::classes(item.token.0) {
+			if class.owner != item.seller {
+				royalty = Self::royalties(item.token.0).mul_floor(price);
+				Self::repatriate_bid(item.currency_id, winner, &class.owner, royalty)?;
+			}
+		}
+		Self::repatriate_bid(item.currency_id, winner, &item.seller, price.saturating_sub(royalty))?;
+		Ok(royalty)
+	}
+
+	fn repatriate_bid(
+		currency_id: CurrencyIdOf<T>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		let remaining = T::BidCurrency::repatriate_reserved(currency_id, from, to, amount, BalanceStatus::Free)?;
+		ensure!(remaining.is_zero(), Error::<T>::BidNotReserved);
+		Ok(())
+	}
+}
+
+impl<T: Config> AuctionHandler<T::AccountId, BalanceOf<T>, T::BlockNumber, T::AuctionId> for Pallet<T> {
+	fn on_new_bid(
+		_now: T::BlockNumber,
+		id: T::AuctionId,
+		new_bid: (T::AccountId, BalanceOf<T>),
+		last_bid: Option<(T::AccountId, BalanceOf<T>)>,
+	) -> OnNewBidResult<T::BlockNumber> {
+		let reject = OnNewBidResult {
+			accept_bid: false,
+			auction_end_change: Change::NoChange,
+		};
+
+		let item = match Self::auction_items(id) {
+			Some(item) => item,
+			None => return reject,
+		};
+		let (bidder, amount) = new_bid;
+		if bidder == item.seller || amount < item.min_price {
+			return reject;
+		}
+
+		// a bidder raising its own bid only reserves the difference
+		let (to_reserve, outbid) = match last_bid {
+			Some((last_bidder, last_amount)) if last_bidder == bidder => (amount.saturating_sub(last_amount), None),
+			last_bid => (amount, last_bid),
+		};
+		if T::BidCurrency::reserve(item.currency_id, &bidder, to_reserve).is_err() {
+			return reject;
+		}
+		if let Some((last_bidder, last_amount)) = outbid {
+			T::BidCurrency::unreserve(item.currency_id, &last_bidder, last_amount);
+		}
+
+		OnNewBidResult {
+			accept_bid: true,
+			auction_end_change: Change::NoChange,
+		}
+	}
+
+	fn on_auction_ended(id: T::AuctionId, winner: Option<(T::AccountId, BalanceOf<T>)>) {
+		let item = match AuctionItems::<T>::take(id) {
+			Some(item) => item,
+			None => return,
+		};
+
+		if let Some((winner, price)) = winner {
+			let settled = with_transaction(|| match Self::settle(&item, &winner, price) {
+				Ok(royalty) => TransactionOutcome::Commit(Ok(royalty)),
+				Err(e) => TransactionOutcome::Rollback(Err(e)),
+			});
+			match settled {
+				Ok(royalty) => {
+					Self::deposit_event(Event::AuctionSettled(id, winner, price, royalty));
+					return;
+				}
+				Err(_) => {
+					T::BidCurrency::unreserve(item.currency_id, &winner, price);
+					Self::deposit_event(Event::SettlementFailed(id));
+				}
+			}
+		} else {
+			Self::deposit_event(Event::AuctionClosed(id));
+		}
+
+		// the token was transferred to the pallet account when the auction was
+		// created, so it can be transferred back
+		let _ = orml_nft::Pallet::<T>::transfer(&Self::account_id(), &item.seller, item.token);
+	}
+}
@@ -0,0 +1,229 @@
+//! Mocks for the auction NFT module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{Everything, Nothing},
+};
+use orml_traits::{parameter_type_with_key, BatchAuctionHandler};
+use sp_core::H256;
+use sp_runtime::{
+	testing::{Header, TestSignature, UintAuthorityId},
+	traits::IdentityLookup,
+};
+
+use crate as auction_nft;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+pub type AccountId = u64;
+pub type Balance = u64;
+pub type BlockNumber = u64;
+pub type CurrencyId = u32;
+pub type AuctionId = u64;
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = Everything;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = i64;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+	type OnSlash = ();
+	type OnDeposit = ();
+	type OnTransfer = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = Nothing;
+	type OnNewTokenAccount = ();
+	type OnKilledTokenAccount = ();
+	type TransferFee = ();
+	type TransferFeeExemptions = Nothing;
+	type OnLockChanged = ();
+}
+
+parameter_types! {
+	pub const MaxClassMetadata: u32 = 1;
+	pub const MaxTokenMetadata: u32 = 1;
+	pub const MaxBatchSize: u32 = 3;
+	pub const MaxVoucherPrunes: u32 = 2;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct VoucherSigner;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl orml_nft::BenchmarkHelper<UintAuthorityId, AccountId, TestSignature> for VoucherSigner {
+	fn signer() -> (UintAuthorityId, AccountId) {
+		(UintAuthorityId(ALICE), ALICE)
+	}
+
+	fn sign(signer: &UintAuthorityId, message: &[u8]) -> TestSignature {
+		TestSignature(signer.0, message.to_vec())
+	}
+}
+
+impl orml_nft::Config for Runtime {
+	type Event = Event;
+	type ClassId = u64;
+	type TokenId = u64;
+	type ClassData = ();
+	type TokenData = ();
+	type MaxClassMetadata = MaxClassMetadata;
+	type MaxTokenMetadata = MaxTokenMetadata;
+	type MaxBatchSize = MaxBatchSize;
+	type Currency = Tokens;
+	type OffchainSignature = TestSignature;
+	type OffchainPublic = UintAuthorityId;
+	type MaxVoucherPrunes = MaxVoucherPrunes;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = VoucherSigner;
+	type WeightInfo = ();
+}
+
+pub struct NoBatchAuction;
+
+impl BatchAuctionHandler<AccountId, Balance, BlockNumber, AuctionId> for NoBatchAuction {
+	fn on_new_bid(
+		_now: BlockNumber,
+		_id: AuctionId,
+		_new_bid: (AccountId, Balance),
+		_outbid: Option<(AccountId, Balance)>,
+	) -> OnNewBidResult<BlockNumber> {
+		OnNewBidResult {
+			accept_bid: false,
+			auction_end_change: Change::NoChange,
+		}
+	}
+
+	fn on_auction_ended(_id: AuctionId, _winners: Vec<(AccountId, Balance)>) {}
+}
+
+parameter_types! {
+	pub const MaxLots: u32 = 1;
+	pub const GetDot: CurrencyId = DOT;
+	pub const CreationDeposit: Balance = 0;
+}
+
+impl orml_auction::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AuctionId = AuctionId;
+	type Handler = AuctionNft;
+	type BatchHandler = NoBatchAuction;
+	type MaxLots = MaxLots;
+	type Currency = orml_tokens::CurrencyAdapter<Runtime, GetDot>;
+	type CreationDeposit = CreationDeposit;
+	type OnSlash = ();
+	type MinimumIncrement = ();
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MaxRoyalty: Perbill = Perbill::from_percent(20);
+	pub const AuctionNftPalletId: PalletId = PalletId(*b"orml/auc");
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type AuctionId = AuctionId;
+	type Auction = AuctionModule;
+	type BidCurrency = Tokens;
+	type MaxRoyalty = MaxRoyalty;
+	type PalletId = AuctionNftPalletId;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
+		NonFungibleTokenModule: orml_nft::{Pallet, Storage, Event<T>, Config<T>},
+		AuctionModule: orml_auction::{Pallet, Storage, Call, Event<T>},
+		AuctionNft: auction_nft::{Pallet, Storage, Call, Event<T>},
+	}
+);
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const DAVE: AccountId = 4;
+pub const DOT: CurrencyId = 1;
+pub const CLASS_ID: <Runtime as orml_nft::Config>::ClassId = 0;
+pub const TOKEN_ID: <Runtime as orml_nft::Config>::TokenId = 0;
+
+pub struct ExtBuilder;
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		ExtBuilder
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		orml_tokens::GenesisConfig::<Runtime> {
+			balances: vec![(BOB, DOT, 1_000), (CHARLIE, DOT, 1_000)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			// DAVE owns the class, ALICE the token
+			NonFungibleTokenModule::create_class(&DAVE, vec![1], ()).unwrap();
+			NonFungibleTokenModule::mint(&ALICE, CLASS_ID, vec![1], ()).unwrap();
+		});
+		ext
+	}
+}
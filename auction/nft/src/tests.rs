@@ -0,0 +1,126 @@
+//! Unit tests for the auction NFT module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+
+#[test]
+fn set_royalty_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AuctionNft::set_royalty(Origin::signed(ALICE), CLASS_ID, Perbill::from_percent(10)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			AuctionNft::set_royalty(Origin::signed(DAVE), 100, Perbill::from_percent(10)),
+			Error::<Runtime>::ClassNotFound
+		);
+		assert_noop!(
+			AuctionNft::set_royalty(Origin::signed(DAVE), CLASS_ID, Perbill::from_percent(30)),
+			Error::<Runtime>::RoyaltyTooHigh
+		);
+
+		assert_ok!(AuctionNft::set_royalty(
+			Origin::signed(DAVE),
+			CLASS_ID,
+			Perbill::from_percent(10)
+		));
+		System::assert_last_event(Event::AuctionNft(crate::Event::RoyaltySet(
+			CLASS_ID,
+			Perbill::from_percent(10),
+		)));
+		assert_eq!(AuctionNft::royalties(CLASS_ID), Perbill::from_percent(10));
+	});
+}
+
+#[test]
+fn auction_should_settle_with_royalty() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(AuctionNft::set_royalty(
+			Origin::signed(DAVE),
+			CLASS_ID,
+			Perbill::from_percent(10)
+		));
+
+		assert_ok!(AuctionNft::create_auction(
+			Origin::signed(ALICE),
+			(CLASS_ID, TOKEN_ID),
+			DOT,
+			100,
+			1,
+			10
+		));
+		System::assert_last_event(Event::AuctionNft(crate::Event::AuctionCreated(
+			0, ALICE, CLASS_ID, TOKEN_ID,
+		)));
+		assert!(NonFungibleTokenModule::is_owner(
+			&AuctionNft::account_id(),
+			(CLASS_ID, TOKEN_ID)
+		));
+
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 0, 150));
+		assert_eq!(Tokens::reserved_balance(DOT, &BOB), 150);
+
+		// the outbid bidder is refunded
+		assert_ok!(AuctionModule::bid(Origin::signed(CHARLIE), 0, 200));
+		assert_eq!(Tokens::reserved_balance(DOT, &BOB), 0);
+		assert_eq!(Tokens::reserved_balance(DOT, &CHARLIE), 200);
+
+		// raising a bid reserves the difference
+		assert_ok!(AuctionModule::bid(Origin::signed(CHARLIE), 0, 250));
+		assert_eq!(Tokens::reserved_balance(DOT, &CHARLIE), 250);
+		assert_eq!(Tokens::free_balance(DOT, &CHARLIE), 750);
+
+		AuctionModule::on_finalize(10);
+		System::assert_last_event(Event::AuctionNft(crate::Event::AuctionSettled(0, CHARLIE, 250, 25)));
+		assert_eq!(AuctionNft::auction_items(0), None);
+		assert!(NonFungibleTokenModule::is_owner(&CHARLIE, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(Tokens::reserved_balance(DOT, &CHARLIE), 0);
+		assert_eq!(Tokens::free_balance(DOT, &CHARLIE), 750);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 225);
+		assert_eq!(Tokens::free_balance(DOT, &DAVE), 25);
+	});
+}
+
+#[test]
+fn auction_without_valid_bids_should_return_token() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AuctionNft::create_auction(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID), DOT, 100, 10, 10),
+			Error::<Runtime>::InvalidAuctionPeriod
+		);
+		assert_noop!(
+			AuctionNft::create_auction(Origin::signed(BOB), (CLASS_ID, TOKEN_ID), DOT, 100, 1, 10),
+			orml_nft::Error::<Runtime>::NoPermission
+		);
+		assert_ok!(AuctionNft::create_auction(
+			Origin::signed(ALICE),
+			(CLASS_ID, TOKEN_ID),
+			DOT,
+			100,
+			1,
+			10
+		));
+
+		// the seller, bids below the minimum price and bidders without funds are
+		// rejected
+		assert_noop!(
+			AuctionModule::bid(Origin::signed(ALICE), 0, 100),
+			orml_auction::Error::<Runtime>::BidNotAccepted
+		);
+		assert_noop!(
+			AuctionModule::bid(Origin::signed(BOB), 0, 99),
+			orml_auction::Error::<Runtime>::BidNotAccepted
+		);
+		assert_noop!(
+			AuctionModule::bid(Origin::signed(DAVE), 0, 100),
+			orml_auction::Error::<Runtime>::BidNotAccepted
+		);
+
+		AuctionModule::on_finalize(10);
+		System::assert_last_event(Event::AuctionNft(crate::Event::AuctionClosed(0)));
+		assert!(NonFungibleTokenModule::is_owner(&ALICE, (CLASS_ID, TOKEN_ID)));
+	});
+}
@@ -0,0 +1,37 @@
+//! Weights for orml_auction_nft
+//!
+//! These weights are estimates, not benchmark output. Generate them with the
+//! benchmarks in `benchmarking.rs`:
+//!
+//! target/release/acala benchmark --chain=dev --steps=50 --repeat=20
+//! --pallet=orml_auction_nft --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --heap-pages=4096 --output=./auction/nft/src/weights.rs
+//! --template=../templates/orml-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for orml_auction_nft.
+pub trait WeightInfo {
+	fn set_royalty() -> Weight;
+	fn create_auction() -> Weight;
+}
+
+/// Default weights.
+impl WeightInfo for () {
+	fn set_royalty() -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn create_auction() -> Weight {
+		(62_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+}
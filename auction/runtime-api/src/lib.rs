@@ -0,0 +1,24 @@
+//! Runtime API definition for auction module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	pub trait AuctionApi<AuctionId, AuctionInfo, Balance> where
+		AuctionId: Codec,
+		AuctionInfo: Codec,
+		Balance: Codec,
+	{
+		/// The auction `id`, including its current highest bid.
+		fn auction_info(id: AuctionId) -> Option<AuctionInfo>;
+
+		/// The minimum bid accepted for auction `id`, taking into account
+		/// the minimum increment over the current highest bid.
+		fn minimum_next_bid(id: AuctionId) -> Option<Balance>;
+	}
+}
@@ -0,0 +1,36 @@
+//! Benchmarks for the auction module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+benchmarks! {
+	// all lots have a winner, so the new bid outbids the lowest one
+	bid_batch_auction {
+		let l in 1 .. T::MaxLots::get();
+
+		let auction_id = <Pallet<T> as BatchAuction<T::AccountId, T::BlockNumber>>::new_batch_auction(
+			l,
+			false,
+			Zero::zero(),
+			None,
+		)?;
+		for i in 0..l {
+			let bidder: T::AccountId = account("bidder", i, SEED);
+			Pallet::<T>::bid_batch(RawOrigin::Signed(bidder).into(), auction_id, (i + 1).into())?;
+		}
+		let caller: T::AccountId = whitelisted_caller();
+		let value: T::Balance = (l + 1).into();
+	}: bid_batch(RawOrigin::Signed(caller.clone()), auction_id, value)
+	verify {
+		let auction = BatchAuctions::<T>::get(auction_id).ok_or("auction not found")?;
+		assert_eq!(auction.bids[0], (caller, value));
+		assert_eq!(auction.bids.len() as u32, l);
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::default().build(), crate::mock::Runtime);
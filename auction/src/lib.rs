@@ -7,20 +7,41 @@
 //!
 //! The auction logic can be customized by implement and supplying
 //! `AuctionHandler` trait.
+//!
+//! Opening an auction with the `create_auction` call, or by another module
+//! with `create_auction_with_deposit`, reserves `Config::CreationDeposit`
+//! from the creator. The deposit is refunded when the auction ends with a
+//! bid from another account, or is removed, and slashed to `Config::OnSlash`
+//! when the auction ends without bids or won by its creator, so junk auctions
+//! don't come for free.
+//!
+//! A bid must outbid the current highest bid by at least
+//! `Config::MinimumIncrement` of it, and by at least one unit. The minimum
+//! acceptable next bid is exposed by `minimum_next_bid`, also available to
+//! frontends through the `AuctionApi` runtime API.
+//!
+//! Batch auctions sell multiple identical lots in a single auction. The top
+//! bids win the lots, optionally all paying the lowest winning bid. Their
+//! logic can be customized by `BatchAuctionHandler`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 // Disable the following two lints since they originate from an external macro (namely decl_storage)
 #![allow(clippy::string_lit_as_bytes)]
 #![allow(clippy::unused_unit)]
 
-use frame_support::pallet_prelude::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, OnUnbalanced, ReservableCurrency},
+	transactional,
+};
 use frame_system::{ensure_signed, pallet_prelude::*};
-use orml_traits::{Auction, AuctionHandler, AuctionInfo, Change};
+use orml_traits::{Auction, AuctionHandler, AuctionInfo, BatchAuction, BatchAuctionHandler, BatchAuctionInfo, Change};
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, Bounded, MaybeSerializeDeserialize, Member, One, Zero},
-	DispatchError, DispatchResult,
+	traits::{AtLeast32BitUnsigned, Bounded, MaybeSerializeDeserialize, Member, One, Saturating, Zero},
+	DispatchError, DispatchResult, PerThing, Permill,
 };
 
+mod benchmarking;
 mod mock;
 mod tests;
 mod weights;
@@ -32,6 +53,11 @@ pub use weights::WeightInfo;
 pub mod module {
 	use super::*;
 
+	pub(crate) type DepositBalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub(crate) type NegativeImbalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
@@ -53,6 +79,30 @@ pub mod module {
 		/// auction result.
 		type Handler: AuctionHandler<Self::AccountId, Self::Balance, Self::BlockNumber, Self::AuctionId>;
 
+		/// The `BatchAuctionHandler` that allow custom bidding logic and
+		/// handles batch auction result.
+		type BatchHandler: BatchAuctionHandler<Self::AccountId, Self::Balance, Self::BlockNumber, Self::AuctionId>;
+
+		/// The maximum number of lots in a batch auction.
+		#[pallet::constant]
+		type MaxLots: Get<u32>;
+
+		/// The currency creation deposits are reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved from the creator of an auction.
+		#[pallet::constant]
+		type CreationDeposit: Get<DepositBalanceOf<Self>>;
+
+		/// Handler for the deposits slashed from auctions ended without bids
+		/// or won by their creator, e.g. the treasury.
+		type OnSlash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// The minimum increment of a bid over the current highest bid, in
+		/// proportion to the current highest bid.
+		#[pallet::constant]
+		type MinimumIncrement: Get<Permill>;
+
 		/// Weight information for extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -64,6 +114,10 @@ pub mod module {
 		BidNotAccepted,
 		InvalidBidPrice,
 		NoAvailableAuctionId,
+		InvalidLots,
+		/// The auction doesn't end after it starts and after the current
+		/// block.
+		InvalidAuctionPeriod,
 	}
 
 	#[pallet::event]
@@ -71,6 +125,15 @@ pub mod module {
 	pub enum Event<T: Config> {
 		/// A bid is placed. [auction_id, bidder, bidding_amount]
 		Bid(T::AuctionId, T::AccountId, T::Balance),
+		/// An auction is created with a deposit. [auction_id, creator,
+		/// deposit]
+		AuctionCreated(T::AuctionId, T::AccountId, DepositBalanceOf<T>),
+		/// The creation deposit of an auction is refunded. [auction_id,
+		/// creator, deposit]
+		DepositRefunded(T::AuctionId, T::AccountId, DepositBalanceOf<T>),
+		/// The creation deposit of an auction ended without bids, or won by
+		/// its creator, is slashed. [auction_id, creator, deposit]
+		DepositSlashed(T::AuctionId, T::AccountId, DepositBalanceOf<T>),
 	}
 
 	/// Stores on-going and future auctions. Closed auction are removed.
@@ -79,6 +142,17 @@ pub mod module {
 	pub type Auctions<T: Config> =
 		StorageMap<_, Twox64Concat, T::AuctionId, AuctionInfo<T::AccountId, T::Balance, T::BlockNumber>, OptionQuery>;
 
+	/// Stores on-going and future batch auctions. Closed auction are removed.
+	#[pallet::storage]
+	#[pallet::getter(fn batch_auctions)]
+	pub type BatchAuctions<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AuctionId,
+		BatchAuctionInfo<T::AccountId, T::Balance, T::BlockNumber>,
+		OptionQuery,
+	>;
+
 	/// Track the next auction ID.
 	#[pallet::storage]
 	#[pallet::getter(fn auctions_index)]
@@ -90,6 +164,12 @@ pub mod module {
 	pub type AuctionEndTime<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Blake2_128Concat, T::AuctionId, (), OptionQuery>;
 
+	/// The creator and creation deposit of auctions.
+	#[pallet::storage]
+	#[pallet::getter(fn auction_deposits)]
+	pub type AuctionDeposits<T: Config> =
+		StorageMap<_, Twox64Concat, T::AuctionId, (T::AccountId, DepositBalanceOf<T>), OptionQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -102,7 +182,20 @@ pub mod module {
 		fn on_finalize(now: T::BlockNumber) {
 			for (auction_id, _) in AuctionEndTime::<T>::drain_prefix(&now) {
 				if let Some(auction) = Auctions::<T>::take(&auction_id) {
+					Self::settle_deposit(
+						auction_id,
+						|creator| matches!(&auction.bid, Some((bidder, _)) if bidder != creator),
+					);
 					T::Handler::on_auction_ended(auction_id, auction.bid);
+				} else if let Some(auction) = BatchAuctions::<T>::take(&auction_id) {
+					let winners = match auction.bids.last() {
+						Some((_, clearing_price)) if auction.uniform_price => {
+							let clearing_price = *clearing_price;
+							auction.bids.into_iter().map(|(who, _)| (who, clearing_price)).collect()
+						}
+						_ => auction.bids,
+					};
+					T::BatchHandler::on_auction_ended(auction_id, winners);
 				}
 			}
 		}
@@ -110,6 +203,21 @@ pub mod module {
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
+		/// Create an auction from `start` to `end`, reserving
+		/// `CreationDeposit` from the caller.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::create_auction())]
+		pub fn create_auction(origin: OriginFor<T>, start: T::BlockNumber, end: T::BlockNumber) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(end > start && end > now, Error::<T>::InvalidAuctionPeriod);
+
+			Self::create_auction_with_deposit(&who, start, Some(end))?;
+			Ok(())
+		}
+
 		/// Bid an auction.
 		///
 		/// The dispatch origin for this call must be `Signed` by the
@@ -126,11 +234,10 @@ pub mod module {
 				// make sure auction is started
 				ensure!(block_number >= auction.start, Error::<T>::AuctionNotStarted);
 
-				if let Some(ref current_bid) = auction.bid {
-					ensure!(value > current_bid.1, Error::<T>::InvalidBidPrice);
-				} else {
-					ensure!(!value.is_zero(), Error::<T>::InvalidBidPrice);
-				}
+				ensure!(
+					value >= Self::next_bid_threshold(auction.bid.as_ref().map(|(_, price)| *price)),
+					Error::<T>::InvalidBidPrice
+				);
 				let bid_result = T::Handler::on_new_bid(block_number, id, (from.clone(), value), auction.bid.clone());
 
 				ensure!(bid_result.accept_bid, Error::<T>::BidNotAccepted);
@@ -154,6 +261,128 @@ pub mod module {
 			Self::deposit_event(Event::Bid(id, from, value));
 			Ok(())
 		}
+
+		/// Bid a lot of a batch auction.
+		///
+		/// If all lots have a winner, the bid must be higher than the lowest
+		/// winning bid, which is outbid.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::bid_batch_auction(T::MaxLots::get()))]
+		pub fn bid_batch(
+			origin: OriginFor<T>,
+			id: T::AuctionId,
+			#[pallet::compact] value: T::Balance,
+		) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+
+			BatchAuctions::<T>::try_mutate_exists(id, |auction| -> DispatchResult {
+				let mut auction = auction.as_mut().ok_or(Error::<T>::AuctionNotExist)?;
+
+				let block_number = <frame_system::Pallet<T>>::block_number();
+
+				// make sure auction is started
+				ensure!(block_number >= auction.start, Error::<T>::AuctionNotStarted);
+				ensure!(!value.is_zero(), Error::<T>::InvalidBidPrice);
+
+				let outbid = if auction.bids.len() as u32 >= auction.lots {
+					let lowest_bid = auction.bids.pop().ok_or(Error::<T>::InvalidLots)?;
+					ensure!(value > lowest_bid.1, Error::<T>::InvalidBidPrice);
+					Some(lowest_bid)
+				} else {
+					None
+				};
+				let bid_result = T::BatchHandler::on_new_bid(block_number, id, (from.clone(), value), outbid);
+
+				ensure!(bid_result.accept_bid, Error::<T>::BidNotAccepted);
+				match bid_result.auction_end_change {
+					Change::NewValue(new_end) => {
+						if let Some(old_end_block) = auction.end {
+							AuctionEndTime::<T>::remove(&old_end_block, id);
+						}
+						if let Some(new_end_block) = new_end {
+							AuctionEndTime::<T>::insert(&new_end_block, id, ());
+						}
+						auction.end = new_end;
+					}
+					Change::NoChange => {}
+				}
+				// keep bids sorted in descending order, earlier bids first for equal prices
+				let index = auction
+					.bids
+					.iter()
+					.position(|(_, price)| *price < value)
+					.unwrap_or_else(|| auction.bids.len());
+				auction.bids.insert(index, (from.clone(), value));
+
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::Bid(id, from, value));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The minimum bid accepted for auction `id`, `None` if the auction
+	/// doesn't exist.
+	pub fn minimum_next_bid(id: T::AuctionId) -> Option<T::Balance> {
+		Auctions::<T>::get(id).map(|auction| Self::next_bid_threshold(auction.bid.map(|(_, price)| price)))
+	}
+
+	/// The minimum bid outbidding `current_price`, by at least
+	/// `MinimumIncrement` of it and at least one unit.
+	fn next_bid_threshold(current_price: Option<T::Balance>) -> T::Balance {
+		match current_price {
+			Some(price) => {
+				let increment = T::MinimumIncrement::get().mul_ceil(price).max(One::one());
+				price.saturating_add(increment)
+			}
+			None => One::one(),
+		}
+	}
+
+	/// Create an auction on behalf of `who`, reserving `CreationDeposit`
+	/// from it.
+	#[transactional]
+	pub fn create_auction_with_deposit(
+		who: &T::AccountId,
+		start: T::BlockNumber,
+		end: Option<T::BlockNumber>,
+	) -> sp_std::result::Result<T::AuctionId, DispatchError> {
+		let auction_id = <Self as Auction<T::AccountId, T::BlockNumber>>::new_auction(start, end)?;
+		let deposit = T::CreationDeposit::get();
+		T::Currency::reserve(who, deposit)?;
+		AuctionDeposits::<T>::insert(auction_id, (who.clone(), deposit));
+
+		Self::deposit_event(Event::AuctionCreated(auction_id, who.clone(), deposit));
+		Ok(auction_id)
+	}
+
+	/// Refund the creation deposit of an auction if `refund` returns true
+	/// for its creator, or slash it otherwise.
+	fn settle_deposit(id: T::AuctionId, refund: impl FnOnce(&T::AccountId) -> bool) {
+		if let Some((who, deposit)) = AuctionDeposits::<T>::take(id) {
+			if refund(&who) {
+				T::Currency::unreserve(&who, deposit);
+				Self::deposit_event(Event::DepositRefunded(id, who, deposit));
+			} else {
+				let (imbalance, _) = T::Currency::slash_reserved(&who, deposit);
+				T::OnSlash::on_unbalanced(imbalance);
+				Self::deposit_event(Event::DepositSlashed(id, who, deposit));
+			}
+		}
+	}
+
+	fn next_auction_id() -> sp_std::result::Result<T::AuctionId, DispatchError> {
+		<AuctionsIndex<T>>::try_mutate(|n| -> sp_std::result::Result<T::AuctionId, DispatchError> {
+			let id = *n;
+			ensure!(id != T::AuctionId::max_value(), Error::<T>::NoAvailableAuctionId);
+			*n += One::one();
+			Ok(id)
+		})
 	}
 }
 
@@ -185,13 +414,7 @@ impl<T: Config> Auction<T::AccountId, T::BlockNumber> for Pallet<T> {
 		end: Option<T::BlockNumber>,
 	) -> sp_std::result::Result<Self::AuctionId, DispatchError> {
 		let auction = AuctionInfo { bid: None, start, end };
-		let auction_id =
-			<AuctionsIndex<T>>::try_mutate(|n| -> sp_std::result::Result<Self::AuctionId, DispatchError> {
-				let id = *n;
-				ensure!(id != Self::AuctionId::max_value(), Error::<T>::NoAvailableAuctionId);
-				*n += One::one();
-				Ok(id)
-			})?;
+		let auction_id = Self::next_auction_id()?;
 		Auctions::<T>::insert(auction_id, auction);
 		if let Some(end_block) = end {
 			AuctionEndTime::<T>::insert(&end_block, auction_id, ());
@@ -205,6 +428,49 @@ impl<T: Config> Auction<T::AccountId, T::BlockNumber> for Pallet<T> {
 			if let Some(end_block) = auction.end {
 				AuctionEndTime::<T>::remove(end_block, id);
 			}
+			Self::settle_deposit(id, |_| true);
+		}
+	}
+}
+
+impl<T: Config> BatchAuction<T::AccountId, T::BlockNumber> for Pallet<T> {
+	type AuctionId = T::AuctionId;
+	type Balance = T::Balance;
+
+	fn batch_auction_info(
+		id: Self::AuctionId,
+	) -> Option<BatchAuctionInfo<T::AccountId, Self::Balance, T::BlockNumber>> {
+		Self::batch_auctions(id)
+	}
+
+	fn new_batch_auction(
+		lots: u32,
+		uniform_price: bool,
+		start: T::BlockNumber,
+		end: Option<T::BlockNumber>,
+	) -> sp_std::result::Result<Self::AuctionId, DispatchError> {
+		ensure!(lots > 0 && lots <= T::MaxLots::get(), Error::<T>::InvalidLots);
+		let auction = BatchAuctionInfo {
+			lots,
+			bids: Default::default(),
+			uniform_price,
+			start,
+			end,
+		};
+		let auction_id = Self::next_auction_id()?;
+		BatchAuctions::<T>::insert(auction_id, auction);
+		if let Some(end_block) = end {
+			AuctionEndTime::<T>::insert(&end_block, auction_id, ());
+		}
+
+		Ok(auction_id)
+	}
+
+	fn remove_batch_auction(id: Self::AuctionId) {
+		if let Some(auction) = BatchAuctions::<T>::take(&id) {
+			if let Some(end_block) = auction.end {
+				AuctionEndTime::<T>::remove(end_block, id);
+			}
 		}
 	}
 }
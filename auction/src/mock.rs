@@ -6,7 +6,8 @@ use super::*;
 use frame_support::{construct_runtime, parameter_types, traits::Everything};
 use orml_traits::OnNewBidResult;
 use sp_core::H256;
-use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_runtime::{testing::Header, traits::IdentityLookup, Permill};
+use sp_std::cell::RefCell;
 
 use crate as auction;
 
@@ -35,7 +36,7 @@ impl frame_system::Config for Runtime {
 	type BlockLength = ();
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<Balance>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type DbWeight = ();
@@ -45,6 +46,22 @@ impl frame_system::Config for Runtime {
 	type OnSetCode = ();
 }
 
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Pallet<Runtime>;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
 pub struct Handler;
 
 impl AuctionHandler<AccountId, Balance, BlockNumber, AuctionId> for Handler {
@@ -70,11 +87,57 @@ impl AuctionHandler<AccountId, Balance, BlockNumber, AuctionId> for Handler {
 	fn on_auction_ended(_id: AuctionId, _winner: Option<(AccountId, Balance)>) {}
 }
 
+thread_local! {
+	pub static OUTBIDS: RefCell<Vec<(AccountId, Balance)>> = RefCell::new(vec![]);
+	pub static BATCH_WINNERS: RefCell<Vec<(AccountId, Balance)>> = RefCell::new(vec![]);
+}
+
+pub struct BatchHandler;
+
+impl BatchAuctionHandler<AccountId, Balance, BlockNumber, AuctionId> for BatchHandler {
+	fn on_new_bid(
+		_now: BlockNumber,
+		_id: AuctionId,
+		new_bid: (AccountId, Balance),
+		outbid: Option<(AccountId, Balance)>,
+	) -> OnNewBidResult<BlockNumber> {
+		if new_bid.0 == CHARLIE {
+			return OnNewBidResult {
+				accept_bid: false,
+				auction_end_change: Change::NoChange,
+			};
+		}
+		if let Some(outbid) = outbid {
+			OUTBIDS.with(|v| v.borrow_mut().push(outbid));
+		}
+		OnNewBidResult {
+			accept_bid: true,
+			auction_end_change: Change::NoChange,
+		}
+	}
+
+	fn on_auction_ended(_id: AuctionId, winners: Vec<(AccountId, Balance)>) {
+		BATCH_WINNERS.with(|v| *v.borrow_mut() = winners);
+	}
+}
+
+parameter_types! {
+	pub const MaxLots: u32 = 3;
+	pub const CreationDeposit: Balance = 10;
+	pub const MinimumIncrement: Permill = Permill::from_percent(10);
+}
+
 impl Config for Runtime {
 	type Event = Event;
 	type Balance = Balance;
 	type AuctionId = AuctionId;
 	type Handler = Handler;
+	type BatchHandler = BatchHandler;
+	type MaxLots = MaxLots;
+	type Currency = PalletBalances;
+	type CreationDeposit = CreationDeposit;
+	type OnSlash = ();
+	type MinimumIncrement = MinimumIncrement;
 	type WeightInfo = ();
 }
 
@@ -88,12 +151,15 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		PalletBalances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		AuctionModule: auction::{Pallet, Storage, Call, Event<T>},
 	}
 );
 
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const DAVE: AccountId = 4;
 pub const BID_EXTEND_BLOCK: BlockNumber = 10;
 
 pub struct ExtBuilder;
@@ -106,10 +172,16 @@ impl Default for ExtBuilder {
 
 impl ExtBuilder {
 	pub fn build(self) -> sp_io::TestExternalities {
-		let t = frame_system::GenesisConfig::default()
+		let mut t = frame_system::GenesisConfig::default()
 			.build_storage::<Runtime>()
 			.unwrap();
 
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(ALICE, 100), (BOB, 100)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		t.into()
 	}
 }
@@ -100,6 +100,25 @@ fn bid_should_fail() {
 	});
 }
 
+#[test]
+fn bid_should_respect_minimum_increment() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(AuctionModule::new_auction(0, Some(100)), 0);
+		assert_eq!(AuctionModule::minimum_next_bid(0), Some(1));
+		assert_eq!(AuctionModule::minimum_next_bid(1), None);
+
+		assert_ok!(AuctionModule::bid(Origin::signed(ALICE), 0, 5));
+		assert_eq!(AuctionModule::minimum_next_bid(0), Some(6));
+		assert_ok!(AuctionModule::bid(Origin::signed(ALICE), 0, 101));
+		assert_eq!(AuctionModule::minimum_next_bid(0), Some(112));
+		assert_noop!(
+			AuctionModule::bid(Origin::signed(ALICE), 0, 111),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+		assert_ok!(AuctionModule::bid(Origin::signed(ALICE), 0, 112));
+	});
+}
+
 #[test]
 fn remove_auction_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -153,3 +172,153 @@ fn cannot_add_new_auction_when_no_available_id() {
 		);
 	});
 }
+
+#[test]
+fn new_batch_auction_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(AuctionModule::new_auction(10, Some(100)), 0);
+		assert_ok!(AuctionModule::new_batch_auction(3, false, 10, Some(100)), 1);
+		assert_eq!(
+			AuctionModule::batch_auction_info(1),
+			Some(BatchAuctionInfo {
+				lots: 3,
+				bids: vec![],
+				uniform_price: false,
+				start: 10,
+				end: Some(100)
+			})
+		);
+		assert_eq!(AuctionModule::auction_end_time(100, 1), Some(()));
+		assert_noop!(
+			AuctionModule::new_batch_auction(0, false, 10, Some(100)),
+			Error::<Runtime>::InvalidLots
+		);
+		assert_noop!(
+			AuctionModule::new_batch_auction(4, false, 10, Some(100)),
+			Error::<Runtime>::InvalidLots
+		);
+
+		AuctionModule::remove_batch_auction(1);
+		assert_eq!(AuctionModule::batch_auctions(1), None);
+		assert_eq!(AuctionModule::auction_end_time(100, 1), None);
+	});
+}
+
+#[test]
+fn bid_batch_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionModule::new_batch_auction(2, false, 0, Some(5)), 0);
+		assert_ok!(AuctionModule::new_auction(0, Some(5)), 1);
+		assert_noop!(
+			AuctionModule::bid_batch(Origin::signed(ALICE), 1, 10),
+			Error::<Runtime>::AuctionNotExist
+		);
+		assert_noop!(
+			AuctionModule::bid_batch(Origin::signed(ALICE), 0, 0),
+			Error::<Runtime>::InvalidBidPrice
+		);
+		assert_noop!(
+			AuctionModule::bid_batch(Origin::signed(CHARLIE), 0, 10),
+			Error::<Runtime>::BidNotAccepted
+		);
+
+		assert_ok!(AuctionModule::bid_batch(Origin::signed(ALICE), 0, 10));
+		System::assert_last_event(Event::AuctionModule(crate::Event::Bid(0, ALICE, 10)));
+		assert_ok!(AuctionModule::bid_batch(Origin::signed(BOB), 0, 20));
+		assert_eq!(
+			AuctionModule::batch_auctions(0).map(|x| x.bids),
+			Some(vec![(BOB, 20), (ALICE, 10)])
+		);
+
+		// all lots have a winner, must outbid the lowest bid
+		assert_noop!(
+			AuctionModule::bid_batch(Origin::signed(DAVE), 0, 10),
+			Error::<Runtime>::InvalidBidPrice
+		);
+		assert_ok!(AuctionModule::bid_batch(Origin::signed(DAVE), 0, 15));
+		assert_eq!(OUTBIDS.with(|v| v.borrow().clone()), vec![(ALICE, 10)]);
+		assert_eq!(
+			AuctionModule::batch_auctions(0).map(|x| x.bids),
+			Some(vec![(BOB, 20), (DAVE, 15)])
+		);
+
+		AuctionModule::on_finalize(5);
+		assert_eq!(AuctionModule::batch_auctions(0), None);
+		assert_eq!(BATCH_WINNERS.with(|v| v.borrow().clone()), vec![(BOB, 20), (DAVE, 15)]);
+	});
+}
+
+#[test]
+fn batch_auction_with_uniform_price_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionModule::new_batch_auction(3, true, 0, Some(5)), 0);
+		assert_ok!(AuctionModule::bid_batch(Origin::signed(ALICE), 0, 10));
+		assert_ok!(AuctionModule::bid_batch(Origin::signed(BOB), 0, 30));
+		assert_ok!(AuctionModule::bid_batch(Origin::signed(ALICE), 0, 20));
+
+		AuctionModule::on_finalize(5);
+		assert_eq!(
+			BATCH_WINNERS.with(|v| v.borrow().clone()),
+			vec![(BOB, 10), (ALICE, 10), (ALICE, 10)]
+		);
+	});
+}
+
+#[test]
+fn create_auction_should_reserve_deposit() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			AuctionModule::create_auction(Origin::signed(ALICE), 5, 5),
+			Error::<Runtime>::InvalidAuctionPeriod
+		);
+		assert_noop!(
+			AuctionModule::create_auction(Origin::signed(CHARLIE), 0, 10),
+			pallet_balances::Error::<Runtime>::InsufficientBalance
+		);
+
+		assert_ok!(AuctionModule::create_auction(Origin::signed(ALICE), 0, 10));
+		System::assert_last_event(Event::AuctionModule(crate::Event::AuctionCreated(0, ALICE, 10)));
+		assert_ok!(AuctionModule::create_auction(Origin::signed(BOB), 0, 10));
+		assert_ok!(AuctionModule::create_auction(Origin::signed(BOB), 0, 10));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 10);
+		assert_eq!(PalletBalances::reserved_balance(BOB), 20);
+		assert_eq!(AuctionModule::auction_deposits(1), Some((BOB, 10)));
+
+		// the bids extend the auctions to block 11
+		assert_ok!(AuctionModule::bid(Origin::signed(ALICE), 0, 20));
+		assert_ok!(AuctionModule::bid(Origin::signed(ALICE), 1, 20));
+
+		// ended without bids
+		AuctionModule::on_finalize(10);
+		System::assert_last_event(Event::AuctionModule(crate::Event::DepositSlashed(2, BOB, 10)));
+		assert_eq!(PalletBalances::reserved_balance(BOB), 10);
+		assert_eq!(PalletBalances::free_balance(BOB), 80);
+		assert_eq!(PalletBalances::total_issuance(), 190);
+		assert_eq!(AuctionModule::auction_deposits(2), None);
+
+		// won by its creator, and won by another account
+		AuctionModule::on_finalize(11);
+		System::assert_has_event(Event::AuctionModule(crate::Event::DepositSlashed(0, ALICE, 10)));
+		System::assert_has_event(Event::AuctionModule(crate::Event::DepositRefunded(1, BOB, 10)));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 0);
+		assert_eq!(PalletBalances::free_balance(ALICE), 90);
+		assert_eq!(PalletBalances::reserved_balance(BOB), 0);
+		assert_eq!(PalletBalances::free_balance(BOB), 90);
+		assert_eq!(PalletBalances::total_issuance(), 180);
+	});
+}
+
+#[test]
+fn create_auction_should_not_reserve_deposit_without_auction() {
+	ExtBuilder::default().build().execute_with(|| {
+		<AuctionsIndex<Runtime>>::put(AuctionId::max_value());
+		assert_noop!(
+			AuctionModule::create_auction(Origin::signed(ALICE), 0, 10),
+			Error::<Runtime>::NoAvailableAuctionId
+		);
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 0);
+	});
+}
@@ -30,17 +30,32 @@ use sp_std::marker::PhantomData;
 
 /// Weight functions needed for orml_auction.
 pub trait WeightInfo {
+	fn create_auction() -> Weight;
 	fn bid_collateral_auction() -> Weight;
+	fn bid_batch_auction(l: u32, ) -> Weight;
 	fn on_finalize(c: u32, ) -> Weight;
 }
 
 /// Default weights.
 impl WeightInfo for () {
+	fn create_auction() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
 	fn bid_collateral_auction() -> Weight {
 		(108_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
 	}
+	// Estimated, not generated: regenerate with the `bid_batch_auction`
+	// benchmark in `benchmarking.rs`.
+	fn bid_batch_auction(l: u32, ) -> Weight {
+		(115_000_000 as Weight)
+			.saturating_add((300_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
 	fn on_finalize(c: u32, ) -> Weight {
 		(9_779_000 as Weight)
 			// Standard Error: 13_000
@@ -0,0 +1,26 @@
+//! Runtime API definition for authority module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_std::prelude::Vec;
+
+pub use orml_authority::PendingDispatch;
+
+sp_api::decl_runtime_apis! {
+	pub trait AuthorityApi<PalletsOrigin, BlockNumber, Hash, Call> where
+		PalletsOrigin: Codec,
+		BlockNumber: Codec,
+		Hash: Codec,
+		Call: Codec,
+	{
+		/// The scheduled tasks pending dispatch, with the calls to be
+		/// dispatched, or their hashes if they are too long to be kept, and
+		/// when.
+		fn pending_dispatches() -> Vec<PendingDispatch<PalletsOrigin, BlockNumber, Hash, Call>>;
+	}
+}
@@ -11,6 +11,29 @@
 //! Two functionalities are provided by this module:
 //! - schedule a dispatchable
 //! - dispatch method with on behalf of other origins
+//!
+//! A scheduled dispatchable can always be cancelled or rescheduled by the
+//! origin that scheduled it, other origins are checked by `AuthorityConfig`.
+//!
+//! Scheduling a dispatchable with delayed origin by a signed origin reserves
+//! `DelayedCallDeposit`, which is returned once the dispatchable is dispatched
+//! or cancelled by its caller, and slashed if it is cancelled by other
+//! origins. Dispatchables not dispatched `ScheduledCallExpiry` blocks after the
+//! block they are scheduled at, as fast tracked, delayed or rescheduled, are
+//! cancelled.
+//!
+//! The origin an `AsOriginId` dispatches as can be rotated: the origin
+//! currently bound to it proposes a new origin, which takes over once it
+//! accepts the transfer, or root force sets a new origin. The pending tasks
+//! scheduled by the previous origin are checked against the new origin, and
+//! are taken over by it if it is allowed to schedule them, or cancelled
+//! otherwise. The transfer takes a witness of the number of these tasks, and
+//! fails if it is too low.
+//!
+//! The call hashes of the scheduled tasks are kept until the tasks are
+//! settled, along with the encoded calls no longer than
+//! `MaxScheduledCallLen`, so `pending_dispatches` previews what will be
+//! dispatched and when.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 // Disable the following three lints since they originate from an external macro
@@ -24,17 +47,18 @@ use frame_support::{
 	pallet_prelude::*,
 	traits::{
 		schedule::{DispatchTime, Named as ScheduleNamed, Priority},
-		EnsureOrigin, Get, IsType, OriginTrait,
+		Currency, EnsureOrigin, Get, IsType, OriginTrait, ReservableCurrency,
 	},
+	transactional,
 	weights::{DispatchClass, GetDispatchInfo, Pays},
 };
 use frame_system::{pallet_prelude::*, EnsureOneOf, EnsureRoot, EnsureSigned};
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{CheckedSub, Dispatchable, Hash, Saturating},
+	traits::{CheckedSub, Dispatchable, Hash, One, Saturating, Zero},
 	ArithmeticError, DispatchError, DispatchResult, Either, RuntimeDebug,
 };
-use sp_std::prelude::*;
+use sp_std::{convert::TryFrom, prelude::*};
 
 mod mock;
 mod tests;
@@ -117,6 +141,42 @@ pub trait AsOriginId<Origin, PalletsOrigin> {
 /// The schedule task index type.
 pub type ScheduleTaskIndex = u32;
 
+/// A scheduled task.
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct ScheduledTask<PalletsOrigin, AccountId, Balance, BlockNumber> {
+	/// The origin the task is dispatched with, which is the `initial_origin`
+	/// identifying the task along with its index.
+	pub origin: PalletsOrigin,
+	/// The origin that scheduled the task.
+	pub caller: PalletsOrigin,
+	/// The deposit reserved from the account that scheduled the task.
+	pub deposit: Option<(AccountId, Balance)>,
+	/// The block number after which the task is cancelled if not dispatched.
+	pub expire_at: BlockNumber,
+	/// The priority the task is scheduled with.
+	pub priority: Priority,
+}
+
+/// A scheduled task pending dispatch, with the call to be dispatched.
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct PendingDispatch<PalletsOrigin, BlockNumber, Hash, Call> {
+	/// The index of the task.
+	pub task_id: ScheduleTaskIndex,
+	/// The origin the task is dispatched with.
+	pub origin: PalletsOrigin,
+	/// The block number the task is dispatched at.
+	pub when: BlockNumber,
+	/// The block number after which the task is cancelled if not dispatched.
+	pub expire_at: BlockNumber,
+	/// The priority the task is scheduled with.
+	pub priority: Priority,
+	/// The hash of the call to be dispatched.
+	pub call_hash: Hash,
+	/// The call to be dispatched, `None` if its encoding is longer than
+	/// `MaxScheduledCallLen`.
+	pub call: Option<Call>,
+}
+
 pub use module::*;
 
 #[frame_support::pallet]
@@ -127,6 +187,20 @@ pub mod module {
 	#[pallet::origin]
 	pub type Origin<T> = DelayedOrigin<<T as frame_system::Config>::BlockNumber, <T as Config>::PalletsOrigin>;
 	pub(crate) type CallOf<T> = <T as Config>::Call;
+	pub(crate) type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub(crate) type ScheduledTaskOf<T> = ScheduledTask<
+		<T as Config>::PalletsOrigin,
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+	pub(crate) type PendingDispatchOf<T> = PendingDispatch<
+		<T as Config>::PalletsOrigin,
+		<T as frame_system::Config>::BlockNumber,
+		<T as frame_system::Config>::Hash,
+		CallOf<T>,
+	>;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -159,6 +233,24 @@ pub mod module {
 			Self::BlockNumber,
 		>;
 
+		/// The currency to reserve deposits of delayed calls.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved from a signed origin scheduling a call with
+		/// delayed origin.
+		#[pallet::constant]
+		type DelayedCallDeposit: Get<BalanceOf<Self>>;
+
+		/// The number of blocks after the scheduled block a call is cancelled
+		/// if not dispatched.
+		#[pallet::constant]
+		type ScheduledCallExpiry: Get<Self::BlockNumber>;
+
+		/// The maximum length of the encoded call kept for a scheduled task,
+		/// only the hash of a longer call is kept.
+		#[pallet::constant]
+		type MaxScheduledCallLen: Get<u32>;
+
 		/// Weight information for extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -179,6 +271,14 @@ pub mod module {
 		TriggerCallNotPermitted,
 		/// Call weight bound is wrong.
 		WrongCallWeightBound,
+		/// Failed to reschedule a task.
+		FailedToReschedule,
+		/// The scheduled task does not exist.
+		TaskNotFound,
+		/// No origin transfer is proposed for the origin id.
+		NoPendingOriginTransfer,
+		/// The previous origin has more pending tasks than the witness count.
+		InvalidTaskWitness,
 	}
 
 	#[pallet::event]
@@ -186,6 +286,8 @@ pub mod module {
 	pub enum Event<T: Config> {
 		/// A call is dispatched. [result]
 		Dispatched(DispatchResult),
+		/// A batch of calls is dispatched. [count]
+		BatchDispatched(u32),
 		/// A call is scheduled. [origin, index]
 		Scheduled(T::PalletsOrigin, ScheduleTaskIndex),
 		/// A scheduled call is fast tracked. [origin, index, when]
@@ -194,27 +296,126 @@ pub mod module {
 		Delayed(T::PalletsOrigin, ScheduleTaskIndex, T::BlockNumber),
 		/// A scheduled call is cancelled. [origin, index]
 		Cancelled(T::PalletsOrigin, ScheduleTaskIndex),
+		/// A scheduled call is rescheduled by its caller. [origin, index, when]
+		Rescheduled(T::PalletsOrigin, ScheduleTaskIndex, T::BlockNumber),
+		/// A scheduled call expired without being dispatched. [origin, index]
+		Expired(T::PalletsOrigin, ScheduleTaskIndex),
 		/// A call is authorized. \[hash, caller\]
 		AuthorizedCall(T::Hash, Option<T::AccountId>),
 		/// An authorized call was removed. \[hash\]
 		RemovedAuthorizedCall(T::Hash),
 		/// An authorized call was triggered. \[hash, caller\]
 		TriggeredCallBy(T::Hash, T::AccountId),
+		/// An origin transfer is proposed. [as_origin, new_origin]
+		OriginTransferProposed(T::AsOriginId, T::PalletsOrigin),
+		/// The origin bound to an origin id is transferred. [as_origin,
+		/// old_origin, new_origin]
+		OriginTransferred(T::AsOriginId, T::PalletsOrigin, T::PalletsOrigin),
 	}
 
 	#[pallet::storage]
 	#[pallet::getter(fn next_task_index)]
 	pub type NextTaskIndex<T: Config> = StorageValue<_, ScheduleTaskIndex, ValueQuery>;
 
+	/// The scheduled tasks.
+	///
+	/// map ScheduleTaskIndex => Option<ScheduledTask>
+	#[pallet::storage]
+	#[pallet::getter(fn scheduled_tasks)]
+	pub type ScheduledTasks<T: Config> =
+		StorageMap<_, Twox64Concat, ScheduleTaskIndex, ScheduledTaskOf<T>, OptionQuery>;
+
+	/// The call hashes of the scheduled tasks, with the encoded calls if
+	/// they are no longer than `MaxScheduledCallLen`.
+	///
+	/// map ScheduleTaskIndex => Option<(Hash, Option<EncodedCall>)>
+	#[pallet::storage]
+	#[pallet::getter(fn scheduled_calls)]
+	pub type ScheduledCalls<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		ScheduleTaskIndex,
+		(T::Hash, Option<BoundedVec<u8, T::MaxScheduledCallLen>>),
+		OptionQuery,
+	>;
+
+	/// The pending tasks scheduled by each origin.
+	///
+	/// double_map PalletsOrigin, ScheduleTaskIndex => ()
+	#[pallet::storage]
+	pub type TasksByCaller<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::PalletsOrigin, Twox64Concat, ScheduleTaskIndex, (), OptionQuery>;
+
+	/// The scheduled tasks to be checked at a block, whether they have been
+	/// dispatched or have expired.
+	///
+	/// double_map BlockNumber, ScheduleTaskIndex => ()
+	#[pallet::storage]
+	pub type TaskChecks<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, ScheduleTaskIndex, (), OptionQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn saved_calls)]
 	pub type SavedCalls<T: Config> = StorageMap<_, Identity, T::Hash, (CallOf<T>, Option<T::AccountId>), OptionQuery>;
 
+	/// The origins bound to origin ids, overriding `AsOriginId::into_origin`.
+	///
+	/// map AsOriginId => Option<PalletsOrigin>
+	#[pallet::storage]
+	#[pallet::getter(fn bound_origins)]
+	pub type BoundOrigins<T: Config> = StorageMap<_, Twox64Concat, T::AsOriginId, T::PalletsOrigin, OptionQuery>;
+
+	/// The proposed origin transfers, waiting for the new origin to accept.
+	///
+	/// map AsOriginId => Option<PalletsOrigin>
+	#[pallet::storage]
+	#[pallet::getter(fn pending_origin_transfers)]
+	pub type PendingOriginTransfers<T: Config> =
+		StorageMap<_, Twox64Concat, T::AsOriginId, T::PalletsOrigin, OptionQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Settle the deposits of dispatched tasks and cancel expired tasks.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut reads: Weight = 0;
+			let mut writes: Weight = 0;
+
+			for (task_id, _) in TaskChecks::<T>::drain_prefix(now) {
+				reads += 2;
+				writes += 1;
+				let task = match Self::scheduled_tasks(task_id) {
+					Some(task) => task,
+					None => continue,
+				};
+
+				let key = (&task.origin, task_id).encode();
+				match T::Scheduler::next_dispatch_time(key.clone()) {
+					Ok(_) if now > task.expire_at => {
+						let _ = T::Scheduler::cancel_named(key);
+						Self::remove_task(task_id, &task, false);
+						writes += 4;
+						Self::deposit_event(Event::Expired(task.origin, task_id));
+					}
+					Ok(when) => {
+						// still scheduled, check again after dispatched or expired
+						let check_at = when.max(now).min(task.expire_at).saturating_add(One::one());
+						TaskChecks::<T>::insert(check_at, task_id, ());
+						writes += 1;
+					}
+					Err(_) => {
+						// dispatched
+						Self::remove_task(task_id, &task, false);
+						writes += 3;
+					}
+				}
+			}
+
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -226,15 +427,55 @@ pub mod module {
 		pub fn dispatch_as(origin: OriginFor<T>, as_origin: T::AsOriginId, call: Box<CallOf<T>>) -> DispatchResult {
 			as_origin.check_dispatch_from(origin)?;
 
-			let e = call.dispatch(as_origin.into_origin().into());
+			let e = call.dispatch(Self::origin_of(as_origin).into());
 
 			Self::deposit_event(Event::Dispatched(e.map(|_| ()).map_err(|e| e.error)));
 			Ok(())
 		}
 
+		/// Dispatch a batch of dispatchables, each on behalf of its own
+		/// origin.
+		///
+		/// All calls are dispatched or none: if any permission check or
+		/// dispatch fails, the whole batch is reverted.
+		#[pallet::weight({
+			let infos = calls.iter().map(|(_, call)| call.get_dispatch_info()).collect::<Vec<_>>();
+			let weight = infos.iter().fold(0, |total: Weight, info| {
+				total.saturating_add(T::WeightInfo::dispatch_as()).saturating_add(info.weight)
+			});
+			let class = if infos.iter().all(|info| info.class == DispatchClass::Operational) && !infos.is_empty() {
+				DispatchClass::Operational
+			} else {
+				DispatchClass::Normal
+			};
+			(weight, class)
+		})]
+		#[transactional]
+		pub fn batch_dispatch_as(origin: OriginFor<T>, calls: Vec<(T::AsOriginId, CallOf<T>)>) -> DispatchResult {
+			for (as_origin, _) in calls.iter() {
+				as_origin.check_dispatch_from(origin.clone())?;
+			}
+
+			let count = calls.len() as u32;
+			for (as_origin, call) in calls {
+				call.dispatch(Self::origin_of(as_origin).into()).map_err(|e| e.error)?;
+			}
+
+			Self::deposit_event(Event::BatchDispatched(count));
+			Ok(())
+		}
+
 		/// Schedule a dispatchable to be dispatched at later block.
 		/// This is the only way to dispatch a call with `DelayedOrigin`.
-		#[pallet::weight(T::WeightInfo::schedule_dispatch_without_delay())]
+		// the writes of `ScheduledCalls`, `TaskChecks`, `TasksByCaller` and
+		// `ScheduledTasks`, and reserving the deposit are not included in the
+		// benchmarked weights
+		#[pallet::weight(if *with_delayed_origin {
+			T::WeightInfo::schedule_dispatch_with_delay().saturating_add(T::DbWeight::get().reads_writes(1, 5))
+		} else {
+			T::WeightInfo::schedule_dispatch_without_delay().saturating_add(T::DbWeight::get().writes(4))
+		})]
+		#[transactional]
 		pub fn schedule_dispatch(
 			origin: OriginFor<T>,
 			when: DispatchTime<T::BlockNumber>,
@@ -243,6 +484,15 @@ pub mod module {
 			call: Box<CallOf<T>>,
 		) -> DispatchResult {
 			T::AuthorityConfig::check_schedule_dispatch(origin.clone(), priority)?;
+			let caller = <T as Config>::Origin::from(origin.clone()).caller().clone();
+			let deposit = match ensure_signed(origin.clone()) {
+				Ok(who) if with_delayed_origin && !T::DelayedCallDeposit::get().is_zero() => {
+					let amount = T::DelayedCallDeposit::get();
+					T::Currency::reserve(&who, amount)?;
+					Some((who, amount))
+				}
+				_ => None,
+			};
 
 			let id = NextTaskIndex::<T>::mutate(|id| -> sp_std::result::Result<ScheduleTaskIndex, DispatchError> {
 				let current_id = *id;
@@ -266,22 +516,36 @@ pub mod module {
 			};
 			let pallets_origin = schedule_origin.caller().clone();
 
-			T::Scheduler::schedule_named(
-				Encode::encode(&(&pallets_origin, id)),
-				when,
-				None,
-				priority,
-				pallets_origin.clone(),
-				*call,
-			)
-			.map_err(|_| Error::<T>::FailedToSchedule)?;
+			let key = Encode::encode(&(&pallets_origin, id));
+			let encoded_call = call.encode();
+			let call_hash = T::Hashing::hash(&encoded_call);
+			ScheduledCalls::<T>::insert(id, (call_hash, BoundedVec::try_from(encoded_call).ok()));
+			T::Scheduler::schedule_named(key.clone(), when, None, priority, pallets_origin.clone(), *call)
+				.map_err(|_| Error::<T>::FailedToSchedule)?;
+
+			let dispatch_at = T::Scheduler::next_dispatch_time(key).map_err(|_| Error::<T>::FailedToSchedule)?;
+			let expire_at = dispatch_at.saturating_add(T::ScheduledCallExpiry::get());
+			TaskChecks::<T>::insert(dispatch_at.saturating_add(One::one()), id, ());
+			TasksByCaller::<T>::insert(&caller, id, ());
+			ScheduledTasks::<T>::insert(
+				id,
+				ScheduledTask {
+					origin: pallets_origin.clone(),
+					caller,
+					deposit,
+					expire_at,
+					priority,
+				},
+			);
 
 			Self::deposit_event(Event::Scheduled(pallets_origin, id));
 			Ok(())
 		}
 
 		/// Fast track a scheduled dispatchable.
-		#[pallet::weight(T::WeightInfo::fast_track_scheduled_dispatch())]
+		#[pallet::weight(
+			T::WeightInfo::fast_track_scheduled_dispatch().saturating_add(T::DbWeight::get().reads_writes(1, 2))
+		)]
 		pub fn fast_track_scheduled_dispatch(
 			origin: OriginFor<T>,
 			initial_origin: Box<T::PalletsOrigin>,
@@ -301,13 +565,16 @@ pub mod module {
 			T::AuthorityConfig::check_fast_track_schedule(origin, &initial_origin, new_delay)?;
 			T::Scheduler::reschedule_named((&initial_origin, task_id).encode(), when)
 				.map_err(|_| Error::<T>::FailedToFastTrack)?;
+			Self::update_task_expiry(&initial_origin, task_id, dispatch_at);
 
 			Self::deposit_event(Event::FastTracked(*initial_origin, task_id, dispatch_at));
 			Ok(())
 		}
 
 		/// Delay a scheduled dispatchable.
-		#[pallet::weight(T::WeightInfo::delay_scheduled_dispatch())]
+		#[pallet::weight(
+			T::WeightInfo::delay_scheduled_dispatch().saturating_add(T::DbWeight::get().reads_writes(1, 2))
+		)]
 		pub fn delay_scheduled_dispatch(
 			origin: OriginFor<T>,
 			initial_origin: Box<T::PalletsOrigin>,
@@ -324,6 +591,7 @@ pub mod module {
 
 			let now = frame_system::Pallet::<T>::block_number();
 			let dispatch_at = now.saturating_add(additional_delay);
+			Self::update_task_expiry(&initial_origin, task_id, dispatch_at);
 
 			Self::deposit_event(Event::Delayed(*initial_origin, task_id, dispatch_at));
 			Ok(())
@@ -336,13 +604,45 @@ pub mod module {
 			initial_origin: Box<T::PalletsOrigin>,
 			task_id: ScheduleTaskIndex,
 		) -> DispatchResult {
-			T::AuthorityConfig::check_cancel_schedule(origin, &initial_origin)?;
+			let maybe_task = Self::scheduled_tasks(task_id).filter(|task| task.origin == *initial_origin);
+			let by_caller = maybe_task.is_some() && Self::ensure_task_caller(origin.clone(), task_id).is_ok();
+			if !by_caller {
+				T::AuthorityConfig::check_cancel_schedule(origin, &initial_origin)?;
+			}
 			T::Scheduler::cancel_named((&initial_origin, task_id).encode()).map_err(|_| Error::<T>::FailedToCancel)?;
+			if let Some(task) = maybe_task {
+				// cancelled by other origins is a veto, slash the deposit
+				Self::remove_task(task_id, &task, !by_caller);
+			}
 
 			Self::deposit_event(Event::Cancelled(*initial_origin, task_id));
 			Ok(())
 		}
 
+		/// Reschedule a scheduled dispatchable, only the origin that scheduled
+		/// it is allowed.
+		#[pallet::weight(T::WeightInfo::reschedule_dispatch().saturating_add(T::DbWeight::get().writes(2)))]
+		pub fn reschedule_dispatch(
+			origin: OriginFor<T>,
+			task_id: ScheduleTaskIndex,
+			when: DispatchTime<T::BlockNumber>,
+		) -> DispatchResult {
+			let task = Self::ensure_task_caller(origin, task_id)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let dispatch_at = match when {
+				DispatchTime::At(x) => x,
+				DispatchTime::After(x) => now.saturating_add(x),
+			};
+
+			T::Scheduler::reschedule_named((&task.origin, task_id).encode(), when)
+				.map_err(|_| Error::<T>::FailedToReschedule)?;
+			Self::update_task_expiry(&task.origin, task_id, dispatch_at);
+
+			Self::deposit_event(Event::Rescheduled(task.origin, task_id, dispatch_at));
+			Ok(())
+		}
+
 		#[pallet::weight(T::WeightInfo::authorize_call())]
 		pub fn authorize_call(
 			origin: OriginFor<T>,
@@ -403,5 +703,166 @@ pub mod module {
 				Ok(Pays::No.into())
 			})
 		}
+
+		/// Propose to transfer the origin id `as_origin` to `new_origin`,
+		/// which takes over once it accepts the transfer.
+		///
+		/// The dispatch origin for this call must be the origin currently
+		/// bound to `as_origin`.
+		#[pallet::weight(T::WeightInfo::propose_origin_transfer())]
+		pub fn propose_origin_transfer(
+			origin: OriginFor<T>,
+			as_origin: T::AsOriginId,
+			new_origin: Box<T::PalletsOrigin>,
+		) -> DispatchResult {
+			let caller = <T as Config>::Origin::from(origin).caller().clone();
+			ensure!(caller == Self::origin_of(as_origin.clone()), DispatchError::BadOrigin);
+
+			PendingOriginTransfers::<T>::insert(&as_origin, &*new_origin);
+			Self::deposit_event(Event::OriginTransferProposed(as_origin, *new_origin));
+			Ok(())
+		}
+
+		/// Accept the proposed transfer of the origin id `as_origin`.
+		///
+		/// The dispatch origin for this call must be the proposed new origin.
+		///
+		/// `task_witness` must be no less than the number of pending tasks
+		/// scheduled by the previous origin.
+		#[pallet::weight(T::WeightInfo::accept_origin_transfer(*task_witness))]
+		#[transactional]
+		pub fn accept_origin_transfer(
+			origin: OriginFor<T>,
+			as_origin: T::AsOriginId,
+			#[pallet::compact] task_witness: u32,
+		) -> DispatchResult {
+			let new_origin = Self::pending_origin_transfers(&as_origin).ok_or(Error::<T>::NoPendingOriginTransfer)?;
+			let caller = <T as Config>::Origin::from(origin).caller().clone();
+			ensure!(caller == new_origin, DispatchError::BadOrigin);
+
+			Self::transfer_origin(as_origin, new_origin, task_witness)
+		}
+
+		/// Force set the origin bound to the origin id `as_origin`.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		///
+		/// `task_witness` must be no less than the number of pending tasks
+		/// scheduled by the previous origin.
+		#[pallet::weight(T::WeightInfo::force_set_origin(*task_witness))]
+		#[transactional]
+		pub fn force_set_origin(
+			origin: OriginFor<T>,
+			as_origin: T::AsOriginId,
+			new_origin: Box<T::PalletsOrigin>,
+			#[pallet::compact] task_witness: u32,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			Self::transfer_origin(as_origin, *new_origin, task_witness)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The origin `as_origin` dispatches as.
+	pub fn origin_of(as_origin: T::AsOriginId) -> T::PalletsOrigin {
+		Self::bound_origins(&as_origin).unwrap_or_else(|| as_origin.into_origin())
+	}
+
+	/// Bind `as_origin` to `new_origin`, and check the pending tasks
+	/// scheduled by the previous origin against `new_origin`: the tasks it
+	/// is allowed to schedule are taken over by it, the others are
+	/// cancelled.
+	///
+	/// Fails if the previous origin has more than `task_witness` pending
+	/// tasks.
+	fn transfer_origin(as_origin: T::AsOriginId, new_origin: T::PalletsOrigin, task_witness: u32) -> DispatchResult {
+		let old_origin = Self::origin_of(as_origin.clone());
+		BoundOrigins::<T>::insert(&as_origin, new_origin.clone());
+		PendingOriginTransfers::<T>::remove(&as_origin);
+
+		if old_origin != new_origin {
+			let task_ids = TasksByCaller::<T>::iter_key_prefix(&old_origin)
+				.take(task_witness.saturating_add(1) as usize)
+				.collect::<Vec<_>>();
+			ensure!(task_ids.len() <= task_witness as usize, Error::<T>::InvalidTaskWitness);
+			for task_id in task_ids {
+				let mut task = match Self::scheduled_tasks(task_id) {
+					Some(task) => task,
+					None => continue,
+				};
+				if T::AuthorityConfig::check_schedule_dispatch(new_origin.clone().into(), task.priority).is_ok() {
+					TasksByCaller::<T>::remove(&old_origin, task_id);
+					TasksByCaller::<T>::insert(&new_origin, task_id, ());
+					task.caller = new_origin.clone();
+					ScheduledTasks::<T>::insert(task_id, task);
+				} else {
+					let _ = T::Scheduler::cancel_named((&task.origin, task_id).encode());
+					Self::remove_task(task_id, &task, false);
+					Self::deposit_event(Event::Cancelled(task.origin, task_id));
+				}
+			}
+		}
+
+		Self::deposit_event(Event::OriginTransferred(as_origin, old_origin, new_origin));
+		Ok(())
+	}
+
+	/// Ensure `origin` is the origin that scheduled the task `task_id`,
+	/// returns the task.
+	fn ensure_task_caller(
+		origin: OriginFor<T>,
+		task_id: ScheduleTaskIndex,
+	) -> sp_std::result::Result<ScheduledTaskOf<T>, DispatchError> {
+		let task = Self::scheduled_tasks(task_id).ok_or(Error::<T>::TaskNotFound)?;
+		let caller = <T as Config>::Origin::from(origin).caller().clone();
+		ensure!(caller == task.caller, DispatchError::BadOrigin);
+		Ok(task)
+	}
+
+	/// Move the expiry of the task `task_id` of `initial_origin` along with
+	/// its new dispatch block `dispatch_at`, and check it once dispatched.
+	fn update_task_expiry(initial_origin: &T::PalletsOrigin, task_id: ScheduleTaskIndex, dispatch_at: T::BlockNumber) {
+		ScheduledTasks::<T>::mutate(task_id, |maybe_task| {
+			if let Some(task) = maybe_task.as_mut().filter(|task| task.origin == *initial_origin) {
+				task.expire_at = dispatch_at.saturating_add(T::ScheduledCallExpiry::get());
+				TaskChecks::<T>::insert(dispatch_at.saturating_add(One::one()), task_id, ());
+			}
+		});
+	}
+
+	/// Remove the task `task_id`, return its deposit or slash it if
+	/// `slash_deposit`.
+	fn remove_task(task_id: ScheduleTaskIndex, task: &ScheduledTaskOf<T>, slash_deposit: bool) {
+		if let Some((who, amount)) = &task.deposit {
+			if slash_deposit {
+				let _ = T::Currency::slash_reserved(who, *amount);
+			} else {
+				T::Currency::unreserve(who, *amount);
+			}
+		}
+		TasksByCaller::<T>::remove(&task.caller, task_id);
+		ScheduledTasks::<T>::remove(task_id);
+		ScheduledCalls::<T>::remove(task_id);
+	}
+
+	/// The scheduled tasks pending dispatch, with the calls to be
+	/// dispatched, or their hashes if they are too long to be kept.
+	pub fn pending_dispatches() -> Vec<PendingDispatchOf<T>> {
+		ScheduledTasks::<T>::iter()
+			.filter_map(|(task_id, task)| {
+				let when = T::Scheduler::next_dispatch_time((&task.origin, task_id).encode()).ok()?;
+				let (call_hash, encoded_call) = Self::scheduled_calls(task_id)?;
+				Some(PendingDispatch {
+					task_id,
+					origin: task.origin,
+					when,
+					expire_at: task.expire_at,
+					priority: task.priority,
+					call_hash,
+					call: encoded_call.and_then(|encoded| CallOf::<T>::decode(&mut &encoded[..]).ok()),
+				})
+			})
+			.collect()
 	}
 }
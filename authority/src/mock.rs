@@ -17,6 +17,7 @@ pub use crate as authority;
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
+pub type Balance = u128;
 
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
@@ -40,7 +41,7 @@ impl frame_system::Config for Runtime {
 	type BlockLength = ();
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<Balance>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type DbWeight = ();
@@ -50,6 +51,22 @@ impl frame_system::Config for Runtime {
 	type OnSetCode = ();
 }
 
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Pallet<Runtime>;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * BlockWeights::get().max_block;
 }
@@ -143,6 +160,12 @@ impl AsOriginId<Origin, OriginCaller> for MockAsOriginId {
 	}
 }
 
+parameter_types! {
+	pub const DelayedCallDeposit: Balance = 10;
+	pub const ScheduledCallExpiry: BlockNumber = 5;
+	pub const MaxScheduledCallLen: u32 = 16;
+}
+
 impl Config for Runtime {
 	type Event = Event;
 	type Origin = Origin;
@@ -151,6 +174,10 @@ impl Config for Runtime {
 	type Call = Call;
 	type AsOriginId = MockAsOriginId;
 	type AuthorityConfig = AuthorityConfigImpl;
+	type Currency = PalletBalances;
+	type DelayedCallDeposit = DelayedCallDeposit;
+	type ScheduledCallExpiry = ScheduledCallExpiry;
+	type MaxScheduledCallLen = MaxScheduledCallLen;
 	type WeightInfo = ();
 }
 
@@ -166,6 +193,7 @@ frame_support::construct_runtime!(
 		System: frame_system::{Pallet, Call, Config, Event<T>},
 		Authority: authority::{Pallet, Call, Origin<T>, Event<T>},
 		Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>},
+		PalletBalances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 	}
 );
 
@@ -179,10 +207,16 @@ impl Default for ExtBuilder {
 
 impl ExtBuilder {
 	pub fn build(self) -> sp_io::TestExternalities {
-		let t = frame_system::GenesisConfig::default()
+		let mut t = frame_system::GenesisConfig::default()
 			.build_storage::<Runtime>()
 			.unwrap();
 
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(1, 100), (2, 100)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		t.into()
 	}
 }
@@ -10,8 +10,8 @@ use frame_support::{
 };
 use frame_system::RawOrigin;
 use mock::{
-	authority, run_to_block, Authority, BlockNumber, Call, ExtBuilder, MockAsOriginId, Origin, OriginCaller, Runtime,
-	System,
+	authority, run_to_block, Authority, BlockNumber, Call, ExtBuilder, MockAsOriginId, Origin, OriginCaller,
+	PalletBalances, Runtime, Scheduler, System,
 };
 use sp_runtime::{traits::BadOrigin, Perbill};
 
@@ -54,6 +54,55 @@ fn dispatch_as_work() {
 	});
 }
 
+#[test]
+fn batch_dispatch_as_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let ensure_root_call = Call::System(frame_system::Call::fill_block { ratio: Perbill::one() });
+		let ensure_signed_call = Call::System(frame_system::Call::remark { remark: vec![] });
+		let transfer_call = Call::PalletBalances(pallet_balances::Call::transfer { dest: 2, value: 10 });
+
+		assert_ok!(Authority::batch_dispatch_as(
+			Origin::root(),
+			vec![
+				(MockAsOriginId::Root, ensure_root_call.clone()),
+				(MockAsOriginId::Account1, ensure_signed_call.clone()),
+			]
+		));
+		System::assert_last_event(mock::Event::Authority(Event::BatchDispatched(2)));
+
+		assert_noop!(
+			Authority::batch_dispatch_as(
+				Origin::signed(1),
+				vec![
+					(MockAsOriginId::Account1, ensure_signed_call.clone()),
+					(MockAsOriginId::Root, ensure_signed_call),
+				]
+			),
+			BadOrigin,
+		);
+
+		// all or nothing
+		assert_noop!(
+			Authority::batch_dispatch_as(
+				Origin::root(),
+				vec![
+					(MockAsOriginId::Account1, transfer_call.clone()),
+					(MockAsOriginId::Account1, ensure_root_call),
+				]
+			),
+			BadOrigin,
+		);
+
+		assert_ok!(Authority::batch_dispatch_as(
+			Origin::signed(1),
+			vec![(MockAsOriginId::Account1, transfer_call)]
+		));
+		assert_eq!(PalletBalances::free_balance(1), 90);
+		assert_eq!(PalletBalances::free_balance(2), 110);
+	});
+}
+
 #[test]
 fn schedule_dispatch_at_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -396,6 +445,245 @@ fn cancel_scheduled_dispatch_work() {
 	});
 }
 
+#[test]
+fn reschedule_and_cancel_by_caller_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let call = Call::System(frame_system::Call::remark { remark: vec![] });
+		let delayed_origin = OriginCaller::Authority(DelayedOrigin {
+			delay: 1,
+			origin: Box::new(OriginCaller::system(RawOrigin::Signed(1))),
+		});
+		run_to_block(1);
+		assert_ok!(Authority::schedule_dispatch(
+			Origin::signed(1),
+			DispatchTime::At(2),
+			0,
+			true,
+			Box::new(call.clone())
+		));
+		assert_eq!(
+			Authority::scheduled_tasks(0),
+			Some(ScheduledTask {
+				origin: delayed_origin.clone(),
+				caller: OriginCaller::system(RawOrigin::Signed(1)),
+				deposit: Some((1, 10)),
+				expire_at: 7,
+				priority: 0,
+			})
+		);
+
+		assert_noop!(
+			Authority::reschedule_dispatch(Origin::signed(2), 0, DispatchTime::At(5)),
+			BadOrigin
+		);
+		assert_noop!(
+			Authority::reschedule_dispatch(Origin::signed(1), 1, DispatchTime::At(5)),
+			Error::<Runtime>::TaskNotFound
+		);
+		assert_ok!(Authority::reschedule_dispatch(
+			Origin::signed(1),
+			0,
+			DispatchTime::After(4)
+		));
+		System::assert_last_event(mock::Event::Authority(Event::Rescheduled(delayed_origin.clone(), 0, 5)));
+		assert_eq!(Authority::scheduled_tasks(0).map(|task| task.expire_at), Some(10));
+
+		assert_noop!(
+			Authority::cancel_scheduled_dispatch(Origin::signed(2), Box::new(delayed_origin.clone()), 0),
+			BadOrigin
+		);
+		assert_ok!(Authority::cancel_scheduled_dispatch(
+			Origin::signed(1),
+			Box::new(delayed_origin.clone()),
+			0
+		));
+		System::assert_last_event(mock::Event::Authority(Event::Cancelled(delayed_origin, 0)));
+		assert_eq!(Authority::scheduled_tasks(0), None);
+		assert_eq!(PalletBalances::reserved_balance(1), 0);
+		assert_eq!(PalletBalances::free_balance(1), 100);
+	});
+}
+
+#[test]
+fn delayed_call_deposit_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let call = Call::System(frame_system::Call::remark { remark: vec![] });
+		let delayed_origin = OriginCaller::Authority(DelayedOrigin {
+			delay: 1,
+			origin: Box::new(OriginCaller::system(RawOrigin::Signed(1))),
+		});
+		run_to_block(1);
+
+		// no deposit without delayed origin
+		assert_ok!(Authority::schedule_dispatch(
+			Origin::signed(1),
+			DispatchTime::At(2),
+			0,
+			false,
+			Box::new(call.clone())
+		));
+		assert_eq!(PalletBalances::reserved_balance(1), 0);
+
+		assert_ok!(Authority::schedule_dispatch(
+			Origin::signed(1),
+			DispatchTime::At(2),
+			0,
+			true,
+			Box::new(call.clone())
+		));
+		assert_eq!(PalletBalances::reserved_balance(1), 10);
+
+		// returned after dispatched
+		run_to_block(2);
+		Authority::on_initialize(3);
+		assert_eq!(Authority::scheduled_tasks(1), None);
+		assert_eq!(PalletBalances::reserved_balance(1), 0);
+		assert_eq!(PalletBalances::free_balance(1), 100);
+
+		// slashed if cancelled by other origins
+		assert_ok!(Authority::schedule_dispatch(
+			Origin::signed(1),
+			DispatchTime::At(3),
+			0,
+			true,
+			Box::new(call)
+		));
+		assert_eq!(PalletBalances::reserved_balance(1), 10);
+		assert_ok!(Authority::cancel_scheduled_dispatch(
+			Origin::root(),
+			Box::new(delayed_origin),
+			2
+		));
+		assert_eq!(PalletBalances::reserved_balance(1), 0);
+		assert_eq!(PalletBalances::free_balance(1), 90);
+	});
+}
+
+#[test]
+fn expired_scheduled_call_is_cancelled() {
+	ExtBuilder::default().build().execute_with(|| {
+		let call = Call::System(frame_system::Call::remark { remark: vec![] });
+		let pallets_origin: OriginCaller = RawOrigin::Root.into();
+		run_to_block(1);
+		assert_ok!(Authority::schedule_dispatch(
+			Origin::root(),
+			DispatchTime::At(2),
+			0,
+			false,
+			Box::new(call)
+		));
+		assert_eq!(Authority::scheduled_tasks(0).map(|task| task.expire_at), Some(7));
+
+		// the expiry moves along with the delay
+		assert_ok!(Authority::delay_scheduled_dispatch(
+			Origin::root(),
+			Box::new(pallets_origin.clone()),
+			0,
+			10
+		));
+		assert_eq!(Authority::scheduled_tasks(0).map(|task| task.expire_at), Some(16));
+
+		// postponed by the scheduler, it's checked every block until expired
+		assert_ok!(
+			<Scheduler as ScheduleNamed<BlockNumber, Call, OriginCaller>>::reschedule_named(
+				(&pallets_origin, 0u32).encode(),
+				DispatchTime::At(20)
+			)
+		);
+		for now in 3..=16 {
+			Authority::on_initialize(now);
+			assert!(Authority::scheduled_tasks(0).is_some());
+		}
+
+		Authority::on_initialize(17);
+		assert_eq!(Authority::scheduled_tasks(0), None);
+		System::assert_last_event(mock::Event::Authority(Event::Expired(pallets_origin.clone(), 0)));
+		assert!(
+			<Scheduler as ScheduleNamed<BlockNumber, Call, OriginCaller>>::next_dispatch_time(
+				(&pallets_origin, 0u32).encode()
+			)
+			.is_err()
+		);
+	});
+}
+
+#[test]
+fn pending_dispatches_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let call = Call::System(frame_system::Call::remark { remark: vec![1] });
+		let long_call = Call::System(frame_system::Call::remark { remark: vec![1; 16] });
+		let call_hash = <Runtime as frame_system::Config>::Hashing::hash_of(&call);
+		let long_call_hash = <Runtime as frame_system::Config>::Hashing::hash_of(&long_call);
+		let pallets_origin: OriginCaller = RawOrigin::Root.into();
+		run_to_block(1);
+		assert_ok!(Authority::schedule_dispatch(
+			Origin::root(),
+			DispatchTime::At(3),
+			0,
+			false,
+			Box::new(call.clone())
+		));
+		assert_ok!(Authority::schedule_dispatch(
+			Origin::root(),
+			DispatchTime::At(3),
+			0,
+			false,
+			Box::new(long_call)
+		));
+		assert_eq!(
+			Authority::scheduled_calls(0),
+			Some((call_hash, Some(BoundedVec::try_from(call.encode()).unwrap())))
+		);
+		// only the hash of a call longer than `MaxScheduledCallLen` is kept
+		assert_eq!(Authority::scheduled_calls(1), Some((long_call_hash, None)));
+		let mut pending_dispatches = Authority::pending_dispatches();
+		pending_dispatches.sort_by_key(|d| d.task_id);
+		assert_eq!(
+			pending_dispatches,
+			vec![
+				PendingDispatch {
+					task_id: 0,
+					origin: pallets_origin.clone(),
+					when: 3,
+					expire_at: 8,
+					priority: 0,
+					call_hash,
+					call: Some(call.clone()),
+				},
+				PendingDispatch {
+					task_id: 1,
+					origin: pallets_origin.clone(),
+					when: 3,
+					expire_at: 8,
+					priority: 0,
+					call_hash: long_call_hash,
+					call: None,
+				}
+			]
+		);
+
+		assert_ok!(Authority::fast_track_scheduled_dispatch(
+			Origin::root(),
+			Box::new(pallets_origin),
+			0,
+			DispatchTime::At(2)
+		));
+		let mut pending_dispatches = Authority::pending_dispatches()
+			.into_iter()
+			.map(|d| (d.task_id, d.when))
+			.collect::<Vec<_>>();
+		pending_dispatches.sort();
+		assert_eq!(pending_dispatches, vec![(0, 2), (1, 3)]);
+
+		// removed once dispatched
+		run_to_block(3);
+		assert_eq!(Authority::pending_dispatches(), vec![]);
+		Authority::on_initialize(4);
+		assert_eq!(Authority::scheduled_calls(0), None);
+		assert_eq!(Authority::scheduled_calls(1), None);
+	});
+}
+
 #[test]
 fn call_size_limit() {
 	assert!(
@@ -570,3 +858,178 @@ fn trigger_call_should_be_free_and_operational() {
 		);
 	});
 }
+
+#[test]
+fn origin_transfer_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let account1 = OriginCaller::system(RawOrigin::Signed(1));
+		let account3 = OriginCaller::system(RawOrigin::Signed(3));
+
+		assert_noop!(
+			Authority::propose_origin_transfer(Origin::signed(2), MockAsOriginId::Account1, Box::new(account3.clone())),
+			BadOrigin
+		);
+		assert_noop!(
+			Authority::accept_origin_transfer(Origin::signed(3), MockAsOriginId::Account1, 0),
+			Error::<Runtime>::NoPendingOriginTransfer
+		);
+
+		assert_ok!(Authority::propose_origin_transfer(
+			Origin::signed(1),
+			MockAsOriginId::Account1,
+			Box::new(account3.clone())
+		));
+		System::assert_last_event(mock::Event::Authority(Event::OriginTransferProposed(
+			MockAsOriginId::Account1,
+			account3.clone(),
+		)));
+		assert_eq!(
+			Authority::pending_origin_transfers(MockAsOriginId::Account1),
+			Some(account3.clone())
+		);
+		// not transferred until accepted
+		assert_eq!(Authority::origin_of(MockAsOriginId::Account1), account1);
+
+		assert_noop!(
+			Authority::accept_origin_transfer(Origin::signed(2), MockAsOriginId::Account1, 0),
+			BadOrigin
+		);
+		assert_ok!(Authority::accept_origin_transfer(
+			Origin::signed(3),
+			MockAsOriginId::Account1,
+			0
+		));
+		System::assert_last_event(mock::Event::Authority(Event::OriginTransferred(
+			MockAsOriginId::Account1,
+			account1,
+			account3.clone(),
+		)));
+		assert_eq!(Authority::origin_of(MockAsOriginId::Account1), account3.clone());
+		assert_eq!(Authority::pending_origin_transfers(MockAsOriginId::Account1), None);
+
+		// only the bound origin can propose
+		assert_noop!(
+			Authority::propose_origin_transfer(Origin::signed(1), MockAsOriginId::Account1, Box::new(account3.clone())),
+			BadOrigin
+		);
+		assert_ok!(Authority::propose_origin_transfer(
+			Origin::signed(3),
+			MockAsOriginId::Account1,
+			Box::new(account3)
+		));
+	});
+}
+
+#[test]
+fn force_set_origin_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let account1 = OriginCaller::system(RawOrigin::Signed(1));
+		let account2 = OriginCaller::system(RawOrigin::Signed(2));
+		let transfer_call = Call::PalletBalances(pallet_balances::Call::transfer { dest: 3, value: 10 });
+
+		assert_ok!(Authority::propose_origin_transfer(
+			Origin::signed(2),
+			MockAsOriginId::Account2,
+			Box::new(account2.clone())
+		));
+		assert_noop!(
+			Authority::force_set_origin(
+				Origin::signed(2),
+				MockAsOriginId::Account2,
+				Box::new(account1.clone()),
+				0
+			),
+			BadOrigin
+		);
+		assert_ok!(Authority::force_set_origin(
+			Origin::root(),
+			MockAsOriginId::Account2,
+			Box::new(account1.clone()),
+			0
+		));
+		System::assert_last_event(mock::Event::Authority(Event::OriginTransferred(
+			MockAsOriginId::Account2,
+			account2,
+			account1,
+		)));
+		assert_eq!(Authority::pending_origin_transfers(MockAsOriginId::Account2), None);
+
+		// dispatched as the new origin
+		assert_ok!(Authority::dispatch_as(
+			Origin::root(),
+			MockAsOriginId::Account2,
+			Box::new(transfer_call)
+		));
+		assert_eq!(PalletBalances::free_balance(1), 90);
+		assert_eq!(PalletBalances::free_balance(2), 100);
+		assert_eq!(PalletBalances::free_balance(3), 10);
+	});
+}
+
+#[test]
+fn origin_transfer_revalidates_scheduled_tasks() {
+	ExtBuilder::default().build().execute_with(|| {
+		let call = Call::System(frame_system::Call::remark { remark: vec![] });
+		let account1 = OriginCaller::system(RawOrigin::Signed(1));
+		let account2 = OriginCaller::system(RawOrigin::Signed(2));
+		let delayed_origin = OriginCaller::Authority(DelayedOrigin {
+			delay: 1,
+			origin: Box::new(account1.clone()),
+		});
+		run_to_block(1);
+		assert_ok!(Authority::schedule_dispatch(
+			Origin::signed(1),
+			DispatchTime::At(2),
+			0,
+			true,
+			Box::new(call.clone())
+		));
+		assert_ok!(Authority::schedule_dispatch(
+			Origin::signed(1),
+			DispatchTime::At(2),
+			0,
+			false,
+			Box::new(call)
+		));
+		assert_eq!(PalletBalances::reserved_balance(1), 10);
+
+		assert_noop!(
+			Authority::force_set_origin(Origin::root(), MockAsOriginId::Account1, Box::new(account2.clone()), 1),
+			Error::<Runtime>::InvalidTaskWitness
+		);
+
+		// account 2 is allowed to schedule, takes over the tasks
+		assert_ok!(Authority::force_set_origin(
+			Origin::root(),
+			MockAsOriginId::Account1,
+			Box::new(account2.clone()),
+			2
+		));
+		assert_eq!(Authority::scheduled_tasks(0).unwrap().caller, account2);
+		assert_eq!(Authority::scheduled_tasks(1).unwrap().caller, account2);
+		assert_eq!(TasksByCaller::<Runtime>::iter_prefix(&account1).count(), 0);
+		assert_eq!(TasksByCaller::<Runtime>::iter_prefix(&account2).count(), 2);
+		assert_ok!(Authority::reschedule_dispatch(
+			Origin::signed(2),
+			0,
+			DispatchTime::At(3)
+		));
+
+		// account 3 is not allowed to schedule, the tasks are cancelled
+		assert_ok!(Authority::force_set_origin(
+			Origin::root(),
+			MockAsOriginId::Account1,
+			Box::new(OriginCaller::system(RawOrigin::Signed(3))),
+			2
+		));
+		System::assert_has_event(mock::Event::Authority(Event::Cancelled(delayed_origin, 0)));
+		System::assert_has_event(mock::Event::Authority(Event::Cancelled(account1, 1)));
+		assert_eq!(Authority::scheduled_tasks(0), None);
+		assert_eq!(Authority::scheduled_tasks(1), None);
+		assert_eq!(TasksByCaller::<Runtime>::iter_prefix(&account2).count(), 0);
+		assert_eq!(PalletBalances::reserved_balance(1), 0);
+		assert_eq!(PalletBalances::free_balance(1), 100);
+	});
+}
@@ -35,9 +35,13 @@ pub trait WeightInfo {
 	fn fast_track_scheduled_dispatch() -> Weight;
 	fn delay_scheduled_dispatch() -> Weight;
 	fn cancel_scheduled_dispatch() -> Weight;
+	fn reschedule_dispatch() -> Weight;
 	fn authorize_call() -> Weight;
 	fn remove_authorized_call() -> Weight;
 	fn trigger_call() -> Weight;
+	fn propose_origin_transfer() -> Weight;
+	fn accept_origin_transfer(c: u32, ) -> Weight;
+	fn force_set_origin(c: u32, ) -> Weight;
 }
 
 /// Default weights.
@@ -67,8 +71,14 @@ impl WeightInfo for () {
 	}
 	fn cancel_scheduled_dispatch() -> Weight {
 		(29_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Estimated, not generated.
+	fn reschedule_dispatch() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
 	fn authorize_call() -> Weight {
 		(14_000_000 as Weight)
@@ -84,4 +94,28 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// Estimated, not generated.
+	fn propose_origin_transfer() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Estimated, not generated.
+	fn accept_origin_transfer(c: u32, ) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((12_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(c as Weight)))
+	}
+	// Estimated, not generated.
+	fn force_set_origin(c: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((12_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(c as Weight)))
+	}
 }
@@ -0,0 +1,291 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+	parse::{Parse, ParseStream},
+	punctuated::Punctuated,
+	Error, Expr, Ident, Item, ItemFn, ItemMod, Token, Type,
+};
+
+/// An argument of `#[bench(..)]`, e.g. `setup = fixture`.
+struct BenchArg {
+	key: Ident,
+	value: Expr,
+}
+
+impl Parse for BenchArg {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let key = input.parse()?;
+		input.parse::<Token![=]>()?;
+		let value = input.parse()?;
+		Ok(BenchArg { key, value })
+	}
+}
+
+/// Turn a `#[bench]` function into a function configuring the bencher.
+///
+/// A function taking the bencher is kept as is. A function without
+/// arguments is the bench block, configured by the `#[bench(..)]`
+/// arguments.
+fn expand_bench(mut item: ItemFn, args: Vec<BenchArg>) -> syn::Result<proc_macro2::TokenStream> {
+	item.vis = syn::parse_quote!(pub);
+
+	if !item.sig.inputs.is_empty() {
+		if let Some(arg) = args.first() {
+			return Err(Error::new(
+				arg.key.span(),
+				"arguments are only supported by benches without inputs",
+			));
+		}
+		return Ok(quote!(#item));
+	}
+
+	let mut config = Vec::new();
+	for BenchArg { key, value } in args {
+		let method = match key.to_string().as_str() {
+			"name" => quote!(name),
+			"genesis" => quote!(genesis),
+			"setup" => quote!(prepare),
+			"verify" => quote!(verify),
+			"scenario" => quote!(scenario),
+			_ => {
+				return Err(Error::new(
+					key.span(),
+					"expected one of `name`, `genesis`, `setup`, `verify` or `scenario`",
+				))
+			}
+		};
+		config.push(quote!(b.#method(#value);));
+	}
+
+	let ItemFn { attrs, vis, sig, block } = item;
+	let ident = &sig.ident;
+	Ok(quote! {
+		#(#attrs)*
+		#vis fn #ident(b: &mut ::orml_bencher::Bencher) {
+			b.bench(|| #block);
+			#(#config)*
+		}
+	})
+}
+
+fn expand_benches(block: Type, item: ItemMod) -> syn::Result<proc_macro2::TokenStream> {
+	let ItemMod {
+		attrs,
+		vis,
+		ident,
+		content,
+		..
+	} = item;
+	let items = match content {
+		Some((_, items)) => items,
+		None => return Err(Error::new(ident.span(), "`#[benches]` requires an inline module")),
+	};
+
+	let mut benches = Vec::new();
+	let mut expanded = Vec::new();
+	for item in items {
+		match item {
+			Item::Fn(mut item_fn) => {
+				let position = item_fn.attrs.iter().position(|attr| attr.path.is_ident("bench"));
+				match position {
+					Some(position) => {
+						let attr = item_fn.attrs.remove(position);
+						let args = if attr.tokens.is_empty() {
+							Vec::new()
+						} else {
+							attr.parse_args_with(Punctuated::<BenchArg, Token![,]>::parse_terminated)?
+								.into_iter()
+								.collect()
+						};
+						benches.push(item_fn.sig.ident.clone());
+						expanded.push(expand_bench(item_fn, args)?);
+					}
+					// shared fixtures
+					None => expanded.push(quote!(#item_fn)),
+				}
+			}
+			item => expanded.push(quote!(#item)),
+		}
+	}
+
+	if benches.is_empty() {
+		return Err(Error::new(ident.span(), "no `#[bench]` function defined"));
+	}
+
+	let block_ident = Ident::new("__OrmlBenchesBlock", Span::call_site());
+	Ok(quote! {
+		#(#attrs)*
+		#vis mod #ident {
+			#(#expanded)*
+		}
+
+		#[doc(hidden)]
+		type #block_ident = #block;
+
+		#[allow(unused_imports)]
+		use #ident::{#(#benches),*};
+
+		::orml_bencher::bench!(#block_ident, #(#benches),*);
+	})
+}
+
+/// Define benches in an inline module, see `orml_bencher::benches`.
+#[proc_macro_attribute]
+pub fn benches(attr: TokenStream, item: TokenStream) -> TokenStream {
+	let block = syn::parse_macro_input!(attr as Type);
+	let item = syn::parse_macro_input!(item as ItemMod);
+	expand_benches(block, item)
+		.unwrap_or_else(|e| e.to_compile_error())
+		.into()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use syn::parse_quote;
+
+	fn expand(block: Type, item: ItemMod) -> String {
+		expand_benches(block, item).unwrap().to_string()
+	}
+
+	fn expand_err(block: Type, item: ItemMod) -> String {
+		expand_benches(block, item).unwrap_err().to_string()
+	}
+
+	#[test]
+	fn expands_to_bench_macro() {
+		let expanded = expand(
+			parse_quote!(mock::Block),
+			parse_quote! {
+				mod benches {
+					use super::*;
+
+					fn setup() {}
+
+					#[bench(name = "renamed", setup = setup, scenario = 10)]
+					fn foo() {
+						Module::foo();
+					}
+
+					#[bench]
+					fn bar(b: &mut Bencher) {
+						b.bench(|| Module::bar());
+					}
+				}
+			},
+		);
+
+		let expected = quote! {
+			mod benches {
+				use super::*;
+
+				fn setup() {}
+
+				pub fn foo(b: &mut ::orml_bencher::Bencher) {
+					b.bench(|| {
+						Module::foo();
+					});
+					b.name("renamed");
+					b.prepare(setup);
+					b.scenario(10);
+				}
+
+				pub fn bar(b: &mut Bencher) {
+					b.bench(|| Module::bar());
+				}
+			}
+
+			#[doc(hidden)]
+			type __OrmlBenchesBlock = mock::Block;
+
+			#[allow(unused_imports)]
+			use benches::{foo, bar};
+
+			::orml_bencher::bench!(__OrmlBenchesBlock, foo, bar);
+		};
+		assert_eq!(expanded, expected.to_string());
+	}
+
+	#[test]
+	fn keeps_attributes_of_benches() {
+		let expanded = expand(
+			parse_quote!(Block),
+			parse_quote! {
+				mod benches {
+					/// doc
+					#[bench(genesis = populated(), verify = check)]
+					fn foo() {}
+				}
+			},
+		);
+
+		let expected = quote! {
+			mod benches {
+				/// doc
+				pub fn foo(b: &mut ::orml_bencher::Bencher) {
+					b.bench(|| {});
+					b.genesis(populated());
+					b.verify(check);
+				}
+			}
+
+			#[doc(hidden)]
+			type __OrmlBenchesBlock = Block;
+
+			#[allow(unused_imports)]
+			use benches::{foo};
+
+			::orml_bencher::bench!(__OrmlBenchesBlock, foo);
+		};
+		assert_eq!(expanded, expected.to_string());
+	}
+
+	#[test]
+	fn rejects_invalid_benches() {
+		assert_eq!(
+			expand_err(
+				parse_quote!(Block),
+				parse_quote!(
+					mod benches;
+				)
+			),
+			"`#[benches]` requires an inline module"
+		);
+		assert_eq!(
+			expand_err(
+				parse_quote!(Block),
+				parse_quote!(
+					mod benches {
+						fn foo() {}
+					}
+				)
+			),
+			"no `#[bench]` function defined"
+		);
+		assert_eq!(
+			expand_err(
+				parse_quote!(Block),
+				parse_quote!(
+					mod benches {
+						#[bench(unknown = 1)]
+						fn foo() {}
+					}
+				)
+			),
+			"expected one of `name`, `genesis`, `setup`, `verify` or `scenario`"
+		);
+		assert_eq!(
+			expand_err(
+				parse_quote!(Block),
+				parse_quote!(
+					mod benches {
+						#[bench(name = "foo")]
+						fn foo(b: &mut Bencher) {}
+					}
+				)
+			),
+			"arguments are only supported by benches without inputs"
+		);
+	}
+}
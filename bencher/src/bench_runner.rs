@@ -7,8 +7,8 @@ use sp_state_machine::{Ext, OverlayedChanges, StorageTransactionCache};
 pub fn run<B: Block>(wasm_code: Vec<u8>) -> std::result::Result<Vec<u8>, String> {
 	let mut overlay = OverlayedChanges::default();
 	let mut cache = StorageTransactionCache::default();
-	let state =
-		sc_client_db::BenchmarkingState::<B>::new(Default::default(), Default::default(), false, false).unwrap();
+	// key tracking is enabled to measure the trie nodes of accessed keys
+	let state = sc_client_db::BenchmarkingState::<B>::new(Default::default(), Default::default(), false, true).unwrap();
 	let mut ext = Ext::<_, NumberFor<B>, _>::new(&mut overlay, &mut cache, &state, None, None);
 
 	let mut host_functions = sp_io::SubstrateHostFunctions::host_functions();
@@ -27,3 +27,24 @@ pub fn run<B: Block>(wasm_code: Vec<u8>) -> std::result::Result<Vec<u8>, String>
 
 	executor.uncached_call(blob, &mut ext, true, "run_benches", &[])
 }
+
+/// Build the benches to WASM, run them and handle the results, the `main` of
+/// the benches defined by `bench!`.
+pub fn main<B: Block>() -> std::io::Result<()> {
+	#[cfg(feature = "cli")]
+	let options = crate::cli::Options::from_args();
+	#[cfg(feature = "cli")]
+	crate::cli::set(options.clone());
+
+	let wasm = crate::build_wasm::build()?;
+	match run::<B>(wasm) {
+		#[cfg(feature = "cli")]
+		Ok(_) if options.list => {}
+		#[cfg(feature = "cli")]
+		Ok(output) => crate::handler::handle_with_options(output, &options)?,
+		#[cfg(not(feature = "cli"))]
+		Ok(output) => crate::handler::handle(output),
+		Err(e) => eprintln!("{:?}", e),
+	}
+	Ok(())
+}
@@ -0,0 +1,138 @@
+//! Command line options of the bench runner, enabled by the `cli` feature.
+//!
+//! Passed after `--`, e.g. `cargo bench --features=bench -- --filter
+//! transfer`:
+//! - `--list`: list the benches, without running them.
+//! - `--filter <regex>`: only run the benches whose name matches.
+//! - `--save-baseline <name>`: save the results as the baseline `name`.
+//! - `--baseline <name>`: compare the results with the baseline `name`.
+//! - `--json <path>`: also write the results as JSON to `path`.
+
+use regex::Regex;
+use std::{cell::RefCell, path::PathBuf};
+
+/// Options of the bench runner.
+#[derive(Default, Debug, Clone)]
+pub struct Options {
+	pub list: bool,
+	pub filter: Option<Regex>,
+	pub save_baseline: Option<String>,
+	pub baseline: Option<String>,
+	pub json: Option<PathBuf>,
+}
+
+impl Options {
+	/// Parse the options from `args`, excluding the program name. The `--bench`
+	/// flag passed by `cargo bench` is ignored.
+	pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+		let mut options = Options::default();
+		let mut args = args.into_iter();
+		while let Some(arg) = args.next() {
+			let mut value = || args.next().ok_or_else(|| format!("Missing value of {}", arg));
+			match arg.as_str() {
+				"--bench" => {}
+				"--list" => options.list = true,
+				"--filter" => {
+					let filter = value()?;
+					options.filter = Some(Regex::new(&filter).map_err(|e| format!("Invalid filter: {}", e))?);
+				}
+				"--save-baseline" => options.save_baseline = Some(value()?),
+				"--baseline" => options.baseline = Some(value()?),
+				"--json" => options.json = Some(value()?.into()),
+				_ => return Err(format!("Unknown argument: {}", arg)),
+			}
+		}
+		Ok(options)
+	}
+
+	/// Parse the options from the command line arguments, exit on errors.
+	pub fn from_args() -> Self {
+		Self::parse(std::env::args().skip(1)).unwrap_or_else(|e| {
+			eprintln!("{}", e);
+			std::process::exit(1);
+		})
+	}
+
+	/// Whether the bench `name` is selected by the filter.
+	pub fn selects(&self, name: &str) -> bool {
+		self.filter.as_ref().map_or(true, |filter| filter.is_match(name))
+	}
+}
+
+thread_local! {
+	static OPTIONS: RefCell<Options> = RefCell::new(Options::default());
+}
+
+/// Set the options of the benches run next.
+pub fn set(options: Options) {
+	OPTIONS.with(|x| *x.borrow_mut() = options);
+}
+
+/// Whether the bench `name` should run. Prints it instead when listing the
+/// benches.
+pub(crate) fn bench_selected(name: &str) -> bool {
+	OPTIONS.with(|options| {
+		let options = options.borrow();
+		if options.list {
+			if options.selects(name) {
+				println!("{}", name);
+			}
+			return false;
+		}
+		options.selects(name)
+	})
+}
+
+/// The path of the baseline `name` of the current package.
+pub fn baseline_path(name: &str) -> PathBuf {
+	let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+	let pkg_name = std::env::var("CARGO_PKG_NAME").unwrap_or_default();
+	PathBuf::from(manifest_dir)
+		.join("target/orml-bencher/baselines")
+		.join(pkg_name)
+		.join(format!("{}.json", name))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn parse(args: &[&str]) -> Result<Options, String> {
+		Options::parse(args.iter().map(|arg| arg.to_string()))
+	}
+
+	#[test]
+	fn parse_should_work() {
+		let options = parse(&[
+			"--bench",
+			"--filter",
+			"^transfer",
+			"--baseline",
+			"main",
+			"--json",
+			"out.json",
+		])
+		.unwrap();
+		assert!(!options.list);
+		assert!(options.selects("transfer_all"));
+		assert!(!options.selects("set_lock"));
+		assert_eq!(options.baseline, Some("main".into()));
+		assert_eq!(options.save_baseline, None);
+		assert_eq!(options.json, Some("out.json".into()));
+
+		let options = parse(&["--list", "--save-baseline", "main"]).unwrap();
+		assert!(options.list);
+		assert!(options.selects("set_lock"));
+		assert_eq!(options.save_baseline, Some("main".into()));
+
+		assert_eq!(
+			parse(&["--filter"]).unwrap_err(),
+			"Missing value of --filter".to_string()
+		);
+		assert!(parse(&["--filter", "("]).is_err());
+		assert_eq!(
+			parse(&["--verbose"]).unwrap_err(),
+			"Unknown argument: --verbose".to_string()
+		);
+	}
+}
@@ -1,8 +1,8 @@
 use crate::{
 	colorize::{cyan, green_bold},
-	BenchResult,
+	BenchExtensions, BenchResult,
 };
-use codec::Decode;
+use frame_benchmarking::frame_support::{traits::Get, weights::constants::RocksDbWeight};
 use linregress::{FormulaRegressionBuilder, RegressionDataBuilder};
 use serde::{Deserialize, Serialize};
 use std::io::Write;
@@ -12,25 +12,86 @@ use std::time::Duration;
 struct BenchData {
 	pub name: String,
 	pub base_weight: u64,
+	/// Standard error of `base_weight` in the regression of the runs.
+	pub base_weight_std_error: u64,
+	/// Number of runs.
+	pub runs: u32,
 	pub base_reads: u32,
 	pub base_repeat_reads: u32,
 	pub base_writes: u32,
 	pub base_repeat_writes: u32,
+	/// `(nodes, reads, writes)`, see `BenchResult::trie_nodes`.
+	pub trie_nodes: Vec<(u32, u32, u32)>,
+	/// Average time recomputing the storage root, in the unit of
+	/// `base_weight`.
+	pub storage_root_weight: u64,
+	/// Average time committing to the DB, in the unit of `base_weight`.
+	pub commit_weight: u64,
+	pub committed_keys: u32,
+	/// See `BenchExtensions`, omitted if not measured.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub proof_size: Option<u32>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub events: Option<u32>,
 }
 
 /// Handle bench results
 pub fn handle(output: Vec<u8>) {
+	let data = process(output);
+	write_json(&data);
+}
+
+/// Handle bench results, comparing them with a baseline, saving them as a
+/// baseline or writing them to a JSON file as set by the `options`.
+#[cfg(feature = "cli")]
+pub fn handle_with_options(output: Vec<u8>, options: &crate::cli::Options) -> std::io::Result<()> {
+	let data = process(output);
+
+	if let Some(ref baseline) = options.baseline {
+		let path = crate::cli::baseline_path(baseline);
+		match std::fs::read(&path)
+			.ok()
+			.and_then(|json| serde_json::from_slice::<Vec<BenchData>>(&json).ok())
+		{
+			Some(baseline_data) => print_comparison(&baseline_data, &data),
+			None => eprintln!("Could not read baseline {} at {}", baseline, path.display()),
+		}
+	}
+
+	let json = serde_json::to_vec(&data).map_err(std::io::Error::from)?;
+	if let Some(ref baseline) = options.save_baseline {
+		let path = crate::cli::baseline_path(baseline);
+		if let Some(dir) = path.parent() {
+			std::fs::create_dir_all(dir)?;
+		}
+		std::fs::write(&path, &json)?;
+		println!("{} saved to {}", green_bold("Baseline"), path.display());
+	}
+	if let Some(ref path) = options.json {
+		std::fs::write(path, &json)?;
+	}
+
+	write_json(&data);
+	Ok(())
+}
+
+/// Print the results of the benches, and compute their bench data.
+fn process(output: Vec<u8>) -> Vec<BenchData> {
 	println!();
 
 	let pkg_name = std::env::var("CARGO_PKG_NAME").unwrap_or_default().replace("-", "_");
 
-	let results = <Vec<BenchResult> as Decode>::decode(&mut &output[..]).unwrap();
+	let results = BenchResult::decode_results(&output).unwrap_or_else(|e| panic!("{}", e));
+	let (scenarios, results): (Vec<BenchResult>, Vec<BenchResult>) =
+		results.into_iter().partition(|result| result.extrinsics > 0);
+
 	let data: Vec<BenchData> = results
 		.into_iter()
 		.map(|result| {
 			let name = String::from_utf8_lossy(&result.method).to_string();
 
 			let y: Vec<f64> = result.elapses.into_iter().map(|x| x as f64).collect();
+			let runs = y.len();
 			let x: Vec<f64> = (0..y.len()).into_iter().map(|x| x as f64).collect();
 			let data = vec![("Y", y), ("X", x)];
 			let data = RegressionDataBuilder::new().build_from(data).unwrap();
@@ -53,21 +114,42 @@ pub fn handle(output: Vec<u8>) {
 				format!("reads: {}", green_bold(&result.reads.to_string())),
 				format!("writes: {}", green_bold(&result.writes.to_string()))
 			);
+			print_trie_nodes(&result.trie_nodes);
+			print_commit(&result);
+			let extensions = crate::bench_extensions(&result.method);
+			print_extensions(&extensions);
 
 			BenchData {
 				name,
 				base_weight: model.parameters.intercept_value as u64 * 1_000,
+				base_weight_std_error: model.se.intercept_value as u64 * 1_000,
+				runs: runs as u32,
 				base_reads: result.reads,
 				base_repeat_reads: result.repeat_reads,
 				base_writes: result.writes,
 				base_repeat_writes: result.repeat_writes,
+				trie_nodes: result.trie_nodes,
+				storage_root_weight: (average(&result.storage_root_elapses) as u64).saturating_mul(1_000),
+				commit_weight: (average(&result.commit_elapses) as u64).saturating_mul(1_000),
+				committed_keys: result.committed_keys,
+				proof_size: extensions.proof_size,
+				events: extensions.events,
 			}
 		})
 		.collect();
 
+	scenarios
+		.into_iter()
+		.for_each(|scenario| print_scenario(&pkg_name, scenario));
+
 	println!();
 
-	if let Ok(json) = serde_json::to_string(&data) {
+	data
+}
+
+/// Write the bench data as JSON to stdout, read by `weight-gen`.
+fn write_json(data: &[BenchData]) {
+	if let Ok(json) = serde_json::to_string(data) {
 		let stdout = ::std::io::stdout();
 		let mut handle = stdout.lock();
 
@@ -76,3 +158,161 @@ pub fn handle(output: Vec<u8>) {
 		eprintln!("Could not write benchdata to JSON");
 	}
 }
+
+/// Print the changes of the benches from the `baseline`.
+#[cfg(feature = "cli")]
+fn print_comparison(baseline: &[BenchData], data: &[BenchData]) {
+	for bench in data {
+		let base = match baseline.iter().find(|base| base.name == bench.name) {
+			Some(base) => base,
+			None => {
+				println!(
+					"{} {:<60} not in baseline",
+					crate::colorize::yellow_bold("Change"),
+					cyan(&bench.name)
+				);
+				continue;
+			}
+		};
+		let change = if base.base_weight == 0 {
+			0.0
+		} else {
+			(bench.base_weight as f64 - base.base_weight as f64) * 100.0 / base.base_weight as f64
+		};
+		let change = format!("{:+.2}%", change);
+		let change = if bench.base_weight > base.base_weight {
+			crate::colorize::red_bold(&change)
+		} else {
+			green_bold(&change)
+		};
+		println!(
+			"{} {:<60} {:>20}  {:<20}  {:<20}",
+			crate::colorize::yellow_bold("Change"),
+			cyan(&bench.name),
+			change,
+			format!("reads: {:+}", i64::from(bench.base_reads) - i64::from(base.base_reads)),
+			format!(
+				"writes: {:+}",
+				i64::from(bench.base_writes) - i64::from(base.base_writes)
+			)
+		);
+	}
+}
+
+/// Print the aggregate block weight of a scenario. Scenarios are not
+/// written to the bench data, as they don't map to a single weight.
+fn print_scenario(pkg_name: &str, scenario: BenchResult) {
+	let name = String::from_utf8_lossy(&scenario.method).to_string();
+
+	let elapsed = average(&scenario.elapses);
+	let redundant = average(&scenario.redundant_elapses);
+	let (block_weight, extrinsic_weight) = scenario_weights(&scenario);
+
+	println!(
+		"{} {:<60} {:>20}  {:<20}  {:<20}",
+		green_bold("Scenario"),
+		cyan(&format!("{}::{}", pkg_name, name)),
+		green_bold(&format!("{:?}", Duration::from_nanos(elapsed as u64))),
+		format!("reads: {}", green_bold(&scenario.reads.to_string())),
+		format!("writes: {}", green_bold(&scenario.writes.to_string()))
+	);
+	println!(
+		"{:>9}extrinsics: {}  redundant: {}  block weight: {}  per extrinsic: {}",
+		"",
+		green_bold(&scenario.extrinsics.to_string()),
+		green_bold(&format!("{:?}", Duration::from_nanos(redundant as u64))),
+		green_bold(&block_weight.to_string()),
+		green_bold(&extrinsic_weight.to_string())
+	);
+	print_trie_nodes(&scenario.trie_nodes);
+	print_commit(&scenario);
+	print_extensions(&crate::bench_extensions(&scenario.method));
+}
+
+/// Print the optional metrics, if measured by the runtime.
+fn print_extensions(extensions: &BenchExtensions) {
+	let metrics: Vec<String> = [("proof size", extensions.proof_size), ("events", extensions.events)]
+		.iter()
+		.filter_map(|(name, value)| value.map(|value| format!("{}: {}", name, green_bold(&value.to_string()))))
+		.collect();
+	if !metrics.is_empty() {
+		println!("{:>9}{}", "", metrics.join("  "));
+	}
+}
+
+/// Print the time spent recomputing the storage root and committing the
+/// changes to the DB, which are not included in the bench time.
+fn print_commit(result: &BenchResult) {
+	if result.commit_elapses.is_empty() {
+		return;
+	}
+
+	println!(
+		"{:>9}storage root: {}  commit: {}  committed keys: {}",
+		"",
+		green_bold(&format!(
+			"{:?}",
+			Duration::from_nanos(average(&result.storage_root_elapses) as u64)
+		)),
+		green_bold(&format!(
+			"{:?}",
+			Duration::from_nanos(average(&result.commit_elapses) as u64)
+		)),
+		green_bold(&result.committed_keys.to_string())
+	);
+}
+
+/// Print the histogram of trie nodes touched per read and write, so deep
+/// accesses which are expensive in proofs are visible.
+fn print_trie_nodes(trie_nodes: &[(u32, u32, u32)]) {
+	if trie_nodes.is_empty() {
+		return;
+	}
+
+	let buckets: Vec<String> = trie_nodes
+		.iter()
+		.map(|(nodes, reads, writes)| format!("{} (r: {}, w: {})", green_bold(&nodes.to_string()), reads, writes))
+		.collect();
+	println!("{:>9}trie nodes: {}", "", buckets.join("  "));
+}
+
+/// The aggregate block weight of a scenario, from its average time and its
+/// reads and writes, and the average weight per extrinsic.
+fn scenario_weights(scenario: &BenchResult) -> (u64, u64) {
+	let block_weight = (average(&scenario.elapses) as u64)
+		.saturating_mul(1_000)
+		.saturating_add(RocksDbWeight::get().reads_writes(scenario.reads.into(), scenario.writes.into()));
+	(block_weight, block_weight / u64::from(scenario.extrinsics.max(1)))
+}
+
+fn average(elapses: &[u128]) -> u128 {
+	if elapses.is_empty() {
+		return 0;
+	}
+	elapses.iter().sum::<u128>() / elapses.len() as u128
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn scenario_weights_should_work() {
+		let scenario = BenchResult {
+			method: b"mixed_block".to_vec(),
+			elapses: vec![1_000, 3_000],
+			reads: 2,
+			writes: 1,
+			extrinsics: 4,
+			..Default::default()
+		};
+		// 2_000ns, 2 reads of 25_000_000 and 1 write of 100_000_000
+		assert_eq!(scenario_weights(&scenario), (152_000_000, 38_000_000));
+	}
+
+	#[test]
+	fn average_should_work() {
+		assert_eq!(average(&[]), 0);
+		assert_eq!(average(&[1, 2, 6]), 3);
+	}
+}
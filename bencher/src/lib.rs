@@ -13,20 +13,93 @@ pub extern crate sp_std;
 
 mod macros;
 
+/// Define benches with an attribute on an inline module, as an alternative
+/// to the `bench!` macro.
+///
+/// Each function annotated with `#[bench]` becomes a bench, other items of
+/// the module are kept as shared fixtures. A bench either takes the
+/// `Bencher` to configure, or has no inputs and its body is the bench block,
+/// configured by optional `name`, `genesis`, `setup`, `verify` and
+/// `scenario` arguments.
+///
+/// Create a file `src/benches.rs`:
+/// ```.ignore
+/// #![allow(dead_code)]
+///
+/// #[orml_bencher::benches(your_module::mock::Block)]
+/// mod benches {
+///     use orml_bencher::Bencher;
+///     use your_module::mock::YourModule;
+///
+///     // shared fixture
+///     fn setup_accounts() {
+///         YourModule::set_balance(ALICE, 1_000);
+///     }
+///
+///     fn check_accounts() {
+///         assert_eq!(YourModule::balance(ALICE), 0);
+///     }
+///
+///     #[bench(setup = setup_accounts, verify = check_accounts)]
+///     fn foo() {
+///         // foo must have macro `[orml_weight_meter::weight(..)]`
+///         YourModule::foo();
+///     }
+///
+///     fn populated() -> orml_bencher::BenchExt {
+///         orml_bencher::BenchExt::new().items(1_000, |i| (YourModule::item_key(i), i.encode()))
+///     }
+///
+///     #[bench(name = "bench_name", genesis = populated())]
+///     fn bar() {
+///         YourModule::bar();
+///     }
+///
+///     #[bench]
+///     fn mixed_block(b: &mut Bencher) {
+///         b.scenario(100).prepare(setup_accounts).bench(|| {
+///             for _ in 0..100 {
+///                 YourModule::foo();
+///             }
+///         });
+///     }
+/// }
+/// ```
+///
+/// The rest of the setup is the same as for `bench!`.
+pub use bencher_procedural::benches;
+
 #[cfg(feature = "std")]
 pub mod bench_runner;
 #[cfg(feature = "std")]
 pub mod build_wasm;
+#[cfg(feature = "cli")]
+pub mod cli;
 #[cfg(feature = "std")]
 mod colorize;
 #[cfg(feature = "std")]
 pub mod handler;
 #[cfg(feature = "std")]
 mod redundant_meter;
+#[cfg(feature = "std")]
+pub mod reporter;
+#[cfg(feature = "std")]
+mod trie_meter;
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, Output};
 use sp_std::prelude::{Box, Vec};
 
+/// Prefix of encoded versioned bench results, followed by the
+/// `BENCH_RESULT_VERSION` byte. Read as a compact length, it announces more
+/// results than fit in memory, so it is never the start of the unversioned
+/// results of runtimes built with an older bencher.
+pub const BENCH_RESULT_MAGIC: [u8; 4] = *b"ormb";
+
+/// Version of the SCALE layout of `BenchResult`, bumped whenever it changes.
+/// Version 1 is the unversioned layout, without the fields following
+/// `repeat_writes`.
+pub const BENCH_RESULT_VERSION: u8 = 2;
+
 #[derive(Encode, Decode, Default, Clone, PartialEq, Debug)]
 pub struct BenchResult {
 	pub method: Vec<u8>,
@@ -35,14 +108,194 @@ pub struct BenchResult {
 	pub repeat_reads: u32,
 	pub writes: u32,
 	pub repeat_writes: u32,
+	/// Number of extrinsics executed by a scenario, zero for a single method
+	/// bench.
+	pub extrinsics: u32,
+	/// Time spent in already benchmarked methods, only measured for
+	/// scenarios.
+	pub redundant_elapses: Vec<u128>,
+	/// Histogram of the trie nodes on the path of accessed keys, as
+	/// `(nodes, reads, writes)` with the number of keys read and written
+	/// touching that many nodes.
+	pub trie_nodes: Vec<(u32, u32, u32)>,
+	/// Time spent recomputing the storage root of the changes of the bench
+	/// block, not included in `elapses`.
+	pub storage_root_elapses: Vec<u128>,
+	/// Time spent committing the changes of the bench block to the DB, not
+	/// included in `elapses`.
+	pub commit_elapses: Vec<u128>,
+	/// Number of keys committed to the DB by the bench block, including
+	/// whitelisted keys.
+	pub committed_keys: u32,
+}
+
+/// `BenchResult` as encoded by runtimes built with an older bencher, before
+/// the layout was versioned.
+#[cfg(feature = "std")]
+#[derive(Decode)]
+struct BenchResultV1 {
+	method: Vec<u8>,
+	elapses: Vec<u128>,
+	reads: u32,
+	repeat_reads: u32,
+	writes: u32,
+	repeat_writes: u32,
+}
+
+#[cfg(feature = "std")]
+impl From<BenchResultV1> for BenchResult {
+	fn from(result: BenchResultV1) -> Self {
+		BenchResult {
+			method: result.method,
+			elapses: result.elapses,
+			reads: result.reads,
+			repeat_reads: result.repeat_reads,
+			writes: result.writes,
+			repeat_writes: result.repeat_writes,
+			..Default::default()
+		}
+	}
+}
+
+/// Bench results encoded with `BENCH_RESULT_MAGIC` and
+/// `BENCH_RESULT_VERSION` before them, returned by `run_benches`.
+pub struct Versioned<T>(pub T);
+
+impl<T: Encode> Encode for Versioned<T> {
+	fn encode_to<O: Output + ?Sized>(&self, dest: &mut O) {
+		dest.write(&BENCH_RESULT_MAGIC);
+		dest.push_byte(BENCH_RESULT_VERSION);
+		self.0.encode_to(dest);
+	}
+}
+
+#[cfg(feature = "std")]
+impl BenchResult {
+	/// Decode the results returned by `run_benches`, either versioned or
+	/// unversioned from a runtime built with an older bencher, the metrics
+	/// it doesn't measure left default.
+	pub fn decode_results(input: &[u8]) -> Result<Vec<BenchResult>, String> {
+		decode_versioned(input, |input| {
+			<Vec<BenchResultV1>>::decode(input).map(|results| results.into_iter().map(Into::into).collect())
+		})
+	}
+
+	/// Decode a result reported to `bencher::bench_finished`, see
+	/// `decode_results`.
+	pub fn decode_result(input: &[u8]) -> Result<BenchResult, String> {
+		decode_versioned(input, |input| BenchResultV1::decode(input).map(Into::into))
+	}
+}
+
+/// Decode `input` prefixed with `BENCH_RESULT_MAGIC` and the version, or with
+/// `legacy` if not prefixed.
+#[cfg(feature = "std")]
+fn decode_versioned<T: Decode>(
+	input: &[u8],
+	legacy: impl FnOnce(&mut &[u8]) -> Result<T, codec::Error>,
+) -> Result<T, String> {
+	match input.strip_prefix(&BENCH_RESULT_MAGIC[..]) {
+		Some(versioned) => {
+			let (version, mut rest) = versioned.split_first().ok_or("bench results without version")?;
+			if *version != BENCH_RESULT_VERSION {
+				return Err(format!(
+					"bench results of version {} are not supported by this bencher of version {}, upgrade orml-bencher",
+					version, BENCH_RESULT_VERSION
+				));
+			}
+			T::decode(&mut rest).map_err(|e| format!("could not decode bench results: {:?}", e))
+		}
+		None => legacy(&mut &input[..]).map_err(|e| format!("could not decode unversioned bench results: {:?}", e)),
+	}
+}
+
+/// Optional metrics of a bench, measured by version 2 of the bencher host
+/// functions, `None` if not measured.
+#[derive(Encode, Decode, Default, Clone, Copy, PartialEq, Debug)]
+pub struct BenchExtensions {
+	/// Estimated storage proof size of the bench block, the size of the keys
+	/// and values read, in bytes.
+	pub proof_size: Option<u32>,
+	/// Number of events deposited by the bench block.
+	pub events: Option<u32>,
+}
+
+impl BenchExtensions {
+	/// The largest metrics of `self` and `other`.
+	pub fn max(self, other: Self) -> Self {
+		BenchExtensions {
+			proof_size: self.proof_size.max(other.proof_size),
+			events: self.events.max(other.events),
+		}
+	}
+}
+
+/// Results of a run of the bench block, returned by version 2 of
+/// `bencher::finalized_results`.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Debug)]
+pub struct FinalizedResults {
+	pub elapsed: u128,
+	pub reads: u32,
+	pub repeat_reads: u32,
+	pub writes: u32,
+	pub repeat_writes: u32,
+	pub extensions: BenchExtensions,
+}
+
+/// Builder of the storage a bench runs against, seeded before the prepare
+/// block of each run, e.g. endowed accounts or maps pre-populated to a given
+/// size. Its storage access is not measured.
+#[derive(Default)]
+pub struct BenchExt {
+	storage: Vec<(Vec<u8>, Vec<u8>)>,
+	builders: Vec<Box<dyn Fn()>>,
+}
+
+impl BenchExt {
+	/// Empty storage
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Insert a raw storage item
+	pub fn storage(mut self, key: Vec<u8>, value: Vec<u8>) -> Self {
+		self.storage.push((key, value));
+		self
+	}
+
+	/// Insert `count` raw storage items generated by `item` from their
+	/// index, e.g. to pre-populate a map.
+	pub fn items(mut self, count: u32, item: impl Fn(u32) -> (Vec<u8>, Vec<u8>)) -> Self {
+		self.storage.extend((0..count).map(item));
+		self
+	}
+
+	/// Seed storage with `build`, e.g. to endow accounts through the
+	/// runtime modules.
+	pub fn execute_with(mut self, build: impl Fn() + 'static) -> Self {
+		self.builders.push(Box::new(build));
+		self
+	}
+
+	/// Write the storage
+	pub fn build(&self) {
+		for (key, value) in &self.storage {
+			sp_io::storage::set(key, value);
+		}
+		for build in &self.builders {
+			build();
+		}
+	}
 }
 
 pub struct Bencher {
 	pub name: Vec<u8>,
 	pub results: Vec<BenchResult>,
+	pub genesis: BenchExt,
 	pub prepare: Box<dyn Fn()>,
 	pub bench: Box<dyn Fn()>,
 	pub verify: Box<dyn Fn()>,
+	pub extrinsics: u32,
 }
 
 impl Default for Bencher {
@@ -50,9 +303,11 @@ impl Default for Bencher {
 		Bencher {
 			name: Vec::new(),
 			results: Vec::new(),
+			genesis: BenchExt::default(),
 			prepare: Box::new(|| {}),
 			bench: Box::new(|| {}),
 			verify: Box::new(|| {}),
+			extrinsics: 0,
 		}
 	}
 }
@@ -61,9 +316,11 @@ impl Bencher {
 	/// Reset name and blocks
 	pub fn reset(&mut self) {
 		self.name = Vec::new();
+		self.genesis = BenchExt::default();
 		self.prepare = Box::new(|| {});
 		self.bench = Box::new(|| {});
 		self.verify = Box::new(|| {});
+		self.extrinsics = 0;
 	}
 
 	/// Set bench name
@@ -72,6 +329,12 @@ impl Bencher {
 		self
 	}
 
+	/// Set the storage the bench runs against
+	pub fn genesis(&mut self, genesis: BenchExt) -> &mut Self {
+		self.genesis = genesis;
+		self
+	}
+
 	/// Set prepare block
 	pub fn prepare(&mut self, prepare: impl Fn() + 'static) -> &mut Self {
 		self.prepare = Box::new(prepare);
@@ -90,9 +353,23 @@ impl Bencher {
 		self
 	}
 
+	/// Mark bench as a block scenario executing `extrinsics` extrinsics.
+	///
+	/// Scenarios measure a mixed workload as a whole: the time and storage
+	/// access of already benchmarked methods is not subtracted, but reported
+	/// separately as redundant time, together with the aggregate block weight.
+	/// See `orml-bencher-test` for scenarios of vesting claims, oracle feeds
+	/// and token transfers.
+	pub fn scenario(&mut self, extrinsics: u32) -> &mut Self {
+		self.extrinsics = extrinsics;
+		self
+	}
+
 	/// Run benchmark for tests
 	#[cfg(feature = "std")]
 	pub fn run(&mut self) {
+		// Seed storage
+		self.genesis.build();
 		// Execute prepare block
 		(self.prepare)();
 		// Execute bench block
@@ -105,16 +382,25 @@ impl Bencher {
 	#[cfg(not(feature = "std"))]
 	pub fn run(&mut self) {
 		assert!(self.name.len() > 0, "bench name not defined");
+		if !bencher::bench_selected(self.name.clone()) {
+			return;
+		}
 		// Warm up the DB
 		frame_benchmarking::benchmarking::commit_db();
 		frame_benchmarking::benchmarking::wipe_db();
 
+		bencher::bench_started(self.name.clone());
+
 		let mut result = BenchResult {
 			method: self.name.clone(),
+			extrinsics: self.extrinsics,
 			..Default::default()
 		};
+		let mut extensions = BenchExtensions::default();
 
 		for _ in 0..50 {
+			// Seed storage, wiped with the DB after each run
+			self.genesis.build();
 			// Execute prepare block
 			(self.prepare)();
 
@@ -126,10 +412,41 @@ impl Bencher {
 			// Execute bench block
 			(self.bench)();
 			let end_time = frame_benchmarking::benchmarking::current_time();
+			// The commit reuses the storage root computed here, so both are measured
+			// apart.
+			sp_io::storage::root();
+			let root_time = frame_benchmarking::benchmarking::current_time();
 			frame_benchmarking::benchmarking::commit_db();
-
-			let (elapsed, reads, repeat_reads, writes, repeat_writes) =
-				bencher::finalized_results(end_time - start_time);
+			let commit_time = frame_benchmarking::benchmarking::current_time();
+			let committed_keys = frame_benchmarking::benchmarking::get_read_and_written_keys()
+				.into_iter()
+				.filter(|(_, _, writes, _)| *writes > 0)
+				.count() as u32;
+
+			let total_elapsed = end_time - start_time;
+			let total_counts = frame_benchmarking::benchmarking::read_write_count();
+
+			let FinalizedResults {
+				elapsed,
+				reads,
+				repeat_reads,
+				writes,
+				repeat_writes,
+				extensions: run_extensions,
+			} = FinalizedResults::decode(&mut &bencher::finalized_results(total_elapsed)[..])
+				.expect("bench host returns valid results");
+			extensions = extensions.max(run_extensions);
+			// measured after `finalized_results`, as copying the state reads all of it
+			let trie_nodes = bencher::trie_nodes();
+
+			// Scenarios keep the totals, and report the redundant time apart
+			let (elapsed, reads, repeat_reads, writes, repeat_writes) = if self.extrinsics > 0 {
+				result.redundant_elapses.push(total_elapsed - elapsed);
+				let (reads, repeat_reads, writes, repeat_writes) = total_counts;
+				(total_elapsed, reads, repeat_reads, writes, repeat_writes)
+			} else {
+				(elapsed, reads, repeat_reads, writes, repeat_writes)
+			};
 
 			// Execute verify block
 			(self.verify)();
@@ -138,12 +455,17 @@ impl Bencher {
 			frame_benchmarking::benchmarking::wipe_db();
 
 			result.elapses.push(elapsed);
+			result.trie_nodes = trie_nodes;
+			result.storage_root_elapses.push(root_time - end_time);
+			result.commit_elapses.push(commit_time - root_time);
+			result.committed_keys = sp_std::cmp::max(result.committed_keys, committed_keys);
 
 			result.reads = sp_std::cmp::max(result.reads, reads);
 			result.repeat_reads = sp_std::cmp::max(result.repeat_reads, repeat_reads);
 			result.writes = sp_std::cmp::max(result.writes, writes);
 			result.repeat_writes = sp_std::cmp::max(result.repeat_writes, repeat_writes);
 		}
+		bencher::bench_finished(Versioned(&result).encode(), extensions.encode());
 		self.results.push(result);
 	}
 }
@@ -151,8 +473,127 @@ impl Bencher {
 #[cfg(feature = "std")]
 thread_local! {
 	static REDUNDANT_METER: std::cell::RefCell<redundant_meter::RedundantMeter> = std::cell::RefCell::new(redundant_meter::RedundantMeter::default());
+	static EVENTS_AT_RESET: std::cell::Cell<u32> = std::cell::Cell::new(0);
+	static EXTENSIONS: std::cell::RefCell<std::collections::BTreeMap<Vec<u8>, BenchExtensions>> = Default::default();
+	static ACCESSED_KEYS: std::cell::RefCell<Option<Vec<(Vec<u8>, u32, u32)>>> = Default::default();
+}
+
+/// The keys read or written since the last reset of the read and write
+/// count, with their `(reads, writes)`, excluding whitelisted keys.
+#[cfg(feature = "std")]
+fn accessed_keys() -> Vec<(Vec<u8>, u32, u32)> {
+	frame_benchmarking::benchmarking::get_read_and_written_keys()
+		.into_iter()
+		.filter(|(_, _, _, whitelisted)| !whitelisted)
+		.map(|(key, reads, writes, _)| (key, reads, writes))
+		.collect()
+}
+
+/// Copy the committed state, with its child tries, to count the trie nodes
+/// on the path of keys.
+#[cfg(feature = "std")]
+fn copy_state() -> trie_meter::TrieCopy {
+	let mut top = Vec::new();
+	let mut children = Vec::new();
+	let mut next = sp_io::storage::next_key(&[]);
+	while let Some(key) = next {
+		next = sp_io::storage::next_key(&key);
+		if let Some(storage_key) = key.strip_prefix(sp_core::storage::well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX)
+		{
+			children.push((storage_key.to_vec(), copy_child_state(storage_key)));
+		}
+		if let Some(value) = sp_io::storage::get(&key) {
+			top.push((key, value));
+		}
+	}
+	trie_meter::TrieCopy::new(top, children)
+}
+
+#[cfg(feature = "std")]
+fn copy_child_state(storage_key: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)> {
+	let mut pairs = Vec::new();
+	let mut next = sp_io::default_child_storage::next_key(storage_key, &[]);
+	while let Some(key) = next {
+		next = sp_io::default_child_storage::next_key(storage_key, &key);
+		if let Some(value) = sp_io::default_child_storage::get(storage_key, &key) {
+			pairs.push((key, value));
+		}
+	}
+	pairs
+}
+
+/// The read and write counts of the bench block, excluding already
+/// benchmarked methods.
+#[cfg(feature = "std")]
+fn finalized_counts(elapsed: u128) -> (u128, u32, u32, u32, u32) {
+	let (reads, repeat_reads, writes, repeat_writes) = frame_benchmarking::benchmarking::read_write_count();
+
+	let (redundant_elapsed, redundant_reads, redundant_repeat_reads, redundant_writes, redundant_repeat_writes) =
+		REDUNDANT_METER.with(|x| x.borrow_mut().take_results());
+
+	let elapsed = elapsed - redundant_elapsed;
+	let reads = reads - redundant_reads;
+	let repeat_reads = repeat_reads - redundant_repeat_reads;
+	let writes = writes - redundant_writes;
+	let repeat_writes = repeat_writes - redundant_repeat_writes;
+
+	(elapsed, reads, repeat_reads, writes, repeat_writes)
+}
+
+/// Number of events deposited since the genesis of the bench storage.
+#[cfg(feature = "std")]
+fn event_count() -> u32 {
+	let key = [
+		sp_io::hashing::twox_128(b"System"),
+		sp_io::hashing::twox_128(b"EventCount"),
+	]
+	.concat();
+	sp_io::storage::get(&key)
+		.and_then(|count| u32::decode(&mut &count[..]).ok())
+		.unwrap_or_default()
+}
+
+/// Measure the optional metrics of the bench block, which accessed the
+/// `accesses` keys.
+#[cfg(feature = "std")]
+fn measure_extensions(accesses: &[(Vec<u8>, u32, u32)]) -> BenchExtensions {
+	let proof_size = accesses
+		.iter()
+		.filter(|(_, reads, _)| *reads > 0)
+		.map(|(key, _, _)| key.len() + sp_io::storage::get(key).map_or(0, |value| value.len()))
+		.sum::<usize>();
+
+	BenchExtensions {
+		proof_size: Some(proof_size.min(u32::MAX as usize) as u32),
+		events: Some(event_count().saturating_sub(EVENTS_AT_RESET.with(|x| x.get()))),
+	}
+}
+
+/// Whether the bench `name` is selected to run by the command line options.
+#[cfg(all(feature = "std", feature = "cli"))]
+fn is_selected(name: &[u8]) -> bool {
+	cli::bench_selected(&String::from_utf8_lossy(name))
 }
 
+/// Whether the bench `name` is selected to run, all of them without the
+/// `cli` feature.
+#[cfg(all(feature = "std", not(feature = "cli")))]
+fn is_selected(_name: &[u8]) -> bool {
+	true
+}
+
+/// The optional metrics reported by the bench `method`, default if reported
+/// by an older runtime.
+#[cfg(feature = "std")]
+pub(crate) fn bench_extensions(method: &[u8]) -> BenchExtensions {
+	EXTENSIONS.with(|x| x.borrow().get(method).copied().unwrap_or_default())
+}
+
+/// Host functions of the bencher.
+///
+/// Changes are added as new versions of the host functions, keeping the
+/// older versions, so a newer bench host still runs the benches of older
+/// runtimes, only without the newer optional metrics.
 #[sp_runtime_interface::runtime_interface]
 pub trait Bencher {
 	fn panic(str: Vec<u8>) {
@@ -172,23 +613,114 @@ pub trait Bencher {
 	}
 
 	fn finalized_results(elapsed: u128) -> (u128, u32, u32, u32, u32) {
-		let (reads, repeat_reads, writes, repeat_writes) = frame_benchmarking::benchmarking::read_write_count();
+		finalized_counts(elapsed)
+	}
+
+	/// Same as version 1, with the optional metrics of the bench block, as
+	/// encoded `FinalizedResults`.
+	#[version(2)]
+	fn finalized_results(elapsed: u128) -> Vec<u8> {
+		// the counts and the accessed keys are taken before the optional metrics,
+		// whose storage access is tracked too
+		let (elapsed, reads, repeat_reads, writes, repeat_writes) = finalized_counts(elapsed);
+		let accesses = accessed_keys();
+		let extensions = measure_extensions(&accesses);
+		ACCESSED_KEYS.with(|x| *x.borrow_mut() = Some(accesses));
+		FinalizedResults {
+			elapsed,
+			reads,
+			repeat_reads,
+			writes,
+			repeat_writes,
+			extensions,
+		}
+		.encode()
+	}
+
+	/// Histogram of the trie nodes on the path of keys read or written since
+	/// the last reset of the read and write count, as taken by
+	/// `finalized_results`. Counted from the storage proofs of the keys
+	/// against a copy of the committed state, including its child tries and
+	/// the storage access of already benchmarked methods.
+	fn trie_nodes() -> Vec<(u32, u32, u32)> {
+		let accesses = ACCESSED_KEYS
+			.with(|x| x.borrow_mut().take())
+			.unwrap_or_else(accessed_keys);
+		trie_meter::histogram(&copy_state(), accesses)
+	}
+
+	/// Whether the bench `name` should run, see `cli`.
+	fn bench_selected(name: Vec<u8>) -> bool {
+		is_selected(&name)
+	}
 
-		let (redundant_elapsed, redundant_reads, redundant_repeat_reads, redundant_writes, redundant_repeat_writes) =
-			REDUNDANT_METER.with(|x| x.borrow_mut().take_results());
+	/// Report a bench started, see `reporter`.
+	fn bench_started(name: Vec<u8>) {
+		reporter::started(name);
+	}
 
-		let elapsed = elapsed - redundant_elapsed;
-		let reads = reads - redundant_reads;
-		let repeat_reads = repeat_reads - redundant_repeat_reads;
-		let writes = writes - redundant_writes;
-		let repeat_writes = repeat_writes - redundant_repeat_writes;
+	/// Report a bench finished with the encoded `BenchResult`, versioned or
+	/// not, see `reporter`.
+	fn bench_finished(result: Vec<u8>) {
+		reporter::finished(result, BenchExtensions::default());
+	}
 
-		(elapsed, reads, repeat_reads, writes, repeat_writes)
+	/// Same as version 1, with the encoded `BenchExtensions` of the bench.
+	#[version(2)]
+	fn bench_finished(result: Vec<u8>, extensions: Vec<u8>) {
+		let extensions = BenchExtensions::decode(&mut &extensions[..]).unwrap_or_default();
+		if let Ok(BenchResult { method, .. }) = BenchResult::decode_result(&result) {
+			EXTENSIONS.with(|x| x.borrow_mut().insert(method, extensions));
+		}
+		reporter::finished(result, extensions);
 	}
 
 	fn reset() {
 		REDUNDANT_METER.with(|x| {
 			x.borrow_mut().reset();
 		});
+		EVENTS_AT_RESET.with(|x| x.set(event_count()));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn versioned_results_should_decode() {
+		let result = BenchResult {
+			method: b"transfer".to_vec(),
+			elapses: vec![100],
+			reads: 2,
+			writes: 1,
+			committed_keys: 3,
+			..Default::default()
+		};
+		let encoded = Versioned(vec![result.clone()]).encode();
+		assert!(encoded.starts_with(&BENCH_RESULT_MAGIC));
+		assert_eq!(BenchResult::decode_results(&encoded), Ok(vec![result.clone()]));
+		assert_eq!(BenchResult::decode_result(&Versioned(&result).encode()), Ok(result));
+
+		let mut unsupported = BENCH_RESULT_MAGIC.to_vec();
+		unsupported.push(BENCH_RESULT_VERSION + 1);
+		assert!(BenchResult::decode_results(&unsupported).is_err());
+	}
+
+	#[test]
+	fn unversioned_results_should_decode() {
+		// the layout of runtimes built with an older bencher
+		let legacy = vec![(b"transfer".to_vec(), vec![100u128], 2u32, 1u32, 1u32, 0u32)].encode();
+		assert_eq!(
+			BenchResult::decode_results(&legacy),
+			Ok(vec![BenchResult {
+				method: b"transfer".to_vec(),
+				elapses: vec![100],
+				reads: 2,
+				repeat_reads: 1,
+				writes: 1,
+				..Default::default()
+			}])
+		);
 	}
 }
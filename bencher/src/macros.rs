@@ -26,7 +26,7 @@
 /// ```.ignore
 /// #![allow(dead_code)]
 ///
-/// use orml_bencher::{Bencher, bench};
+/// use orml_bencher::{BenchExt, Bencher, bench};
 /// use your_module::mock::{Block, YourModule};
 ///
 /// fn foo(b: &mut Bencher) {
@@ -42,6 +42,19 @@
 ///     });
 /// }
 ///
+/// fn baz(b: &mut Bencher) {
+///     // optional. storage seeded before the prepare block of each run, so
+///     // the bench runs against non-empty state
+///     b.genesis(
+///         BenchExt::new()
+///             .execute_with(|| YourModule::set_balance(ALICE, 1_000))
+///             .items(1_000, |i| (YourModule::item_key(i), i.encode())),
+///     )
+///     .bench(|| {
+///         YourModule::baz();
+///     });
+/// }
+///
 /// fn bar(b: &mut Bencher) {
 ///     // optional. method name is used by default i.e: `bar`
 ///     b.name("bench_name")
@@ -51,7 +64,21 @@
 ///     });
 /// }
 ///
-/// bench!(Block, foo, bar); // Tests are generated automatically
+/// fn mixed_block(b: &mut Bencher) {
+///     // optional. measure a mixed workload as a whole, e.g. vesting claims,
+///     // oracle feeds and token transfers in one block. Reports the aggregate
+///     // block weight and the redundant time of already benchmarked methods.
+///     b.scenario(300)
+///     .bench(|| {
+///         for i in 0..100 {
+///             YourModule::foo();
+///             OtherModule::baz(i);
+///             YourModule::bar();
+///         }
+///     });
+/// }
+///
+/// bench!(Block, foo, baz, bar, mixed_block); // Tests are generated automatically
 /// ```
 /// Update `src/lib.rs`:
 /// ```.ignore
@@ -64,6 +91,18 @@
 /// ```
 ///
 /// Run benchmarking: `cargo bench --features=bench`
+///
+/// Progress could be followed live as newline-delimited JSON events, by
+/// setting `ORML_BENCHER_REPORT` to `stdout` or to `unix:<path>` of a UNIX
+/// socket: `ORML_BENCHER_REPORT=unix:/tmp/bench.sock cargo bench
+/// --features=bench`
+///
+/// With the `cli` feature of `orml-bencher`, a single bench could be run or
+/// compared with a saved baseline, see `cli`: `cargo bench --features=bench
+/// -- --filter bench_name --baseline main`
+///
+/// Benches could also be defined with the `#[orml_bencher::benches]`
+/// attribute.
 #[macro_export]
 macro_rules! bench {
     (
@@ -72,7 +111,7 @@ macro_rules! bench {
     ) => {
         #[cfg(feature = "bench")]
         $crate::sp_core::wasm_export_functions! {
-            fn run_benches() -> $crate::sp_std::vec::Vec<$crate::BenchResult> {
+            fn run_benches() -> $crate::Versioned<$crate::sp_std::vec::Vec<$crate::BenchResult>> {
                 let mut bencher = $crate::Bencher::default();
                 $(
                     bencher.reset();
@@ -83,7 +122,7 @@ macro_rules! bench {
                     }
                     bencher.run();
                 )+
-                bencher.results
+                $crate::Versioned(bencher.results)
             }
         }
 
@@ -100,12 +139,7 @@ macro_rules! bench {
 
         #[cfg(all(feature = "std", feature = "bench"))]
         pub fn main() -> std::io::Result<()> {
-            let wasm = $crate::build_wasm::build()?;
-            match $crate::bench_runner::run::<$block>(wasm) {
-                Ok(output) => { $crate::handler::handle(output); }
-                Err(e) => { eprintln!("{:?}", e); }
-            };
-            Ok(())
+            $crate::bench_runner::main::<$block>()
         }
 
         // Tests
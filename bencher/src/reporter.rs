@@ -0,0 +1,170 @@
+//! Live progress of benches, as newline-delimited JSON events.
+//!
+//! Enabled by the `ORML_BENCHER_REPORT` environment variable, `stdout` to
+//! write the events to stdout, or `unix:<path>` to stream them to a UNIX
+//! socket, e.g. for a collector feeding a dashboard.
+
+use crate::{BenchExtensions, BenchResult};
+use serde::Serialize;
+use std::{cell::RefCell, io::Write, time::Instant};
+
+/// Environment variable enabling the reporter.
+const ORML_BENCHER_REPORT: &str = "ORML_BENCHER_REPORT";
+
+/// A progress event.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ProgressEvent {
+	/// A bench started.
+	Started { name: String },
+	/// A bench finished.
+	Finished {
+		name: String,
+		/// Wall time of all the runs of the bench, including prepare and
+		/// verify blocks, in nanoseconds.
+		duration: u128,
+		/// Number of runs.
+		runs: u32,
+		/// Average time of the bench block, in nanoseconds.
+		average: u128,
+		reads: u32,
+		repeat_reads: u32,
+		writes: u32,
+		repeat_writes: u32,
+		committed_keys: u32,
+		/// See `BenchExtensions`, omitted if not measured.
+		#[serde(skip_serializing_if = "Option::is_none")]
+		proof_size: Option<u32>,
+		#[serde(skip_serializing_if = "Option::is_none")]
+		events: Option<u32>,
+	},
+}
+
+struct Reporter {
+	output: Box<dyn Write>,
+	started_at: Option<Instant>,
+}
+
+thread_local! {
+	static REPORTER: RefCell<Option<Reporter>> = RefCell::new(open());
+}
+
+/// Open the output configured by `ORML_BENCHER_REPORT`, if any.
+fn open() -> Option<Reporter> {
+	let target = std::env::var(ORML_BENCHER_REPORT).ok()?;
+	let output: Box<dyn Write> = match target.as_str() {
+		"stdout" => Box::new(std::io::stdout()),
+		#[cfg(unix)]
+		target if target.starts_with("unix:") => match std::os::unix::net::UnixStream::connect(&target["unix:".len()..]) {
+			Ok(stream) => Box::new(stream),
+			Err(e) => {
+				eprintln!("Could not connect the bench reporter to {}: {:?}", target, e);
+				return None;
+			}
+		},
+		target => {
+			eprintln!("Unsupported bench reporter output: {}", target);
+			return None;
+		}
+	};
+	Some(Reporter {
+		output,
+		started_at: None,
+	})
+}
+
+fn emit(reporter: &mut Reporter, event: &ProgressEvent) {
+	if let Ok(mut line) = serde_json::to_vec(event) {
+		line.push(b'\n');
+		// progress is best effort, it never fails the benches
+		let _ = reporter.output.write_all(&line).and_then(|_| reporter.output.flush());
+	}
+}
+
+/// Report a bench named `name` started.
+pub fn started(name: Vec<u8>) {
+	REPORTER.with(|reporter| {
+		if let Some(reporter) = reporter.borrow_mut().as_mut() {
+			reporter.started_at = Some(Instant::now());
+			let name = String::from_utf8_lossy(&name).to_string();
+			emit(reporter, &ProgressEvent::Started { name });
+		}
+	});
+}
+
+/// Report a bench finished with the encoded `BenchResult` and its
+/// `extensions`.
+pub fn finished(result: Vec<u8>, extensions: BenchExtensions) {
+	REPORTER.with(|reporter| {
+		if let Some(reporter) = reporter.borrow_mut().as_mut() {
+			let duration = reporter
+				.started_at
+				.take()
+				.map(|started_at| started_at.elapsed().as_nanos())
+				.unwrap_or_default();
+			if let Ok(result) = BenchResult::decode_result(&result) {
+				emit(reporter, &finished_event(result, extensions, duration));
+			}
+		}
+	});
+}
+
+fn finished_event(result: BenchResult, extensions: BenchExtensions, duration: u128) -> ProgressEvent {
+	let runs = result.elapses.len() as u32;
+	let average = if runs > 0 {
+		result.elapses.iter().sum::<u128>() / u128::from(runs)
+	} else {
+		0
+	};
+	ProgressEvent::Finished {
+		name: String::from_utf8_lossy(&result.method).to_string(),
+		duration,
+		runs,
+		average,
+		reads: result.reads,
+		repeat_reads: result.repeat_reads,
+		writes: result.writes,
+		repeat_writes: result.repeat_writes,
+		committed_keys: result.committed_keys,
+		proof_size: extensions.proof_size,
+		events: extensions.events,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn finished_event_serializes_as_json_line() {
+		let result = BenchResult {
+			method: b"transfer".to_vec(),
+			elapses: vec![100, 200],
+			reads: 2,
+			writes: 1,
+			committed_keys: 3,
+			..Default::default()
+		};
+		let event = finished_event(result.clone(), Default::default(), 1_000);
+		assert_eq!(
+			serde_json::to_string(&event).unwrap(),
+			r#"{"event":"finished","name":"transfer","duration":1000,"runs":2,"average":150,"reads":2,"repeat_reads":0,"writes":1,"repeat_writes":0,"committed_keys":3}"#
+		);
+		assert_eq!(
+			serde_json::to_string(&ProgressEvent::Started {
+				name: "transfer".into()
+			})
+			.unwrap(),
+			r#"{"event":"started","name":"transfer"}"#
+		);
+
+		let extensions = BenchExtensions {
+			proof_size: Some(128),
+			events: None,
+		};
+		assert_eq!(
+			serde_json::to_string(&finished_event(result, extensions, 1_000)).unwrap(),
+			r#"{"event":"finished","name":"transfer","duration":1000,"runs":2,"average":150,"reads":2,"repeat_reads":0,"writes":1,"repeat_writes":0,"committed_keys":3,"proof_size":128}"#
+		);
+	}
+}
@@ -0,0 +1,142 @@
+use sp_core::{
+	storage::{well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX, ChildInfo},
+	Blake2Hasher,
+};
+use sp_state_machine::{prove_child_read_on_trie_backend, prove_read_on_trie_backend, InMemoryBackend};
+use std::collections::BTreeSet;
+
+/// A copy of the state trie and its child tries, used to count the trie
+/// nodes on the path of keys from their storage proofs.
+///
+/// Values are kept as they are, since the nodes small enough are inlined in
+/// their parent and don't count as a node of the proof.
+pub struct TrieCopy {
+	backend: InMemoryBackend<Blake2Hasher>,
+	top_keys: BTreeSet<Vec<u8>>,
+	children: Vec<(ChildInfo, BTreeSet<Vec<u8>>)>,
+}
+
+impl TrieCopy {
+	/// Copy the `top` key-values, and the key-values of the `children` tries
+	/// by their storage key. The child trie roots of `top` are skipped, they
+	/// are computed from `children`.
+	pub fn new(top: Vec<(Vec<u8>, Vec<u8>)>, children: Vec<(Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>)>) -> Self {
+		let top: Vec<_> = top
+			.into_iter()
+			.filter(|(key, _)| !key.starts_with(DEFAULT_CHILD_STORAGE_KEY_PREFIX))
+			.collect();
+		let top_keys = top.iter().map(|(key, _)| key.clone()).collect();
+		let children: Vec<_> = children
+			.into_iter()
+			.map(|(storage_key, pairs)| (ChildInfo::new_default(&storage_key), pairs))
+			.collect();
+
+		let mut collections = vec![(None, collection(top))];
+		collections.extend(
+			children
+				.iter()
+				.map(|(child_info, pairs)| (Some(child_info.clone()), collection(pairs.clone()))),
+		);
+
+		TrieCopy {
+			backend: collections.into(),
+			top_keys,
+			children: children
+				.into_iter()
+				.map(|(child_info, pairs)| (child_info, pairs.into_iter().map(|(key, _)| key).collect()))
+				.collect(),
+		}
+	}
+
+	/// Count the trie nodes in the storage proof of `key`. A key of a child
+	/// trie also counts the nodes on the path of the child trie root. Keys not
+	/// found are proven missing from the top trie, counting the nodes up to
+	/// where the lookup stops.
+	pub fn path_nodes(&self, key: &[u8]) -> u32 {
+		let child = if self.top_keys.contains(key) {
+			None
+		} else {
+			self.children.iter().find(|(_, keys)| keys.contains(key))
+		};
+
+		let proof = match child {
+			Some((child_info, _)) => prove_child_read_on_trie_backend(&self.backend, child_info, &[key]),
+			None => prove_read_on_trie_backend(&self.backend, &[key]),
+		}
+		.expect("in memory backend doesn't fail");
+
+		proof.into_nodes().len() as u32
+	}
+}
+
+fn collection(pairs: Vec<(Vec<u8>, Vec<u8>)>) -> Vec<(Vec<u8>, Option<Vec<u8>>)> {
+	pairs.into_iter().map(|(key, value)| (key, Some(value))).collect()
+}
+
+/// Build a histogram of the trie nodes touched per key, from keys and their
+/// `(reads, writes)`. Returns `(nodes, reads, writes)` sorted by `nodes`,
+/// with `reads` and `writes` the number of keys read or written with that
+/// many nodes on their path.
+pub fn histogram(trie: &TrieCopy, accesses: Vec<(Vec<u8>, u32, u32)>) -> Vec<(u32, u32, u32)> {
+	let mut buckets = std::collections::BTreeMap::<u32, (u32, u32)>::new();
+	for (key, reads, writes) in accesses {
+		let bucket = buckets.entry(trie.path_nodes(&key)).or_default();
+		if reads > 0 {
+			bucket.0 += 1;
+		}
+		if writes > 0 {
+			bucket.1 += 1;
+		}
+	}
+
+	buckets
+		.into_iter()
+		.map(|(nodes, (reads, writes))| (nodes, reads, writes))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// large enough for the leaves not to be inlined in their parent
+	fn pairs(keys: &[&[u8]]) -> Vec<(Vec<u8>, Vec<u8>)> {
+		keys.iter().map(|key| (key.to_vec(), vec![0u8; 32])).collect()
+	}
+
+	#[test]
+	fn path_nodes_should_work() {
+		assert_eq!(TrieCopy::new(pairs(&[&[0x12]]), vec![]).path_nodes(&[0x12]), 1);
+
+		// root branch at nibble 1, leaves below
+		let trie = TrieCopy::new(pairs(&[&[0x10], &[0x12], &[0x13, 0x45], &[0x13, 0x46]]), vec![]);
+		assert_eq!(trie.path_nodes(&[0x10]), 2);
+		// branch at nibble 1, branch at nibble 3, leaf
+		assert_eq!(trie.path_nodes(&[0x13, 0x45]), 3);
+		// missing child of the root branch
+		assert_eq!(trie.path_nodes(&[0x14]), 1);
+		// diverges from the partial key of the root branch
+		assert_eq!(trie.path_nodes(&[0x20]), 1);
+	}
+
+	#[test]
+	fn path_nodes_should_count_child_tries() {
+		let trie = TrieCopy::new(pairs(&[&[0x10]]), vec![(b"child".to_vec(), pairs(&[&[0x20], &[0x22]]))]);
+		// root branch, leaf
+		assert_eq!(trie.path_nodes(&[0x10]), 2);
+		// root branch, leaf of the child trie root, child root branch, leaf
+		assert_eq!(trie.path_nodes(&[0x22]), 4);
+	}
+
+	#[test]
+	fn histogram_should_work() {
+		let trie = TrieCopy::new(pairs(&[&[0x13, 0x46], &[0x10], &[0x13, 0x45], &[0x12]]), vec![]);
+		assert_eq!(
+			histogram(
+				&trie,
+				vec![(vec![0x10], 2, 0), (vec![0x12], 1, 1), (vec![0x13, 0x45], 0, 1)]
+			),
+			vec![(2, 2, 1), (3, 0, 1)]
+		);
+	}
+}
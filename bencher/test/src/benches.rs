@@ -0,0 +1,119 @@
+#![allow(dead_code)]
+
+use frame_support::assert_ok;
+use orml_bencher::{bench, BenchExt, Bencher};
+use orml_bencher_test::mock::{
+	AccountId, Balance, Block, Oracle, Origin, System, Tokens, Vesting, DOT, NATIVE, ORACLE_MEMBERS,
+};
+use orml_traits::MultiCurrency;
+use orml_vesting::VestingSchedule;
+use sp_std::vec;
+
+/// The number of vesting claims in a block.
+const CLAIMS: u32 = 100;
+/// The number of token transfers in a block.
+const TRANSFERS: u32 = 100;
+/// The block the benches run at, half-way of the vesting schedules.
+const BENCH_BLOCK: u64 = 5;
+
+fn claimer(i: u32) -> AccountId {
+	1_000 + AccountId::from(i)
+}
+
+fn sender(i: u32) -> AccountId {
+	2_000 + AccountId::from(i)
+}
+
+fn recipient(i: u32) -> AccountId {
+	3_000 + AccountId::from(i)
+}
+
+/// Claimers with a vesting schedule, and endowed senders.
+fn genesis() -> BenchExt {
+	BenchExt::new().execute_with(|| {
+		for i in 0..CLAIMS {
+			assert_ok!(Tokens::deposit(NATIVE, &claimer(i), 1_000));
+			assert_ok!(Vesting::update_vesting_schedules(
+				Origin::root(),
+				claimer(i),
+				vec![VestingSchedule {
+					start: 0,
+					period: 1,
+					period_count: 10,
+					per_period: 100,
+				}],
+			));
+		}
+		for i in 0..TRANSFERS {
+			assert_ok!(Tokens::deposit(DOT, &sender(i), 1_000));
+		}
+	})
+}
+
+fn prepare() {
+	System::set_block_number(BENCH_BLOCK);
+}
+
+fn claim_vesting() {
+	for i in 0..CLAIMS {
+		assert_ok!(Vesting::claim(Origin::signed(claimer(i))));
+	}
+}
+
+fn feed_oracle() {
+	for member in 0..ORACLE_MEMBERS {
+		assert_ok!(Oracle::feed_values(
+			Origin::signed(AccountId::from(member)),
+			vec![(0, 1_000 + u128::from(member))]
+		));
+	}
+}
+
+fn transfer_tokens() {
+	for i in 0..TRANSFERS {
+		assert_ok!(Tokens::transfer(Origin::signed(sender(i)), recipient(i), DOT, 100));
+	}
+}
+
+fn vesting_claims(b: &mut Bencher) {
+	b.scenario(CLAIMS)
+		.genesis(genesis())
+		.prepare(prepare)
+		.bench(claim_vesting)
+		.verify(|| {
+			assert_eq!(Vesting::total_scheduled(), 1_000 * Balance::from(CLAIMS));
+		});
+}
+
+fn oracle_feeds(b: &mut Bencher) {
+	b.scenario(ORACLE_MEMBERS)
+		.prepare(prepare)
+		.bench(feed_oracle)
+		.verify(|| {
+			assert!(Oracle::get(&0).is_some());
+		});
+}
+
+fn token_transfers(b: &mut Bencher) {
+	b.scenario(TRANSFERS)
+		.genesis(genesis())
+		.prepare(prepare)
+		.bench(transfer_tokens)
+		.verify(|| {
+			assert_eq!(Tokens::free_balance(DOT, &recipient(0)), 100);
+		});
+}
+
+/// All of the above in one block.
+fn mixed_block(b: &mut Bencher) {
+	b.scenario(CLAIMS + ORACLE_MEMBERS + TRANSFERS)
+		.genesis(genesis())
+		.prepare(prepare)
+		.bench(|| {
+			claim_vesting();
+			feed_oracle();
+			transfer_tokens();
+		});
+}
+
+bench!(Block, vesting_claims, oracle_feeds, token_transfers, mixed_block);
@@ -0,0 +1,17 @@
+//! # Bencher Test
+//!
+//! Block scenario benches of orml modules, measuring mixed workloads of
+//! vesting claims, oracle feeds and token transfers as a whole, to validate
+//! the block fullness assumptions of chains.
+//!
+//! Run the benches: `cargo bench --features=bench`
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+#[cfg(any(feature = "bench", test))]
+pub mod benches;
+#[cfg(any(feature = "bench", test))]
+pub mod mock;
+
+extern crate self as orml_bencher_test;
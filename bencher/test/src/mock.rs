@@ -0,0 +1,173 @@
+//! Mock runtime of the benches, compiled into wasm to run them.
+
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{Everything, Nothing, SortedMembers, Time},
+};
+use frame_system::EnsureRoot;
+use orml_traits::parameter_type_with_key;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	Perbill,
+};
+use sp_std::prelude::*;
+
+pub type AccountId = u64;
+pub type BlockNumber = u64;
+pub type Balance = u128;
+pub type CurrencyId = u32;
+pub type OracleKey = u32;
+pub type OracleValue = u128;
+
+pub const NATIVE: CurrencyId = 0;
+pub const DOT: CurrencyId = 1;
+
+/// The number of oracle members, each feeding once per block.
+pub const ORACLE_MEMBERS: u32 = 100;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = Everything;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		1
+	};
+}
+
+parameter_types! {
+	pub const MaxLocks: u32 = 10;
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = i128;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+	type OnSlash = ();
+	type OnDeposit = ();
+	type OnTransfer = ();
+	type MaxLocks = MaxLocks;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = Nothing;
+	type OnNewTokenAccount = ();
+	type OnKilledTokenAccount = ();
+	type TransferFee = ();
+	type TransferFeeExemptions = Nothing;
+	type OnLockChanged = ();
+}
+
+parameter_types! {
+	pub const GetNativeCurrencyId: CurrencyId = NATIVE;
+	pub const MinVestedTransfer: Balance = 1;
+	pub const MaxVestingSchedules: u32 = 2;
+	pub const MaxBatchSize: u32 = 3;
+}
+
+impl orml_vesting::Config for Runtime {
+	type Event = Event;
+	type Currency = orml_tokens::CurrencyAdapter<Runtime, GetNativeCurrencyId>;
+	type MinVestedTransfer = MinVestedTransfer;
+	type VestedTransferOrigin = frame_system::EnsureSigned<AccountId>;
+	type ScheduleTransferOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = ();
+	type MaxVestingSchedules = MaxVestingSchedules;
+	type BlockNumberProvider = System;
+	type MaxBatchSize = MaxBatchSize;
+}
+
+/// The block number as the time of the oracle values.
+pub struct BlockTime;
+impl Time for BlockTime {
+	type Moment = BlockNumber;
+
+	fn now() -> Self::Moment {
+		System::block_number()
+	}
+}
+
+pub struct OracleMembers;
+impl SortedMembers<AccountId> for OracleMembers {
+	fn sorted_members() -> Vec<AccountId> {
+		(0..ORACLE_MEMBERS as AccountId).collect()
+	}
+}
+
+parameter_types! {
+	pub const MinimumCount: u32 = 3;
+	pub const ExpiresIn: BlockNumber = 600;
+	pub const RootOperatorAccountId: AccountId = AccountId::max_value();
+	pub const MaxHasDispatchedSize: u32 = ORACLE_MEMBERS;
+	pub const RoundPeriod: BlockNumber = 0;
+	pub const Quorum: Perbill = Perbill::from_percent(100);
+	pub const MaxRoundParticipants: u32 = ORACLE_MEMBERS;
+	pub const MaxRoundTimeouts: u32 = 2;
+	pub const MaxRoundRechecks: u32 = 1;
+}
+
+impl orml_oracle::Config for Runtime {
+	type Event = Event;
+	type OnNewData = ();
+	type CombineData = orml_oracle::DefaultCombineData<Runtime, MinimumCount, ExpiresIn>;
+	type Time = BlockTime;
+	type OracleKey = OracleKey;
+	type OracleValue = OracleValue;
+	type RootOperatorAccountId = RootOperatorAccountId;
+	type Members = OracleMembers;
+	type WeightInfo = ();
+	type MaxHasDispatchedSize = MaxHasDispatchedSize;
+	type RoundPeriod = RoundPeriod;
+	type Quorum = Quorum;
+	type MaxRoundParticipants = MaxRoundParticipants;
+	type MaxRoundTimeouts = MaxRoundTimeouts;
+	type MaxRoundRechecks = MaxRoundRechecks;
+	type ValueChange = orml_oracle::UnsignedChange;
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+pub type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
+		Vesting: orml_vesting::{Pallet, Storage, Call, Event<T>, Config<T>},
+		Oracle: orml_oracle::{Pallet, Storage, Call, Event<T>},
+	}
+);
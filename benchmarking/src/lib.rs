@@ -3,8 +3,13 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod registry;
+#[cfg(feature = "std")]
+pub mod snapshot;
 mod tests;
 
+pub use registry::{BenchmarkProvider, BenchmarkRegistry, PalletBenchmarks, RegisterBenchmarks};
+
 pub use frame_benchmarking::{
 	benchmarking, whitelisted_caller, BenchmarkBatch, BenchmarkConfig, BenchmarkError, BenchmarkList,
 	BenchmarkMetadata, BenchmarkParameter, BenchmarkResult, Benchmarking, BenchmarkingSetup,
@@ -26,6 +31,16 @@ pub use sp_std::{self, boxed::Box, prelude::Vec, str, vec};
 #[doc(hidden)]
 pub use sp_storage::TrackedStorageKey;
 
+/// How the proof size of a benchmark is recorded.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PovMode {
+	/// Record the proof size measured while running the benchmark.
+	Measured,
+	/// Record a zero proof size, for benchmarks whose storage accesses are
+	/// accounted for elsewhere.
+	Ignored,
+}
+
 /// Whitelist the given account.
 #[macro_export]
 macro_rules! whitelist_account {
@@ -161,6 +176,19 @@ macro_rules! whitelist_account {
 ///
 /// These `verify` blocks will not affect your benchmark results!
 ///
+/// The proof size of a benchmark is measured by default. Annotate it with
+/// `#[pov_mode = Ignored]` to record a zero proof size instead, for example
+/// when the accessed storage is already accounted for by another benchmark:
+///
+/// ```ignore
+/// #[pov_mode = Ignored]
+/// on_initialize_noop {
+///     let x in 1 .. 100;
+/// }: { MyPallet::on_initialize(x.into()); }
+/// ```
+///
+/// The annotation can be combined with `#[extra]` in any order.
+///
 /// You can construct benchmark tests like so:
 ///
 /// ```ignore
@@ -188,6 +216,7 @@ macro_rules! runtime_benchmarks {
 			( )
 			( )
 			( )
+			( )
 			$( $rest )*
 		);
 	}
@@ -207,6 +236,7 @@ macro_rules! runtime_benchmarks_instance {
 			( )
 			( )
 			( )
+			( )
 			$( $rest )*
 		);
 	}
@@ -223,7 +253,9 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $names_pov:tt )* )
 		#[extra]
+		$( #[pov_mode = $mode:ident] )?
 		$name:ident
 		$( $rest:tt )*
 	) => {
@@ -234,6 +266,35 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra )* $name )
 			( $( $names_skip_meta )* )
+			( $( $names_pov )* )
+			$( #[pov_mode = $mode] )?
+			$name
+			$( $rest )*
+		}
+	};
+	// detect and extract `#[pov_mode = Mode]` tag:
+	(
+		{ $( $instance:ident )? }
+		$runtime:ident
+		$pallet:ident
+		( $( $names:tt )* )
+		( $( $names_extra:tt )* )
+		( $( $names_skip_meta:tt )* )
+		( $( $names_pov:tt )* )
+		#[pov_mode = $mode:ident]
+		$( #[$attr:ident] )*
+		$name:ident
+		$( $rest:tt )*
+	) => {
+		$crate::benchmarks_iter! {
+			{ $( $instance)? }
+			$runtime
+			$pallet
+			( $( $names )* )
+			( $( $names_extra )* )
+			( $( $names_skip_meta )* )
+			( $( $names_pov )* { $name $mode } )
+			$( #[$attr] )*
 			$name
 			$( $rest )*
 		}
@@ -246,6 +307,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* ) // This contains $( $( { $instance } )? $name:ident )*
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $names_pov:tt )* )
 		$name:ident { $( $code:tt )* }: _ $(< $origin_type:ty>)? ( $origin:expr $( , $arg:expr )* )
 		verify $postcode:block
 		$( $rest:tt )*
@@ -257,6 +319,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra )* )
 			( $( $names_skip_meta )* )
+			( $( $names_pov )* )
 			$name { $( $code )* }: $name $(< $origin_type >)? ( $origin $( , $arg )* )
 			verify $postcode
 			$( $rest )*
@@ -270,6 +333,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $names_pov:tt )* )
 		$name:ident { $( $code:tt )* }: $dispatch:ident $(< $origin_type:ty>)? ( $origin:expr $( , $arg:expr )* )
 		verify $postcode:block
 		$( $rest:tt )*
@@ -282,6 +346,7 @@ macro_rules! benchmarks_iter {
 				( $( $names )* )
 					( $( $names_extra )* )
 					( $( $names_skip_meta )* )
+					( $( $names_pov )* )
 					$name {
 						$( $code )*
 							let __call =
@@ -315,6 +380,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $names_pov:tt )* )
 		$name:ident { $( $code:tt )* }: $eval:block
 		verify $postcode:block
 		$( $rest:tt )*
@@ -345,6 +411,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* { $( $instance )? } $name )
 			( $( $names_extra )* )
 			( $( $names_skip_meta )* )
+			( $( $names_pov )* )
 			$( $rest )*
 		);
 	};
@@ -356,6 +423,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $names_pov:tt )* )
 	) => {
 		$crate::selected_benchmark!(
 			$runtime
@@ -370,6 +438,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra ),* )
 			( $( $names_skip_meta )* )
+			( $( $names_pov )* )
 		);
 	};
 	// add verify block to _() format
@@ -380,6 +449,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $names_pov:tt )* )
 		$name:ident { $( $code:tt )* }: _ ( $origin:expr $( , $arg:expr )* )
 		$( $rest:tt )*
 	) => {
@@ -390,6 +460,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra )* )
 			( $( $names_skip_meta )* )
+			( $( $names_pov )* )
 			$name { $( $code )* }: _ ( $origin $( , $arg )* )
 			verify { }
 			$( $rest )*
@@ -403,6 +474,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $names_pov:tt )* )
 		$name:ident { $( $code:tt )* }: $dispatch:ident ( $origin:expr $( , $arg:expr )* )
 		$( $rest:tt )*
 	) => {
@@ -413,6 +485,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra )* )
 			( $( $names_skip_meta )* )
+			( $( $names_pov )* )
 			$name { $( $code )* }: $dispatch ( $origin $( , $arg )* )
 			verify { }
 			$( $rest )*
@@ -426,6 +499,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $names_pov:tt )* )
 		$name:ident { $( $code:tt )* }: $eval:block
 		$( $rest:tt )*
 	) => {
@@ -436,6 +510,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra )* )
 			( $( $names_skip_meta )* )
+			( $( $names_pov )* )
 			$name { $( $code )* }: $eval
 			verify { }
 			$( $rest )*
@@ -657,7 +732,7 @@ macro_rules! benchmark_backend {
 // Every variant must implement [`BenchmarkingSetup`].
 //
 // ```nocompile
-// 
+//
 // struct Transfer;
 // impl BenchmarkingSetup for Transfer { ... }
 //
@@ -720,9 +795,21 @@ macro_rules! impl_benchmark {
 		( $( { $( $name_inst:ident )? } $name:ident )* )
 		( $( $name_extra:ident ),* )
 		( $( $name_skip_meta:ident ),* )
+		( $( { $pov_name:ident $pov_mode:ident } )* )
 	) => {
 		pub struct Benchmark;
 
+		impl Benchmark {
+			/// The PoV mode of the benchmark `name`, `Measured` if not annotated.
+			#[allow(unused)]
+			pub fn pov_mode(name: &[u8]) -> $crate::PovMode {
+				match $crate::str::from_utf8(name).unwrap_or_default() {
+					$( stringify!($pov_name) => $crate::PovMode::$pov_mode, )*
+					_ => $crate::PovMode::Measured,
+				}
+			}
+		}
+
 		impl $crate::Benchmarking for Benchmark {
 			fn benchmarks(extra: bool) -> $crate::Vec<$crate::BenchmarkMetadata> {
 				let mut all_names = $crate::vec![ $( stringify!($name).as_ref() ),* ];
@@ -846,7 +933,10 @@ macro_rules! impl_benchmark {
 						repeat_reads: read_write_count.1,
 						writes: read_write_count.2,
 						repeat_writes: read_write_count.3,
-						proof_size: diff_pov,
+						proof_size: match Self::pov_mode(extrinsic.as_bytes()) {
+							$crate::PovMode::Measured => diff_pov,
+							$crate::PovMode::Ignored => 0,
+						},
 						keys: read_and_written_keys,
 					});
 
@@ -1344,6 +1434,16 @@ macro_rules! add_benchmark {
 /// ```
 ///
 /// This should match what exists with the `add_benchmark!` macro.
+///
+/// Benchmarks published by other crates can be collected in a
+/// [`BenchmarkRegistry`] instead of being named here:
+///
+/// ```ignore
+/// let registry = BenchmarkRegistry::with::<(some_pallet::Benchmarks, other_pallet::Benchmarks)>();
+/// registry.list(&mut list, extra);
+/// // and in `dispatch_benchmark`
+/// registry.add(&config, &whitelist, &mut batches)?;
+/// ```
 
 #[macro_export]
 macro_rules! list_benchmark {
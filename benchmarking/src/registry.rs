@@ -0,0 +1,183 @@
+//! Registry of pallet benchmarks, for runtimes composed of pallets whose
+//! benchmarks are published in other crates.
+
+use crate::{
+	BenchmarkBatch, BenchmarkConfig, BenchmarkError, BenchmarkList, BenchmarkMetadata, BenchmarkParameter,
+	BenchmarkResult, Benchmarking, TrackedStorageKey,
+};
+use sp_std::{boxed::Box, marker::PhantomData, prelude::*};
+
+/// The benchmarks of a pallet, as a trait object.
+pub trait BenchmarkProvider {
+	/// The pallet name, matched against `BenchmarkConfig::pallet`.
+	fn pallet(&self) -> &'static str;
+
+	/// The instance name.
+	fn instance(&self) -> &'static str;
+
+	/// The metadata of all benchmarks.
+	fn benchmarks(&self, extra: bool) -> Vec<BenchmarkMetadata>;
+
+	/// Run the benchmark `extrinsic`.
+	fn run_benchmark(
+		&self,
+		extrinsic: &[u8],
+		components: &[(BenchmarkParameter, u32)],
+		whitelist: &[TrackedStorageKey],
+		verify: bool,
+		internal_repeats: u32,
+	) -> Result<Vec<BenchmarkResult>, BenchmarkError>;
+}
+
+/// A `BenchmarkProvider` for the `Benchmark` struct generated by
+/// `runtime_benchmarks!`.
+pub struct PalletBenchmarks<B> {
+	pallet: &'static str,
+	instance: &'static str,
+	_phantom: PhantomData<B>,
+}
+
+impl<B: Benchmarking> PalletBenchmarks<B> {
+	pub fn new(pallet: &'static str, instance: &'static str) -> Self {
+		Self {
+			pallet,
+			instance,
+			_phantom: PhantomData,
+		}
+	}
+}
+
+impl<B: Benchmarking> BenchmarkProvider for PalletBenchmarks<B> {
+	fn pallet(&self) -> &'static str {
+		self.pallet
+	}
+
+	fn instance(&self) -> &'static str {
+		self.instance
+	}
+
+	fn benchmarks(&self, extra: bool) -> Vec<BenchmarkMetadata> {
+		B::benchmarks(extra)
+	}
+
+	fn run_benchmark(
+		&self,
+		extrinsic: &[u8],
+		components: &[(BenchmarkParameter, u32)],
+		whitelist: &[TrackedStorageKey],
+		verify: bool,
+		internal_repeats: u32,
+	) -> Result<Vec<BenchmarkResult>, BenchmarkError> {
+		B::run_benchmark(extrinsic, components, whitelist, verify, internal_repeats)
+	}
+}
+
+/// Register benchmarks into a `BenchmarkRegistry`.
+///
+/// Crates publishing pallet benchmarks implement it, the runtime lists them
+/// once as a tuple.
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+pub trait RegisterBenchmarks {
+	fn register_benchmarks(registry: &mut BenchmarkRegistry);
+}
+
+/// A registry of pallet benchmarks.
+///
+/// It lists and runs the registered benchmarks like `list_benchmark!` and
+/// `add_benchmark!` do for the pallets named in the runtime.
+#[derive(Default)]
+pub struct BenchmarkRegistry {
+	providers: Vec<Box<dyn BenchmarkProvider>>,
+}
+
+impl BenchmarkRegistry {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Create a registry with the benchmarks registered by `R`.
+	pub fn with<R: RegisterBenchmarks>() -> Self {
+		let mut registry = Self::new();
+		R::register_benchmarks(&mut registry);
+		registry
+	}
+
+	/// Register the benchmarks of a pallet.
+	pub fn register(&mut self, provider: Box<dyn BenchmarkProvider>) {
+		self.providers.push(provider);
+	}
+
+	/// The names of the registered pallets.
+	pub fn pallets(&self) -> Vec<&'static str> {
+		self.providers.iter().map(|provider| provider.pallet()).collect()
+	}
+
+	/// Append the benchmarks of all registered pallets to `list`.
+	pub fn list(&self, list: &mut Vec<BenchmarkList>, extra: bool) {
+		for provider in self.providers.iter() {
+			list.push(BenchmarkList {
+				pallet: provider.pallet().as_bytes().to_vec(),
+				instance: provider.instance().as_bytes().to_vec(),
+				benchmarks: provider.benchmarks(extra),
+			});
+		}
+	}
+
+	/// Run the benchmark selected by `config` if it belongs to a registered
+	/// pallet, and append the results to `batches`.
+	pub fn add(
+		&self,
+		config: &BenchmarkConfig,
+		whitelist: &[TrackedStorageKey],
+		batches: &mut Vec<BenchmarkBatch>,
+	) -> Result<(), &'static str> {
+		for provider in self.providers.iter() {
+			if config.pallet[..] != *provider.pallet().as_bytes() {
+				continue;
+			}
+
+			let results = match provider.run_benchmark(
+				&config.benchmark[..],
+				&config.selected_components[..],
+				whitelist,
+				config.verify,
+				config.internal_repeats,
+			) {
+				Ok(results) => results,
+				Err(BenchmarkError::Override(mut result)) => {
+					log::error!(
+						"WARNING: benchmark error overrided - {}",
+						sp_std::str::from_utf8(&config.benchmark).expect("benchmark name is always a valid string!")
+					);
+					result.keys.insert(0, (b"Benchmark Override".to_vec(), 0, 0, false));
+					sp_std::vec![result]
+				}
+				Err(BenchmarkError::Stop(e)) => {
+					crate::show_benchmark_debug_info(
+						provider.instance().as_bytes(),
+						&config.benchmark,
+						&config.selected_components,
+						&config.verify,
+						e,
+					);
+					return Err(e);
+				}
+				Err(BenchmarkError::Skip) => {
+					log::error!(
+						"WARNING: benchmark error skipped - {}",
+						sp_std::str::from_utf8(&config.benchmark).expect("benchmark name is always a valid string!")
+					);
+					continue;
+				}
+			};
+
+			batches.push(BenchmarkBatch {
+				pallet: provider.pallet().as_bytes().to_vec(),
+				instance: provider.instance().as_bytes().to_vec(),
+				benchmark: config.benchmark.clone(),
+				results,
+			});
+		}
+		Ok(())
+	}
+}
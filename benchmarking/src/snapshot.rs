@@ -0,0 +1,122 @@
+//! Benchmarking on top of a snapshot of live chain state.
+//!
+//! Weights benchmarked on the genesis state miss the cost of large storage,
+//! e.g. the trie depth of millions of token accounts. The state of a live
+//! chain is written into the genesis of a raw chain spec, and the `benchmark`
+//! command run with this chain spec executes the `runtime_benchmarks!`
+//! batches on top of it.
+//!
+//! With the `remote` feature, the state is scraped from a node with
+//! `remote-externalities`.
+
+use sp_core::hexdisplay::HexDisplay;
+use sp_std::prelude::*;
+use sp_storage::well_known_keys;
+
+/// The top-level key-value pairs of a chain state.
+pub type StoragePairs = Vec<(Vec<u8>, Vec<u8>)>;
+
+fn to_hex(data: &[u8]) -> String {
+	format!("0x{}", HexDisplay::from(&data))
+}
+
+/// Replace the genesis of the raw chain spec `spec` with the state `pairs`.
+///
+/// The runtime code of `spec` is kept, so the benchmarks are run with the
+/// local runtime rather than the one of the snapshot. Child tries are not
+/// included.
+pub fn into_raw_chain_spec(
+	mut spec: serde_json::Value,
+	pairs: StoragePairs,
+) -> Result<serde_json::Value, &'static str> {
+	let code_key = to_hex(well_known_keys::CODE);
+	let code = spec
+		.pointer(&format!("/genesis/raw/top/{}", code_key))
+		.cloned()
+		.ok_or("chain spec is not raw or has no runtime code")?;
+
+	let mut top = pairs
+		.into_iter()
+		.filter(|(key, _)| !well_known_keys::is_child_storage_key(key))
+		.map(|(key, value)| (to_hex(&key), serde_json::Value::String(to_hex(&value))))
+		.collect::<serde_json::Map<_, _>>();
+	top.insert(code_key, code);
+
+	spec["genesis"] = serde_json::json!({
+		"raw": {
+			"top": top,
+			"childrenDefault": {},
+		}
+	});
+	Ok(spec)
+}
+
+#[cfg(feature = "remote")]
+pub use remote::{load_snapshot, write_snapshot_chain_spec, SnapshotSource};
+
+#[cfg(feature = "remote")]
+mod remote {
+	use super::*;
+	use remote_externalities::{Builder, Mode, OfflineConfig, OnlineConfig, SnapshotConfig};
+	use serde::de::DeserializeOwned;
+	use sp_runtime::traits::Block as BlockT;
+	use sp_state_machine::Backend;
+	use std::path::{Path, PathBuf};
+
+	/// Where the state is taken from.
+	pub enum SnapshotSource<B: BlockT> {
+		/// Scrape the storage of `pallets`, or all of it if empty, from the
+		/// node at `uri`, at block `at` or the best block. The state is cached
+		/// to `cache` if given.
+		Live {
+			uri: String,
+			at: Option<B::Hash>,
+			pallets: Vec<String>,
+			cache: Option<PathBuf>,
+		},
+		/// Load the state cached by a previous scrape.
+		Cached(PathBuf),
+	}
+
+	/// Load the state from `source`.
+	pub async fn load_snapshot<B: BlockT + DeserializeOwned>(
+		source: SnapshotSource<B>,
+	) -> Result<StoragePairs, String> {
+		let mode = match source {
+			SnapshotSource::Live {
+				uri,
+				at,
+				pallets,
+				cache,
+			} => Mode::Online(OnlineConfig {
+				transport: uri.into(),
+				at,
+				pallets,
+				state_snapshot: cache.map(SnapshotConfig::new),
+				..Default::default()
+			}),
+			SnapshotSource::Cached(path) => Mode::Offline(OfflineConfig {
+				state_snapshot: SnapshotConfig::new(path),
+			}),
+		};
+
+		let mut ext = Builder::<B>::new().mode(mode).build().await?;
+		ext.commit_all()?;
+		Ok(ext.as_backend().pairs())
+	}
+
+	/// Load the state from `source` into the raw chain spec at `spec`, see
+	/// `into_raw_chain_spec`, and write the result to `output`.
+	pub async fn write_snapshot_chain_spec<B: BlockT + DeserializeOwned>(
+		source: SnapshotSource<B>,
+		spec: &Path,
+		output: &Path,
+	) -> Result<(), String> {
+		let spec = std::fs::read(spec).map_err(|e| e.to_string())?;
+		let spec = serde_json::from_slice(&spec).map_err(|e| e.to_string())?;
+		let pairs = load_snapshot(source).await?;
+		let spec = into_raw_chain_spec(spec, pairs)?;
+		let spec = serde_json::to_vec_pretty(&spec).map_err(|e| e.to_string())?;
+		std::fs::write(output, spec).map_err(|e| e.to_string())
+	}
+}
@@ -144,6 +144,19 @@ runtime_benchmarks! {
 		let caller = account::<AccountId>("caller", 0, 0);
 	}: dummy (RawOrigin::Signed(caller), b)
 
+	#[pov_mode = Ignored]
+	ignored_pov {
+		let b in 1 .. 1000;
+		let caller = account::<AccountId>("caller", 0, 0);
+	}: set_value (RawOrigin::Signed(caller), b)
+
+	#[extra]
+	#[pov_mode = Measured]
+	extra_measured_pov {
+		let b in 1 .. 1000;
+		let caller = account::<AccountId>("caller", 0, 0);
+	}: set_value (RawOrigin::Signed(caller), b)
+
 	bad_verify {
 		let x in 1 .. 10000;
 		let mut m = Vec::<u32>::new();
@@ -250,3 +263,84 @@ fn benchmarks_generate_unit_tests() {
 		assert_err!(Benchmark::test_benchmark_bad_verify(), "You forgot to sort!");
 	});
 }
+
+#[test]
+fn benchmarks_macro_pov_mode_works() {
+	assert_eq!(Benchmark::pov_mode(b"set_value"), PovMode::Measured);
+	assert_eq!(Benchmark::pov_mode(b"ignored_pov"), PovMode::Ignored);
+	assert_eq!(Benchmark::pov_mode(b"extra_measured_pov"), PovMode::Measured);
+
+	let names = |extra| {
+		Benchmark::benchmarks(extra)
+			.into_iter()
+			.map(|metadata| metadata.name)
+			.collect::<Vec<_>>()
+	};
+	assert!(names(true).contains(&b"extra_measured_pov".to_vec()));
+	assert!(!names(false).contains(&b"extra_measured_pov".to_vec()));
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Benchmark::test_benchmark_ignored_pov());
+		assert_ok!(Benchmark::test_benchmark_extra_measured_pov());
+	});
+}
+
+struct TestBenchmarks;
+
+impl RegisterBenchmarks for TestBenchmarks {
+	fn register_benchmarks(registry: &mut BenchmarkRegistry) {
+		registry.register(Box::new(PalletBenchmarks::<Benchmark>::new("test", "Pallet")));
+	}
+}
+
+#[test]
+fn benchmark_registry_works() {
+	let registry = BenchmarkRegistry::with::<(TestBenchmarks,)>();
+	assert_eq!(registry.pallets(), vec!["test"]);
+
+	let mut list = Vec::<BenchmarkList>::new();
+	registry.list(&mut list, false);
+	assert_eq!(list.len(), 1);
+	assert_eq!(list[0].pallet, b"test".to_vec());
+	assert_eq!(list[0].instance, b"Pallet".to_vec());
+	assert_eq!(list[0].benchmarks, Benchmark::benchmarks(false));
+
+	// benchmarks of other pallets are not run
+	let config = BenchmarkConfig {
+		pallet: b"other".to_vec(),
+		benchmark: b"set_value".to_vec(),
+		selected_components: vec![(BenchmarkParameter::b, 1)],
+		verify: true,
+		internal_repeats: 1,
+	};
+	let mut batches = Vec::<BenchmarkBatch>::new();
+	assert_ok!(registry.add(&config, &[], &mut batches));
+	assert!(batches.is_empty());
+}
+
+#[test]
+fn snapshot_raw_chain_spec_works() {
+	let spec = serde_json::json!({
+		"name": "Test",
+		"genesis": { "raw": { "top": { "0x3a636f6465": "0x0102", "0x11": "0x22" }, "childrenDefault": {} } },
+	});
+	let pairs = vec![
+		(b":code".to_vec(), vec![9]),
+		(vec![0xaa, 0xbb], vec![0x01]),
+		(b":child_storage:default:x".to_vec(), vec![0x02]),
+	];
+
+	// the runtime code of the chain spec is kept
+	assert_eq!(
+		snapshot::into_raw_chain_spec(spec.clone(), pairs.clone()),
+		Ok(serde_json::json!({
+			"name": "Test",
+			"genesis": { "raw": { "top": { "0x3a636f6465": "0x0102", "0xaabb": "0x01" }, "childrenDefault": {} } },
+		}))
+	);
+
+	assert_eq!(
+		snapshot::into_raw_chain_spec(serde_json::json!({ "genesis": { "runtime": {} } }), pairs),
+		Err("chain spec is not raw or has no runtime code")
+	);
+}
@@ -0,0 +1,81 @@
+//! Benchmarks for the bridge module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+const CURRENCY: u32 = 1;
+
+/// Set `caller` as the only relayer, committing alone executes a batch.
+fn set_single_relayer<T: Config>(caller: &T::AccountId) -> Result<(), &'static str> {
+	let relayers: BoundedVec<T::AccountId, T::MaxRelayers> =
+		vec![caller.clone()].try_into().map_err(|_| "too many relayers")?;
+	Relayers::<T>::put(relayers);
+	Threshold::<T>::put(1);
+	Ok(())
+}
+
+benchmarks! {
+	where_clause { where CurrencyIdOf<T>: From<u32>, BalanceOf<T>: From<u32> }
+
+	set_relayers {
+		let r in 1 .. T::MaxRelayers::get();
+
+		let relayers = (0..r).map(|i| account("relayer", i, SEED)).collect::<Vec<T::AccountId>>();
+	}: _<T::Origin>(T::GovernanceOrigin::successful_origin(), relayers, r)
+	verify {
+		assert_eq!(Threshold::<T>::get(), r);
+	}
+
+	// the batch reaches the threshold and all messages are deposited
+	commit_batch {
+		let n in 1 .. T::MaxBatchSize::get();
+
+		let caller: T::AccountId = whitelisted_caller();
+		set_single_relayer::<T>(&caller)?;
+		let messages = (0..n).map(|i| InboundMessage {
+			nonce: i as u64,
+			currency_id: CURRENCY.into(),
+			amount: 1u32.into(),
+			recipient: account("recipient", i, SEED),
+		}).collect::<Vec<_>>();
+	}: _(RawOrigin::Signed(caller), messages)
+	verify {
+		assert!(ProcessedInbound::<T>::contains_key((n - 1) as u64));
+	}
+
+	transfer_out {
+		let caller: T::AccountId = whitelisted_caller();
+		let currency_id: CurrencyIdOf<T> = CURRENCY.into();
+		T::Currency::deposit(currency_id, &caller, 100u32.into())?;
+		let recipient = vec![0u8; T::MaxRecipientLen::get() as usize];
+	}: _(RawOrigin::Signed(caller), currency_id, 1u32.into(), recipient)
+	verify {
+		assert!(Outbox::<T>::contains_key(0));
+	}
+
+	// the confirmation reaches the threshold and all messages are removed
+	confirm_delivery {
+		let n in 1 .. T::MaxBatchSize::get();
+
+		let caller: T::AccountId = whitelisted_caller();
+		set_single_relayer::<T>(&caller)?;
+		for nonce in 0..n as u64 {
+			Outbox::<T>::insert(nonce, OutboundMessage {
+				sender: caller.clone(),
+				currency_id: CURRENCY.into(),
+				amount: 1u32.into(),
+				recipient: Default::default(),
+			});
+		}
+		let nonces = (0..n as u64).collect::<Vec<_>>();
+	}: _(RawOrigin::Signed(caller), nonces)
+	verify {
+		assert_eq!(Outbox::<T>::iter().count(), 0);
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::default().build(), crate::mock::Runtime);
@@ -0,0 +1,454 @@
+//! # Bridge
+//!
+//! ## Overview
+//!
+//! This module connects to external chains without XCM, settling transfers
+//! in a `MultiCurrency`.
+//!
+//! Inbound messages are committed in batches by the relayers set by
+//! `GovernanceOrigin`. A batch is executed once `threshold` relayers have
+//! committed the same batch, depositing the amounts to the recipients. Each
+//! inbound message has a nonce which is processed only once, so batches
+//! can't be replayed. A message failing to process is skipped, leaving its
+//! nonce unprocessed for a later batch. Outbound transfers withdraw the
+//! amount from the sender and queue a message in the outbox for the relayers
+//! to deliver. Delivered messages are removed from the outbox once
+//! `threshold` relayers have confirmed the same delivery.
+//!
+//! Commits are kept per relayer set, commits of previous relayer sets are
+//! pruned in `on_initialize`, at most `MaxCommitPrunes` per block.
+//!
+//! Transfers in both directions are limited per asset by `T::RateLimiter`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, transactional, BoundedVec};
+use frame_system::pallet_prelude::*;
+use orml_traits::{MultiCurrency, RateLimiter};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{Hash, SaturatedConversion},
+	RuntimeDebug,
+};
+use sp_std::{convert::TryInto, prelude::*};
+
+mod benchmarking;
+mod mock;
+mod tests;
+mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// A transfer from an external chain.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct InboundMessage<AccountId, CurrencyId, Balance> {
+	/// The nonce of the message, unique per message.
+	pub nonce: u64,
+	/// The currency transferred.
+	pub currency_id: CurrencyId,
+	/// The amount transferred.
+	pub amount: Balance,
+	/// The recipient on this chain.
+	pub recipient: AccountId,
+}
+
+/// A transfer to an external chain.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct OutboundMessage<AccountId, CurrencyId, Balance, Recipient> {
+	/// The sender on this chain.
+	pub sender: AccountId,
+	/// The currency transferred.
+	pub currency_id: CurrencyId,
+	/// The amount transferred.
+	pub amount: Balance,
+	/// The encoded recipient on the external chain.
+	pub recipient: Recipient,
+}
+
+/// The direction of a transfer, rate limited apart.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum Direction {
+	/// From the external chain.
+	Inbound,
+	/// To the external chain.
+	Outbound,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	pub(crate) type BalanceOf<T> =
+		<<T as Config>::Currency as MultiCurrency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub(crate) type CurrencyIdOf<T> =
+		<<T as Config>::Currency as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
+	pub(crate) type InboundMessageOf<T> =
+		InboundMessage<<T as frame_system::Config>::AccountId, CurrencyIdOf<T>, BalanceOf<T>>;
+	pub(crate) type OutboundMessageOf<T> = OutboundMessage<
+		<T as frame_system::Config>::AccountId,
+		CurrencyIdOf<T>,
+		BalanceOf<T>,
+		BoundedVec<u8, <T as Config>::MaxRecipientLen>,
+	>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency transfers are settled in.
+		type Currency: MultiCurrency<Self::AccountId>;
+
+		/// The maximum number of relayers.
+		#[pallet::constant]
+		type MaxRelayers: Get<u32>;
+
+		/// The maximum number of messages in an inbound batch.
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
+
+		/// The maximum length of an encoded recipient on the external chain.
+		#[pallet::constant]
+		type MaxRecipientLen: Get<u32>;
+
+		/// The maximum number of commits of previous relayer sets pruned per
+		/// block.
+		#[pallet::constant]
+		type MaxCommitPrunes: Get<u32>;
+
+		/// The rate limiter of transfers, keyed by direction and currency.
+		type RateLimiter: RateLimiter;
+
+		/// The rate limiter id of transfers.
+		type RateLimiterId: Get<<Self::RateLimiter as RateLimiter>::RateLimiterId>;
+
+		/// The origin which may set the relayers.
+		type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The threshold is zero or exceeds the number of relayers.
+		InvalidThreshold,
+		/// Too many relayers.
+		TooManyRelayers,
+		/// The caller is not a relayer.
+		NotRelayer,
+		/// The batch is empty or exceeds `MaxBatchSize`.
+		InvalidBatchSize,
+		/// The relayer already committed the batch.
+		AlreadyCommitted,
+		/// All messages of the batch were already processed or delivered.
+		BatchAlreadyExecuted,
+		/// The inbound message was already processed.
+		MessageAlreadyProcessed,
+		/// The recipient exceeds `MaxRecipientLen`.
+		RecipientTooLong,
+		/// The transfer exceeds the rate limit.
+		RateLimited,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The relayers are set. [relayers, threshold]
+		RelayersSet(Vec<T::AccountId>, u32),
+		/// A relayer committed a batch. [relayer, batch_hash]
+		BatchCommitted(T::AccountId, T::Hash),
+		/// A batch reached the threshold and is executed. [batch_hash]
+		BatchExecuted(T::Hash),
+		/// An inbound transfer is deposited. [nonce, currency_id, amount,
+		/// recipient]
+		InboundTransferred(u64, CurrencyIdOf<T>, BalanceOf<T>, T::AccountId),
+		/// An inbound message of an executed batch failed and is skipped.
+		/// [nonce, error]
+		InboundFailed(u64, DispatchError),
+		/// An outbound transfer is queued in the outbox. [nonce, sender,
+		/// currency_id, amount, recipient]
+		OutboundQueued(u64, T::AccountId, CurrencyIdOf<T>, BalanceOf<T>, Vec<u8>),
+		/// An outbound message is delivered and removed from the outbox.
+		/// [nonce]
+		OutboundDelivered(u64),
+	}
+
+	/// The relayers committing inbound batches.
+	///
+	/// Relayers: Vec<AccountId>
+	#[pallet::storage]
+	#[pallet::getter(fn relayers)]
+	pub type Relayers<T: Config> = StorageValue<_, BoundedVec<T::AccountId, T::MaxRelayers>, ValueQuery>;
+
+	/// The number of relayers which must commit a batch to execute it.
+	///
+	/// Threshold: u32
+	#[pallet::storage]
+	#[pallet::getter(fn threshold)]
+	pub type Threshold<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The index of the current relayer set, bumped by `set_relayers`.
+	///
+	/// RelayerSetIndex: u32
+	#[pallet::storage]
+	#[pallet::getter(fn relayer_set_index)]
+	pub type RelayerSetIndex<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The relayers which committed each pending batch, by the index of the
+	/// relayer set they committed under.
+	///
+	/// double_map RelayerSetIndex, Hash => Vec<AccountId>
+	#[pallet::storage]
+	#[pallet::getter(fn batch_commits)]
+	pub type BatchCommits<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, u32, Identity, T::Hash, BoundedVec<T::AccountId, T::MaxRelayers>, ValueQuery>;
+
+	/// The lowest relayer set index which may still have commits to prune.
+	///
+	/// CommitPruneCursor: u32
+	#[pallet::storage]
+	#[pallet::getter(fn commit_prune_cursor)]
+	pub type CommitPruneCursor<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The nonces of processed inbound messages.
+	///
+	/// map u64 => Option<()>
+	#[pallet::storage]
+	#[pallet::getter(fn processed_inbound)]
+	pub type ProcessedInbound<T: Config> = StorageMap<_, Twox64Concat, u64, (), OptionQuery>;
+
+	/// The nonce of the next outbound message.
+	///
+	/// OutboundNonce: u64
+	#[pallet::storage]
+	#[pallet::getter(fn outbound_nonce)]
+	pub type OutboundNonce<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// The queued outbound messages.
+	///
+	/// map u64 => Option<OutboundMessage>
+	#[pallet::storage]
+	#[pallet::getter(fn outbox)]
+	pub type Outbox<T: Config> = StorageMap<_, Twox64Concat, u64, OutboundMessageOf<T>, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			Self::prune_stale_commits()
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the relayers and the number of them which must commit a batch.
+		/// Pending batches are discarded, their commits are pruned in later
+		/// blocks.
+		///
+		/// The dispatch origin for this call must be `GovernanceOrigin`.
+		#[pallet::weight(T::WeightInfo::set_relayers(relayers.len() as u32))]
+		pub fn set_relayers(origin: OriginFor<T>, relayers: Vec<T::AccountId>, threshold: u32) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			ensure!(
+				threshold > 0 && threshold as usize <= relayers.len(),
+				Error::<T>::InvalidThreshold
+			);
+
+			let bounded: BoundedVec<T::AccountId, T::MaxRelayers> =
+				relayers.clone().try_into().map_err(|_| Error::<T>::TooManyRelayers)?;
+			Relayers::<T>::put(bounded);
+			Threshold::<T>::put(threshold);
+			RelayerSetIndex::<T>::mutate(|index| *index = index.saturating_add(1));
+
+			Self::deposit_event(Event::RelayersSet(relayers, threshold));
+			Ok(())
+		}
+
+		/// Commit a batch of inbound messages, executed when `threshold`
+		/// relayers committed the same batch. Messages failing to process are
+		/// skipped.
+		///
+		/// The dispatch origin for this call must be `Signed` by a relayer.
+		#[pallet::weight(T::WeightInfo::commit_batch(messages.len() as u32))]
+		#[transactional]
+		pub fn commit_batch(origin: OriginFor<T>, messages: Vec<InboundMessageOf<T>>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::relayers().contains(&who), Error::<T>::NotRelayer);
+			ensure!(
+				!messages.is_empty() && messages.len() <= T::MaxBatchSize::get() as usize,
+				Error::<T>::InvalidBatchSize
+			);
+
+			ensure!(
+				messages
+					.iter()
+					.any(|message| !ProcessedInbound::<T>::contains_key(message.nonce)),
+				Error::<T>::BatchAlreadyExecuted
+			);
+
+			let batch_hash = T::Hashing::hash_of(&(Direction::Inbound, &messages));
+			if Self::commit(who, batch_hash)? {
+				for message in messages {
+					let nonce = message.nonce;
+					if let Err(e) = Self::process_inbound(message) {
+						Self::deposit_event(Event::InboundFailed(nonce, e));
+					}
+				}
+				Self::deposit_event(Event::BatchExecuted(batch_hash));
+			}
+			Ok(())
+		}
+
+		/// Confirm the delivery of outbound messages, removed from the outbox
+		/// when `threshold` relayers confirmed the same nonces.
+		///
+		/// The dispatch origin for this call must be `Signed` by a relayer.
+		#[pallet::weight(T::WeightInfo::confirm_delivery(nonces.len() as u32))]
+		#[transactional]
+		pub fn confirm_delivery(origin: OriginFor<T>, nonces: Vec<u64>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::relayers().contains(&who), Error::<T>::NotRelayer);
+			ensure!(
+				!nonces.is_empty() && nonces.len() <= T::MaxBatchSize::get() as usize,
+				Error::<T>::InvalidBatchSize
+			);
+			ensure!(
+				nonces.iter().any(|nonce| Outbox::<T>::contains_key(nonce)),
+				Error::<T>::BatchAlreadyExecuted
+			);
+
+			let batch_hash = T::Hashing::hash_of(&(Direction::Outbound, &nonces));
+			if Self::commit(who, batch_hash)? {
+				for nonce in nonces {
+					if Outbox::<T>::take(nonce).is_some() {
+						Self::deposit_event(Event::OutboundDelivered(nonce));
+					}
+				}
+				Self::deposit_event(Event::BatchExecuted(batch_hash));
+			}
+			Ok(())
+		}
+
+		/// Transfer `amount` of `currency_id` to `recipient` on the external
+		/// chain. The amount is withdrawn and the transfer queued in the
+		/// outbox.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::transfer_out())]
+		#[transactional]
+		pub fn transfer_out(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			amount: BalanceOf<T>,
+			recipient: Vec<u8>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let bounded_recipient: BoundedVec<u8, T::MaxRecipientLen> =
+				recipient.clone().try_into().map_err(|_| Error::<T>::RecipientTooLong)?;
+
+			Self::consume_limit(Direction::Outbound, currency_id, amount)?;
+			T::Currency::withdraw(currency_id, &sender, amount)?;
+
+			let nonce = OutboundNonce::<T>::mutate(|nonce| {
+				let current = *nonce;
+				*nonce = nonce.saturating_add(1);
+				current
+			});
+			Outbox::<T>::insert(
+				nonce,
+				OutboundMessage {
+					sender: sender.clone(),
+					currency_id,
+					amount,
+					recipient: bounded_recipient,
+				},
+			);
+
+			Self::deposit_event(Event::OutboundQueued(nonce, sender, currency_id, amount, recipient));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Commit a batch by a relayer of the current relayer set, returns
+	/// whether the batch reached the threshold, removing its commits if so.
+	fn commit(who: T::AccountId, batch_hash: T::Hash) -> Result<bool, DispatchError> {
+		let set_index = Self::relayer_set_index();
+		let commits = BatchCommits::<T>::try_mutate(set_index, batch_hash, |commits| -> Result<u32, DispatchError> {
+			ensure!(!commits.contains(&who), Error::<T>::AlreadyCommitted);
+			commits.try_push(who.clone()).map_err(|_| Error::<T>::TooManyRelayers)?;
+			Ok(commits.len() as u32)
+		})?;
+		Self::deposit_event(Event::BatchCommitted(who, batch_hash));
+
+		let reached = commits >= Self::threshold();
+		if reached {
+			BatchCommits::<T>::remove(set_index, batch_hash);
+		}
+		Ok(reached)
+	}
+
+	/// Prune the commits of previous relayer sets, at most
+	/// `MaxCommitPrunes` of them, or of the relayer sets stepped over, per
+	/// block. Returns the consumed weight.
+	fn prune_stale_commits() -> Weight {
+		let current = Self::relayer_set_index();
+		let mut budget = T::MaxCommitPrunes::get();
+		let mut cursor = Self::commit_prune_cursor();
+		let mut reads: Weight = 2;
+		let mut writes: Weight = 0;
+		while cursor < current && budget > 0 {
+			let removed = BatchCommits::<T>::drain_prefix(cursor).take(budget as usize).count() as u32;
+			reads += removed.max(1) as Weight;
+			writes += removed as Weight;
+			if removed < budget {
+				// all removed, stepping over a relayer set costs at least one
+				budget = budget.saturating_sub(removed.max(1));
+				cursor = cursor.saturating_add(1);
+			} else {
+				budget = 0;
+			}
+		}
+		if reads > 2 {
+			CommitPruneCursor::<T>::put(cursor);
+			writes += 1;
+		}
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	#[transactional]
+	fn process_inbound(message: InboundMessageOf<T>) -> DispatchResult {
+		ensure!(
+			!ProcessedInbound::<T>::contains_key(message.nonce),
+			Error::<T>::MessageAlreadyProcessed
+		);
+		ProcessedInbound::<T>::insert(message.nonce, ());
+
+		Self::consume_limit(Direction::Inbound, message.currency_id, message.amount)?;
+		T::Currency::deposit(message.currency_id, &message.recipient, message.amount)?;
+
+		Self::deposit_event(Event::InboundTransferred(
+			message.nonce,
+			message.currency_id,
+			message.amount,
+			message.recipient,
+		));
+		Ok(())
+	}
+
+	fn consume_limit(direction: Direction, currency_id: CurrencyIdOf<T>, amount: BalanceOf<T>) -> DispatchResult {
+		T::RateLimiter::try_consume(
+			T::RateLimiterId::get(),
+			(direction, currency_id),
+			amount.saturated_into(),
+			None::<()>,
+		)
+		.map_err(|_| Error::<T>::RateLimited.into())
+	}
+}
@@ -0,0 +1,177 @@
+//! Mocks for the bridge module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{
+	construct_runtime, parameter_types,
+	storage::unhashed,
+	traits::{Everything, Nothing},
+};
+use frame_system::EnsureRoot;
+use orml_traits::{parameter_type_with_key, RateLimiterError};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+
+use crate as bridge;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+pub type AccountId = u64;
+pub type Balance = u64;
+pub type CurrencyId = u32;
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = Everything;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = i64;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+	type OnSlash = ();
+	type OnDeposit = ();
+	type OnTransfer = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = Nothing;
+	type OnNewTokenAccount = ();
+	type OnKilledTokenAccount = ();
+	type TransferFee = ();
+	type TransferFeeExemptions = Nothing;
+	type OnLockChanged = ();
+}
+
+pub const RATE_LIMIT: u128 = 1_000;
+
+/// Limit the total value consumed under each key to `RATE_LIMIT`.
+pub struct MockRateLimiter;
+impl RateLimiter for MockRateLimiter {
+	type RateLimiterId = u8;
+
+	fn is_whitelist(_: u8, _: impl Encode) -> bool {
+		false
+	}
+
+	fn can_consume(limiter_id: u8, key: impl Encode, value: u128) -> Result<(), RateLimiterError> {
+		let consumed: u128 = unhashed::get_or_default(&(limiter_id, key).encode());
+		if consumed.saturating_add(value) <= RATE_LIMIT {
+			Ok(())
+		} else {
+			Err(RateLimiterError::ExceedLimit)
+		}
+	}
+
+	fn consume(limiter_id: u8, key: impl Encode, value: u128) {
+		let storage_key = (limiter_id, key).encode();
+		let consumed: u128 = unhashed::get_or_default(&storage_key);
+		unhashed::put(&storage_key, &consumed.saturating_add(value));
+	}
+}
+
+parameter_types! {
+	pub const MaxRelayers: u32 = 3;
+	pub const MaxBatchSize: u32 = 4;
+	pub const MaxRecipientLen: u32 = 20;
+	pub const MaxCommitPrunes: u32 = 2;
+	pub const BridgeRateLimiterId: u8 = 1;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type Currency = Tokens;
+	type MaxRelayers = MaxRelayers;
+	type MaxBatchSize = MaxBatchSize;
+	type MaxRecipientLen = MaxRecipientLen;
+	type MaxCommitPrunes = MaxCommitPrunes;
+	type RateLimiter = MockRateLimiter;
+	type RateLimiterId = BridgeRateLimiterId;
+	type GovernanceOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
+		Bridge: bridge::{Pallet, Storage, Call, Event<T>},
+	}
+);
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const DAVE: AccountId = 4;
+pub const DOT: CurrencyId = 1;
+
+pub struct ExtBuilder;
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		ExtBuilder
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		orml_tokens::GenesisConfig::<Runtime> {
+			balances: vec![(DAVE, DOT, 2_000)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			// ALICE, BOB and CHARLIE are relayers, two of them must commit
+			Bridge::set_relayers(Origin::root(), vec![ALICE, BOB, CHARLIE], 2).unwrap();
+		});
+		ext
+	}
+}
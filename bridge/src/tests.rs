@@ -0,0 +1,227 @@
+//! Unit tests for the bridge module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+fn inbound(nonce: u64, amount: Balance, recipient: AccountId) -> InboundMessageOf<Runtime> {
+	InboundMessage {
+		nonce,
+		currency_id: DOT,
+		amount,
+		recipient,
+	}
+}
+
+#[test]
+fn set_relayers_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(Bridge::set_relayers(Origin::signed(ALICE), vec![ALICE], 1), BadOrigin);
+		assert_noop!(
+			Bridge::set_relayers(Origin::root(), vec![ALICE], 0),
+			Error::<Runtime>::InvalidThreshold
+		);
+		assert_noop!(
+			Bridge::set_relayers(Origin::root(), vec![ALICE], 2),
+			Error::<Runtime>::InvalidThreshold
+		);
+		assert_noop!(
+			Bridge::set_relayers(Origin::root(), vec![ALICE, BOB, CHARLIE, DAVE], 2),
+			Error::<Runtime>::TooManyRelayers
+		);
+
+		// pending batches are discarded
+		assert_ok!(Bridge::commit_batch(Origin::signed(ALICE), vec![inbound(0, 100, DAVE)]));
+		assert_ok!(Bridge::set_relayers(Origin::root(), vec![BOB, CHARLIE], 2));
+		System::assert_last_event(Event::Bridge(crate::Event::RelayersSet(vec![BOB, CHARLIE], 2)));
+		assert_eq!(Bridge::relayer_set_index(), 2);
+		assert_eq!(BatchCommits::<Runtime>::iter_prefix(2).count(), 0);
+		assert_noop!(
+			Bridge::commit_batch(Origin::signed(ALICE), vec![inbound(0, 100, DAVE)]),
+			Error::<Runtime>::NotRelayer
+		);
+	});
+}
+
+#[test]
+fn commit_batch_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let batch = vec![inbound(0, 100, ALICE), inbound(1, 200, BOB)];
+		assert_noop!(
+			Bridge::commit_batch(Origin::signed(DAVE), batch.clone()),
+			Error::<Runtime>::NotRelayer
+		);
+		assert_noop!(
+			Bridge::commit_batch(Origin::signed(ALICE), vec![]),
+			Error::<Runtime>::InvalidBatchSize
+		);
+
+		assert_ok!(Bridge::commit_batch(Origin::signed(ALICE), batch.clone()));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 0);
+		assert_noop!(
+			Bridge::commit_batch(Origin::signed(ALICE), batch.clone()),
+			Error::<Runtime>::AlreadyCommitted
+		);
+
+		// threshold reached
+		assert_ok!(Bridge::commit_batch(Origin::signed(BOB), batch.clone()));
+		let batch_hash = <Runtime as frame_system::Config>::Hashing::hash_of(&(Direction::Inbound, &batch));
+		System::assert_has_event(Event::Bridge(crate::Event::InboundTransferred(1, DOT, 200, BOB)));
+		System::assert_last_event(Event::Bridge(crate::Event::BatchExecuted(batch_hash)));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 100);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 200);
+		assert!(!BatchCommits::<Runtime>::contains_key(
+			Bridge::relayer_set_index(),
+			batch_hash
+		));
+
+		// late commits of an executed batch are rejected
+		assert_noop!(
+			Bridge::commit_batch(Origin::signed(CHARLIE), batch.clone()),
+			Error::<Runtime>::BatchAlreadyExecuted
+		);
+
+		// processed messages are skipped
+		let batch = vec![inbound(0, 100, ALICE), inbound(2, 100, BOB)];
+		assert_ok!(Bridge::commit_batch(Origin::signed(ALICE), batch.clone()));
+		assert_ok!(Bridge::commit_batch(Origin::signed(BOB), batch));
+		System::assert_has_event(Event::Bridge(crate::Event::InboundFailed(
+			0,
+			Error::<Runtime>::MessageAlreadyProcessed.into(),
+		)));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 100);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 300);
+
+		// inbound transfers are rate limited per asset, a failing message
+		// doesn't revert the others and stays unprocessed
+		let batch = vec![inbound(3, 800, ALICE), inbound(4, 100, CHARLIE)];
+		assert_ok!(Bridge::commit_batch(Origin::signed(ALICE), batch.clone()));
+		assert_ok!(Bridge::commit_batch(Origin::signed(BOB), batch));
+		System::assert_has_event(Event::Bridge(crate::Event::InboundFailed(
+			3,
+			Error::<Runtime>::RateLimited.into(),
+		)));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 100);
+		assert_eq!(Tokens::free_balance(DOT, &CHARLIE), 100);
+		assert!(!ProcessedInbound::<Runtime>::contains_key(3));
+		assert!(ProcessedInbound::<Runtime>::contains_key(4));
+	});
+}
+
+#[test]
+fn stale_commits_should_be_pruned() {
+	ExtBuilder::default().build().execute_with(|| {
+		for nonce in 0..3 {
+			assert_ok!(Bridge::commit_batch(
+				Origin::signed(ALICE),
+				vec![inbound(nonce, 100, DAVE)]
+			));
+		}
+		assert_ok!(Bridge::set_relayers(Origin::root(), vec![ALICE, BOB], 2));
+		assert_eq!(BatchCommits::<Runtime>::iter_prefix(1).count(), 3);
+
+		// at most `MaxCommitPrunes` per block, stepping over a relayer set
+		// costs one
+		Bridge::on_initialize(2);
+		assert_eq!(BatchCommits::<Runtime>::iter_prefix(1).count(), 2);
+		assert_eq!(Bridge::commit_prune_cursor(), 1);
+		Bridge::on_initialize(3);
+		assert_eq!(BatchCommits::<Runtime>::iter_prefix(1).count(), 0);
+		Bridge::on_initialize(4);
+		assert_eq!(Bridge::commit_prune_cursor(), 2);
+
+		// commits of the current relayer set are kept
+		assert_ok!(Bridge::commit_batch(Origin::signed(ALICE), vec![inbound(0, 100, DAVE)]));
+		Bridge::on_initialize(5);
+		assert_eq!(BatchCommits::<Runtime>::iter_prefix(2).count(), 1);
+	});
+}
+
+#[test]
+fn confirm_delivery_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Bridge::transfer_out(Origin::signed(DAVE), DOT, 100, vec![1]));
+		assert_ok!(Bridge::transfer_out(Origin::signed(DAVE), DOT, 100, vec![1]));
+
+		assert_noop!(
+			Bridge::confirm_delivery(Origin::signed(DAVE), vec![0]),
+			Error::<Runtime>::NotRelayer
+		);
+		assert_noop!(
+			Bridge::confirm_delivery(Origin::signed(ALICE), vec![]),
+			Error::<Runtime>::InvalidBatchSize
+		);
+		assert_noop!(
+			Bridge::confirm_delivery(Origin::signed(ALICE), vec![2]),
+			Error::<Runtime>::BatchAlreadyExecuted
+		);
+
+		assert_ok!(Bridge::confirm_delivery(Origin::signed(ALICE), vec![0]));
+		assert!(Bridge::outbox(0).is_some());
+		assert_noop!(
+			Bridge::confirm_delivery(Origin::signed(ALICE), vec![0]),
+			Error::<Runtime>::AlreadyCommitted
+		);
+
+		// threshold reached
+		assert_ok!(Bridge::confirm_delivery(Origin::signed(BOB), vec![0]));
+		System::assert_has_event(Event::Bridge(crate::Event::OutboundDelivered(0)));
+		assert_eq!(Bridge::outbox(0), None);
+		assert!(Bridge::outbox(1).is_some());
+
+		// late confirmations are rejected
+		assert_noop!(
+			Bridge::confirm_delivery(Origin::signed(CHARLIE), vec![0]),
+			Error::<Runtime>::BatchAlreadyExecuted
+		);
+	});
+}
+
+#[test]
+fn transfer_out_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Bridge::transfer_out(Origin::signed(DAVE), DOT, 100, vec![1; 21]),
+			Error::<Runtime>::RecipientTooLong
+		);
+
+		assert_ok!(Bridge::transfer_out(Origin::signed(DAVE), DOT, 600, vec![1; 20]));
+		System::assert_last_event(Event::Bridge(crate::Event::OutboundQueued(
+			0,
+			DAVE,
+			DOT,
+			600,
+			vec![1; 20],
+		)));
+		assert_eq!(Tokens::free_balance(DOT, &DAVE), 1_400);
+		assert_eq!(Tokens::total_issuance(DOT), 1_400);
+		assert_eq!(
+			Bridge::outbox(0),
+			Some(OutboundMessage {
+				sender: DAVE,
+				currency_id: DOT,
+				amount: 600,
+				recipient: vec![1; 20].try_into().unwrap(),
+			})
+		);
+		assert_eq!(Bridge::outbound_nonce(), 1);
+
+		// outbound transfers are limited apart from inbound ones
+		assert_noop!(
+			Bridge::transfer_out(Origin::signed(DAVE), DOT, 500, vec![1]),
+			Error::<Runtime>::RateLimited
+		);
+		assert_ok!(Bridge::commit_batch(
+			Origin::signed(ALICE),
+			vec![inbound(0, 1_000, ALICE)]
+		));
+		assert_ok!(Bridge::commit_batch(
+			Origin::signed(BOB),
+			vec![inbound(0, 1_000, ALICE)]
+		));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 1_000);
+	});
+}
@@ -0,0 +1,56 @@
+//! Weights for orml_bridge
+//!
+//! These weights are estimates, not benchmark output. Generate them with the
+//! benchmarks in `benchmarking.rs`:
+//!
+//! target/release/acala benchmark --chain=dev --steps=50 --repeat=20
+//! --pallet=orml_bridge --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --heap-pages=4096 --output=./bridge/src/weights.rs
+//! --template=../templates/orml-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for orml_bridge.
+pub trait WeightInfo {
+	fn set_relayers(r: u32, ) -> Weight;
+	fn commit_batch(n: u32, ) -> Weight;
+	fn transfer_out() -> Weight;
+	fn confirm_delivery(n: u32, ) -> Weight;
+}
+
+/// Default weights.
+impl WeightInfo for () {
+	fn set_relayers(r: u32, ) -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add((120_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn commit_batch(n: u32, ) -> Weight {
+		(34_000_000 as Weight)
+			.saturating_add((41_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+	}
+	fn transfer_out() -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn confirm_delivery(n: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((12_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+}
@@ -0,0 +1,141 @@
+//! Adapter exposing ERC20 contracts in an EVM as currencies.
+//!
+//! `Erc20Adapter` implements `MultiCurrency` by routing currencies converted
+//! into an ERC20 contract address by `IsErc20` to an `Erc20Bridge`, and all
+//! other currencies to `Other`, typically this module.
+
+use frame_support::traits::ExistenceRequirement;
+use orml_traits::MultiCurrency;
+use sp_runtime::{
+	traits::{Convert, Zero},
+	DispatchError, DispatchResult, TokenError,
+};
+use sp_std::marker;
+
+/// A bridge to ERC20 contracts in an EVM.
+pub trait Erc20Bridge<AccountId, Balance> {
+	/// The contract address type.
+	type Address;
+
+	/// The total supply of the ERC20 contract at `contract`.
+	fn total_supply(contract: Self::Address) -> Result<Balance, DispatchError>;
+
+	/// The balance of `who` in the ERC20 contract at `contract`.
+	fn balance_of(contract: Self::Address, who: &AccountId) -> Result<Balance, DispatchError>;
+
+	/// Transfer `amount` from `from` to `to` in the ERC20 contract at
+	/// `contract`.
+	fn transfer(contract: Self::Address, from: &AccountId, to: &AccountId, amount: Balance) -> DispatchResult;
+}
+
+/// Route ERC20 currencies to `Bridge` and the rest to `Other`.
+///
+/// ERC20 balances are owned by their contracts, so `deposit` and `withdraw`
+/// are unsupported and nothing could be slashed. The minimum balance of ERC20
+/// currencies is zero, and failed bridge queries are treated as zero.
+pub struct Erc20Adapter<AccountId, Bridge, IsErc20, Other>(marker::PhantomData<(AccountId, Bridge, IsErc20, Other)>);
+
+impl<AccountId, Bridge, IsErc20, Other> MultiCurrency<AccountId> for Erc20Adapter<AccountId, Bridge, IsErc20, Other>
+where
+	Other: MultiCurrency<AccountId>,
+	Bridge: Erc20Bridge<AccountId, Other::Balance>,
+	IsErc20: Convert<Other::CurrencyId, Option<Bridge::Address>>,
+{
+	type CurrencyId = Other::CurrencyId;
+	type Balance = Other::Balance;
+
+	fn minimum_balance(currency_id: Self::CurrencyId) -> Self::Balance {
+		match IsErc20::convert(currency_id) {
+			Some(_) => Zero::zero(),
+			None => Other::minimum_balance(currency_id),
+		}
+	}
+
+	fn total_issuance(currency_id: Self::CurrencyId) -> Self::Balance {
+		match IsErc20::convert(currency_id) {
+			Some(contract) => Bridge::total_supply(contract).unwrap_or_default(),
+			None => Other::total_issuance(currency_id),
+		}
+	}
+
+	fn total_balance(currency_id: Self::CurrencyId, who: &AccountId) -> Self::Balance {
+		match IsErc20::convert(currency_id) {
+			Some(contract) => Bridge::balance_of(contract, who).unwrap_or_default(),
+			None => Other::total_balance(currency_id, who),
+		}
+	}
+
+	fn free_balance(currency_id: Self::CurrencyId, who: &AccountId) -> Self::Balance {
+		match IsErc20::convert(currency_id) {
+			Some(contract) => Bridge::balance_of(contract, who).unwrap_or_default(),
+			None => Other::free_balance(currency_id, who),
+		}
+	}
+
+	fn ensure_can_withdraw(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		match IsErc20::convert(currency_id) {
+			Some(contract) => {
+				if Bridge::balance_of(contract, who)? >= amount {
+					Ok(())
+				} else {
+					Err(TokenError::NoFunds.into())
+				}
+			}
+			None => Other::ensure_can_withdraw(currency_id, who, amount),
+		}
+	}
+
+	fn transfer(
+		currency_id: Self::CurrencyId,
+		from: &AccountId,
+		to: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		match IsErc20::convert(currency_id) {
+			Some(contract) => Bridge::transfer(contract, from, to, amount),
+			None => Other::transfer(currency_id, from, to, amount),
+		}
+	}
+
+	fn transfer_with_existence_requirement(
+		currency_id: Self::CurrencyId,
+		from: &AccountId,
+		to: &AccountId,
+		amount: Self::Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		match IsErc20::convert(currency_id) {
+			// ERC20 balances have no existential deposit
+			Some(contract) => Bridge::transfer(contract, from, to, amount),
+			None => Other::transfer_with_existence_requirement(currency_id, from, to, amount, existence_requirement),
+		}
+	}
+
+	fn deposit(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		match IsErc20::convert(currency_id) {
+			Some(_) => Err(TokenError::Unsupported.into()),
+			None => Other::deposit(currency_id, who, amount),
+		}
+	}
+
+	fn withdraw(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		match IsErc20::convert(currency_id) {
+			Some(_) => Err(TokenError::Unsupported.into()),
+			None => Other::withdraw(currency_id, who, amount),
+		}
+	}
+
+	fn can_slash(currency_id: Self::CurrencyId, who: &AccountId, value: Self::Balance) -> bool {
+		match IsErc20::convert(currency_id) {
+			Some(_) => value.is_zero(),
+			None => Other::can_slash(currency_id, who, value),
+		}
+	}
+
+	fn slash(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> Self::Balance {
+		match IsErc20::convert(currency_id) {
+			Some(_) => amount,
+			None => Other::slash(currency_id, who, amount),
+		}
+	}
+}
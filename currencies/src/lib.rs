@@ -7,7 +7,8 @@
 //! multi-currency which implements `MultiCurrency`.
 //!
 //! It also provides an adapter, to adapt `frame_support::traits::Currency`
-//! implementations into `BasicCurrencyExtended`.
+//! implementations into `BasicCurrencyExtended`, and `Erc20Adapter` to use
+//! ERC20 contracts in an EVM as currencies through `MultiCurrency`.
 //!
 //! The currencies module provides functionality of both `MultiCurrencyExtended`
 //! and `BasicCurrencyExtended`, via unified interfaces, and all calls would be
@@ -15,6 +16,11 @@
 //! A native currency ID could be set by `Config::GetNativeCurrencyId`, to
 //! identify the native currency.
 //!
+//! `transfer_with_existence_requirement` transfers keep the sender alive on
+//! `KeepAlive`. Plain `transfer`s of other pallets use
+//! `Config::DefaultExistenceRequirement`, while the `transfer` calls allow
+//! death.
+//!
 //! ### Implementations
 //!
 //! The currencies module provides implementations for following traits.
@@ -35,6 +41,13 @@
 //! `Config::NativeCurrency`.
 //! - `update_balance` - Update balance by signed integer amount, in a given
 //!   currency, root origin required.
+//!
+//! ### Module Functions
+//!
+//! - `with_transaction_multi` - Apply a batch of transfer, deposit and
+//!   withdraw operations across currencies atomically.
+//! - `transfer_with_conversion` - Swap a currency to another one and transfer
+//!   the received amount atomically.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -51,24 +64,28 @@ use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
 use orml_traits::{
 	arithmetic::{Signed, SimpleArithmetic},
 	currency::TransferAll,
-	BalanceStatus, BasicCurrency, BasicCurrencyExtended, BasicLockableCurrency, BasicReservableCurrency,
-	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency,
+	BalanceStatus, BasicCurrency, BasicCurrencyExtended, BasicLockableCurrency, BasicReservableCurrency, HookWeight,
+	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency, OnDeposit,
+	OnSlash, OnTransfer, Swap,
 };
 use orml_utilities::with_transaction_result;
 use sp_runtime::{
-	traits::{CheckedSub, MaybeSerializeDeserialize, StaticLookup, Zero},
+	traits::{CheckedSub, MaybeSerializeDeserialize, Saturating, StaticLookup, Zero},
 	DispatchError, DispatchResult,
 };
 use sp_std::{
 	convert::{TryFrom, TryInto},
 	fmt::Debug,
 	marker, result,
+	vec::Vec,
 };
 
+mod erc20;
 mod mock;
 mod tests;
 mod weights;
 
+pub use erc20::{Erc20Adapter, Erc20Bridge};
 pub use module::*;
 pub use weights::WeightInfo;
 
@@ -99,6 +116,24 @@ pub mod module {
 		#[pallet::constant]
 		type GetNativeCurrencyId: Get<CurrencyIdOf<Self>>;
 
+		/// The existence requirement of `MultiCurrency::transfer` and
+		/// `BasicCurrency::transfer`, `KeepAlive` to never reap the senders
+		/// of transfers made by other pallets.
+		type DefaultExistenceRequirement: Get<ExistenceRequirement>;
+
+		/// Handler for when the native currency is slashed. The other
+		/// currencies call the handler of `MultiCurrency`, e.g. orml-tokens.
+		///
+		/// The weight returned by the hooks is charged to the calls of this
+		/// pallet, see `HookWeight`.
+		type OnSlash: OnSlash<Self::AccountId, CurrencyIdOf<Self>, BalanceOf<Self>>;
+
+		/// Handler for when the native currency is deposited.
+		type OnDeposit: OnDeposit<Self::AccountId, CurrencyIdOf<Self>, BalanceOf<Self>>;
+
+		/// Handler for when the native currency is transferred.
+		type OnTransfer: OnTransfer<Self::AccountId, CurrencyIdOf<Self>, BalanceOf<Self>>;
+
 		/// Weight information for extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -124,6 +159,16 @@ pub mod module {
 		Deposited(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
 		/// Withdraw success. \[currency_id, who, amount\]
 		Withdrawn(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Currency swapped and transferred. \[from, to, pay_currency_id,
+		/// paid_amount, receive_currency_id, received_amount\]
+		TransferredWithConversion(
+			T::AccountId,
+			T::AccountId,
+			CurrencyIdOf<T>,
+			BalanceOf<T>,
+			CurrencyIdOf<T>,
+			BalanceOf<T>,
+		),
 	}
 
 	#[pallet::pallet]
@@ -144,11 +189,21 @@ pub mod module {
 			dest: <T::Lookup as StaticLookup>::Source,
 			currency_id: CurrencyIdOf<T>,
 			#[pallet::compact] amount: BalanceOf<T>,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(dest)?;
-			<Self as MultiCurrency<T::AccountId>>::transfer(currency_id, &from, &to, amount)?;
-			Ok(())
+			let (result, hook_weight) = HookWeight::measure(|| {
+				<Self as MultiCurrency<T::AccountId>>::transfer_with_existence_requirement(
+					currency_id,
+					&from,
+					&to,
+					amount,
+					ExistenceRequirement::AllowDeath,
+				)
+			});
+			result?;
+			let weight = T::WeightInfo::transfer_non_native_currency();
+			Ok(Some(Self::cap_actual_weight(weight.saturating_add(hook_weight), weight)).into())
 		}
 
 		/// Transfer some native currency to another account.
@@ -160,13 +215,20 @@ pub mod module {
 			origin: OriginFor<T>,
 			dest: <T::Lookup as StaticLookup>::Source,
 			#[pallet::compact] amount: BalanceOf<T>,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(dest)?;
-			T::NativeCurrency::transfer(&from, &to, amount)?;
+			T::NativeCurrency::transfer_with_existence_requirement(
+				&from,
+				&to,
+				amount,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			let ((), hook_weight) = HookWeight::measure(|| Self::on_native_transferred(&from, &to, amount));
 
 			Self::deposit_event(Event::Transferred(T::GetNativeCurrencyId::get(), from, to, amount));
-			Ok(())
+			let weight = T::WeightInfo::transfer_native_currency();
+			Ok(Some(Self::cap_actual_weight(weight.saturating_add(hook_weight), weight)).into())
 		}
 
 		/// update amount of account `who` under `currency_id`.
@@ -178,13 +240,139 @@ pub mod module {
 			who: <T::Lookup as StaticLookup>::Source,
 			currency_id: CurrencyIdOf<T>,
 			amount: AmountOf<T>,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
 			let dest = T::Lookup::lookup(who)?;
-			<Self as MultiCurrencyExtended<T::AccountId>>::update_balance(currency_id, &dest, amount)?;
-			Ok(())
+			let (result, hook_weight) = HookWeight::measure(|| {
+				<Self as MultiCurrencyExtended<T::AccountId>>::update_balance(currency_id, &dest, amount)
+			});
+			result?;
+			let weight = T::WeightInfo::update_balance_non_native_currency();
+			Ok(Some(Self::cap_actual_weight(weight.saturating_add(hook_weight), weight)).into())
+		}
+	}
+}
+
+/// A single balance operation applied by `Pallet::with_transaction_multi`.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum Operation<AccountId, CurrencyId, Balance> {
+	/// Transfer `amount` of `currency_id` from `from` to `to`.
+	Transfer {
+		currency_id: CurrencyId,
+		from: AccountId,
+		to: AccountId,
+		amount: Balance,
+	},
+	/// Deposit `amount` of `currency_id` to `who`.
+	Deposit {
+		currency_id: CurrencyId,
+		who: AccountId,
+		amount: Balance,
+	},
+	/// Withdraw `amount` of `currency_id` from `who`.
+	Withdraw {
+		currency_id: CurrencyId,
+		who: AccountId,
+		amount: Balance,
+	},
+}
+
+pub type OperationOf<T> = Operation<<T as frame_system::Config>::AccountId, CurrencyIdOf<T>, BalanceOf<T>>;
+
+impl<T: Config> Pallet<T> {
+	/// Charge the `weight` consumed by the hooks of the native currency to
+	/// the call being measured, or register it to the block if not measured.
+	fn register_hook_weight(weight: Weight) {
+		if weight > 0 && !HookWeight::consume(weight) {
+			frame_system::Pallet::<T>::register_extra_weight_unchecked(weight, DispatchClass::Normal);
+		}
+	}
+
+	/// Cap the actual `weight` of a call at its `declared` weight, the excess
+	/// consumed by the hooks is registered to the block.
+	fn cap_actual_weight(weight: Weight, declared: Weight) -> Weight {
+		if weight > declared {
+			frame_system::Pallet::<T>::register_extra_weight_unchecked(weight - declared, DispatchClass::Normal);
+		}
+		weight.min(declared)
+	}
+
+	fn on_native_transferred(from: &T::AccountId, to: &T::AccountId, amount: BalanceOf<T>) {
+		if !amount.is_zero() && from != to {
+			Self::register_hook_weight(T::OnTransfer::on_transfer(
+				from,
+				to,
+				T::GetNativeCurrencyId::get(),
+				amount,
+			));
+		}
+	}
+
+	fn on_native_slashed(who: &T::AccountId, amount: BalanceOf<T>) {
+		if !amount.is_zero() {
+			Self::register_hook_weight(T::OnSlash::on_slash(who, T::GetNativeCurrencyId::get(), amount));
 		}
 	}
+
+	/// Apply `operations` in order, in a new storage transaction.
+	///
+	/// If any operation fails, all changes made by the previous operations
+	/// are rolled back and the error is returned.
+	pub fn with_transaction_multi(operations: Vec<OperationOf<T>>) -> DispatchResult {
+		with_transaction_result(|| {
+			for operation in operations {
+				match operation {
+					Operation::Transfer {
+						currency_id,
+						from,
+						to,
+						amount,
+					} => <Self as MultiCurrency<T::AccountId>>::transfer(currency_id, &from, &to, amount)?,
+					Operation::Deposit {
+						currency_id,
+						who,
+						amount,
+					} => <Self as MultiCurrency<T::AccountId>>::deposit(currency_id, &who, amount)?,
+					Operation::Withdraw {
+						currency_id,
+						who,
+						amount,
+					} => <Self as MultiCurrency<T::AccountId>>::withdraw(currency_id, &who, amount)?,
+				}
+			}
+			Ok(())
+		})
+	}
+
+	/// Swap `amount` of `pay_currency_id` of `from` to `receive_currency_id`
+	/// by `S`, and transfer the received amount to `to`, in a new storage
+	/// transaction. Returns the amount received by `to`.
+	///
+	/// If either the swap or the transfer fails, all changes are rolled back
+	/// and the error is returned.
+	pub fn transfer_with_conversion<S: Swap<T::AccountId, CurrencyIdOf<T>, BalanceOf<T>>>(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		pay_currency_id: CurrencyIdOf<T>,
+		receive_currency_id: CurrencyIdOf<T>,
+		amount: BalanceOf<T>,
+	) -> result::Result<BalanceOf<T>, DispatchError> {
+		let received = with_transaction_result(|| {
+			let received = S::swap(from, pay_currency_id, receive_currency_id, amount)?;
+			<Self as MultiCurrency<T::AccountId>>::transfer(receive_currency_id, from, to, received)?;
+			Ok(received)
+		})?;
+
+		Self::deposit_event(Event::TransferredWithConversion(
+			from.clone(),
+			to.clone(),
+			pay_currency_id,
+			amount,
+			receive_currency_id,
+			received,
+		));
+		Ok(received)
+	}
 }
 
 impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
@@ -236,14 +424,31 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 		from: &T::AccountId,
 		to: &T::AccountId,
 		amount: Self::Balance,
+	) -> DispatchResult {
+		Self::transfer_with_existence_requirement(currency_id, from, to, amount, T::DefaultExistenceRequirement::get())
+	}
+
+	fn transfer_with_existence_requirement(
+		currency_id: Self::CurrencyId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: Self::Balance,
+		existence_requirement: ExistenceRequirement,
 	) -> DispatchResult {
 		if amount.is_zero() || from == to {
 			return Ok(());
 		}
 		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::transfer(from, to, amount)?;
+			T::NativeCurrency::transfer_with_existence_requirement(from, to, amount, existence_requirement)?;
+			Self::on_native_transferred(from, to, amount);
 		} else {
-			T::MultiCurrency::transfer(currency_id, from, to, amount)?;
+			T::MultiCurrency::transfer_with_existence_requirement(
+				currency_id,
+				from,
+				to,
+				amount,
+				existence_requirement,
+			)?;
 		}
 		Self::deposit_event(Event::Transferred(currency_id, from.clone(), to.clone(), amount));
 		Ok(())
@@ -255,6 +460,7 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 		}
 		if currency_id == T::GetNativeCurrencyId::get() {
 			T::NativeCurrency::deposit(who, amount)?;
+			Self::register_hook_weight(T::OnDeposit::on_deposit(who, currency_id, amount));
 		} else {
 			T::MultiCurrency::deposit(currency_id, who, amount)?;
 		}
@@ -285,7 +491,9 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 
 	fn slash(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> Self::Balance {
 		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::slash(who, amount)
+			let remaining = T::NativeCurrency::slash(who, amount);
+			Self::on_native_slashed(who, amount.saturating_sub(remaining));
+			remaining
 		} else {
 			T::MultiCurrency::slash(currency_id, who, amount)
 		}
@@ -355,7 +563,9 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 
 	fn slash_reserved(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
 		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::slash_reserved(who, value)
+			let remaining = T::NativeCurrency::slash_reserved(who, value);
+			Self::on_native_slashed(who, value.saturating_sub(remaining));
+			remaining
 		} else {
 			T::MultiCurrency::slash_reserved(currency_id, who, value)
 		}
@@ -433,6 +643,21 @@ where
 		<Pallet<T> as MultiCurrency<T::AccountId>>::transfer(GetCurrencyId::get(), from, to, amount)
 	}
 
+	fn transfer_with_existence_requirement(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: Self::Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		<Pallet<T> as MultiCurrency<T::AccountId>>::transfer_with_existence_requirement(
+			GetCurrencyId::get(),
+			from,
+			to,
+			amount,
+			existence_requirement,
+		)
+	}
+
 	fn deposit(who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
 		<Pallet<T>>::deposit(GetCurrencyId::get(), who, amount)
 	}
@@ -564,7 +789,16 @@ where
 	}
 
 	fn transfer(from: &AccountId, to: &AccountId, amount: Self::Balance) -> DispatchResult {
-		Currency::transfer(from, to, amount, ExistenceRequirement::AllowDeath)
+		Currency::transfer(from, to, amount, T::DefaultExistenceRequirement::get())
+	}
+
+	fn transfer_with_existence_requirement(
+		from: &AccountId,
+		to: &AccountId,
+		amount: Self::Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		Currency::transfer(from, to, amount, existence_requirement)
 	}
 
 	fn deposit(who: &AccountId, amount: Self::Balance) -> DispatchResult {
@@ -692,7 +926,10 @@ impl<T: Config> TransferAll<T::AccountId> for Pallet<T> {
 			T::MultiCurrency::transfer_all(source, dest)?;
 
 			// transfer all free to dest
-			T::NativeCurrency::transfer(source, dest, T::NativeCurrency::free_balance(source))
+			let amount = T::NativeCurrency::free_balance(source);
+			T::NativeCurrency::transfer(source, dest, amount)?;
+			Self::on_native_transferred(source, dest, amount);
+			Ok(())
 		})
 	}
 }
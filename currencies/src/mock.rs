@@ -9,12 +9,13 @@ use frame_support::{
 	PalletId,
 };
 use orml_traits::parameter_type_with_key;
-use sp_core::H256;
+use sp_core::{H160, H256};
 use sp_runtime::{
 	testing::Header,
-	traits::{AccountIdConversion, IdentityLookup},
+	traits::{AccountIdConversion, Convert, IdentityLookup},
 	AccountId32,
 };
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap};
 
 use crate as currencies;
 
@@ -87,8 +88,18 @@ impl orml_tokens::Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type OnDust = orml_tokens::TransferDust<Runtime, DustAccount>;
+	type OnSlash = ();
+	type OnDeposit = ();
+	type OnTransfer = ();
 	type MaxLocks = MaxLocks;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
 	type DustRemovalWhitelist = Nothing;
+	type OnNewTokenAccount = ();
+	type OnKilledTokenAccount = ();
+	type TransferFee = ();
+	type TransferFeeExemptions = Nothing;
+	type OnLockChanged = ();
 }
 
 pub const NATIVE_CURRENCY_ID: CurrencyId = 1;
@@ -96,6 +107,7 @@ pub const X_TOKEN_ID: CurrencyId = 2;
 
 parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = NATIVE_CURRENCY_ID;
+	pub const DefaultExistenceRequirement: ExistenceRequirement = ExistenceRequirement::AllowDeath;
 }
 
 impl Config for Runtime {
@@ -103,11 +115,104 @@ impl Config for Runtime {
 	type MultiCurrency = Tokens;
 	type NativeCurrency = AdaptedBasicCurrency;
 	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type DefaultExistenceRequirement = DefaultExistenceRequirement;
+	type OnSlash = TrackNativeChanges;
+	type OnDeposit = ();
+	type OnTransfer = TrackNativeChanges;
 	type WeightInfo = ();
 }
+thread_local! {
+	pub static NATIVE_SLASHES: RefCell<Vec<(AccountId, Balance)>> = RefCell::new(vec![]);
+	pub static NATIVE_TRANSFERS: RefCell<Vec<(AccountId, AccountId, Balance)>> = RefCell::new(vec![]);
+}
+
+pub struct TrackNativeChanges;
+impl OnSlash<AccountId, CurrencyId, Balance> for TrackNativeChanges {
+	fn on_slash(who: &AccountId, _currency_id: CurrencyId, amount: Balance) -> Weight {
+		NATIVE_SLASHES.with(|v| v.borrow_mut().push((who.clone(), amount)));
+		0
+	}
+}
+impl OnTransfer<AccountId, CurrencyId, Balance> for TrackNativeChanges {
+	fn on_transfer(from: &AccountId, to: &AccountId, _currency_id: CurrencyId, amount: Balance) -> Weight {
+		NATIVE_TRANSFERS.with(|v| v.borrow_mut().push((from.clone(), to.clone(), amount)));
+		0
+	}
+}
+
 pub type NativeCurrency = NativeCurrencyOf<Runtime>;
 pub type AdaptedBasicCurrency = BasicCurrencyAdapter<Runtime, PalletBalances, i64, u64>;
 
+pub const ERC20_ID: CurrencyId = 3;
+pub const ERC20_ADDRESS: H160 = H160::repeat_byte(3);
+
+thread_local! {
+	pub static ERC20_BALANCES: RefCell<BTreeMap<(H160, AccountId), Balance>> = RefCell::new(BTreeMap::new());
+}
+
+pub struct MockErc20Bridge;
+impl Erc20Bridge<AccountId, Balance> for MockErc20Bridge {
+	type Address = H160;
+
+	fn total_supply(contract: H160) -> Result<Balance, DispatchError> {
+		Ok(ERC20_BALANCES.with(|v| {
+			v.borrow()
+				.iter()
+				.filter(|((address, _), _)| *address == contract)
+				.map(|(_, balance)| balance)
+				.sum()
+		}))
+	}
+
+	fn balance_of(contract: H160, who: &AccountId) -> Result<Balance, DispatchError> {
+		Ok(ERC20_BALANCES.with(|v| v.borrow().get(&(contract, who.clone())).copied().unwrap_or_default()))
+	}
+
+	fn transfer(contract: H160, from: &AccountId, to: &AccountId, amount: Balance) -> DispatchResult {
+		ERC20_BALANCES.with(|v| {
+			let mut balances = v.borrow_mut();
+			let from_balance = balances.entry((contract, from.clone())).or_default();
+			*from_balance = from_balance
+				.checked_sub(amount)
+				.ok_or(DispatchError::Other("ERC20 transfer failed"))?;
+			*balances.entry((contract, to.clone())).or_default() += amount;
+			Ok(())
+		})
+	}
+}
+
+pub struct IsErc20;
+impl Convert<CurrencyId, Option<H160>> for IsErc20 {
+	fn convert(currency_id: CurrencyId) -> Option<H160> {
+		if currency_id == ERC20_ID {
+			Some(ERC20_ADDRESS)
+		} else {
+			None
+		}
+	}
+}
+
+pub type Erc20Currencies = Erc20Adapter<AccountId, MockErc20Bridge, IsErc20, Currencies>;
+
+/// Swaps at a rate of 1 to 2, only from `X_TOKEN_ID` to the native currency.
+pub struct MockSwap;
+impl Swap<AccountId, CurrencyId, Balance> for MockSwap {
+	fn swap(
+		who: &AccountId,
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		supply_amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		if supply_currency_id != X_TOKEN_ID || target_currency_id != NATIVE_CURRENCY_ID {
+			return Err(DispatchError::Other("no liquidity"));
+		}
+		let target_amount = supply_amount * 2;
+		Currencies::withdraw(supply_currency_id, who, supply_amount)?;
+		Currencies::deposit(target_currency_id, who, target_amount)?;
+		Ok(target_amount)
+	}
+}
+
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
 type Block = frame_system::mocking::MockBlock<Runtime>;
 
@@ -131,11 +236,15 @@ pub const ID_1: LockIdentifier = *b"1       ";
 
 pub struct ExtBuilder {
 	balances: Vec<(AccountId, CurrencyId, Balance)>,
+	erc20_balances: Vec<(AccountId, Balance)>,
 }
 
 impl Default for ExtBuilder {
 	fn default() -> Self {
-		Self { balances: vec![] }
+		Self {
+			balances: vec![],
+			erc20_balances: vec![],
+		}
 	}
 }
 
@@ -145,6 +254,11 @@ impl ExtBuilder {
 		self
 	}
 
+	pub fn erc20_balances(mut self, erc20_balances: Vec<(AccountId, Balance)>) -> Self {
+		self.erc20_balances = erc20_balances;
+		self
+	}
+
 	pub fn one_hundred_for_alice_n_bob(self) -> Self {
 		self.balances(vec![
 			(ALICE, NATIVE_CURRENCY_ID, 100),
@@ -174,6 +288,7 @@ impl ExtBuilder {
 		orml_tokens::GenesisConfig::<Runtime> {
 			balances: self
 				.balances
+				.clone()
 				.into_iter()
 				.filter(|(_, currency_id, _)| *currency_id != NATIVE_CURRENCY_ID)
 				.collect::<Vec<_>>(),
@@ -181,6 +296,14 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		ERC20_BALANCES.with(|v| {
+			*v.borrow_mut() = self
+				.erc20_balances
+				.into_iter()
+				.map(|(who, balance)| ((ERC20_ADDRESS, who), balance))
+				.collect()
+		});
+
 		t.into()
 	}
 }
@@ -5,7 +5,7 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{Event, *};
-use sp_runtime::traits::BadOrigin;
+use sp_runtime::{traits::BadOrigin, TokenError};
 
 #[test]
 fn multi_lockable_currency_should_work() {
@@ -131,6 +131,26 @@ fn native_currency_should_work() {
 		});
 }
 
+#[test]
+fn native_currency_hooks_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Currencies::transfer_native_currency(Some(ALICE).into(), BOB, 50));
+			assert_ok!(NativeCurrency::transfer(&ALICE, &BOB, 10));
+			assert_ok!(Currencies::transfer(Some(ALICE).into(), BOB, X_TOKEN_ID, 10));
+			assert_eq!(Currencies::slash(NATIVE_CURRENCY_ID, &ALICE, 10), 0);
+			assert_eq!(Currencies::slash(X_TOKEN_ID, &ALICE, 10), 0);
+
+			assert_eq!(
+				NATIVE_TRANSFERS.with(|v| v.borrow().clone()),
+				vec![(ALICE, BOB, 50), (ALICE, BOB, 10)]
+			);
+			assert_eq!(NATIVE_SLASHES.with(|v| v.borrow().clone()), vec![(ALICE, 10)]);
+		});
+}
+
 #[test]
 fn native_currency_extended_should_work() {
 	ExtBuilder::default()
@@ -166,6 +186,53 @@ fn basic_currency_adapting_pallet_balances_transfer() {
 		});
 }
 
+#[test]
+fn transfer_with_existence_requirement_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Currencies::transfer_with_existence_requirement(
+					NATIVE_CURRENCY_ID,
+					&ALICE,
+					&BOB,
+					99,
+					ExistenceRequirement::KeepAlive
+				),
+				pallet_balances::Error::<Runtime>::KeepAlive
+			);
+			assert_noop!(
+				AdaptedBasicCurrency::transfer_with_existence_requirement(
+					&ALICE,
+					&BOB,
+					99,
+					ExistenceRequirement::KeepAlive
+				),
+				pallet_balances::Error::<Runtime>::KeepAlive
+			);
+
+			assert_ok!(Currencies::transfer_with_existence_requirement(
+				NATIVE_CURRENCY_ID,
+				&ALICE,
+				&BOB,
+				98,
+				ExistenceRequirement::KeepAlive
+			));
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 2);
+
+			assert_ok!(Currencies::transfer_with_existence_requirement(
+				NATIVE_CURRENCY_ID,
+				&ALICE,
+				&BOB,
+				2,
+				ExistenceRequirement::AllowDeath
+			));
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 0);
+			assert_eq!(NativeCurrency::free_balance(&BOB), 200);
+		});
+}
+
 #[test]
 fn basic_currency_adapting_pallet_balances_deposit() {
 	ExtBuilder::default()
@@ -293,3 +360,150 @@ fn call_event_should_work() {
 			System::assert_last_event(Event::Currencies(crate::Event::Withdrawn(X_TOKEN_ID, ALICE, 20)));
 		});
 }
+
+#[test]
+fn with_transaction_multi_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Currencies::with_transaction_multi(vec![
+				Operation::Transfer {
+					currency_id: X_TOKEN_ID,
+					from: ALICE,
+					to: BOB,
+					amount: 50,
+				},
+				Operation::Deposit {
+					currency_id: NATIVE_CURRENCY_ID,
+					who: ALICE,
+					amount: 20,
+				},
+				Operation::Withdraw {
+					currency_id: X_TOKEN_ID,
+					who: BOB,
+					amount: 30,
+				},
+			]));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 50);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &BOB), 120);
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 120);
+			assert_eq!(Currencies::total_issuance(X_TOKEN_ID), 170);
+		});
+}
+
+#[test]
+fn with_transaction_multi_should_rollback_on_failure() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Currencies::with_transaction_multi(vec![
+					Operation::Transfer {
+						currency_id: X_TOKEN_ID,
+						from: ALICE,
+						to: BOB,
+						amount: 50,
+					},
+					Operation::Deposit {
+						currency_id: NATIVE_CURRENCY_ID,
+						who: ALICE,
+						amount: 20,
+					},
+					Operation::Withdraw {
+						currency_id: X_TOKEN_ID,
+						who: ALICE,
+						amount: 60,
+					},
+				]),
+				orml_tokens::Error::<Runtime>::BalanceTooLow
+			);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 100);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &BOB), 100);
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 100);
+		});
+}
+
+#[test]
+fn erc20_adapter_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.erc20_balances(vec![(ALICE, 100), (BOB, 50)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(Erc20Currencies::total_issuance(ERC20_ID), 150);
+			assert_eq!(Erc20Currencies::free_balance(ERC20_ID, &ALICE), 100);
+			assert_eq!(Erc20Currencies::total_balance(ERC20_ID, &BOB), 50);
+			assert_eq!(Erc20Currencies::minimum_balance(ERC20_ID), 0);
+			assert_ok!(Erc20Currencies::ensure_can_withdraw(ERC20_ID, &ALICE, 100));
+			assert_noop!(
+				Erc20Currencies::ensure_can_withdraw(ERC20_ID, &ALICE, 101),
+				TokenError::NoFunds
+			);
+
+			assert_ok!(Erc20Currencies::transfer(ERC20_ID, &ALICE, &BOB, 30));
+			assert_eq!(Erc20Currencies::free_balance(ERC20_ID, &ALICE), 70);
+			assert_eq!(Erc20Currencies::free_balance(ERC20_ID, &BOB), 80);
+			assert!(Erc20Currencies::transfer(ERC20_ID, &ALICE, &BOB, 71).is_err());
+
+			assert_noop!(Erc20Currencies::deposit(ERC20_ID, &ALICE, 10), TokenError::Unsupported);
+			assert_noop!(Erc20Currencies::withdraw(ERC20_ID, &ALICE, 10), TokenError::Unsupported);
+			assert!(!Erc20Currencies::can_slash(ERC20_ID, &ALICE, 10));
+			assert_eq!(Erc20Currencies::slash(ERC20_ID, &ALICE, 10), 10);
+
+			// other currencies are routed to `Currencies`
+			assert_ok!(Erc20Currencies::transfer(X_TOKEN_ID, &ALICE, &BOB, 50));
+			assert_eq!(Erc20Currencies::free_balance(X_TOKEN_ID, &BOB), 150);
+			assert_eq!(Erc20Currencies::total_issuance(NATIVE_CURRENCY_ID), 200);
+		});
+}
+
+#[test]
+fn transfer_with_conversion_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_eq!(
+				Currencies::transfer_with_conversion::<MockSwap>(&ALICE, &BOB, X_TOKEN_ID, NATIVE_CURRENCY_ID, 30),
+				Ok(60)
+			);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 70);
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 100);
+			assert_eq!(NativeCurrency::free_balance(&BOB), 160);
+			System::assert_last_event(Event::Currencies(crate::Event::TransferredWithConversion(
+				ALICE,
+				BOB,
+				X_TOKEN_ID,
+				30,
+				NATIVE_CURRENCY_ID,
+				60,
+			)));
+		});
+}
+
+#[test]
+fn transfer_with_conversion_should_rollback_on_failure() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Currencies::transfer_with_conversion::<MockSwap>(&ALICE, &BOB, NATIVE_CURRENCY_ID, X_TOKEN_ID, 30),
+				DispatchError::Other("no liquidity")
+			);
+
+			// the swap succeeds but the received amount is locked, the swap is
+			// rolled back
+			assert_ok!(Currencies::set_lock(ID_1, NATIVE_CURRENCY_ID, &ALICE, 200));
+			assert_noop!(
+				Currencies::transfer_with_conversion::<MockSwap>(&ALICE, &BOB, X_TOKEN_ID, NATIVE_CURRENCY_ID, 30),
+				pallet_balances::Error::<Runtime>::LiquidityRestrictions
+			);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 100);
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 100);
+		});
+}
@@ -0,0 +1,32 @@
+//! Benchmarks for the delay tasks module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+
+fn add_task<T: Config>() -> Result<DelayedTaskId, &'static str> {
+	let id = Pallet::<T>::next_delayed_task_id();
+	Pallet::<T>::add_delay_task(T::BenchmarkHelper::task(), One::one())?;
+	Ok(id)
+}
+
+benchmarks! {
+	cancel_delayed_task {
+		let id = add_task::<T>()?;
+	}: _<T::Origin>(T::GovernanceOrigin::successful_origin(), id)
+	verify {
+		assert!(!DelayedTasks::<T>::contains_key(id));
+	}
+
+	reset_execute_time {
+		let id = add_task::<T>()?;
+		let when = DispatchTime::After(10u32.into());
+	}: _<T::Origin>(T::GovernanceOrigin::successful_origin(), id, when)
+	verify {
+		let execute_at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		assert!(DelayedTaskQueue::<T>::contains_key(execute_at, id));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::default().build(), crate::mock::Runtime);
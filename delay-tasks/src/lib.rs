@@ -0,0 +1,262 @@
+//! # Delay Tasks
+//!
+//! ## Overview
+//!
+//! This module executes tasks after a delay.
+//!
+//! Other pallets add tasks through the `DelayTasksManager` implementation of
+//! this module. Tasks implement `DelayedTask`, they are validated by
+//! `pre_check` when added and again right before being dispatched in
+//! `on_initialize` of the block they are scheduled at. At most
+//! `MaxTaskWeightPerBlock` of tasks, including the bookkeeping of the queue,
+//! are dispatched in a block, the rest are carried over to the next blocks,
+//! keeping their execution time. Tasks failing the check or the dispatch are
+//! kept in `DeadLetters`.
+//!
+//! `GovernanceOrigin` can cancel tasks or reset their execution time.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, traits::schedule::DispatchTime};
+use frame_system::pallet_prelude::*;
+use orml_traits::{DelayTasksManager, DelayedTask};
+use orml_utilities::with_transaction_result;
+use sp_runtime::traits::{One, Saturating};
+
+mod benchmarking;
+mod mock;
+mod tests;
+mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// Provide a task to add in benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<Task> {
+	/// A task passing `pre_check`.
+	fn task() -> Task;
+}
+
+/// The delayed task ID type.
+pub type DelayedTaskId = u64;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The task type.
+		type Task: DelayedTask + Parameter;
+
+		/// The origin which may cancel tasks or reset their execution time.
+		type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum weight of tasks dispatched in a block, including the
+		/// bookkeeping of the queue.
+		#[pallet::constant]
+		type MaxTaskWeightPerBlock: Get<Weight>;
+
+		/// Helper to provide a task in benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::Task>;
+
+		/// Weight information for extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The task does not exist.
+		TaskNotFound,
+		/// The execution time is not in the future.
+		InvalidExecutionTime,
+		/// No available task ID.
+		NoAvailableTaskId,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A task is added. [id, execute_at]
+		DelayedTaskAdded(DelayedTaskId, T::BlockNumber),
+		/// A task is executed. [id]
+		DelayedTaskExecuted(DelayedTaskId),
+		/// A task failed and was moved to dead letters. [id, error]
+		DelayedTaskFailed(DelayedTaskId, DispatchError),
+		/// The execution time of a task is reset. [id, execute_at]
+		DelayedTaskReset(DelayedTaskId, T::BlockNumber),
+		/// A task is cancelled. [id]
+		DelayedTaskCancelled(DelayedTaskId),
+	}
+
+	/// Track the next task ID.
+	#[pallet::storage]
+	#[pallet::getter(fn next_delayed_task_id)]
+	pub type NextDelayedTaskId<T: Config> = StorageValue<_, DelayedTaskId, ValueQuery>;
+
+	/// The pending tasks and their execution time.
+	///
+	/// map DelayedTaskId => Option<(Task, BlockNumber)>
+	#[pallet::storage]
+	#[pallet::getter(fn delayed_tasks)]
+	pub type DelayedTasks<T: Config> =
+		StorageMap<_, Twox64Concat, DelayedTaskId, (T::Task, T::BlockNumber), OptionQuery>;
+
+	/// Index pending tasks by execution time.
+	///
+	/// double_map BlockNumber, DelayedTaskId => ()
+	#[pallet::storage]
+	#[pallet::getter(fn delayed_task_queue)]
+	pub type DelayedTaskQueue<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, DelayedTaskId, (), OptionQuery>;
+
+	/// The earliest block of which queued tasks may not all be dispatched
+	/// yet, the tasks of earlier blocks are all dispatched.
+	#[pallet::storage]
+	#[pallet::getter(fn next_queued_block)]
+	pub type NextQueuedBlock<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
+	/// The failed tasks and their errors.
+	///
+	/// map DelayedTaskId => Option<(Task, DispatchError)>
+	#[pallet::storage]
+	#[pallet::getter(fn dead_letters)]
+	pub type DeadLetters<T: Config> = StorageMap<_, Twox64Concat, DelayedTaskId, (T::Task, DispatchError), OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let max_weight = T::MaxTaskWeightPerBlock::get();
+			// read and write the cursor
+			let mut used_weight = db_weight.reads_writes(1, 1);
+			let mut dispatched = false;
+			let mut block = Self::next_queued_block().unwrap_or(now);
+
+			while block <= now {
+				let id = match DelayedTaskQueue::<T>::iter_prefix(block).next() {
+					Some((id, _)) => id,
+					None => {
+						// all tasks of the block are dispatched
+						if used_weight.saturating_add(db_weight.reads(1)) > max_weight {
+							break;
+						}
+						used_weight = used_weight.saturating_add(db_weight.reads(1));
+						block = block.saturating_add(One::one());
+						continue;
+					}
+				};
+				let task = DelayedTasks::<T>::get(id).map(|(task, _)| task);
+
+				// read and remove the queue entry and the task, and insert the dead letter
+				let cost = task
+					.as_ref()
+					.map_or(0, |task| task.weight())
+					.saturating_add(db_weight.reads_writes(2, 3));
+				// the first task is always dispatched, so a task heavier than
+				// `MaxTaskWeightPerBlock` isn't carried over forever
+				if dispatched && used_weight.saturating_add(cost) > max_weight {
+					break;
+				}
+				used_weight = used_weight.saturating_add(cost);
+
+				DelayedTaskQueue::<T>::remove(block, id);
+				let task = match task {
+					Some(task) => task,
+					None => continue,
+				};
+				DelayedTasks::<T>::remove(id);
+				dispatched = true;
+
+				let result = with_transaction_result(|| {
+					task.pre_check()?;
+					task.clone().dispatch()
+				});
+				match result {
+					Ok(()) => Self::deposit_event(Event::DelayedTaskExecuted(id)),
+					Err(e) => {
+						DeadLetters::<T>::insert(id, (task, e));
+						Self::deposit_event(Event::DelayedTaskFailed(id, e));
+					}
+				}
+			}
+			NextQueuedBlock::<T>::put(block);
+
+			used_weight
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Cancel a pending task.
+		///
+		/// The dispatch origin for this call must be `GovernanceOrigin`.
+		#[pallet::weight(T::WeightInfo::cancel_delayed_task())]
+		pub fn cancel_delayed_task(origin: OriginFor<T>, id: DelayedTaskId) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			let (_, execute_at) = DelayedTasks::<T>::take(id).ok_or(Error::<T>::TaskNotFound)?;
+			DelayedTaskQueue::<T>::remove(execute_at, id);
+
+			Self::deposit_event(Event::DelayedTaskCancelled(id));
+			Ok(())
+		}
+
+		/// Reset the execution time of a pending task.
+		///
+		/// The dispatch origin for this call must be `GovernanceOrigin`.
+		#[pallet::weight(T::WeightInfo::reset_execute_time())]
+		pub fn reset_execute_time(
+			origin: OriginFor<T>,
+			id: DelayedTaskId,
+			when: DispatchTime<T::BlockNumber>,
+		) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			let new_execute_at = match when {
+				DispatchTime::At(x) => x,
+				DispatchTime::After(x) => now.saturating_add(x),
+			};
+			ensure!(new_execute_at > now, Error::<T>::InvalidExecutionTime);
+
+			DelayedTasks::<T>::try_mutate(id, |maybe_task| -> DispatchResult {
+				let (_, execute_at) = maybe_task.as_mut().ok_or(Error::<T>::TaskNotFound)?;
+				DelayedTaskQueue::<T>::remove(*execute_at, id);
+				DelayedTaskQueue::<T>::insert(new_execute_at, id, ());
+				*execute_at = new_execute_at;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::DelayedTaskReset(id, new_execute_at));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> DelayTasksManager<T::Task, T::BlockNumber> for Pallet<T> {
+	fn add_delay_task(task: T::Task, delay_blocks: T::BlockNumber) -> DispatchResult {
+		task.pre_check()?;
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let execute_at = now.saturating_add(delay_blocks);
+		ensure!(execute_at > now, Error::<T>::InvalidExecutionTime);
+
+		let id = NextDelayedTaskId::<T>::try_mutate(|id| -> Result<DelayedTaskId, DispatchError> {
+			let current_id = *id;
+			*id = id.checked_add(1).ok_or(Error::<T>::NoAvailableTaskId)?;
+			Ok(current_id)
+		})?;
+		DelayedTasks::<T>::insert(id, (task, execute_at));
+		DelayedTaskQueue::<T>::insert(execute_at, id, ());
+
+		Self::deposit_event(Event::DelayedTaskAdded(id, execute_at));
+		Ok(())
+	}
+}
@@ -0,0 +1,157 @@
+//! Mocks for the delay tasks module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{construct_runtime, parameter_types, traits::Everything};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup, DispatchResult, RuntimeDebug};
+use sp_std::cell::RefCell;
+
+use crate as delay_tasks;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = Everything;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+thread_local! {
+	pub static DISPATCHED: RefCell<Vec<u32>> = RefCell::new(vec![]);
+	pub static VALID: RefCell<bool> = RefCell::new(true);
+}
+
+/// A task recording its value when dispatched, or failing the dispatch
+/// after writing to storage.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum MockTask {
+	Record(u32, Weight),
+	Fail,
+}
+
+impl DelayedTask for MockTask {
+	fn pre_check(&self) -> DispatchResult {
+		if VALID.with(|v| *v.borrow()) {
+			Ok(())
+		} else {
+			Err(DispatchError::Other("invalid"))
+		}
+	}
+
+	fn weight(&self) -> Weight {
+		match self {
+			MockTask::Record(_, weight) => *weight,
+			MockTask::Fail => 10,
+		}
+	}
+
+	fn dispatch(self) -> DispatchResult {
+		match self {
+			MockTask::Record(value, _) => {
+				DISPATCHED.with(|v| v.borrow_mut().push(value));
+				Ok(())
+			}
+			MockTask::Fail => {
+				NextDelayedTaskId::<Runtime>::put(100);
+				Err(DispatchError::Other("failed"))
+			}
+		}
+	}
+}
+
+pub fn dispatched() -> Vec<u32> {
+	DISPATCHED.with(|v| v.borrow().clone())
+}
+
+pub fn set_valid(valid: bool) {
+	VALID.with(|v| *v.borrow_mut() = valid);
+}
+
+parameter_types! {
+	pub const MaxTaskWeightPerBlock: Weight = 100;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct TaskProvider;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<MockTask> for TaskProvider {
+	fn task() -> MockTask {
+		MockTask::Record(1, 10)
+	}
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type Task = MockTask;
+	type GovernanceOrigin = EnsureRoot<AccountId>;
+	type MaxTaskWeightPerBlock = MaxTaskWeightPerBlock;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = TaskProvider;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		DelayTasks: delay_tasks::{Pallet, Storage, Call, Event<T>},
+	}
+);
+
+pub struct ExtBuilder;
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		ExtBuilder
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		DISPATCHED.with(|v| v.borrow_mut().clear());
+		set_valid(true);
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
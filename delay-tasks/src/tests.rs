@@ -0,0 +1,165 @@
+//! Unit tests for the delay tasks module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn add_delay_task_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(DelayTasks::add_delay_task(MockTask::Record(1, 10), 2));
+		System::assert_last_event(Event::DelayTasks(crate::Event::DelayedTaskAdded(0, 3)));
+		assert_eq!(DelayTasks::delayed_tasks(0), Some((MockTask::Record(1, 10), 3)));
+		assert_eq!(DelayTasks::delayed_task_queue(3, 0), Some(()));
+		assert_eq!(DelayTasks::next_delayed_task_id(), 1);
+
+		assert_noop!(
+			DelayTasks::add_delay_task(MockTask::Record(1, 10), 0),
+			Error::<Runtime>::InvalidExecutionTime
+		);
+
+		set_valid(false);
+		assert_noop!(
+			DelayTasks::add_delay_task(MockTask::Record(1, 10), 2),
+			DispatchError::Other("invalid")
+		);
+	});
+}
+
+#[test]
+fn tasks_are_dispatched_on_time() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(DelayTasks::add_delay_task(MockTask::Record(1, 10), 1));
+		assert_ok!(DelayTasks::add_delay_task(MockTask::Record(2, 10), 2));
+
+		DelayTasks::on_initialize(1);
+		assert!(dispatched().is_empty());
+
+		DelayTasks::on_initialize(2);
+		assert_eq!(dispatched(), vec![1]);
+		System::assert_last_event(Event::DelayTasks(crate::Event::DelayedTaskExecuted(0)));
+		assert_eq!(DelayTasks::delayed_tasks(0), None);
+
+		DelayTasks::on_initialize(3);
+		assert_eq!(dispatched(), vec![1, 2]);
+		assert_eq!(DelayTasks::delayed_tasks(1), None);
+		assert_eq!(DelayTasks::delayed_task_queue(3, 1), None);
+	});
+}
+
+#[test]
+fn tasks_exceeding_block_weight_are_carried_over() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(DelayTasks::add_delay_task(MockTask::Record(1, 60), 1));
+		assert_ok!(DelayTasks::add_delay_task(MockTask::Record(2, 60), 1));
+		// heavier than the limit, dispatched alone
+		assert_ok!(DelayTasks::add_delay_task(MockTask::Record(3, 200), 2));
+
+		assert_eq!(DelayTasks::on_initialize(2), 60);
+		assert_eq!(dispatched().len(), 1);
+		// the other task keeps its execution time
+		assert_eq!(
+			DelayTasks::delayed_task_queue(2, 0).is_some() as u8 + DelayTasks::delayed_task_queue(2, 1).is_some() as u8,
+			1
+		);
+		assert_eq!(DelayTasks::next_queued_block(), Some(2));
+
+		// the carried over task is dispatched first
+		assert_eq!(DelayTasks::on_initialize(3), 60);
+		let mut values = dispatched();
+		values.sort_unstable();
+		assert_eq!(values, vec![1, 2]);
+		assert_eq!(DelayTasks::next_queued_block(), Some(3));
+
+		assert_eq!(DelayTasks::on_initialize(4), 200);
+		assert_eq!(dispatched().len(), 3);
+		assert_eq!(DelayTasks::delayed_tasks(2), None);
+	});
+}
+
+#[test]
+fn failed_tasks_go_to_dead_letters() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(DelayTasks::add_delay_task(MockTask::Fail, 1));
+		assert_ok!(DelayTasks::add_delay_task(MockTask::Record(1, 10), 1));
+
+		set_valid(false);
+		DelayTasks::on_initialize(2);
+		assert!(dispatched().is_empty());
+		assert_eq!(
+			DelayTasks::dead_letters(1),
+			Some((MockTask::Record(1, 10), DispatchError::Other("invalid")))
+		);
+		System::assert_has_event(Event::DelayTasks(crate::Event::DelayedTaskFailed(
+			1,
+			DispatchError::Other("invalid"),
+		)));
+
+		set_valid(true);
+		assert_ok!(DelayTasks::add_delay_task(MockTask::Fail, 1));
+		DelayTasks::on_initialize(3);
+		assert_eq!(
+			DelayTasks::dead_letters(2),
+			Some((MockTask::Fail, DispatchError::Other("failed")))
+		);
+		// storage changes of the failed dispatch are reverted
+		assert_eq!(DelayTasks::next_delayed_task_id(), 3);
+		assert_eq!(DelayTasks::delayed_tasks(2), None);
+	});
+}
+
+#[test]
+fn cancel_delayed_task_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(DelayTasks::add_delay_task(MockTask::Record(1, 10), 1));
+		assert_noop!(DelayTasks::cancel_delayed_task(Origin::signed(1), 0), BadOrigin);
+		assert_noop!(
+			DelayTasks::cancel_delayed_task(Origin::root(), 1),
+			Error::<Runtime>::TaskNotFound
+		);
+
+		assert_ok!(DelayTasks::cancel_delayed_task(Origin::root(), 0));
+		System::assert_last_event(Event::DelayTasks(crate::Event::DelayedTaskCancelled(0)));
+		assert_eq!(DelayTasks::delayed_tasks(0), None);
+		assert_eq!(DelayTasks::delayed_task_queue(2, 0), None);
+
+		DelayTasks::on_initialize(2);
+		assert!(dispatched().is_empty());
+	});
+}
+
+#[test]
+fn reset_execute_time_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(DelayTasks::add_delay_task(MockTask::Record(1, 10), 1));
+		assert_noop!(
+			DelayTasks::reset_execute_time(Origin::signed(1), 0, DispatchTime::At(5)),
+			BadOrigin
+		);
+		assert_noop!(
+			DelayTasks::reset_execute_time(Origin::root(), 0, DispatchTime::At(1)),
+			Error::<Runtime>::InvalidExecutionTime
+		);
+		assert_noop!(
+			DelayTasks::reset_execute_time(Origin::root(), 1, DispatchTime::At(5)),
+			Error::<Runtime>::TaskNotFound
+		);
+
+		assert_ok!(DelayTasks::reset_execute_time(
+			Origin::root(),
+			0,
+			DispatchTime::After(4)
+		));
+		System::assert_last_event(Event::DelayTasks(crate::Event::DelayedTaskReset(0, 5)));
+		assert_eq!(DelayTasks::delayed_tasks(0), Some((MockTask::Record(1, 10), 5)));
+		assert_eq!(DelayTasks::delayed_task_queue(2, 0), None);
+
+		DelayTasks::on_initialize(2);
+		assert!(dispatched().is_empty());
+		DelayTasks::on_initialize(5);
+		assert_eq!(dispatched(), vec![1]);
+	});
+}
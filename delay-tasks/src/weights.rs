@@ -0,0 +1,37 @@
+//! Weights for orml_delay_tasks
+//!
+//! These weights are estimates, not benchmark output. Generate them with the
+//! benchmarks in `benchmarking.rs`:
+//!
+//! target/release/acala benchmark --chain=dev --steps=50 --repeat=20
+//! --pallet=orml_delay_tasks --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --heap-pages=4096 --output=./delay-tasks/src/weights.rs
+//! --template=../templates/orml-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for orml_delay_tasks.
+pub trait WeightInfo {
+	fn cancel_delayed_task() -> Weight;
+	fn reset_execute_time() -> Weight;
+}
+
+/// Default weights.
+impl WeightInfo for () {
+	fn cancel_delayed_task() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn reset_execute_time() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+}
@@ -17,10 +17,28 @@ impl crate::WeightInfo for () {
 			.saturating_add(DbWeight::get().reads(1 as Weight))
 			.saturating_add(DbWeight::get().writes(1 as Weight))
 	}
+	fn gradually_update_linear() -> Weight {
+		(57_922_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn force_finish() -> Weight {
+		(66_687_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
 	fn on_finalize(u: u32) -> Weight {
 		(37_067_000 as Weight)
 			.saturating_add((20_890_000 as Weight).saturating_mul(u as Weight))
 			.saturating_add(DbWeight::get().reads(3 as Weight))
 			.saturating_add(DbWeight::get().writes(3 as Weight))
 	}
+	fn set_storage_target() -> Weight {
+		(24_315_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn gradually_update_typed() -> Weight {
+		(71_408_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
 }
@@ -10,7 +10,24 @@
 //! This module exposes capabilities for scheduling updates to storage values
 //! gradually. This is useful to change parameter values gradually to ensure a
 //! smooth transition. It is also possible to cancel an update before it reaches
-//! to target value.
+//! to target value, or to force it to finish immediately.
+//!
+//! Updates are applied every `UpdateFrequency` blocks by default, an update
+//! scheduled by `gradually_update_with_frequency` is applied at its own
+//! frequency instead.
+//!
+//! A linear update scheduled by `gradually_update_linear` only specifies the
+//! target value and the block to reach it at, the value is interpolated
+//! between its value when scheduled and the target value every
+//! `UpdateFrequency` blocks.
+//!
+//! The updates above target raw storage keys holding values encoded as bytes.
+//! Entries of storage values, maps and double maps of other pallets are
+//! targeted by `gradually_update_typed` instead: `DispatchOrigin` registers
+//! the storage item with `set_storage_target`, giving the hashers of its keys
+//! and the type hash and encoded length of its values. The key of an entry is
+//! then built from the encoded keys supplied at call time, and the target and
+//! current values are checked against the registered value type.
 //!
 //! NOTE: Only unsigned integer value up to 128 bits are supported. But a
 //! "newtype" pattern struct that wraps an unsigned integer works too such as
@@ -27,12 +44,17 @@ use frame_support::{
 	pallet_prelude::*,
 	storage,
 	traits::{EnsureOrigin, Get},
-	BoundedVec,
+	transactional, Blake2_128Concat, BoundedVec, Identity, StorageHasher, Twox64Concat,
 };
 use frame_system::pallet_prelude::*;
+use orml_traits::OnGraduallyUpdated;
 use scale_info::TypeInfo;
-use sp_runtime::{traits::SaturatedConversion, DispatchResult, RuntimeDebug};
-use sp_std::convert::TryInto;
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{SaturatedConversion, Saturating, Zero},
+	DispatchError, DispatchResult, RuntimeDebug,
+};
+use sp_std::{convert::TryInto, prelude::*};
 
 mod default_weight;
 mod mock;
@@ -48,6 +70,68 @@ pub struct GraduallyUpdate<Key, Value> {
 	pub per_block: Value,
 }
 
+/// Linearly update a value stored at `key` from `start_value` at
+/// `start_block` to `target_value` at `end_block`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct LinearUpdate<Key, Value, BlockNumber> {
+	pub key: Key,
+	pub start_value: Value,
+	pub target_value: Value,
+	pub start_block: BlockNumber,
+	pub end_block: BlockNumber,
+}
+
+/// The hash identifying the type of the values of a storage item, e.g. the
+/// `blake2_256` of its type name.
+pub type TypeHash = [u8; 32];
+
+/// The hasher of a key of a storage map.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum KeyHasher {
+	Blake2_128Concat,
+	Twox64Concat,
+	Identity,
+}
+
+impl KeyHasher {
+	/// Hash the encoded `key`.
+	pub fn hash(&self, key: &[u8]) -> Vec<u8> {
+		match self {
+			KeyHasher::Blake2_128Concat => Blake2_128Concat::hash(key),
+			KeyHasher::Twox64Concat => Twox64Concat::hash(key),
+			KeyHasher::Identity => Identity::hash(key),
+		}
+	}
+}
+
+/// The layout of a storage item.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum StorageLayout {
+	Value,
+	Map(KeyHasher),
+	DoubleMap(KeyHasher, KeyHasher),
+}
+
+impl StorageLayout {
+	/// The hashers of the keys of an entry.
+	pub fn hashers(&self) -> Vec<KeyHasher> {
+		match self {
+			StorageLayout::Value => Vec::new(),
+			StorageLayout::Map(hasher) => sp_std::vec![*hasher],
+			StorageLayout::DoubleMap(hasher1, hasher2) => sp_std::vec![*hasher1, *hasher2],
+		}
+	}
+}
+
+/// A storage item targeted by typed updates, its values are unsigned
+/// integers encoded in `value_len` bytes.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct StorageTarget {
+	pub layout: StorageLayout,
+	pub type_hash: TypeHash,
+	pub value_len: u32,
+}
+
 pub use module::*;
 
 #[frame_support::pallet]
@@ -57,13 +141,19 @@ pub mod module {
 	pub trait WeightInfo {
 		fn gradually_update() -> Weight;
 		fn cancel_gradually_update() -> Weight;
+		fn gradually_update_linear() -> Weight;
+		fn force_finish() -> Weight;
 		fn on_finalize(u: u32) -> Weight;
+		fn set_storage_target() -> Weight;
+		fn gradually_update_typed() -> Weight;
 	}
 
 	pub(crate) type StorageKeyBytes<T> = BoundedVec<u8, <T as Config>::MaxStorageKeyBytes>;
 	pub(crate) type StorageValueBytes<T> = BoundedVec<u8, <T as Config>::MaxStorageValueBytes>;
 
 	type GraduallyUpdateOf<T> = GraduallyUpdate<StorageKeyBytes<T>, StorageValueBytes<T>>;
+	pub(crate) type LinearUpdateOf<T> =
+		LinearUpdate<StorageKeyBytes<T>, StorageValueBytes<T>, <T as frame_system::Config>::BlockNumber>;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -87,6 +177,9 @@ pub mod module {
 
 		/// Maximum size of storage value
 		type MaxStorageValueBytes: Get<u32>;
+
+		/// Handler invoked after each update applied to a value.
+		type OnGraduallyUpdated: OnGraduallyUpdated<StorageKeyBytes<Self>, StorageValueBytes<Self>>;
 	}
 
 	#[pallet::error]
@@ -105,6 +198,18 @@ pub mod module {
 		MaxStorageKeyBytesExceeded,
 		/// Maximum value size exceeded
 		MaxStorageValueBytesExceeded,
+		/// The update frequency is zero.
+		InvalidFrequency,
+		/// The end block of linear update is not in the future.
+		InvalidEndBlock,
+		/// The storage item is not registered as target.
+		StorageTargetNotFound,
+		/// The value length of the storage target is invalid.
+		InvalidStorageTarget,
+		/// The type hash doesn't match the storage target.
+		TypeMismatch,
+		/// The keys don't match the layout of the storage target.
+		InvalidKeys,
 	}
 
 	#[pallet::event]
@@ -116,6 +221,16 @@ pub mod module {
 		GraduallyUpdateCancelled(StorageKeyBytes<T>),
 		/// Gradually update applied. [block_number, key, target_value]
 		Updated(T::BlockNumber, StorageKeyBytes<T>, StorageValueBytes<T>),
+		/// Gradually update added with its own frequency. [key, frequency]
+		GraduallyUpdateFrequencySet(StorageKeyBytes<T>, T::BlockNumber),
+		/// Linear update added. [key, target_value, end_block]
+		LinearUpdateAdded(StorageKeyBytes<T>, StorageValueBytes<T>, T::BlockNumber),
+		/// Gradually update forced to finish. [key, target_value]
+		GraduallyUpdateForceFinished(StorageKeyBytes<T>, StorageValueBytes<T>),
+		/// Storage target set, `None` if removed. [prefix, target]
+		StorageTargetSet(StorageKeyBytes<T>, Option<StorageTarget>),
+		/// Typed gradually update added. [prefix, key]
+		TypedGraduallyUpdateAdded(StorageKeyBytes<T>, StorageKeyBytes<T>),
 	}
 
 	/// All the on-going updates
@@ -129,6 +244,37 @@ pub mod module {
 	#[pallet::getter(fn last_updated_at)]
 	pub(crate) type LastUpdatedAt<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
 
+	/// All the on-going linear updates
+	#[pallet::storage]
+	#[pallet::getter(fn linear_updates)]
+	pub(crate) type LinearUpdates<T: Config> =
+		StorageValue<_, BoundedVec<LinearUpdateOf<T>, T::MaxGraduallyUpdate>, ValueQuery>;
+
+	/// The frequency and last updated block number of the updates with their
+	/// own frequency.
+	///
+	/// map StorageKeyBytes => Option<(frequency, last_updated_at)>
+	#[pallet::storage]
+	#[pallet::getter(fn update_frequencies)]
+	pub(crate) type UpdateFrequencies<T: Config> =
+		StorageMap<_, Twox64Concat, StorageKeyBytes<T>, (T::BlockNumber, T::BlockNumber), OptionQuery>;
+
+	/// The storage items targeted by typed updates, keyed by the prefix of
+	/// their entries.
+	///
+	/// map StorageKeyBytes => Option<StorageTarget>
+	#[pallet::storage]
+	#[pallet::getter(fn storage_targets)]
+	pub type StorageTargets<T: Config> = StorageMap<_, Twox64Concat, StorageKeyBytes<T>, StorageTarget, OptionQuery>;
+
+	/// The keys of the on-going typed updates, their values are stored
+	/// encoded as is rather than as bytes.
+	///
+	/// map StorageKeyBytes => Option<()>
+	#[pallet::storage]
+	#[pallet::getter(fn typed_keys)]
+	pub(crate) type TypedKeys<T: Config> = StorageMap<_, Twox64Concat, StorageKeyBytes<T>, (), OptionQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -136,8 +282,12 @@ pub mod module {
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
 		/// `on_initialize` to return the weight used in `on_finalize`.
 		fn on_initialize(now: T::BlockNumber) -> Weight {
-			if Self::_need_update(now) {
-				T::WeightInfo::on_finalize(GraduallyUpdates::<T>::get().len() as u32)
+			if Self::_need_update(now) || UpdateFrequencies::<T>::iter().next().is_some() {
+				T::WeightInfo::on_finalize(
+					GraduallyUpdates::<T>::decode_len()
+						.unwrap_or_default()
+						.saturating_add(LinearUpdates::<T>::decode_len().unwrap_or_default()) as u32,
+				)
 			} else {
 				0
 			}
@@ -155,39 +305,86 @@ pub mod module {
 		#[pallet::weight(T::WeightInfo::gradually_update())]
 		pub fn gradually_update(origin: OriginFor<T>, update: GraduallyUpdateOf<T>) -> DispatchResult {
 			T::DispatchOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+			Self::do_gradually_update(update)
+		}
 
-			// Support max value is u128, ensure per_block and target_value <= 16 bytes.
-			ensure!(
-				update.per_block.len() == update.target_value.len() && update.per_block.len() <= 16,
-				Error::<T>::InvalidPerBlockOrTargetValue
-			);
+		/// Add gradually_update to adjust numeric parameter, applied every
+		/// `frequency` blocks instead of `T::UpdateFrequency`.
+		#[pallet::weight(T::WeightInfo::gradually_update().saturating_add(T::DbWeight::get().writes(1)))]
+		pub fn gradually_update_with_frequency(
+			origin: OriginFor<T>,
+			update: GraduallyUpdateOf<T>,
+			frequency: T::BlockNumber,
+		) -> DispatchResult {
+			T::DispatchOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+			ensure!(!frequency.is_zero(), Error::<T>::InvalidFrequency);
 
-			if storage::unhashed::exists(&update.key) {
-				let current_value = storage::unhashed::get::<StorageValueBytes<T>>(&update.key).unwrap();
-				ensure!(
-					current_value.len() == update.target_value.len(),
-					Error::<T>::InvalidTargetValue
-				);
-			}
+			let key = update.key.clone();
+			Self::do_gradually_update(update)?;
 
-			GraduallyUpdates::<T>::try_mutate(|gradually_updates| -> DispatchResult {
+			let now = frame_system::Pallet::<T>::block_number();
+			UpdateFrequencies::<T>::insert(&key, (frequency, now));
+			Self::deposit_event(Event::GraduallyUpdateFrequencySet(key, frequency));
+			Ok(())
+		}
+
+		/// Add linear update to adjust numeric parameter to `target_value` at
+		/// `end_block`.
+		///
+		/// Both unsigned integers and fixed point numbers wrapping an unsigned
+		/// integer, e.g. `FixedU128`, are supported.
+		#[pallet::weight(T::WeightInfo::gradually_update_linear())]
+		pub fn gradually_update_linear(
+			origin: OriginFor<T>,
+			key: StorageKeyBytes<T>,
+			target_value: StorageValueBytes<T>,
+			end_block: T::BlockNumber,
+		) -> DispatchResult {
+			T::DispatchOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+
+			// Support max value is u128, ensure target_value <= 16 bytes.
+			ensure!(target_value.len() <= 16, Error::<T>::InvalidPerBlockOrTargetValue);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(end_block > now, Error::<T>::InvalidEndBlock);
+
+			let start_value = match Self::get_value(&key) {
+				Some(current_value) => {
+					ensure!(
+						current_value.len() == target_value.len(),
+						Error::<T>::InvalidTargetValue
+					);
+					current_value
+				}
+				None => sp_std::vec![0u8; target_value.len()]
+					.try_into()
+					.map_err(|_| Error::<T>::MaxStorageValueBytesExceeded)?,
+			};
+
+			ensure!(
+				!Self::gradually_updates().iter().any(|item| item.key == key),
+				Error::<T>::GraduallyUpdateHasExisted
+			);
+			LinearUpdates::<T>::try_mutate(|linear_updates| -> DispatchResult {
 				ensure!(
-					!gradually_updates.contains(&update),
+					!linear_updates.iter().any(|item| item.key == key),
 					Error::<T>::GraduallyUpdateHasExisted
 				);
 
-				gradually_updates
-					.try_push(update.clone())
+				linear_updates
+					.try_push(LinearUpdate {
+						key: key.clone(),
+						start_value,
+						target_value: target_value.clone(),
+						start_block: now,
+						end_block,
+					})
 					.map_err(|_| Error::<T>::MaxGraduallyUpdateExceeded)?;
 
 				Ok(())
 			})?;
 
-			Self::deposit_event(Event::GraduallyUpdateAdded(
-				update.key,
-				update.per_block,
-				update.target_value,
-			));
+			Self::deposit_event(Event::LinearUpdateAdded(key, target_value, end_block));
 			Ok(())
 		}
 
@@ -196,28 +393,213 @@ pub mod module {
 		pub fn cancel_gradually_update(origin: OriginFor<T>, key: StorageKeyBytes<T>) -> DispatchResult {
 			T::DispatchOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
 
-			GraduallyUpdates::<T>::try_mutate(|gradually_updates| -> DispatchResult {
-				let old_len = gradually_updates.len();
-				gradually_updates.retain(|item| item.key != key);
+			let mut gradually_updates = GraduallyUpdates::<T>::get();
+			let mut linear_updates = LinearUpdates::<T>::get();
+			let old_len = gradually_updates.len() + linear_updates.len();
+			gradually_updates.retain(|item| item.key != key);
+			linear_updates.retain(|item| item.key != key);
 
-				ensure!(gradually_updates.len() != old_len, Error::<T>::GraduallyUpdateNotFound);
+			ensure!(
+				gradually_updates.len() + linear_updates.len() != old_len,
+				Error::<T>::GraduallyUpdateNotFound
+			);
 
-				Ok(())
-			})?;
+			GraduallyUpdates::<T>::put(gradually_updates);
+			LinearUpdates::<T>::put(linear_updates);
+			UpdateFrequencies::<T>::remove(&key);
+			TypedKeys::<T>::remove(&key);
 
 			Self::deposit_event(Event::GraduallyUpdateCancelled(key));
 			Ok(())
 		}
+
+		/// Force gradually_update to finish, setting the value to
+		/// `target_value` immediately.
+		#[pallet::weight(T::WeightInfo::force_finish())]
+		pub fn force_finish(origin: OriginFor<T>, key: StorageKeyBytes<T>) -> DispatchResult {
+			T::DispatchOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+
+			// remove all the updates of `key`
+			let mut target_value = None;
+			GraduallyUpdates::<T>::mutate(|gradually_updates| {
+				gradually_updates.retain(|item| {
+					if item.key == key {
+						target_value = Some(item.target_value.clone());
+					}
+					item.key != key
+				})
+			});
+			LinearUpdates::<T>::mutate(|linear_updates| {
+				linear_updates.retain(|item| {
+					if item.key == key {
+						target_value = Some(item.target_value.clone());
+					}
+					item.key != key
+				})
+			});
+			let target_value = target_value.ok_or(Error::<T>::GraduallyUpdateNotFound)?;
+			UpdateFrequencies::<T>::remove(&key);
+
+			let old_value = Self::get_value(&key).unwrap_or_default();
+			Self::put_value(&key, &target_value);
+			TypedKeys::<T>::remove(&key);
+			T::OnGraduallyUpdated::on_gradually_updated(&key, &old_value, &target_value);
+
+			Self::deposit_event(Event::GraduallyUpdateForceFinished(key, target_value));
+			Ok(())
+		}
+
+		/// Register the storage item whose entries are prefixed by `prefix` as
+		/// target of typed updates, `None` to remove it.
+		#[pallet::weight(T::WeightInfo::set_storage_target())]
+		pub fn set_storage_target(
+			origin: OriginFor<T>,
+			prefix: StorageKeyBytes<T>,
+			target: Option<StorageTarget>,
+		) -> DispatchResult {
+			T::DispatchOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+			if let Some(target) = target {
+				// Support max value is u128, ensure value_len <= 16 bytes.
+				ensure!(
+					target.value_len > 0 && target.value_len <= 16,
+					Error::<T>::InvalidStorageTarget
+				);
+			}
+
+			StorageTargets::<T>::set(&prefix, target);
+			Self::deposit_event(Event::StorageTargetSet(prefix, target));
+			Ok(())
+		}
+
+		/// Add gradually_update to adjust the entry of the storage target
+		/// `prefix` at the encoded `keys`.
+		///
+		/// `type_hash` must be the type hash of the storage target, and
+		/// `target_value` and `per_block` encoded values of this type.
+		#[pallet::weight(T::WeightInfo::gradually_update_typed())]
+		#[transactional]
+		pub fn gradually_update_typed(
+			origin: OriginFor<T>,
+			prefix: StorageKeyBytes<T>,
+			keys: Vec<Vec<u8>>,
+			type_hash: TypeHash,
+			target_value: StorageValueBytes<T>,
+			per_block: StorageValueBytes<T>,
+		) -> DispatchResult {
+			T::DispatchOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+
+			let target = Self::storage_targets(&prefix).ok_or(Error::<T>::StorageTargetNotFound)?;
+			ensure!(target.type_hash == type_hash, Error::<T>::TypeMismatch);
+			ensure!(
+				target_value.len() == target.value_len as usize,
+				Error::<T>::InvalidPerBlockOrTargetValue
+			);
+
+			let key = Self::typed_key(&prefix, &keys)?;
+			if let Some(current_value) = storage::unhashed::get_raw(&key) {
+				ensure!(
+					current_value.len() == target.value_len as usize,
+					Error::<T>::InvalidTargetValue
+				);
+			}
+			ensure!(
+				!Self::gradually_updates().iter().any(|item| item.key == key),
+				Error::<T>::GraduallyUpdateHasExisted
+			);
+
+			TypedKeys::<T>::insert(&key, ());
+			Self::do_gradually_update(GraduallyUpdate {
+				key: key.clone(),
+				target_value,
+				per_block,
+			})?;
+
+			Self::deposit_event(Event::TypedGraduallyUpdateAdded(prefix, key));
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// The key of the entry of the storage target `prefix` at the encoded
+	/// `keys`.
+	pub fn typed_key(prefix: &StorageKeyBytes<T>, keys: &[Vec<u8>]) -> Result<StorageKeyBytes<T>, DispatchError> {
+		let target = Self::storage_targets(prefix).ok_or(Error::<T>::StorageTargetNotFound)?;
+		let hashers = target.layout.hashers();
+		ensure!(hashers.len() == keys.len(), Error::<T>::InvalidKeys);
+
+		let mut key = prefix.to_vec();
+		for (hasher, k) in hashers.iter().zip(keys) {
+			key.extend(hasher.hash(k));
+		}
+		key.try_into()
+			.map_err(|_| Error::<T>::MaxStorageKeyBytesExceeded.into())
+	}
+
+	/// The value at `key`, typed keys hold the encoded value as is.
+	fn get_value(key: &StorageKeyBytes<T>) -> Option<StorageValueBytes<T>> {
+		if TypedKeys::<T>::contains_key(key) {
+			storage::unhashed::get_raw(key).and_then(|value| value.try_into().ok())
+		} else {
+			storage::unhashed::get::<StorageValueBytes<T>>(key)
+		}
+	}
+
+	fn put_value(key: &StorageKeyBytes<T>, value: &[u8]) {
+		if TypedKeys::<T>::contains_key(key) {
+			storage::unhashed::put_raw(key, value);
+		} else {
+			storage::unhashed::put(key, value);
+		}
+	}
+
+	fn do_gradually_update(update: GraduallyUpdateOf<T>) -> DispatchResult {
+		// Support max value is u128, ensure per_block and target_value <= 16 bytes.
+		ensure!(
+			update.per_block.len() == update.target_value.len() && update.per_block.len() <= 16,
+			Error::<T>::InvalidPerBlockOrTargetValue
+		);
+
+		if let Some(current_value) = Self::get_value(&update.key) {
+			ensure!(
+				current_value.len() == update.target_value.len(),
+				Error::<T>::InvalidTargetValue
+			);
+		}
+
+		ensure!(
+			!Self::linear_updates().iter().any(|item| item.key == update.key),
+			Error::<T>::GraduallyUpdateHasExisted
+		);
+		GraduallyUpdates::<T>::try_mutate(|gradually_updates| -> DispatchResult {
+			// the frequency and the progress are tracked by key
+			ensure!(
+				!gradually_updates.iter().any(|item| item.key == update.key),
+				Error::<T>::GraduallyUpdateHasExisted
+			);
+
+			gradually_updates
+				.try_push(update.clone())
+				.map_err(|_| Error::<T>::MaxGraduallyUpdateExceeded)?;
+
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::GraduallyUpdateAdded(
+			update.key,
+			update.per_block,
+			update.target_value,
+		));
+		Ok(())
+	}
+
 	fn _need_update(now: T::BlockNumber) -> bool {
 		now >= Self::last_updated_at() + T::UpdateFrequency::get()
 	}
 
 	fn _on_finalize(now: T::BlockNumber) {
-		if !Self::_need_update(now) {
+		let need_update = Self::_need_update(now);
+		if !need_update && UpdateFrequencies::<T>::iter().next().is_none() {
 			return;
 		}
 
@@ -225,21 +607,30 @@ impl<T: Config> Pallet<T> {
 		let initial_count = gradually_updates.len();
 
 		gradually_updates.retain(|update| {
+			let maybe_frequency = Self::update_frequencies(&update.key);
+			let (due, frequency) = match maybe_frequency {
+				Some((frequency, last_updated_at)) => (now >= last_updated_at + frequency, frequency),
+				None => (need_update, T::UpdateFrequency::get()),
+			};
+			if !due {
+				return true;
+			}
+
 			let mut keep = true;
-			let current_value = storage::unhashed::get::<StorageValueBytes<T>>(&update.key).unwrap_or_default();
+			let current_value = Self::get_value(&update.key).unwrap_or_default();
 			let current_value_u128 = u128::from_le_bytes(Self::convert_vec_to_u8(&current_value));
 
-			let frequency_u128: u128 = T::UpdateFrequency::get().saturated_into();
+			let frequency_u128: u128 = frequency.saturated_into();
 
 			let step = u128::from_le_bytes(Self::convert_vec_to_u8(&update.per_block));
-			let step_u128 = step.checked_mul(frequency_u128).unwrap();
+			let step_u128 = step.saturating_mul(frequency_u128);
 
 			let target_u128 = u128::from_le_bytes(Self::convert_vec_to_u8(&update.target_value));
 
 			let new_value_u128 = if current_value_u128 > target_u128 {
-				(current_value_u128.checked_sub(step_u128).unwrap()).max(target_u128)
+				current_value_u128.saturating_sub(step_u128).max(target_u128)
 			} else {
-				(current_value_u128.checked_add(step_u128).unwrap()).min(target_u128)
+				current_value_u128.saturating_add(step_u128).min(target_u128)
 			};
 
 			// current_value equal target_value, remove gradually_update
@@ -250,9 +641,21 @@ impl<T: Config> Pallet<T> {
 			let mut value = new_value_u128.encode();
 			value.truncate(update.target_value.len());
 
-			storage::unhashed::put(&update.key, &value);
+			Self::put_value(&update.key, &value);
+			if !keep {
+				TypedKeys::<T>::remove(&update.key);
+			}
+
+			if maybe_frequency.is_some() {
+				if keep {
+					UpdateFrequencies::<T>::insert(&update.key, (frequency, now));
+				} else {
+					UpdateFrequencies::<T>::remove(&update.key);
+				}
+			}
 
 			let bounded_value: StorageValueBytes<T> = value.to_vec().try_into().unwrap();
+			T::OnGraduallyUpdated::on_gradually_updated(&update.key, &current_value, &bounded_value);
 
 			Self::deposit_event(Event::Updated(now, update.key.clone(), bounded_value));
 
@@ -264,7 +667,58 @@ impl<T: Config> Pallet<T> {
 			GraduallyUpdates::<T>::put(gradually_updates);
 		}
 
-		LastUpdatedAt::<T>::put(now);
+		if need_update {
+			Self::apply_linear_updates(now);
+			LastUpdatedAt::<T>::put(now);
+		}
+	}
+
+	fn apply_linear_updates(now: T::BlockNumber) {
+		let mut linear_updates = LinearUpdates::<T>::get();
+		if linear_updates.is_empty() {
+			return;
+		}
+		let initial_count = linear_updates.len();
+
+		linear_updates.retain(|update| {
+			let start_u128 = u128::from_le_bytes(Self::convert_vec_to_u8(&update.start_value));
+			let target_u128 = u128::from_le_bytes(Self::convert_vec_to_u8(&update.target_value));
+
+			let elapsed: u128 = now.saturating_sub(update.start_block).saturated_into();
+			let duration: u128 = update.end_block.saturating_sub(update.start_block).saturated_into();
+
+			let keep = now < update.end_block;
+			let new_value_u128 = if !keep {
+				target_u128
+			} else if start_u128 > target_u128 {
+				let delta = multiply_by_rational(start_u128 - target_u128, elapsed, duration).unwrap_or_default();
+				start_u128.saturating_sub(delta).max(target_u128)
+			} else {
+				let delta = multiply_by_rational(target_u128 - start_u128, elapsed, duration).unwrap_or_default();
+				start_u128.saturating_add(delta).min(target_u128)
+			};
+
+			let mut value = new_value_u128.encode();
+			value.truncate(update.target_value.len());
+
+			let old_value = Self::get_value(&update.key).unwrap_or_default();
+			Self::put_value(&update.key, &value);
+			if !keep {
+				TypedKeys::<T>::remove(&update.key);
+			}
+
+			let bounded_value: StorageValueBytes<T> = value.to_vec().try_into().unwrap();
+			T::OnGraduallyUpdated::on_gradually_updated(&update.key, &old_value, &bounded_value);
+
+			Self::deposit_event(Event::Updated(now, update.key.clone(), bounded_value));
+
+			keep
+		});
+
+		// linear update has finished. Remove it from LinearUpdates.
+		if linear_updates.len() < initial_count {
+			LinearUpdates::<T>::put(linear_updates);
+		}
 	}
 
 	#[allow(clippy::ptr_arg)]
@@ -6,6 +6,7 @@ use super::*;
 use frame_support::{construct_runtime, parameter_types, traits::Everything};
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_std::cell::RefCell;
 
 use crate as gradually_update;
 
@@ -49,6 +50,21 @@ parameter_types! {
 	pub MaxStorageValueBytes: u32 = 100_000;
 }
 
+thread_local! {
+	pub static UPDATED: RefCell<Vec<(Vec<u8>, Vec<u8>, Vec<u8>)>> = RefCell::new(Vec::new());
+}
+
+pub struct OnUpdated;
+impl OnGraduallyUpdated<StorageKeyBytes<Runtime>, StorageValueBytes<Runtime>> for OnUpdated {
+	fn on_gradually_updated(
+		key: &StorageKeyBytes<Runtime>,
+		old: &StorageValueBytes<Runtime>,
+		new: &StorageValueBytes<Runtime>,
+	) {
+		UPDATED.with(|v| v.borrow_mut().push((key.to_vec(), old.to_vec(), new.to_vec())));
+	}
+}
+
 impl Config for Runtime {
 	type Event = Event;
 	type UpdateFrequency = UpdateFrequency;
@@ -57,6 +73,7 @@ impl Config for Runtime {
 	type MaxGraduallyUpdate = MaxGraduallyUpdate;
 	type MaxStorageKeyBytes = MaxStorageKeyBytes;
 	type MaxStorageValueBytes = MaxStorageValueBytes;
+	type OnGraduallyUpdated = OnUpdated;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
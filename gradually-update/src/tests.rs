@@ -425,3 +425,395 @@ fn exceeding_max_gradually_updates_should_fail() {
 		assert_eq!(storage_get(&update4.key), vec![120]);
 	});
 }
+
+#[test]
+fn gradually_update_with_frequency_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		let update: GraduallyUpdate<StorageKeyBytes<Runtime>, StorageValueBytes<Runtime>> = GraduallyUpdate {
+			key: vec![1].try_into().unwrap(),
+			target_value: vec![30].try_into().unwrap(),
+			per_block: vec![1].try_into().unwrap(),
+		};
+		let update2: GraduallyUpdate<StorageKeyBytes<Runtime>, StorageValueBytes<Runtime>> = GraduallyUpdate {
+			key: vec![2].try_into().unwrap(),
+			target_value: vec![30].try_into().unwrap(),
+			per_block: vec![1].try_into().unwrap(),
+		};
+		assert_noop!(
+			GraduallyUpdateModule::gradually_update_with_frequency(Origin::root(), update.clone(), 0),
+			Error::<Runtime>::InvalidFrequency
+		);
+		assert_ok!(GraduallyUpdateModule::gradually_update_with_frequency(
+			Origin::root(),
+			update.clone(),
+			5
+		));
+		System::assert_last_event(Event::GraduallyUpdateModule(crate::Event::GraduallyUpdateFrequencySet(
+			update.key.clone(),
+			5,
+		)));
+		// one update per key, the frequency is tracked by key
+		assert_noop!(
+			GraduallyUpdateModule::gradually_update_with_frequency(
+				Origin::root(),
+				GraduallyUpdate {
+					key: update.key.clone(),
+					target_value: vec![20].try_into().unwrap(),
+					per_block: vec![2].try_into().unwrap(),
+				},
+				2
+			),
+			Error::<Runtime>::GraduallyUpdateHasExisted
+		);
+		assert_ok!(GraduallyUpdateModule::gradually_update(Origin::root(), update2.clone()));
+
+		GraduallyUpdateModule::on_finalize(6);
+		assert_eq!(storage_get(&update.key), vec![5]);
+		assert_eq!(storage_get(&update2.key), Vec::<u8>::new());
+		System::assert_last_event(Event::GraduallyUpdateModule(crate::Event::Updated(
+			6,
+			update.key.clone(),
+			vec![5].try_into().unwrap(),
+		)));
+
+		GraduallyUpdateModule::on_finalize(10);
+		assert_eq!(storage_get(&update.key), vec![5]);
+		assert_eq!(storage_get(&update2.key), vec![10]);
+
+		GraduallyUpdateModule::on_finalize(11);
+		assert_eq!(storage_get(&update.key), vec![10]);
+		assert_eq!(GraduallyUpdateModule::update_frequencies(&update.key), Some((5, 11)));
+
+		for now in vec![16, 21, 26, 31] {
+			GraduallyUpdateModule::on_finalize(now);
+		}
+		assert_eq!(storage_get(&update.key), vec![30]);
+		assert_eq!(GraduallyUpdateModule::update_frequencies(&update.key), None);
+	});
+}
+
+#[test]
+fn force_finish_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		let update: GraduallyUpdate<StorageKeyBytes<Runtime>, StorageValueBytes<Runtime>> = GraduallyUpdate {
+			key: vec![1].try_into().unwrap(),
+			target_value: vec![30].try_into().unwrap(),
+			per_block: vec![1].try_into().unwrap(),
+		};
+		assert_noop!(
+			GraduallyUpdateModule::force_finish(Origin::root(), update.key.clone()),
+			Error::<Runtime>::GraduallyUpdateNotFound
+		);
+
+		assert_ok!(GraduallyUpdateModule::gradually_update_with_frequency(
+			Origin::root(),
+			update.clone(),
+			5
+		));
+		GraduallyUpdateModule::on_finalize(6);
+		assert_eq!(storage_get(&update.key), vec![5]);
+
+		assert_ok!(GraduallyUpdateModule::force_finish(Origin::root(), update.key.clone()));
+		assert_eq!(storage_get(&update.key), vec![30]);
+		assert_eq!(GraduallyUpdateModule::gradually_updates().len(), 0);
+		assert_eq!(GraduallyUpdateModule::update_frequencies(&update.key), None);
+		System::assert_last_event(Event::GraduallyUpdateModule(
+			crate::Event::GraduallyUpdateForceFinished(update.key, update.target_value),
+		));
+	});
+}
+
+#[test]
+fn gradually_update_linear_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		let key: StorageKeyBytes<Runtime> = vec![1].try_into().unwrap();
+		storage_set(&key.to_vec(), &10u32.encode());
+
+		assert_noop!(
+			GraduallyUpdateModule::gradually_update_linear(
+				Origin::root(),
+				key.clone(),
+				50u32.encode().try_into().unwrap(),
+				1
+			),
+			Error::<Runtime>::InvalidEndBlock
+		);
+		assert_noop!(
+			GraduallyUpdateModule::gradually_update_linear(
+				Origin::root(),
+				key.clone(),
+				50u64.encode().try_into().unwrap(),
+				41
+			),
+			Error::<Runtime>::InvalidTargetValue
+		);
+
+		assert_ok!(GraduallyUpdateModule::gradually_update_linear(
+			Origin::root(),
+			key.clone(),
+			50u32.encode().try_into().unwrap(),
+			41
+		));
+		System::assert_last_event(Event::GraduallyUpdateModule(crate::Event::LinearUpdateAdded(
+			key.clone(),
+			50u32.encode().try_into().unwrap(),
+			41,
+		)));
+		assert_noop!(
+			GraduallyUpdateModule::gradually_update_linear(
+				Origin::root(),
+				key.clone(),
+				50u32.encode().try_into().unwrap(),
+				41
+			),
+			Error::<Runtime>::GraduallyUpdateHasExisted
+		);
+		// a stepped update would race the linear one
+		assert_noop!(
+			GraduallyUpdateModule::gradually_update(
+				Origin::root(),
+				GraduallyUpdate {
+					key: key.clone(),
+					target_value: 50u32.encode().try_into().unwrap(),
+					per_block: 1u32.encode().try_into().unwrap(),
+				}
+			),
+			Error::<Runtime>::GraduallyUpdateHasExisted
+		);
+
+		GraduallyUpdateModule::on_finalize(11);
+		assert_eq!(storage_get(&key), 20u32.encode());
+		GraduallyUpdateModule::on_finalize(15);
+		assert_eq!(storage_get(&key), 20u32.encode());
+		GraduallyUpdateModule::on_finalize(31);
+		assert_eq!(storage_get(&key), 40u32.encode());
+		GraduallyUpdateModule::on_finalize(45);
+		assert_eq!(storage_get(&key), 50u32.encode());
+		assert_eq!(GraduallyUpdateModule::linear_updates().len(), 0);
+	});
+}
+
+#[test]
+fn gradually_update_linear_fixed_u128_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		let key: StorageKeyBytes<Runtime> = vec![1].try_into().unwrap();
+		storage_set(&key.to_vec(), &FixedU128::saturating_from_integer(3).encode());
+
+		assert_ok!(GraduallyUpdateModule::gradually_update_linear(
+			Origin::root(),
+			key.clone(),
+			FixedU128::saturating_from_rational(1, 2).encode().try_into().unwrap(),
+			21
+		));
+
+		GraduallyUpdateModule::on_finalize(11);
+		assert_eq!(storage_get(&key), FixedU128::saturating_from_rational(7, 4).encode());
+
+		// cancelled, stays at the interpolated value
+		assert_ok!(GraduallyUpdateModule::cancel_gradually_update(
+			Origin::root(),
+			key.clone()
+		));
+		GraduallyUpdateModule::on_finalize(21);
+		assert_eq!(storage_get(&key), FixedU128::saturating_from_rational(7, 4).encode());
+	});
+}
+
+#[test]
+fn on_gradually_updated_should_be_called() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		let update: GraduallyUpdate<StorageKeyBytes<Runtime>, StorageValueBytes<Runtime>> = GraduallyUpdate {
+			key: vec![1].try_into().unwrap(),
+			target_value: vec![15].try_into().unwrap(),
+			per_block: vec![1].try_into().unwrap(),
+		};
+		let key2: StorageKeyBytes<Runtime> = vec![2].try_into().unwrap();
+		assert_ok!(GraduallyUpdateModule::gradually_update(Origin::root(), update.clone()));
+		assert_ok!(GraduallyUpdateModule::gradually_update_linear(
+			Origin::root(),
+			key2.clone(),
+			vec![100].try_into().unwrap(),
+			21
+		));
+
+		GraduallyUpdateModule::on_finalize(10);
+		assert_eq!(
+			UPDATED.with(|v| v.borrow().clone()),
+			vec![(vec![1], vec![], vec![10]), (vec![2], vec![0], vec![45])]
+		);
+
+		assert_ok!(GraduallyUpdateModule::force_finish(Origin::root(), update.key.clone()));
+		assert_eq!(
+			UPDATED.with(|v| v.borrow().last().cloned()),
+			Some((vec![1], vec![10], vec![15]))
+		);
+	});
+}
+
+#[test]
+fn gradually_update_typed_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		let prefix: StorageKeyBytes<Runtime> = vec![7; 32].try_into().unwrap();
+		let target = StorageTarget {
+			layout: StorageLayout::DoubleMap(KeyHasher::Twox64Concat, KeyHasher::Blake2_128Concat),
+			type_hash: [1; 32],
+			value_len: 4,
+		};
+		assert_noop!(
+			GraduallyUpdateModule::set_storage_target(
+				Origin::root(),
+				prefix.clone(),
+				Some(StorageTarget {
+					value_len: 17,
+					..target
+				})
+			),
+			Error::<Runtime>::InvalidStorageTarget
+		);
+		assert_ok!(GraduallyUpdateModule::set_storage_target(
+			Origin::root(),
+			prefix.clone(),
+			Some(target)
+		));
+		System::assert_last_event(Event::GraduallyUpdateModule(crate::Event::StorageTargetSet(
+			prefix.clone(),
+			Some(target),
+		)));
+
+		let keys = vec![1u32.encode(), 2u64.encode()];
+		let key = GraduallyUpdateModule::typed_key(&prefix, &keys).unwrap();
+		let mut expected_key = vec![7; 32];
+		expected_key.extend(Twox64Concat::hash(&1u32.encode()));
+		expected_key.extend(Blake2_128Concat::hash(&2u64.encode()));
+		assert_eq!(key.to_vec(), expected_key);
+
+		assert_noop!(
+			GraduallyUpdateModule::gradually_update_typed(
+				Origin::root(),
+				vec![8; 32].try_into().unwrap(),
+				keys.clone(),
+				[1; 32],
+				30u32.encode().try_into().unwrap(),
+				1u32.encode().try_into().unwrap()
+			),
+			Error::<Runtime>::StorageTargetNotFound
+		);
+		assert_noop!(
+			GraduallyUpdateModule::gradually_update_typed(
+				Origin::root(),
+				prefix.clone(),
+				keys.clone(),
+				[2; 32],
+				30u32.encode().try_into().unwrap(),
+				1u32.encode().try_into().unwrap()
+			),
+			Error::<Runtime>::TypeMismatch
+		);
+		assert_noop!(
+			GraduallyUpdateModule::gradually_update_typed(
+				Origin::root(),
+				prefix.clone(),
+				vec![1u32.encode()],
+				[1; 32],
+				30u32.encode().try_into().unwrap(),
+				1u32.encode().try_into().unwrap()
+			),
+			Error::<Runtime>::InvalidKeys
+		);
+		assert_noop!(
+			GraduallyUpdateModule::gradually_update_typed(
+				Origin::root(),
+				prefix.clone(),
+				keys.clone(),
+				[1; 32],
+				30u64.encode().try_into().unwrap(),
+				1u64.encode().try_into().unwrap()
+			),
+			Error::<Runtime>::InvalidPerBlockOrTargetValue
+		);
+
+		// the current value doesn't decode as the target type
+		frame_support::storage::unhashed::put(&key, &10u64);
+		assert_noop!(
+			GraduallyUpdateModule::gradually_update_typed(
+				Origin::root(),
+				prefix.clone(),
+				keys.clone(),
+				[1; 32],
+				30u32.encode().try_into().unwrap(),
+				1u32.encode().try_into().unwrap()
+			),
+			Error::<Runtime>::InvalidTargetValue
+		);
+
+		frame_support::storage::unhashed::put(&key, &10u32);
+		assert_ok!(GraduallyUpdateModule::gradually_update_typed(
+			Origin::root(),
+			prefix.clone(),
+			keys,
+			[1; 32],
+			30u32.encode().try_into().unwrap(),
+			1u32.encode().try_into().unwrap()
+		));
+		System::assert_last_event(Event::GraduallyUpdateModule(crate::Event::TypedGraduallyUpdateAdded(
+			prefix,
+			key.clone(),
+		)));
+		assert_eq!(GraduallyUpdateModule::typed_keys(&key), Some(()));
+
+		GraduallyUpdateModule::on_finalize(10);
+		assert_eq!(frame_support::storage::unhashed::get::<u32>(&key), Some(20));
+
+		GraduallyUpdateModule::on_finalize(20);
+		assert_eq!(frame_support::storage::unhashed::get::<u32>(&key), Some(30));
+		assert_eq!(GraduallyUpdateModule::typed_keys(&key), None);
+	});
+}
+
+#[test]
+fn gradually_update_step_should_saturate() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		let update: GraduallyUpdate<StorageKeyBytes<Runtime>, StorageValueBytes<Runtime>> = GraduallyUpdate {
+			key: vec![1].try_into().unwrap(),
+			target_value: u128::MAX.encode().try_into().unwrap(),
+			per_block: (u128::MAX / 2).encode().try_into().unwrap(),
+		};
+		let update2: GraduallyUpdate<StorageKeyBytes<Runtime>, StorageValueBytes<Runtime>> = GraduallyUpdate {
+			key: vec![2].try_into().unwrap(),
+			target_value: 0u128.encode().try_into().unwrap(),
+			per_block: (u128::MAX / 2).encode().try_into().unwrap(),
+		};
+		storage_set(&update.key, &1u128.encode());
+		storage_set(&update2.key, &10u128.encode());
+
+		assert_ok!(GraduallyUpdateModule::gradually_update_with_frequency(
+			Origin::root(),
+			update.clone(),
+			5
+		));
+		assert_ok!(GraduallyUpdateModule::gradually_update_with_frequency(
+			Origin::root(),
+			update2.clone(),
+			5
+		));
+
+		GraduallyUpdateModule::on_finalize(6);
+		assert_eq!(storage_get(&update.key), update.target_value);
+		assert_eq!(storage_get(&update2.key), update2.target_value);
+		assert_eq!(GraduallyUpdateModule::gradually_updates().len(), 0);
+	});
+}
@@ -0,0 +1,109 @@
+//! Benchmarks for the nft module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+const CURRENCY: u32 = 1;
+
+fn token_metadata<T: Config>() -> Vec<u8> {
+	vec![0u8; T::MaxTokenMetadata::get() as usize]
+}
+
+fn class_metadata<T: Config>() -> Vec<u8> {
+	vec![0u8; T::MaxClassMetadata::get() as usize]
+}
+
+benchmarks! {
+	where_clause { where T::ClassData: Default, T::TokenData: Default, CurrencyIdOf<T>: From<u32> }
+
+	mint_with_voucher {
+		let (signer, owner) = T::BenchmarkHelper::signer();
+		let buyer: T::AccountId = account("buyer", 0, SEED);
+		let currency_id: CurrencyIdOf<T> = CURRENCY.into();
+		let price: BalanceOf<T> = 100u32.into();
+		T::Currency::deposit(currency_id, &buyer, price)?;
+		let class_id = Pallet::<T>::create_class(&owner, vec![], Default::default())?;
+		let voucher = MintVoucher {
+			class_id,
+			metadata_hash: T::Hashing::hash(&[][..]),
+			data: Default::default(),
+			currency_id,
+			price,
+			expiry: frame_system::Pallet::<T>::block_number(),
+		};
+		let signature = T::BenchmarkHelper::sign(&signer, &Pallet::<T>::voucher_payload(&voucher));
+	}: _(RawOrigin::Signed(buyer.clone()), voucher, vec![], signature)
+	verify {
+		assert!(Pallet::<T>::is_owner(&buyer, (class_id, Zero::zero())));
+	}
+
+	mint_batch {
+		let i in 1 .. T::MaxBatchSize::get();
+
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let class_id = Pallet::<T>::create_class(&owner, vec![], Default::default())?;
+		let metadata = (0..i).map(|_| token_metadata::<T>()).collect::<Vec<_>>();
+	}: _(RawOrigin::Signed(owner.clone()), class_id, metadata, Default::default())
+	verify {
+		assert_eq!(Pallet::<T>::next_token_id(class_id), i.into());
+	}
+
+	transfer_batch {
+		let i in 1 .. T::MaxBatchSize::get();
+
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let class_id = Pallet::<T>::create_class(&owner, vec![], Default::default())?;
+		let mut transfers = vec![];
+		for index in 0..i {
+			let token_id = Pallet::<T>::mint(&owner, class_id, token_metadata::<T>(), Default::default())?;
+			let to: T::AccountId = account("to", index, SEED);
+			transfers.push(((class_id, token_id), T::Lookup::unlookup(to)));
+		}
+	}: _(RawOrigin::Signed(owner), transfers)
+	verify {
+		for index in 0..i {
+			let to: T::AccountId = account("to", index, SEED);
+			assert!(Pallet::<T>::is_owner(&to, (class_id, index.into())));
+		}
+	}
+
+	freeze_class {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let class_id = Pallet::<T>::create_class(&owner, vec![], Default::default())?;
+	}: _(RawOrigin::Signed(owner), class_id)
+	verify {
+		assert!(FrozenClasses::<T>::contains_key(class_id));
+	}
+
+	schedule_reveal {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let class_id = Pallet::<T>::create_class(&owner, vec![], Default::default())?;
+		let metadata_hash = T::Hashing::hash(&class_metadata::<T>()[..]);
+	}: _(RawOrigin::Signed(owner), class_id, Zero::zero(), metadata_hash)
+	verify {
+		assert!(Reveals::<T>::contains_key(class_id));
+	}
+
+	// the metadata of the largest size is hashed and stored
+	reveal {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let class_id = Pallet::<T>::create_class(&owner, vec![], Default::default())?;
+		let metadata = class_metadata::<T>();
+		Pallet::<T>::schedule_reveal(
+			RawOrigin::Signed(owner).into(),
+			class_id,
+			Zero::zero(),
+			T::Hashing::hash(&metadata[..]),
+		)?;
+	}: _(RawOrigin::Signed(caller), class_id, metadata.clone())
+	verify {
+		assert_eq!(Pallet::<T>::classes(class_id).unwrap().metadata.to_vec(), metadata);
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::default().build(), crate::mock::Runtime);
@@ -9,6 +9,10 @@
 //!
 //! This module provides basic functions to create and manager
 //! NFT(non fungible token) such as `create_class`, `transfer`, `mint`, `burn`.
+//!
+//! The class properties, returned by the class data through
+//! `HasClassProperties`, are checked when transferring, burning and minting
+//! tokens of the class, e.g. to issue non-transferable credential tokens.
 
 //! ### Module Functions
 //!
@@ -17,21 +21,49 @@
 //! - `mint` - Mint NFT(non fungible token)
 //! - `burn` - Burn NFT(non fungible token)
 //! - `destroy_class` - Destroy NFT(non fungible token) class
+//!
+//! ### Dispatchable Functions
+//!
+//! - `mint_with_voucher` - Mint NFT(non fungible token) by redeeming a voucher
+//!   signed by the class owner, paying the owner the voucher price. Vouchers
+//!   are signed with `VOUCHER_SIGNING_CONTEXT` and the genesis hash, so they
+//!   can't be replayed on other chains, and redeemed vouchers are pruned once
+//!   expired
+//! - `mint_batch` - Mint a batch of NFT(non fungible token) of a class by the
+//!   class owner
+//! - `transfer_batch` - Transfer a batch of NFT(non fungible token)
+//! - `freeze_class` - Freeze a class by the class owner, no more tokens could
+//!   be minted
+//! - `schedule_reveal` - Commit to the hash of the class metadata revealed at
+//!   a block, the current class metadata being the placeholder until then
+//! - `reveal` - Replace the class metadata by the committed metadata, once the
+//!   reveal block is reached
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_support::{ensure, pallet_prelude::*, traits::Get, BoundedVec, Parameter};
+use frame_support::{ensure, pallet_prelude::*, traits::Get, transactional, BoundedVec, Parameter};
+use frame_system::{ensure_signed, pallet_prelude::*};
+use orml_traits::MultiCurrency;
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedSub, MaybeSerializeDeserialize, Member, One, Zero},
+	traits::{
+		AtLeast32BitUnsigned, CheckedAdd, CheckedSub, Hash, IdentifyAccount, MaybeSerializeDeserialize, Member, One,
+		Saturating, StaticLookup, Verify, Zero,
+	},
 	ArithmeticError, DispatchError, DispatchResult, RuntimeDebug,
 };
 use sp_std::{convert::TryInto, vec::Vec};
 
+mod benchmarking;
 mod mock;
 mod tests;
+mod weights;
+
+pub use weights::WeightInfo;
 
 /// Class info
 #[derive(Encode, Decode, Clone, Eq, PartialEq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
@@ -46,6 +78,47 @@ pub struct ClassInfo<TokenId, AccountId, Data, ClassMetadataOf> {
 	pub data: Data,
 }
 
+/// The properties of a class checked by the module.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ClassProperties<BlockNumber> {
+	/// Tokens could be transferred
+	pub transferable: bool,
+	/// Tokens could be burned
+	pub burnable: bool,
+	/// The last block tokens could be minted at, `None` for no limit
+	pub mintable_until: Option<BlockNumber>,
+}
+
+impl<BlockNumber> Default for ClassProperties<BlockNumber> {
+	fn default() -> Self {
+		Self {
+			transferable: true,
+			burnable: true,
+			mintable_until: None,
+		}
+	}
+}
+
+/// Class data providing the class properties.
+pub trait HasClassProperties<BlockNumber> {
+	/// The properties of the class
+	fn class_properties(&self) -> ClassProperties<BlockNumber>;
+}
+
+/// No restriction.
+impl<BlockNumber> HasClassProperties<BlockNumber> for () {
+	fn class_properties(&self) -> ClassProperties<BlockNumber> {
+		Default::default()
+	}
+}
+
+impl<BlockNumber: Copy> HasClassProperties<BlockNumber> for ClassProperties<BlockNumber> {
+	fn class_properties(&self) -> ClassProperties<BlockNumber> {
+		*self
+	}
+}
+
 /// Token info
 #[derive(Encode, Decode, Clone, Eq, PartialEq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
 pub struct TokenInfo<AccountId, Data, TokenMetadataOf> {
@@ -57,6 +130,45 @@ pub struct TokenInfo<AccountId, Data, TokenMetadataOf> {
 	pub data: Data,
 }
 
+/// A voucher signed by a class owner, which could be redeemed for minting
+/// a token of the class.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct MintVoucher<ClassId, Hash, TokenData, CurrencyId, Balance, BlockNumber> {
+	/// The class of the token
+	pub class_id: ClassId,
+	/// The hash of the token metadata
+	pub metadata_hash: Hash,
+	/// Token Properties
+	pub data: TokenData,
+	/// The currency of the price
+	pub currency_id: CurrencyId,
+	/// The price paid to the class owner
+	pub price: Balance,
+	/// The last block the voucher could be redeemed at
+	pub expiry: BlockNumber,
+}
+
+/// The context vouchers are signed with, along with the genesis hash.
+pub const VOUCHER_SIGNING_CONTEXT: &[u8] = b"orml-nft/voucher";
+
+/// Sign vouchers in benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<Public, AccountId, Signature> {
+	/// A signer and its account.
+	fn signer() -> (Public, AccountId);
+	/// Sign `message` by `signer`.
+	fn sign(signer: &Public, message: &[u8]) -> Signature;
+}
+
+/// A scheduled reveal of class metadata.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct RevealInfo<BlockNumber, Hash> {
+	/// The first block the metadata could be revealed at
+	pub reveal_at: BlockNumber,
+	/// The hash of the revealed metadata
+	pub metadata_hash: Hash,
+}
+
 pub use module::*;
 
 #[frame_support::pallet]
@@ -65,18 +177,37 @@ pub mod module {
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		/// The class ID type
 		type ClassId: Parameter + Member + AtLeast32BitUnsigned + Default + Copy;
 		/// The token ID type
 		type TokenId: Parameter + Member + AtLeast32BitUnsigned + Default + Copy;
 		/// The class properties type
-		type ClassData: Parameter + Member + MaybeSerializeDeserialize;
+		type ClassData: Parameter + Member + MaybeSerializeDeserialize + HasClassProperties<Self::BlockNumber>;
 		/// The token properties type
 		type TokenData: Parameter + Member + MaybeSerializeDeserialize;
 		/// The maximum size of a class's metadata
 		type MaxClassMetadata: Get<u32>;
 		/// The maximum size of a token's metadata
 		type MaxTokenMetadata: Get<u32>;
+		/// The maximum number of tokens minted or transferred in a batch
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
+		/// The currency to pay for vouchers
+		type Currency: MultiCurrency<Self::AccountId>;
+		/// The signature type of vouchers
+		type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+		/// The public key type of voucher signers
+		type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+		/// The maximum number of redeemed vouchers pruned per block
+		#[pallet::constant]
+		type MaxVoucherPrunes: Get<u32>;
+		/// Helper to sign vouchers in benchmarks
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::OffchainPublic, Self::AccountId, Self::OffchainSignature>;
+		/// Weight information for extrinsics in this module.
+		type WeightInfo: WeightInfo;
 	}
 
 	pub type ClassMetadataOf<T> = BoundedVec<u8, <T as Config>::MaxClassMetadata>;
@@ -89,6 +220,17 @@ pub mod module {
 	>;
 	pub type TokenInfoOf<T> =
 		TokenInfo<<T as frame_system::Config>::AccountId, <T as Config>::TokenData, TokenMetadataOf<T>>;
+	pub type CurrencyIdOf<T> =
+		<<T as Config>::Currency as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
+	pub type BalanceOf<T> = <<T as Config>::Currency as MultiCurrency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type MintVoucherOf<T> = MintVoucher<
+		<T as Config>::ClassId,
+		<T as frame_system::Config>::Hash,
+		<T as Config>::TokenData,
+		CurrencyIdOf<T>,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
 
 	pub type GenesisTokenData<T> = (
 		<T as frame_system::Config>::AccountId, // Token owner
@@ -120,6 +262,46 @@ pub mod module {
 		CannotDestroyClass,
 		/// Failed because the Maximum amount of metadata was exceeded
 		MaxMetadataExceeded,
+		/// The voucher is expired
+		VoucherExpired,
+		/// The voucher is already redeemed
+		VoucherRedeemed,
+		/// The voucher is not signed by the class owner
+		InvalidSignature,
+		/// The metadata doesn't match the voucher metadata hash
+		MetadataMismatch,
+		/// Tokens of the class are not transferable
+		NonTransferable,
+		/// Tokens of the class are not burnable
+		NonBurnable,
+		/// Tokens of the class are no longer mintable
+		NonMintable,
+		/// The batch exceeds `MaxBatchSize`
+		BatchTooLarge,
+		/// The class is frozen
+		ClassFrozen,
+		/// A reveal is already scheduled for the class
+		RevealAlreadyScheduled,
+		/// No reveal is scheduled for the class
+		RevealNotScheduled,
+		/// The reveal block is not reached yet
+		RevealNotDue,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A token is minted in a batch. [owner, class_id, token_id]
+		Minted(T::AccountId, T::ClassId, T::TokenId),
+		/// A token is transferred in a batch. [from, to, class_id, token_id]
+		Transferred(T::AccountId, T::AccountId, T::ClassId, T::TokenId),
+		/// A class is frozen. [class_id]
+		ClassFrozen(T::ClassId),
+		/// A reveal of class metadata is scheduled. [class_id, reveal_at,
+		/// metadata_hash]
+		RevealScheduled(T::ClassId, T::BlockNumber, T::Hash),
+		/// The class metadata is revealed. [class_id]
+		Revealed(T::ClassId),
 	}
 
 	/// Next available class ID.
@@ -161,6 +343,28 @@ pub mod module {
 		ValueQuery,
 	>;
 
+	/// Redeemed vouchers by expiry and hash, pruned once expired.
+	#[pallet::storage]
+	#[pallet::getter(fn redeemed_vouchers)]
+	pub type RedeemedVouchers<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Identity, T::Hash, (), OptionQuery>;
+
+	/// The next expiry of which redeemed vouchers are pruned.
+	#[pallet::storage]
+	#[pallet::getter(fn voucher_prune_cursor)]
+	pub type VoucherPruneCursor<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// Frozen classes, of which no more tokens could be minted.
+	#[pallet::storage]
+	#[pallet::getter(fn frozen_classes)]
+	pub type FrozenClasses<T: Config> = StorageMap<_, Twox64Concat, T::ClassId, (), OptionQuery>;
+
+	/// Scheduled reveals of class metadata.
+	#[pallet::storage]
+	#[pallet::getter(fn reveals)]
+	pub type Reveals<T: Config> =
+		StorageMap<_, Twox64Concat, T::ClassId, RevealInfo<T::BlockNumber, T::Hash>, OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub tokens: Vec<GenesisTokens<T>>,
@@ -191,13 +395,228 @@ pub mod module {
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			Self::prune_redeemed_vouchers(now)
+		}
+	}
 
 	#[pallet::call]
-	impl<T: Config> Pallet<T> {}
+	impl<T: Config> Pallet<T> {
+		/// Mint a token by redeeming a voucher signed by the class owner, and
+		/// pay the voucher price to the class owner.
+		///
+		/// The dispatch origin for this call must be `Signed` by the buyer,
+		/// who owns the minted token.
+		///
+		/// - `voucher`: the voucher signed by the class owner.
+		/// - `metadata`: the token metadata, must match `voucher.metadata_hash`.
+		/// - `signature`: the signature of `voucher_payload(voucher)`.
+		#[pallet::weight(T::WeightInfo::mint_with_voucher())]
+		#[transactional]
+		pub fn mint_with_voucher(
+			origin: OriginFor<T>,
+			voucher: MintVoucherOf<T>,
+			metadata: Vec<u8>,
+			signature: T::OffchainSignature,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= voucher.expiry,
+				Error::<T>::VoucherExpired
+			);
+
+			let voucher_hash = T::Hashing::hash_of(&voucher);
+			ensure!(
+				!RedeemedVouchers::<T>::contains_key(voucher.expiry, voucher_hash),
+				Error::<T>::VoucherRedeemed
+			);
+
+			let class_owner = Self::classes(voucher.class_id).ok_or(Error::<T>::ClassNotFound)?.owner;
+			ensure!(
+				signature.verify(&Self::voucher_payload(&voucher)[..], &class_owner),
+				Error::<T>::InvalidSignature
+			);
+			ensure!(
+				T::Hashing::hash(&metadata[..]) == voucher.metadata_hash,
+				Error::<T>::MetadataMismatch
+			);
+
+			T::Currency::transfer(voucher.currency_id, &who, &class_owner, voucher.price)?;
+			Self::mint(&who, voucher.class_id, metadata, voucher.data)?;
+			RedeemedVouchers::<T>::insert(voucher.expiry, voucher_hash, ());
+
+			Ok(())
+		}
+
+		/// Mint a token of `class_id` for each of `metadata`, all with
+		/// `data`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the class
+		/// owner, who owns the minted tokens.
+		#[pallet::weight(T::WeightInfo::mint_batch(metadata.len() as u32))]
+		#[transactional]
+		pub fn mint_batch(
+			origin: OriginFor<T>,
+			class_id: T::ClassId,
+			metadata: Vec<Vec<u8>>,
+			data: T::TokenData,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				metadata.len() as u32 <= T::MaxBatchSize::get(),
+				Error::<T>::BatchTooLarge
+			);
+			let class_owner = Self::classes(class_id).ok_or(Error::<T>::ClassNotFound)?.owner;
+			ensure!(who == class_owner, Error::<T>::NoPermission);
+
+			for token_metadata in metadata {
+				let token_id = Self::mint(&who, class_id, token_metadata, data.clone())?;
+				Self::deposit_event(Event::Minted(who.clone(), class_id, token_id));
+			}
+			Ok(())
+		}
+
+		/// Transfer each of `transfers` tokens to its destination.
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner of
+		/// the tokens.
+		#[pallet::weight(T::WeightInfo::transfer_batch(transfers.len() as u32))]
+		#[transactional]
+		pub fn transfer_batch(
+			origin: OriginFor<T>,
+			transfers: Vec<((T::ClassId, T::TokenId), <T::Lookup as StaticLookup>::Source)>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				transfers.len() as u32 <= T::MaxBatchSize::get(),
+				Error::<T>::BatchTooLarge
+			);
+
+			for (token, dest) in transfers {
+				let to = T::Lookup::lookup(dest)?;
+				Self::transfer(&who, &to, token)?;
+				Self::deposit_event(Event::Transferred(who.clone(), to, token.0, token.1));
+			}
+			Ok(())
+		}
+
+		/// Freeze `class_id`, no more tokens of the class could be minted.
+		///
+		/// The dispatch origin for this call must be `Signed` by the class
+		/// owner.
+		#[pallet::weight(T::WeightInfo::freeze_class())]
+		pub fn freeze_class(origin: OriginFor<T>, class_id: T::ClassId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_class_owner(&who, class_id)?;
+
+			FrozenClasses::<T>::insert(class_id, ());
+			Self::deposit_event(Event::ClassFrozen(class_id));
+			Ok(())
+		}
+
+		/// Schedule the reveal of the metadata of `class_id` at `reveal_at`,
+		/// committing to `metadata_hash`. The current class metadata is the
+		/// placeholder until the reveal.
+		///
+		/// The dispatch origin for this call must be `Signed` by the class
+		/// owner.
+		#[pallet::weight(T::WeightInfo::schedule_reveal())]
+		pub fn schedule_reveal(
+			origin: OriginFor<T>,
+			class_id: T::ClassId,
+			reveal_at: T::BlockNumber,
+			metadata_hash: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_class_owner(&who, class_id)?;
+			ensure!(
+				!Reveals::<T>::contains_key(class_id),
+				Error::<T>::RevealAlreadyScheduled
+			);
+
+			Reveals::<T>::insert(
+				class_id,
+				RevealInfo {
+					reveal_at,
+					metadata_hash,
+				},
+			);
+			Self::deposit_event(Event::RevealScheduled(class_id, reveal_at, metadata_hash));
+			Ok(())
+		}
+
+		/// Replace the metadata of `class_id` by `metadata`, once the reveal
+		/// block is reached.
+		///
+		/// The dispatch origin for this call must be `Signed`, by anyone
+		/// knowing the committed metadata.
+		///
+		/// - `metadata`: the class metadata, must match the committed hash.
+		#[pallet::weight(T::WeightInfo::reveal())]
+		pub fn reveal(origin: OriginFor<T>, class_id: T::ClassId, metadata: Vec<u8>) -> DispatchResult {
+			ensure_signed(origin)?;
+			let reveal = Self::reveals(class_id).ok_or(Error::<T>::RevealNotScheduled)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= reveal.reveal_at,
+				Error::<T>::RevealNotDue
+			);
+			ensure!(
+				T::Hashing::hash(&metadata[..]) == reveal.metadata_hash,
+				Error::<T>::MetadataMismatch
+			);
+			let bounded_metadata: ClassMetadataOf<T> =
+				metadata.try_into().map_err(|_| Error::<T>::MaxMetadataExceeded)?;
+
+			Classes::<T>::try_mutate(class_id, |class_info| -> DispatchResult {
+				let info = class_info.as_mut().ok_or(Error::<T>::ClassNotFound)?;
+				info.metadata = bounded_metadata;
+				Ok(())
+			})?;
+			Reveals::<T>::remove(class_id);
+			Self::deposit_event(Event::Revealed(class_id));
+			Ok(())
+		}
+	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// The payload signed by the class owner for `voucher`, bound to this
+	/// module and chain.
+	pub fn voucher_payload(voucher: &MintVoucherOf<T>) -> Vec<u8> {
+		let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+		(VOUCHER_SIGNING_CONTEXT, genesis_hash, voucher).encode()
+	}
+
+	/// Prune the redeemed vouchers expired before `now`, at most
+	/// `MaxVoucherPrunes` of them, or of the expiries stepped over, per
+	/// block. Returns the consumed weight.
+	fn prune_redeemed_vouchers(now: T::BlockNumber) -> Weight {
+		let mut budget = T::MaxVoucherPrunes::get();
+		let mut cursor = Self::voucher_prune_cursor();
+		let mut reads: Weight = 1;
+		let mut writes: Weight = 0;
+		while cursor < now && budget > 0 {
+			let removed = RedeemedVouchers::<T>::drain_prefix(cursor)
+				.take(budget as usize)
+				.count() as u32;
+			reads += removed.max(1) as Weight;
+			writes += removed as Weight;
+			if removed < budget {
+				// all removed, stepping over an expiry costs at least one
+				budget = budget.saturating_sub(removed.max(1));
+				cursor = cursor.saturating_add(One::one());
+			} else {
+				budget = 0;
+			}
+		}
+		if reads > 1 {
+			VoucherPruneCursor::<T>::put(cursor);
+			writes += 1;
+		}
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
 	/// Create NFT(non fungible token) class
 	pub fn create_class(
 		owner: &T::AccountId,
@@ -229,6 +648,10 @@ impl<T: Config> Pallet<T> {
 		Tokens::<T>::try_mutate(token.0, token.1, |token_info| -> DispatchResult {
 			let mut info = token_info.as_mut().ok_or(Error::<T>::TokenNotFound)?;
 			ensure!(info.owner == *from, Error::<T>::NoPermission);
+			ensure!(
+				Self::class_properties(token.0)?.transferable,
+				Error::<T>::NonTransferable
+			);
 			if from == to {
 				// no change needed
 				return Ok(());
@@ -250,6 +673,8 @@ impl<T: Config> Pallet<T> {
 		metadata: Vec<u8>,
 		data: T::TokenData,
 	) -> Result<T::TokenId, DispatchError> {
+		ensure!(!FrozenClasses::<T>::contains_key(class_id), Error::<T>::ClassFrozen);
+
 		NextTokenId::<T>::try_mutate(class_id, |id| -> Result<T::TokenId, DispatchError> {
 			let bounded_metadata: BoundedVec<u8, T::MaxTokenMetadata> =
 				metadata.try_into().map_err(|_| Error::<T>::MaxMetadataExceeded)?;
@@ -259,6 +684,12 @@ impl<T: Config> Pallet<T> {
 
 			Classes::<T>::try_mutate(class_id, |class_info| -> DispatchResult {
 				let info = class_info.as_mut().ok_or(Error::<T>::ClassNotFound)?;
+				if let Some(mintable_until) = info.data.class_properties().mintable_until {
+					ensure!(
+						frame_system::Pallet::<T>::block_number() <= mintable_until,
+						Error::<T>::NonMintable
+					);
+				}
 				info.total_issuance = info
 					.total_issuance
 					.checked_add(&One::one())
@@ -286,6 +717,7 @@ impl<T: Config> Pallet<T> {
 
 			Classes::<T>::try_mutate(token.0, |class_info| -> DispatchResult {
 				let info = class_info.as_mut().ok_or(Error::<T>::ClassNotFound)?;
+				ensure!(info.data.class_properties().burnable, Error::<T>::NonBurnable);
 				info.total_issuance = info
 					.total_issuance
 					.checked_sub(&One::one())
@@ -307,11 +739,27 @@ impl<T: Config> Pallet<T> {
 			ensure!(info.total_issuance == Zero::zero(), Error::<T>::CannotDestroyClass);
 
 			NextTokenId::<T>::remove(class_id);
+			FrozenClasses::<T>::remove(class_id);
+			Reveals::<T>::remove(class_id);
 
 			Ok(())
 		})
 	}
 
+	/// The properties of class `class_id`
+	pub fn class_properties(class_id: T::ClassId) -> Result<ClassProperties<T::BlockNumber>, DispatchError> {
+		Ok(Self::classes(class_id)
+			.ok_or(Error::<T>::ClassNotFound)?
+			.data
+			.class_properties())
+	}
+
+	fn ensure_class_owner(who: &T::AccountId, class_id: T::ClassId) -> DispatchResult {
+		let class_owner = Self::classes(class_id).ok_or(Error::<T>::ClassNotFound)?.owner;
+		ensure!(*who == class_owner, Error::<T>::NoPermission);
+		Ok(())
+	}
+
 	pub fn is_owner(account: &T::AccountId, token: (T::ClassId, T::TokenId)) -> bool {
 		TokensByOwner::<T>::contains_key((account, token.0, token.1))
 	}
@@ -1,10 +1,17 @@
-//! Mocks for the gradually-update module.
+//! Mocks for the non-fungible-token module.
 
 #![cfg(test)]
 
-use frame_support::{construct_runtime, parameter_types, traits::Everything};
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{Everything, Nothing},
+};
+use orml_traits::parameter_type_with_key;
 use sp_core::H256;
-use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_runtime::{
+	testing::{Header, TestSignature, UintAuthorityId},
+	traits::IdentityLookup,
+};
 
 use super::*;
 
@@ -14,7 +21,9 @@ parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 }
 
-pub type AccountId = u128;
+pub type AccountId = u64;
+pub type Balance = u64;
+pub type CurrencyId = u32;
 pub type BlockNumber = u64;
 
 impl frame_system::Config for Runtime {
@@ -43,18 +52,75 @@ impl frame_system::Config for Runtime {
 	type OnSetCode = ();
 }
 
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+parameter_types! {
+	pub const MaxLocks: u32 = 10;
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = i64;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+	type OnSlash = ();
+	type OnDeposit = ();
+	type OnTransfer = ();
+	type MaxLocks = MaxLocks;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = Nothing;
+	type OnNewTokenAccount = ();
+	type OnKilledTokenAccount = ();
+	type TransferFee = ();
+	type TransferFeeExemptions = Nothing;
+	type OnLockChanged = ();
+}
+
 parameter_types! {
 	pub const MaxClassMetadata: u32 = 1;
 	pub const MaxTokenMetadata: u32 = 1;
+	pub const MaxBatchSize: u32 = 3;
+	pub const MaxVoucherPrunes: u32 = 2;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct VoucherSigner;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<UintAuthorityId, AccountId, TestSignature> for VoucherSigner {
+	fn signer() -> (UintAuthorityId, AccountId) {
+		(UintAuthorityId(ALICE), ALICE)
+	}
+
+	fn sign(signer: &UintAuthorityId, message: &[u8]) -> TestSignature {
+		TestSignature(signer.0, message.to_vec())
+	}
 }
 
 impl Config for Runtime {
+	type Event = Event;
 	type ClassId = u64;
 	type TokenId = u64;
-	type ClassData = ();
+	type ClassData = ClassProperties<BlockNumber>;
 	type TokenData = ();
 	type MaxClassMetadata = MaxClassMetadata;
 	type MaxTokenMetadata = MaxTokenMetadata;
+	type MaxBatchSize = MaxBatchSize;
+	type Currency = OrmlTokens;
+	type OffchainSignature = TestSignature;
+	type OffchainPublic = UintAuthorityId;
+	type MaxVoucherPrunes = MaxVoucherPrunes;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = VoucherSigner;
+	type WeightInfo = ();
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -67,12 +133,14 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
-		NonFungibleTokenModule: nft::{Pallet, Storage, Config<T>},
+		OrmlTokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
+		NonFungibleTokenModule: nft::{Pallet, Call, Storage, Event<T>, Config<T>},
 	}
 );
 
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
+pub const DOT: CurrencyId = 1;
 pub const CLASS_ID: <Runtime as Config>::ClassId = 0;
 pub const CLASS_ID_NOT_EXIST: <Runtime as Config>::ClassId = 100;
 pub const TOKEN_ID: <Runtime as Config>::TokenId = 0;
@@ -88,10 +156,16 @@ impl Default for ExtBuilder {
 
 impl ExtBuilder {
 	pub fn build(self) -> sp_io::TestExternalities {
-		let t = frame_system::GenesisConfig::default()
+		let mut t = frame_system::GenesisConfig::default()
 			.build_storage::<Runtime>()
 			.unwrap();
 
+		orml_tokens::GenesisConfig::<Runtime> {
+			balances: vec![(ALICE, DOT, 1_000), (BOB, DOT, 1_000)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		let mut ext = sp_io::TestExternalities::new(t);
 		ext.execute_with(|| System::set_block_number(1));
 		ext
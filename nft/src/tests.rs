@@ -5,11 +5,16 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::*;
+use sp_runtime::testing::TestSignature;
 
 #[test]
 fn create_class_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			Default::default()
+		));
 	});
 }
 
@@ -18,7 +23,7 @@ fn create_class_should_fail() {
 	ExtBuilder::default().build().execute_with(|| {
 		NextClassId::<Runtime>::mutate(|id| *id = <Runtime as Config>::ClassId::max_value());
 		assert_noop!(
-			NonFungibleTokenModule::create_class(&ALICE, vec![1], ()),
+			NonFungibleTokenModule::create_class(&ALICE, vec![1], Default::default()),
 			Error::<Runtime>::NoAvailableClassId
 		);
 	});
@@ -29,7 +34,11 @@ fn mint_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		let next_class_id = NonFungibleTokenModule::next_class_id();
 		assert_eq!(next_class_id, CLASS_ID);
-		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			Default::default()
+		));
 		assert_eq!(NonFungibleTokenModule::next_token_id(CLASS_ID), 0);
 		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
 		assert_eq!(NonFungibleTokenModule::next_token_id(CLASS_ID), 1);
@@ -37,7 +46,11 @@ fn mint_should_work() {
 		assert_eq!(NonFungibleTokenModule::next_token_id(CLASS_ID), 2);
 
 		let next_class_id = NonFungibleTokenModule::next_class_id();
-		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			Default::default()
+		));
 		assert_eq!(NonFungibleTokenModule::next_token_id(next_class_id), 0);
 		assert_ok!(NonFungibleTokenModule::mint(&BOB, next_class_id, vec![1], ()));
 		assert_eq!(NonFungibleTokenModule::next_token_id(next_class_id), 1);
@@ -49,7 +62,11 @@ fn mint_should_work() {
 #[test]
 fn mint_should_fail() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			Default::default()
+		));
 		Classes::<Runtime>::mutate(CLASS_ID, |class_info| {
 			class_info.as_mut().unwrap().total_issuance = <Runtime as Config>::TokenId::max_value();
 		});
@@ -69,7 +86,11 @@ fn mint_should_fail() {
 #[test]
 fn transfer_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			Default::default()
+		));
 		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
 		assert_ok!(NonFungibleTokenModule::transfer(&BOB, &BOB, (CLASS_ID, TOKEN_ID)));
 		assert_ok!(NonFungibleTokenModule::transfer(&BOB, &ALICE, (CLASS_ID, TOKEN_ID)));
@@ -81,7 +102,11 @@ fn transfer_should_work() {
 #[test]
 fn transfer_should_fail() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			Default::default()
+		));
 		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
 		assert_noop!(
 			NonFungibleTokenModule::transfer(&BOB, &ALICE, (CLASS_ID, TOKEN_ID_NOT_EXIST)),
@@ -105,7 +130,11 @@ fn transfer_should_fail() {
 #[test]
 fn burn_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			Default::default()
+		));
 		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
 		assert_ok!(NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID)));
 	});
@@ -114,7 +143,11 @@ fn burn_should_work() {
 #[test]
 fn burn_should_fail() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			Default::default()
+		));
 		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
 		assert_noop!(
 			NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID_NOT_EXIST)),
@@ -128,7 +161,11 @@ fn burn_should_fail() {
 	});
 
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			Default::default()
+		));
 		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
 
 		Classes::<Runtime>::mutate(CLASS_ID, |class_info| {
@@ -144,7 +181,11 @@ fn burn_should_fail() {
 #[test]
 fn destroy_class_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			Default::default()
+		));
 		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
 		assert_ok!(NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID)));
 		assert_ok!(NonFungibleTokenModule::destroy_class(&ALICE, CLASS_ID));
@@ -156,7 +197,11 @@ fn destroy_class_should_work() {
 #[test]
 fn destroy_class_should_fail() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			Default::default()
+		));
 		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
 		assert_noop!(
 			NonFungibleTokenModule::destroy_class(&ALICE, CLASS_ID_NOT_EXIST),
@@ -183,13 +228,385 @@ fn destroy_class_should_fail() {
 fn exceeding_max_metadata_should_fail() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_noop!(
-			NonFungibleTokenModule::create_class(&ALICE, vec![1, 2], ()),
+			NonFungibleTokenModule::create_class(&ALICE, vec![1, 2], Default::default()),
 			Error::<Runtime>::MaxMetadataExceeded
 		);
-		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			Default::default()
+		));
 		assert_noop!(
 			NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1, 2], ()),
 			Error::<Runtime>::MaxMetadataExceeded
 		);
 	});
 }
+
+#[test]
+fn class_properties_should_restrict_transfer_and_burn() {
+	ExtBuilder::default().build().execute_with(|| {
+		let properties = ClassProperties {
+			transferable: false,
+			burnable: false,
+			mintable_until: None,
+		};
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], properties));
+		assert_eq!(NonFungibleTokenModule::class_properties(CLASS_ID), Ok(properties));
+		assert_eq!(
+			NonFungibleTokenModule::class_properties(CLASS_ID_NOT_EXIST),
+			Err(Error::<Runtime>::ClassNotFound.into())
+		);
+
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_noop!(
+			NonFungibleTokenModule::transfer(&BOB, &ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NonTransferable
+		);
+		assert_noop!(
+			NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NonBurnable
+		);
+		assert!(NonFungibleTokenModule::is_owner(&BOB, (CLASS_ID, TOKEN_ID)));
+	});
+}
+
+#[test]
+fn class_properties_should_restrict_mint_period() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			ClassProperties {
+				transferable: true,
+				burnable: true,
+				mintable_until: Some(5),
+			}
+		));
+
+		System::set_block_number(5);
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+
+		System::set_block_number(6);
+		assert_noop!(
+			NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()),
+			Error::<Runtime>::NonMintable
+		);
+		assert_eq!(NonFungibleTokenModule::classes(CLASS_ID).unwrap().total_issuance, 1);
+
+		// existing tokens are not affected
+		assert_ok!(NonFungibleTokenModule::transfer(&BOB, &ALICE, (CLASS_ID, TOKEN_ID)));
+		assert_ok!(NonFungibleTokenModule::burn(&ALICE, (CLASS_ID, TOKEN_ID)));
+	});
+}
+
+fn signed_voucher(price: u64, expiry: u64) -> (MintVoucherOf<Runtime>, TestSignature) {
+	let voucher = MintVoucher {
+		class_id: CLASS_ID,
+		metadata_hash: <Runtime as frame_system::Config>::Hashing::hash(&[1][..]),
+		data: (),
+		currency_id: DOT,
+		price,
+		expiry,
+	};
+	let signature = TestSignature(ALICE, NonFungibleTokenModule::voucher_payload(&voucher));
+	(voucher, signature)
+}
+
+#[test]
+fn mint_with_voucher_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			Default::default()
+		));
+		let (voucher, signature) = signed_voucher(100, 10);
+
+		assert_ok!(NonFungibleTokenModule::mint_with_voucher(
+			Origin::signed(BOB),
+			voucher.clone(),
+			vec![1],
+			signature
+		));
+		assert!(NonFungibleTokenModule::is_owner(&BOB, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(OrmlTokens::free_balance(DOT, &ALICE), 1_100);
+		assert_eq!(OrmlTokens::free_balance(DOT, &BOB), 900);
+		assert_eq!(
+			NonFungibleTokenModule::redeemed_vouchers(
+				10,
+				<Runtime as frame_system::Config>::Hashing::hash_of(&voucher)
+			),
+			Some(())
+		);
+	});
+}
+
+#[test]
+fn mint_with_voucher_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (voucher, signature) = signed_voucher(100, 10);
+		assert_noop!(
+			NonFungibleTokenModule::mint_with_voucher(Origin::signed(BOB), voucher.clone(), vec![1], signature.clone()),
+			Error::<Runtime>::ClassNotFound
+		);
+
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			Default::default()
+		));
+		assert_noop!(
+			NonFungibleTokenModule::mint_with_voucher(
+				Origin::signed(BOB),
+				voucher.clone(),
+				vec![1],
+				TestSignature(BOB, NonFungibleTokenModule::voucher_payload(&voucher))
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+		// not bound to this module and chain
+		assert_noop!(
+			NonFungibleTokenModule::mint_with_voucher(
+				Origin::signed(BOB),
+				voucher.clone(),
+				vec![1],
+				TestSignature(ALICE, voucher.encode())
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+		assert_noop!(
+			NonFungibleTokenModule::mint_with_voucher(Origin::signed(BOB), voucher.clone(), vec![2], signature.clone()),
+			Error::<Runtime>::MetadataMismatch
+		);
+
+		let (expensive_voucher, expensive_signature) = signed_voucher(2_000, 10);
+		assert!(NonFungibleTokenModule::mint_with_voucher(
+			Origin::signed(BOB),
+			expensive_voucher,
+			vec![1],
+			expensive_signature
+		)
+		.is_err());
+		assert_eq!(NonFungibleTokenModule::next_token_id(CLASS_ID), 0);
+
+		assert_ok!(NonFungibleTokenModule::mint_with_voucher(
+			Origin::signed(BOB),
+			voucher.clone(),
+			vec![1],
+			signature.clone()
+		));
+		assert_noop!(
+			NonFungibleTokenModule::mint_with_voucher(Origin::signed(BOB), voucher, vec![1], signature),
+			Error::<Runtime>::VoucherRedeemed
+		);
+
+		System::set_block_number(11);
+		let (voucher, signature) = signed_voucher(100, 10);
+		assert_noop!(
+			NonFungibleTokenModule::mint_with_voucher(Origin::signed(BOB), voucher, vec![1], signature),
+			Error::<Runtime>::VoucherExpired
+		);
+	});
+}
+
+#[test]
+fn redeemed_vouchers_should_be_pruned_once_expired() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			Default::default()
+		));
+		for price in 1..=3 {
+			let (voucher, signature) = signed_voucher(price, 2);
+			assert_ok!(NonFungibleTokenModule::mint_with_voucher(
+				Origin::signed(BOB),
+				voucher,
+				vec![1],
+				signature
+			));
+		}
+		let (voucher, signature) = signed_voucher(4, 3);
+		assert_ok!(NonFungibleTokenModule::mint_with_voucher(
+			Origin::signed(BOB),
+			voucher,
+			vec![1],
+			signature
+		));
+
+		// not expired yet
+		NonFungibleTokenModule::on_initialize(2);
+		assert_eq!(NonFungibleTokenModule::voucher_prune_cursor(), 2);
+		assert_eq!(RedeemedVouchers::<Runtime>::iter_prefix(2).count(), 3);
+
+		// at most `MaxVoucherPrunes` per block
+		NonFungibleTokenModule::on_initialize(3);
+		assert_eq!(NonFungibleTokenModule::voucher_prune_cursor(), 2);
+		assert_eq!(RedeemedVouchers::<Runtime>::iter_prefix(2).count(), 1);
+
+		NonFungibleTokenModule::on_initialize(4);
+		assert_eq!(NonFungibleTokenModule::voucher_prune_cursor(), 3);
+		assert_eq!(RedeemedVouchers::<Runtime>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn mint_batch_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			Default::default()
+		));
+		assert_noop!(
+			NonFungibleTokenModule::mint_batch(Origin::signed(BOB), CLASS_ID, vec![vec![1]], ()),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NonFungibleTokenModule::mint_batch(Origin::signed(ALICE), CLASS_ID, vec![vec![1]; 4], ()),
+			Error::<Runtime>::BatchTooLarge
+		);
+
+		assert_ok!(NonFungibleTokenModule::mint_batch(
+			Origin::signed(ALICE),
+			CLASS_ID,
+			vec![vec![1]; 3],
+			()
+		));
+		System::assert_last_event(Event::NonFungibleTokenModule(crate::Event::Minted(ALICE, CLASS_ID, 2)));
+		assert_eq!(NonFungibleTokenModule::next_token_id(CLASS_ID), 3);
+		assert!(NonFungibleTokenModule::is_owner(&ALICE, (CLASS_ID, 0)));
+		assert!(NonFungibleTokenModule::is_owner(&ALICE, (CLASS_ID, 2)));
+	});
+}
+
+#[test]
+fn transfer_batch_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			Default::default()
+		));
+		assert_ok!(NonFungibleTokenModule::mint_batch(
+			Origin::signed(ALICE),
+			CLASS_ID,
+			vec![vec![1]; 2],
+			()
+		));
+		assert_noop!(
+			NonFungibleTokenModule::transfer_batch(Origin::signed(ALICE), vec![((CLASS_ID, 0), BOB); 4]),
+			Error::<Runtime>::BatchTooLarge
+		);
+		// the whole batch fails if one transfer fails
+		assert_noop!(
+			NonFungibleTokenModule::transfer_batch(
+				Origin::signed(ALICE),
+				vec![((CLASS_ID, 0), BOB), ((CLASS_ID, TOKEN_ID_NOT_EXIST), BOB)]
+			),
+			Error::<Runtime>::TokenNotFound
+		);
+
+		assert_ok!(NonFungibleTokenModule::transfer_batch(
+			Origin::signed(ALICE),
+			vec![((CLASS_ID, 0), BOB), ((CLASS_ID, 1), BOB)]
+		));
+		System::assert_last_event(Event::NonFungibleTokenModule(crate::Event::Transferred(
+			ALICE, BOB, CLASS_ID, 1,
+		)));
+		assert!(NonFungibleTokenModule::is_owner(&BOB, (CLASS_ID, 0)));
+		assert!(NonFungibleTokenModule::is_owner(&BOB, (CLASS_ID, 1)));
+	});
+}
+
+#[test]
+fn freeze_class_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			Default::default()
+		));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_noop!(
+			NonFungibleTokenModule::freeze_class(Origin::signed(BOB), CLASS_ID),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NonFungibleTokenModule::freeze_class(Origin::signed(ALICE), CLASS_ID_NOT_EXIST),
+			Error::<Runtime>::ClassNotFound
+		);
+
+		assert_ok!(NonFungibleTokenModule::freeze_class(Origin::signed(ALICE), CLASS_ID));
+		System::assert_last_event(Event::NonFungibleTokenModule(crate::Event::ClassFrozen(CLASS_ID)));
+		assert_noop!(
+			NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()),
+			Error::<Runtime>::ClassFrozen
+		);
+		assert_noop!(
+			NonFungibleTokenModule::mint_batch(Origin::signed(ALICE), CLASS_ID, vec![vec![1]], ()),
+			Error::<Runtime>::ClassFrozen
+		);
+		// minted tokens are still transferable
+		assert_ok!(NonFungibleTokenModule::transfer(&BOB, &ALICE, (CLASS_ID, TOKEN_ID)));
+	});
+}
+
+#[test]
+fn reveal_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(
+			&ALICE,
+			vec![1],
+			Default::default()
+		));
+		let metadata_hash = <Runtime as frame_system::Config>::Hashing::hash(&[2][..]);
+		assert_noop!(
+			NonFungibleTokenModule::schedule_reveal(Origin::signed(BOB), CLASS_ID, 10, metadata_hash),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NonFungibleTokenModule::reveal(Origin::signed(BOB), CLASS_ID, vec![2]),
+			Error::<Runtime>::RevealNotScheduled
+		);
+
+		assert_ok!(NonFungibleTokenModule::schedule_reveal(
+			Origin::signed(ALICE),
+			CLASS_ID,
+			10,
+			metadata_hash
+		));
+		System::assert_last_event(Event::NonFungibleTokenModule(crate::Event::RevealScheduled(
+			CLASS_ID,
+			10,
+			metadata_hash,
+		)));
+		assert_noop!(
+			NonFungibleTokenModule::schedule_reveal(Origin::signed(ALICE), CLASS_ID, 10, metadata_hash),
+			Error::<Runtime>::RevealAlreadyScheduled
+		);
+		assert_noop!(
+			NonFungibleTokenModule::reveal(Origin::signed(BOB), CLASS_ID, vec![2]),
+			Error::<Runtime>::RevealNotDue
+		);
+
+		// the placeholder is served until the reveal
+		System::set_block_number(10);
+		assert_eq!(
+			NonFungibleTokenModule::classes(CLASS_ID).unwrap().metadata.to_vec(),
+			vec![1]
+		);
+		assert_noop!(
+			NonFungibleTokenModule::reveal(Origin::signed(BOB), CLASS_ID, vec![3]),
+			Error::<Runtime>::MetadataMismatch
+		);
+		assert_ok!(NonFungibleTokenModule::reveal(Origin::signed(BOB), CLASS_ID, vec![2]));
+		System::assert_last_event(Event::NonFungibleTokenModule(crate::Event::Revealed(CLASS_ID)));
+		assert_eq!(
+			NonFungibleTokenModule::classes(CLASS_ID).unwrap().metadata.to_vec(),
+			vec![2]
+		);
+		assert_eq!(NonFungibleTokenModule::reveals(CLASS_ID), None);
+	});
+}
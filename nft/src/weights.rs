@@ -0,0 +1,74 @@
+//! Weights for orml_nft
+//!
+//! These weights are estimates, not benchmark output. Generate them with the
+//! benchmarks in `benchmarking.rs`:
+//!
+//! target/release/acala benchmark --chain=dev --steps=50 --repeat=20
+//! --pallet=orml_nft --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --heap-pages=4096 --output=./nft/src/weights.rs
+//! --template=../templates/orml-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for orml_nft.
+pub trait WeightInfo {
+	fn mint_with_voucher() -> Weight;
+	fn mint_batch(i: u32, ) -> Weight;
+	fn transfer_batch(i: u32, ) -> Weight;
+	fn freeze_class() -> Weight;
+	fn schedule_reveal() -> Weight;
+	fn reveal() -> Weight;
+}
+
+/// Default weights.
+impl WeightInfo for () {
+	fn mint_with_voucher() -> Weight {
+		(98_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	// Estimated, not generated: regenerate with the `mint_batch`
+	// benchmark in `benchmarking.rs`.
+	fn mint_batch(i: u32, ) -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add((36_000_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(i as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(i as Weight)))
+	}
+	// Estimated, not generated: regenerate with the `transfer_batch`
+	// benchmark in `benchmarking.rs`.
+	fn transfer_batch(i: u32, ) -> Weight {
+		(8_000_000 as Weight)
+			.saturating_add((30_000_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(i as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(i as Weight)))
+	}
+	// Estimated, not generated: regenerate with the `freeze_class`
+	// benchmark in `benchmarking.rs`.
+	fn freeze_class() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Estimated, not generated: regenerate with the `schedule_reveal`
+	// benchmark in `benchmarking.rs`.
+	fn schedule_reveal() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Estimated, not generated: regenerate with the `reveal`
+	// benchmark in `benchmarking.rs`.
+	fn reveal() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+}
@@ -0,0 +1,34 @@
+//! Benchmarks for the oracle rewards module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+
+benchmarks! {
+	where_clause { where T::OracleKey: From<u32> }
+
+	// a new key is rewarded, so the rewarded keys are counted
+	set_feed_reward {
+		let key: T::OracleKey = 0u32.into();
+		let reward = T::Currency::minimum_balance().saturating_add(One::one());
+	}: _<T::Origin>(T::GovernanceOrigin::successful_origin(), key.clone(), Some(reward))
+	verify {
+		assert_eq!(FeedRewards::<T>::get(&key), Some(reward));
+	}
+
+	// the payout is reduced by a missed feed
+	claim_rewards {
+		let caller: T::AccountId = whitelisted_caller();
+		let pending = T::Currency::minimum_balance().saturating_mul(1_000u32.into());
+		T::Currency::make_free_balance_be(&T::RewardPool::get(), pending.saturating_mul(2u32.into()));
+		PendingRewards::<T>::insert(&caller, pending);
+		MissedFeeds::<T>::insert(&caller, 1);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(PendingRewards::<T>::get(&caller).is_zero());
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::default().build(), crate::mock::Runtime);
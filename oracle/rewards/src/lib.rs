@@ -0,0 +1,328 @@
+//! # Oracle Rewards
+//!
+//! ## Overview
+//!
+//! This module pays oracle operators for reliable feeding, and implements
+//! `orml_traits::OnNewData` to be used as the `OnNewData` hook of the oracle
+//! module.
+//!
+//! `GovernanceOrigin` sets the reward of each oracle key. Feeding is
+//! accounted in sessions of `T::SessionPeriod` blocks: a member accrues the
+//! reward of a key the first time it feeds the key in a session, and misses
+//! the key if it hasn't fed it when the session ends. Members claim the
+//! accrued rewards from the `T::RewardPool` account, and each missed feed
+//! since the last claim reduces the payout by `T::MissPenalty`. The
+//! forfeited rewards are kept in the pool.
+//!
+//! Members count the rewarded keys they fed in a session as they feed. When
+//! a session ends, the missed feeds of at most `T::MaxSettlementsPerBlock`
+//! members are recorded per block, the rest in the following blocks, so the
+//! work per block doesn't grow with the members and keys.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement, SortedMembers},
+	transactional,
+};
+use frame_system::pallet_prelude::*;
+use orml_traits::OnNewData;
+use sp_runtime::{
+	traits::{One, Saturating, Zero},
+	Perbill, RuntimeDebug,
+};
+use sp_std::prelude::*;
+
+mod benchmarking;
+mod mock;
+mod tests;
+mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The recording of the feeds missed by members in an ended session.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct SessionSettlement<BlockNumber> {
+	/// The ended session.
+	pub session: BlockNumber,
+	/// The number of rewarded keys when the session ended.
+	pub keys: u32,
+	/// The index of the next member to record in the sorted members.
+	pub next_member: u32,
+}
+
+/// The rewarded keys fed by a member in its last session of feeding, and
+/// in the session before it.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct SessionFeedCount<BlockNumber> {
+	/// The last session the member fed in.
+	pub session: BlockNumber,
+	/// The rewarded keys fed in `session`.
+	pub fed: u32,
+	/// The rewarded keys fed in the session before `session`.
+	pub previous: u32,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	pub(crate) type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency rewards are paid in.
+		type Currency: Currency<Self::AccountId>;
+
+		/// The oracle key type.
+		type OracleKey: Parameter + Member;
+
+		/// Oracle operators, only members are rewarded.
+		type Members: SortedMembers<Self::AccountId>;
+
+		/// The account rewards are paid from.
+		type RewardPool: Get<Self::AccountId>;
+
+		/// The length of sessions in blocks. Zero to disable sessions, so
+		/// rewards are only accrued once per key and feeds are never missed.
+		#[pallet::constant]
+		type SessionPeriod: Get<Self::BlockNumber>;
+
+		/// The reduction of the payout for each missed feed.
+		#[pallet::constant]
+		type MissPenalty: Get<Perbill>;
+
+		/// The maximum number of members whose missed feeds are recorded per
+		/// block after a session ends. Members not recorded when the next
+		/// session ends are not charged for the earlier session.
+		#[pallet::constant]
+		type MaxSettlementsPerBlock: Get<u32>;
+
+		/// The origin which may set the rewards of keys.
+		type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// No rewards to claim.
+		NoRewards,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The reward of a key is set, `None` to stop rewarding it. [key,
+		/// reward]
+		FeedRewardSet(T::OracleKey, Option<BalanceOf<T>>),
+		/// Rewards are claimed. [who, payout, missed_feeds]
+		RewardsClaimed(T::AccountId, BalanceOf<T>, u32),
+	}
+
+	/// The reward of each key per session.
+	///
+	/// map OracleKey => Option<Balance>
+	#[pallet::storage]
+	#[pallet::getter(fn feed_rewards)]
+	pub type FeedRewards<T: Config> = StorageMap<_, Twox64Concat, T::OracleKey, BalanceOf<T>, OptionQuery>;
+
+	/// The number of keys with a reward.
+	///
+	/// RewardedKeyCount: u32
+	#[pallet::storage]
+	#[pallet::getter(fn rewarded_key_count)]
+	pub type RewardedKeyCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The last session each key was fed in by members.
+	///
+	/// double_map AccountId, OracleKey => Option<Session>
+	#[pallet::storage]
+	#[pallet::getter(fn session_feeds)]
+	pub type SessionFeeds<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::OracleKey, T::BlockNumber, OptionQuery>;
+
+	/// The rewarded keys fed by members in their last sessions of feeding.
+	///
+	/// map AccountId => SessionFeedCount
+	#[pallet::storage]
+	#[pallet::getter(fn session_feed_counts)]
+	pub type SessionFeedCounts<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, SessionFeedCount<T::BlockNumber>, ValueQuery>;
+
+	/// The recording of the missed feeds of the last ended session, until
+	/// all members are recorded.
+	///
+	/// PendingSettlement: Option<SessionSettlement>
+	#[pallet::storage]
+	#[pallet::getter(fn pending_settlement)]
+	pub type PendingSettlement<T: Config> = StorageValue<_, SessionSettlement<T::BlockNumber>, OptionQuery>;
+
+	/// The rewards accrued by members since their last claim.
+	///
+	/// map AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn pending_rewards)]
+	pub type PendingRewards<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// The feeds missed by members since their last claim.
+	///
+	/// map AccountId => u32
+	#[pallet::storage]
+	#[pallet::getter(fn missed_feeds)]
+	pub type MissedFeeds<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// `on_initialize` to end the session and record the missed feeds.
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let period = T::SessionPeriod::get();
+			let mut weight = T::DbWeight::get().reads(1);
+			if !period.is_zero() && !n.is_zero() && (n % period).is_zero() {
+				PendingSettlement::<T>::put(SessionSettlement {
+					session: n / period - One::one(),
+					keys: Self::rewarded_key_count(),
+					next_member: 0,
+				});
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+			}
+			weight.saturating_add(Self::record_missed_feeds())
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the reward of a key per session, `None` to stop rewarding it.
+		///
+		/// The dispatch origin for this call must be `GovernanceOrigin`.
+		#[pallet::weight(T::WeightInfo::set_feed_reward())]
+		pub fn set_feed_reward(
+			origin: OriginFor<T>,
+			key: T::OracleKey,
+			reward: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			match (FeedRewards::<T>::contains_key(&key), reward.is_some()) {
+				(false, true) => RewardedKeyCount::<T>::mutate(|count| *count = count.saturating_add(1)),
+				(true, false) => RewardedKeyCount::<T>::mutate(|count| *count = count.saturating_sub(1)),
+				_ => {}
+			}
+			FeedRewards::<T>::set(&key, reward);
+			Self::deposit_event(Event::FeedRewardSet(key, reward));
+			Ok(())
+		}
+
+		/// Claim the accrued rewards from the reward pool, reduced by the
+		/// missed feeds.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::claim_rewards())]
+		#[transactional]
+		pub fn claim_rewards(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let pending = PendingRewards::<T>::take(&who);
+			ensure!(!pending.is_zero(), Error::<T>::NoRewards);
+			let missed = MissedFeeds::<T>::take(&who);
+
+			let payout = pending.saturating_sub(Self::penalty(missed).mul_floor(pending));
+			T::Currency::transfer(&T::RewardPool::get(), &who, payout, ExistenceRequirement::AllowDeath)?;
+
+			Self::deposit_event(Event::RewardsClaimed(who, payout, missed));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The reduction of the payout for `missed` feeds.
+	pub fn penalty(missed: u32) -> Perbill {
+		Perbill::from_parts(T::MissPenalty::get().deconstruct().saturating_mul(missed))
+	}
+
+	/// The current session, always zero if sessions are disabled.
+	pub fn current_session() -> T::BlockNumber {
+		let period = T::SessionPeriod::get();
+		if period.is_zero() {
+			Zero::zero()
+		} else {
+			frame_system::Pallet::<T>::block_number() / period
+		}
+	}
+
+	/// Record the missed feeds in the pending settlement of at most
+	/// `T::MaxSettlementsPerBlock` members. Returns the consumed weight.
+	fn record_missed_feeds() -> Weight {
+		let mut settlement = match Self::pending_settlement() {
+			Some(settlement) => settlement,
+			None => return 0,
+		};
+
+		let members = T::Members::sorted_members();
+		let start = (settlement.next_member as usize).min(members.len());
+		let end = start
+			.saturating_add(T::MaxSettlementsPerBlock::get() as usize)
+			.min(members.len());
+		for who in &members[start..end] {
+			let count = Self::session_feed_counts(who);
+			let fed = if count.session == settlement.session {
+				count.fed
+			} else if count.session == settlement.session.saturating_add(One::one()) {
+				count.previous
+			} else {
+				0
+			};
+			let missed = settlement.keys.saturating_sub(fed);
+			if missed > 0 {
+				MissedFeeds::<T>::mutate(who, |count| *count = count.saturating_add(missed));
+			}
+		}
+
+		if end >= members.len() {
+			PendingSettlement::<T>::kill();
+		} else {
+			settlement.next_member = end as u32;
+			PendingSettlement::<T>::put(settlement);
+		}
+		let recorded = (end - start) as Weight;
+		T::DbWeight::get().reads_writes(recorded.saturating_mul(2).saturating_add(1), recorded.saturating_add(1))
+	}
+}
+
+impl<T: Config, Value> OnNewData<T::AccountId, T::OracleKey, Value> for Pallet<T> {
+	fn on_new_data(who: &T::AccountId, key: &T::OracleKey, _value: &Value) {
+		let session = Self::current_session();
+		if !T::Members::contains(who) || Self::session_feeds(who, key) == Some(session) {
+			return;
+		}
+		if let Some(reward) = Self::feed_rewards(key) {
+			SessionFeeds::<T>::insert(who, key, session);
+			SessionFeedCounts::<T>::mutate(who, |count| {
+				if count.session != session {
+					let previous = if count.session.saturating_add(One::one()) == session {
+						count.fed
+					} else {
+						0
+					};
+					*count = SessionFeedCount {
+						session,
+						fed: 0,
+						previous,
+					};
+				}
+				count.fed = count.fed.saturating_add(1);
+			});
+			PendingRewards::<T>::mutate(who, |pending| *pending = pending.saturating_add(reward));
+		}
+	}
+}
@@ -0,0 +1,155 @@
+//! Unit tests for the oracle rewards module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+fn feed(who: AccountId, key: u32) {
+	<OracleRewards as OnNewData<AccountId, u32, u32>>::on_new_data(&who, &key, &100);
+}
+
+#[test]
+fn set_feed_reward_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			OracleRewards::set_feed_reward(Origin::signed(ALICE), KEY_1, Some(100)),
+			BadOrigin
+		);
+
+		assert_ok!(OracleRewards::set_feed_reward(Origin::root(), KEY_1, Some(100)));
+		System::assert_last_event(Event::OracleRewards(crate::Event::FeedRewardSet(KEY_1, Some(100))));
+		assert_eq!(OracleRewards::feed_rewards(KEY_1), Some(100));
+
+		assert_ok!(OracleRewards::set_feed_reward(Origin::root(), KEY_1, None));
+		System::assert_last_event(Event::OracleRewards(crate::Event::FeedRewardSet(KEY_1, None)));
+		assert_eq!(OracleRewards::feed_rewards(KEY_1), None);
+	});
+}
+
+#[test]
+fn feeds_accrue_rewards_once_per_session() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(OracleRewards::set_feed_reward(Origin::root(), KEY_1, Some(100)));
+
+		feed(ALICE, KEY_1);
+		feed(ALICE, KEY_1);
+		assert_eq!(OracleRewards::pending_rewards(ALICE), 100);
+		assert_eq!(OracleRewards::session_feeds(ALICE, KEY_1), Some(0));
+
+		// keys without reward and non-members are not rewarded
+		feed(ALICE, KEY_2);
+		feed(CHARLIE, KEY_1);
+		assert_eq!(OracleRewards::pending_rewards(ALICE), 100);
+		assert_eq!(OracleRewards::session_feeds(ALICE, KEY_2), None);
+		assert_eq!(OracleRewards::pending_rewards(CHARLIE), 0);
+
+		// not the end of a session
+		OracleRewards::on_initialize(9);
+		assert_eq!(OracleRewards::pending_settlement(), None);
+
+		// one member recorded per block
+		System::set_block_number(10);
+		OracleRewards::on_initialize(10);
+		assert_eq!(OracleRewards::missed_feeds(ALICE), 0);
+		assert_eq!(
+			OracleRewards::pending_settlement(),
+			Some(SessionSettlement {
+				session: 0,
+				keys: 1,
+				next_member: 1,
+			})
+		);
+
+		// feeds of the new session don't count for the ended one
+		feed(BOB, KEY_1);
+		OracleRewards::on_initialize(11);
+		assert_eq!(OracleRewards::missed_feeds(BOB), 1);
+		assert_eq!(OracleRewards::pending_settlement(), None);
+
+		assert_eq!(OracleRewards::session_feeds(ALICE, KEY_1), Some(1));
+		assert_eq!(OracleRewards::pending_rewards(ALICE), 200);
+		assert_eq!(OracleRewards::pending_rewards(BOB), 100);
+	});
+}
+
+#[test]
+fn rewarded_keys_should_be_counted() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(OracleRewards::set_feed_reward(Origin::root(), KEY_1, Some(100)));
+		assert_ok!(OracleRewards::set_feed_reward(Origin::root(), KEY_1, Some(200)));
+		assert_ok!(OracleRewards::set_feed_reward(Origin::root(), KEY_2, Some(100)));
+		assert_eq!(OracleRewards::rewarded_key_count(), 2);
+
+		assert_ok!(OracleRewards::set_feed_reward(Origin::root(), KEY_1, None));
+		assert_ok!(OracleRewards::set_feed_reward(Origin::root(), KEY_1, None));
+		assert_eq!(OracleRewards::rewarded_key_count(), 1);
+	});
+}
+
+#[test]
+fn claim_rewards_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			OracleRewards::claim_rewards(Origin::signed(ALICE)),
+			Error::<Runtime>::NoRewards
+		);
+
+		assert_ok!(OracleRewards::set_feed_reward(Origin::root(), KEY_1, Some(100)));
+		assert_ok!(OracleRewards::set_feed_reward(Origin::root(), KEY_2, Some(50)));
+		feed(ALICE, KEY_1);
+		feed(ALICE, KEY_2);
+		feed(BOB, KEY_1);
+		System::set_block_number(10);
+		OracleRewards::on_initialize(10);
+		feed(ALICE, KEY_1);
+		feed(ALICE, KEY_2);
+		feed(BOB, KEY_2);
+		OracleRewards::on_initialize(11);
+		System::set_block_number(20);
+		OracleRewards::on_initialize(20);
+		OracleRewards::on_initialize(21);
+		assert_eq!(OracleRewards::missed_feeds(BOB), 2);
+
+		assert_ok!(OracleRewards::claim_rewards(Origin::signed(ALICE)));
+		System::assert_last_event(Event::OracleRewards(crate::Event::RewardsClaimed(ALICE, 300, 0)));
+		assert_eq!(PalletBalances::free_balance(ALICE), 300);
+
+		// 20% less for 2 missed feeds, the rest is kept in the pool
+		assert_ok!(OracleRewards::claim_rewards(Origin::signed(BOB)));
+		System::assert_last_event(Event::OracleRewards(crate::Event::RewardsClaimed(BOB, 120, 2)));
+		assert_eq!(PalletBalances::free_balance(BOB), 120);
+		assert_eq!(PalletBalances::free_balance(POOL), 580);
+		assert_eq!(OracleRewards::pending_rewards(BOB), 0);
+		assert_eq!(OracleRewards::missed_feeds(BOB), 0);
+
+		assert_noop!(
+			OracleRewards::claim_rewards(Origin::signed(BOB)),
+			Error::<Runtime>::NoRewards
+		);
+	});
+}
+
+#[test]
+fn claim_rewards_fails_if_pool_is_insufficient() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(OracleRewards::set_feed_reward(Origin::root(), KEY_1, Some(2000)));
+		feed(ALICE, KEY_1);
+
+		assert!(OracleRewards::claim_rewards(Origin::signed(ALICE)).is_err());
+		assert_eq!(OracleRewards::pending_rewards(ALICE), 2000);
+		assert_eq!(PalletBalances::free_balance(POOL), 1000);
+	});
+}
+
+#[test]
+fn penalty_saturates() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(OracleRewards::penalty(0), Perbill::from_percent(0));
+		assert_eq!(OracleRewards::penalty(3), Perbill::from_percent(30));
+		assert_eq!(OracleRewards::penalty(20), Perbill::from_percent(100));
+		assert_eq!(OracleRewards::penalty(u32::MAX), Perbill::from_percent(100));
+	});
+}
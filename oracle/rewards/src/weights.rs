@@ -0,0 +1,36 @@
+//! Weights for orml_oracle_rewards
+//!
+//! These weights are estimates, not benchmark output. Generate them with the
+//! benchmarks in `benchmarking.rs`:
+//!
+//! target/release/acala benchmark --chain=dev --steps=50 --repeat=20
+//! --pallet=orml_oracle_rewards --extrinsic=* --execution=wasm --wasm-execution=compiled
+//! --heap-pages=4096 --output=./oracle/rewards/src/weights.rs
+//! --template=../templates/orml-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+/// Weight functions needed for orml_oracle_rewards.
+pub trait WeightInfo {
+	fn set_feed_reward() -> Weight;
+	fn claim_rewards() -> Weight;
+}
+
+/// Default weights.
+impl WeightInfo for () {
+	fn set_feed_reward() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn claim_rewards() -> Weight {
+		(58_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+}
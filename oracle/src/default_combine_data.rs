@@ -31,9 +31,15 @@ where
 			return prev_value;
 		}
 
-		let mid_index = count / 2;
-		// Won't panic as `values` ensured not empty.
-		let (_, value, _) = values.select_nth_unstable_by(mid_index as usize, |a, b| a.value.cmp(&b.value));
-		Some(value.clone())
+		Some(median::<T, I>(values))
 	}
 }
+
+/// Returns the timestamped value with the median value of non-empty
+/// `values`.
+pub(crate) fn median<T: Config<I>, I: 'static>(mut values: Vec<TimestampedValueOf<T, I>>) -> TimestampedValueOf<T, I> {
+	let mid_index = values.len() / 2;
+	// Won't panic as `values` ensured not empty.
+	let (_, value, _) = values.select_nth_unstable_by(mid_index, |a, b| a.value.cmp(&b.value));
+	value.clone()
+}
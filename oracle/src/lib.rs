@@ -13,6 +13,35 @@
 //!
 //! The data is valid only if feeded by an authorized operator.
 //! `pallet_membership` in FRAME can be used to as source of `T::Members`.
+//!
+//! By default raw values are combined whenever the value is read after a new
+//! feed. If `T::RoundPeriod` is not zero, values are fed in rounds instead: a
+//! round of a key opens every `T::RoundPeriod` blocks, and the value of the
+//! key is only combined from the values fed in the round, once `T::Quorum` of
+//! the members has fed, or when the round times out. Rounds are timed out in
+//! `on_initialize`, at most `T::MaxRoundTimeouts` per block, the rest in the
+//! following blocks.
+//!
+//! The pallet implements `ChangeMembers`, to be notified by the source of
+//! `T::Members`, e.g. `pallet_membership`. The raw values of the outgoing
+//! members are removed, they no longer count as participants of the open
+//! rounds, and the quorum of the open rounds is checked again against the
+//! new members. Rounds are checked again in `on_initialize`, at most
+//! `T::MaxRoundRechecks` per block, resuming where the previous block
+//! stopped.
+//!
+//! Root can set bounds on the values of a key: a minimum, a maximum and a
+//! maximum change from the current combined value, checked by
+//! `T::ValueChange`. Fed values out of bounds are rejected with an event and
+//! never combined.
+//!
+//! `T::OnNewData` is called for each accepted value. Handlers interested in
+//! some keys only are subscribed with `SubscribedOnNewData`, and composed as
+//! tuples.
+//!
+//! Keys could be namespaced, e.g. `(MarketType, AssetPair)`, for one oracle
+//! to serve different kinds of feeds. `NamespacedCombineData` combines the
+//! values of each namespace with its own strategy, minimum count and expiry.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 // Disable the following two lints since they originate from an external macro (namely decl_storage)
@@ -29,25 +58,72 @@ use frame_support::{
 	pallet_prelude::*,
 	traits::{ChangeMembers, Get, SortedMembers, Time},
 	weights::{Pays, Weight},
-	Parameter,
+	BoundedVec, CloneNoBound, EqNoBound, Parameter, PartialEqNoBound, RuntimeDebugNoBound,
 };
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
-pub use orml_traits::{CombineData, DataFeeder, DataProvider, DataProviderExtended, OnNewData};
+pub use orml_traits::{CombineData, DataFeeder, DataProvider, DataProviderExtended, OnNewData, SubscribedOnNewData};
 use orml_utilities::OrderedSet;
 use scale_info::TypeInfo;
-use sp_runtime::{traits::Member, DispatchResult, RuntimeDebug};
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, Member, Zero},
+	DispatchResult, FixedPointNumber, PerThing, Perbill, RuntimeDebug,
+};
 use sp_std::{prelude::*, vec};
 
 pub use crate::default_combine_data::DefaultCombineData;
+pub use crate::namespaced_combine_data::{CombineConfig, CombineStrategy, NamespacedCombineData, NamespacedKey};
 
 mod default_combine_data;
+pub mod migration;
 mod mock;
+mod namespaced_combine_data;
 mod tests;
 mod weights;
 
 pub use module::*;
 pub use weights::WeightInfo;
 
+/// Check the change between two values against a maximum change.
+pub trait ValueChange<Value> {
+	/// True if the change from `old` to `new` is within `max_change` of
+	/// `old`.
+	fn is_within(old: &Value, new: &Value, max_change: Perbill) -> bool;
+}
+
+/// No change limit.
+impl<Value> ValueChange<Value> for () {
+	fn is_within(_old: &Value, _new: &Value, _max_change: Perbill) -> bool {
+		true
+	}
+}
+
+/// `ValueChange` for unsigned integer values.
+pub struct UnsignedChange;
+
+impl<Value: AtLeast32BitUnsigned> ValueChange<Value> for UnsignedChange {
+	fn is_within(old: &Value, new: &Value, max_change: Perbill) -> bool {
+		let change = if new > old {
+			new.clone() - old.clone()
+		} else {
+			old.clone() - new.clone()
+		};
+		change <= max_change.mul_ceil(old.clone())
+	}
+}
+
+/// `ValueChange` for unsigned fixed point values, e.g. `FixedU128`.
+pub struct FixedPointChange;
+
+impl<Value> ValueChange<Value> for FixedPointChange
+where
+	Value: FixedPointNumber,
+	Value::Inner: AtLeast32BitUnsigned,
+{
+	fn is_within(old: &Value, new: &Value, max_change: Perbill) -> bool {
+		UnsignedChange::is_within(&old.into_inner(), &new.into_inner(), max_change)
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -62,11 +138,35 @@ pub mod module {
 		pub timestamp: Moment,
 	}
 
+	/// A feeding round of a key.
+	#[derive(Encode, Decode, RuntimeDebugNoBound, EqNoBound, PartialEqNoBound, CloneNoBound, TypeInfo)]
+	#[scale_info(skip_type_params(T, I))]
+	pub struct Round<T: Config<I>, I: 'static = ()> {
+		/// The block the round opened at.
+		pub start: T::BlockNumber,
+		/// The accounts fed in the round, at most `T::MaxRoundParticipants`.
+		pub participants: BoundedVec<T::AccountId, T::MaxRoundParticipants>,
+		/// True if the value of the round has been finalized.
+		pub finalized: bool,
+	}
+
+	/// The bounds of the values of a key.
+	#[derive(Encode, Decode, RuntimeDebug, Eq, PartialEq, Clone, TypeInfo)]
+	pub struct Bounds<Value> {
+		/// The minimum value.
+		pub min: Option<Value>,
+		/// The maximum value.
+		pub max: Option<Value>,
+		/// The maximum change from the current combined value.
+		pub max_change: Option<Perbill>,
+	}
+
 	#[pallet::config]
 	pub trait Config<I: 'static = ()>: frame_system::Config {
 		type Event: From<Event<Self, I>> + IsType<<Self as frame_system::Config>::Event>;
 
-		/// Hook on new data received
+		/// Hook on new data received, `SubscribedOnNewData` subscribes
+		/// handlers to some keys only.
 		type OnNewData: OnNewData<Self::AccountId, Self::OracleKey, Self::OracleValue>;
 
 		/// Provide the implementation to combine raw values to produce
@@ -93,6 +193,35 @@ pub mod module {
 
 		/// Maximum size of HasDispatched
 		type MaxHasDispatchedSize: Get<u32>;
+
+		/// The length of feeding rounds in blocks. Zero to disable rounds and
+		/// combine values on every feed.
+		#[pallet::constant]
+		type RoundPeriod: Get<Self::BlockNumber>;
+
+		/// The proportion of members that must feed in a round to finalize
+		/// it before it times out.
+		#[pallet::constant]
+		type Quorum: Get<Perbill>;
+
+		/// The maximum number of participants counted in a round, at least
+		/// the maximum number of members.
+		#[pallet::constant]
+		type MaxRoundParticipants: Get<u32>;
+
+		/// The maximum number of rounds timed out, or of round periods
+		/// stepped over, per block.
+		#[pallet::constant]
+		type MaxRoundTimeouts: Get<u32>;
+
+		/// The maximum number of rounds checked again per block after the
+		/// members changed.
+		#[pallet::constant]
+		type MaxRoundRechecks: Get<u32>;
+
+		/// Check the change of fed values against the maximum change of
+		/// their bounds.
+		type ValueChange: ValueChange<Self::OracleValue>;
 	}
 
 	#[pallet::error]
@@ -108,6 +237,22 @@ pub mod module {
 	pub enum Event<T: Config<I>, I: 'static = ()> {
 		/// New feed data is submitted. [sender, values]
 		NewFeedData(T::AccountId, Vec<(T::OracleKey, T::OracleValue)>),
+		/// A fed value is out of bounds and rejected. [sender, key, value]
+		FeedRejected(T::AccountId, T::OracleKey, T::OracleValue),
+		/// The bounds of a key are set, `None` if removed. [key, bounds]
+		ValueBoundsSet(T::OracleKey, Option<Bounds<T::OracleValue>>),
+		/// The value of a round is finalized.
+		RoundFinalized {
+			key: T::OracleKey,
+			value: T::OracleValue,
+			participants: Vec<T::AccountId>,
+		},
+		/// The members are changed, the state of the outgoing members is
+		/// cleared.
+		MembersChanged {
+			incoming: Vec<T::AccountId>,
+			outgoing: Vec<T::AccountId>,
+		},
 	}
 
 	/// Raw values for each oracle operators
@@ -133,14 +278,48 @@ pub mod module {
 	pub(crate) type HasDispatched<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, OrderedSet<T::AccountId, T::MaxHasDispatchedSize>, ValueQuery>;
 
+	/// The current feeding round of each key
+	#[pallet::storage]
+	#[pallet::getter(fn rounds)]
+	pub type Rounds<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, <T as Config<I>>::OracleKey, Round<T, I>>;
+
+	/// The keys of the rounds opened at each block, until they time out
+	#[pallet::storage]
+	#[pallet::getter(fn open_rounds)]
+	pub type OpenRounds<T: Config<I>, I: 'static = ()> =
+		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, <T as Config<I>>::OracleKey, ()>;
+
+	/// The start of the oldest rounds which may not have timed out, `None`
+	/// until a round is opened
+	#[pallet::storage]
+	#[pallet::getter(fn round_timeout_cursor)]
+	pub type RoundTimeoutCursor<T: Config<I>, I: 'static = ()> = StorageValue<_, T::BlockNumber>;
+
+	/// Set when the members changed, the rounds are checked again against
+	/// the new members, resuming after the key, or from the first round if
+	/// `None`
+	#[pallet::storage]
+	#[pallet::getter(fn round_recheck_cursor)]
+	pub type RoundRecheckCursor<T: Config<I>, I: 'static = ()> = StorageValue<_, Option<<T as Config<I>>::OracleKey>>;
+
+	/// The bounds of the values of each key
+	#[pallet::storage]
+	#[pallet::getter(fn value_bounds)]
+	pub type ValueBounds<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, <T as Config<I>>::OracleKey, Bounds<T::OracleValue>>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
 	#[pallet::hooks]
 	impl<T: Config<I>, I: 'static> Hooks<T::BlockNumber> for Pallet<T, I> {
-		/// `on_initialize` to return the weight used in `on_finalize`.
-		fn on_initialize(_n: T::BlockNumber) -> Weight {
+		/// `on_initialize` to time out rounds, and return the weight used in
+		/// `on_finalize`.
+		fn on_initialize(n: T::BlockNumber) -> Weight {
 			T::WeightInfo::on_finalize()
+				.saturating_add(Self::time_out_rounds(n))
+				.saturating_add(Self::recheck_rounds())
 		}
 
 		fn on_finalize(_n: T::BlockNumber) {
@@ -154,7 +333,10 @@ pub mod module {
 		/// Feed the external value.
 		///
 		/// Require authorized operator.
-		#[pallet::weight(T::WeightInfo::feed_values(values.len() as u32))]
+		#[pallet::weight(
+			T::WeightInfo::feed_values(values.len() as u32)
+				.saturating_add(Pallet::<T, I>::round_feed_weight(values.len() as u32))
+		)]
 		pub fn feed_values(
 			origin: OriginFor<T>,
 			values: Vec<(T::OracleKey, T::OracleValue)>,
@@ -164,6 +346,21 @@ pub mod module {
 			Self::do_feed_values(feeder, values)?;
 			Ok(Pays::No.into())
 		}
+
+		/// Set the bounds of the values of `key`, `None` to remove them.
+		///
+		/// The dispatch origin for this call must be `Root`.
+		#[pallet::weight(T::WeightInfo::set_value_bounds())]
+		pub fn set_value_bounds(
+			origin: OriginFor<T>,
+			key: T::OracleKey,
+			bounds: Option<Bounds<T::OracleValue>>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ValueBounds::<T, I>::set(&key, bounds.clone());
+			Self::deposit_event(Event::ValueBoundsSet(key, bounds));
+			Ok(())
+		}
 	}
 }
 
@@ -177,11 +374,11 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	}
 
 	/// Returns fresh combined value if has update, or latest combined
-	/// value.
+	/// value. With rounds, returns the value of the last finalized round.
 	///
 	/// Note this will update values storage if has update.
 	pub fn get(key: &T::OracleKey) -> Option<TimestampedValueOf<T, I>> {
-		if Self::is_updated(key) {
+		if Self::is_updated(key) || Self::rounds_enabled() {
 			<Values<T, I>>::get(key)
 		} else {
 			let timestamped = Self::combined(key)?;
@@ -196,7 +393,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	///
 	/// This is a no-op function which would not change storage.
 	pub fn get_no_op(key: &T::OracleKey) -> Option<TimestampedValueOf<T, I>> {
-		if Self::is_updated(key) {
+		if Self::is_updated(key) || Self::rounds_enabled() {
 			Self::values(key)
 		} else {
 			Self::combined(key)
@@ -219,6 +416,200 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		T::CombineData::combine_data(key, values, Self::values(key))
 	}
 
+	/// True if `value` is within the bounds of `key`.
+	///
+	/// The change is limited from the fresh combined value, so the accepted
+	/// values move it even if nobody reads it. A move larger than
+	/// `max_change` in one step is rejected until the bounds are relaxed.
+	pub fn is_within_bounds(key: &T::OracleKey, value: &T::OracleValue) -> bool {
+		let bounds = match Self::value_bounds(key) {
+			Some(bounds) => bounds,
+			None => return true,
+		};
+		if bounds.min.map_or(false, |min| *value < min) || bounds.max.map_or(false, |max| *value > max) {
+			return false;
+		}
+		match (bounds.max_change, Self::get_no_op(key)) {
+			(Some(max_change), Some(current)) => T::ValueChange::is_within(&current.value, value, max_change),
+			_ => true,
+		}
+	}
+
+	fn rounds_enabled() -> bool {
+		!T::RoundPeriod::get().is_zero()
+	}
+
+	/// The weight of the rounds of `c` fed values, each possibly finalizing
+	/// a round from the raw values of its participants.
+	fn round_feed_weight(c: u32) -> Weight {
+		if !Self::rounds_enabled() {
+			return 0;
+		}
+		let c = c as Weight;
+		let reads = c.saturating_mul(T::MaxRoundParticipants::get() as Weight + 3);
+		T::DbWeight::get().reads_writes(reads, c.saturating_mul(4))
+	}
+
+	/// Time out the rounds opened before the current round period, at most
+	/// `T::MaxRoundTimeouts` of them, or of the round periods stepped over,
+	/// per block. Returns the consumed weight.
+	fn time_out_rounds(now: T::BlockNumber) -> Weight {
+		let period = T::RoundPeriod::get();
+		let mut cursor = match Self::round_timeout_cursor() {
+			Some(cursor) if !period.is_zero() => cursor,
+			_ => return T::DbWeight::get().reads(1),
+		};
+		let current_start = now - now % period;
+		let mut budget = T::MaxRoundTimeouts::get();
+		let mut reads: Weight = 1;
+		let mut writes: Weight = 0;
+		while cursor < current_start && budget > 0 {
+			let keys = OpenRounds::<T, I>::drain_prefix(cursor)
+				.take(budget as usize)
+				.map(|(key, _)| key)
+				.collect::<Vec<_>>();
+			let removed = keys.len() as u32;
+			reads += removed.max(1) as Weight;
+			writes += removed as Weight;
+			for key in keys {
+				reads += 1;
+				// the round may have been replaced by a round of a later period
+				if let Some(round) = Self::rounds(&key).filter(|round| round.start == cursor) {
+					Rounds::<T, I>::remove(&key);
+					writes += 1;
+					if !round.finalized {
+						reads += round.participants.len() as Weight + 1;
+						writes += 2;
+						Self::finalize_round(&key, round.participants.into_inner());
+					}
+				}
+			}
+			if removed < budget {
+				// all timed out, stepping over a round period costs at least one
+				budget = budget.saturating_sub(removed.max(1));
+				cursor = cursor - cursor % period + period;
+			} else {
+				budget = 0;
+			}
+		}
+		if reads > 1 {
+			RoundTimeoutCursor::<T, I>::put(cursor);
+			writes += 1;
+		}
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	/// Check the rounds against the current members after the members
+	/// changed, at most `T::MaxRoundRechecks` of them per block. Returns the
+	/// consumed weight.
+	fn recheck_rounds() -> Weight {
+		let last = match Self::round_recheck_cursor() {
+			Some(last) => last,
+			None => return T::DbWeight::get().reads(1),
+		};
+		let max = T::MaxRoundRechecks::get() as usize;
+		let batch = match last {
+			Some(key) => Rounds::<T, I>::iter_from(Rounds::<T, I>::hashed_key_for(key)),
+			None => Rounds::<T, I>::iter(),
+		}
+		.take(max)
+		.collect::<Vec<_>>();
+
+		let members = T::Members::sorted_members();
+		let root = T::RootOperatorAccountId::get();
+		let mut reads: Weight = 2;
+		let mut writes: Weight = 1;
+		let done = batch.len() < max;
+		let mut last = None;
+		for (key, mut round) in batch {
+			reads += 1;
+			if !round.finalized {
+				// outgoing members no longer count for the round
+				round
+					.participants
+					.retain(|who| *who == root || members.binary_search(who).is_ok());
+				Self::try_finalize_round(&key, &mut round, members.len());
+				if round.finalized {
+					reads += round.participants.len() as Weight + 1;
+					writes += 2;
+				}
+				Rounds::<T, I>::insert(&key, round);
+				writes += 1;
+			}
+			last = Some(key);
+		}
+
+		if done {
+			RoundRecheckCursor::<T, I>::kill();
+		} else {
+			RoundRecheckCursor::<T, I>::put(last);
+		}
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	/// Open a round of `key` at `start`, timed out in `on_initialize` after
+	/// its period.
+	fn open_round(key: &T::OracleKey, start: T::BlockNumber) -> Round<T, I> {
+		OpenRounds::<T, I>::insert(start, key, ());
+		if Self::round_timeout_cursor().is_none() {
+			RoundTimeoutCursor::<T, I>::put(start);
+		}
+		Round {
+			start,
+			participants: Default::default(),
+			finalized: false,
+		}
+	}
+
+	/// Add `who` to the participants of the current round of `key`, and
+	/// finalize the round if the quorum is reached.
+	fn participate_round(who: &T::AccountId, key: &T::OracleKey) {
+		let now = frame_system::Pallet::<T>::block_number();
+		let start = now - now % T::RoundPeriod::get();
+
+		let mut round = match Self::rounds(key) {
+			Some(round) if round.start == start => round,
+			maybe_round => {
+				// the previous round timed out
+				if let Some(round) = maybe_round.filter(|round| !round.finalized) {
+					Self::finalize_round(key, round.participants.into_inner());
+				}
+				Self::open_round(key, start)
+			}
+		};
+
+		if !round.participants.contains(who) {
+			// participants beyond `T::MaxRoundParticipants` are not counted
+			let _ = round.participants.try_push(who.clone());
+		}
+		Self::try_finalize_round(key, &mut round, T::Members::sorted_members().len());
+		Rounds::<T, I>::insert(key, round);
+	}
+
+	/// Finalize `round` of `key` if `T::Quorum` of `members_count` members
+	/// has fed.
+	fn try_finalize_round(key: &T::OracleKey, round: &mut Round<T, I>, members_count: usize) {
+		let quorum = T::Quorum::get().mul_ceil(members_count as u32);
+		if !round.finalized && !round.participants.is_empty() && round.participants.len() as u32 >= quorum {
+			round.finalized = true;
+			Self::finalize_round(key, round.participants.clone().into_inner());
+		}
+	}
+
+	/// Combine the raw values fed by `participants` into the value of `key`.
+	fn finalize_round(key: &T::OracleKey, participants: Vec<T::AccountId>) {
+		let values = participants.iter().filter_map(|x| Self::raw_values(x, key)).collect();
+		if let Some(timestamped) = T::CombineData::combine_data(key, values, Self::values(key)) {
+			Values::<T, I>::insert(key, timestamped.clone());
+			IsUpdated::<T, I>::insert(key, true);
+			Self::deposit_event(Event::RoundFinalized {
+				key: key.clone(),
+				value: timestamped.value,
+				participants,
+			});
+		}
+	}
+
 	fn do_feed_values(who: T::AccountId, values: Vec<(T::OracleKey, T::OracleValue)>) -> DispatchResult {
 		// ensure feeder is authorized
 		ensure!(
@@ -233,30 +624,57 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		);
 
 		let now = T::Time::now();
-		for (key, value) in &values {
+		let mut accepted = Vec::with_capacity(values.len());
+		for (key, value) in values {
+			if !Self::is_within_bounds(&key, &value) {
+				Self::deposit_event(Event::FeedRejected(who.clone(), key, value));
+				continue;
+			}
+
 			let timestamped = TimestampedValue {
 				value: value.clone(),
 				timestamp: now,
 			};
 			RawValues::<T, I>::insert(&who, &key, timestamped);
-			IsUpdated::<T, I>::remove(&key);
-
-			T::OnNewData::on_new_data(&who, key, value);
+			if Self::rounds_enabled() {
+				Self::participate_round(&who, &key);
+			} else {
+				IsUpdated::<T, I>::remove(&key);
+			}
+
+			T::OnNewData::on_new_data(&who, &key, &value);
+			accepted.push((key, value));
 		}
-		Self::deposit_event(Event::NewFeedData(who, values));
+		Self::deposit_event(Event::NewFeedData(who, accepted));
 		Ok(())
 	}
 }
 
 impl<T: Config<I>, I: 'static> ChangeMembers<T::AccountId> for Pallet<T, I> {
-	fn change_members_sorted(_incoming: &[T::AccountId], outgoing: &[T::AccountId], _new: &[T::AccountId]) {
+	fn change_members_sorted(incoming: &[T::AccountId], outgoing: &[T::AccountId], _new: &[T::AccountId]) {
 		// remove values
 		for removed in outgoing {
 			RawValues::<T, I>::remove_prefix(removed, None);
 		}
+		HasDispatched::<T, I>::mutate(|set| {
+			for removed in outgoing {
+				set.remove(removed);
+			}
+		});
 
 		// not bothering to track which key needs recompute, just update all
 		IsUpdated::<T, I>::remove_all(None);
+
+		// outgoing members no longer count for the open rounds, and the quorum
+		// is recomputed for the new members in `on_initialize`
+		if Self::rounds_enabled() {
+			RoundRecheckCursor::<T, I>::put(None::<T::OracleKey>);
+		}
+
+		Self::deposit_event(Event::MembersChanged {
+			incoming: incoming.to_vec(),
+			outgoing: outgoing.to_vec(),
+		});
 	}
 
 	fn set_prime(_prime: Option<T::AccountId>) {
@@ -0,0 +1,113 @@
+//! Migrations importing oracle data from other oracle pallets.
+//!
+//! Chains consolidating on orml-oracle could implement `OracleSource` to read
+//! the storage of their previous oracle pallet, and use
+//! `ImportFromOracleSource` as an `OnRuntimeUpgrade` to import members, raw
+//! values and combined values into orml-oracle.
+
+use crate::{Config, IsUpdated, MomentOf, RawValues, TimestampedValue, Values};
+use frame_support::{
+	traits::{ChangeMembers, Get, OnRuntimeUpgrade, SortedMembers},
+	weights::Weight,
+};
+use sp_std::{marker, prelude::*};
+
+/// A source of oracle data to import from.
+pub trait OracleSource<AccountId, Key, Value, Moment> {
+	/// The oracle members, in any order.
+	fn members() -> Vec<AccountId>;
+
+	/// Raw values fed by each member.
+	fn raw_values() -> Vec<(AccountId, Key, TimestampedValue<Value, Moment>)>;
+
+	/// Combined values.
+	fn values() -> Vec<(Key, TimestampedValue<Value, Moment>)>;
+}
+
+/// Import members, raw values and combined values from `Source`.
+///
+/// Members are handed over to `MembersTarget`, which is expected to update
+/// the storage backing `Config::Members`. Raw values fed by accounts that
+/// are not oracle members after the import are skipped.
+pub struct ImportFromOracleSource<T, Source, MembersTarget, I = ()>(marker::PhantomData<(T, Source, MembersTarget, I)>);
+
+impl<T, Source, MembersTarget, I> ImportFromOracleSource<T, Source, MembersTarget, I>
+where
+	T: Config<I>,
+	I: 'static,
+	Source: OracleSource<T::AccountId, T::OracleKey, T::OracleValue, MomentOf<T, I>>,
+	MembersTarget: ChangeMembers<T::AccountId>,
+{
+	/// Import all data from `Source`, returns the consumed weight.
+	pub fn import() -> Weight {
+		let mut reads: Weight = 0;
+		let mut writes: Weight = 0;
+
+		let mut members = Source::members();
+		members.sort();
+		members.dedup();
+		MembersTarget::set_members_sorted(&members, &T::Members::sorted_members());
+		reads += 1;
+		writes += 1;
+
+		for (who, key, value) in Source::raw_values() {
+			reads += 1;
+			if Self::is_feeder(&who) {
+				RawValues::<T, I>::insert(&who, &key, value);
+				writes += 1;
+			}
+		}
+
+		for (key, value) in Source::values() {
+			reads += 1;
+			Values::<T, I>::insert(&key, value);
+			IsUpdated::<T, I>::insert(&key, true);
+			writes += 2;
+		}
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	/// Verify all data from `Source` has been imported.
+	pub fn verify() -> Result<(), &'static str> {
+		let members = T::Members::sorted_members();
+		if Source::members().iter().any(|who| !members.contains(who)) {
+			return Err("oracle member not imported");
+		}
+
+		for (who, key, value) in Source::raw_values() {
+			if Self::is_feeder(&who) && RawValues::<T, I>::get(&who, &key) != Some(value) {
+				return Err("oracle raw value not imported");
+			}
+		}
+
+		for (key, value) in Source::values() {
+			if Values::<T, I>::get(&key) != Some(value) {
+				return Err("oracle combined value not imported");
+			}
+		}
+
+		Ok(())
+	}
+
+	fn is_feeder(who: &T::AccountId) -> bool {
+		T::Members::contains(who) || *who == T::RootOperatorAccountId::get()
+	}
+}
+
+impl<T, Source, MembersTarget, I> OnRuntimeUpgrade for ImportFromOracleSource<T, Source, MembersTarget, I>
+where
+	T: Config<I>,
+	I: 'static,
+	Source: OracleSource<T::AccountId, T::OracleKey, T::OracleValue, MomentOf<T, I>>,
+	MembersTarget: ChangeMembers<T::AccountId>,
+{
+	fn on_runtime_upgrade() -> Weight {
+		Self::import()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		Self::verify()
+	}
+}
@@ -3,9 +3,12 @@
 use super::*;
 
 use frame_support::{
-	construct_runtime, parameter_types,
-	traits::{Everything, SortedMembers},
+	construct_runtime,
+	instances::Instance1,
+	parameter_types,
+	traits::{Contains, Everything, SortedMembers},
 };
+use orml_traits::parameter_type_with_key;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -21,6 +24,7 @@ mod oracle {
 pub type AccountId = u128;
 type Key = u32;
 type Value = u32;
+pub type MarketType = u8;
 
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
@@ -71,7 +75,7 @@ impl Timestamp {
 }
 
 parameter_types! {
-	pub const MinimumCount: u32 = 3;
+	pub static MinimumCount: u32 = 3;
 	pub const ExpiresIn: u32 = 600;
 	pub const RootOperatorAccountId: AccountId = 4;
 	pub static OracleMembers: Vec<AccountId> = vec![1, 2, 3];
@@ -87,11 +91,49 @@ impl SortedMembers<AccountId> for Members {
 
 parameter_types! {
 	pub const MaxHasDispatchedSize: u32 = 100;
+	pub static RoundPeriod: u64 = 0;
+	pub static Quorum: Perbill = Perbill::from_percent(100);
+	pub const MaxRoundParticipants: u32 = 5;
+	pub const MaxRoundTimeouts: u32 = 2;
+	pub const MaxRoundRechecks: u32 = 1;
+}
+
+parameter_types! {
+	pub static EvenKeyFeeds: Vec<(AccountId, Key, Value)> = vec![];
+	pub static AllFeeds: Vec<(AccountId, Key, Value)> = vec![];
+}
+
+pub struct EvenKeys;
+impl Contains<Key> for EvenKeys {
+	fn contains(key: &Key) -> bool {
+		key % 2 == 0
+	}
+}
+
+pub struct RecordEvenKeyFeeds;
+impl OnNewData<AccountId, Key, Value> for RecordEvenKeyFeeds {
+	fn on_new_data(who: &AccountId, key: &Key, value: &Value) {
+		let mut feeds = EvenKeyFeeds::get();
+		feeds.push((*who, *key, *value));
+		EvenKeyFeeds::set(feeds);
+	}
+}
+
+pub struct RecordAllFeeds;
+impl OnNewData<AccountId, Key, Value> for RecordAllFeeds {
+	fn on_new_data(who: &AccountId, key: &Key, value: &Value) {
+		let mut feeds = AllFeeds::get();
+		feeds.push((*who, *key, *value));
+		AllFeeds::set(feeds);
+	}
 }
 
 impl Config for Test {
 	type Event = Event;
-	type OnNewData = ();
+	type OnNewData = (
+		SubscribedOnNewData<EvenKeys, RecordEvenKeyFeeds>,
+		SubscribedOnNewData<Everything, RecordAllFeeds>,
+	);
 	type CombineData = DefaultCombineData<Self, MinimumCount, ExpiresIn>;
 	type Time = Timestamp;
 	type OracleKey = Key;
@@ -100,6 +142,52 @@ impl Config for Test {
 	type Members = Members;
 	type WeightInfo = ();
 	type MaxHasDispatchedSize = MaxHasDispatchedSize;
+	type RoundPeriod = RoundPeriod;
+	type Quorum = Quorum;
+	type MaxRoundParticipants = MaxRoundParticipants;
+	type MaxRoundTimeouts = MaxRoundTimeouts;
+	type MaxRoundRechecks = MaxRoundRechecks;
+	type ValueChange = UnsignedChange;
+}
+
+pub const SPOT: MarketType = 0;
+pub const FUNDING: MarketType = 1;
+
+parameter_type_with_key! {
+	pub CombineConfigs: |market_type: MarketType| -> Option<CombineConfig<u32>> {
+		match *market_type {
+			SPOT => Some(CombineConfig {
+				strategy: CombineStrategy::Median,
+				minimum_count: 3,
+				expires_in: 600,
+			}),
+			FUNDING => Some(CombineConfig {
+				strategy: CombineStrategy::Latest,
+				minimum_count: 1,
+				expires_in: 60,
+			}),
+			_ => None,
+		}
+	};
+}
+
+impl Config<Instance1> for Test {
+	type Event = Event;
+	type OnNewData = ();
+	type CombineData = NamespacedCombineData<Self, CombineConfigs, Instance1>;
+	type Time = Timestamp;
+	type OracleKey = (MarketType, Key);
+	type OracleValue = Value;
+	type RootOperatorAccountId = RootOperatorAccountId;
+	type Members = Members;
+	type WeightInfo = ();
+	type MaxHasDispatchedSize = MaxHasDispatchedSize;
+	type RoundPeriod = RoundPeriod;
+	type Quorum = Quorum;
+	type MaxRoundParticipants = MaxRoundParticipants;
+	type MaxRoundTimeouts = MaxRoundTimeouts;
+	type MaxRoundRechecks = MaxRoundRechecks;
+	type ValueChange = UnsignedChange;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -113,6 +201,7 @@ construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
 		ModuleOracle: oracle::{Pallet, Storage, Call, Event<T>},
+		NamespacedOracle: oracle::<Instance1>::{Pallet, Storage, Call, Event<T>},
 	}
 );
 
@@ -0,0 +1,85 @@
+use crate::{default_combine_data::median, Config, MomentOf, TimestampedValueOf};
+use codec::{Decode, Encode};
+use frame_support::traits::Time;
+use orml_traits::{CombineData, GetByKey};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::{marker, prelude::*};
+
+/// An oracle key in a namespace, so one oracle could serve different kinds
+/// of feeds, e.g. spot prices and funding rates keyed by `(MarketType,
+/// AssetPair)`.
+pub trait NamespacedKey {
+	/// The namespace type.
+	type Namespace;
+
+	/// The namespace of the key.
+	fn namespace(&self) -> Self::Namespace;
+}
+
+impl<Namespace: Clone, Key> NamespacedKey for (Namespace, Key) {
+	type Namespace = Namespace;
+
+	fn namespace(&self) -> Namespace {
+		self.0.clone()
+	}
+}
+
+/// How the raw values of a namespace are combined.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum CombineStrategy {
+	/// The median value.
+	Median,
+	/// The most recently fed value.
+	Latest,
+}
+
+/// The combining config of a namespace.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct CombineConfig<Moment> {
+	/// How the raw values are combined.
+	pub strategy: CombineStrategy,
+	/// The minimum number of unexpired raw values to combine.
+	pub minimum_count: u32,
+	/// The time raw values expire in.
+	pub expires_in: Moment,
+}
+
+/// Combine the raw values of a key with the config of its namespace, given
+/// by `GetConfig`. Returns prev_value if not enough valid values, or if the
+/// namespace has no config.
+pub struct NamespacedCombineData<T, GetConfig, I = ()>(marker::PhantomData<(T, I, GetConfig)>);
+
+impl<T, I, GetConfig> CombineData<<T as Config<I>>::OracleKey, TimestampedValueOf<T, I>>
+	for NamespacedCombineData<T, GetConfig, I>
+where
+	T: Config<I>,
+	I: 'static,
+	<T as Config<I>>::OracleKey: NamespacedKey,
+	GetConfig:
+		GetByKey<<<T as Config<I>>::OracleKey as NamespacedKey>::Namespace, Option<CombineConfig<MomentOf<T, I>>>>,
+{
+	fn combine_data(
+		key: &<T as Config<I>>::OracleKey,
+		mut values: Vec<TimestampedValueOf<T, I>>,
+		prev_value: Option<TimestampedValueOf<T, I>>,
+	) -> Option<TimestampedValueOf<T, I>> {
+		let config = match GetConfig::get(&key.namespace()) {
+			Some(config) => config,
+			None => return prev_value,
+		};
+		let now = T::Time::now();
+
+		values.retain(|x| x.timestamp + config.expires_in > now);
+
+		let count = values.len() as u32;
+		if count < config.minimum_count || count == 0 {
+			return prev_value;
+		}
+
+		match config.strategy {
+			CombineStrategy::Median => Some(median::<T, I>(values)),
+			CombineStrategy::Latest => values.into_iter().max_by_key(|x| x.timestamp),
+		}
+	}
+}
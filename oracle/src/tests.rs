@@ -3,6 +3,7 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{Event, *};
+use sp_std::convert::TryInto;
 
 #[test]
 fn should_feed_values_from_member() {
@@ -52,6 +53,19 @@ fn should_feed_values_from_member() {
 	});
 }
 
+#[test]
+fn should_notify_subscribed_handlers() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ModuleOracle::feed_values(
+			Origin::signed(1),
+			vec![(50, 1000), (51, 900), (52, 800)]
+		));
+
+		assert_eq!(EvenKeyFeeds::get(), vec![(1, 50, 1000), (1, 52, 800)]);
+		assert_eq!(AllFeeds::get(), vec![(1, 50, 1000), (1, 51, 900), (1, 52, 800)]);
+	});
+}
+
 #[test]
 fn should_feed_values_from_root() {
 	new_test_ext().execute_with(|| {
@@ -281,3 +295,410 @@ fn should_clear_data_for_removed_members() {
 		assert_eq!(ModuleOracle::raw_values(&1, 50), None);
 	});
 }
+
+#[test]
+fn change_members_should_clear_outgoing_state() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ModuleOracle::feed_values(Origin::signed(1), vec![(50, 1000)]));
+		assert!(HasDispatched::<Test>::get().contains(&1));
+
+		OracleMembers::set(vec![2, 3, 4]);
+		ModuleOracle::change_members_sorted(&[4], &[1], &[2, 3, 4]);
+		System::assert_last_event(Event::ModuleOracle(crate::Event::MembersChanged {
+			incoming: vec![4],
+			outgoing: vec![1],
+		}));
+		assert!(!HasDispatched::<Test>::get().contains(&1));
+	});
+}
+
+#[test]
+fn change_members_should_recheck_round_quorum() {
+	new_test_ext().execute_with(|| {
+		RoundPeriod::set(10);
+		MinimumCount::set(2);
+		System::set_block_number(11);
+
+		assert_ok!(ModuleOracle::feed_values(Origin::signed(1), vec![(50, 1000)]));
+		assert_ok!(ModuleOracle::feed_values(Origin::signed(2), vec![(50, 1100)]));
+		assert_ok!(ModuleOracle::feed_values(Origin::signed(3), vec![(51, 10)]));
+
+		// 3 leaves, the quorum of 2 members is reached by 1 and 2 once the
+		// rounds are checked again in `on_initialize`, one per block
+		OracleMembers::set(vec![1, 2]);
+		ModuleOracle::change_members_sorted(&[], &[3], &[1, 2]);
+		assert_eq!(ModuleOracle::round_recheck_cursor(), Some(None));
+		assert_eq!(ModuleOracle::get(&50), None);
+
+		ModuleOracle::on_initialize(12);
+		ModuleOracle::on_initialize(13);
+		assert!(ModuleOracle::round_recheck_cursor().is_some());
+		ModuleOracle::on_initialize(14);
+		assert_eq!(ModuleOracle::round_recheck_cursor(), None);
+		System::assert_has_event(Event::ModuleOracle(crate::Event::RoundFinalized {
+			key: 50,
+			value: 1100,
+			participants: vec![1, 2],
+		}));
+		assert_eq!(ModuleOracle::get(&50).map(|x| x.value), Some(1100));
+		assert_eq!(
+			ModuleOracle::rounds(&51),
+			Some(Round {
+				start: 10,
+				participants: Default::default(),
+				finalized: false,
+			})
+		);
+	});
+}
+
+struct MockOracleSource;
+impl migration::OracleSource<AccountId, u32, u32, u32> for MockOracleSource {
+	fn members() -> Vec<AccountId> {
+		vec![3, 1, 5]
+	}
+
+	fn raw_values() -> Vec<(AccountId, u32, TimestampedValue<u32, u32>)> {
+		vec![
+			(
+				1,
+				50,
+				TimestampedValue {
+					value: 1000,
+					timestamp: 12340,
+				},
+			),
+			(
+				2,
+				50,
+				TimestampedValue {
+					value: 1100,
+					timestamp: 12340,
+				},
+			),
+			(
+				5,
+				51,
+				TimestampedValue {
+					value: 900,
+					timestamp: 12341,
+				},
+			),
+		]
+	}
+
+	fn values() -> Vec<(u32, TimestampedValue<u32, u32>)> {
+		vec![(
+			50,
+			TimestampedValue {
+				value: 1050,
+				timestamp: 12340,
+			},
+		)]
+	}
+}
+
+struct MockMembersTarget;
+impl ChangeMembers<AccountId> for MockMembersTarget {
+	fn change_members_sorted(_incoming: &[AccountId], _outgoing: &[AccountId], new: &[AccountId]) {
+		OracleMembers::set(new.to_vec());
+	}
+
+	fn set_prime(_prime: Option<AccountId>) {}
+}
+
+type ImportFromMockOracleSource = migration::ImportFromOracleSource<Test, MockOracleSource, MockMembersTarget>;
+
+#[test]
+fn should_import_from_oracle_source() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ImportFromMockOracleSource::verify(), Err("oracle member not imported"));
+
+		ImportFromMockOracleSource::import();
+
+		assert_eq!(OracleMembers::get(), vec![1, 3, 5]);
+		assert_eq!(
+			ModuleOracle::raw_values(&1, 50),
+			Some(TimestampedValue {
+				value: 1000,
+				timestamp: 12340,
+			})
+		);
+		// not a member anymore
+		assert_eq!(ModuleOracle::raw_values(&2, 50), None);
+		assert_eq!(
+			ModuleOracle::raw_values(&5, 51),
+			Some(TimestampedValue {
+				value: 900,
+				timestamp: 12341,
+			})
+		);
+		assert!(ModuleOracle::is_updated(50));
+		assert_eq!(
+			ModuleOracle::get(&50),
+			Some(TimestampedValue {
+				value: 1050,
+				timestamp: 12340,
+			})
+		);
+		assert_ok!(ImportFromMockOracleSource::verify());
+	});
+}
+
+#[test]
+fn round_should_finalize_on_quorum() {
+	new_test_ext().execute_with(|| {
+		RoundPeriod::set(10);
+		System::set_block_number(11);
+
+		assert_ok!(ModuleOracle::feed_values(Origin::signed(1), vec![(50, 1000)]));
+		assert_ok!(ModuleOracle::feed_values(Origin::signed(2), vec![(50, 1100)]));
+		assert_eq!(ModuleOracle::get(&50), None);
+		assert_eq!(
+			ModuleOracle::rounds(&50),
+			Some(Round {
+				start: 10,
+				participants: vec![1, 2].try_into().unwrap(),
+				finalized: false,
+			})
+		);
+
+		System::set_block_number(12);
+		assert_ok!(ModuleOracle::feed_values(Origin::signed(3), vec![(50, 1200)]));
+		System::assert_has_event(Event::ModuleOracle(crate::Event::RoundFinalized {
+			key: 50,
+			value: 1100,
+			participants: vec![1, 2, 3],
+		}));
+		let expected = Some(TimestampedValue {
+			value: 1100,
+			timestamp: 12345,
+		});
+		assert_eq!(ModuleOracle::get(&50), expected);
+
+		// feeds after finalization don't change the value of the round
+		System::set_block_number(13);
+		assert_ok!(ModuleOracle::feed_values(Origin::signed(1), vec![(50, 1300)]));
+		assert_eq!(ModuleOracle::get(&50), expected);
+		assert_eq!(ModuleOracle::get_no_op(&50), expected);
+	});
+}
+
+#[test]
+fn round_should_finalize_on_timeout() {
+	new_test_ext().execute_with(|| {
+		RoundPeriod::set(10);
+		MinimumCount::set(1);
+		System::set_block_number(11);
+
+		assert_ok!(ModuleOracle::feed_values(Origin::signed(1), vec![(50, 1000), (51, 10)]));
+		assert_ok!(ModuleOracle::feed_values(Origin::signed(2), vec![(50, 1100)]));
+		assert_eq!(ModuleOracle::get(&50), None);
+
+		ModuleOracle::on_initialize(20);
+		System::assert_has_event(Event::ModuleOracle(crate::Event::RoundFinalized {
+			key: 50,
+			value: 1100,
+			participants: vec![1, 2],
+		}));
+		System::assert_has_event(Event::ModuleOracle(crate::Event::RoundFinalized {
+			key: 51,
+			value: 10,
+			participants: vec![1],
+		}));
+		assert_eq!(ModuleOracle::rounds(&50), None);
+		assert_eq!(ModuleOracle::get(&51).map(|x| x.value), Some(10));
+
+		// a timed out round is also finalized by feeds of the next round
+		System::set_block_number(25);
+		assert_ok!(ModuleOracle::feed_values(Origin::signed(1), vec![(51, 20)]));
+		System::set_block_number(36);
+		assert_ok!(ModuleOracle::feed_values(Origin::signed(2), vec![(51, 30)]));
+		System::assert_has_event(Event::ModuleOracle(crate::Event::RoundFinalized {
+			key: 51,
+			value: 20,
+			participants: vec![1],
+		}));
+		assert_eq!(ModuleOracle::get(&51).map(|x| x.value), Some(20));
+		assert_eq!(ModuleOracle::rounds(&51).map(|x| x.start), Some(30));
+	});
+}
+
+#[test]
+fn rounds_should_time_out_in_bounded_steps() {
+	new_test_ext().execute_with(|| {
+		RoundPeriod::set(10);
+		MinimumCount::set(1);
+		System::set_block_number(11);
+
+		assert_ok!(ModuleOracle::feed_values(
+			Origin::signed(1),
+			vec![(50, 1000), (51, 10), (52, 20)]
+		));
+		assert_eq!(ModuleOracle::round_timeout_cursor(), Some(10));
+
+		// nothing to time out in the current round period
+		ModuleOracle::on_initialize(15);
+		assert_eq!(OpenRounds::<Test>::iter_prefix(10).count(), 3);
+
+		// at most `MaxRoundTimeouts` per block
+		ModuleOracle::on_initialize(20);
+		assert_eq!(OpenRounds::<Test>::iter_prefix(10).count(), 1);
+		assert_eq!(Rounds::<Test>::iter().count(), 1);
+		assert_eq!(ModuleOracle::round_timeout_cursor(), Some(10));
+
+		ModuleOracle::on_initialize(21);
+		assert_eq!(Rounds::<Test>::iter().count(), 0);
+		assert_eq!(ModuleOracle::round_timeout_cursor(), Some(20));
+		for key in 50..53 {
+			assert!(ModuleOracle::get(&key).is_some());
+		}
+
+		// empty round periods are stepped over
+		ModuleOracle::on_initialize(55);
+		assert_eq!(ModuleOracle::round_timeout_cursor(), Some(40));
+		ModuleOracle::on_initialize(56);
+		assert_eq!(ModuleOracle::round_timeout_cursor(), Some(50));
+	});
+}
+
+#[test]
+fn should_reject_values_out_of_bounds() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		MinimumCount::set(1);
+
+		let bounds = Bounds {
+			min: Some(500),
+			max: Some(2000),
+			max_change: Some(Perbill::from_percent(10)),
+		};
+		assert_noop!(
+			ModuleOracle::set_value_bounds(Origin::signed(1), 50, Some(bounds.clone())),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_ok!(ModuleOracle::set_value_bounds(Origin::root(), 50, Some(bounds.clone())));
+		System::assert_last_event(Event::ModuleOracle(crate::Event::ValueBoundsSet(50, Some(bounds))));
+
+		assert_ok!(ModuleOracle::feed_values(Origin::signed(1), vec![(50, 400), (51, 5)]));
+		System::assert_has_event(Event::ModuleOracle(crate::Event::FeedRejected(1, 50, 400)));
+		System::assert_last_event(Event::ModuleOracle(crate::Event::NewFeedData(1, vec![(51, 5)])));
+		assert_eq!(ModuleOracle::raw_values(&1, &50), None);
+		assert_eq!(ModuleOracle::get(&50), None);
+
+		assert_ok!(ModuleOracle::feed_values(Origin::signed(2), vec![(50, 2100)]));
+		System::assert_has_event(Event::ModuleOracle(crate::Event::FeedRejected(2, 50, 2100)));
+		assert_ok!(ModuleOracle::feed_values(Origin::signed(3), vec![(50, 1000)]));
+		assert_eq!(ModuleOracle::get(&50).map(|x| x.value), Some(1000));
+
+		// the change from the combined value is limited
+		ModuleOracle::on_finalize(1);
+		assert_ok!(ModuleOracle::feed_values(Origin::signed(1), vec![(50, 1200)]));
+		System::assert_has_event(Event::ModuleOracle(crate::Event::FeedRejected(1, 50, 1200)));
+		assert_ok!(ModuleOracle::feed_values(Origin::signed(2), vec![(50, 900)]));
+		assert_eq!(ModuleOracle::raw_values(&2, &50).map(|x| x.value), Some(900));
+
+		assert_ok!(ModuleOracle::set_value_bounds(Origin::root(), 50, None));
+		assert_eq!(ModuleOracle::value_bounds(&50), None);
+		assert_ok!(ModuleOracle::feed_values(Origin::signed(3), vec![(50, 400)]));
+		assert_eq!(ModuleOracle::raw_values(&3, &50).map(|x| x.value), Some(400));
+	});
+}
+
+#[test]
+fn bounds_should_follow_gradual_moves() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ModuleOracle::set_value_bounds(
+			Origin::root(),
+			50,
+			Some(Bounds {
+				min: None,
+				max: None,
+				max_change: Some(Perbill::from_percent(10)),
+			})
+		));
+
+		for who in 1..=3 {
+			assert_ok!(ModuleOracle::feed_values(Origin::signed(who), vec![(50, 1000)]));
+		}
+		assert_eq!(ModuleOracle::get(&50).map(|x| x.value), Some(1000));
+
+		// each step is within the limit of the previous one, but not of the
+		// last value read
+		for (block, price) in [(2, 1080), (3, 1160), (4, 1250), (5, 1350)] {
+			ModuleOracle::on_finalize(block - 1);
+			System::set_block_number(block);
+			for who in 1..=3 {
+				assert_ok!(ModuleOracle::feed_values(Origin::signed(who), vec![(50, price)]));
+				assert_eq!(ModuleOracle::raw_values(&who, &50).map(|x| x.value), Some(price));
+			}
+			assert_eq!(ModuleOracle::get_no_op(&50).map(|x| x.value), Some(price));
+		}
+
+		// a jump is still rejected
+		ModuleOracle::on_finalize(5);
+		assert_ok!(ModuleOracle::feed_values(Origin::signed(1), vec![(50, 2000)]));
+		System::assert_has_event(Event::ModuleOracle(crate::Event::FeedRejected(1, 50, 2000)));
+		assert_eq!(ModuleOracle::get(&50).map(|x| x.value), Some(1350));
+	});
+}
+
+#[test]
+fn namespaced_keys_should_combine_by_namespace() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(NamespacedOracle::feed_values(
+			Origin::signed(1),
+			vec![((SPOT, 50), 1000), ((FUNDING, 50), 10), ((2, 50), 5)]
+		));
+
+		// not enough spot values, and no config for the namespace 2
+		assert_eq!(NamespacedOracle::get_no_op(&(SPOT, 50)), None);
+		assert_eq!(
+			NamespacedOracle::get_no_op(&(FUNDING, 50)),
+			Some(TimestampedValue {
+				value: 10,
+				timestamp: 12345,
+			})
+		);
+		assert_eq!(NamespacedOracle::get_no_op(&(2, 50)), None);
+
+		Timestamp::set_timestamp(12350);
+		assert_ok!(NamespacedOracle::feed_values(
+			Origin::signed(2),
+			vec![((SPOT, 50), 1200), ((FUNDING, 50), 30)]
+		));
+		Timestamp::set_timestamp(12360);
+		assert_ok!(NamespacedOracle::feed_values(
+			Origin::signed(3),
+			vec![((SPOT, 50), 1100), ((FUNDING, 50), 20)]
+		));
+
+		// the median of spot values, the latest of funding values
+		assert_eq!(
+			NamespacedOracle::get_no_op(&(SPOT, 50)),
+			Some(TimestampedValue {
+				value: 1100,
+				timestamp: 12360,
+			})
+		);
+		assert_eq!(
+			NamespacedOracle::get_no_op(&(FUNDING, 50)),
+			Some(TimestampedValue {
+				value: 20,
+				timestamp: 12360,
+			})
+		);
+
+		// funding values expire earlier than spot values
+		Timestamp::set_timestamp(12345 + 100);
+		assert_eq!(
+			NamespacedOracle::get_no_op(&(SPOT, 50)),
+			Some(TimestampedValue {
+				value: 1100,
+				timestamp: 12360,
+			})
+		);
+		assert_eq!(NamespacedOracle::get_no_op(&(FUNDING, 50)), None);
+	});
+}
@@ -32,6 +32,7 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn feed_values(c: u32, ) -> Weight;
 	fn on_finalize() -> Weight;
+	fn set_value_bounds() -> Weight;
 }
 
 /// Default weights.
@@ -48,4 +49,8 @@ impl WeightInfo for () {
 		(3_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	fn set_value_bounds() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
@@ -0,0 +1,43 @@
+//! Benchmarks for the parameters module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use sp_runtime::traits::One;
+
+benchmarks! {
+	// the parameter is already set, so the old value is read and replaced
+	set_parameter {
+		let (origin, key_value) = T::BenchmarkHelper::parameter();
+		let (key, value) = key_value.clone().into_parts();
+		if let Some(value) = value.clone() {
+			Parameters::<T>::insert(&key, value);
+		}
+	}: _<T::Origin>(origin, key_value)
+	verify {
+		assert_eq!(Parameters::<T>::get(&key), value);
+	}
+
+	set_parameter_gradually {
+		let (origin, target, per_block) = T::BenchmarkHelper::gradual_parameter();
+		let (key, _) = target.clone().into_parts();
+	}: _<T::Origin>(origin, target, per_block)
+	verify {
+		assert!(GradualUpdates::<T>::contains_key(&key));
+	}
+
+	// a step not reaching the target, so the update is kept
+	apply_gradual_update {
+		let (origin, target, per_block) = T::BenchmarkHelper::gradual_parameter();
+		let (key, _) = target.clone().into_parts();
+		Pallet::<T>::set_parameter_gradually(origin, target, per_block)?;
+	}: {
+		Pallet::<T>::on_initialize(One::one());
+	}
+	verify {
+		assert!(Parameters::<T>::contains_key(&key));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::default().build(), crate::mock::Runtime);
@@ -0,0 +1,255 @@
+//! # Parameters
+//!
+//! ## Overview
+//!
+//! This module stores typed runtime parameters.
+//!
+//! Parameters are declared with `orml_traits::define_parameters!`, which
+//! generates an aggregated key/value enum used as `AggregatedKeyValue`. Each
+//! parameter can be set or removed by the origin `AdminOrigin` allows for its
+//! key. Other pallets read the parameters through the `GetByKey`
+//! implementation of this module, keyed by the parameter key structs.
+//!
+//! A parameter can also be applied gradually by `set_parameter_gradually`:
+//! its value steps towards the target by a per block amount in
+//! `on_initialize`, until it reaches the target or the parameter is set
+//! again. Like orml-gradually-update, only unsigned integer values up to 128
+//! bits are supported, or "newtype" wrappers of them like `Permill`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use orml_traits::{
+	parameters::{AggregatedKeyValue, EnsureOriginWithArg, Key},
+	GetByKey,
+};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+mod benchmarking;
+mod mock;
+mod tests;
+mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// Provide a parameter to set in benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<Origin, AggregatedKeyValue> {
+	/// A parameter, and an origin allowed to set it.
+	fn parameter() -> (Origin, AggregatedKeyValue);
+
+	/// A numeric parameter target, its step per block, and an origin allowed
+	/// to set it.
+	fn gradual_parameter() -> (Origin, AggregatedKeyValue, AggregatedKeyValue);
+}
+
+/// A parameter gradually updated to `target`, changed by `per_block` every
+/// block.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct GradualUpdate<Value> {
+	pub target: Value,
+	pub per_block: Value,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	pub(crate) type KeyOf<T> = <<T as Config>::AggregatedKeyValue as AggregatedKeyValue>::AggregatedKey;
+	pub(crate) type ValueOf<T> = <<T as Config>::AggregatedKeyValue as AggregatedKeyValue>::AggregatedValue;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The aggregated key/value type of all parameters.
+		type AggregatedKeyValue: AggregatedKeyValue;
+
+		/// The origin which may set a given parameter.
+		type AdminOrigin: EnsureOriginWithArg<Self::Origin, KeyOf<Self>>;
+
+		/// Helper to provide a parameter in benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::Origin, Self::AggregatedKeyValue>;
+
+		/// Weight information for extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The target and the step of a gradual update are not set values of
+		/// the same numeric parameter, or the step is zero.
+		InvalidGradualUpdate,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A parameter is updated. [key, old_value, new_value]
+		Updated(KeyOf<T>, Option<ValueOf<T>>, Option<ValueOf<T>>),
+		/// A parameter is scheduled to be updated gradually. [key, target,
+		/// per_block]
+		GradualUpdateScheduled(KeyOf<T>, ValueOf<T>, ValueOf<T>),
+	}
+
+	/// The stored parameters.
+	///
+	/// map AggregatedKey => Option<AggregatedValue>
+	#[pallet::storage]
+	#[pallet::getter(fn parameters)]
+	pub type Parameters<T: Config> = StorageMap<_, Blake2_128Concat, KeyOf<T>, ValueOf<T>, OptionQuery>;
+
+	/// The gradual updates in progress, at most one per parameter.
+	///
+	/// map AggregatedKey => Option<GradualUpdate>
+	#[pallet::storage]
+	#[pallet::getter(fn gradual_updates)]
+	pub type GradualUpdates<T: Config> =
+		StorageMap<_, Blake2_128Concat, KeyOf<T>, GradualUpdate<ValueOf<T>>, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Step the gradually updated parameters towards their targets.
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			let mut count: Weight = 0;
+			GradualUpdates::<T>::translate(|key, update: GradualUpdate<ValueOf<T>>| {
+				count += 1;
+				Self::apply_gradual_update(key, update)
+			});
+			T::DbWeight::get()
+				.reads(1)
+				.saturating_add(T::WeightInfo::apply_gradual_update().saturating_mul(count))
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set a parameter, or remove it if the value is `None`. A gradual
+		/// update of the parameter in progress is cancelled.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin` for the
+		/// parameter key.
+		#[pallet::weight(T::WeightInfo::set_parameter())]
+		pub fn set_parameter(origin: OriginFor<T>, key_value: T::AggregatedKeyValue) -> DispatchResult {
+			let (key, value) = key_value.into_parts();
+			T::AdminOrigin::ensure_origin(origin, &key)?;
+
+			GradualUpdates::<T>::remove(&key);
+			let old_value = Parameters::<T>::mutate(&key, |v| sp_std::mem::replace(v, value.clone()));
+
+			Self::deposit_event(Event::Updated(key, old_value, value));
+			Ok(())
+		}
+
+		/// Update a numeric parameter to the value of `target` gradually,
+		/// changing it by the value of `per_block` every block, starting
+		/// from zero if it's not set. Replaces the gradual update of the
+		/// parameter in progress, if any.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin` for the
+		/// parameter key.
+		#[pallet::weight(T::WeightInfo::set_parameter_gradually())]
+		pub fn set_parameter_gradually(
+			origin: OriginFor<T>,
+			target: T::AggregatedKeyValue,
+			per_block: T::AggregatedKeyValue,
+		) -> DispatchResult {
+			let (key, target) = target.into_parts();
+			let (per_block_key, per_block) = per_block.into_parts();
+			T::AdminOrigin::ensure_origin(origin, &key)?;
+
+			let (target, per_block) = match (target, per_block) {
+				(Some(target), Some(per_block)) if per_block_key == key => (target, per_block),
+				_ => return Err(Error::<T>::InvalidGradualUpdate.into()),
+			};
+			let target_bytes = target.encode();
+			let per_block_bytes = per_block.encode();
+			ensure!(
+				target_bytes.len() == per_block_bytes.len()
+					&& target_bytes.len() > 1
+					&& target_bytes.len() <= 17
+					&& per_block_bytes[1..].iter().any(|b| *b != 0),
+				Error::<T>::InvalidGradualUpdate
+			);
+			if let Some(current) = Self::parameters(&key) {
+				ensure!(
+					current.encode().len() == target_bytes.len(),
+					Error::<T>::InvalidGradualUpdate
+				);
+			}
+
+			GradualUpdates::<T>::insert(
+				&key,
+				GradualUpdate {
+					target: target.clone(),
+					per_block: per_block.clone(),
+				},
+			);
+			Self::deposit_event(Event::GradualUpdateScheduled(key, target, per_block));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Step the parameter `key` by its gradual `update`, returns the update
+	/// if the target isn't reached yet.
+	///
+	/// The values are encoded as the index of the parameter followed by the
+	/// little endian bytes of the integer, as checked when scheduled.
+	fn apply_gradual_update(key: KeyOf<T>, update: GradualUpdate<ValueOf<T>>) -> Option<GradualUpdate<ValueOf<T>>> {
+		let target_bytes = update.target.encode();
+		let old_value = Self::parameters(&key);
+		let current_bytes = old_value.as_ref().map(Encode::encode).unwrap_or_else(|| {
+			let mut bytes = sp_std::vec![0u8; target_bytes.len()];
+			bytes[0] = target_bytes[0];
+			bytes
+		});
+
+		let current = Self::payload_to_u128(&current_bytes);
+		let target = Self::payload_to_u128(&target_bytes);
+		let step = Self::payload_to_u128(&update.per_block.encode());
+		let new = if current > target {
+			current.saturating_sub(step).max(target)
+		} else {
+			current.saturating_add(step).min(target)
+		};
+
+		let mut new_bytes = current_bytes[..1].to_vec();
+		new_bytes.extend_from_slice(&new.to_le_bytes()[..target_bytes.len() - 1]);
+		let new_value = ValueOf::<T>::decode(&mut &new_bytes[..]).ok()?;
+		Parameters::<T>::insert(&key, new_value.clone());
+		Self::deposit_event(Event::Updated(key, old_value, Some(new_value)));
+
+		if new == target {
+			None
+		} else {
+			Some(update)
+		}
+	}
+
+	/// The integer encoded after the index of the parameter in `bytes`.
+	fn payload_to_u128(bytes: &[u8]) -> u128 {
+		let mut payload = [0u8; 16];
+		let len = bytes.len().saturating_sub(1).min(16);
+		payload[..len].copy_from_slice(&bytes[1..=len]);
+		u128::from_le_bytes(payload)
+	}
+}
+
+impl<T: Config, K> GetByKey<K, Option<K::Value>> for Pallet<T>
+where
+	K: Key<ValueOf<T>> + Clone + Into<KeyOf<T>>,
+{
+	fn get(key: &K) -> Option<K::Value> {
+		Parameters::<T>::get(key.clone().into()).and_then(K::try_from_aggregated)
+	}
+}
@@ -0,0 +1,133 @@
+//! Mocks for the parameters module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{construct_runtime, parameter_types, traits::Everything};
+use frame_system::{ensure_root, ensure_signed};
+use orml_traits::define_parameters;
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup, Permill};
+
+use crate as parameters;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+pub type AccountId = u128;
+pub type Balance = u64;
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = Everything;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+define_parameters! {
+	pub RuntimeParameters, RuntimeParametersKey, RuntimeParametersValue = {
+		MinBond: Balance = 0,
+		RewardRate: Permill = 1,
+	}
+}
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+
+/// Root can set all parameters, `ALICE` can set `MinBond`.
+pub struct EnsureParametersAdmin;
+
+impl EnsureOriginWithArg<Origin, RuntimeParametersKey> for EnsureParametersAdmin {
+	type Success = ();
+
+	fn try_origin(o: Origin, key: &RuntimeParametersKey) -> Result<(), Origin> {
+		if ensure_root(o.clone()).is_ok() {
+			return Ok(());
+		}
+		match key {
+			RuntimeParametersKey::MinBond(_) if matches!(ensure_signed(o.clone()), Ok(ALICE)) => Ok(()),
+			_ => Err(o),
+		}
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct ParameterProvider;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<Origin, RuntimeParameters> for ParameterProvider {
+	fn parameter() -> (Origin, RuntimeParameters) {
+		(Origin::root(), RuntimeParameters::MinBond(MinBond, Some(100)))
+	}
+
+	fn gradual_parameter() -> (Origin, RuntimeParameters, RuntimeParameters) {
+		(
+			Origin::root(),
+			RuntimeParameters::MinBond(MinBond, Some(100)),
+			RuntimeParameters::MinBond(MinBond, Some(10)),
+		)
+	}
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type AggregatedKeyValue = RuntimeParameters;
+	type AdminOrigin = EnsureParametersAdmin;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ParameterProvider;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		ParametersModule: parameters::{Pallet, Storage, Call, Event<T>},
+	}
+);
+
+pub struct ExtBuilder;
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		ExtBuilder
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
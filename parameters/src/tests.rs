@@ -0,0 +1,216 @@
+//! Unit tests for the parameters module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::{traits::BadOrigin, Permill};
+
+#[test]
+fn set_parameter_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(<ParametersModule as GetByKey<_, _>>::get(&MinBond), None);
+
+		assert_ok!(ParametersModule::set_parameter(
+			Origin::root(),
+			RuntimeParameters::MinBond(MinBond, Some(100))
+		));
+		System::assert_last_event(Event::ParametersModule(crate::Event::Updated(
+			RuntimeParametersKey::MinBond(MinBond),
+			None,
+			Some(RuntimeParametersValue::MinBond(100)),
+		)));
+		assert_eq!(<ParametersModule as GetByKey<_, _>>::get(&MinBond), Some(100));
+
+		assert_ok!(ParametersModule::set_parameter(
+			Origin::root(),
+			RuntimeParameters::RewardRate(RewardRate, Some(Permill::from_percent(5)))
+		));
+		assert_eq!(
+			<ParametersModule as GetByKey<_, _>>::get(&RewardRate),
+			Some(Permill::from_percent(5))
+		);
+		assert_eq!(<ParametersModule as GetByKey<_, _>>::get(&MinBond), Some(100));
+	});
+}
+
+#[test]
+fn remove_parameter_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ParametersModule::set_parameter(
+			Origin::root(),
+			RuntimeParameters::MinBond(MinBond, Some(100))
+		));
+		assert_ok!(ParametersModule::set_parameter(
+			Origin::root(),
+			RuntimeParameters::MinBond(MinBond, None)
+		));
+		System::assert_last_event(Event::ParametersModule(crate::Event::Updated(
+			RuntimeParametersKey::MinBond(MinBond),
+			Some(RuntimeParametersValue::MinBond(100)),
+			None,
+		)));
+		assert_eq!(<ParametersModule as GetByKey<_, _>>::get(&MinBond), None);
+		assert_eq!(
+			ParametersModule::parameters(RuntimeParametersKey::MinBond(MinBond)),
+			None
+		);
+	});
+}
+
+#[test]
+fn set_parameter_requires_key_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ParametersModule::set_parameter(
+			Origin::signed(ALICE),
+			RuntimeParameters::MinBond(MinBond, Some(100))
+		));
+		assert_noop!(
+			ParametersModule::set_parameter(
+				Origin::signed(ALICE),
+				RuntimeParameters::RewardRate(RewardRate, Some(Permill::from_percent(5)))
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			ParametersModule::set_parameter(Origin::signed(BOB), RuntimeParameters::MinBond(MinBond, Some(1))),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn set_parameter_gradually_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ParametersModule::set_parameter(
+			Origin::root(),
+			RuntimeParameters::MinBond(MinBond, Some(100))
+		));
+		assert_ok!(ParametersModule::set_parameter_gradually(
+			Origin::signed(ALICE),
+			RuntimeParameters::MinBond(MinBond, Some(125)),
+			RuntimeParameters::MinBond(MinBond, Some(10))
+		));
+		System::assert_last_event(Event::ParametersModule(crate::Event::GradualUpdateScheduled(
+			RuntimeParametersKey::MinBond(MinBond),
+			RuntimeParametersValue::MinBond(125),
+			RuntimeParametersValue::MinBond(10),
+		)));
+		assert_eq!(<ParametersModule as GetByKey<_, _>>::get(&MinBond), Some(100));
+
+		ParametersModule::on_initialize(2);
+		System::assert_last_event(Event::ParametersModule(crate::Event::Updated(
+			RuntimeParametersKey::MinBond(MinBond),
+			Some(RuntimeParametersValue::MinBond(100)),
+			Some(RuntimeParametersValue::MinBond(110)),
+		)));
+		ParametersModule::on_initialize(3);
+		assert_eq!(<ParametersModule as GetByKey<_, _>>::get(&MinBond), Some(120));
+
+		// the target is not overshot
+		ParametersModule::on_initialize(4);
+		assert_eq!(<ParametersModule as GetByKey<_, _>>::get(&MinBond), Some(125));
+		assert_eq!(
+			ParametersModule::gradual_updates(RuntimeParametersKey::MinBond(MinBond)),
+			None
+		);
+	});
+}
+
+#[test]
+fn set_parameter_gradually_should_work_downwards_from_unset() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ParametersModule::set_parameter_gradually(
+			Origin::root(),
+			RuntimeParameters::RewardRate(RewardRate, Some(Permill::from_percent(2))),
+			RuntimeParameters::RewardRate(RewardRate, Some(Permill::from_percent(1)))
+		));
+		ParametersModule::on_initialize(2);
+		assert_eq!(
+			<ParametersModule as GetByKey<_, _>>::get(&RewardRate),
+			Some(Permill::from_percent(1))
+		);
+
+		// downwards, replacing the update in progress
+		assert_ok!(ParametersModule::set_parameter_gradually(
+			Origin::root(),
+			RuntimeParameters::RewardRate(RewardRate, Some(Permill::zero())),
+			RuntimeParameters::RewardRate(RewardRate, Some(Permill::from_perthousand(5)))
+		));
+		ParametersModule::on_initialize(3);
+		assert_eq!(
+			<ParametersModule as GetByKey<_, _>>::get(&RewardRate),
+			Some(Permill::from_perthousand(5))
+		);
+		ParametersModule::on_initialize(4);
+		assert_eq!(
+			<ParametersModule as GetByKey<_, _>>::get(&RewardRate),
+			Some(Permill::zero())
+		);
+		assert_eq!(
+			ParametersModule::gradual_updates(RuntimeParametersKey::RewardRate(RewardRate)),
+			None
+		);
+	});
+}
+
+#[test]
+fn set_parameter_should_cancel_gradual_update() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ParametersModule::set_parameter_gradually(
+			Origin::root(),
+			RuntimeParameters::MinBond(MinBond, Some(100)),
+			RuntimeParameters::MinBond(MinBond, Some(10))
+		));
+		assert_ok!(ParametersModule::set_parameter(
+			Origin::root(),
+			RuntimeParameters::MinBond(MinBond, Some(50))
+		));
+		assert_eq!(
+			ParametersModule::gradual_updates(RuntimeParametersKey::MinBond(MinBond)),
+			None
+		);
+
+		ParametersModule::on_initialize(2);
+		assert_eq!(<ParametersModule as GetByKey<_, _>>::get(&MinBond), Some(50));
+	});
+}
+
+#[test]
+fn set_parameter_gradually_should_check_update() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			ParametersModule::set_parameter_gradually(
+				Origin::signed(ALICE),
+				RuntimeParameters::RewardRate(RewardRate, Some(Permill::from_percent(5))),
+				RuntimeParameters::RewardRate(RewardRate, Some(Permill::from_percent(1)))
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			ParametersModule::set_parameter_gradually(
+				Origin::root(),
+				RuntimeParameters::MinBond(MinBond, Some(100)),
+				RuntimeParameters::RewardRate(RewardRate, Some(Permill::from_percent(1)))
+			),
+			Error::<Runtime>::InvalidGradualUpdate
+		);
+		assert_noop!(
+			ParametersModule::set_parameter_gradually(
+				Origin::root(),
+				RuntimeParameters::MinBond(MinBond, None),
+				RuntimeParameters::MinBond(MinBond, Some(10))
+			),
+			Error::<Runtime>::InvalidGradualUpdate
+		);
+		assert_noop!(
+			ParametersModule::set_parameter_gradually(
+				Origin::root(),
+				RuntimeParameters::MinBond(MinBond, Some(100)),
+				RuntimeParameters::MinBond(MinBond, Some(0))
+			),
+			Error::<Runtime>::InvalidGradualUpdate
+		);
+	});
+}
@@ -0,0 +1,22 @@
+//! Runtime API definition for payments module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_std::prelude::Vec;
+
+pub use orml_payments::{PaymentId, PaymentRole};
+
+sp_api::decl_runtime_apis! {
+	pub trait PaymentsApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// The open payments of `who`, as payer or payee, skipping the
+		/// first `start` of them and returning at most `limit`.
+		fn open_payments(who: AccountId, start: u32, limit: u32) -> Vec<(PaymentId, PaymentRole)>;
+	}
+}
@@ -0,0 +1,109 @@
+//! Benchmarks for the payments module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+const CURRENCY: u32 = 1;
+const AMOUNT: u32 = 1_000;
+
+/// Create `n` payments from the whitelisted caller, returns the payer, the
+/// payee and the first payment ID.
+fn create_payments<T: Config>(n: u32) -> Result<(T::AccountId, T::AccountId, PaymentId), &'static str>
+where
+	CurrencyIdOf<T>: From<u32>,
+{
+	let payer: T::AccountId = whitelisted_caller();
+	let payee: T::AccountId = account("payee", 0, SEED);
+	let currency_id: CurrencyIdOf<T> = CURRENCY.into();
+	T::MultiCurrency::deposit(
+		currency_id,
+		&payer,
+		BalanceOf::<T>::from(AMOUNT).saturating_mul(n.into()),
+	)?;
+
+	let payment_id = Pallet::<T>::next_payment_id();
+	for _ in 0..n {
+		Pallet::<T>::create(
+			RawOrigin::Signed(payer.clone()).into(),
+			T::Lookup::unlookup(payee.clone()),
+			currency_id,
+			AMOUNT.into(),
+		)?;
+	}
+	Ok((payer, payee, payment_id))
+}
+
+/// Create payments overflowing the index of the payer and the payee, and
+/// accept the first one. Returns the payer, the payee and the first payment
+/// ID.
+fn accepted_payment<T: Config>() -> Result<(T::AccountId, T::AccountId, PaymentId), &'static str>
+where
+	CurrencyIdOf<T>: From<u32>,
+{
+	let (payer, payee, payment_id) = create_payments::<T>(T::MaxIndexedPayments::get().saturating_add(1))?;
+	Pallet::<T>::accept(RawOrigin::Signed(payee.clone()).into(), payment_id)?;
+	Ok((payer, payee, payment_id))
+}
+
+benchmarks! {
+	where_clause { where CurrencyIdOf<T>: From<u32> }
+
+	// the payment overflows the index of the payer and the payee
+	create {
+		let (payer, payee, _) = create_payments::<T>(T::MaxIndexedPayments::get())?;
+		let currency_id: CurrencyIdOf<T> = CURRENCY.into();
+		T::MultiCurrency::deposit(currency_id, &payer, AMOUNT.into())?;
+		let payment_id = Pallet::<T>::next_payment_id();
+	}: _(RawOrigin::Signed(payer), T::Lookup::unlookup(payee.clone()), currency_id, AMOUNT.into())
+	verify {
+		assert!(Payments::<T>::contains_key(payment_id));
+		assert!(OpenPaymentsOverflow::<T>::contains_key(&payee, payment_id));
+	}
+
+	// an overflowed payment moves into the freed slot of the index
+	cancel {
+		let (payer, _, payment_id) = create_payments::<T>(T::MaxIndexedPayments::get().saturating_add(1))?;
+	}: _(RawOrigin::Signed(payer), payment_id)
+	verify {
+		assert!(!Payments::<T>::contains_key(payment_id));
+	}
+
+	accept {
+		let (_, payee, payment_id) = create_payments::<T>(1)?;
+	}: _(RawOrigin::Signed(payee), payment_id)
+	verify {
+		assert!(matches!(Pallet::<T>::payments(payment_id).map(|payment| payment.state), Some(PaymentState::Accepted { .. })));
+	}
+
+	dispute {
+		let (payer, _, payment_id) = accepted_payment::<T>()?;
+	}: _(RawOrigin::Signed(payer), payment_id)
+	verify {
+		assert_eq!(Pallet::<T>::payments(payment_id).map(|payment| payment.state), Some(PaymentState::Disputed));
+	}
+
+	claim {
+		let (_, payee, payment_id) = accepted_payment::<T>()?;
+		let now = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(now.saturating_add(T::DisputeWindow::get()));
+	}: _(RawOrigin::Signed(payee.clone()), payment_id)
+	verify {
+		assert!(!Payments::<T>::contains_key(payment_id));
+		assert_eq!(T::MultiCurrency::free_balance(CURRENCY.into(), &payee), AMOUNT.into());
+	}
+
+	// the payment is split between the payee and the payer
+	resolve {
+		let (payer, _, payment_id) = accepted_payment::<T>()?;
+		Pallet::<T>::dispute(RawOrigin::Signed(payer).into(), payment_id)?;
+	}: _<T::Origin>(T::JudgeOrigin::successful_origin(), payment_id, Permill::from_percent(50))
+	verify {
+		assert!(!Payments::<T>::contains_key(payment_id));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::default().build(), crate::mock::Runtime);
@@ -0,0 +1,437 @@
+//! # Payments
+//!
+//! ## Overview
+//!
+//! This module holds payments in escrow.
+//!
+//! A payer creates a payment of any `NamedMultiReservableCurrency` asset, the
+//! amount is reserved from the payer under `Config::ReserveId`. The payer can cancel the payment until
+//! the payee accepts it. Once accepted, either party can dispute the payment
+//! within `DisputeWindow` blocks, after which the payee can claim it.
+//! Disputed payments are resolved by `JudgeOrigin`, which decides the share
+//! of the payment the payee receives, the rest is returned to the payer.
+//!
+//! The open payments of each account, as payer or payee, are indexed. Up to
+//! `MaxIndexedPayments` of them are kept in `OpenPayments`, the rest overflow
+//! to `OpenPaymentsOverflow`. `open_payments_page` lists them a page at a
+//! time, also available to wallets through the `PaymentsApi` runtime API.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::MaxEncodedLen;
+use frame_support::{pallet_prelude::*, transactional, BoundedVec};
+use frame_system::{ensure_signed, pallet_prelude::*};
+use orml_traits::{BalanceStatus, MultiCurrency, NamedMultiReservableCurrency};
+use sp_runtime::{
+	traits::{Saturating, StaticLookup, Zero},
+	DispatchResult, Permill, RuntimeDebug,
+};
+use sp_std::vec::Vec;
+
+mod benchmarking;
+mod mock;
+mod tests;
+mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The payment ID type.
+pub type PaymentId = u32;
+
+/// The state of a payment.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum PaymentState<BlockNumber> {
+	/// Created by the payer, can be cancelled by the payer.
+	Created,
+	/// Accepted by the payee, can be claimed by the payee at
+	/// `claimable_at` if not disputed.
+	Accepted { claimable_at: BlockNumber },
+	/// Disputed, to be resolved by `JudgeOrigin`.
+	Disputed,
+}
+
+/// The role of an account in an open payment.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum PaymentRole {
+	/// The account pays the payment.
+	Payer,
+	/// The account receives the payment.
+	Payee,
+}
+
+/// A payment held in escrow.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct PaymentDetail<AccountId, CurrencyId, Balance, BlockNumber> {
+	/// The account paying, whose funds are reserved.
+	pub payer: AccountId,
+	/// The account receiving the payment.
+	pub payee: AccountId,
+	/// The currency of the payment.
+	pub currency_id: CurrencyId,
+	/// The amount of the payment.
+	pub amount: Balance,
+	/// The state of the payment.
+	pub state: PaymentState<BlockNumber>,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	pub(crate) type BalanceOf<T> =
+		<<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub(crate) type CurrencyIdOf<T> =
+		<<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
+	pub(crate) type ReserveIdentifierOf<T> = <<T as Config>::MultiCurrency as NamedMultiReservableCurrency<
+		<T as frame_system::Config>::AccountId,
+	>>::ReserveIdentifier;
+	pub(crate) type PaymentDetailOf<T> = PaymentDetail<
+		<T as frame_system::Config>::AccountId,
+		CurrencyIdOf<T>,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency to hold payments in escrow.
+		type MultiCurrency: NamedMultiReservableCurrency<Self::AccountId>;
+
+		/// The identifier of the named reserve holding payments in escrow.
+		type ReserveId: Get<ReserveIdentifierOf<Self>>;
+
+		/// The number of blocks after acceptance during which a payment can
+		/// be disputed.
+		#[pallet::constant]
+		type DisputeWindow: Get<Self::BlockNumber>;
+
+		/// The origin which resolves disputes.
+		type JudgeOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum number of open payments of an account indexed in
+		/// `OpenPayments`, the rest overflow to `OpenPaymentsOverflow`.
+		#[pallet::constant]
+		type MaxIndexedPayments: Get<u32>;
+
+		/// Weight information for extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The payment does not exist.
+		PaymentNotFound,
+		/// The payment amount is zero.
+		ZeroAmount,
+		/// The payer and the payee are the same account.
+		PayToSelf,
+		/// The origin is not the payer of the payment.
+		NotPayer,
+		/// The origin is not the payee of the payment.
+		NotPayee,
+		/// The origin is neither the payer nor the payee of the payment.
+		NotParticipant,
+		/// The payment is not in the state the operation requires.
+		InvalidPaymentState,
+		/// The dispute window has not passed yet.
+		DisputeWindowNotPassed,
+		/// The dispute window has passed.
+		DisputeWindowPassed,
+		/// No available payment ID.
+		NoAvailablePaymentId,
+		/// The payer has less reserved balance than the payment amount.
+		InsufficientReservedBalance,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A payment is created. [payment_id, payer, payee, currency_id,
+		/// amount]
+		PaymentCreated(PaymentId, T::AccountId, T::AccountId, CurrencyIdOf<T>, BalanceOf<T>),
+		/// A payment is cancelled by the payer. [payment_id]
+		PaymentCancelled(PaymentId),
+		/// A payment is accepted by the payee. [payment_id, claimable_at]
+		PaymentAccepted(PaymentId, T::BlockNumber),
+		/// A payment is disputed. [payment_id, who]
+		PaymentDisputed(PaymentId, T::AccountId),
+		/// A payment is claimed by the payee. [payment_id]
+		PaymentClaimed(PaymentId),
+		/// A disputed payment is resolved. [payment_id, to_payee, to_payer]
+		PaymentResolved(PaymentId, BalanceOf<T>, BalanceOf<T>),
+	}
+
+	/// The payments held in escrow. Closed payments are removed.
+	///
+	/// map PaymentId => Option<PaymentDetail>
+	#[pallet::storage]
+	#[pallet::getter(fn payments)]
+	pub type Payments<T: Config> = StorageMap<_, Twox64Concat, PaymentId, PaymentDetailOf<T>, OptionQuery>;
+
+	/// The open payments of accounts, up to `MaxIndexedPayments` of them.
+	///
+	/// map AccountId => BoundedVec<(PaymentId, PaymentRole)>
+	#[pallet::storage]
+	#[pallet::getter(fn open_payments)]
+	pub type OpenPayments<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<(PaymentId, PaymentRole), T::MaxIndexedPayments>,
+		ValueQuery,
+	>;
+
+	/// The open payments of accounts overflowing `OpenPayments`.
+	///
+	/// double_map AccountId, PaymentId => Option<PaymentRole>
+	#[pallet::storage]
+	#[pallet::getter(fn open_payments_overflow)]
+	pub type OpenPaymentsOverflow<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, PaymentId, PaymentRole, OptionQuery>;
+
+	/// Track the next payment ID.
+	#[pallet::storage]
+	#[pallet::getter(fn next_payment_id)]
+	pub type NextPaymentId<T: Config> = StorageValue<_, PaymentId, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a payment to `payee`, reserving `amount` of `currency_id`
+		/// from the payer.
+		///
+		/// The dispatch origin for this call must be `Signed` by the payer.
+		#[pallet::weight(T::WeightInfo::create())]
+		#[transactional]
+		pub fn create(
+			origin: OriginFor<T>,
+			payee: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let payer = ensure_signed(origin)?;
+			let payee = T::Lookup::lookup(payee)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			ensure!(payer != payee, Error::<T>::PayToSelf);
+
+			let payment_id = NextPaymentId::<T>::try_mutate(|id| -> Result<PaymentId, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(1).ok_or(Error::<T>::NoAvailablePaymentId)?;
+				Ok(current_id)
+			})?;
+			T::MultiCurrency::reserve_named(&T::ReserveId::get(), currency_id, &payer, amount)?;
+
+			Self::index_open_payment(&payer, payment_id, PaymentRole::Payer);
+			Self::index_open_payment(&payee, payment_id, PaymentRole::Payee);
+			Payments::<T>::insert(
+				payment_id,
+				PaymentDetail {
+					payer: payer.clone(),
+					payee: payee.clone(),
+					currency_id,
+					amount,
+					state: PaymentState::Created,
+				},
+			);
+
+			Self::deposit_event(Event::PaymentCreated(payment_id, payer, payee, currency_id, amount));
+			Ok(())
+		}
+
+		/// Cancel a payment not accepted yet, the reserved amount is returned
+		/// to the payer.
+		///
+		/// The dispatch origin for this call must be `Signed` by the payer.
+		#[pallet::weight(T::WeightInfo::cancel())]
+		#[transactional]
+		pub fn cancel(origin: OriginFor<T>, payment_id: PaymentId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let payment = Self::payments(payment_id).ok_or(Error::<T>::PaymentNotFound)?;
+			ensure!(who == payment.payer, Error::<T>::NotPayer);
+			ensure!(payment.state == PaymentState::Created, Error::<T>::InvalidPaymentState);
+
+			let remaining = T::MultiCurrency::unreserve_named(
+				&T::ReserveId::get(),
+				payment.currency_id,
+				&payment.payer,
+				payment.amount,
+			);
+			ensure!(remaining.is_zero(), Error::<T>::InsufficientReservedBalance);
+			Self::close_payment(payment_id, &payment);
+
+			Self::deposit_event(Event::PaymentCancelled(payment_id));
+			Ok(())
+		}
+
+		/// Accept a payment, starting the dispute window.
+		///
+		/// The dispatch origin for this call must be `Signed` by the payee.
+		#[pallet::weight(T::WeightInfo::accept())]
+		pub fn accept(origin: OriginFor<T>, payment_id: PaymentId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let claimable_at = Payments::<T>::try_mutate(payment_id, |maybe_payment| -> Result<_, DispatchError> {
+				let payment = maybe_payment.as_mut().ok_or(Error::<T>::PaymentNotFound)?;
+				ensure!(who == payment.payee, Error::<T>::NotPayee);
+				ensure!(payment.state == PaymentState::Created, Error::<T>::InvalidPaymentState);
+
+				let claimable_at = frame_system::Pallet::<T>::block_number().saturating_add(T::DisputeWindow::get());
+				payment.state = PaymentState::Accepted { claimable_at };
+				Ok(claimable_at)
+			})?;
+
+			Self::deposit_event(Event::PaymentAccepted(payment_id, claimable_at));
+			Ok(())
+		}
+
+		/// Dispute an accepted payment within the dispute window.
+		///
+		/// The dispatch origin for this call must be `Signed` by the payer or
+		/// the payee.
+		#[pallet::weight(T::WeightInfo::dispute())]
+		pub fn dispute(origin: OriginFor<T>, payment_id: PaymentId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Payments::<T>::try_mutate(payment_id, |maybe_payment| -> DispatchResult {
+				let payment = maybe_payment.as_mut().ok_or(Error::<T>::PaymentNotFound)?;
+				ensure!(who == payment.payer || who == payment.payee, Error::<T>::NotParticipant);
+				match payment.state {
+					PaymentState::Accepted { claimable_at } => ensure!(
+						frame_system::Pallet::<T>::block_number() < claimable_at,
+						Error::<T>::DisputeWindowPassed
+					),
+					_ => return Err(Error::<T>::InvalidPaymentState.into()),
+				}
+
+				payment.state = PaymentState::Disputed;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::PaymentDisputed(payment_id, who));
+			Ok(())
+		}
+
+		/// Claim an accepted payment after the dispute window.
+		///
+		/// The dispatch origin for this call must be `Signed` by the payee.
+		#[pallet::weight(T::WeightInfo::claim())]
+		#[transactional]
+		pub fn claim(origin: OriginFor<T>, payment_id: PaymentId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let payment = Self::payments(payment_id).ok_or(Error::<T>::PaymentNotFound)?;
+			ensure!(who == payment.payee, Error::<T>::NotPayee);
+			match payment.state {
+				PaymentState::Accepted { claimable_at } => ensure!(
+					frame_system::Pallet::<T>::block_number() >= claimable_at,
+					Error::<T>::DisputeWindowNotPassed
+				),
+				_ => return Err(Error::<T>::InvalidPaymentState.into()),
+			}
+
+			let remaining = T::MultiCurrency::repatriate_reserved_named(
+				&T::ReserveId::get(),
+				payment.currency_id,
+				&payment.payer,
+				&payment.payee,
+				payment.amount,
+				BalanceStatus::Free,
+			)?;
+			ensure!(remaining.is_zero(), Error::<T>::InsufficientReservedBalance);
+			Self::close_payment(payment_id, &payment);
+
+			Self::deposit_event(Event::PaymentClaimed(payment_id));
+			Ok(())
+		}
+
+		/// Resolve a disputed payment. `payee_share` of the payment is paid
+		/// to the payee, the rest is returned to the payer.
+		///
+		/// The dispatch origin for this call must be `JudgeOrigin`.
+		#[pallet::weight(T::WeightInfo::resolve())]
+		#[transactional]
+		pub fn resolve(origin: OriginFor<T>, payment_id: PaymentId, payee_share: Permill) -> DispatchResult {
+			T::JudgeOrigin::ensure_origin(origin)?;
+			let payment = Self::payments(payment_id).ok_or(Error::<T>::PaymentNotFound)?;
+			ensure!(payment.state == PaymentState::Disputed, Error::<T>::InvalidPaymentState);
+
+			let to_payee = payee_share * payment.amount;
+			let to_payer = payment.amount.saturating_sub(to_payee);
+			let reserve_id = T::ReserveId::get();
+			if !to_payee.is_zero() {
+				let remaining = T::MultiCurrency::repatriate_reserved_named(
+					&reserve_id,
+					payment.currency_id,
+					&payment.payer,
+					&payment.payee,
+					to_payee,
+					BalanceStatus::Free,
+				)?;
+				ensure!(remaining.is_zero(), Error::<T>::InsufficientReservedBalance);
+			}
+			let remaining =
+				T::MultiCurrency::unreserve_named(&reserve_id, payment.currency_id, &payment.payer, to_payer);
+			ensure!(remaining.is_zero(), Error::<T>::InsufficientReservedBalance);
+			Self::close_payment(payment_id, &payment);
+
+			Self::deposit_event(Event::PaymentResolved(payment_id, to_payee, to_payer));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The open payments of `who`, as payer or payee, skipping the first
+	/// `start` of them and returning at most `limit`.
+	pub fn open_payments_page(who: &T::AccountId, start: u32, limit: u32) -> Vec<(PaymentId, PaymentRole)> {
+		Self::open_payments(who)
+			.into_iter()
+			.chain(OpenPaymentsOverflow::<T>::iter_prefix(who))
+			.skip(start as usize)
+			.take(limit as usize)
+			.collect()
+	}
+
+	/// Remove a closed payment and its index entries.
+	fn close_payment(payment_id: PaymentId, payment: &PaymentDetailOf<T>) {
+		Self::unindex_open_payment(&payment.payer, payment_id);
+		Self::unindex_open_payment(&payment.payee, payment_id);
+		Payments::<T>::remove(payment_id);
+	}
+
+	/// Index `payment_id` as an open payment of `who`, overflowing to
+	/// `OpenPaymentsOverflow` once `MaxIndexedPayments` are indexed.
+	fn index_open_payment(who: &T::AccountId, payment_id: PaymentId, role: PaymentRole) {
+		OpenPayments::<T>::mutate(who, |payments| {
+			if payments.try_push((payment_id, role)).is_err() {
+				OpenPaymentsOverflow::<T>::insert(who, payment_id, role);
+			}
+		});
+	}
+
+	/// Remove `payment_id` from the open payments of `who`, moving an
+	/// overflowed payment into the freed slot.
+	fn unindex_open_payment(who: &T::AccountId, payment_id: PaymentId) {
+		if OpenPaymentsOverflow::<T>::take(who, payment_id).is_some() {
+			return;
+		}
+		OpenPayments::<T>::mutate_exists(who, |maybe_payments| {
+			if let Some(payments) = maybe_payments {
+				payments.retain(|(id, _)| *id != payment_id);
+				if let Some(overflowed) = OpenPaymentsOverflow::<T>::drain_prefix(who).next() {
+					// a slot was freed, so the push can't fail
+					let _ = payments.try_push(overflowed);
+				}
+			}
+			if maybe_payments.as_ref().map_or(false, |payments| payments.is_empty()) {
+				*maybe_payments = None;
+			}
+		});
+	}
+}
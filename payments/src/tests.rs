@@ -0,0 +1,295 @@
+//! Unit tests for the payments module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+fn create_payment(amount: Balance) -> PaymentId {
+	let payment_id = PaymentsModule::next_payment_id();
+	assert_ok!(PaymentsModule::create(Origin::signed(ALICE), BOB, DOT, amount));
+	payment_id
+}
+
+#[test]
+fn create_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let payment_id = create_payment(100);
+		System::assert_last_event(Event::PaymentsModule(crate::Event::PaymentCreated(
+			payment_id, ALICE, BOB, DOT, 100,
+		)));
+		assert_eq!(
+			PaymentsModule::payments(payment_id),
+			Some(PaymentDetail {
+				payer: ALICE,
+				payee: BOB,
+				currency_id: DOT,
+				amount: 100,
+				state: PaymentState::Created,
+			})
+		);
+		assert_eq!(
+			Tokens::reserved_balance_named(&PaymentsReserveId::get(), DOT, &ALICE),
+			100
+		);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 900);
+		assert_eq!(PaymentsModule::next_payment_id(), 1);
+	});
+}
+
+#[test]
+fn create_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			PaymentsModule::create(Origin::signed(ALICE), BOB, DOT, 0),
+			Error::<Runtime>::ZeroAmount
+		);
+		assert_noop!(
+			PaymentsModule::create(Origin::signed(ALICE), ALICE, DOT, 100),
+			Error::<Runtime>::PayToSelf
+		);
+		assert_noop!(
+			PaymentsModule::create(Origin::signed(CHARLIE), BOB, DOT, 100),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+
+		NextPaymentId::<Runtime>::put(PaymentId::MAX);
+		assert_noop!(
+			PaymentsModule::create(Origin::signed(ALICE), BOB, DOT, 100),
+			Error::<Runtime>::NoAvailablePaymentId
+		);
+	});
+}
+
+#[test]
+fn cancel_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let payment_id = create_payment(100);
+		assert_noop!(
+			PaymentsModule::cancel(Origin::signed(BOB), payment_id),
+			Error::<Runtime>::NotPayer
+		);
+		assert_noop!(
+			PaymentsModule::cancel(Origin::signed(ALICE), payment_id + 1),
+			Error::<Runtime>::PaymentNotFound
+		);
+
+		assert_ok!(PaymentsModule::cancel(Origin::signed(ALICE), payment_id));
+		System::assert_last_event(Event::PaymentsModule(crate::Event::PaymentCancelled(payment_id)));
+		assert_eq!(PaymentsModule::payments(payment_id), None);
+		assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 1_000);
+	});
+}
+
+#[test]
+fn open_payments_should_be_indexed() {
+	ExtBuilder::default().build().execute_with(|| {
+		let first = create_payment(100);
+		let second = create_payment(100);
+		let third = create_payment(100);
+
+		// the third payment overflows
+		assert_eq!(
+			PaymentsModule::open_payments(ALICE).into_inner(),
+			vec![(first, PaymentRole::Payer), (second, PaymentRole::Payer)]
+		);
+		assert_eq!(
+			PaymentsModule::open_payments_overflow(ALICE, third),
+			Some(PaymentRole::Payer)
+		);
+		assert_eq!(
+			PaymentsModule::open_payments_page(&ALICE, 0, 10),
+			vec![
+				(first, PaymentRole::Payer),
+				(second, PaymentRole::Payer),
+				(third, PaymentRole::Payer)
+			]
+		);
+		assert_eq!(
+			PaymentsModule::open_payments_page(&BOB, 1, 1),
+			vec![(second, PaymentRole::Payee)]
+		);
+
+		// the overflowed payment moves into the freed slot
+		assert_ok!(PaymentsModule::cancel(Origin::signed(ALICE), first));
+		assert_eq!(
+			PaymentsModule::open_payments(BOB).into_inner(),
+			vec![(second, PaymentRole::Payee), (third, PaymentRole::Payee)]
+		);
+		assert_eq!(PaymentsModule::open_payments_overflow(BOB, third), None);
+
+		assert_ok!(PaymentsModule::cancel(Origin::signed(ALICE), second));
+		assert_ok!(PaymentsModule::cancel(Origin::signed(ALICE), third));
+		assert!(!OpenPayments::<Runtime>::contains_key(ALICE));
+		assert!(!OpenPayments::<Runtime>::contains_key(BOB));
+	});
+}
+
+#[test]
+fn cannot_cancel_accepted_payment() {
+	ExtBuilder::default().build().execute_with(|| {
+		let payment_id = create_payment(100);
+		assert_noop!(
+			PaymentsModule::accept(Origin::signed(ALICE), payment_id),
+			Error::<Runtime>::NotPayee
+		);
+		assert_ok!(PaymentsModule::accept(Origin::signed(BOB), payment_id));
+		System::assert_last_event(Event::PaymentsModule(crate::Event::PaymentAccepted(
+			payment_id,
+			1 + DISPUTE_WINDOW,
+		)));
+		assert_noop!(
+			PaymentsModule::accept(Origin::signed(BOB), payment_id),
+			Error::<Runtime>::InvalidPaymentState
+		);
+		assert_noop!(
+			PaymentsModule::cancel(Origin::signed(ALICE), payment_id),
+			Error::<Runtime>::InvalidPaymentState
+		);
+	});
+}
+
+#[test]
+fn claim_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let payment_id = create_payment(100);
+		assert_noop!(
+			PaymentsModule::claim(Origin::signed(BOB), payment_id),
+			Error::<Runtime>::InvalidPaymentState
+		);
+		assert_ok!(PaymentsModule::accept(Origin::signed(BOB), payment_id));
+
+		System::set_block_number(DISPUTE_WINDOW);
+		assert_noop!(
+			PaymentsModule::claim(Origin::signed(BOB), payment_id),
+			Error::<Runtime>::DisputeWindowNotPassed
+		);
+
+		System::set_block_number(1 + DISPUTE_WINDOW);
+		assert_noop!(
+			PaymentsModule::claim(Origin::signed(ALICE), payment_id),
+			Error::<Runtime>::NotPayee
+		);
+		assert_noop!(
+			PaymentsModule::dispute(Origin::signed(ALICE), payment_id),
+			Error::<Runtime>::DisputeWindowPassed
+		);
+		assert_ok!(PaymentsModule::claim(Origin::signed(BOB), payment_id));
+		System::assert_last_event(Event::PaymentsModule(crate::Event::PaymentClaimed(payment_id)));
+		assert_eq!(PaymentsModule::payments(payment_id), None);
+		assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 900);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 1_100);
+		assert!(PaymentsModule::open_payments_page(&ALICE, 0, 10).is_empty());
+		assert!(PaymentsModule::open_payments_page(&BOB, 0, 10).is_empty());
+	});
+}
+
+#[test]
+fn cannot_pay_out_more_than_reserved() {
+	ExtBuilder::default().build().execute_with(|| {
+		let claimed = create_payment(100);
+		let resolved = create_payment(100);
+		assert_ok!(PaymentsModule::accept(Origin::signed(BOB), claimed));
+		assert_ok!(PaymentsModule::accept(Origin::signed(BOB), resolved));
+		assert_ok!(PaymentsModule::dispute(Origin::signed(BOB), resolved));
+
+		// the escrow is released by another module
+		assert_eq!(Tokens::unreserve_named(&PaymentsReserveId::get(), DOT, &ALICE, 150), 0);
+		System::set_block_number(1 + DISPUTE_WINDOW);
+		assert_noop!(
+			PaymentsModule::claim(Origin::signed(BOB), claimed),
+			Error::<Runtime>::InsufficientReservedBalance
+		);
+		assert_noop!(
+			PaymentsModule::resolve(Origin::root(), resolved, Permill::from_percent(30)),
+			Error::<Runtime>::InsufficientReservedBalance
+		);
+	});
+}
+
+#[test]
+fn dispute_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let payment_id = create_payment(100);
+		assert_noop!(
+			PaymentsModule::dispute(Origin::signed(ALICE), payment_id),
+			Error::<Runtime>::InvalidPaymentState
+		);
+		assert_ok!(PaymentsModule::accept(Origin::signed(BOB), payment_id));
+		assert_noop!(
+			PaymentsModule::dispute(Origin::signed(CHARLIE), payment_id),
+			Error::<Runtime>::NotParticipant
+		);
+
+		assert_ok!(PaymentsModule::dispute(Origin::signed(ALICE), payment_id));
+		System::assert_last_event(Event::PaymentsModule(crate::Event::PaymentDisputed(payment_id, ALICE)));
+		assert_eq!(
+			PaymentsModule::payments(payment_id).map(|payment| payment.state),
+			Some(PaymentState::Disputed)
+		);
+		assert_noop!(
+			PaymentsModule::dispute(Origin::signed(BOB), payment_id),
+			Error::<Runtime>::InvalidPaymentState
+		);
+
+		// disputed payments can't be claimed
+		System::set_block_number(1 + DISPUTE_WINDOW);
+		assert_noop!(
+			PaymentsModule::claim(Origin::signed(BOB), payment_id),
+			Error::<Runtime>::InvalidPaymentState
+		);
+	});
+}
+
+#[test]
+fn resolve_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let payment_id = create_payment(100);
+		assert_noop!(
+			PaymentsModule::resolve(Origin::root(), payment_id, Permill::from_percent(30)),
+			Error::<Runtime>::InvalidPaymentState
+		);
+		assert_ok!(PaymentsModule::accept(Origin::signed(BOB), payment_id));
+		assert_ok!(PaymentsModule::dispute(Origin::signed(BOB), payment_id));
+
+		assert_noop!(
+			PaymentsModule::resolve(Origin::signed(ALICE), payment_id, Permill::from_percent(30)),
+			BadOrigin
+		);
+		assert_ok!(PaymentsModule::resolve(
+			Origin::root(),
+			payment_id,
+			Permill::from_percent(30)
+		));
+		System::assert_last_event(Event::PaymentsModule(crate::Event::PaymentResolved(payment_id, 30, 70)));
+		assert_eq!(PaymentsModule::payments(payment_id), None);
+		assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 970);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 1_030);
+	});
+}
+
+#[test]
+fn resolve_in_full_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let first = create_payment(100);
+		let second = create_payment(100);
+		for payment_id in [first, second] {
+			assert_ok!(PaymentsModule::accept(Origin::signed(BOB), payment_id));
+			assert_ok!(PaymentsModule::dispute(Origin::signed(ALICE), payment_id));
+		}
+
+		assert_ok!(PaymentsModule::resolve(Origin::root(), first, Permill::zero()));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 900);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 1_000);
+
+		assert_ok!(PaymentsModule::resolve(Origin::root(), second, Permill::one()));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 900);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 1_100);
+		assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
+	});
+}
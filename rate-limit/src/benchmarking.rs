@@ -0,0 +1,42 @@
+//! Benchmarks for the rate limit module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+
+const LIMITER: u8 = 1;
+
+benchmarks! {
+	where_clause { where T::RateLimiterId: From<u8> }
+
+	// the default rule is replaced, which bumps its version
+	update_rate_limit_rule {
+		let limiter_id: T::RateLimiterId = LIMITER.into();
+		let rule = RateLimitRule::FixedWindow { period: 10u32.into(), quota: 100 };
+		DefaultRateLimitRules::<T>::insert(limiter_id, RateLimitRule::NotAllowed);
+	}: _<T::Origin>(T::GovernanceOrigin::successful_origin(), limiter_id, None, Some(rule))
+	verify {
+		assert_eq!(DefaultRateLimitRules::<T>::get(limiter_id), Some(rule));
+		assert_eq!(DefaultRuleVersions::<T>::get(limiter_id), 1);
+	}
+
+	add_whitelist {
+		let limiter_id: T::RateLimiterId = LIMITER.into();
+		let key = vec![0u8; 32];
+	}: _<T::Origin>(T::GovernanceOrigin::successful_origin(), limiter_id, key.clone())
+	verify {
+		assert!(Whitelist::<T>::contains_key(limiter_id, &key));
+	}
+
+	remove_whitelist {
+		let limiter_id: T::RateLimiterId = LIMITER.into();
+		let key = vec![0u8; 32];
+		Whitelist::<T>::insert(limiter_id, &key, ());
+	}: _<T::Origin>(T::GovernanceOrigin::successful_origin(), limiter_id, key.clone())
+	verify {
+		assert!(!Whitelist::<T>::contains_key(limiter_id, &key));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::default().build(), crate::mock::Runtime);
@@ -0,0 +1,349 @@
+//! # Rate Limit
+//!
+//! ## Overview
+//!
+//! This module limits the rate of values consumed under keys, and implements
+//! `orml_traits::RateLimiter` for other pallets to use.
+//!
+//! Each rate limiter, identified by `RateLimiterId`, has rules set by
+//! `GovernanceOrigin` for encoded keys, and a default rule for keys without
+//! a rule of their own. Keys without any rule are not limited. Rules are
+//! either a fixed window quota, a token bucket, unlimited or not allowed.
+//! Keys in the whitelist of a rate limiter bypass its rules.
+//!
+//! Updating the default rule does not remove the consumption states kept
+//! under the previous default rule. It bumps the default rule version of the
+//! rate limiter instead, and states of an older version are ignored, so the
+//! update costs the same however many keys have consumed.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use orml_traits::rate_limit::{RateLimiter, RateLimiterError, TokenBucket};
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::prelude::*;
+
+mod benchmarking;
+mod mock;
+mod tests;
+mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The rule limiting the values consumed under a key.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum RateLimitRule<BlockNumber> {
+	/// At most `quota` could be consumed in every window of `period` blocks.
+	FixedWindow { period: BlockNumber, quota: u128 },
+	/// Consume tokens from a bucket holding up to `capacity` tokens, refilled
+	/// by `refill` tokens every `period` blocks.
+	TokenBucket {
+		period: BlockNumber,
+		refill: u128,
+		capacity: u128,
+	},
+	/// No limit.
+	Unlimited,
+	/// Nothing could be consumed.
+	NotAllowed,
+}
+
+/// The consumption state of a key.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum RateLimitState<BlockNumber> {
+	/// `consumed` in the window starting at `window_start`.
+	FixedWindow { window_start: BlockNumber, consumed: u128 },
+	/// The token bucket of the key.
+	TokenBucket(TokenBucket<BlockNumber>),
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which may update rules and whitelists.
+		type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The rate limiter ID type.
+		type RateLimiterId: Parameter + Member + Copy;
+
+		/// Weight information for extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The period of a fixed window rule is zero.
+		InvalidRule,
+		/// The key is already in the whitelist.
+		AlreadyWhitelisted,
+		/// The key is not in the whitelist.
+		NotWhitelisted,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A rule is updated, `None` key for the default rule and `None` rule
+		/// for removal. [limiter_id, key, rule]
+		RateLimitRuleUpdated(T::RateLimiterId, Option<Vec<u8>>, Option<RateLimitRule<T::BlockNumber>>),
+		/// A key is added to the whitelist. [limiter_id, key]
+		WhitelistAdded(T::RateLimiterId, Vec<u8>),
+		/// A key is removed from the whitelist. [limiter_id, key]
+		WhitelistRemoved(T::RateLimiterId, Vec<u8>),
+	}
+
+	/// The default rule of rate limiters, applied to keys without a rule.
+	///
+	/// map RateLimiterId => Option<RateLimitRule>
+	#[pallet::storage]
+	#[pallet::getter(fn default_rate_limit_rules)]
+	pub type DefaultRateLimitRules<T: Config> =
+		StorageMap<_, Twox64Concat, T::RateLimiterId, RateLimitRule<T::BlockNumber>, OptionQuery>;
+
+	/// The rules of encoded keys.
+	///
+	/// double_map RateLimiterId, EncodedKey => Option<RateLimitRule>
+	#[pallet::storage]
+	#[pallet::getter(fn rate_limit_rules)]
+	pub type RateLimitRules<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::RateLimiterId,
+		Blake2_128Concat,
+		Vec<u8>,
+		RateLimitRule<T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// The version of the default rule of rate limiters, bumped on every
+	/// update of the default rule.
+	///
+	/// map RateLimiterId => DefaultRuleVersion
+	#[pallet::storage]
+	#[pallet::getter(fn default_rule_versions)]
+	pub type DefaultRuleVersions<T: Config> = StorageMap<_, Twox64Concat, T::RateLimiterId, u32, ValueQuery>;
+
+	/// The consumption states of encoded keys, with the version of the default
+	/// rule they were kept under, `None` if kept under a rule of the key.
+	///
+	/// double_map RateLimiterId, EncodedKey => Option<(Option<DefaultRuleVersion>,
+	/// RateLimitState)>
+	#[pallet::storage]
+	#[pallet::getter(fn rate_limit_states)]
+	pub type RateLimitStates<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::RateLimiterId,
+		Blake2_128Concat,
+		Vec<u8>,
+		(Option<u32>, RateLimitState<T::BlockNumber>),
+		OptionQuery,
+	>;
+
+	/// The encoded keys bypassing the rules.
+	///
+	/// double_map RateLimiterId, EncodedKey => Option<()>
+	#[pallet::storage]
+	#[pallet::getter(fn whitelist)]
+	pub type Whitelist<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::RateLimiterId, Blake2_128Concat, Vec<u8>, (), OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Update the rule of an encoded key, or the default rule if `key` is
+		/// `None`. Remove the rule if `rule` is `None`.
+		///
+		/// The consumption states affected by the rule are reset. States under
+		/// the default rule are invalidated by bumping its version rather than
+		/// removed.
+		///
+		/// The dispatch origin for this call must be `GovernanceOrigin`.
+		#[pallet::weight(T::WeightInfo::update_rate_limit_rule())]
+		pub fn update_rate_limit_rule(
+			origin: OriginFor<T>,
+			limiter_id: T::RateLimiterId,
+			key: Option<Vec<u8>>,
+			rule: Option<RateLimitRule<T::BlockNumber>>,
+		) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			if let Some(RateLimitRule::FixedWindow { period, .. }) = rule {
+				ensure!(!period.is_zero(), Error::<T>::InvalidRule);
+			}
+
+			match &key {
+				Some(key) => {
+					RateLimitRules::<T>::set(limiter_id, key, rule);
+					RateLimitStates::<T>::remove(limiter_id, key);
+				}
+				None => {
+					DefaultRateLimitRules::<T>::set(limiter_id, rule);
+					DefaultRuleVersions::<T>::mutate(limiter_id, |version| *version = version.wrapping_add(1));
+				}
+			}
+
+			Self::deposit_event(Event::RateLimitRuleUpdated(limiter_id, key, rule));
+			Ok(())
+		}
+
+		/// Add an encoded key to the whitelist.
+		///
+		/// The dispatch origin for this call must be `GovernanceOrigin`.
+		#[pallet::weight(T::WeightInfo::add_whitelist())]
+		pub fn add_whitelist(origin: OriginFor<T>, limiter_id: T::RateLimiterId, key: Vec<u8>) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			ensure!(
+				!Whitelist::<T>::contains_key(limiter_id, &key),
+				Error::<T>::AlreadyWhitelisted
+			);
+			Whitelist::<T>::insert(limiter_id, &key, ());
+
+			Self::deposit_event(Event::WhitelistAdded(limiter_id, key));
+			Ok(())
+		}
+
+		/// Remove an encoded key from the whitelist.
+		///
+		/// The dispatch origin for this call must be `GovernanceOrigin`.
+		#[pallet::weight(T::WeightInfo::remove_whitelist())]
+		pub fn remove_whitelist(origin: OriginFor<T>, limiter_id: T::RateLimiterId, key: Vec<u8>) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			ensure!(
+				Whitelist::<T>::contains_key(limiter_id, &key),
+				Error::<T>::NotWhitelisted
+			);
+			Whitelist::<T>::remove(limiter_id, &key);
+
+			Self::deposit_event(Event::WhitelistRemoved(limiter_id, key));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The rule applied to an encoded key.
+	pub fn rule_of(limiter_id: T::RateLimiterId, encoded_key: &[u8]) -> Option<RateLimitRule<T::BlockNumber>> {
+		Self::applied_rule(limiter_id, encoded_key).map(|(rule, _)| rule)
+	}
+
+	/// The rule applied to an encoded key, with the version of the default
+	/// rule if the key has no rule of its own.
+	fn applied_rule(
+		limiter_id: T::RateLimiterId,
+		encoded_key: &[u8],
+	) -> Option<(RateLimitRule<T::BlockNumber>, Option<u32>)> {
+		match RateLimitRules::<T>::get(limiter_id, encoded_key) {
+			Some(rule) => Some((rule, None)),
+			None => DefaultRateLimitRules::<T>::get(limiter_id)
+				.map(|rule| (rule, Some(DefaultRuleVersions::<T>::get(limiter_id)))),
+		}
+	}
+
+	/// The consumption state of an encoded key under `rule` of `version` at
+	/// the current block, `None` if `rule` keeps no state. A state kept under
+	/// another version is discarded.
+	fn current_state(
+		limiter_id: T::RateLimiterId,
+		encoded_key: &[u8],
+		rule: RateLimitRule<T::BlockNumber>,
+		version: Option<u32>,
+	) -> Option<RateLimitState<T::BlockNumber>> {
+		let now = frame_system::Pallet::<T>::block_number();
+		let state = RateLimitStates::<T>::get(limiter_id, encoded_key)
+			.filter(|(state_version, _)| *state_version == version)
+			.map(|(_, state)| state);
+		match rule {
+			RateLimitRule::FixedWindow { period, .. } => {
+				let window_start = now.saturating_sub(now % period);
+				let consumed = match state {
+					Some(RateLimitState::FixedWindow {
+						window_start: start,
+						consumed,
+					}) if start == window_start => consumed,
+					_ => Zero::zero(),
+				};
+				Some(RateLimitState::FixedWindow { window_start, consumed })
+			}
+			RateLimitRule::TokenBucket {
+				period,
+				refill,
+				capacity,
+			} => {
+				let bucket = match state {
+					Some(RateLimitState::TokenBucket(mut bucket)) => {
+						bucket.refill(now);
+						bucket
+					}
+					_ => TokenBucket::new(capacity, refill, period, now),
+				};
+				Some(RateLimitState::TokenBucket(bucket))
+			}
+			RateLimitRule::Unlimited | RateLimitRule::NotAllowed => None,
+		}
+	}
+}
+
+impl<T: Config> RateLimiter for Pallet<T> {
+	type RateLimiterId = T::RateLimiterId;
+
+	fn is_whitelist(limiter_id: Self::RateLimiterId, key: impl Encode) -> bool {
+		Whitelist::<T>::contains_key(limiter_id, key.encode())
+	}
+
+	fn can_consume(limiter_id: Self::RateLimiterId, key: impl Encode, value: u128) -> Result<(), RateLimiterError> {
+		let encoded_key = key.encode();
+		let (rule, version) = match Self::applied_rule(limiter_id, &encoded_key) {
+			Some(applied) => applied,
+			None => return Ok(()),
+		};
+
+		let allowed = match (rule, Self::current_state(limiter_id, &encoded_key, rule, version)) {
+			(RateLimitRule::FixedWindow { quota, .. }, Some(RateLimitState::FixedWindow { consumed, .. })) => {
+				consumed.saturating_add(value) <= quota
+			}
+			(_, Some(RateLimitState::TokenBucket(bucket))) => bucket.can_consume(value),
+			(RateLimitRule::NotAllowed, _) => false,
+			_ => true,
+		};
+
+		if allowed {
+			Ok(())
+		} else {
+			Err(RateLimiterError::ExceedLimit)
+		}
+	}
+
+	fn consume(limiter_id: Self::RateLimiterId, key: impl Encode, value: u128) {
+		let encoded_key = key.encode();
+		let (rule, version) = match Self::applied_rule(limiter_id, &encoded_key) {
+			Some(applied) => applied,
+			None => return,
+		};
+
+		let state = match Self::current_state(limiter_id, &encoded_key, rule, version) {
+			Some(RateLimitState::FixedWindow { window_start, consumed }) => RateLimitState::FixedWindow {
+				window_start,
+				consumed: consumed.saturating_add(value),
+			},
+			Some(RateLimitState::TokenBucket(mut bucket)) => {
+				bucket.consume(value);
+				RateLimitState::TokenBucket(bucket)
+			}
+			None => return,
+		};
+		RateLimitStates::<T>::insert(limiter_id, encoded_key, (version, state));
+	}
+}
@@ -0,0 +1,298 @@
+//! Unit tests for the rate limit module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn update_rate_limit_rule_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let rule = RateLimitRule::FixedWindow { period: 10, quota: 100 };
+		assert_noop!(
+			RateLimit::update_rate_limit_rule(Origin::signed(ALICE), LIMITER, Some(ALICE.encode()), Some(rule)),
+			BadOrigin
+		);
+		assert_noop!(
+			RateLimit::update_rate_limit_rule(
+				Origin::root(),
+				LIMITER,
+				Some(ALICE.encode()),
+				Some(RateLimitRule::FixedWindow { period: 0, quota: 100 })
+			),
+			Error::<Runtime>::InvalidRule
+		);
+
+		assert_ok!(RateLimit::update_rate_limit_rule(
+			Origin::root(),
+			LIMITER,
+			Some(ALICE.encode()),
+			Some(rule)
+		));
+		System::assert_last_event(Event::RateLimit(crate::Event::RateLimitRuleUpdated(
+			LIMITER,
+			Some(ALICE.encode()),
+			Some(rule),
+		)));
+		assert_eq!(RateLimit::rate_limit_rules(LIMITER, ALICE.encode()), Some(rule));
+		assert_eq!(RateLimit::rule_of(LIMITER, &BOB.encode()), None);
+
+		// updating the rule resets the state
+		RateLimit::consume(LIMITER, ALICE, 10);
+		assert!(RateLimit::rate_limit_states(LIMITER, ALICE.encode()).is_some());
+		assert_ok!(RateLimit::update_rate_limit_rule(
+			Origin::root(),
+			LIMITER,
+			Some(ALICE.encode()),
+			None
+		));
+		assert_eq!(RateLimit::rate_limit_rules(LIMITER, ALICE.encode()), None);
+		assert_eq!(RateLimit::rate_limit_states(LIMITER, ALICE.encode()), None);
+
+		// the default rule applies to keys without a rule
+		assert_ok!(RateLimit::update_rate_limit_rule(
+			Origin::root(),
+			LIMITER,
+			None,
+			Some(RateLimitRule::NotAllowed)
+		));
+		assert_eq!(
+			RateLimit::default_rate_limit_rules(LIMITER),
+			Some(RateLimitRule::NotAllowed)
+		);
+		assert_eq!(
+			RateLimit::rule_of(LIMITER, &BOB.encode()),
+			Some(RateLimitRule::NotAllowed)
+		);
+	});
+}
+
+#[test]
+fn default_rule_update_should_reset_states() {
+	ExtBuilder::default().build().execute_with(|| {
+		let rule = RateLimitRule::FixedWindow { period: 10, quota: 100 };
+		assert_ok!(RateLimit::update_rate_limit_rule(
+			Origin::root(),
+			LIMITER,
+			None,
+			Some(rule)
+		));
+		assert_eq!(RateLimit::default_rule_versions(LIMITER), 1);
+
+		RateLimit::consume(LIMITER, ALICE, 100);
+		RateLimit::consume(LIMITER, BOB, 100);
+		assert_eq!(
+			RateLimit::can_consume(LIMITER, ALICE, 1),
+			Err(RateLimiterError::ExceedLimit)
+		);
+
+		// the states are kept, but those of the previous version are ignored
+		assert_ok!(RateLimit::update_rate_limit_rule(
+			Origin::root(),
+			LIMITER,
+			None,
+			Some(rule)
+		));
+		assert_eq!(RateLimit::default_rule_versions(LIMITER), 2);
+		assert_eq!(
+			RateLimit::rate_limit_states(LIMITER, ALICE.encode()),
+			Some((
+				Some(1),
+				RateLimitState::FixedWindow {
+					window_start: 0,
+					consumed: 100
+				}
+			))
+		);
+		assert_ok!(RateLimit::can_consume(LIMITER, ALICE, 100));
+		assert_ok!(RateLimit::can_consume(LIMITER, BOB, 100));
+
+		RateLimit::consume(LIMITER, ALICE, 10);
+		assert_eq!(
+			RateLimit::rate_limit_states(LIMITER, ALICE.encode()),
+			Some((
+				Some(2),
+				RateLimitState::FixedWindow {
+					window_start: 0,
+					consumed: 10
+				}
+			))
+		);
+
+		// updating the rule of a key does not bump the default rule version
+		assert_ok!(RateLimit::update_rate_limit_rule(
+			Origin::root(),
+			LIMITER,
+			Some(BOB.encode()),
+			Some(rule)
+		));
+		assert_eq!(RateLimit::default_rule_versions(LIMITER), 2);
+		RateLimit::consume(LIMITER, BOB, 10);
+		assert_eq!(
+			RateLimit::rate_limit_states(LIMITER, BOB.encode()),
+			Some((
+				None,
+				RateLimitState::FixedWindow {
+					window_start: 0,
+					consumed: 10
+				}
+			))
+		);
+	});
+}
+
+#[test]
+fn fixed_window_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(RateLimit::update_rate_limit_rule(
+			Origin::root(),
+			LIMITER,
+			Some(ALICE.encode()),
+			Some(RateLimitRule::FixedWindow { period: 10, quota: 100 })
+		));
+
+		assert_ok!(RateLimit::can_consume(LIMITER, ALICE, 100));
+		assert_eq!(
+			RateLimit::can_consume(LIMITER, ALICE, 101),
+			Err(RateLimiterError::ExceedLimit)
+		);
+
+		RateLimit::consume(LIMITER, ALICE, 60);
+		assert_eq!(
+			RateLimit::rate_limit_states(LIMITER, ALICE.encode()),
+			Some((
+				None,
+				RateLimitState::FixedWindow {
+					window_start: 0,
+					consumed: 60
+				}
+			))
+		);
+		assert_ok!(RateLimit::can_consume(LIMITER, ALICE, 40));
+		assert_eq!(
+			RateLimit::can_consume(LIMITER, ALICE, 41),
+			Err(RateLimiterError::ExceedLimit)
+		);
+
+		// keys without a rule are not limited
+		assert_ok!(RateLimit::can_consume(LIMITER, BOB, u128::MAX));
+
+		// a new window
+		System::set_block_number(10);
+		assert_ok!(RateLimit::can_consume(LIMITER, ALICE, 100));
+		RateLimit::consume(LIMITER, ALICE, 100);
+		assert_eq!(
+			RateLimit::can_consume(LIMITER, ALICE, 1),
+			Err(RateLimiterError::ExceedLimit)
+		);
+	});
+}
+
+#[test]
+fn token_bucket_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(RateLimit::update_rate_limit_rule(
+			Origin::root(),
+			LIMITER,
+			None,
+			Some(RateLimitRule::TokenBucket {
+				period: 5,
+				refill: 10,
+				capacity: 50
+			})
+		));
+
+		RateLimit::consume(LIMITER, ALICE, 50);
+		assert_eq!(
+			RateLimit::can_consume(LIMITER, ALICE, 1),
+			Err(RateLimiterError::ExceedLimit)
+		);
+		// each key has its own bucket
+		assert_ok!(RateLimit::can_consume(LIMITER, BOB, 50));
+
+		System::set_block_number(6);
+		assert_ok!(RateLimit::can_consume(LIMITER, ALICE, 10));
+		assert_eq!(
+			RateLimit::can_consume(LIMITER, ALICE, 11),
+			Err(RateLimiterError::ExceedLimit)
+		);
+
+		System::set_block_number(100);
+		assert_ok!(RateLimit::can_consume(LIMITER, ALICE, 50));
+		assert_eq!(
+			RateLimit::can_consume(LIMITER, ALICE, 51),
+			Err(RateLimiterError::ExceedLimit)
+		);
+	});
+}
+
+#[test]
+fn unlimited_and_not_allowed_rules_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(RateLimit::update_rate_limit_rule(
+			Origin::root(),
+			LIMITER,
+			None,
+			Some(RateLimitRule::NotAllowed)
+		));
+		assert_ok!(RateLimit::update_rate_limit_rule(
+			Origin::root(),
+			LIMITER,
+			Some(ALICE.encode()),
+			Some(RateLimitRule::Unlimited)
+		));
+
+		assert_ok!(RateLimit::can_consume(LIMITER, ALICE, u128::MAX));
+		RateLimit::consume(LIMITER, ALICE, u128::MAX);
+		assert_eq!(RateLimit::rate_limit_states(LIMITER, ALICE.encode()), None);
+
+		assert_eq!(
+			RateLimit::can_consume(LIMITER, BOB, 1),
+			Err(RateLimiterError::ExceedLimit)
+		);
+	});
+}
+
+#[test]
+fn whitelist_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(RateLimit::update_rate_limit_rule(
+			Origin::root(),
+			LIMITER,
+			None,
+			Some(RateLimitRule::NotAllowed)
+		));
+
+		assert_noop!(
+			RateLimit::add_whitelist(Origin::signed(ALICE), LIMITER, ALICE.encode()),
+			BadOrigin
+		);
+		assert_ok!(RateLimit::add_whitelist(Origin::root(), LIMITER, ALICE.encode()));
+		System::assert_last_event(Event::RateLimit(crate::Event::WhitelistAdded(LIMITER, ALICE.encode())));
+		assert_noop!(
+			RateLimit::add_whitelist(Origin::root(), LIMITER, ALICE.encode()),
+			Error::<Runtime>::AlreadyWhitelisted
+		);
+		assert!(RateLimit::is_whitelist(LIMITER, ALICE));
+		assert!(!RateLimit::is_whitelist(LIMITER, BOB));
+
+		assert_ok!(RateLimit::try_consume(LIMITER, 0u32, 100, Some(ALICE)));
+		assert_eq!(
+			RateLimit::try_consume(LIMITER, 0u32, 100, Some(BOB)),
+			Err(RateLimiterError::ExceedLimit)
+		);
+
+		assert_ok!(RateLimit::remove_whitelist(Origin::root(), LIMITER, ALICE.encode()));
+		System::assert_last_event(Event::RateLimit(crate::Event::WhitelistRemoved(
+			LIMITER,
+			ALICE.encode(),
+		)));
+		assert_noop!(
+			RateLimit::remove_whitelist(Origin::root(), LIMITER, ALICE.encode()),
+			Error::<Runtime>::NotWhitelisted
+		);
+		assert!(!RateLimit::is_whitelist(LIMITER, ALICE));
+	});
+}
@@ -0,0 +1,23 @@
+//! Runtime API definition for rewards module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_runtime::FixedU128;
+use sp_std::prelude::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait RewardsApi<PoolId, CurrencyId, BlockNumber> where
+		PoolId: Codec,
+		CurrencyId: Codec,
+		BlockNumber: Codec,
+	{
+		/// The annualised rewards per share of each reward currency of
+		/// `pool_id`, over the latest `window` blocks.
+		fn pool_apr(pool_id: PoolId, window: BlockNumber) -> Vec<(CurrencyId, FixedU128)>;
+	}
+}
@@ -5,15 +5,15 @@ mod mock;
 mod tests;
 
 use codec::{FullCodec, HasCompact};
-use frame_support::pallet_prelude::*;
-use orml_traits::RewardHandler;
+use frame_support::{pallet_prelude::*, BoundedVec};
+use orml_traits::{LockIdentifier, OnLockChanged, OnRewardDeduction, RewardHandler, ShareBoost};
 use scale_info::TypeInfo;
 use sp_core::U256;
 use sp_runtime::{
 	traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member, Saturating, UniqueSaturatedInto, Zero},
-	FixedPointOperand, RuntimeDebug, SaturatedConversion,
+	FixedPointNumber, FixedPointOperand, FixedU128, Permill, RuntimeDebug, SaturatedConversion,
 };
-use sp_std::{borrow::ToOwned, collections::btree_map::BTreeMap, fmt::Debug, prelude::*};
+use sp_std::{borrow::ToOwned, collections::btree_map::BTreeMap, fmt::Debug, marker::PhantomData, prelude::*};
 
 /// The Reward Pool Info.
 #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
@@ -38,6 +38,58 @@ where
 	}
 }
 
+/// Where the rewards deducted at claim time go.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum DeductionDestination {
+	/// Accumulate the deducted rewards back to the other shares of the pool,
+	/// or hand them over to `Config::OnDeduction` if there are none.
+	Redistribute,
+	/// Hand the deducted rewards over to `Config::OnDeduction`.
+	Handler,
+}
+
+/// The reward deduction applied at claim time.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ClaimDeduction {
+	/// The rate of claimed rewards to be deducted.
+	pub rate: Permill,
+	/// Where the deducted rewards go.
+	pub destination: DeductionDestination,
+}
+
+/// The periodic decay of an emission schedule.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct EmissionDecay<BlockNumber> {
+	/// The number of blocks between two decays.
+	pub period: BlockNumber,
+	/// The rate the emission per block is reduced by at each decay, e.g. 50%
+	/// for halving.
+	pub rate: Permill,
+	/// The block number of the next decay.
+	pub next_decay_at: BlockNumber,
+}
+
+/// The reward emission schedule of a reward currency in a pool.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct EmissionSchedule<Balance, BlockNumber> {
+	/// The reward accumulated to the pool each block.
+	pub per_block: Balance,
+	/// The optional decay of `per_block`.
+	pub decay: Option<EmissionDecay<BlockNumber>>,
+}
+
+/// The snapshot of a pool, taken every `Config::SnapshotPeriod` blocks.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PoolSnapshot<Share, Balance, CurrencyId: Ord, BlockNumber> {
+	/// The block number the snapshot was taken at.
+	pub taken_at: BlockNumber,
+	/// Total shares amount
+	pub total_shares: Share,
+	/// Rewards ever accumulated to the pool <reward_currency,
+	/// accumulated_reward>
+	pub accumulated_rewards: BTreeMap<CurrencyId, Balance>,
+}
+
 pub use module::*;
 
 #[frame_support::pallet]
@@ -67,18 +119,56 @@ pub mod module {
 			+ FixedPointOperand;
 
 		/// The reward pool ID type.
-		type PoolId: Parameter + Member + Clone + FullCodec;
+		type PoolId: Parameter + Member + Clone + FullCodec + Ord;
 
 		type CurrencyId: Parameter + Member + Copy + MaybeSerializeDeserialize + Ord;
 
 		/// The `RewardHandler`
 		type Handler: RewardHandler<Self::AccountId, Self::CurrencyId, Balance = Self::Balance, PoolId = Self::PoolId>;
+
+		/// Handler for the rewards deducted at claim time, with
+		/// `DeductionDestination::Handler`.
+		type OnDeduction: OnRewardDeduction<Self::AccountId, Self::PoolId, Self::CurrencyId, Self::Balance>;
+
+		/// The share multipliers of accounts, the shares of accounts are
+		/// boosted by their multipliers.
+		type ShareBoost: ShareBoost<Self::AccountId, Self::PoolId>;
+
+		/// The number of blocks between two pool snapshots, zero to disable
+		/// snapshots.
+		#[pallet::constant]
+		type SnapshotPeriod: Get<Self::BlockNumber>;
+
+		/// The maximum number of snapshots kept for each pool.
+		#[pallet::constant]
+		type MaxSnapshots: Get<u32>;
+
+		/// The maximum number of pools snapshotted per block. The pools not
+		/// snapshotted in the snapshot block are snapshotted in the following
+		/// blocks.
+		#[pallet::constant]
+		type MaxSnapshotsPerBlock: Get<u32>;
+
+		/// The number of blocks per year, used to annualise the pool APR.
+		#[pallet::constant]
+		type BlocksPerYear: Get<Self::BlockNumber>;
+
+		/// The maximum number of emission schedules, all accumulated in
+		/// `on_initialize` of each block.
+		#[pallet::constant]
+		type MaxEmissionSchedules: Get<u32>;
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Pool does not exist
 		PoolDoesNotExist,
+		/// Pool is paused
+		PoolPaused,
+		/// Shares can only be migrated from a paused pool to another pool
+		InvalidMigration,
+		/// The number of emission schedules would exceed `MaxEmissionSchedules`
+		TooManyEmissionSchedules,
 	}
 
 	/// Record reward pool info.
@@ -105,11 +195,154 @@ pub mod module {
 		ValueQuery,
 	>;
 
+	/// Record the share of specific `AccountId` under `PoolId` before boosted
+	/// by its multiplier, only if it differs from the boosted share.
+	///
+	/// double_map (PoolId, AccountId) => Option<Share>
+	#[pallet::storage]
+	pub type BaseShares<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::PoolId, Twox64Concat, T::AccountId, T::Share, OptionQuery>;
+
+	/// Record reward deduction applied at claim time for specific pool.
+	///
+	/// map PoolId => Option<ClaimDeduction>
+	#[pallet::storage]
+	#[pallet::getter(fn claim_deductions)]
+	pub type ClaimDeductions<T: Config> = StorageMap<_, Twox64Concat, T::PoolId, ClaimDeduction, OptionQuery>;
+
+	/// Record reward emission schedule of specific reward currency under
+	/// `PoolId`.
+	///
+	/// double_map (PoolId, CurrencyId) => Option<EmissionSchedule>
+	#[pallet::storage]
+	#[pallet::getter(fn emission_schedules)]
+	pub type EmissionSchedules<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::PoolId,
+		Twox64Concat,
+		T::CurrencyId,
+		EmissionSchedule<T::Balance, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// The number of emission schedules.
+	///
+	/// EmissionScheduleCount: u32
+	#[pallet::storage]
+	#[pallet::getter(fn emission_schedule_count)]
+	pub type EmissionScheduleCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Record rewards ever accumulated to specific reward currency under
+	/// `PoolId`.
+	///
+	/// double_map (PoolId, CurrencyId) => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn accumulated_rewards)]
+	pub type AccumulatedRewards<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::PoolId, Twox64Concat, T::CurrencyId, T::Balance, ValueQuery>;
+
+	/// The latest snapshots of pool, oldest first, the oldest dropped when
+	/// `MaxSnapshots` are kept.
+	///
+	/// map PoolId => Vec<PoolSnapshot>
+	#[pallet::storage]
+	#[pallet::getter(fn pool_snapshots)]
+	pub type PoolSnapshots<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::PoolId,
+		BoundedVec<PoolSnapshot<T::Share, T::Balance, T::CurrencyId, T::BlockNumber>, T::MaxSnapshots>,
+		ValueQuery,
+	>;
+
+	/// The pools being snapshotted, resuming after the pool, or from the
+	/// first pool if `None`.
+	///
+	/// SnapshotProgress: Option<Option<PoolId>>
+	#[pallet::storage]
+	#[pallet::getter(fn snapshot_progress)]
+	pub type SnapshotProgress<T: Config> = StorageValue<_, Option<T::PoolId>, OptionQuery>;
+
+	/// The paused pools, not accumulating rewards nor paying them out.
+	///
+	/// map PoolId => Option<()>
+	#[pallet::storage]
+	pub type PausedPools<T: Config> = StorageMap<_, Twox64Concat, T::PoolId, (), OptionQuery>;
+
+	/// Record rewards owed to specific `AccountId` under `PoolId` outside of
+	/// its share, from the shares removed from paused pools or migrated from
+	/// other pools, paid out at the next claim.
+	///
+	/// The rewards are recorded by the pool they were earned in, and are net
+	/// of the claim deduction of that pool.
+	///
+	/// double_map (PoolId, AccountId) => BTreeMap<(PoolId, CurrencyId),
+	/// Balance>
+	#[pallet::storage]
+	#[pallet::getter(fn pending_rewards)]
+	pub type PendingRewards<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::PoolId,
+		Twox64Concat,
+		T::AccountId,
+		BTreeMap<(T::PoolId, T::CurrencyId), T::Balance>,
+		ValueQuery,
+	>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Accumulate rewards to pools according to the emission schedules.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			// bounded by `MaxEmissionSchedules` in `set_emission_schedule`
+			let schedules = EmissionSchedules::<T>::iter()
+				.take(T::MaxEmissionSchedules::get() as usize)
+				.collect::<Vec<_>>();
+			let mut reads: Weight = schedules.len() as Weight;
+			let mut writes: Weight = 0;
+
+			for (pool, reward_currency, mut schedule) in schedules {
+				if !schedule.per_block.is_zero() && PoolInfos::<T>::contains_key(&pool) && !Self::is_paused(&pool) {
+					// the pool exists and is not paused, accumulation won't fail
+					let _ = Self::accumulate_reward(&pool, reward_currency, schedule.per_block);
+					writes += 1;
+				}
+				reads += 2;
+
+				if let Some(decay) = schedule.decay.as_mut() {
+					if now >= decay.next_decay_at {
+						schedule.per_block = schedule
+							.per_block
+							.saturating_sub(decay.rate.mul_floor(schedule.per_block));
+						decay.next_decay_at = now.saturating_add(decay.period);
+
+						if schedule.per_block.is_zero() {
+							EmissionSchedules::<T>::remove(&pool, reward_currency);
+							EmissionScheduleCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+							writes += 1;
+						} else {
+							EmissionSchedules::<T>::insert(&pool, reward_currency, schedule);
+						}
+						writes += 1;
+					}
+				}
+			}
+
+			let period = T::SnapshotPeriod::get();
+			if !period.is_zero() && (now % period).is_zero() {
+				SnapshotProgress::<T>::put(None::<T::PoolId>);
+				writes += 1;
+			}
+
+			T::DbWeight::get()
+				.reads_writes(reads, writes)
+				.saturating_add(Self::take_snapshots(now))
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {}
@@ -124,6 +357,7 @@ impl<T: Config> Pallet<T> {
 		if reward_increment.is_zero() {
 			return Ok(());
 		}
+		ensure!(!Self::is_paused(pool), Error::<T>::PoolPaused);
 		PoolInfos::<T>::mutate_exists(pool, |maybe_pool_info| -> DispatchResult {
 			let pool_info = maybe_pool_info.as_mut().ok_or(Error::<T>::PoolDoesNotExist)?;
 
@@ -135,15 +369,200 @@ impl<T: Config> Pallet<T> {
 				})
 				.or_insert((reward_increment, Zero::zero()));
 
+			AccumulatedRewards::<T>::mutate(pool, reward_currency, |accumulated_reward| {
+				*accumulated_reward = accumulated_reward.saturating_add(reward_increment);
+			});
+
 			Ok(())
 		})
 	}
 
+	/// The annualised rewards per share of `pool` for each reward currency,
+	/// over the latest `window` blocks.
+	///
+	/// Computed against the oldest snapshot within `window`, returns empty if
+	/// there is no such snapshot.
+	pub fn pool_apr(pool: &T::PoolId, window: T::BlockNumber) -> Vec<(T::CurrencyId, FixedU128)> {
+		let now = frame_system::Pallet::<T>::block_number();
+		let since = now.saturating_sub(window);
+
+		let snapshot = match Self::pool_snapshots(pool)
+			.into_inner()
+			.into_iter()
+			.find(|snapshot| snapshot.taken_at >= since)
+		{
+			Some(snapshot) => snapshot,
+			None => return Vec::new(),
+		};
+
+		let elapsed = now.saturating_sub(snapshot.taken_at).saturated_into::<u128>();
+		let average_shares = snapshot
+			.total_shares
+			.saturated_into::<u128>()
+			.saturating_add(Self::pool_infos(pool).total_shares.saturated_into::<u128>())
+			/ 2;
+		if elapsed.is_zero() || average_shares.is_zero() {
+			return Vec::new();
+		}
+
+		let blocks_per_year = T::BlocksPerYear::get().saturated_into::<u128>();
+		AccumulatedRewards::<T>::iter_prefix(pool)
+			.map(|(reward_currency, accumulated_reward)| {
+				let reward_increment = accumulated_reward.saturating_sub(
+					snapshot
+						.accumulated_rewards
+						.get(&reward_currency)
+						.copied()
+						.unwrap_or_default(),
+				);
+				let apr = FixedU128::saturating_from_rational(
+					reward_increment
+						.saturated_into::<u128>()
+						.saturating_mul(blocks_per_year),
+					average_shares.saturating_mul(elapsed),
+				);
+				(reward_currency, apr)
+			})
+			.collect()
+	}
+
+	/// Snapshot the pools in progress, at most `MaxSnapshotsPerBlock` of
+	/// them per block. Returns the consumed weight.
+	fn take_snapshots(now: T::BlockNumber) -> Weight {
+		let last = match Self::snapshot_progress() {
+			Some(last) => last,
+			None => return T::DbWeight::get().reads(1),
+		};
+		let max = T::MaxSnapshotsPerBlock::get() as usize;
+		let pools = match last {
+			Some(pool) => PoolInfos::<T>::iter_from(PoolInfos::<T>::hashed_key_for(pool)),
+			None => PoolInfos::<T>::iter(),
+		}
+		.take(max)
+		.collect::<Vec<_>>();
+
+		let done = pools.len() < max;
+		let mut reads: Weight = 1;
+		let mut writes: Weight = 1;
+		let mut last = None;
+		for (pool, pool_info) in pools {
+			reads += 2 + pool_info.rewards.len() as Weight;
+			writes += 1;
+			Self::take_snapshot(&pool, pool_info.total_shares, now);
+			last = Some(pool);
+		}
+
+		if done {
+			SnapshotProgress::<T>::kill();
+		} else {
+			SnapshotProgress::<T>::put(last);
+		}
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	fn take_snapshot(pool: &T::PoolId, total_shares: T::Share, now: T::BlockNumber) {
+		let snapshot = PoolSnapshot {
+			taken_at: now,
+			total_shares,
+			accumulated_rewards: AccumulatedRewards::<T>::iter_prefix(pool).collect(),
+		};
+
+		PoolSnapshots::<T>::mutate(pool, |snapshots| {
+			if !snapshots.is_empty() && snapshots.len() >= T::MaxSnapshots::get() as usize {
+				snapshots.remove(0);
+			}
+			// the oldest is dropped when full, the push only fails if
+			// `MaxSnapshots` is zero
+			let _ = snapshots.try_push(snapshot);
+		});
+	}
+
+	/// The share of `who` in `pool` before boosted by its multiplier.
+	pub fn base_share(who: &T::AccountId, pool: &T::PoolId) -> T::Share {
+		BaseShares::<T>::get(pool, who).unwrap_or_else(|| Self::shares_and_withdrawn_rewards(pool, who).0)
+	}
+
+	/// Add `add_amount` of share of `who` to `pool`, boosted by the share
+	/// multiplier of `who`.
 	pub fn add_share(who: &T::AccountId, pool: &T::PoolId, add_amount: T::Share) {
 		if add_amount.is_zero() {
 			return;
 		}
 
+		let base_share = Self::base_share(who, pool).saturating_add(add_amount);
+		Self::apply_share_boost(who, pool, base_share, false);
+	}
+
+	/// Remove `remove_amount` of share of `who` from `pool`, claiming the
+	/// rewards of `who` firstly.
+	pub fn remove_share(who: &T::AccountId, pool: &T::PoolId, remove_amount: T::Share) {
+		if remove_amount.is_zero() {
+			return;
+		}
+
+		let base_share = Self::base_share(who, pool).saturating_sub(remove_amount);
+		Self::apply_share_boost(who, pool, base_share, false);
+	}
+
+	/// Remove `remove_amount` of share of `who` from `pool`, without
+	/// claiming rewards.
+	///
+	/// The pending rewards of `who` are carried forward to the remaining
+	/// share, only the part the remaining share cannot carry is settled by
+	/// paying it out, net of the claim deduction, or kept pending if `pool`
+	/// is paused.
+	pub fn withdraw_share(who: &T::AccountId, pool: &T::PoolId, remove_amount: T::Share) {
+		if remove_amount.is_zero() {
+			return;
+		}
+
+		let base_share = Self::base_share(who, pool).saturating_sub(remove_amount);
+		Self::apply_share_boost(who, pool, base_share, true);
+	}
+
+	/// Apply the current share multiplier of `who` to its share in `pool`,
+	/// to be called once the multiplier changes.
+	pub fn update_share_boost(who: &T::AccountId, pool: &T::PoolId) {
+		Self::apply_share_boost(who, pool, Self::base_share(who, pool), false);
+	}
+
+	/// Set the share of `who` in `pool` to `base_share` boosted by the share
+	/// multiplier of `who`. Shares are added or removed by the difference
+	/// with the current boosted share, so the reward accounting is kept.
+	fn apply_share_boost(who: &T::AccountId, pool: &T::PoolId, base_share: T::Share, withdraw: bool) {
+		let (share, _) = Self::shares_and_withdrawn_rewards(pool, who);
+		let boosted_share = T::ShareBoost::share_multiplier(who, pool).saturating_mul_int(base_share);
+
+		if boosted_share > share {
+			Self::do_add_share(who, pool, boosted_share.saturating_sub(share));
+		} else if Self::is_paused(pool) {
+			// rewards can't be claimed from a paused pool, keep them pending
+			if withdraw {
+				let rewards = Self::do_withdraw_share(who, pool, share.saturating_sub(boosted_share));
+				Self::add_pending_rewards(who, pool, Self::deduct_rewards(who, pool, rewards));
+			} else {
+				Self::park_rewards(who, pool);
+				Self::do_remove_share(who, pool, share.saturating_sub(boosted_share));
+			}
+		} else if withdraw {
+			let rewards = Self::do_withdraw_share(who, pool, share.saturating_sub(boosted_share));
+			Self::payout_rewards(who, pool, Self::deduct_rewards(who, pool, rewards));
+		} else {
+			Self::do_remove_share(who, pool, share.saturating_sub(boosted_share));
+		}
+
+		if boosted_share == base_share {
+			BaseShares::<T>::remove(pool, who);
+		} else {
+			BaseShares::<T>::insert(pool, who, base_share);
+		}
+	}
+
+	fn do_add_share(who: &T::AccountId, pool: &T::PoolId, add_amount: T::Share) {
+		if add_amount.is_zero() {
+			return;
+		}
+
 		PoolInfos::<T>::mutate(pool, |pool_info| {
 			let initial_total_shares = pool_info.total_shares;
 			pool_info.total_shares = pool_info.total_shares.saturating_add(add_amount);
@@ -187,7 +606,7 @@ impl<T: Config> Pallet<T> {
 		});
 	}
 
-	pub fn remove_share(who: &T::AccountId, pool: &T::PoolId, remove_amount: T::Share) {
+	fn do_remove_share(who: &T::AccountId, pool: &T::PoolId, remove_amount: T::Share) {
 		if remove_amount.is_zero() {
 			return;
 		}
@@ -249,8 +668,94 @@ impl<T: Config> Pallet<T> {
 		});
 	}
 
+	/// Remove `remove_amount` of share of `who` from `pool`, returns the
+	/// rewards settled as the remaining share can't carry them.
+	fn do_withdraw_share(
+		who: &T::AccountId,
+		pool: &T::PoolId,
+		remove_amount: T::Share,
+	) -> Vec<(T::CurrencyId, T::Balance)> {
+		let mut settled_rewards = Vec::<(T::CurrencyId, T::Balance)>::new();
+		if remove_amount.is_zero() {
+			return settled_rewards;
+		}
+
+		SharesAndWithdrawnRewards::<T>::mutate_exists(pool, who, |share_info| {
+			if let Some((share, mut withdrawn_rewards)) = share_info.take() {
+				let remove_amount = remove_amount.min(share);
+
+				PoolInfos::<T>::mutate_exists(pool, |maybe_pool_info| {
+					if let Some(mut pool_info) = maybe_pool_info.take() {
+						let total_shares = U256::from(pool_info.total_shares.saturated_into::<u128>());
+						let removing_share = U256::from(remove_amount.saturated_into::<u128>());
+
+						pool_info.total_shares = pool_info.total_shares.saturating_sub(remove_amount);
+
+						// update rewards for each reward currency
+						pool_info.rewards.iter_mut().for_each(
+							|(reward_currency, (total_reward, total_withdrawn_reward))| {
+								let reward_to_remove: T::Balance = removing_share
+									.saturating_mul(total_reward.to_owned().saturated_into::<u128>().into())
+									.checked_div(total_shares)
+									.unwrap_or_default()
+									.as_u128()
+									.saturated_into();
+								let withdrawn_reward =
+									withdrawn_rewards.get(reward_currency).copied().unwrap_or_default();
+
+								// the remaining share can't carry the pending reward beyond its withdrawn
+								// reward, settle it
+								let reward_to_settle = reward_to_remove.saturating_sub(withdrawn_reward);
+								if !reward_to_settle.is_zero() {
+									settled_rewards.push((*reward_currency, reward_to_settle));
+								}
+
+								*total_reward = total_reward.saturating_sub(reward_to_remove);
+								*total_withdrawn_reward = total_withdrawn_reward
+									.saturating_add(reward_to_settle)
+									.saturating_sub(reward_to_remove);
+
+								let new_withdrawn_reward = withdrawn_reward
+									.saturating_add(reward_to_settle)
+									.saturating_sub(reward_to_remove);
+								if new_withdrawn_reward.is_zero() {
+									withdrawn_rewards.remove(reward_currency);
+								} else {
+									withdrawn_rewards.insert(*reward_currency, new_withdrawn_reward);
+								}
+							},
+						);
+
+						// remove if all reward is withdrawn
+						pool_info.rewards.retain(|_, (total_reward, _)| !total_reward.is_zero());
+
+						if !pool_info.total_shares.is_zero() {
+							*maybe_pool_info = Some(pool_info);
+						}
+					}
+				});
+
+				let share = share.saturating_sub(remove_amount);
+				if !share.is_zero() {
+					*share_info = Some((share, withdrawn_rewards));
+				}
+			}
+		});
+
+		settled_rewards
+	}
+
+	/// An estimate of the weight of `set_share` in `pool`: reading the pool,
+	/// its pause and claim deduction, and reading and writing the shares, the
+	/// base share and the pending rewards of the account, plus accumulating
+	/// and paying out each reward currency of the pool.
+	pub fn set_share_weight(pool: &T::PoolId) -> Weight {
+		let reward_currencies = Self::pool_infos(pool).rewards.len() as Weight;
+		T::DbWeight::get().reads_writes(7 + 3 * reward_currencies, 4 + 3 * reward_currencies)
+	}
+
 	pub fn set_share(who: &T::AccountId, pool: &T::PoolId, new_share: T::Share) {
-		let (share, _) = Self::shares_and_withdrawn_rewards(pool, who);
+		let share = Self::base_share(who, pool);
 
 		if new_share > share {
 			Self::add_share(who, pool, new_share.saturating_sub(share));
@@ -259,7 +764,106 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	pub fn claim_rewards(who: &T::AccountId, pool: &T::PoolId) {
+	/// Set the reward emission schedule of `reward_currency` for `pool`,
+	/// `None` to remove it.
+	///
+	/// Rewards are accumulated to `pool` in `on_initialize` of each block,
+	/// while `pool` has shares. At most `MaxEmissionSchedules` schedules
+	/// could be set.
+	pub fn set_emission_schedule(
+		pool: &T::PoolId,
+		reward_currency: T::CurrencyId,
+		schedule: Option<EmissionSchedule<T::Balance, T::BlockNumber>>,
+	) -> DispatchResult {
+		match (
+			EmissionSchedules::<T>::contains_key(pool, reward_currency),
+			schedule.is_some(),
+		) {
+			(false, true) => EmissionScheduleCount::<T>::try_mutate(|count| -> DispatchResult {
+				ensure!(
+					*count < T::MaxEmissionSchedules::get(),
+					Error::<T>::TooManyEmissionSchedules
+				);
+				*count += 1;
+				Ok(())
+			})?,
+			(true, false) => EmissionScheduleCount::<T>::mutate(|count| *count = count.saturating_sub(1)),
+			_ => {}
+		}
+		EmissionSchedules::<T>::set(pool, reward_currency, schedule);
+		Ok(())
+	}
+
+	/// Set the reward deduction applied at claim time for `pool`, `None` to
+	/// remove it.
+	pub fn set_claim_deduction(pool: &T::PoolId, deduction: Option<ClaimDeduction>) {
+		ClaimDeductions::<T>::set(pool, deduction);
+	}
+
+	/// Pause `pool`: rewards are no longer accumulated to it nor claimed
+	/// from it, until it's resumed.
+	pub fn pause_pool(pool: &T::PoolId) {
+		PausedPools::<T>::insert(pool, ());
+	}
+
+	/// Resume the paused `pool`.
+	pub fn resume_pool(pool: &T::PoolId) {
+		PausedPools::<T>::remove(pool);
+	}
+
+	/// Whether `pool` is paused.
+	pub fn is_paused(pool: &T::PoolId) -> bool {
+		PausedPools::<T>::contains_key(pool)
+	}
+
+	/// Move the shares and pending rewards of at most `max_accounts` accounts
+	/// from the paused pool `from` to `to`, to be called over blocks until
+	/// it returns `true` once all shares are migrated.
+	///
+	/// The shares are added to `to` boosted by the current multipliers, and
+	/// the pending rewards are paid out at the next claim from `to`, still
+	/// attributed to `from` and net of its claim deduction.
+	pub fn migrate_shares(
+		from: &T::PoolId,
+		to: &T::PoolId,
+		max_accounts: u32,
+	) -> sp_std::result::Result<bool, DispatchError> {
+		ensure!(from != to && Self::is_paused(from), Error::<T>::InvalidMigration);
+
+		// accounts with shares, then accounts with only pending rewards
+		let accounts = SharesAndWithdrawnRewards::<T>::iter_key_prefix(from)
+			.chain(PendingRewards::<T>::iter_key_prefix(from))
+			.take(max_accounts.saturating_add(1) as usize)
+			.collect::<Vec<_>>();
+		let finished = accounts.len() <= max_accounts as usize;
+
+		for who in accounts.into_iter().take(max_accounts as usize) {
+			let base_share = Self::base_share(&who, from);
+			Self::park_rewards(&who, from);
+			let (share, _) = Self::shares_and_withdrawn_rewards(from, &who);
+			Self::do_remove_share(&who, from, share);
+			BaseShares::<T>::remove(from, &who);
+
+			let pending_rewards = PendingRewards::<T>::take(from, &who);
+			if !pending_rewards.is_empty() {
+				PendingRewards::<T>::mutate(to, &who, |rewards| {
+					for (key, reward) in pending_rewards {
+						let pending = rewards.entry(key).or_default();
+						*pending = pending.saturating_add(reward);
+					}
+				});
+			}
+			Self::add_share(&who, to, base_share);
+		}
+
+		Ok(finished)
+	}
+
+	/// Withdraw the pending rewards of the share of `who` in `pool`, returns
+	/// the withdrawn rewards.
+	fn withdraw_rewards(who: &T::AccountId, pool: &T::PoolId) -> Vec<(T::CurrencyId, T::Balance)> {
+		let mut rewards = Vec::<(T::CurrencyId, T::Balance)>::new();
+
 		SharesAndWithdrawnRewards::<T>::mutate_exists(pool, who, |maybe_share_withdrawn| {
 			if let Some((share, withdrawn_rewards)) = maybe_share_withdrawn {
 				if share.is_zero() {
@@ -291,13 +895,189 @@ impl<T: Config> Pallet<T> {
 							*total_withdrawn_reward = total_withdrawn_reward.saturating_add(reward_to_withdraw);
 							withdrawn_rewards
 								.insert(*reward_currency, withdrawn_reward.saturating_add(reward_to_withdraw));
-
-							// pay reward to `who`
-							T::Handler::payout(who, pool, *reward_currency, reward_to_withdraw);
+							rewards.push((*reward_currency, reward_to_withdraw));
 						},
 					);
 				});
 			}
 		});
+
+		rewards
+	}
+
+	/// Deduct the claim deduction of `pool` from the `rewards` of `who`,
+	/// returns the rewards left to pay out.
+	fn deduct_rewards(
+		who: &T::AccountId,
+		pool: &T::PoolId,
+		rewards: Vec<(T::CurrencyId, T::Balance)>,
+	) -> Vec<(T::CurrencyId, T::Balance)> {
+		let deduction = match Self::claim_deductions(pool) {
+			Some(deduction) => deduction,
+			None => return rewards,
+		};
+
+		let mut deducted_rewards = Vec::<(T::CurrencyId, T::Balance)>::new();
+		let rewards = rewards
+			.into_iter()
+			.map(|(reward_currency, reward)| {
+				let deducted_reward = deduction.rate.mul_floor(reward);
+				if !deducted_reward.is_zero() {
+					deducted_rewards.push((reward_currency, deducted_reward));
+				}
+				(reward_currency, reward.saturating_sub(deducted_reward))
+			})
+			.collect();
+
+		for (reward_currency, deducted_reward) in deducted_rewards {
+			match deduction.destination {
+				DeductionDestination::Redistribute
+					if Self::redistribute_reward(who, pool, reward_currency, deducted_reward) => {}
+				_ => {
+					T::OnDeduction::on_reward_deduction(who, pool, reward_currency, deducted_reward);
+				}
+			}
+		}
+
+		rewards
+	}
+
+	/// Accumulate the `deducted_reward` of `who` to the other shares of
+	/// `pool`, returns false if there are no other shares.
+	///
+	/// The reward is scaled up to all the shares, and the part of the share of
+	/// `who` is withdrawn to it, so it earns nothing of its own deduction.
+	fn redistribute_reward(
+		who: &T::AccountId,
+		pool: &T::PoolId,
+		reward_currency: T::CurrencyId,
+		deducted_reward: T::Balance,
+	) -> bool {
+		let share = Self::shares_and_withdrawn_rewards(pool, who).0;
+		let mut withdrawn_by_share: Option<T::Balance> = None;
+
+		// also while paused, the pool exists as `who` has withdrawn rewards from it
+		PoolInfos::<T>::mutate(pool, |pool_info| {
+			let total_shares = U256::from(pool_info.total_shares.to_owned().saturated_into::<u128>());
+			let other_shares = total_shares.saturating_sub(U256::from(share.saturated_into::<u128>()));
+			if other_shares.is_zero() {
+				return;
+			}
+
+			let reward_increment: T::Balance = U256::from(deducted_reward.saturated_into::<u128>())
+				.saturating_mul(total_shares)
+				.checked_div(other_shares)
+				.unwrap_or_default()
+				.min(U256::from(u128::MAX))
+				.as_u128()
+				.unique_saturated_into();
+			let withdrawn = reward_increment.saturating_sub(deducted_reward);
+
+			let (total_reward, total_withdrawn_reward) = pool_info
+				.rewards
+				.entry(reward_currency)
+				.or_insert((Zero::zero(), Zero::zero()));
+			*total_reward = total_reward.saturating_add(reward_increment);
+			*total_withdrawn_reward = total_withdrawn_reward.saturating_add(withdrawn);
+			withdrawn_by_share = Some(withdrawn);
+		});
+
+		let withdrawn = match withdrawn_by_share {
+			Some(withdrawn) => withdrawn,
+			None => return false,
+		};
+		if !withdrawn.is_zero() {
+			SharesAndWithdrawnRewards::<T>::mutate(pool, who, |(_, withdrawn_rewards)| {
+				let withdrawn_reward = withdrawn_rewards.entry(reward_currency).or_insert_with(Zero::zero);
+				*withdrawn_reward = withdrawn_reward.saturating_add(withdrawn);
+			});
+		}
+		AccumulatedRewards::<T>::mutate(pool, reward_currency, |accumulated_reward| {
+			*accumulated_reward = accumulated_reward.saturating_add(deducted_reward);
+		});
+
+		true
+	}
+
+	/// Withdraw the pending rewards of the share of `who` in `pool` to its
+	/// pending rewards outside of the share, net of the claim deduction.
+	fn park_rewards(who: &T::AccountId, pool: &T::PoolId) {
+		let rewards = Self::deduct_rewards(who, pool, Self::withdraw_rewards(who, pool));
+		Self::add_pending_rewards(who, pool, rewards);
+	}
+
+	/// Add the `rewards` of `who` earned in `pool` to its pending rewards.
+	fn add_pending_rewards(who: &T::AccountId, pool: &T::PoolId, rewards: Vec<(T::CurrencyId, T::Balance)>) {
+		if rewards.is_empty() {
+			return;
+		}
+
+		PendingRewards::<T>::mutate(pool, who, |pending_rewards| {
+			for (reward_currency, reward) in rewards {
+				let pending = pending_rewards.entry((pool.clone(), reward_currency)).or_default();
+				*pending = pending.saturating_add(reward);
+			}
+		});
+	}
+
+	/// Pay the `rewards` of `who` in `pool` out.
+	fn payout_rewards(who: &T::AccountId, pool: &T::PoolId, rewards: Vec<(T::CurrencyId, T::Balance)>) {
+		for (reward_currency, reward_to_payout) in rewards {
+			// pay reward to `who`
+			if !reward_to_payout.is_zero() {
+				T::Handler::payout(who, pool, reward_currency, reward_to_payout);
+			}
+		}
+	}
+
+	/// Claim the rewards of `who` in `pool`, a no-op if `pool` is paused.
+	pub fn claim_rewards(who: &T::AccountId, pool: &T::PoolId) {
+		if Self::is_paused(pool) {
+			return;
+		}
+
+		let rewards = Self::withdraw_rewards(who, pool);
+		Self::payout_rewards(who, pool, Self::deduct_rewards(who, pool, rewards));
+
+		// already deducted when parked, paid out of the pool they were earned in
+		for ((earned_pool, reward_currency), reward) in PendingRewards::<T>::take(pool, who) {
+			T::Handler::payout(who, &earned_pool, reward_currency, reward);
+		}
+	}
+}
+
+/// Sync the amounts of the `GetLockId` locks on `GetCurrencyId` into the
+/// shares of accounts in the `GetPoolId` pool, to be used as the
+/// `OnLockChanged` handler of orml-tokens, so locking the currency stakes it
+/// in the pool.
+pub struct LockedBalanceAsShare<T, GetPoolId, GetCurrencyId, GetLockId>(
+	PhantomData<(T, GetPoolId, GetCurrencyId, GetLockId)>,
+);
+
+impl<T, CurrencyId, Balance, GetPoolId, GetCurrencyId, GetLockId> OnLockChanged<T::AccountId, CurrencyId, Balance>
+	for LockedBalanceAsShare<T, GetPoolId, GetCurrencyId, GetLockId>
+where
+	T: Config,
+	CurrencyId: PartialEq,
+	Balance: UniqueSaturatedInto<u128>,
+	GetPoolId: Get<T::PoolId>,
+	GetCurrencyId: Get<CurrencyId>,
+	GetLockId: Get<LockIdentifier>,
+{
+	fn on_lock_changed(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		lock_id: LockIdentifier,
+		amount: Balance,
+	) -> Weight {
+		if currency_id == GetCurrencyId::get() && lock_id == GetLockId::get() {
+			let pool = GetPoolId::get();
+			let weight = Pallet::<T>::set_share_weight(&pool);
+			let amount: u128 = amount.unique_saturated_into();
+			Pallet::<T>::set_share(who, &pool, amount.saturated_into());
+			weight
+		} else {
+			0
+		}
 	}
 }
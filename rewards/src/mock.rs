@@ -78,12 +78,60 @@ impl RewardHandler<AccountId, CurrencyId> for Handler {
 	}
 }
 
+thread_local! {
+	pub static RECEIVED_DEDUCTION: RefCell<HashMap<(PoolId, AccountId, CurrencyId), Balance>> = RefCell::new(HashMap::new());
+}
+
+pub struct OnDeduction;
+impl OnRewardDeduction<AccountId, PoolId, CurrencyId, Balance> for OnDeduction {
+	fn on_reward_deduction(who: &AccountId, pool: &PoolId, currency_id: CurrencyId, amount: Balance) {
+		RECEIVED_DEDUCTION.with(|v| {
+			*v.borrow_mut().entry((*pool, *who, currency_id)).or_default() += amount;
+		});
+	}
+}
+
+thread_local! {
+	pub static SHARE_MULTIPLIERS: RefCell<HashMap<(PoolId, AccountId), FixedU128>> = RefCell::new(HashMap::new());
+}
+
+pub struct MockShareBoost;
+impl ShareBoost<AccountId, PoolId> for MockShareBoost {
+	fn share_multiplier(who: &AccountId, pool: &PoolId) -> FixedU128 {
+		SHARE_MULTIPLIERS.with(|v| {
+			v.borrow()
+				.get(&(*pool, *who))
+				.copied()
+				.unwrap_or_else(|| FixedU128::saturating_from_integer(1))
+		})
+	}
+}
+
+pub fn set_share_multiplier(who: AccountId, pool: PoolId, multiplier: FixedU128) {
+	SHARE_MULTIPLIERS.with(|v| v.borrow_mut().insert((pool, who), multiplier));
+}
+
+parameter_types! {
+	pub const SnapshotPeriod: BlockNumber = 10;
+	pub const MaxSnapshots: u32 = 3;
+	pub const MaxSnapshotsPerBlock: u32 = 1;
+	pub const BlocksPerYear: BlockNumber = 100;
+	pub const MaxEmissionSchedules: u32 = 2;
+}
+
 impl Config for Runtime {
 	type Share = Share;
 	type Balance = Balance;
 	type PoolId = PoolId;
 	type CurrencyId = CurrencyId;
 	type Handler = Handler;
+	type OnDeduction = OnDeduction;
+	type ShareBoost = MockShareBoost;
+	type SnapshotPeriod = SnapshotPeriod;
+	type MaxSnapshots = MaxSnapshots;
+	type MaxSnapshotsPerBlock = MaxSnapshotsPerBlock;
+	type BlocksPerYear = BlocksPerYear;
+	type MaxEmissionSchedules = MaxEmissionSchedules;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -507,3 +507,741 @@ fn share_to_zero_removes_storage() {
 		assert_eq!(SharesAndWithdrawnRewards::<Runtime>::contains_key(DOT_POOL, BOB), false);
 	});
 }
+
+#[test]
+fn claim_rewards_should_pay_all_reward_currencies() {
+	ExtBuilder::default().build().execute_with(|| {
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		RewardsModule::add_share(&BOB, &DOT_POOL, 100);
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 1_000));
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, STABLE_COIN, 400));
+
+		RewardsModule::claim_rewards(&ALICE, &DOT_POOL);
+		assert_eq!(
+			RewardsModule::pool_infos(DOT_POOL),
+			PoolInfo {
+				total_shares: 200,
+				rewards: vec![(NATIVE_COIN, (1_000, 500)), (STABLE_COIN, (400, 200))]
+					.into_iter()
+					.collect(),
+			}
+		);
+		assert_eq!(
+			RewardsModule::shares_and_withdrawn_rewards(DOT_POOL, ALICE),
+			(100, vec![(NATIVE_COIN, 500), (STABLE_COIN, 200)].into_iter().collect())
+		);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			500
+		);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, STABLE_COIN)).unwrap_or(&0)),
+			200
+		);
+	});
+}
+
+#[test]
+fn claim_rewards_should_redistribute_deduction() {
+	ExtBuilder::default().build().execute_with(|| {
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		RewardsModule::add_share(&BOB, &DOT_POOL, 100);
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 1_000));
+		RewardsModule::set_claim_deduction(
+			&DOT_POOL,
+			Some(ClaimDeduction {
+				rate: Permill::from_percent(20),
+				destination: DeductionDestination::Redistribute,
+			}),
+		);
+
+		RewardsModule::claim_rewards(&ALICE, &DOT_POOL);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			400
+		);
+		assert_eq!(
+			RewardsModule::pool_infos(DOT_POOL),
+			PoolInfo {
+				total_shares: 200,
+				rewards: vec![(NATIVE_COIN, (1_200, 600))].into_iter().collect(),
+			}
+		);
+		assert_eq!(
+			RewardsModule::shares_and_withdrawn_rewards(DOT_POOL, ALICE),
+			(100, vec![(NATIVE_COIN, 600)].into_iter().collect())
+		);
+
+		// ALICE earns nothing of its own deduction, it all goes to BOB
+		RewardsModule::set_claim_deduction(&DOT_POOL, None);
+		RewardsModule::remove_share(&ALICE, &DOT_POOL, 100);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			400
+		);
+		RewardsModule::claim_rewards(&BOB, &DOT_POOL);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, BOB, NATIVE_COIN)).unwrap_or(&0)),
+			600
+		);
+	});
+}
+
+#[test]
+fn redistribute_deduction_without_other_shares_should_go_to_handler() {
+	ExtBuilder::default().build().execute_with(|| {
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 1_000));
+		RewardsModule::set_claim_deduction(
+			&DOT_POOL,
+			Some(ClaimDeduction {
+				rate: Permill::from_percent(20),
+				destination: DeductionDestination::Redistribute,
+			}),
+		);
+
+		RewardsModule::claim_rewards(&ALICE, &DOT_POOL);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			800
+		);
+		assert_eq!(
+			RECEIVED_DEDUCTION.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			200
+		);
+		assert_eq!(
+			RewardsModule::pool_infos(DOT_POOL),
+			PoolInfo {
+				total_shares: 100,
+				rewards: vec![(NATIVE_COIN, (1_000, 1_000))].into_iter().collect(),
+			}
+		);
+	});
+}
+
+#[test]
+fn claim_rewards_should_route_deduction_to_handler() {
+	ExtBuilder::default().build().execute_with(|| {
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 1_000));
+		RewardsModule::set_claim_deduction(
+			&DOT_POOL,
+			Some(ClaimDeduction {
+				rate: Permill::from_percent(10),
+				destination: DeductionDestination::Handler,
+			}),
+		);
+
+		RewardsModule::claim_rewards(&ALICE, &DOT_POOL);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			900
+		);
+		assert_eq!(
+			RECEIVED_DEDUCTION.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			100
+		);
+		assert_eq!(
+			RewardsModule::pool_infos(DOT_POOL),
+			PoolInfo {
+				total_shares: 100,
+				rewards: vec![(NATIVE_COIN, (1_000, 1_000))].into_iter().collect(),
+			}
+		);
+	});
+}
+
+#[test]
+fn withdraw_share_should_deduct_settled_rewards() {
+	ExtBuilder::default().build().execute_with(|| {
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		RewardsModule::add_share(&BOB, &DOT_POOL, 100);
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 1_000));
+		RewardsModule::set_claim_deduction(
+			&DOT_POOL,
+			Some(ClaimDeduction {
+				rate: Permill::from_percent(20),
+				destination: DeductionDestination::Handler,
+			}),
+		);
+
+		// 250 of the 500 pending rewards of ALICE are settled
+		RewardsModule::withdraw_share(&ALICE, &DOT_POOL, 50);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			200
+		);
+		assert_eq!(
+			RECEIVED_DEDUCTION.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			50
+		);
+	});
+}
+
+#[test]
+fn emission_schedules_should_be_capped() {
+	ExtBuilder::default().build().execute_with(|| {
+		let other_pool: PoolId = 2;
+		let schedule = EmissionSchedule {
+			per_block: 100,
+			decay: Some(EmissionDecay {
+				period: 1,
+				rate: Permill::from_percent(100),
+				next_decay_at: 2,
+			}),
+		};
+		assert_ok!(RewardsModule::set_emission_schedule(
+			&DOT_POOL,
+			NATIVE_COIN,
+			Some(schedule)
+		));
+		assert_ok!(RewardsModule::set_emission_schedule(
+			&DOT_POOL,
+			STABLE_COIN,
+			Some(schedule)
+		));
+		assert_noop!(
+			RewardsModule::set_emission_schedule(&other_pool, NATIVE_COIN, Some(schedule)),
+			Error::<Runtime>::TooManyEmissionSchedules
+		);
+
+		// replacing a schedule doesn't count
+		assert_ok!(RewardsModule::set_emission_schedule(
+			&DOT_POOL,
+			NATIVE_COIN,
+			Some(schedule)
+		));
+		assert_eq!(RewardsModule::emission_schedule_count(), 2);
+
+		assert_ok!(RewardsModule::set_emission_schedule(&DOT_POOL, STABLE_COIN, None));
+		assert_eq!(RewardsModule::emission_schedule_count(), 1);
+		assert_ok!(RewardsModule::set_emission_schedule(
+			&other_pool,
+			NATIVE_COIN,
+			Some(schedule)
+		));
+		assert_eq!(RewardsModule::emission_schedule_count(), 2);
+
+		// schedules decayed to zero are removed
+		RewardsModule::on_initialize(2);
+		assert_eq!(RewardsModule::emission_schedules(DOT_POOL, NATIVE_COIN), None);
+		assert_eq!(RewardsModule::emission_schedule_count(), 0);
+	});
+}
+
+#[test]
+fn emission_schedule_should_accumulate_rewards() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(RewardsModule::set_emission_schedule(
+			&DOT_POOL,
+			NATIVE_COIN,
+			Some(EmissionSchedule {
+				per_block: 100,
+				decay: Some(EmissionDecay {
+					period: 2,
+					rate: Permill::from_percent(50),
+					next_decay_at: 2,
+				}),
+			}),
+		));
+
+		// pool has no shares yet
+		RewardsModule::on_initialize(1);
+		assert_eq!(RewardsModule::pool_infos(DOT_POOL), Default::default());
+
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		RewardsModule::on_initialize(2);
+		assert_eq!(
+			RewardsModule::pool_infos(DOT_POOL),
+			PoolInfo {
+				total_shares: 100,
+				rewards: vec![(NATIVE_COIN, (100, 0))].into_iter().collect(),
+			}
+		);
+		// halved
+		assert_eq!(
+			RewardsModule::emission_schedules(DOT_POOL, NATIVE_COIN),
+			Some(EmissionSchedule {
+				per_block: 50,
+				decay: Some(EmissionDecay {
+					period: 2,
+					rate: Permill::from_percent(50),
+					next_decay_at: 4,
+				}),
+			})
+		);
+
+		RewardsModule::on_initialize(3);
+		assert_eq!(
+			RewardsModule::pool_infos(DOT_POOL),
+			PoolInfo {
+				total_shares: 100,
+				rewards: vec![(NATIVE_COIN, (150, 0))].into_iter().collect(),
+			}
+		);
+
+		assert_ok!(RewardsModule::set_emission_schedule(&DOT_POOL, NATIVE_COIN, None));
+		RewardsModule::on_initialize(4);
+		assert_eq!(
+			RewardsModule::pool_infos(DOT_POOL),
+			PoolInfo {
+				total_shares: 100,
+				rewards: vec![(NATIVE_COIN, (150, 0))].into_iter().collect(),
+			}
+		);
+	});
+}
+
+#[test]
+fn withdraw_share_should_carry_forward_pending_rewards() {
+	ExtBuilder::default().build().execute_with(|| {
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		RewardsModule::add_share(&BOB, &DOT_POOL, 100);
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 1_000));
+
+		// ALICE has 500 pending rewards, the remaining 50 share carries them
+		RewardsModule::withdraw_share(&ALICE, &DOT_POOL, 50);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			250
+		);
+		assert_eq!(
+			RewardsModule::pool_infos(DOT_POOL),
+			PoolInfo {
+				total_shares: 150,
+				rewards: vec![(NATIVE_COIN, (750, 0))].into_iter().collect(),
+			}
+		);
+		assert_eq!(
+			RewardsModule::shares_and_withdrawn_rewards(DOT_POOL, ALICE),
+			(50, Default::default())
+		);
+
+		RewardsModule::claim_rewards(&ALICE, &DOT_POOL);
+		RewardsModule::claim_rewards(&BOB, &DOT_POOL);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			500
+		);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, BOB, NATIVE_COIN)).unwrap_or(&0)),
+			500
+		);
+
+		// claimed rewards are carried forward without settlement
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 300));
+		RewardsModule::withdraw_share(&BOB, &DOT_POOL, 50);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, BOB, NATIVE_COIN)).unwrap_or(&0)),
+			500
+		);
+		RewardsModule::claim_rewards(&BOB, &DOT_POOL);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, BOB, NATIVE_COIN)).unwrap_or(&0)),
+			700
+		);
+	});
+}
+
+#[test]
+fn withdraw_share_should_conserve_total_rewards() {
+	ExtBuilder::default().build().execute_with(|| {
+		let accounts = [ALICE, BOB, CAROL];
+		let total_payout = || {
+			RECEIVED_PAYOUT.with(|v| {
+				v.borrow()
+					.iter()
+					.filter(|((pool, _, currency_id), _)| *pool == DOT_POOL && *currency_id == NATIVE_COIN)
+					.map(|(_, amount)| *amount)
+					.sum::<Balance>()
+			})
+		};
+		let unclaimed = || {
+			RewardsModule::pool_infos(DOT_POOL)
+				.rewards
+				.get(&NATIVE_COIN)
+				.map(|(total_reward, total_withdrawn_reward)| total_reward - total_withdrawn_reward)
+				.unwrap_or_default()
+		};
+
+		// ALICE keeps a share so the pool is never removed
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 1_000);
+		let mut accumulated: Balance = 0;
+		let mut seed: u64 = 42;
+		let ops = 500;
+		for _ in 0..ops {
+			seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+			let who = &accounts[1 + (seed >> 33) as usize % 2];
+			let amount = (seed >> 40) % 1_000 + 1;
+			match (seed >> 20) % 5 {
+				0 => RewardsModule::add_share(who, &DOT_POOL, amount),
+				1 => RewardsModule::withdraw_share(who, &DOT_POOL, amount),
+				2 => RewardsModule::remove_share(who, &DOT_POOL, amount),
+				3 => RewardsModule::claim_rewards(who, &DOT_POOL),
+				_ => {
+					assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, amount));
+					accumulated += amount;
+				}
+			}
+			assert_eq!(total_payout() + unclaimed(), accumulated);
+		}
+
+		for who in accounts.iter() {
+			RewardsModule::claim_rewards(who, &DOT_POOL);
+		}
+		assert_eq!(total_payout() + unclaimed(), accumulated);
+		// only rounding dust is left
+		assert!(unclaimed() <= ops);
+	});
+}
+
+#[test]
+fn pool_apr_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+
+		// not a snapshot block
+		RewardsModule::on_initialize(5);
+		assert_eq!(RewardsModule::pool_snapshots(DOT_POOL).into_inner(), vec![]);
+
+		System::set_block_number(10);
+		RewardsModule::on_initialize(10);
+		assert_eq!(
+			RewardsModule::pool_snapshots(DOT_POOL).into_inner(),
+			vec![PoolSnapshot {
+				taken_at: 10,
+				total_shares: 100,
+				accumulated_rewards: Default::default(),
+			}]
+		);
+		assert_eq!(RewardsModule::pool_apr(&DOT_POOL, 10), vec![]);
+
+		System::set_block_number(20);
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 50));
+		RewardsModule::claim_rewards(&ALICE, &DOT_POOL);
+		assert_eq!(RewardsModule::accumulated_rewards(DOT_POOL, NATIVE_COIN), 50);
+		// 50 rewards per 100 shares over 10 blocks, 100 blocks per year
+		assert_eq!(
+			RewardsModule::pool_apr(&DOT_POOL, 10),
+			vec![(NATIVE_COIN, FixedU128::saturating_from_integer(5))]
+		);
+		// no snapshot within window
+		assert_eq!(RewardsModule::pool_apr(&DOT_POOL, 5), vec![]);
+
+		RewardsModule::on_initialize(20);
+		RewardsModule::on_initialize(30);
+		RewardsModule::on_initialize(40);
+		let snapshots = RewardsModule::pool_snapshots(DOT_POOL);
+		assert_eq!(snapshots.len(), 3);
+		assert_eq!(snapshots[0].taken_at, 20);
+		assert_eq!(
+			snapshots[0].accumulated_rewards,
+			vec![(NATIVE_COIN, 50)].into_iter().collect()
+		);
+	});
+}
+
+#[test]
+fn snapshots_should_be_taken_in_bounded_steps() {
+	ExtBuilder::default().build().execute_with(|| {
+		let other_pool: PoolId = 2;
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		RewardsModule::add_share(&ALICE, &other_pool, 100);
+
+		// one pool per block
+		RewardsModule::on_initialize(10);
+		assert_eq!(
+			RewardsModule::pool_snapshots(DOT_POOL).len() + RewardsModule::pool_snapshots(other_pool).len(),
+			1
+		);
+		assert!(RewardsModule::snapshot_progress().is_some());
+
+		RewardsModule::on_initialize(11);
+		assert_eq!(RewardsModule::pool_snapshots(DOT_POOL).len(), 1);
+		assert_eq!(RewardsModule::pool_snapshots(other_pool).len(), 1);
+		RewardsModule::on_initialize(12);
+		assert_eq!(RewardsModule::snapshot_progress(), None);
+	});
+}
+
+#[test]
+fn boosted_share_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_share_multiplier(ALICE, DOT_POOL, FixedU128::saturating_from_rational(3, 2));
+
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		RewardsModule::add_share(&BOB, &DOT_POOL, 150);
+		assert_eq!(RewardsModule::shares_and_withdrawn_rewards(DOT_POOL, ALICE).0, 150);
+		assert_eq!(RewardsModule::base_share(&ALICE, &DOT_POOL), 100);
+		assert_eq!(BaseShares::<Runtime>::get(DOT_POOL, ALICE), Some(100));
+		assert_eq!(RewardsModule::base_share(&BOB, &DOT_POOL), 150);
+		assert_eq!(BaseShares::<Runtime>::get(DOT_POOL, BOB), None);
+		assert_eq!(RewardsModule::pool_infos(DOT_POOL).total_shares, 300);
+
+		// boosted share earns boosted rewards
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 600));
+		RewardsModule::claim_rewards(&ALICE, &DOT_POOL);
+		RewardsModule::claim_rewards(&BOB, &DOT_POOL);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			300
+		);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, BOB, NATIVE_COIN)).unwrap_or(&0)),
+			300
+		);
+
+		RewardsModule::remove_share(&ALICE, &DOT_POOL, 50);
+		assert_eq!(RewardsModule::shares_and_withdrawn_rewards(DOT_POOL, ALICE).0, 75);
+		assert_eq!(RewardsModule::base_share(&ALICE, &DOT_POOL), 50);
+
+		RewardsModule::set_share(&ALICE, &DOT_POOL, 200);
+		assert_eq!(RewardsModule::shares_and_withdrawn_rewards(DOT_POOL, ALICE).0, 300);
+		assert_eq!(RewardsModule::base_share(&ALICE, &DOT_POOL), 200);
+
+		RewardsModule::remove_share(&ALICE, &DOT_POOL, 200);
+		assert_eq!(
+			RewardsModule::shares_and_withdrawn_rewards(DOT_POOL, ALICE),
+			Default::default()
+		);
+		assert_eq!(BaseShares::<Runtime>::get(DOT_POOL, ALICE), None);
+		assert_eq!(RewardsModule::pool_infos(DOT_POOL).total_shares, 150);
+	});
+}
+
+#[test]
+fn update_share_boost_should_conserve_rewards() {
+	ExtBuilder::default().build().execute_with(|| {
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		RewardsModule::add_share(&BOB, &DOT_POOL, 100);
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 1_000));
+
+		// the pending rewards are kept when the boost applies
+		set_share_multiplier(ALICE, DOT_POOL, FixedU128::saturating_from_integer(2));
+		RewardsModule::update_share_boost(&ALICE, &DOT_POOL);
+		assert_eq!(RewardsModule::shares_and_withdrawn_rewards(DOT_POOL, ALICE).0, 200);
+		assert_eq!(RewardsModule::base_share(&ALICE, &DOT_POOL), 100);
+		assert_eq!(RewardsModule::pool_infos(DOT_POOL).total_shares, 300);
+
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 3_000));
+		RewardsModule::claim_rewards(&ALICE, &DOT_POOL);
+		RewardsModule::claim_rewards(&BOB, &DOT_POOL);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			2_500
+		);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, BOB, NATIVE_COIN)).unwrap_or(&0)),
+			1_500
+		);
+
+		// the boost ends
+		set_share_multiplier(ALICE, DOT_POOL, FixedU128::saturating_from_integer(1));
+		RewardsModule::update_share_boost(&ALICE, &DOT_POOL);
+		assert_eq!(RewardsModule::shares_and_withdrawn_rewards(DOT_POOL, ALICE).0, 100);
+		assert_eq!(BaseShares::<Runtime>::get(DOT_POOL, ALICE), None);
+		assert_eq!(RewardsModule::pool_infos(DOT_POOL).total_shares, 200);
+	});
+}
+
+#[test]
+fn pause_pool_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		RewardsModule::add_share(&BOB, &DOT_POOL, 100);
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 1_000));
+
+		RewardsModule::pause_pool(&DOT_POOL);
+		assert!(RewardsModule::is_paused(&DOT_POOL));
+		assert_noop!(
+			RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 1_000),
+			Error::<Runtime>::PoolPaused
+		);
+		assert_ok!(RewardsModule::set_emission_schedule(
+			&DOT_POOL,
+			NATIVE_COIN,
+			Some(EmissionSchedule {
+				per_block: 100,
+				decay: None,
+			}),
+		));
+		RewardsModule::on_initialize(1);
+		assert_eq!(
+			RewardsModule::pool_infos(DOT_POOL).rewards,
+			vec![(NATIVE_COIN, (1_000, 0))].into_iter().collect()
+		);
+
+		RewardsModule::claim_rewards(&ALICE, &DOT_POOL);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			0
+		);
+
+		// the rewards of removed shares are kept pending
+		RewardsModule::remove_share(&BOB, &DOT_POOL, 100);
+		assert_eq!(
+			RewardsModule::pending_rewards(DOT_POOL, BOB),
+			vec![((DOT_POOL, NATIVE_COIN), 500)].into_iter().collect()
+		);
+		assert_eq!(
+			RewardsModule::pool_infos(DOT_POOL),
+			PoolInfo {
+				total_shares: 100,
+				rewards: vec![(NATIVE_COIN, (500, 0))].into_iter().collect()
+			}
+		);
+
+		// as well as the settled rewards of withdrawn shares
+		RewardsModule::withdraw_share(&ALICE, &DOT_POOL, 50);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			0
+		);
+		assert_eq!(
+			RewardsModule::pending_rewards(DOT_POOL, ALICE),
+			vec![((DOT_POOL, NATIVE_COIN), 250)].into_iter().collect()
+		);
+
+		RewardsModule::resume_pool(&DOT_POOL);
+		RewardsModule::claim_rewards(&ALICE, &DOT_POOL);
+		RewardsModule::claim_rewards(&BOB, &DOT_POOL);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			500
+		);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, BOB, NATIVE_COIN)).unwrap_or(&0)),
+			500
+		);
+		assert_eq!(RewardsModule::pending_rewards(DOT_POOL, BOB), Default::default());
+	});
+}
+
+#[test]
+fn migrate_shares_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		const NEW_POOL: PoolId = 2;
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		RewardsModule::add_share(&BOB, &DOT_POOL, 300);
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 400));
+
+		assert_noop!(
+			RewardsModule::migrate_shares(&DOT_POOL, &NEW_POOL, 1),
+			Error::<Runtime>::InvalidMigration
+		);
+		RewardsModule::pause_pool(&DOT_POOL);
+		assert_noop!(
+			RewardsModule::migrate_shares(&DOT_POOL, &DOT_POOL, 1),
+			Error::<Runtime>::InvalidMigration
+		);
+
+		assert_eq!(RewardsModule::migrate_shares(&DOT_POOL, &NEW_POOL, 1), Ok(false));
+		assert_eq!(RewardsModule::migrate_shares(&DOT_POOL, &NEW_POOL, 1), Ok(true));
+
+		assert_eq!(RewardsModule::pool_infos(DOT_POOL), Default::default());
+		assert_eq!(
+			RewardsModule::pool_infos(NEW_POOL),
+			PoolInfo {
+				total_shares: 400,
+				..Default::default()
+			}
+		);
+		assert_eq!(
+			RewardsModule::pending_rewards(NEW_POOL, ALICE),
+			vec![((DOT_POOL, NATIVE_COIN), 100)].into_iter().collect()
+		);
+		assert_eq!(
+			RewardsModule::pending_rewards(NEW_POOL, BOB),
+			vec![((DOT_POOL, NATIVE_COIN), 300)].into_iter().collect()
+		);
+
+		// paid out of the pool the rewards were earned in
+		RewardsModule::claim_rewards(&ALICE, &NEW_POOL);
+		RewardsModule::claim_rewards(&BOB, &NEW_POOL);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			100
+		);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, BOB, NATIVE_COIN)).unwrap_or(&0)),
+			300
+		);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(NEW_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			0
+		);
+	});
+}
+
+#[test]
+fn migrate_shares_should_apply_claim_deduction() {
+	ExtBuilder::default().build().execute_with(|| {
+		const NEW_POOL: PoolId = 2;
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 1_000));
+		RewardsModule::set_claim_deduction(
+			&DOT_POOL,
+			Some(ClaimDeduction {
+				rate: Permill::from_percent(10),
+				destination: DeductionDestination::Handler,
+			}),
+		);
+
+		RewardsModule::pause_pool(&DOT_POOL);
+		assert_eq!(RewardsModule::migrate_shares(&DOT_POOL, &NEW_POOL, 1), Ok(true));
+		assert_eq!(
+			RECEIVED_DEDUCTION.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			100
+		);
+		assert_eq!(
+			RewardsModule::pending_rewards(NEW_POOL, ALICE),
+			vec![((DOT_POOL, NATIVE_COIN), 900)].into_iter().collect()
+		);
+
+		// not deducted again by the new pool
+		RewardsModule::set_claim_deduction(
+			&NEW_POOL,
+			Some(ClaimDeduction {
+				rate: Permill::from_percent(50),
+				destination: DeductionDestination::Handler,
+			}),
+		);
+		RewardsModule::claim_rewards(&ALICE, &NEW_POOL);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			900
+		);
+		assert_eq!(
+			RECEIVED_DEDUCTION.with(|v| *v.borrow().get(&(NEW_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			0
+		);
+	});
+}
+
+#[test]
+fn locked_balance_as_share_should_work() {
+	frame_support::parameter_types! {
+		pub const LockPool: PoolId = DOT_POOL;
+		pub const LockCurrency: CurrencyId = NATIVE_COIN;
+		pub const StakingLockId: LockIdentifier = *b"staking ";
+	}
+	type LockedShare = LockedBalanceAsShare<Runtime, LockPool, LockCurrency, StakingLockId>;
+
+	ExtBuilder::default().build().execute_with(|| {
+		LockedShare::on_lock_changed(&ALICE, NATIVE_COIN, *b"staking ", 100u64);
+		assert_eq!(RewardsModule::shares_and_withdrawn_rewards(DOT_POOL, ALICE).0, 100);
+		assert_eq!(RewardsModule::pool_infos(DOT_POOL).total_shares, 100);
+
+		// other locks and currencies are ignored
+		LockedShare::on_lock_changed(&ALICE, STABLE_COIN, *b"staking ", 500u64);
+		LockedShare::on_lock_changed(&ALICE, NATIVE_COIN, *b"vesting ", 500u64);
+		assert_eq!(RewardsModule::shares_and_withdrawn_rewards(DOT_POOL, ALICE).0, 100);
+
+		LockedShare::on_lock_changed(&ALICE, NATIVE_COIN, *b"staking ", 40u64);
+		assert_eq!(RewardsModule::shares_and_withdrawn_rewards(DOT_POOL, ALICE).0, 40);
+
+		// removed lock
+		LockedShare::on_lock_changed(&ALICE, NATIVE_COIN, *b"staking ", 0u64);
+		assert_eq!(RewardsModule::pool_infos(DOT_POOL).total_shares, 0);
+	});
+}
@@ -0,0 +1,34 @@
+//! Runtime API definition for tokens module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_std::prelude::Vec;
+
+pub use orml_tokens::{BalanceLock, ReserveData};
+
+sp_api::decl_runtime_apis! {
+	pub trait TokensApi<AccountId, CurrencyId, Balance, ReserveIdentifier> where
+		AccountId: Codec,
+		CurrencyId: Codec,
+		Balance: Codec,
+		ReserveIdentifier: Codec,
+	{
+		/// The locks of `who` in `currency_id`, the lock identifiers are the
+		/// reasons of the locks.
+		fn locks(who: AccountId, currency_id: CurrencyId) -> Vec<BalanceLock<Balance>>;
+
+		/// The named reserves of `who` in `currency_id`.
+		fn reserves(who: AccountId, currency_id: CurrencyId) -> Vec<ReserveData<ReserveIdentifier, Balance>>;
+
+		/// The locks of `who` in each currency.
+		fn all_locks(who: AccountId) -> Vec<(CurrencyId, Vec<BalanceLock<Balance>>)>;
+
+		/// The named reserves of `who` in each currency.
+		fn all_reserves(who: AccountId) -> Vec<(CurrencyId, Vec<ReserveData<ReserveIdentifier, Balance>>)>;
+	}
+}
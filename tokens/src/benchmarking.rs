@@ -0,0 +1,108 @@
+//! Benchmarks for the tokens module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_runtime::traits::One;
+
+const SEED: u32 = 0;
+/// The benchmarked currency, expected to have a non-zero existential deposit
+/// so dust can be removed.
+const CURRENCY: u32 = 1;
+
+fn existential_deposit<T: Config>(currency_id: T::CurrencyId) -> T::Balance {
+	T::ExistentialDeposits::get(&currency_id).max(One::one())
+}
+
+fn funded_account<T: Config>(currency_id: T::CurrencyId) -> Result<(T::AccountId, T::Balance), &'static str> {
+	let who: T::AccountId = whitelisted_caller();
+	let amount = existential_deposit::<T>(currency_id).saturating_mul(100u32.into());
+	<Pallet<T> as MultiCurrency<T::AccountId>>::deposit(currency_id, &who, amount)?;
+	Ok((who, amount))
+}
+
+benchmarks! {
+	where_clause { where T::CurrencyId: From<u32> }
+
+	// a new recipient account is created
+	transfer {
+		let currency_id: T::CurrencyId = CURRENCY.into();
+		let (from, amount) = funded_account::<T>(currency_id)?;
+		let to: T::AccountId = account("to", 0, SEED);
+	}: _(RawOrigin::Signed(from), T::Lookup::unlookup(to.clone()), currency_id, amount / 2u32.into())
+	verify {
+		assert_eq!(<Pallet<T> as MultiCurrency<T::AccountId>>::free_balance(currency_id, &to), amount / 2u32.into());
+	}
+
+	transfer_all {
+		let currency_id: T::CurrencyId = CURRENCY.into();
+		let (from, amount) = funded_account::<T>(currency_id)?;
+		let to: T::AccountId = account("to", 0, SEED);
+	}: _(RawOrigin::Signed(from.clone()), T::Lookup::unlookup(to.clone()), currency_id, false)
+	verify {
+		assert!(!Accounts::<T>::contains_key(&from, currency_id));
+		assert_eq!(<Pallet<T> as MultiCurrency<T::AccountId>>::free_balance(currency_id, &to), amount);
+	}
+
+	transfer_keep_alive {
+		let currency_id: T::CurrencyId = CURRENCY.into();
+		let (from, amount) = funded_account::<T>(currency_id)?;
+		let to: T::AccountId = account("to", 0, SEED);
+	}: _(RawOrigin::Signed(from), T::Lookup::unlookup(to.clone()), currency_id, amount / 2u32.into())
+	verify {
+		assert_eq!(<Pallet<T> as MultiCurrency<T::AccountId>>::free_balance(currency_id, &to), amount / 2u32.into());
+	}
+
+	force_transfer {
+		let currency_id: T::CurrencyId = CURRENCY.into();
+		let (from, amount) = funded_account::<T>(currency_id)?;
+		let to: T::AccountId = account("to", 0, SEED);
+	}: _(RawOrigin::Root, T::Lookup::unlookup(from), T::Lookup::unlookup(to.clone()), currency_id, amount / 2u32.into())
+	verify {
+		assert_eq!(<Pallet<T> as MultiCurrency<T::AccountId>>::free_balance(currency_id, &to), amount / 2u32.into());
+	}
+
+	// the part of a transfer spent on creating the recipient account
+	create_account {
+		let currency_id: T::CurrencyId = CURRENCY.into();
+		let who: T::AccountId = account("who", 0, SEED);
+		let amount = existential_deposit::<T>(currency_id);
+	}: {
+		Pallet::<T>::set_free_balance(currency_id, &who, amount);
+	}
+	verify {
+		assert!(Accounts::<T>::contains_key(&who, currency_id));
+	}
+
+	// the part of a transfer spent on removing the dust of the sender
+	remove_dust {
+		let currency_id: T::CurrencyId = CURRENCY.into();
+		let who: T::AccountId = account("who", 0, SEED);
+		let amount = existential_deposit::<T>(currency_id);
+		<Pallet<T> as MultiCurrency<T::AccountId>>::deposit(currency_id, &who, amount)?;
+	}: {
+		Pallet::<T>::set_free_balance(currency_id, &who, amount.saturating_sub(One::one()));
+	}
+	verify {
+		if !T::ExistentialDeposits::get(&currency_id).is_zero() {
+			assert!(!Accounts::<T>::contains_key(&who, currency_id));
+		}
+	}
+
+	// all locks share an id, so they are merged into one
+	consolidate_locks {
+		let l in 1 .. T::MaxLocks::get();
+
+		let currency_id: T::CurrencyId = CURRENCY.into();
+		let (who, amount) = funded_account::<T>(currency_id)?;
+		let locks = (0..l).map(|_| BalanceLock { id: *b"orml/bnc", amount }).collect::<Vec<_>>();
+		Pallet::<T>::update_locks(currency_id, &who, &locks)?;
+	}: _(RawOrigin::Signed(who.clone()), currency_id)
+	verify {
+		assert_eq!(Pallet::<T>::locks(&who, currency_id).len(), 1);
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::default().build(), crate::mock::Runtime);
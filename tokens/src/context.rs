@@ -0,0 +1,61 @@
+//! Deferred hooks of account mutations.
+//!
+//! The hooks called on account mutations, e.g. `OnDust` and
+//! `OnKilledTokenAccount`, could reenter the tokens module. While nested
+//! mutations are in progress, e.g. of both accounts of a transfer, the outer
+//! account isn't written yet, and a reentrant change to it would be
+//! overwritten. So the hooks are collected in the `MutateContext` and run once
+//! the outermost mutation completes.
+
+use sp_std::{boxed::Box, vec::Vec};
+
+environmental::environmental!(context: MutateContext);
+
+/// The hooks deferred by the account mutations in progress.
+#[derive(Default)]
+pub struct MutateContext {
+	hooks: Vec<Box<dyn FnOnce()>>,
+}
+
+impl MutateContext {
+	/// Run `f` deferring the hooks, then run them if `f` succeeded. If
+	/// already in a context, the hooks are left to the outermost one.
+	pub(crate) fn with<R, E>(f: impl FnOnce() -> Result<R, E>) -> Result<R, E> {
+		if let Some(len) = context::with(|ctx| ctx.hooks.len()) {
+			let result = f();
+			if result.is_err() {
+				// drop the hooks of the failed mutations
+				context::with(|ctx| ctx.hooks.truncate(len));
+			}
+			return result;
+		}
+
+		let mut ctx = MutateContext::default();
+		let result = context::using(&mut ctx, f);
+		if result.is_ok() {
+			for hook in ctx.hooks {
+				hook();
+			}
+		}
+		result
+	}
+
+	/// Defer `hook` to the current context, or run it right away if not in
+	/// any.
+	pub(crate) fn defer(hook: impl FnOnce() + 'static) {
+		let mut hook = Some(hook);
+		context::with(|ctx| {
+			if let Some(hook) = hook.take() {
+				ctx.hooks.push(Box::new(hook));
+			}
+		});
+		if let Some(hook) = hook {
+			hook();
+		}
+	}
+
+	/// Whether in a context, i.e. the hooks are deferred.
+	pub fn is_active() -> bool {
+		context::with(|_| ()).is_some()
+	}
+}
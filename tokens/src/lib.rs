@@ -29,16 +29,40 @@
 //! - `transfer` - Transfer some balance to another account.
 //! - `transfer_all` - Transfer all balance to another account.
 //!
+//! ### Transfer Fees
+//!
+//! A runtime could charge a fee on the transfers of some currencies, a rate
+//! of the transferred amount skimmed from the recipient to a fee account, by
+//! `TransferFee`. Transfers from or to `TransferFeeExemptions` accounts are
+//! free. Set `TransferFee` to `()` to disable transfer fees.
+//!
 //! ### Genesis Config
 //!
 //! The tokens module depends on the `GenesisConfig`. Endowed accounts could be
 //! configured in genesis configs.
+//!
+//! ### Offchain Indexing
+//!
+//! Dust removals and account reaps are written to the offchain index, keyed
+//! by `Pallet::offchain_index_key`, so indexer nodes could reconstruct the
+//! history of small balances without scanning events. The records are only
+//! kept by nodes with offchain indexing enabled.
+//!
+//! ### Deferred Hooks
+//!
+//! The hooks called on balance changes, e.g. `OnDust`,
+//! `OnKilledTokenAccount` and `OnTransfer`, run after the account mutations
+//! complete, so they could safely call back into the tokens module. Callers
+//! making several changes could defer the hooks until all of them are done
+//! with `Pallet::with_deferred_hooks`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 #![allow(clippy::comparison_chain)]
 
+pub use crate::context::MutateContext;
 pub use crate::imbalances::{NegativeImbalance, PositiveImbalance};
+pub use crate::try_state::InvariantViolation;
 
 use codec::MaxEncodedLen;
 use frame_support::{
@@ -48,7 +72,7 @@ use frame_support::{
 		tokens::{fungible, fungibles, DepositConsequence, WithdrawConsequence},
 		BalanceStatus as Status, Contains, Currency as PalletCurrency, ExistenceRequirement, Get, Imbalance,
 		LockableCurrency as PalletLockableCurrency, ReservableCurrency as PalletReservableCurrency, SignedImbalance,
-		WithdrawReasons,
+		StorageVersion, WithdrawReasons,
 	},
 	transactional, BoundedVec,
 };
@@ -59,7 +83,7 @@ use sp_runtime::{
 		AtLeast32BitUnsigned, Bounded, CheckedAdd, CheckedSub, MaybeSerializeDeserialize, Member, Saturating,
 		StaticLookup, Zero,
 	},
-	ArithmeticError, DispatchError, DispatchResult, RuntimeDebug,
+	ArithmeticError, DispatchError, DispatchResult, PerThing, Permill, RuntimeDebug,
 };
 use sp_std::{
 	convert::{Infallible, TryFrom, TryInto},
@@ -71,26 +95,36 @@ use sp_std::{
 use orml_traits::{
 	arithmetic::{self, Signed},
 	currency::TransferAll,
-	BalanceStatus, GetByKey, LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency,
-	MultiReservableCurrency, OnDust,
+	BalanceStatus, GetByKey, Happened, HookWeight, LockIdentifier, MultiCurrency, MultiCurrencyExtended,
+	MultiLockableCurrency, MultiReservableCurrency, NamedMultiReservableCurrency, OnDeposit, OnDust, OnLockChanged,
+	OnSlash, OnTransfer, TryHappened,
 };
 
+mod benchmarking;
+mod context;
 mod imbalances;
 mod impls;
+pub mod migration;
 mod mock;
 mod tests;
+mod try_state;
 mod weights;
 
 pub use impls::*;
 pub use weights::WeightInfo;
 
+/// Prefix of the offchain-indexed records of dust lost, holding the amount.
+pub const OFFCHAIN_DUST_PREFIX: &[u8] = b"orml-tokens/dust/";
+/// Prefix of the offchain-indexed records of reaped accounts, holding `()`.
+pub const OFFCHAIN_REAPED_PREFIX: &[u8] = b"orml-tokens/reaped/";
+
 pub struct TransferDust<T, GetAccountId>(marker::PhantomData<(T, GetAccountId)>);
 impl<T, GetAccountId> OnDust<T::AccountId, T::CurrencyId, T::Balance> for TransferDust<T, GetAccountId>
 where
 	T: Config,
 	GetAccountId: Get<T::AccountId>,
 {
-	fn on_dust(who: &T::AccountId, currency_id: T::CurrencyId, amount: T::Balance) {
+	fn on_dust(who: &T::AccountId, currency_id: T::CurrencyId, amount: T::Balance) -> Weight {
 		// transfer the dust to treasury account, ignore the result,
 		// if failed will leave some dust which still could be recycled.
 		let _ = Pallet::<T>::do_transfer(
@@ -100,15 +134,19 @@ where
 			amount,
 			ExistenceRequirement::AllowDeath,
 		);
+		// included in `WeightInfo::remove_dust`
+		0
 	}
 }
 
 pub struct BurnDust<T>(marker::PhantomData<T>);
 impl<T: Config> OnDust<T::AccountId, T::CurrencyId, T::Balance> for BurnDust<T> {
-	fn on_dust(who: &T::AccountId, currency_id: T::CurrencyId, amount: T::Balance) {
+	fn on_dust(who: &T::AccountId, currency_id: T::CurrencyId, amount: T::Balance) -> Weight {
 		// burn the dust, ignore the result,
 		// if failed will leave some dust which still could be recycled.
 		let _ = Pallet::<T>::do_withdraw(currency_id, who, amount, ExistenceRequirement::AllowDeath, true);
+		// included in `WeightInfo::remove_dust`
+		0
 	}
 }
 
@@ -124,6 +162,15 @@ pub struct BalanceLock<Balance> {
 	pub amount: Balance,
 }
 
+/// Store named reserved balance.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct ReserveData<ReserveIdentifier, Balance> {
+	/// The identifier for the named reserve.
+	pub id: ReserveIdentifier,
+	/// The amount of the named reserve.
+	pub amount: Balance,
+}
+
 /// balance information for an account.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, MaxEncodedLen, RuntimeDebug, TypeInfo)]
 pub struct AccountData<Balance> {
@@ -146,6 +193,15 @@ pub struct AccountData<Balance> {
 	pub frozen: Balance,
 }
 
+/// The funds movements an account is blocked from.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum BlockedStatus {
+	/// Blocked from sending, but could still receive.
+	Outgoing,
+	/// Blocked from sending and receiving.
+	All,
+}
+
 impl<Balance: Saturating + Copy + Ord> AccountData<Balance> {
 	/// The amount that this account's free balance may not be reduced
 	/// beyond.
@@ -159,6 +215,41 @@ impl<Balance: Saturating + Copy + Ord> AccountData<Balance> {
 	}
 }
 
+/// The accounts confiscated from.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum ConfiscationTargets<AccountId> {
+	/// The given accounts.
+	Accounts(Vec<AccountId>),
+	/// All the accounts holding the currency.
+	AllHolders,
+}
+
+/// The progress of a confiscation over the targets.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum ConfiscationProgress<AccountId, CurrencyId> {
+	/// The accounts left are in `ConfiscationAccounts`.
+	Accounts,
+	/// The accounts are scanned in storage order, after the last scanned
+	/// account entry.
+	AllHolders { last: Option<(AccountId, CurrencyId)> },
+}
+
+/// A confiscation processed over multiple blocks.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct Confiscation<AccountId, CurrencyId, Balance> {
+	/// The confiscated currency.
+	pub currency_id: CurrencyId,
+	/// The account receiving the confiscated balances.
+	pub dest: AccountId,
+	/// The progress over the targets.
+	pub progress: ConfiscationProgress<AccountId, CurrencyId>,
+	/// The amount confiscated so far.
+	pub confiscated: Balance,
+}
+
+/// The ID of a confiscation.
+pub type ConfiscationId = u32;
+
 pub use module::*;
 
 #[frame_support::pallet]
@@ -190,7 +281,7 @@ pub mod module {
 			+ MaybeSerializeDeserialize;
 
 		/// The currency ID type
-		type CurrencyId: Parameter + Member + Copy + MaybeSerializeDeserialize + Ord + TypeInfo;
+		type CurrencyId: Parameter + Member + Copy + MaybeSerializeDeserialize + Ord + TypeInfo + MaxEncodedLen;
 
 		/// Weight information for extrinsics in this module.
 		type WeightInfo: WeightInfo;
@@ -205,12 +296,52 @@ pub mod module {
 		/// Handler to burn or transfer account's dust
 		type OnDust: OnDust<Self::AccountId, Self::CurrencyId, Self::Balance>;
 
+		/// Handler for when a balance is slashed. The weight it returns is
+		/// charged to the calls of this pallet, see `HookWeight`.
+		type OnSlash: OnSlash<Self::AccountId, Self::CurrencyId, Self::Balance>;
+
+		/// Handler for when a balance is deposited. The weight it returns is
+		/// charged to the calls of this pallet, see `HookWeight`.
+		type OnDeposit: OnDeposit<Self::AccountId, Self::CurrencyId, Self::Balance>;
+
+		/// Handler for when a balance is transferred. The weight it returns is
+		/// charged to the calls of this pallet, see `HookWeight`.
+		type OnTransfer: OnTransfer<Self::AccountId, Self::CurrencyId, Self::Balance>;
+
+		/// The maximum number of locks that can exist on an account.
 		#[pallet::constant]
 		type MaxLocks: Get<u32>;
 
+		/// The maximum number of named reserves that can exist on an account.
+		#[pallet::constant]
+		type MaxReserves: Get<u32>;
+
+		/// The id type for named reserves.
+		type ReserveIdentifier: Parameter + Member + MaxEncodedLen + Ord + Copy;
+
 		// The whitelist of accounts that will not be reaped even if its total
 		// is zero or below ED.
 		type DustRemovalWhitelist: Contains<Self::AccountId>;
+
+		/// Handler for when an account is created, returning an error vetoes
+		/// the creation. Not called for accounts created at genesis.
+		type OnNewTokenAccount: TryHappened<(Self::AccountId, Self::CurrencyId)>;
+
+		/// Handler for when an account is reaped.
+		type OnKilledTokenAccount: Happened<(Self::AccountId, Self::CurrencyId)>;
+
+		/// The fee charged on the transfers of a currency, the rate of the
+		/// transferred amount and the account receiving it. `()` disables
+		/// transfer fees.
+		type TransferFee: GetByKey<Self::CurrencyId, Option<(Permill, Self::AccountId)>>;
+
+		/// The accounts exempted from transfer fees, e.g. pallet accounts and
+		/// the treasury.
+		type TransferFeeExemptions: Contains<Self::AccountId>;
+
+		/// Handler for when a lock is set, extended or removed, e.g. to sync
+		/// the locked balances into reward pools.
+		type OnLockChanged: OnLockChanged<Self::AccountId, Self::CurrencyId, Self::Balance>;
 	}
 
 	#[pallet::error]
@@ -222,13 +353,17 @@ pub mod module {
 		/// Failed because liquidity restrictions due to locking
 		LiquidityRestrictions,
 		/// Failed because the maximum locks was exceeded
-		MaxLocksExceeded,
+		TooManyLocks,
+		/// Failed because the maximum reserves was exceeded
+		TooManyReserves,
 		/// Transfer/payment would kill account
 		KeepAlive,
 		/// Value too low to create account due to existential deposit
 		ExistentialDeposit,
 		/// Beneficiary account must pre-exist
 		DeadAccount,
+		/// The account is blocked from moving funds
+		AccountBlocked,
 	}
 
 	#[pallet::event]
@@ -251,6 +386,27 @@ pub mod module {
 		Unreserved(T::CurrencyId, T::AccountId, T::Balance),
 		/// A balance was set by root. \[who, free, reserved\]
 		BalanceSet(T::CurrencyId, T::AccountId, T::Balance, T::Balance),
+		/// The blocked status of an account was set by root, `None` if
+		/// unblocked. \[who, status\]
+		BlockedStatusSet(T::AccountId, Option<BlockedStatus>),
+		/// The sufficiency of a currency was set by root. \[currency_id,
+		/// is_sufficient\]
+		SufficientSet(T::CurrencyId, bool),
+		/// Locks sharing an identifier were merged. \[currency_id, who,
+		/// merged\]
+		LocksConsolidated(T::CurrencyId, T::AccountId, u32),
+		/// A transfer fee was charged. \[currency_id, from, fee_account,
+		/// fee\]
+		FeeCharged(T::CurrencyId, T::AccountId, T::AccountId, T::Balance),
+		/// A confiscation was scheduled by root. \[confiscation_id,
+		/// currency_id, dest\]
+		ConfiscationScheduled(ConfiscationId, T::CurrencyId, T::AccountId),
+		/// The balance of an account was confiscated. \[confiscation_id,
+		/// currency_id, who, amount\]
+		Confiscated(ConfiscationId, T::CurrencyId, T::AccountId, T::Balance),
+		/// A confiscation was completed. \[confiscation_id, currency_id,
+		/// total\]
+		ConfiscationCompleted(ConfiscationId, T::CurrencyId, T::Balance),
 	}
 
 	/// The total issuance of a token type.
@@ -272,6 +428,19 @@ pub mod module {
 		ValueQuery,
 	>;
 
+	/// Named reserves on some account balances.
+	#[pallet::storage]
+	#[pallet::getter(fn reserves)]
+	pub type Reserves<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::CurrencyId,
+		BoundedVec<ReserveData<T::ReserveIdentifier, T::Balance>, T::MaxReserves>,
+		ValueQuery,
+	>;
+
 	/// The balance of a token type under an account.
 	///
 	/// NOTE: If the total is ever zero, decrease account ref account.
@@ -290,6 +459,42 @@ pub mod module {
 		ValueQuery,
 	>;
 
+	/// The accounts blocked from moving funds of any currency.
+	#[pallet::storage]
+	#[pallet::getter(fn blocked_accounts)]
+	pub type BlockedAccounts<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, BlockedStatus, OptionQuery>;
+
+	/// True if the currency is sufficient: its accounts are referenced as
+	/// sufficients in `frame_system` rather than as providers, like the
+	/// accounts of sufficient assets in `pallet-assets`.
+	#[pallet::storage]
+	#[pallet::getter(fn is_sufficient)]
+	pub type SufficientCurrencies<T: Config> = StorageMap<_, Twox64Concat, T::CurrencyId, bool, ValueQuery>;
+
+	/// The accounts created with a sufficient reference, the reference is
+	/// released when the account is reaped even if the sufficiency of the
+	/// currency has changed since.
+	#[pallet::storage]
+	pub type SufficientAccounts<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, T::CurrencyId, (), OptionQuery>;
+
+	/// Next available confiscation ID.
+	#[pallet::storage]
+	#[pallet::getter(fn next_confiscation_id)]
+	pub type NextConfiscationId<T: Config> = StorageValue<_, ConfiscationId, ValueQuery>;
+
+	/// The scheduled confiscations, processed in `on_idle`.
+	#[pallet::storage]
+	#[pallet::getter(fn confiscations)]
+	pub type Confiscations<T: Config> =
+		StorageMap<_, Twox64Concat, ConfiscationId, Confiscation<T::AccountId, T::CurrencyId, T::Balance>, OptionQuery>;
+
+	/// The accounts left to confiscate from, of confiscations of given
+	/// accounts.
+	#[pallet::storage]
+	pub type ConfiscationAccounts<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ConfiscationId, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub balances: Vec<(T::AccountId, T::CurrencyId, T::Balance)>,
@@ -335,11 +540,30 @@ pub mod module {
 		}
 	}
 
+	/// The current storage version, 1 since all storage items are bounded.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	#[pallet::generate_storage_info]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::process_confiscations(remaining_weight)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			Self::do_try_state().map_err(|violations| {
+				for violation in &violations {
+					log::error!("tokens invariant violated: {:?}", violation);
+				}
+				violations[0].as_str()
+			})
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -356,19 +580,29 @@ pub mod module {
 		/// - `dest`: The recipient of the transfer.
 		/// - `currency_id`: currency type.
 		/// - `amount`: free balance amount to tranfer.
-		#[pallet::weight(T::WeightInfo::transfer())]
+		#[pallet::weight(T::WeightInfo::transfer()
+			.saturating_add(T::WeightInfo::remove_dust())
+			.saturating_add(Pallet::<T>::transfer_fee_weight(*currency_id)))]
 		pub fn transfer(
 			origin: OriginFor<T>,
 			dest: <T::Lookup as StaticLookup>::Source,
 			currency_id: T::CurrencyId,
 			#[pallet::compact] amount: T::Balance,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(dest)?;
-			Self::do_transfer(currency_id, &from, &to, amount, ExistenceRequirement::AllowDeath)?;
+			let weight = Self::do_transfer_with_weight(
+				currency_id,
+				&from,
+				&to,
+				amount,
+				ExistenceRequirement::AllowDeath,
+				T::WeightInfo::transfer(),
+				T::WeightInfo::transfer().saturating_add(T::WeightInfo::remove_dust()),
+			)?;
 
 			Self::deposit_event(Event::Transfer(currency_id, from, to, amount));
-			Ok(())
+			Ok(Some(weight).into())
 		}
 
 		/// Transfer all remaining balance to the given account.
@@ -390,21 +624,36 @@ pub mod module {
 		///   the sender account to be killed (false), or transfer everything
 		///   except at least the existential deposit, which will guarantee to
 		///   keep the sender account alive (true).
-		#[pallet::weight(T::WeightInfo::transfer_all())]
+		#[pallet::weight(T::WeightInfo::transfer_all()
+			.saturating_add(T::WeightInfo::remove_dust())
+			.saturating_add(Pallet::<T>::transfer_fee_weight(*currency_id)))]
 		pub fn transfer_all(
 			origin: OriginFor<T>,
 			dest: <T::Lookup as StaticLookup>::Source,
 			currency_id: T::CurrencyId,
 			keep_alive: bool,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(dest)?;
 			let reducible_balance =
 				<Self as fungibles::Inspect<T::AccountId>>::reducible_balance(currency_id, &from, keep_alive);
-			<Self as fungibles::Transfer<_>>::transfer(currency_id, &from, &to, reducible_balance, keep_alive)?;
+			let existence_requirement = if keep_alive {
+				ExistenceRequirement::KeepAlive
+			} else {
+				ExistenceRequirement::AllowDeath
+			};
+			let weight = Self::do_transfer_with_weight(
+				currency_id,
+				&from,
+				&to,
+				reducible_balance,
+				existence_requirement,
+				T::WeightInfo::transfer_all(),
+				T::WeightInfo::transfer_all().saturating_add(T::WeightInfo::remove_dust()),
+			)?;
 
 			Self::deposit_event(Event::Transfer(currency_id, from, to, reducible_balance));
-			Ok(())
+			Ok(Some(weight).into())
 		}
 
 		/// Same as the [`transfer`] call, but with a check that the transfer
@@ -418,7 +667,8 @@ pub mod module {
 		/// - `dest`: The recipient of the transfer.
 		/// - `currency_id`: currency type.
 		/// - `amount`: free balance amount to tranfer.
-		#[pallet::weight(T::WeightInfo::transfer_keep_alive())]
+		#[pallet::weight(T::WeightInfo::transfer_keep_alive()
+			.saturating_add(Pallet::<T>::transfer_fee_weight(*currency_id)))]
 		pub fn transfer_keep_alive(
 			origin: OriginFor<T>,
 			dest: <T::Lookup as StaticLookup>::Source,
@@ -427,10 +677,19 @@ pub mod module {
 		) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(dest)?;
-			Self::do_transfer(currency_id, &from, &to, amount, ExistenceRequirement::KeepAlive)?;
+			// the sender is kept alive, so no dust is removed
+			let weight = Self::do_transfer_with_weight(
+				currency_id,
+				&from,
+				&to,
+				amount,
+				ExistenceRequirement::KeepAlive,
+				T::WeightInfo::transfer_keep_alive(),
+				T::WeightInfo::transfer_keep_alive(),
+			)?;
 
 			Self::deposit_event(Event::Transfer(currency_id, from, to, amount));
-			Ok(().into())
+			Ok(Some(weight).into())
 		}
 
 		/// Exactly as `transfer`, except the origin must be root and the source
@@ -442,21 +701,31 @@ pub mod module {
 		/// - `dest`: The recipient of the transfer.
 		/// - `currency_id`: currency type.
 		/// - `amount`: free balance amount to tranfer.
-		#[pallet::weight(T::WeightInfo::force_transfer())]
+		#[pallet::weight(T::WeightInfo::force_transfer()
+			.saturating_add(T::WeightInfo::remove_dust())
+			.saturating_add(Pallet::<T>::transfer_fee_weight(*currency_id)))]
 		pub fn force_transfer(
 			origin: OriginFor<T>,
 			source: <T::Lookup as StaticLookup>::Source,
 			dest: <T::Lookup as StaticLookup>::Source,
 			currency_id: T::CurrencyId,
 			#[pallet::compact] amount: T::Balance,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
 			let from = T::Lookup::lookup(source)?;
 			let to = T::Lookup::lookup(dest)?;
-			Self::do_transfer(currency_id, &from, &to, amount, ExistenceRequirement::AllowDeath)?;
+			let weight = Self::do_transfer_with_weight(
+				currency_id,
+				&from,
+				&to,
+				amount,
+				ExistenceRequirement::AllowDeath,
+				T::WeightInfo::force_transfer(),
+				T::WeightInfo::force_transfer().saturating_add(T::WeightInfo::remove_dust()),
+			)?;
 
 			Self::deposit_event(Event::Transfer(currency_id, from, to, amount));
-			Ok(())
+			Ok(Some(weight).into())
 		}
 
 		/// Set the balances of a given account.
@@ -511,10 +780,271 @@ pub mod module {
 				Ok(())
 			})
 		}
+
+		/// Block an account from moving funds of any currency, or unblock it
+		/// if `status` is `None`.
+		///
+		/// The dispatch origin for this call is `root`.
+		#[pallet::weight(T::WeightInfo::set_blocked_status())]
+		pub fn set_blocked_status(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			status: Option<BlockedStatus>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			BlockedAccounts::<T>::set(&who, status);
+			Self::deposit_event(Event::BlockedStatusSet(who, status));
+			Ok(())
+		}
+
+		/// Set whether `currency_id` is sufficient. Accounts of sufficient
+		/// currencies can exist without any provider, e.g. without native
+		/// balance.
+		///
+		/// The dispatch origin for this call is `root`.
+		#[pallet::weight(T::WeightInfo::set_sufficient())]
+		pub fn set_sufficient(origin: OriginFor<T>, currency_id: T::CurrencyId, is_sufficient: bool) -> DispatchResult {
+			ensure_root(origin)?;
+
+			SufficientCurrencies::<T>::insert(currency_id, is_sufficient);
+			Self::deposit_event(Event::SufficientSet(currency_id, is_sufficient));
+			Ok(())
+		}
+
+		/// Merge the locks of the caller under `currency_id` sharing an
+		/// identifier into one, keeping the largest amount.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::consolidate_locks(T::MaxLocks::get()))]
+		pub fn consolidate_locks(origin: OriginFor<T>, currency_id: T::CurrencyId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let locks = Self::locks(&who, currency_id);
+			let weight = T::WeightInfo::consolidate_locks(locks.len() as u32);
+			let mut consolidated: Vec<BalanceLock<T::Balance>> = Vec::with_capacity(locks.len());
+			for lock in locks.iter() {
+				match consolidated.iter_mut().find(|l| l.id == lock.id) {
+					Some(l) => l.amount = l.amount.max(lock.amount),
+					None => consolidated.push(lock.clone()),
+				}
+			}
+
+			let merged = locks.len().saturating_sub(consolidated.len()) as u32;
+			if merged > 0 {
+				Self::update_locks(currency_id, &who, &consolidated[..])?;
+				Self::deposit_event(Event::LocksConsolidated(currency_id, who, merged));
+			}
+			Ok(Some(weight).into())
+		}
+
+		/// Schedule the confiscation of the `currency_id` balances of
+		/// `targets` to `dest`. The balances, free and reserved, are moved
+		/// in chunks in `on_idle` of the following blocks, and the locks and
+		/// named reserves of the accounts are removed.
+		///
+		/// The dispatch origin for this call is `root`.
+		#[pallet::weight(T::WeightInfo::schedule_confiscation(match targets {
+			ConfiscationTargets::Accounts(accounts) => accounts.len() as u32,
+			ConfiscationTargets::AllHolders => 0,
+		}))]
+		pub fn schedule_confiscation(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			targets: ConfiscationTargets<T::AccountId>,
+			dest: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+
+			let id = NextConfiscationId::<T>::try_mutate(|id| -> Result<ConfiscationId, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+				Ok(current_id)
+			})?;
+			let progress = match targets {
+				ConfiscationTargets::Accounts(accounts) => {
+					for who in accounts {
+						ConfiscationAccounts::<T>::insert(id, who, ());
+					}
+					ConfiscationProgress::Accounts
+				}
+				ConfiscationTargets::AllHolders => ConfiscationProgress::AllHolders { last: None },
+			};
+			Confiscations::<T>::insert(
+				id,
+				Confiscation {
+					currency_id,
+					dest: dest.clone(),
+					progress,
+					confiscated: Zero::zero(),
+				},
+			);
+
+			Self::deposit_event(Event::ConfiscationScheduled(id, currency_id, dest));
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// Returns true if `who` is blocked from sending funds if `outgoing`, or
+	/// from receiving funds otherwise.
+	pub fn is_blocked(who: &T::AccountId, outgoing: bool) -> bool {
+		match Self::blocked_accounts(who) {
+			Some(BlockedStatus::All) => true,
+			Some(BlockedStatus::Outgoing) => outgoing,
+			None => false,
+		}
+	}
+
+	/// Ensure `who` is not blocked from sending funds if `outgoing`, or from
+	/// receiving funds otherwise.
+	///
+	/// Blocked attempts are monitored by the `AccountBlocked` error of the
+	/// failed extrinsics, as any event would be reverted with the failed
+	/// changes.
+	pub(crate) fn ensure_not_blocked(who: &T::AccountId, outgoing: bool) -> DispatchResult {
+		ensure!(!Self::is_blocked(who, outgoing), Error::<T>::AccountBlocked);
+		Ok(())
+	}
+
+	/// Process the scheduled confiscations within `remaining_weight`,
+	/// returning the weight used. Each account step, confiscated or skipped,
+	/// is charged `confiscate_account` plus the weight of the hooks it
+	/// called, and each confiscation read is charged a read.
+	pub(crate) fn process_confiscations(remaining_weight: Weight) -> Weight {
+		let read_weight = T::DbWeight::get().reads(1);
+		let step_weight = T::WeightInfo::confiscate_account();
+		let mut used_weight: Weight = 0;
+
+		// completed confiscations are removed, so the first one is the next to
+		// process, read only if a step of it fits
+		while used_weight.saturating_add(read_weight).saturating_add(step_weight) <= remaining_weight {
+			used_weight = used_weight.saturating_add(read_weight);
+			let (id, mut confiscation) = match Confiscations::<T>::iter().next() {
+				Some(next) => next,
+				None => break,
+			};
+
+			let mut completed = false;
+			while used_weight.saturating_add(step_weight) <= remaining_weight {
+				let (target, hook_weight) = HookWeight::measure(|| {
+					let target = Self::next_confiscation_target(id, &mut confiscation);
+					if let Some(Some(who)) = &target {
+						let amount = Self::confiscate(confiscation.currency_id, who, &confiscation.dest);
+						if !amount.is_zero() {
+							confiscation.confiscated = confiscation.confiscated.saturating_add(amount);
+							Self::deposit_event(Event::Confiscated(id, confiscation.currency_id, who.clone(), amount));
+						}
+					}
+					target
+				});
+				used_weight = used_weight.saturating_add(step_weight).saturating_add(hook_weight);
+				if target.is_none() {
+					completed = true;
+					break;
+				}
+			}
+
+			if completed {
+				Confiscations::<T>::remove(id);
+				Self::deposit_event(Event::ConfiscationCompleted(
+					id,
+					confiscation.currency_id,
+					confiscation.confiscated,
+				));
+			} else {
+				Confiscations::<T>::insert(id, confiscation);
+				break;
+			}
+		}
+
+		used_weight
+	}
+
+	/// The next account to confiscate from, `Some(None)` if the scanned
+	/// account is skipped, or `None` if all the targets are processed.
+	fn next_confiscation_target(
+		id: ConfiscationId,
+		confiscation: &mut Confiscation<T::AccountId, T::CurrencyId, T::Balance>,
+	) -> Option<Option<T::AccountId>> {
+		match &mut confiscation.progress {
+			ConfiscationProgress::Accounts => {
+				let (who, _) = ConfiscationAccounts::<T>::iter_prefix(id).next()?;
+				ConfiscationAccounts::<T>::remove(id, &who);
+				Some(Some(who))
+			}
+			ConfiscationProgress::AllHolders { last } => {
+				let mut iter = match last {
+					Some((who, currency_id)) => {
+						Accounts::<T>::iter_from(Accounts::<T>::hashed_key_for(who, currency_id))
+					}
+					None => Accounts::<T>::iter(),
+				};
+				let (who, currency_id, _) = iter.next()?;
+				*last = Some((who.clone(), currency_id));
+				if currency_id == confiscation.currency_id && who != confiscation.dest {
+					Some(Some(who))
+				} else {
+					Some(None)
+				}
+			}
+		}
+	}
+
+	/// Move the `currency_id` balance of `who`, free and reserved, to `dest`,
+	/// removing the locks and named reserves of `who`. Returns the amount
+	/// moved.
+	fn confiscate(currency_id: T::CurrencyId, who: &T::AccountId, dest: &T::AccountId) -> T::Balance {
+		if who == dest {
+			return Zero::zero();
+		}
+
+		let locks = Self::locks(who, currency_id);
+		if !locks.is_empty() && Self::update_locks(currency_id, who, &[]).is_ok() {
+			for lock in locks {
+				Self::register_hook_weight(T::OnLockChanged::on_lock_changed(
+					who,
+					currency_id,
+					lock.id,
+					Zero::zero(),
+				));
+			}
+		}
+		Reserves::<T>::remove(who, currency_id);
+
+		let amount = Self::mutate_account(who, currency_id, |account, _| {
+			let amount = account.total();
+			account.free = Zero::zero();
+			account.reserved = Zero::zero();
+			amount
+		});
+		if !amount.is_zero() {
+			Self::mutate_account(dest, currency_id, |account, _| {
+				account.free = account.free.saturating_add(amount);
+			});
+		}
+		amount
+	}
+
+	/// The locks of `who` in each currency.
+	pub fn locks_of(who: &T::AccountId) -> Vec<(T::CurrencyId, Vec<BalanceLock<T::Balance>>)> {
+		Locks::<T>::iter_prefix(who)
+			.map(|(currency_id, locks)| (currency_id, locks.into_inner()))
+			.collect()
+	}
+
+	/// The named reserves of `who` in each currency.
+	#[allow(clippy::type_complexity)]
+	pub fn reserves_of(who: &T::AccountId) -> Vec<(T::CurrencyId, Vec<ReserveData<T::ReserveIdentifier, T::Balance>>)> {
+		Reserves::<T>::iter_prefix(who)
+			.map(|(currency_id, reserves)| (currency_id, reserves.into_inner()))
+			.collect()
+	}
+
 	pub(crate) fn deposit_consequence(
 		_who: &T::AccountId,
 		currency_id: T::CurrencyId,
@@ -558,6 +1088,10 @@ impl<T: Config> Pallet<T> {
 			return WithdrawConsequence::Underflow;
 		}
 
+		if Self::is_blocked(who, true) {
+			return WithdrawConsequence::Frozen;
+		}
+
 		let new_total_balance = match account.total().checked_sub(&amount) {
 			Some(x) => x,
 			None => return WithdrawConsequence::NoFunds,
@@ -604,6 +1138,7 @@ impl<T: Config> Pallet<T> {
 			return Ok(());
 		}
 
+		ensure!(!Self::is_blocked(who, true), Error::<T>::AccountBlocked);
 		let new_balance = Self::free_balance(currency_id, who)
 			.checked_sub(&amount)
 			.ok_or(Error::<T>::BalanceTooLow)?;
@@ -614,45 +1149,92 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	pub(crate) fn try_mutate_account<R, E>(
+	/// Run `f`, deferring the hooks of the balance changes in it, e.g.
+	/// `OnDust`, `OnKilledTokenAccount` and `OnTransfer`, until it completes.
+	///
+	/// The hooks run in order if `f` succeeds, and are dropped if it fails, as
+	/// its changes are expected to be reverted, e.g. by `#[transactional]`.
+	/// If nested, the hooks run once the outermost call completes.
+	///
+	/// `OnNewTokenAccount` isn't deferred, as it could reject a new account.
+	pub fn with_deferred_hooks<R, E>(f: impl FnOnce() -> sp_std::result::Result<R, E>) -> sp_std::result::Result<R, E> {
+		MutateContext::with(f)
+	}
+
+	/// Mutate the account of `who` in `currency_id` by `f`, which is passed
+	/// whether the account existed. Fails without changes if `f` fails, or if
+	/// `OnNewTokenAccount` vetoes a new account.
+	pub(crate) fn try_mutate_account<R, E: From<DispatchError>>(
 		who: &T::AccountId,
 		currency_id: T::CurrencyId,
 		f: impl FnOnce(&mut AccountData<T::Balance>, bool) -> sp_std::result::Result<R, E>,
+	) -> sp_std::result::Result<R, E> {
+		// the hooks of nested mutations run once the outer account is written
+		Self::with_deferred_hooks(|| {
+			Self::do_try_mutate_account(who, currency_id, f, || {
+				T::OnNewTokenAccount::try_happened(&(who.clone(), currency_id)).map_err(Into::into)
+			})
+		})
+	}
+
+	fn do_try_mutate_account<R, E>(
+		who: &T::AccountId,
+		currency_id: T::CurrencyId,
+		f: impl FnOnce(&mut AccountData<T::Balance>, bool) -> sp_std::result::Result<R, E>,
+		on_new_account: impl FnOnce() -> sp_std::result::Result<(), E>,
 	) -> sp_std::result::Result<R, E> {
 		Accounts::<T>::try_mutate_exists(who, currency_id, |maybe_account| {
 			let existed = maybe_account.is_some();
 			let mut account = maybe_account.take().unwrap_or_default();
-			f(&mut account, existed).map(move |result| {
-				let maybe_endowed = if !existed { Some(account.free) } else { None };
-				let mut maybe_dust: Option<T::Balance> = None;
-				let total = account.total();
-				*maybe_account = if total < T::ExistentialDeposits::get(&currency_id) {
-					// if ED is not zero, but account total is zero, account will be reaped
-					if total.is_zero() {
-						None
-					} else {
-						if !T::DustRemovalWhitelist::contains(who) {
-							maybe_dust = Some(total);
-						}
-						Some(account)
-					}
+			let result = f(&mut account, existed)?;
+
+			let maybe_endowed = if !existed { Some(account.free) } else { None };
+			let mut maybe_dust: Option<T::Balance> = None;
+			let total = account.total();
+			*maybe_account = if total < T::ExistentialDeposits::get(&currency_id) {
+				// if ED is not zero, but account total is zero, account will be reaped
+				if total.is_zero() {
+					None
 				} else {
-					// Note: if ED is zero, account will never be reaped
+					if !T::DustRemovalWhitelist::contains(who) {
+						maybe_dust = Some(total);
+					}
 					Some(account)
-				};
+				}
+			} else {
+				// Note: if ED is zero, account will never be reaped
+				Some(account)
+			};
 
-				(maybe_endowed, existed, maybe_account.is_some(), maybe_dust, result)
-			})
+			if !existed && maybe_account.is_some() {
+				on_new_account()?;
+			}
+
+			Ok((maybe_endowed, existed, maybe_account.is_some(), maybe_dust, result))
 		})
 		.map(|(maybe_endowed, existed, exists, maybe_dust, result)| {
 			if existed && !exists {
-				// If existed before, decrease account provider.
-				// Ignore the result, because if it failed then there are remaining consumers,
-				// and the account storage in frame_system shouldn't be reaped.
-				let _ = frame_system::Pallet::<T>::dec_providers(who);
+				if SufficientAccounts::<T>::take(who, currency_id).is_some() {
+					// If created as sufficient, decrease account sufficients.
+					frame_system::Pallet::<T>::dec_sufficients(who);
+				} else {
+					// If existed before, decrease account provider.
+					// Ignore the result, because if it failed then there are remaining consumers,
+					// and the account storage in frame_system shouldn't be reaped.
+					let _ = frame_system::Pallet::<T>::dec_providers(who);
+				}
+				Self::offchain_index(OFFCHAIN_REAPED_PREFIX, who, currency_id, ());
+				let who = who.clone();
+				MutateContext::defer(move || T::OnKilledTokenAccount::happened(&(who, currency_id)));
 			} else if !existed && exists {
-				// if new, increase account provider
-				frame_system::Pallet::<T>::inc_providers(who);
+				if Self::is_sufficient(currency_id) {
+					// if new and sufficient, increase account sufficients
+					frame_system::Pallet::<T>::inc_sufficients(who);
+					SufficientAccounts::<T>::insert(who, currency_id, ());
+				} else {
+					// if new, increase account provider
+					frame_system::Pallet::<T>::inc_providers(who);
+				}
 			}
 
 			if let Some(endowed) = maybe_endowed {
@@ -660,23 +1242,54 @@ impl<T: Config> Pallet<T> {
 			}
 
 			if let Some(dust_amount) = maybe_dust {
-				// `OnDust` maybe get/set storage `Accounts` of `who`, trigger handler here
-				// to avoid some unexpected errors.
-				T::OnDust::on_dust(who, currency_id, dust_amount);
-				Self::deposit_event(Event::DustLost(currency_id, who.clone(), dust_amount));
+				// `OnDust` maybe get/set storage `Accounts` of `who`, defer the handler until
+				// the accounts are written.
+				let who = who.clone();
+				MutateContext::defer(move || {
+					Self::register_hook_weight(T::OnDust::on_dust(&who, currency_id, dust_amount));
+					Self::offchain_index(OFFCHAIN_DUST_PREFIX, &who, currency_id, dust_amount);
+					Self::deposit_event(Event::DustLost(currency_id, who, dust_amount));
+				});
 			}
 
 			result
 		})
 	}
 
+	/// The offchain index key of the record under `prefix` of `who` in
+	/// `currency_id` at `block_number`.
+	pub fn offchain_index_key(
+		prefix: &[u8],
+		block_number: T::BlockNumber,
+		who: &T::AccountId,
+		currency_id: T::CurrencyId,
+	) -> Vec<u8> {
+		(block_number, who, currency_id).using_encoded(|key| [prefix, key].concat())
+	}
+
+	fn offchain_index(prefix: &[u8], who: &T::AccountId, currency_id: T::CurrencyId, record: impl Encode) {
+		let key = Self::offchain_index_key(prefix, frame_system::Pallet::<T>::block_number(), who, currency_id);
+		sp_io::offchain_index::set(&key, &record.encode());
+	}
+
+	/// Mutate the account of `who` in `currency_id` by `f`, which can't fail.
+	///
+	/// `OnNewTokenAccount` can't veto a new account here, so it's only for
+	/// changes which don't create accounts, and the ones which must not fail,
+	/// e.g. at genesis, in migrations or for confiscations. Use
+	/// `try_mutate_account` otherwise.
 	pub(crate) fn mutate_account<R>(
 		who: &T::AccountId,
 		currency_id: T::CurrencyId,
 		f: impl FnOnce(&mut AccountData<T::Balance>, bool) -> R,
 	) -> R {
-		Self::try_mutate_account(who, currency_id, |account, existed| -> Result<R, Infallible> {
-			Ok(f(account, existed))
+		Self::with_deferred_hooks(|| {
+			Self::do_try_mutate_account(
+				who,
+				currency_id,
+				|account, existed| -> Result<R, Infallible> { Ok(f(account, existed)) },
+				|| Ok(()),
+			)
 		})
 		.expect("Error is infallible; qed")
 	}
@@ -712,6 +1325,9 @@ impl<T: Config> Pallet<T> {
 		who: &T::AccountId,
 		locks: &[BalanceLock<T::Balance>],
 	) -> DispatchResult {
+		// check the bound before any change
+		ensure!(locks.len() as u32 <= T::MaxLocks::get(), Error::<T>::TooManyLocks);
+
 		// update account data
 		Self::mutate_account(who, currency_id, |account, _| {
 			account.frozen = Zero::zero();
@@ -730,7 +1346,7 @@ impl<T: Config> Pallet<T> {
 			}
 		} else {
 			let bounded_locks: BoundedVec<BalanceLock<T::Balance>, T::MaxLocks> =
-				locks.to_vec().try_into().map_err(|_| Error::<T>::MaxLocksExceeded)?;
+				locks.to_vec().try_into().map_err(|_| Error::<T>::TooManyLocks)?;
 			Locks::<T>::insert(who, currency_id, bounded_locks);
 			if !existed {
 				// increase account ref count when initialize lock
@@ -749,12 +1365,56 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Mutate the named reserve `id` of `who`, the named reserve is removed
+	/// once it's zero.
+	pub(crate) fn try_mutate_reserve_named<R, E: From<Error<T>>>(
+		id: &T::ReserveIdentifier,
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		f: impl FnOnce(&mut T::Balance) -> sp_std::result::Result<R, E>,
+	) -> sp_std::result::Result<R, E> {
+		Reserves::<T>::try_mutate_exists(who, currency_id, |maybe_reserves| {
+			let mut reserves = maybe_reserves.take().map(|r| r.into_inner()).unwrap_or_default();
+			let index = match reserves.binary_search_by_key(id, |data| data.id) {
+				Ok(index) => index,
+				Err(index) => {
+					// check the bound before any change by `f`
+					ensure!(
+						(reserves.len() as u32) < T::MaxReserves::get(),
+						Error::<T>::TooManyReserves
+					);
+					reserves.insert(
+						index,
+						ReserveData {
+							id: *id,
+							amount: Zero::zero(),
+						},
+					);
+					index
+				}
+			};
+
+			let result = f(&mut reserves[index].amount)?;
+			if reserves[index].amount.is_zero() {
+				reserves.remove(index);
+			}
+
+			*maybe_reserves = if reserves.is_empty() {
+				None
+			} else {
+				Some(reserves.try_into().map_err(|_| Error::<T>::TooManyReserves)?)
+			};
+			Ok(result)
+		})
+	}
+
 	/// Transfer some free balance from `from` to `to`. Ensure from_account
 	/// allow death or new balance will not be reaped, and ensure
 	/// to_account will not be removed dust.
 	///
 	/// Is a no-op if value to be transferred is zero or the `from` is the same
 	/// as `to`.
+	#[transactional]
 	pub(crate) fn do_transfer(
 		currency_id: T::CurrencyId,
 		from: &T::AccountId,
@@ -766,7 +1426,10 @@ impl<T: Config> Pallet<T> {
 			return Ok(());
 		}
 
-		Self::try_mutate_account(to, currency_id, |to_account, _existed| -> DispatchResult {
+		Self::ensure_not_blocked(from, true)?;
+		Self::ensure_not_blocked(to, false)?;
+
+		Self::try_mutate_account(to, currency_id, |to_account, _to_existed| -> DispatchResult {
 			Self::try_mutate_account(from, currency_id, |from_account, _existed| -> DispatchResult {
 				from_account.free = from_account
 					.free
@@ -804,7 +1467,151 @@ impl<T: Config> Pallet<T> {
 				Ok(())
 			})?;
 			Ok(())
-		})
+		})?;
+
+		Self::charge_transfer_fee(currency_id, from, to, amount)?;
+		let (from, to) = (from.clone(), to.clone());
+		MutateContext::defer(move || {
+			Self::register_hook_weight(T::OnTransfer::on_transfer(&from, &to, currency_id, amount))
+		});
+		Ok(())
+	}
+
+	/// Charge the `weight` consumed by the `OnDust`, `OnSlash`, `OnDeposit`,
+	/// `OnTransfer` and `OnLockChanged` hooks to the call being measured, or
+	/// register it to the block if not measured, e.g. if called by another
+	/// pallet.
+	fn register_hook_weight(weight: Weight) {
+		if weight > 0 && !HookWeight::consume(weight) {
+			frame_system::Pallet::<T>::register_extra_weight_unchecked(weight, DispatchClass::Normal);
+		}
+	}
+
+	/// Call the `OnSlash` hook if `amount` is slashed from `who`.
+	pub(crate) fn on_slashed(currency_id: T::CurrencyId, who: &T::AccountId, amount: T::Balance) {
+		if !amount.is_zero() {
+			let who = who.clone();
+			MutateContext::defer(move || Self::register_hook_weight(T::OnSlash::on_slash(&who, currency_id, amount)));
+		}
+	}
+
+	/// Charge the transfer fee of `currency_id`, if any, from the `amount`
+	/// received by `to`.
+	fn charge_transfer_fee(
+		currency_id: T::CurrencyId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		let (rate, fee_account) = match T::TransferFee::get(&currency_id) {
+			Some(fee) => fee,
+			None => return Ok(()),
+		};
+		let exempted = |who: &T::AccountId| *who == fee_account || T::TransferFeeExemptions::contains(who);
+		if exempted(from) || exempted(to) {
+			return Ok(());
+		}
+		let fee = rate.mul_floor(amount);
+		if fee.is_zero() {
+			return Ok(());
+		}
+
+		Self::try_mutate_account(
+			&fee_account,
+			currency_id,
+			|fee_account_data, _existed| -> DispatchResult {
+				Self::try_mutate_account(to, currency_id, |to_account, _existed| -> DispatchResult {
+					to_account.free = to_account.free.checked_sub(&fee).ok_or(Error::<T>::BalanceTooLow)?;
+					fee_account_data.free = fee_account_data
+						.free
+						.checked_add(&fee)
+						.ok_or(ArithmeticError::Overflow)?;
+
+					let ed = T::ExistentialDeposits::get(&currency_id);
+					ensure!(
+						to_account.total() >= ed || T::DustRemovalWhitelist::contains(to),
+						Error::<T>::ExistentialDeposit
+					);
+					ensure!(
+						fee_account_data.total() >= ed || T::DustRemovalWhitelist::contains(&fee_account),
+						Error::<T>::ExistentialDeposit
+					);
+					Ok(())
+				})
+			},
+		)?;
+
+		Self::deposit_event(Event::FeeCharged(currency_id, from.clone(), fee_account, fee));
+		Ok(())
+	}
+
+	/// Same as `do_transfer`, but returns the actual weight of a transfer
+	/// call from its `base` weight.
+	///
+	/// `base` is the weight of a transfer creating the recipient account, the
+	/// creation is refunded if no account was created. Removing the dust of
+	/// the sender is charged on top of `base` if it happened, so the calls
+	/// which could remove dust must include `WeightInfo::remove_dust` in
+	/// their weight. The weight of the hooks called by the transfer is added
+	/// as well, the part above the `declared` weight of the call is
+	/// registered to the block, as FRAME caps the actual weight at it.
+	///
+	/// The weight of charging the transfer fee is added to both `base` and
+	/// `declared`, the calls must include `transfer_fee_weight` in their
+	/// weight.
+	pub(crate) fn do_transfer_with_weight(
+		currency_id: T::CurrencyId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: T::Balance,
+		existence_requirement: ExistenceRequirement,
+		base: Weight,
+		declared: Weight,
+	) -> Result<Weight, DispatchError> {
+		let to_existed = Accounts::<T>::contains_key(to, currency_id);
+		let from_remaining = Self::accounts(from, currency_id).total().saturating_sub(amount);
+
+		let (result, hook_weight) =
+			HookWeight::measure(|| Self::do_transfer(currency_id, from, to, amount, existence_requirement));
+		result?;
+
+		let fee_weight = Self::transfer_fee_weight(currency_id);
+		let declared = declared.saturating_add(fee_weight);
+		let mut weight = base.saturating_add(fee_weight).saturating_add(hook_weight);
+		if to_existed || !Accounts::<T>::contains_key(to, currency_id) {
+			weight = weight.saturating_sub(T::WeightInfo::create_account());
+		}
+		// `do_transfer` is a no-op for these, the dust of the sender is kept
+		let transferred = !amount.is_zero() && from != to;
+		let dust_removed = transferred
+			&& !from_remaining.is_zero()
+			&& from_remaining < T::ExistentialDeposits::get(&currency_id)
+			&& !T::DustRemovalWhitelist::contains(from);
+		if dust_removed {
+			weight = weight.saturating_add(T::WeightInfo::remove_dust());
+		}
+		Ok(Self::cap_actual_weight(weight, declared))
+	}
+
+	/// The weight of charging the transfer fee of `currency_id`, if any:
+	/// reading and writing the accounts of the recipient and of the fee
+	/// account, the system account of the fee account if it's created, and
+	/// the `FeeCharged` event.
+	pub fn transfer_fee_weight(currency_id: T::CurrencyId) -> Weight {
+		if T::TransferFee::get(&currency_id).is_some() {
+			T::DbWeight::get().reads_writes(3, 4)
+		} else {
+			0
+		}
+	}
+
+	/// Cap the actual `weight` of a call at its `declared` weight, the excess
+	/// consumed by the hooks is registered to the block.
+	fn cap_actual_weight(weight: Weight, declared: Weight) -> Weight {
+		if weight > declared {
+			frame_system::Pallet::<T>::register_extra_weight_unchecked(weight - declared, DispatchClass::Normal);
+		}
+		weight.min(declared)
 	}
 
 	/// Withdraw some free balance from an account, respecting existence
@@ -826,6 +1633,8 @@ impl<T: Config> Pallet<T> {
 			return Ok(());
 		}
 
+		Self::ensure_not_blocked(who, true)?;
+
 		Self::try_mutate_account(who, currency_id, |account, _existed| -> DispatchResult {
 			Self::ensure_can_withdraw(currency_id, who, amount)?;
 			let previous_total = account.total();
@@ -879,6 +1688,8 @@ impl<T: Config> Pallet<T> {
 			return Ok(());
 		}
 
+		Self::ensure_not_blocked(who, false)?;
+
 		Self::try_mutate_account(who, currency_id, |account, existed| -> DispatchResult {
 			if require_existed {
 				ensure!(existed, Error::<T>::DeadAccount);
@@ -901,7 +1712,11 @@ impl<T: Config> Pallet<T> {
 			account.free += amount;
 
 			Ok(())
-		})
+		})?;
+
+		let who = who.clone();
+		MutateContext::defer(move || Self::register_hook_weight(T::OnDeposit::on_deposit(&who, currency_id, amount)));
+		Ok(())
 	}
 }
 
@@ -939,6 +1754,16 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 		Self::do_transfer(currency_id, from, to, amount, ExistenceRequirement::AllowDeath)
 	}
 
+	fn transfer_with_existence_requirement(
+		currency_id: Self::CurrencyId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: Self::Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		Self::do_transfer(currency_id, from, to, amount, existence_requirement)
+	}
+
 	fn deposit(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
 		// do not require existing
 		Self::do_deposit(currency_id, who, amount, false, true)
@@ -992,6 +1817,7 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 		// Cannot underflow because the slashed value cannot be greater than total
 		// issuance
 		TotalIssuance::<T>::mutate(currency_id, |v| *v -= amount - remaining_slash);
+		Self::on_slashed(currency_id, who, amount - remaining_slash);
 		remaining_slash
 	}
 }
@@ -1050,7 +1876,10 @@ impl<T: Config> MultiLockableCurrency<T::AccountId> for Pallet<T> {
 		if let Some(lock) = new_lock {
 			locks.push(lock)
 		}
-		Self::update_locks(currency_id, who, &locks[..])
+		Self::update_locks(currency_id, who, &locks[..])?;
+
+		Self::register_hook_weight(T::OnLockChanged::on_lock_changed(who, currency_id, lock_id, amount));
+		Ok(())
 	}
 
 	// Extend a lock on the balance of `who` under `currency_id`.
@@ -1081,14 +1910,35 @@ impl<T: Config> MultiLockableCurrency<T::AccountId> for Pallet<T> {
 		if let Some(lock) = new_lock {
 			locks.push(lock)
 		}
-		Self::update_locks(currency_id, who, &locks[..])
+		Self::update_locks(currency_id, who, &locks[..])?;
+
+		if let Some(lock) = locks.iter().find(|lock| lock.id == lock_id) {
+			Self::register_hook_weight(T::OnLockChanged::on_lock_changed(
+				who,
+				currency_id,
+				lock_id,
+				lock.amount,
+			));
+		}
+		Ok(())
 	}
 
 	fn remove_lock(lock_id: LockIdentifier, currency_id: Self::CurrencyId, who: &T::AccountId) -> DispatchResult {
 		let mut locks = Self::locks(who, currency_id);
+		let existed = locks.iter().any(|lock| lock.id == lock_id);
 		locks.retain(|lock| lock.id != lock_id);
 		let locks_vec = locks.to_vec();
-		Self::update_locks(currency_id, who, &locks_vec[..])
+		Self::update_locks(currency_id, who, &locks_vec[..])?;
+
+		if existed {
+			Self::register_hook_weight(T::OnLockChanged::on_lock_changed(
+				who,
+				currency_id,
+				lock_id,
+				Zero::zero(),
+			));
+		}
+		Ok(())
 	}
 }
 
@@ -1116,6 +1966,7 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 		let actual = reserved_balance.min(value);
 		Self::set_reserved_balance(currency_id, who, reserved_balance - actual);
 		TotalIssuance::<T>::mutate(currency_id, |v| *v -= actual);
+		Self::on_slashed(currency_id, who, actual);
 		value - actual
 	}
 
@@ -1131,6 +1982,7 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 		if value.is_zero() {
 			return Ok(());
 		}
+		Self::ensure_not_blocked(who, true)?;
 		Self::ensure_can_withdraw(currency_id, who, value)?;
 
 		Self::mutate_account(who, currency_id, |account, _| {
@@ -1184,22 +2036,178 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 			};
 		}
 
+		Self::ensure_not_blocked(slashed, true)?;
+		Self::ensure_not_blocked(beneficiary, false)?;
+
 		let from_account = Self::accounts(slashed, currency_id);
-		let to_account = Self::accounts(beneficiary, currency_id);
 		let actual = from_account.reserved.min(value);
-		match status {
-			BalanceStatus::Free => {
-				Self::set_free_balance(currency_id, beneficiary, to_account.free + actual);
+		// the beneficiary could be a new account, to be vetoed by `OnNewTokenAccount`
+		Self::try_mutate_account(beneficiary, currency_id, |to_account, _| -> DispatchResult {
+			match status {
+				BalanceStatus::Free => to_account.free += actual,
+				BalanceStatus::Reserved => to_account.reserved += actual,
 			}
-			BalanceStatus::Reserved => {
-				Self::set_reserved_balance(currency_id, beneficiary, to_account.reserved + actual);
-			}
-		}
+			Ok(())
+		})?;
 		Self::set_reserved_balance(currency_id, slashed, from_account.reserved - actual);
 		Ok(value - actual)
 	}
 }
 
+impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
+	type ReserveIdentifier = T::ReserveIdentifier;
+
+	fn reserved_balance_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+	) -> Self::Balance {
+		let reserves = Self::reserves(who, currency_id);
+		reserves
+			.binary_search_by_key(id, |data| data.id)
+			.map(|index| reserves[index].amount)
+			.unwrap_or_default()
+	}
+
+	/// Move `value` from the free balance from `who` to a named reserve
+	/// balance.
+	///
+	/// Is a no-op if value to be reserved is zero.
+	fn reserve_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> DispatchResult {
+		if value.is_zero() {
+			return Ok(());
+		}
+
+		Self::try_mutate_reserve_named(id, currency_id, who, |amount| -> DispatchResult {
+			<Self as MultiReservableCurrency<_>>::reserve(currency_id, who, value)?;
+			*amount = amount.saturating_add(value);
+			Ok(())
+		})
+	}
+
+	/// Unreserve some funds, returning any amount that was unable to be
+	/// unreserved.
+	///
+	/// Is a no-op if the value to be unreserved is zero.
+	fn unreserve_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> Self::Balance {
+		if value.is_zero() {
+			return Zero::zero();
+		}
+
+		Self::try_mutate_reserve_named(id, currency_id, who, |amount| -> Result<_, Error<T>> {
+			let to_change = (*amount).min(value);
+			let remain = <Self as MultiReservableCurrency<_>>::unreserve(currency_id, who, to_change);
+			// remain should always be zero but just to be defensive here
+			let actual = to_change.saturating_sub(remain);
+			// `actual <= to_change` and `to_change <= amount`; qed;
+			*amount -= actual;
+			Ok(value - actual)
+		})
+		.unwrap_or(value)
+	}
+
+	/// Slash from reserved balance, returning the amount that was unable to
+	/// be slashed.
+	///
+	/// Is a no-op if the value to be slashed is zero.
+	fn slash_reserved_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> Self::Balance {
+		if value.is_zero() {
+			return Zero::zero();
+		}
+
+		Self::try_mutate_reserve_named(id, currency_id, who, |amount| -> Result<_, Error<T>> {
+			let to_change = (*amount).min(value);
+			let remain = <Self as MultiReservableCurrency<_>>::slash_reserved(currency_id, who, to_change);
+			let actual = to_change.saturating_sub(remain);
+			*amount -= actual;
+			Ok(value - actual)
+		})
+		.unwrap_or(value)
+	}
+
+	/// Move the reserved balance of one account into the balance of another,
+	/// according to `status`. If `status` is `Reserved`, the balance will be
+	/// reserved with given `id`.
+	///
+	/// Is a no-op if:
+	/// - the value to be moved is zero; or
+	/// - the `slashed` id equal to `beneficiary` and the `status` is
+	///   `Reserved`.
+	#[transactional]
+	fn repatriate_reserved_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		value: Self::Balance,
+		status: BalanceStatus,
+	) -> Result<Self::Balance, DispatchError> {
+		if value.is_zero() {
+			return Ok(Zero::zero());
+		}
+
+		if slashed == beneficiary {
+			return match status {
+				BalanceStatus::Free => Ok(Self::unreserve_named(id, currency_id, slashed, value)),
+				BalanceStatus::Reserved => {
+					Ok(value.saturating_sub(Self::reserved_balance_named(id, currency_id, slashed)))
+				}
+			};
+		}
+
+		Self::try_mutate_reserve_named(id, currency_id, slashed, |amount| -> Result<_, DispatchError> {
+			let to_change = (*amount).min(value);
+			let actual = match status {
+				BalanceStatus::Free => {
+					let remain = <Self as MultiReservableCurrency<_>>::repatriate_reserved(
+						currency_id,
+						slashed,
+						beneficiary,
+						to_change,
+						status,
+					)?;
+					to_change.saturating_sub(remain)
+				}
+				// keep the repatriated balance under the same identifier
+				BalanceStatus::Reserved => Self::try_mutate_reserve_named(
+					id,
+					currency_id,
+					beneficiary,
+					|beneficiary_amount| -> Result<_, DispatchError> {
+						let remain = <Self as MultiReservableCurrency<_>>::repatriate_reserved(
+							currency_id,
+							slashed,
+							beneficiary,
+							to_change,
+							status,
+						)?;
+						let actual = to_change.saturating_sub(remain);
+						*beneficiary_amount = beneficiary_amount.saturating_add(actual);
+						Ok(actual)
+					},
+				)?,
+			};
+			*amount -= actual;
+			Ok(value - actual)
+		})
+	}
+}
+
 impl<T: Config> fungibles::Inspect<T::AccountId> for Pallet<T> {
 	type AssetId = T::CurrencyId;
 	type Balance = T::Balance;
@@ -1282,8 +2290,10 @@ impl<T: Config> fungibles::Transfer<T::AccountId> for Pallet<T> {
 impl<T: Config> fungibles::Unbalanced<T::AccountId> for Pallet<T> {
 	fn set_balance(asset_id: Self::AssetId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
 		// Balance is the same type and will not overflow
-		Self::mutate_account(who, asset_id, |account, _| account.free = amount);
-		Ok(())
+		Self::try_mutate_account(who, asset_id, |account, _| -> DispatchResult {
+			account.free = amount;
+			Ok(())
+		})
 	}
 
 	fn set_total_issuance(asset_id: Self::AssetId, amount: Self::Balance) {
@@ -1464,11 +2474,13 @@ where
 			let reserved_slashed_amount = account.reserved.min(remaining_slash);
 			remaining_slash -= reserved_slashed_amount;
 			Pallet::<T>::set_reserved_balance(currency_id, who, account.reserved - reserved_slashed_amount);
+			Pallet::<T>::on_slashed(currency_id, who, free_slashed_amount + reserved_slashed_amount);
 			(
 				Self::NegativeImbalance::new(free_slashed_amount + reserved_slashed_amount),
 				remaining_slash,
 			)
 		} else {
+			Pallet::<T>::on_slashed(currency_id, who, value);
 			(Self::NegativeImbalance::new(value), remaining_slash)
 		}
 	}
@@ -1510,7 +2522,7 @@ where
 		Pallet::<T>::try_mutate_account(
 			who,
 			currency_id,
-			|account, existed| -> Result<SignedImbalance<Self::Balance, Self::PositiveImbalance>, ()> {
+			|account, existed| -> Result<SignedImbalance<Self::Balance, Self::PositiveImbalance>, DispatchError> {
 				// If we're attempting to set an existing account to less than ED, then
 				// bypass the entire operation. It's a no-op if you follow it through, but
 				// since this is an instance where we might account for a negative imbalance
@@ -1519,7 +2531,10 @@ where
 				// instance that there's no other accounts on the system at all, we might
 				// underflow the issuance and our arithmetic will be off.
 				let ed = T::ExistentialDeposits::get(&currency_id);
-				ensure!(value.saturating_add(account.reserved) >= ed || existed, ());
+				ensure!(
+					value.saturating_add(account.reserved) >= ed || existed,
+					Error::<T>::ExistentialDeposit
+				);
 
 				let imbalance = if account.free <= value {
 					SignedImbalance::Positive(PositiveImbalance::new(value - account.free))
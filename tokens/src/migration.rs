@@ -0,0 +1,219 @@
+//! Migrations of orml-tokens.
+//!
+//! `BoundLocksAndReserves` bounds the locks and reserves of accounts to
+//! `MaxLocks` and `MaxReserves`, so all storage items have a
+//! `MaxEncodedLen`. It must run before the runtime relies on the storage
+//! info of orml-tokens.
+//!
+//! Chains consolidating on orml-tokens could implement `BalanceSource` to
+//! drain the storage of their previous balance pallets, e.g. pallet-assets or
+//! pallet-balances, and use `MigrateToTokens` as an `OnRuntimeUpgrade` to
+//! move the balances into orml-tokens accounts. Multiple sources could be
+//! migrated with a tuple of `MigrateToTokens`.
+
+use crate::{BalanceLock, Config, Locks, Pallet, ReserveData, Reserves, TotalIssuance, STORAGE_VERSION};
+use frame_support::{
+	log,
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade},
+	weights::Weight,
+	BoundedVec,
+};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	RuntimeDebug,
+};
+use sp_std::{convert::TryFrom, marker, prelude::*};
+
+#[cfg(feature = "try-runtime")]
+use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+#[cfg(feature = "try-runtime")]
+use sp_std::collections::btree_map::BTreeMap;
+
+/// The balance of an account in a source pallet.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SourceBalance<AccountId, CurrencyId, Balance> {
+	pub who: AccountId,
+	/// The currency in orml-tokens the balance is migrated into.
+	pub currency_id: CurrencyId,
+	pub free: Balance,
+	pub reserved: Balance,
+}
+
+/// A balance pallet to migrate from.
+pub trait BalanceSource<AccountId, CurrencyId, Balance> {
+	/// The balances of all accounts in the source, without modifying it.
+	fn balances() -> Vec<SourceBalance<AccountId, CurrencyId, Balance>>;
+
+	/// Remove all balances from the source storage, returns the removed
+	/// balances and the weight consumed by the source.
+	fn drain() -> (Vec<SourceBalance<AccountId, CurrencyId, Balance>>, Weight);
+}
+
+/// Move balances from `Source` into orml-tokens accounts.
+///
+/// The migrated free and reserved balances are added to the existing
+/// orml-tokens balances, and the total issuance increased accordingly.
+/// Locks aren't migrated, the pallets owning them should set them again.
+pub struct MigrateToTokens<T, Source>(marker::PhantomData<(T, Source)>);
+
+impl<T, Source> MigrateToTokens<T, Source>
+where
+	T: Config,
+	Source: BalanceSource<T::AccountId, T::CurrencyId, T::Balance>,
+{
+	/// Migrate all balances from `Source`, returns the consumed weight.
+	pub fn migrate() -> Weight {
+		let (balances, source_weight) = Source::drain();
+		let mut reads: Weight = 0;
+		let mut writes: Weight = 0;
+
+		for balance in balances {
+			let total = balance.free.saturating_add(balance.reserved);
+			// increase total issuance first, in case the account is reaped as dust
+			TotalIssuance::<T>::mutate(balance.currency_id, |v| *v = v.saturating_add(total));
+			Pallet::<T>::mutate_account(&balance.who, balance.currency_id, |account, _| {
+				account.free = account.free.saturating_add(balance.free);
+				account.reserved = account.reserved.saturating_add(balance.reserved);
+			});
+			// total issuance, account and system account
+			reads += 3;
+			writes += 3;
+		}
+
+		source_weight.saturating_add(T::DbWeight::get().reads_writes(reads, writes))
+	}
+}
+
+impl<T, Source> OnRuntimeUpgrade for MigrateToTokens<T, Source>
+where
+	T: Config,
+	Source: BalanceSource<T::AccountId, T::CurrencyId, T::Balance>,
+{
+	fn on_runtime_upgrade() -> Weight {
+		Self::migrate()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		let mut expected: BTreeMap<T::CurrencyId, T::Balance> = BTreeMap::new();
+		for balance in Source::balances() {
+			let total = expected
+				.entry(balance.currency_id)
+				.or_insert_with(|| TotalIssuance::<T>::get(balance.currency_id));
+			*total = total.saturating_add(balance.free.saturating_add(balance.reserved));
+		}
+		Self::set_temp_storage(expected.into_iter().collect::<Vec<_>>(), "expected_total_issuance");
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		if !Source::balances().is_empty() {
+			return Err("source balances not drained");
+		}
+
+		let expected: Vec<(T::CurrencyId, T::Balance)> =
+			Self::get_temp_storage("expected_total_issuance").ok_or("expected total issuance not found")?;
+		for (currency_id, total_issuance) in expected {
+			if TotalIssuance::<T>::get(currency_id) != total_issuance {
+				return Err("total issuance mismatch after migration");
+			}
+		}
+
+		Pallet::<T>::do_try_state().map_err(|violations| violations[0].as_str())
+	}
+}
+
+/// Bound the locks and reserves of accounts to `MaxLocks` and `MaxReserves`.
+///
+/// Entries beyond the bounds keep the largest locks, which set the frozen
+/// balance, and the largest named reserves. The balance of the dropped named
+/// reserves is unreserved, so the reserved balance matches the named
+/// reserves left.
+pub struct BoundLocksAndReserves<T>(marker::PhantomData<T>);
+
+impl<T: Config> BoundLocksAndReserves<T> {
+	/// Bound the locks and reserves, returns the consumed weight.
+	pub fn migrate() -> Weight {
+		let mut entries: Weight = 0;
+		let mut truncated: u32 = 0;
+		let mut dropped: Vec<(T::AccountId, T::CurrencyId, T::Balance)> = Vec::new();
+
+		Locks::<T>::translate::<Vec<BalanceLock<T::Balance>>, _>(|_who, _currency_id, mut locks| {
+			entries += 1;
+			if locks.len() > T::MaxLocks::get() as usize {
+				truncated += 1;
+				locks.sort_by(|a, b| b.amount.cmp(&a.amount));
+				locks.truncate(T::MaxLocks::get() as usize);
+			}
+			if locks.is_empty() {
+				None
+			} else {
+				BoundedVec::try_from(locks).ok()
+			}
+		});
+		Reserves::<T>::translate::<Vec<ReserveData<T::ReserveIdentifier, T::Balance>>, _>(
+			|who, currency_id, mut reserves| {
+				entries += 1;
+				if reserves.len() > T::MaxReserves::get() as usize {
+					truncated += 1;
+					reserves.sort_by(|a, b| b.amount.cmp(&a.amount));
+					let amount = reserves
+						.drain(T::MaxReserves::get() as usize..)
+						.fold(Zero::zero(), |total: T::Balance, reserve| {
+							total.saturating_add(reserve.amount)
+						});
+					dropped.push((who, currency_id, amount));
+				}
+				if reserves.is_empty() {
+					None
+				} else {
+					BoundedVec::try_from(reserves).ok()
+				}
+			},
+		);
+		// the account and system account of each truncated reserves
+		let unreserved = dropped.len() as Weight * 2;
+		for (who, currency_id, amount) in dropped {
+			Pallet::<T>::mutate_account(&who, currency_id, |account, _| {
+				let amount = amount.min(account.reserved);
+				account.reserved -= amount;
+				account.free = account.free.saturating_add(amount);
+			});
+		}
+		STORAGE_VERSION.put::<Pallet<T>>();
+
+		log::info!(
+			"tokens: bounded {:?} locks and reserves entries, {:?} truncated",
+			entries,
+			truncated
+		);
+		T::DbWeight::get().reads_writes(
+			entries.saturating_add(unreserved).saturating_add(1),
+			entries.saturating_add(unreserved).saturating_add(1),
+		)
+	}
+}
+
+impl<T: Config> OnRuntimeUpgrade for BoundLocksAndReserves<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() < STORAGE_VERSION {
+			Self::migrate()
+		} else {
+			T::DbWeight::get().reads(1)
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		if Pallet::<T>::on_chain_storage_version() != STORAGE_VERSION {
+			return Err("storage version not updated");
+		}
+		if Locks::<T>::iter_keys().count() != Locks::<T>::iter().count()
+			|| Reserves::<T>::iter_keys().count() != Reserves::<T>::iter().count()
+		{
+			return Err("unbounded locks or reserves left");
+		}
+		Ok(())
+	}
+}
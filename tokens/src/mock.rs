@@ -20,10 +20,12 @@ use sp_std::cell::RefCell;
 pub type AccountId = AccountId32;
 pub type CurrencyId = u32;
 pub type Balance = u64;
+pub type ReserveIdentifier = [u8; 8];
 
 pub const DOT: CurrencyId = 1;
 pub const BTC: CurrencyId = 2;
 pub const ETH: CurrencyId = 3;
+pub const FEE_TOKEN: CurrencyId = 4;
 pub const ALICE: AccountId = AccountId32::new([0u8; 32]);
 pub const BOB: AccountId = AccountId32::new([1u8; 32]);
 pub const CHARLIE: AccountId = AccountId32::new([2u8; 32]);
@@ -32,6 +34,9 @@ pub const TREASURY_ACCOUNT: AccountId = AccountId32::new([4u8; 32]);
 pub const ID_1: LockIdentifier = *b"1       ";
 pub const ID_2: LockIdentifier = *b"2       ";
 pub const ID_3: LockIdentifier = *b"3       ";
+pub const RID_1: ReserveIdentifier = [1u8; 8];
+pub const RID_2: ReserveIdentifier = [2u8; 8];
+pub const RID_3: ReserveIdentifier = [3u8; 8];
 
 use crate as tokens;
 
@@ -221,9 +226,93 @@ parameter_type_with_key! {
 	};
 }
 
+parameter_type_with_key! {
+	pub TransferFee: |currency_id: CurrencyId| -> Option<(Permill, AccountId)> {
+		match *currency_id {
+			FEE_TOKEN => Some((Permill::from_percent(10), TREASURY_ACCOUNT)),
+			_ => None,
+		}
+	};
+}
+
+pub struct MockTransferFeeExemptions;
+impl Contains<AccountId> for MockTransferFeeExemptions {
+	fn contains(a: &AccountId) -> bool {
+		*a == DustReceiver::get()
+	}
+}
+
 parameter_types! {
 	pub DustReceiver: AccountId = PalletId(*b"orml/dst").into_account();
 	pub MaxLocks: u32 = 2;
+	pub MaxReserves: u32 = 2;
+	pub const StorageDepositReserveId: ReserveIdentifier = RID_3;
+}
+
+thread_local! {
+	pub static NEW_TOKEN_ACCOUNT_VETOED: RefCell<bool> = RefCell::new(false);
+	pub static CREATED_TOKEN_ACCOUNTS: RefCell<Vec<(AccountId, CurrencyId)>> = RefCell::new(vec![]);
+	pub static KILLED_TOKEN_ACCOUNTS: RefCell<Vec<(AccountId, CurrencyId)>> = RefCell::new(vec![]);
+	pub static CHANGED_LOCKS: RefCell<Vec<(AccountId, CurrencyId, LockIdentifier, Balance)>> = RefCell::new(vec![]);
+	pub static SLASHED: RefCell<Vec<(AccountId, CurrencyId, Balance)>> = RefCell::new(vec![]);
+	pub static DEPOSITED: RefCell<Vec<(AccountId, CurrencyId, Balance)>> = RefCell::new(vec![]);
+	pub static TRANSFERRED: RefCell<Vec<(AccountId, AccountId, CurrencyId, Balance)>> = RefCell::new(vec![]);
+}
+
+pub const HOOK_WEIGHT: Weight = 100;
+
+pub struct VetoNewTokenAccount;
+impl TryHappened<(AccountId, CurrencyId)> for VetoNewTokenAccount {
+	fn try_happened(_: &(AccountId, CurrencyId)) -> DispatchResult {
+		if NEW_TOKEN_ACCOUNT_VETOED.with(|v| *v.borrow()) {
+			Err(DispatchError::Other("vetoed"))
+		} else {
+			Ok(())
+		}
+	}
+}
+
+pub struct TrackNewTokenAccount;
+impl TryHappened<(AccountId, CurrencyId)> for TrackNewTokenAccount {
+	fn try_happened(t: &(AccountId, CurrencyId)) -> DispatchResult {
+		CREATED_TOKEN_ACCOUNTS.with(|v| v.borrow_mut().push(t.clone()));
+		Ok(())
+	}
+}
+
+pub struct TrackKilledTokenAccount;
+impl Happened<(AccountId, CurrencyId)> for TrackKilledTokenAccount {
+	fn happened(t: &(AccountId, CurrencyId)) {
+		KILLED_TOKEN_ACCOUNTS.with(|v| v.borrow_mut().push(t.clone()));
+	}
+}
+
+pub struct TrackLockChanged;
+impl OnLockChanged<AccountId, CurrencyId, Balance> for TrackLockChanged {
+	fn on_lock_changed(who: &AccountId, currency_id: CurrencyId, lock_id: LockIdentifier, amount: Balance) -> Weight {
+		CHANGED_LOCKS.with(|v| v.borrow_mut().push((who.clone(), currency_id, lock_id, amount)));
+		HOOK_WEIGHT
+	}
+}
+
+pub struct TrackBalanceChanges;
+impl OnSlash<AccountId, CurrencyId, Balance> for TrackBalanceChanges {
+	fn on_slash(who: &AccountId, currency_id: CurrencyId, amount: Balance) -> Weight {
+		SLASHED.with(|v| v.borrow_mut().push((who.clone(), currency_id, amount)));
+		HOOK_WEIGHT
+	}
+}
+impl OnDeposit<AccountId, CurrencyId, Balance> for TrackBalanceChanges {
+	fn on_deposit(who: &AccountId, currency_id: CurrencyId, amount: Balance) -> Weight {
+		DEPOSITED.with(|v| v.borrow_mut().push((who.clone(), currency_id, amount)));
+		HOOK_WEIGHT
+	}
+}
+impl OnTransfer<AccountId, CurrencyId, Balance> for TrackBalanceChanges {
+	fn on_transfer(from: &AccountId, to: &AccountId, currency_id: CurrencyId, amount: Balance) -> Weight {
+		TRANSFERRED.with(|v| v.borrow_mut().push((from.clone(), to.clone(), currency_id, amount)));
+		HOOK_WEIGHT
+	}
 }
 
 impl Config for Runtime {
@@ -234,8 +323,18 @@ impl Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type OnDust = TransferDust<Runtime, DustReceiver>;
+	type OnSlash = TrackBalanceChanges;
+	type OnDeposit = TrackBalanceChanges;
+	type OnTransfer = TrackBalanceChanges;
 	type MaxLocks = MaxLocks;
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = ReserveIdentifier;
 	type DustRemovalWhitelist = MockDustRemovalWhitelist;
+	type OnNewTokenAccount = (VetoNewTokenAccount, TrackNewTokenAccount);
+	type OnKilledTokenAccount = TrackKilledTokenAccount;
+	type TransferFee = TransferFee;
+	type TransferFeeExemptions = MockTransferFeeExemptions;
+	type OnLockChanged = TrackLockChanged;
 }
 pub type TreasuryCurrencyAdapter = <Runtime as pallet_treasury::Config>::Currency;
 
@@ -6,6 +6,7 @@ use super::*;
 use frame_support::{assert_noop, assert_ok};
 use frame_system::RawOrigin;
 use mock::{Event, *};
+use orml_traits::{NamedReserveStorageDeposit, StorageDepositHandler};
 use sp_runtime::{traits::BadOrigin, TokenError};
 
 // *************************************************
@@ -61,6 +62,63 @@ fn transfer_should_work() {
 		});
 }
 
+#[test]
+fn transfer_should_refund_weight_by_code_path() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			let transfer_weight = <() as WeightInfo>::transfer();
+			let create_account_weight = <() as WeightInfo>::create_account();
+			let remove_dust_weight = <() as WeightInfo>::remove_dust();
+
+			// the `OnTransfer` hook is charged on top
+			// existing recipient, no dust removal
+			assert_eq!(
+				Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10)
+					.unwrap()
+					.actual_weight,
+				Some(transfer_weight - create_account_weight + HOOK_WEIGHT)
+			);
+
+			// new recipient, no dust removal
+			assert_eq!(
+				Tokens::transfer(Some(ALICE).into(), CHARLIE, DOT, 10)
+					.unwrap()
+					.actual_weight,
+				Some(transfer_weight + HOOK_WEIGHT)
+			);
+
+			// existing recipient, dust removal, the dust is transferred as well
+			assert_eq!(
+				Tokens::transfer(Some(ALICE).into(), BOB, DOT, 79)
+					.unwrap()
+					.actual_weight,
+				Some(transfer_weight - create_account_weight + remove_dust_weight + 2 * HOOK_WEIGHT)
+			);
+			assert!(!Accounts::<Runtime>::contains_key(ALICE, DOT));
+			// not registered to the block again
+			assert_eq!(System::block_weight().total(), 0);
+		});
+}
+
+#[test]
+fn transfer_should_register_hook_weight_above_declared_weight() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			// creating the recipient takes all the declared weight
+			assert_eq!(
+				Tokens::transfer_keep_alive(Some(ALICE).into(), BOB, DOT, 10)
+					.unwrap()
+					.actual_weight,
+				Some(<() as WeightInfo>::transfer_keep_alive())
+			);
+			assert_eq!(System::block_weight().total(), HOOK_WEIGHT);
+		});
+}
+
 #[test]
 fn transfer_keep_alive_should_work() {
 	ExtBuilder::default()
@@ -210,6 +268,143 @@ fn set_balance_should_work() {
 		});
 }
 
+#[test]
+fn set_blocked_status_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Tokens::set_blocked_status(Some(ALICE).into(), ALICE, Some(BlockedStatus::All)),
+				BadOrigin
+			);
+
+			assert_ok!(Tokens::set_blocked_status(
+				RawOrigin::Root.into(),
+				ALICE,
+				Some(BlockedStatus::Outgoing)
+			));
+			System::assert_last_event(Event::Tokens(crate::Event::BlockedStatusSet(
+				ALICE,
+				Some(BlockedStatus::Outgoing),
+			)));
+			assert_eq!(Tokens::blocked_accounts(ALICE), Some(BlockedStatus::Outgoing));
+
+			assert_ok!(Tokens::set_blocked_status(RawOrigin::Root.into(), ALICE, None));
+			System::assert_last_event(Event::Tokens(crate::Event::BlockedStatusSet(ALICE, None)));
+			assert_eq!(Tokens::blocked_accounts(ALICE), None);
+		});
+}
+
+#[test]
+fn sufficient_currency_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(Tokens::set_sufficient(Some(ALICE).into(), DOT, true), BadOrigin);
+		assert_ok!(Tokens::set_sufficient(RawOrigin::Root.into(), DOT, true));
+		System::assert_last_event(Event::Tokens(crate::Event::SufficientSet(DOT, true)));
+		assert!(Tokens::is_sufficient(DOT));
+		assert!(!Tokens::is_sufficient(BTC));
+
+		// accounts of sufficient currencies exist without providers
+		assert_ok!(Tokens::deposit(DOT, &ALICE, 100));
+		assert!(System::account_exists(&ALICE));
+		assert_eq!(System::sufficients(&ALICE), 1);
+		assert_eq!(System::providers(&ALICE), 0);
+
+		assert_ok!(Tokens::deposit(BTC, &ALICE, 100));
+		assert_eq!(System::providers(&ALICE), 1);
+
+		// the sufficient reference is released even if the currency is no longer
+		// sufficient
+		assert_ok!(Tokens::set_sufficient(RawOrigin::Root.into(), DOT, false));
+		assert_ok!(Tokens::withdraw(DOT, &ALICE, 100));
+		assert_eq!(System::sufficients(&ALICE), 0);
+		assert_eq!(System::providers(&ALICE), 1);
+
+		assert_ok!(Tokens::deposit(DOT, &ALICE, 100));
+		assert_eq!(System::sufficients(&ALICE), 0);
+		assert_eq!(System::providers(&ALICE), 2);
+	});
+}
+
+#[test]
+fn outgoing_blocked_account_could_only_receive() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::set_blocked_status(
+				RawOrigin::Root.into(),
+				ALICE,
+				Some(BlockedStatus::Outgoing)
+			));
+
+			assert_eq!(
+				Tokens::transfer(Some(ALICE).into(), BOB, DOT, 50),
+				Err(Error::<Runtime>::AccountBlocked.into())
+			);
+			assert_eq!(
+				Tokens::withdraw(DOT, &ALICE, 50),
+				Err(Error::<Runtime>::AccountBlocked.into())
+			);
+			assert_eq!(
+				Tokens::reserve(DOT, &ALICE, 50),
+				Err(Error::<Runtime>::AccountBlocked.into())
+			);
+			assert!(!Tokens::can_reserve(DOT, &ALICE, 50));
+			assert_eq!(
+				Tokens::withdraw_consequence(&ALICE, DOT, 50, &Tokens::accounts(ALICE, DOT)),
+				WithdrawConsequence::Frozen
+			);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 100);
+
+			// still could receive
+			assert_ok!(Tokens::transfer(Some(BOB).into(), ALICE, DOT, 50));
+			assert_ok!(Tokens::deposit(DOT, &ALICE, 50));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 200);
+		});
+}
+
+#[test]
+fn all_blocked_account_could_not_move_funds() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve(DOT, &BOB, 50));
+			assert_ok!(Tokens::set_blocked_status(
+				RawOrigin::Root.into(),
+				ALICE,
+				Some(BlockedStatus::All)
+			));
+
+			assert_eq!(
+				Tokens::transfer(Some(BOB).into(), ALICE, DOT, 10),
+				Err(Error::<Runtime>::AccountBlocked.into())
+			);
+			assert_eq!(
+				Tokens::deposit(DOT, &ALICE, 10),
+				Err(Error::<Runtime>::AccountBlocked.into())
+			);
+			assert_eq!(
+				Tokens::repatriate_reserved(DOT, &BOB, &ALICE, 10, BalanceStatus::Free),
+				Err(Error::<Runtime>::AccountBlocked.into())
+			);
+			assert_eq!(
+				Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10),
+				Err(Error::<Runtime>::AccountBlocked.into())
+			);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 100);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 50);
+
+			// blocks accounts across currencies
+			assert_eq!(
+				Tokens::deposit(BTC, &ALICE, 10),
+				Err(Error::<Runtime>::AccountBlocked.into())
+			);
+		});
+}
+
 // *************************************************
 // tests for inline impl
 // *************************************************
@@ -578,6 +773,57 @@ fn do_transfer_should_work() {
 		});
 }
 
+#[test]
+fn do_transfer_should_charge_transfer_fee() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, FEE_TOKEN, 100), (ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::do_transfer(
+				FEE_TOKEN,
+				&ALICE,
+				&BOB,
+				50,
+				ExistenceRequirement::AllowDeath
+			));
+			System::assert_last_event(Event::Tokens(crate::Event::FeeCharged(
+				FEE_TOKEN,
+				ALICE,
+				TREASURY_ACCOUNT,
+				5,
+			)));
+			assert_eq!(Tokens::free_balance(FEE_TOKEN, &ALICE), 50);
+			assert_eq!(Tokens::free_balance(FEE_TOKEN, &BOB), 45);
+			assert_eq!(Tokens::free_balance(FEE_TOKEN, &TREASURY_ACCOUNT), 5);
+
+			// exempted accounts and other currencies are free
+			assert_ok!(Tokens::do_transfer(
+				FEE_TOKEN,
+				&ALICE,
+				&DustReceiver::get(),
+				10,
+				ExistenceRequirement::AllowDeath
+			));
+			assert_ok!(Tokens::do_transfer(
+				FEE_TOKEN,
+				&BOB,
+				&TREASURY_ACCOUNT,
+				10,
+				ExistenceRequirement::AllowDeath
+			));
+			assert_ok!(Tokens::do_transfer(
+				DOT,
+				&ALICE,
+				&BOB,
+				50,
+				ExistenceRequirement::AllowDeath
+			));
+			assert_eq!(Tokens::free_balance(FEE_TOKEN, &DustReceiver::get()), 10);
+			assert_eq!(Tokens::free_balance(FEE_TOKEN, &TREASURY_ACCOUNT), 15);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 50);
+		});
+}
+
 #[test]
 fn do_transfer_dust_removal_when_allow_death() {
 	ExtBuilder::default()
@@ -1064,6 +1310,181 @@ fn account_survive_due_to_dust_transfer_failure() {
 	});
 }
 
+#[test]
+fn token_account_handlers_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 50));
+			assert_ok!(Tokens::deposit(BTC, &CHARLIE, 10));
+			assert_eq!(
+				CREATED_TOKEN_ACCOUNTS.with(|v| v.borrow().clone()),
+				vec![(BOB, DOT), (CHARLIE, BTC)]
+			);
+
+			assert_ok!(Tokens::transfer(Some(BOB).into(), ALICE, DOT, 50));
+			assert_eq!(KILLED_TOKEN_ACCOUNTS.with(|v| v.borrow().clone()), vec![(BOB, DOT)]);
+
+			// vetoed by the first handler, the second one is not called
+			NEW_TOKEN_ACCOUNT_VETOED.with(|v| *v.borrow_mut() = true);
+			assert_noop!(
+				Tokens::transfer(Some(ALICE).into(), DAVE, DOT, 50),
+				DispatchError::Other("vetoed")
+			);
+			assert_noop!(Tokens::deposit(DOT, &DAVE, 10), DispatchError::Other("vetoed"));
+			// any new account is vetoed, not only by transfers and deposits
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 10));
+			assert_noop!(
+				Tokens::repatriate_reserved(DOT, &ALICE, &DAVE, 10, BalanceStatus::Free),
+				DispatchError::Other("vetoed")
+			);
+			assert_noop!(
+				<Tokens as fungibles::Unbalanced<_>>::set_balance(DOT, &DAVE, 10),
+				DispatchError::Other("vetoed")
+			);
+			TreasuryCurrencyAdapter::make_free_balance_be(&DAVE, 10);
+			assert!(!Accounts::<Runtime>::contains_key(DAVE, DOT));
+			assert_eq!(CREATED_TOKEN_ACCOUNTS.with(|v| v.borrow().len()), 2);
+
+			// deposits to existing accounts are not vetoed
+			assert_ok!(Tokens::deposit(BTC, &CHARLIE, 10));
+		});
+}
+
+#[test]
+fn lock_changed_handler_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 10));
+			assert_ok!(Tokens::extend_lock(ID_1, DOT, &ALICE, 20));
+			assert_ok!(Tokens::extend_lock(ID_1, DOT, &ALICE, 5));
+			assert_ok!(Tokens::remove_lock(ID_1, DOT, &ALICE));
+			// removing a missing lock is not reported
+			assert_ok!(Tokens::remove_lock(ID_2, DOT, &ALICE));
+			assert_eq!(
+				CHANGED_LOCKS.with(|v| v.borrow().clone()),
+				vec![
+					(ALICE, DOT, ID_1, 10),
+					(ALICE, DOT, ID_1, 20),
+					(ALICE, DOT, ID_1, 20),
+					(ALICE, DOT, ID_1, 0)
+				]
+			);
+			assert_eq!(System::block_weight().total(), 4 * HOOK_WEIGHT);
+		});
+}
+
+#[test]
+fn balance_hooks_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::deposit(DOT, &BOB, 50));
+			assert_ok!(<Tokens as MultiCurrency<_>>::transfer(DOT, &ALICE, &BOB, 10));
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 30));
+			assert_eq!(<Tokens as MultiCurrency<_>>::slash(DOT, &ALICE, 70), 0);
+			assert_eq!(Tokens::slash_reserved(DOT, &ALICE, 30), 10);
+			// nothing left to slash is not reported
+			assert_eq!(Tokens::slash_reserved(DOT, &ALICE, 10), 10);
+
+			assert_eq!(DEPOSITED.with(|v| v.borrow().clone()), vec![(BOB, DOT, 50)]);
+			assert_eq!(TRANSFERRED.with(|v| v.borrow().clone()), vec![(ALICE, BOB, DOT, 10)]);
+			assert_eq!(
+				SLASHED.with(|v| v.borrow().clone()),
+				vec![(ALICE, DOT, 70), (ALICE, DOT, 20)]
+			);
+			assert_eq!(System::block_weight().total(), 4 * HOOK_WEIGHT);
+		});
+}
+
+#[test]
+fn dust_hook_should_not_overwrite_transfer_recipient() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			let dust_receiver = DustReceiver::get();
+			// the dust of ALICE goes to the recipient of the transfer leaving it, which
+			// must not be overwritten by the transfer
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), dust_receiver.clone(), DOT, 99));
+			System::assert_has_event(Event::Tokens(crate::Event::DustLost(DOT, ALICE, 1)));
+			assert!(!Accounts::<Runtime>::contains_key(ALICE, DOT));
+			assert_eq!(Tokens::free_balance(DOT, &dust_receiver), 100);
+			assert_eq!(Tokens::total_issuance(DOT), 100);
+			assert_ok!(Tokens::do_try_state());
+		});
+}
+
+#[test]
+fn with_deferred_hooks_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert!(!MutateContext::is_active());
+			assert_ok!(Tokens::with_deferred_hooks(|| -> DispatchResult {
+				assert!(MutateContext::is_active());
+				<Tokens as MultiCurrency<_>>::transfer(DOT, &ALICE, &BOB, 50)?;
+				<Tokens as MultiCurrency<_>>::transfer(DOT, &BOB, &CHARLIE, 50)?;
+				// the hooks run once all changes are done
+				assert!(TRANSFERRED.with(|v| v.borrow().is_empty()));
+				assert!(KILLED_TOKEN_ACCOUNTS.with(|v| v.borrow().is_empty()));
+				Ok(())
+			}));
+			assert!(!MutateContext::is_active());
+			assert_eq!(
+				TRANSFERRED.with(|v| v.borrow().clone()),
+				vec![(ALICE, BOB, DOT, 50), (BOB, CHARLIE, DOT, 50)]
+			);
+			assert_eq!(KILLED_TOKEN_ACCOUNTS.with(|v| v.borrow().clone()), vec![(BOB, DOT)]);
+
+			// the hooks of a failed call are dropped
+			assert_eq!(
+				Tokens::with_deferred_hooks(|| -> DispatchResult {
+					<Tokens as MultiCurrency<_>>::transfer(DOT, &CHARLIE, &ALICE, 10)?;
+					Err(DispatchError::Other("failed"))
+				}),
+				Err(DispatchError::Other("failed"))
+			);
+			assert_eq!(TRANSFERRED.with(|v| v.borrow().len()), 2);
+		});
+}
+
+#[test]
+fn offchain_index_records_should_work() {
+	let mut ext = ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build();
+	ext.execute_with(|| {
+		assert_ok!(Tokens::do_transfer(
+			DOT,
+			&ALICE,
+			&BOB,
+			99,
+			ExistenceRequirement::AllowDeath
+		));
+	});
+	ext.persist_offchain_overlay();
+
+	let db = ext.offchain_db();
+	assert_eq!(
+		db.get(&Tokens::offchain_index_key(OFFCHAIN_DUST_PREFIX, 1, &ALICE, DOT)),
+		Some(1u64.encode())
+	);
+	assert_eq!(
+		db.get(&Tokens::offchain_index_key(OFFCHAIN_REAPED_PREFIX, 1, &ALICE, DOT)),
+		Some(().encode())
+	);
+	assert_eq!(
+		db.get(&Tokens::offchain_index_key(OFFCHAIN_REAPED_PREFIX, 1, &BOB, DOT)),
+		None
+	);
+}
+
 // *************************************************
 // tests for MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency,
 // MultiReservableCurrency traits **********************************************
@@ -1379,6 +1800,148 @@ fn slash_draw_reserved_correct() {
 		});
 }
 
+#[test]
+fn named_multi_reservable_currency_reserve_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 0));
+			assert_eq!(Tokens::reserves(ALICE, DOT).len(), 0);
+
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 30));
+			assert_ok!(Tokens::reserve_named(&RID_2, DOT, &ALICE, 20));
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 10));
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 40);
+			assert_eq!(Tokens::reserved_balance_named(&RID_2, DOT, &ALICE), 20);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 60);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 40);
+
+			// exceeds max reserves
+			assert_noop!(
+				Tokens::reserve_named(&RID_3, DOT, &ALICE, 10),
+				Error::<Runtime>::TooManyReserves
+			);
+			// not enough free balance
+			assert_noop!(
+				Tokens::reserve_named(&RID_1, DOT, &ALICE, 50),
+				Error::<Runtime>::BalanceTooLow
+			);
+
+			assert_eq!(Tokens::unreserve_named(&RID_1, DOT, &ALICE, 50), 10);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 0);
+			assert_eq!(Tokens::reserves(ALICE, DOT).len(), 1);
+			assert_eq!(Tokens::unreserve_named(&RID_3, DOT, &ALICE, 10), 10);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 80);
+
+			assert_eq!(Tokens::slash_reserved_named(&RID_2, DOT, &ALICE, 15), 0);
+			assert_eq!(Tokens::reserved_balance_named(&RID_2, DOT, &ALICE), 5);
+			assert_eq!(Tokens::total_issuance(DOT), 85);
+
+			assert_ok!(Tokens::ensure_reserved_named(&RID_2, DOT, &ALICE, 25));
+			assert_eq!(Tokens::reserved_balance_named(&RID_2, DOT, &ALICE), 25);
+			assert_eq!(Tokens::unreserve_all_named(&RID_2, DOT, &ALICE), 25);
+			assert!(!Reserves::<Runtime>::contains_key(ALICE, DOT));
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 85);
+		});
+}
+
+#[test]
+fn named_multi_reservable_currency_repatriate_reserved_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &BOB, 50));
+			assert_eq!(
+				Tokens::repatriate_reserved_named(&RID_1, DOT, &BOB, &BOB, 60, BalanceStatus::Reserved),
+				Ok(10)
+			);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &BOB), 50);
+
+			assert_eq!(
+				Tokens::repatriate_reserved_named(&RID_1, DOT, &BOB, &ALICE, 30, BalanceStatus::Reserved),
+				Ok(0)
+			);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 30);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 30);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &BOB), 20);
+
+			assert_eq!(
+				Tokens::repatriate_reserved_named(&RID_1, DOT, &BOB, &ALICE, 30, BalanceStatus::Free),
+				Ok(10)
+			);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 90);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &BOB), 0);
+			assert_eq!(Tokens::reserved_balance(DOT, &BOB), 0);
+		});
+}
+
+#[test]
+fn locks_of_and_reserves_of_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(Tokens::locks_of(&ALICE), vec![]);
+			assert_eq!(Tokens::reserves_of(&ALICE), vec![]);
+
+			assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 10));
+			assert_ok!(Tokens::set_lock(ID_2, DOT, &ALICE, 20));
+			assert_ok!(Tokens::set_lock(ID_1, BTC, &ALICE, 30));
+			assert_ok!(Tokens::reserve_named(&RID_1, BTC, &ALICE, 40));
+
+			let mut locks = Tokens::locks_of(&ALICE);
+			locks.sort_by_key(|(currency_id, _)| *currency_id);
+			assert_eq!(
+				locks,
+				vec![
+					(
+						DOT,
+						vec![
+							BalanceLock { id: ID_1, amount: 10 },
+							BalanceLock { id: ID_2, amount: 20 }
+						]
+					),
+					(BTC, vec![BalanceLock { id: ID_1, amount: 30 }]),
+				]
+			);
+			assert_eq!(
+				Tokens::reserves_of(&ALICE),
+				vec![(BTC, vec![ReserveData { id: RID_1, amount: 40 }])]
+			);
+			assert_eq!(Tokens::locks_of(&BOB), vec![]);
+		});
+}
+
+#[test]
+fn named_reserve_storage_deposit_should_work() {
+	type StorageDeposit = NamedReserveStorageDeposit<Tokens, StorageDepositReserveId>;
+
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 10));
+			assert_ok!(StorageDeposit::take_deposit(DOT, &ALICE, 20));
+			assert_ok!(StorageDeposit::take_deposit(BTC, &ALICE, 30));
+			assert_eq!(StorageDeposit::deposit_of(DOT, &ALICE), 20);
+			assert_eq!(StorageDeposit::deposit_of(BTC, &ALICE), 30);
+			assert_eq!(
+				Tokens::reserved_balance_named(&StorageDepositReserveId::get(), DOT, &ALICE),
+				20
+			);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 30);
+
+			// only the storage deposit is refunded
+			assert_eq!(StorageDeposit::refund_deposit(DOT, &ALICE, 25), 5);
+			assert_eq!(StorageDeposit::deposit_of(DOT, &ALICE), 0);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 10);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 90);
+		});
+}
+
 #[test]
 fn no_op_if_amount_is_zero() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -1989,14 +2552,61 @@ fn exceeding_max_locks_should_fail() {
 			assert_eq!(Tokens::locks(ALICE, DOT).len(), 1);
 			assert_ok!(Tokens::set_lock(ID_2, DOT, &ALICE, 10));
 			assert_eq!(Tokens::locks(ALICE, DOT).len(), 2);
-			assert_noop!(
-				Tokens::set_lock(ID_3, DOT, &ALICE, 10),
-				Error::<Runtime>::MaxLocksExceeded
-			);
+			assert_noop!(Tokens::set_lock(ID_3, DOT, &ALICE, 10), Error::<Runtime>::TooManyLocks);
 			assert_eq!(Tokens::locks(ALICE, DOT).len(), 2);
 		});
 }
 
+#[test]
+fn exceeding_max_locks_should_not_change_frozen_balance() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 10));
+			assert_ok!(Tokens::set_lock(ID_2, DOT, &ALICE, 10));
+			assert_noop!(Tokens::set_lock(ID_3, DOT, &ALICE, 50), Error::<Runtime>::TooManyLocks);
+			assert_eq!(Tokens::accounts(ALICE, DOT).frozen, 10);
+		});
+}
+
+#[test]
+fn consolidate_locks_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 10));
+
+			// nothing to merge
+			assert_ok!(Tokens::consolidate_locks(Some(ALICE).into(), DOT));
+			assert_eq!(Tokens::locks(ALICE, DOT).len(), 1);
+
+			let locks: BoundedVec<_, MaxLocks> = vec![
+				BalanceLock { id: ID_1, amount: 10 },
+				BalanceLock { id: ID_1, amount: 20 },
+			]
+			.try_into()
+			.unwrap();
+			Locks::<Runtime>::insert(ALICE, DOT, locks);
+
+			// charged by the number of locks before merging
+			assert_eq!(
+				Tokens::consolidate_locks(Some(ALICE).into(), DOT)
+					.unwrap()
+					.actual_weight,
+				Some(<() as WeightInfo>::consolidate_locks(2))
+			);
+			assert_eq!(
+				Tokens::locks(ALICE, DOT).into_inner(),
+				vec![BalanceLock { id: ID_1, amount: 20 }]
+			);
+			assert_eq!(Tokens::accounts(ALICE, DOT).frozen, 20);
+			System::assert_last_event(Event::Tokens(crate::Event::LocksConsolidated(DOT, ALICE, 1)));
+		});
+}
+
 // *************************************************
 // tests for fungibles traits
 // *************************************************
@@ -2297,3 +2907,337 @@ fn fungibles_mutate_convert_should_work() {
 			);
 		});
 }
+
+#[test]
+fn fungibles_adapters_should_work() {
+	type AdaptedTokens = orml_traits::FungiblesAdapter<Tokens>;
+	type TokensAsFungibles = orml_traits::MultiCurrencyAsFungibles<Tokens>;
+
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			// `MultiCurrency` over `fungibles`
+			assert_ok!(<AdaptedTokens as MultiCurrency<_>>::transfer(DOT, &ALICE, &BOB, 10));
+			assert_ok!(<AdaptedTokens as MultiReservableCurrency<_>>::reserve(DOT, &ALICE, 30));
+			assert_eq!(<AdaptedTokens as MultiCurrency<_>>::free_balance(DOT, &ALICE), 60);
+			assert_eq!(<AdaptedTokens as MultiCurrency<_>>::total_balance(DOT, &ALICE), 90);
+			assert_eq!(
+				<AdaptedTokens as MultiReservableCurrency<_>>::reserved_balance(DOT, &ALICE),
+				30
+			);
+			assert_eq!(
+				<AdaptedTokens as MultiReservableCurrency<_>>::repatriate_reserved(
+					DOT,
+					&ALICE,
+					&BOB,
+					10,
+					orml_traits::BalanceStatus::Free
+				),
+				Ok(0)
+			);
+			assert_eq!(
+				<AdaptedTokens as MultiReservableCurrency<_>>::unreserve(DOT, &ALICE, 30),
+				10
+			);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 80);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 120);
+
+			// `fungibles` over `MultiCurrency`
+			assert_ok!(<TokensAsFungibles as fungibles::Transfer<_>>::transfer(
+				DOT, &BOB, &ALICE, 20, true
+			));
+			assert_noop!(
+				<TokensAsFungibles as fungibles::Transfer<_>>::transfer(DOT, &BOB, &ALICE, 100, true),
+				Error::<Runtime>::KeepAlive
+			);
+			assert_ok!(<TokensAsFungibles as fungibles::MutateHold<_>>::hold(DOT, &ALICE, 50));
+			assert_eq!(
+				<TokensAsFungibles as fungibles::InspectHold<_>>::balance_on_hold(DOT, &ALICE),
+				50
+			);
+			assert_eq!(<TokensAsFungibles as fungibles::Inspect<_>>::balance(DOT, &ALICE), 100);
+			assert_noop!(
+				<TokensAsFungibles as fungibles::MutateHold<_>>::release(DOT, &ALICE, 60, false),
+				TokenError::NoFunds
+			);
+			assert_eq!(
+				<TokensAsFungibles as fungibles::MutateHold<_>>::release(DOT, &ALICE, 60, true),
+				Ok(50)
+			);
+			assert_eq!(
+				<TokensAsFungibles as fungibles::Inspect<_>>::reducible_balance(DOT, &ALICE, true),
+				98
+			);
+		});
+}
+
+#[test]
+fn fungibles_adapter_slash_reserved_should_keep_funds_held_if_not_burned() {
+	type AdaptedTokens = orml_traits::FungiblesAdapter<Tokens>;
+
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(<AdaptedTokens as MultiReservableCurrency<_>>::reserve(DOT, &ALICE, 50));
+			assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 100));
+
+			// the released funds are frozen, nothing is slashed
+			assert_eq!(
+				<AdaptedTokens as MultiReservableCurrency<_>>::slash_reserved(DOT, &ALICE, 30),
+				30
+			);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 50);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 50);
+			assert_eq!(Tokens::total_issuance(DOT), 100);
+
+			assert_ok!(Tokens::remove_lock(ID_1, DOT, &ALICE));
+			assert_eq!(
+				<AdaptedTokens as MultiReservableCurrency<_>>::slash_reserved(DOT, &ALICE, 30),
+				0
+			);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 20);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 50);
+			assert_eq!(Tokens::total_issuance(DOT), 70);
+		});
+}
+
+// *************************************************
+// tests for invariant checks
+// *************************************************
+
+#[test]
+fn do_try_state_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::do_try_state());
+
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), CHARLIE, DOT, 50));
+			assert_ok!(Tokens::reserve(DOT, &BOB, 30));
+			assert_ok!(Tokens::set_lock(ID_1, BTC, &ALICE, 50));
+			assert_ok!(Tokens::deposit(ETH, &DAVE, 100));
+			assert_ok!(Tokens::withdraw(BTC, &ALICE, 20));
+			assert_ok!(Tokens::do_try_state());
+		});
+}
+
+#[test]
+fn do_try_state_should_report_violations() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			Accounts::<Runtime>::mutate(ALICE, DOT, |account| {
+				account.free = 50;
+				account.frozen = 60;
+			});
+			Accounts::<Runtime>::mutate(BOB, BTC, |account| {
+				account.free = Balance::max_value();
+				account.reserved = 1;
+			});
+
+			let violations = Tokens::do_try_state().unwrap_err();
+			assert_eq!(violations.len(), 3);
+			assert!(violations.contains(&InvariantViolation::FrozenExceedsFree {
+				who: ALICE,
+				currency_id: DOT,
+			}));
+			assert!(violations.contains(&InvariantViolation::ReservedExceedsTotal {
+				who: BOB,
+				currency_id: BTC,
+			}));
+			assert!(violations.contains(&InvariantViolation::TotalIssuanceMismatch {
+				currency_id: DOT,
+				total_issuance: 200,
+				accounts_total: 150,
+			}));
+		});
+}
+
+// *************************************************
+// tests for migration
+// *************************************************
+
+#[test]
+fn migrate_to_tokens_should_work() {
+	use crate::migration::{BalanceSource, MigrateToTokens, SourceBalance};
+	use frame_support::traits::OnRuntimeUpgrade;
+	use sp_std::cell::RefCell;
+
+	thread_local! {
+		static SOURCE: RefCell<Vec<SourceBalance<AccountId, CurrencyId, Balance>>> = RefCell::new(vec![
+			SourceBalance { who: ALICE, currency_id: DOT, free: 50, reserved: 10 },
+			SourceBalance { who: CHARLIE, currency_id: DOT, free: 100, reserved: 0 },
+			SourceBalance { who: BOB, currency_id: BTC, free: 0, reserved: 20 },
+			SourceBalance { who: DAVE, currency_id: ETH, free: 30, reserved: 0 },
+		]);
+	}
+
+	pub struct MockSource;
+	impl BalanceSource<AccountId, CurrencyId, Balance> for MockSource {
+		fn balances() -> Vec<SourceBalance<AccountId, CurrencyId, Balance>> {
+			SOURCE.with(|v| v.borrow().clone())
+		}
+
+		fn drain() -> (Vec<SourceBalance<AccountId, CurrencyId, Balance>>, Weight) {
+			(SOURCE.with(|v| v.borrow_mut().drain(..).collect()), 0)
+		}
+	}
+
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			MigrateToTokens::<Runtime, MockSource>::on_runtime_upgrade();
+			assert!(MockSource::balances().is_empty());
+
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 150);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 10);
+			assert_eq!(Tokens::free_balance(DOT, &CHARLIE), 100);
+			assert_eq!(Tokens::total_issuance(DOT), 260);
+			assert_eq!(Tokens::reserved_balance(BTC, &BOB), 20);
+			assert_eq!(Tokens::total_issuance(BTC), 20);
+			assert_eq!(Tokens::free_balance(ETH, &DAVE), 30);
+			assert_eq!(Tokens::total_issuance(ETH), 30);
+			assert_ok!(Tokens::do_try_state());
+		});
+}
+
+#[test]
+fn bound_locks_and_reserves_should_work() {
+	use crate::migration::BoundLocksAndReserves;
+	use frame_support::{
+		storage::unhashed,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			StorageVersion::new(0).put::<Tokens>();
+			let locks = vec![
+				BalanceLock { id: ID_1, amount: 10 },
+				BalanceLock { id: ID_2, amount: 30 },
+				BalanceLock { id: ID_3, amount: 20 },
+			];
+			unhashed::put(&Locks::<Runtime>::hashed_key_for(ALICE, DOT), &locks);
+			unhashed::put(&Locks::<Runtime>::hashed_key_for(BOB, DOT), &locks[..1].to_vec());
+			let reserves = vec![
+				ReserveData { id: RID_1, amount: 5 },
+				ReserveData { id: RID_2, amount: 15 },
+				ReserveData { id: RID_3, amount: 10 },
+			];
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 30));
+			unhashed::put(&Reserves::<Runtime>::hashed_key_for(ALICE, DOT), &reserves);
+
+			BoundLocksAndReserves::<Runtime>::on_runtime_upgrade();
+			assert_eq!(Tokens::on_chain_storage_version(), StorageVersion::new(1));
+			assert_eq!(
+				Tokens::locks(ALICE, DOT).into_inner(),
+				vec![
+					BalanceLock { id: ID_2, amount: 30 },
+					BalanceLock { id: ID_3, amount: 20 }
+				]
+			);
+			assert_eq!(
+				Tokens::locks(BOB, DOT).into_inner(),
+				vec![BalanceLock { id: ID_1, amount: 10 }]
+			);
+			assert_eq!(
+				Tokens::reserves(ALICE, DOT).into_inner(),
+				vec![
+					ReserveData { id: RID_2, amount: 15 },
+					ReserveData { id: RID_3, amount: 10 }
+				]
+			);
+			// the dropped named reserve is unreserved
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 25);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 75);
+			assert_eq!(Tokens::total_balance(DOT, &ALICE), 100);
+			assert_eq!(Tokens::total_issuance(DOT), 100);
+		});
+}
+
+#[test]
+fn confiscation_of_accounts_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100), (CHARLIE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 10));
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 20));
+			assert_noop!(
+				Tokens::schedule_confiscation(
+					Some(ALICE).into(),
+					DOT,
+					ConfiscationTargets::Accounts(vec![ALICE]),
+					TREASURY_ACCOUNT
+				),
+				BadOrigin
+			);
+
+			assert_ok!(Tokens::schedule_confiscation(
+				Origin::root(),
+				DOT,
+				ConfiscationTargets::Accounts(vec![ALICE, BOB]),
+				TREASURY_ACCOUNT
+			));
+			System::assert_last_event(Event::Tokens(crate::Event::ConfiscationScheduled(
+				0,
+				DOT,
+				TREASURY_ACCOUNT,
+			)));
+
+			// one account per block, removing the lock of ALICE calls the hook
+			let step_weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1)
+				+ <Runtime as Config>::WeightInfo::confiscate_account();
+			assert_eq!(Tokens::on_idle(1, step_weight - 1), 0);
+			assert_eq!(Tokens::on_idle(1, step_weight), step_weight + HOOK_WEIGHT);
+			assert_eq!(Tokens::free_balance(DOT, &TREASURY_ACCOUNT), 100);
+			assert!(Tokens::confiscations(0).is_some());
+
+			assert_ok!(Tokens::schedule_confiscation(
+				Origin::root(),
+				DOT,
+				ConfiscationTargets::Accounts(vec![CHARLIE]),
+				TREASURY_ACCOUNT
+			));
+			Tokens::on_idle(2, Weight::max_value());
+			System::assert_has_event(Event::Tokens(crate::Event::ConfiscationCompleted(0, DOT, 200)));
+			System::assert_has_event(Event::Tokens(crate::Event::ConfiscationCompleted(1, DOT, 100)));
+			assert_eq!(Tokens::confiscations(0), None);
+			assert_eq!(Tokens::confiscations(1), None);
+
+			assert_eq!(Tokens::free_balance(DOT, &TREASURY_ACCOUNT), 300);
+			assert_eq!(Tokens::total_balance(DOT, &ALICE), 0);
+			assert_eq!(Tokens::total_balance(DOT, &BOB), 0);
+			assert!(Tokens::locks(ALICE, DOT).is_empty());
+			assert_eq!(Tokens::total_issuance(DOT), 300);
+		});
+}
+
+#[test]
+fn confiscation_of_all_holders_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 50), (BOB, BTC, 30)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::schedule_confiscation(
+				Origin::root(),
+				BTC,
+				ConfiscationTargets::AllHolders,
+				TREASURY_ACCOUNT
+			));
+			Tokens::on_idle(1, Weight::max_value());
+			System::assert_has_event(Event::Tokens(crate::Event::Confiscated(0, BTC, BOB, 30)));
+			System::assert_last_event(Event::Tokens(crate::Event::ConfiscationCompleted(0, BTC, 80)));
+
+			assert_eq!(Tokens::free_balance(BTC, &TREASURY_ACCOUNT), 80);
+			assert_eq!(Tokens::free_balance(BTC, &ALICE), 0);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 100);
+		});
+}
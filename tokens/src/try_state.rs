@@ -0,0 +1,100 @@
+//! Invariant checks on the accounting of the tokens module.
+//!
+//! `Pallet::do_try_state` walks `Accounts` and `TotalIssuance`, and reports
+//! every violation of the invariants below. It's called after runtime
+//! upgrades with the `try-runtime` feature, and could be called in tests.
+//!
+//! - The sum of `free + reserved` over all accounts of a currency equals its
+//!   `TotalIssuance`.
+//! - `reserved` doesn't exceed the account total, i.e. `free + reserved`
+//!   doesn't overflow.
+//! - `frozen` doesn't exceed `free`.
+
+use crate::{Accounts, Config, Pallet, TotalIssuance};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{CheckedAdd, Saturating, Zero},
+	RuntimeDebug,
+};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+/// A violation of the tokens module invariants.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum InvariantViolation<AccountId, CurrencyId, Balance> {
+	/// The sum of account totals differs from the total issuance.
+	TotalIssuanceMismatch {
+		currency_id: CurrencyId,
+		total_issuance: Balance,
+		accounts_total: Balance,
+	},
+	/// The reserved balance exceeds the account total.
+	ReservedExceedsTotal { who: AccountId, currency_id: CurrencyId },
+	/// The frozen balance exceeds the free balance.
+	FrozenExceedsFree { who: AccountId, currency_id: CurrencyId },
+}
+
+impl<AccountId, CurrencyId, Balance> InvariantViolation<AccountId, CurrencyId, Balance> {
+	/// A short description of the violation.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			InvariantViolation::TotalIssuanceMismatch { .. } => "sum of account totals differs from total issuance",
+			InvariantViolation::ReservedExceedsTotal { .. } => "reserved balance exceeds account total",
+			InvariantViolation::FrozenExceedsFree { .. } => "frozen balance exceeds free balance",
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Check the invariants of the tokens module, returns all violations
+	/// found.
+	pub fn do_try_state() -> Result<(), Vec<InvariantViolation<T::AccountId, T::CurrencyId, T::Balance>>> {
+		let mut violations = Vec::new();
+		let mut accounts_totals: BTreeMap<T::CurrencyId, T::Balance> = BTreeMap::new();
+
+		for (who, currency_id, account) in Accounts::<T>::iter() {
+			match account.free.checked_add(&account.reserved) {
+				Some(total) => {
+					let accounts_total = accounts_totals.entry(currency_id).or_insert_with(Zero::zero);
+					*accounts_total = accounts_total.saturating_add(total);
+				}
+				None => violations.push(InvariantViolation::ReservedExceedsTotal {
+					who: who.clone(),
+					currency_id,
+				}),
+			}
+
+			if account.frozen > account.free {
+				violations.push(InvariantViolation::FrozenExceedsFree { who, currency_id });
+			}
+		}
+
+		for (currency_id, total_issuance) in TotalIssuance::<T>::iter() {
+			let accounts_total = accounts_totals.remove(&currency_id).unwrap_or_else(Zero::zero);
+			if accounts_total != total_issuance {
+				violations.push(InvariantViolation::TotalIssuanceMismatch {
+					currency_id,
+					total_issuance,
+					accounts_total,
+				});
+			}
+		}
+
+		// currencies held by accounts but without total issuance
+		for (currency_id, accounts_total) in accounts_totals {
+			if !accounts_total.is_zero() {
+				violations.push(InvariantViolation::TotalIssuanceMismatch {
+					currency_id,
+					total_issuance: Zero::zero(),
+					accounts_total,
+				});
+			}
+		}
+
+		if violations.is_empty() {
+			Ok(())
+		} else {
+			Err(violations)
+		}
+	}
+}
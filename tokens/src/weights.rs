@@ -1,6 +1,10 @@
-//! Autogenerated weights for orml_tokens
+//! Weights for orml_tokens
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! `transfer`, `transfer_all`, `transfer_keep_alive`, `force_transfer` and
+//! `set_balance` were generated with the command below, the other weights are
+//! estimates until they are generated from the benchmarks in `benchmarking.rs`.
+//!
+//! GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2021-09-14, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
 
@@ -34,6 +38,13 @@ pub trait WeightInfo {
 	fn transfer_keep_alive() -> Weight;
 	fn force_transfer() -> Weight;
 	fn set_balance() -> Weight;
+	fn set_blocked_status() -> Weight;
+	fn set_sufficient() -> Weight;
+	fn create_account() -> Weight;
+	fn remove_dust() -> Weight;
+	fn consolidate_locks(l: u32, ) -> Weight;
+	fn schedule_confiscation(i: u32, ) -> Weight;
+	fn confiscate_account() -> Weight;
 }
 
 /// Default weights.
@@ -63,4 +74,50 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
+	// Estimated, not generated.
+	fn set_blocked_status() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Estimated, not generated.
+	fn set_sufficient() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Estimated, not generated: regenerate with the `create_account`
+	// benchmark in `benchmarking.rs`.
+	fn create_account() -> Weight {
+		(8_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Estimated, not generated: regenerate with the `remove_dust`
+	// benchmark in `benchmarking.rs`.
+	fn remove_dust() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Estimated, not generated: regenerate with the `consolidate_locks`
+	// benchmark in `benchmarking.rs`.
+	fn consolidate_locks(l: u32, ) -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add((1_000_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Estimated, not generated.
+	fn schedule_confiscation(i: u32, ) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((3_000_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(i as Weight)))
+	}
+	// Estimated, not generated.
+	fn confiscate_account() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
 }
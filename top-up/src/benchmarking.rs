@@ -0,0 +1,89 @@
+//! Benchmarks for the top up module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+/// The benchmarked currency, expected to be accepted as contribution.
+const CURRENCY: u32 = 1;
+const DURATION: u32 = 10;
+
+fn create<T: Config>() -> Result<CampaignId, &'static str> {
+	let campaign_id = Pallet::<T>::next_campaign_id();
+	let destination: T::AccountId = account("destination", 0, SEED);
+	Pallet::<T>::create_campaign(
+		T::CreateOrigin::successful_origin(),
+		T::Lookup::unlookup(destination),
+		1u32.into(),
+		DURATION.into(),
+		FixedU128::saturating_from_integer(2),
+	)?;
+	Ok(campaign_id)
+}
+
+fn contribute<T: Config>(campaign_id: CampaignId) -> Result<T::AccountId, &'static str>
+where
+	CurrencyIdOf<T>: From<u32>,
+{
+	let who: T::AccountId = whitelisted_caller();
+	let amount: BalanceOf<T> = 1_000_000u32.into();
+	T::MultiCurrency::deposit(CURRENCY.into(), &who, amount)?;
+	Pallet::<T>::contribute(
+		RawOrigin::Signed(who.clone()).into(),
+		campaign_id,
+		CURRENCY.into(),
+		amount,
+	)?;
+	Ok(who)
+}
+
+fn end<T: Config>() {
+	let now = frame_system::Pallet::<T>::block_number();
+	frame_system::Pallet::<T>::set_block_number(now.saturating_add(DURATION.into()));
+}
+
+benchmarks! {
+	where_clause { where CurrencyIdOf<T>: From<u32> }
+
+	create_campaign {
+		let origin = T::CreateOrigin::successful_origin();
+		let destination: T::AccountId = account("destination", 0, SEED);
+	}: _<T::Origin>(origin, T::Lookup::unlookup(destination), 1u32.into(), DURATION.into(), FixedU128::saturating_from_integer(2))
+	verify {
+		assert!(Campaigns::<T>::contains_key(0));
+	}
+
+	contribute {
+		let campaign_id = create::<T>()?;
+		let who: T::AccountId = whitelisted_caller();
+		let amount: BalanceOf<T> = 1_000_000u32.into();
+		T::MultiCurrency::deposit(CURRENCY.into(), &who, amount)?;
+	}: _(RawOrigin::Signed(who.clone()), campaign_id, CURRENCY.into(), amount)
+	verify {
+		assert_eq!(Pallet::<T>::contributions(campaign_id, &who).amounts, vec![(CURRENCY.into(), amount)]);
+	}
+
+	finalize {
+		let campaign_id = create::<T>()?;
+		let who = contribute::<T>(campaign_id)?;
+		end::<T>();
+	}: _(RawOrigin::Signed(who), campaign_id)
+	verify {
+		assert_eq!(Pallet::<T>::campaigns(campaign_id).map(|c| c.status), Some(CampaignStatus::Succeeded));
+	}
+
+	claim {
+		let campaign_id = create::<T>()?;
+		let who = contribute::<T>(campaign_id)?;
+		end::<T>();
+		Pallet::<T>::finalize(RawOrigin::Signed(who.clone()).into(), campaign_id)?;
+	}: _(RawOrigin::Signed(who.clone()), campaign_id)
+	verify {
+		assert!(!Contributions::<T>::contains_key(campaign_id, &who));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::default().build(), crate::mock::Runtime);
@@ -0,0 +1,383 @@
+//! # Top Up
+//!
+//! ## Overview
+//!
+//! This module runs fixed-duration contribution campaigns, similar to
+//! crowdloans.
+//!
+//! A campaign is created by `CreateOrigin` with a destination account, a cap
+//! to raise and a duration. Until the end of the campaign, anyone can
+//! contribute any currency with a value in `ContributionValues`, e.g.
+//! stablecoins of different decimals. Contributions are counted towards the
+//! cap by their value at the time of the contribution. The contributions are
+//! held by the campaign account and tracked per account.
+//!
+//! Once ended, the campaign is finalized. If the cap is met, the raised funds
+//! are moved to the destination account and contributors claim a reward of
+//! `RewardCurrencyId`, minted at the reward rate of the campaign per unit of
+//! value contributed. Otherwise contributors claim a refund of their
+//! contributions.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, transactional, PalletId};
+use frame_system::{ensure_signed, pallet_prelude::*};
+use orml_traits::{GetByKey, MultiCurrency};
+use sp_runtime::{
+	traits::{AccountIdConversion, CheckedAdd, Saturating, StaticLookup, Zero},
+	ArithmeticError, DispatchResult, FixedPointNumber, FixedU128, RuntimeDebug, SaturatedConversion,
+};
+use sp_std::prelude::*;
+
+mod benchmarking;
+mod mock;
+mod tests;
+mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The campaign ID type.
+pub type CampaignId = u32;
+
+/// The status of a campaign.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum CampaignStatus {
+	/// Accepting contributions until the end of the campaign.
+	Ongoing,
+	/// The cap was met, contributors can claim their rewards.
+	Succeeded,
+	/// The cap was not met, contributors can claim their refunds.
+	Failed,
+}
+
+/// A contribution campaign.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct CampaignInfo<AccountId, CurrencyId, Balance, BlockNumber> {
+	/// The account receiving the raised funds on success.
+	pub destination: AccountId,
+	/// The value to raise.
+	pub cap: Balance,
+	/// The block after which contributions are closed.
+	pub end: BlockNumber,
+	/// The reward minted per unit of value contributed on success.
+	pub reward_rate: FixedU128,
+	/// The amount raised per currency.
+	pub raised: Vec<(CurrencyId, Balance)>,
+	/// The value raised.
+	pub raised_value: Balance,
+	/// The status of the campaign.
+	pub status: CampaignStatus,
+}
+
+/// The contributions of an account to a campaign.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Contribution<CurrencyId, Balance> {
+	/// The amount contributed per currency.
+	pub amounts: Vec<(CurrencyId, Balance)>,
+	/// The value contributed.
+	pub value: Balance,
+}
+
+impl<CurrencyId, Balance: Zero> Default for Contribution<CurrencyId, Balance> {
+	fn default() -> Self {
+		Contribution {
+			amounts: Vec::new(),
+			value: Zero::zero(),
+		}
+	}
+}
+
+/// Add `amount` of `currency_id` to `amounts`.
+fn add_amount<CurrencyId: PartialEq, Balance: CheckedAdd>(
+	amounts: &mut Vec<(CurrencyId, Balance)>,
+	currency_id: CurrencyId,
+	amount: Balance,
+) -> DispatchResult {
+	match amounts.iter_mut().find(|(id, _)| *id == currency_id) {
+		Some((_, total)) => *total = total.checked_add(&amount).ok_or(ArithmeticError::Overflow)?,
+		None => amounts.push((currency_id, amount)),
+	}
+	Ok(())
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	pub(crate) type BalanceOf<T> =
+		<<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub(crate) type CurrencyIdOf<T> =
+		<<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
+	pub(crate) type CampaignInfoOf<T> = CampaignInfo<
+		<T as frame_system::Config>::AccountId,
+		CurrencyIdOf<T>,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+	pub(crate) type ContributionOf<T> = Contribution<CurrencyIdOf<T>, BalanceOf<T>>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency of contributions and rewards.
+		type MultiCurrency: MultiCurrency<Self::AccountId>;
+
+		/// The value of one unit of a currency, in units of the cap. `None` if
+		/// the currency is not accepted as contribution.
+		type ContributionValues: GetByKey<CurrencyIdOf<Self>, Option<FixedU128>>;
+
+		/// The currency minted as reward to contributors.
+		#[pallet::constant]
+		type RewardCurrencyId: Get<CurrencyIdOf<Self>>;
+
+		/// The pallet ID, campaign accounts are derived from it.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The origin which creates campaigns.
+		type CreateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The campaign does not exist.
+		CampaignNotFound,
+		/// The currency is not accepted as contribution.
+		CurrencyNotAccepted,
+		/// The contribution amount is zero, or worth nothing.
+		ZeroAmount,
+		/// The campaign duration is zero.
+		ZeroDuration,
+		/// The campaign has ended.
+		CampaignEnded,
+		/// The campaign has not ended yet.
+		CampaignNotEnded,
+		/// The campaign is not in the status the operation requires.
+		InvalidCampaignStatus,
+		/// The account has no contribution to the campaign.
+		NoContribution,
+		/// No available campaign ID.
+		NoAvailableCampaignId,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A campaign is created. [campaign_id, destination, cap, end]
+		CampaignCreated(CampaignId, T::AccountId, BalanceOf<T>, T::BlockNumber),
+		/// A contribution is made. [campaign_id, who, currency_id, amount]
+		Contributed(CampaignId, T::AccountId, CurrencyIdOf<T>, BalanceOf<T>),
+		/// A campaign met its cap, the raised funds are moved to the
+		/// destination. [campaign_id, raised_value]
+		CampaignSucceeded(CampaignId, BalanceOf<T>),
+		/// A campaign did not meet its cap, contributions are refunded.
+		/// [campaign_id, raised_value]
+		CampaignFailed(CampaignId, BalanceOf<T>),
+		/// A contributor claimed the reward. [campaign_id, who, reward]
+		RewardClaimed(CampaignId, T::AccountId, BalanceOf<T>),
+		/// A contributor claimed the refund. [campaign_id, who]
+		Refunded(CampaignId, T::AccountId),
+	}
+
+	/// The campaigns.
+	///
+	/// map CampaignId => Option<CampaignInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn campaigns)]
+	pub type Campaigns<T: Config> = StorageMap<_, Twox64Concat, CampaignId, CampaignInfoOf<T>, OptionQuery>;
+
+	/// The contributions of an account to a campaign, removed once claimed.
+	///
+	/// double_map CampaignId, AccountId => Contribution
+	#[pallet::storage]
+	#[pallet::getter(fn contributions)]
+	pub type Contributions<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CampaignId, Blake2_128Concat, T::AccountId, ContributionOf<T>, ValueQuery>;
+
+	/// Track the next campaign ID.
+	#[pallet::storage]
+	#[pallet::getter(fn next_campaign_id)]
+	pub type NextCampaignId<T: Config> = StorageValue<_, CampaignId, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a campaign raising a value of `cap` for `destination`, open
+		/// for contributions during `duration` blocks. Contributors are
+		/// rewarded `reward_rate` of `RewardCurrencyId` per unit of value
+		/// contributed on success.
+		///
+		/// The dispatch origin for this call must be `CreateOrigin`.
+		#[pallet::weight(T::WeightInfo::create_campaign())]
+		pub fn create_campaign(
+			origin: OriginFor<T>,
+			destination: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] cap: BalanceOf<T>,
+			duration: T::BlockNumber,
+			reward_rate: FixedU128,
+		) -> DispatchResult {
+			T::CreateOrigin::ensure_origin(origin)?;
+			let destination = T::Lookup::lookup(destination)?;
+			ensure!(!duration.is_zero(), Error::<T>::ZeroDuration);
+
+			let campaign_id = NextCampaignId::<T>::try_mutate(|id| -> Result<CampaignId, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(1).ok_or(Error::<T>::NoAvailableCampaignId)?;
+				Ok(current_id)
+			})?;
+			let end = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+
+			Campaigns::<T>::insert(
+				campaign_id,
+				CampaignInfo {
+					destination: destination.clone(),
+					cap,
+					end,
+					reward_rate,
+					raised: Vec::new(),
+					raised_value: Zero::zero(),
+					status: CampaignStatus::Ongoing,
+				},
+			);
+
+			Self::deposit_event(Event::CampaignCreated(campaign_id, destination, cap, end));
+			Ok(())
+		}
+
+		/// Contribute `amount` of `currency_id` to a campaign.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// contributor.
+		#[pallet::weight(T::WeightInfo::contribute())]
+		#[transactional]
+		pub fn contribute(
+			origin: OriginFor<T>,
+			campaign_id: CampaignId,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let unit_value = T::ContributionValues::get(&currency_id).ok_or(Error::<T>::CurrencyNotAccepted)?;
+			let value: BalanceOf<T> = unit_value
+				.saturating_mul_int(amount.saturated_into::<u128>())
+				.saturated_into();
+			ensure!(!value.is_zero(), Error::<T>::ZeroAmount);
+
+			Campaigns::<T>::try_mutate(campaign_id, |maybe_campaign| -> DispatchResult {
+				let campaign = maybe_campaign.as_mut().ok_or(Error::<T>::CampaignNotFound)?;
+				ensure!(
+					frame_system::Pallet::<T>::block_number() < campaign.end,
+					Error::<T>::CampaignEnded
+				);
+
+				add_amount(&mut campaign.raised, currency_id, amount)?;
+				campaign.raised_value = campaign
+					.raised_value
+					.checked_add(&value)
+					.ok_or(ArithmeticError::Overflow)?;
+				Contributions::<T>::try_mutate(campaign_id, &who, |contribution| -> DispatchResult {
+					add_amount(&mut contribution.amounts, currency_id, amount)?;
+					contribution.value = contribution
+						.value
+						.checked_add(&value)
+						.ok_or(ArithmeticError::Overflow)?;
+					Ok(())
+				})?;
+
+				T::MultiCurrency::transfer(currency_id, &who, &Self::campaign_account(campaign_id), amount)
+			})?;
+
+			Self::deposit_event(Event::Contributed(campaign_id, who, currency_id, amount));
+			Ok(())
+		}
+
+		/// Finalize an ended campaign. If the cap is met, the raised funds
+		/// are moved to the destination, otherwise contributions become
+		/// refundable.
+		///
+		/// The dispatch origin for this call must be `Signed`.
+		#[pallet::weight(T::WeightInfo::finalize())]
+		#[transactional]
+		pub fn finalize(origin: OriginFor<T>, campaign_id: CampaignId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let mut campaign = Self::campaigns(campaign_id).ok_or(Error::<T>::CampaignNotFound)?;
+			ensure!(
+				campaign.status == CampaignStatus::Ongoing,
+				Error::<T>::InvalidCampaignStatus
+			);
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= campaign.end,
+				Error::<T>::CampaignNotEnded
+			);
+
+			let raised_value = campaign.raised_value;
+			if raised_value >= campaign.cap {
+				let campaign_account = Self::campaign_account(campaign_id);
+				for (currency_id, amount) in campaign.raised.iter() {
+					T::MultiCurrency::transfer(*currency_id, &campaign_account, &campaign.destination, *amount)?;
+				}
+				campaign.status = CampaignStatus::Succeeded;
+				Self::deposit_event(Event::CampaignSucceeded(campaign_id, raised_value));
+			} else {
+				campaign.status = CampaignStatus::Failed;
+				Self::deposit_event(Event::CampaignFailed(campaign_id, raised_value));
+			}
+			Campaigns::<T>::insert(campaign_id, campaign);
+
+			Ok(())
+		}
+
+		/// Claim the reward of the contributions to a succeeded campaign, or
+		/// the refund of the contributions to a failed campaign.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// contributor.
+		#[pallet::weight(T::WeightInfo::claim())]
+		#[transactional]
+		pub fn claim(origin: OriginFor<T>, campaign_id: CampaignId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let campaign = Self::campaigns(campaign_id).ok_or(Error::<T>::CampaignNotFound)?;
+			ensure!(
+				campaign.status != CampaignStatus::Ongoing,
+				Error::<T>::InvalidCampaignStatus
+			);
+			let contribution = Contributions::<T>::take(campaign_id, &who);
+			ensure!(!contribution.amounts.is_empty(), Error::<T>::NoContribution);
+
+			if campaign.status == CampaignStatus::Succeeded {
+				let reward = campaign
+					.reward_rate
+					.saturating_mul_int(contribution.value.saturated_into::<u128>())
+					.saturated_into();
+				T::MultiCurrency::deposit(T::RewardCurrencyId::get(), &who, reward)?;
+				Self::deposit_event(Event::RewardClaimed(campaign_id, who, reward));
+			} else {
+				let campaign_account = Self::campaign_account(campaign_id);
+				for (currency_id, amount) in contribution.amounts {
+					T::MultiCurrency::transfer(currency_id, &campaign_account, &who, amount)?;
+				}
+				Self::deposit_event(Event::Refunded(campaign_id, who));
+			}
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account holding the contributions to a campaign.
+	pub fn campaign_account(campaign_id: CampaignId) -> T::AccountId {
+		T::PalletId::get().into_sub_account(campaign_id)
+	}
+}
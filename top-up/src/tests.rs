@@ -0,0 +1,199 @@
+//! Unit tests for the top up module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+fn create_campaign(cap: Balance) -> CampaignId {
+	let campaign_id = TopUpModule::next_campaign_id();
+	assert_ok!(TopUpModule::create_campaign(
+		Origin::root(),
+		CHARLIE,
+		cap,
+		10,
+		FixedU128::saturating_from_integer(2)
+	));
+	campaign_id
+}
+
+#[test]
+fn create_campaign_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			TopUpModule::create_campaign(Origin::signed(ALICE), CHARLIE, 500, 10, Default::default()),
+			BadOrigin
+		);
+		assert_noop!(
+			TopUpModule::create_campaign(Origin::root(), CHARLIE, 500, 0, Default::default()),
+			Error::<Runtime>::ZeroDuration
+		);
+
+		let campaign_id = create_campaign(500);
+		System::assert_last_event(Event::TopUpModule(crate::Event::CampaignCreated(
+			campaign_id,
+			CHARLIE,
+			500,
+			11,
+		)));
+		assert_eq!(
+			TopUpModule::campaigns(campaign_id),
+			Some(CampaignInfo {
+				destination: CHARLIE,
+				cap: 500,
+				end: 11,
+				reward_rate: FixedU128::saturating_from_integer(2),
+				raised: vec![],
+				raised_value: 0,
+				status: CampaignStatus::Ongoing,
+			})
+		);
+		assert_eq!(TopUpModule::next_campaign_id(), 1);
+	});
+}
+
+#[test]
+fn contribute_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let campaign_id = create_campaign(500);
+		let campaign_account = TopUpModule::campaign_account(campaign_id);
+
+		assert_ok!(TopUpModule::contribute(Origin::signed(ALICE), campaign_id, AUSD, 100));
+		System::assert_last_event(Event::TopUpModule(crate::Event::Contributed(
+			campaign_id,
+			ALICE,
+			AUSD,
+			100,
+		)));
+		assert_ok!(TopUpModule::contribute(Origin::signed(ALICE), campaign_id, USDT, 50));
+		assert_ok!(TopUpModule::contribute(Origin::signed(ALICE), campaign_id, AUSD, 10));
+		assert_ok!(TopUpModule::contribute(Origin::signed(BOB), campaign_id, AUSD, 200));
+
+		// a unit of USDT is worth half a unit of AUSD
+		assert_eq!(
+			TopUpModule::contributions(campaign_id, ALICE),
+			Contribution {
+				amounts: vec![(AUSD, 110), (USDT, 50)],
+				value: 135,
+			}
+		);
+		assert_eq!(
+			TopUpModule::contributions(campaign_id, BOB),
+			Contribution {
+				amounts: vec![(AUSD, 200)],
+				value: 200,
+			}
+		);
+		let campaign = TopUpModule::campaigns(campaign_id).unwrap();
+		assert_eq!(campaign.raised, vec![(AUSD, 310), (USDT, 50)]);
+		assert_eq!(campaign.raised_value, 335);
+		assert_eq!(Tokens::free_balance(AUSD, &campaign_account), 310);
+		assert_eq!(Tokens::free_balance(USDT, &campaign_account), 50);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 890);
+	});
+}
+
+#[test]
+fn contribute_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			TopUpModule::contribute(Origin::signed(ALICE), 0, AUSD, 100),
+			Error::<Runtime>::CampaignNotFound
+		);
+
+		let campaign_id = create_campaign(500);
+		assert_noop!(
+			TopUpModule::contribute(Origin::signed(ALICE), campaign_id, DOT, 100),
+			Error::<Runtime>::CurrencyNotAccepted
+		);
+		assert_noop!(
+			TopUpModule::contribute(Origin::signed(ALICE), campaign_id, AUSD, 0),
+			Error::<Runtime>::ZeroAmount
+		);
+		assert_noop!(
+			TopUpModule::contribute(Origin::signed(ALICE), campaign_id, USDT, 1),
+			Error::<Runtime>::ZeroAmount
+		);
+		assert_noop!(
+			TopUpModule::contribute(Origin::signed(BOB), campaign_id, USDT, 100),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			TopUpModule::contribute(Origin::signed(ALICE), campaign_id, AUSD, 100),
+			Error::<Runtime>::CampaignEnded
+		);
+	});
+}
+
+#[test]
+fn successful_campaign_should_reward_contributors() {
+	ExtBuilder::default().build().execute_with(|| {
+		let campaign_id = create_campaign(500);
+		assert_ok!(TopUpModule::contribute(Origin::signed(ALICE), campaign_id, AUSD, 200));
+		assert_ok!(TopUpModule::contribute(Origin::signed(ALICE), campaign_id, USDT, 200));
+		assert_ok!(TopUpModule::contribute(Origin::signed(BOB), campaign_id, AUSD, 200));
+
+		assert_noop!(
+			TopUpModule::finalize(Origin::signed(BOB), campaign_id),
+			Error::<Runtime>::CampaignNotEnded
+		);
+		assert_noop!(
+			TopUpModule::claim(Origin::signed(ALICE), campaign_id),
+			Error::<Runtime>::InvalidCampaignStatus
+		);
+
+		System::set_block_number(11);
+		assert_ok!(TopUpModule::finalize(Origin::signed(BOB), campaign_id));
+		System::assert_last_event(Event::TopUpModule(crate::Event::CampaignSucceeded(campaign_id, 500)));
+		assert_eq!(
+			TopUpModule::campaigns(campaign_id).unwrap().status,
+			CampaignStatus::Succeeded
+		);
+		assert_eq!(Tokens::free_balance(AUSD, &CHARLIE), 400);
+		assert_eq!(Tokens::free_balance(USDT, &CHARLIE), 200);
+		assert_noop!(
+			TopUpModule::finalize(Origin::signed(BOB), campaign_id),
+			Error::<Runtime>::InvalidCampaignStatus
+		);
+
+		assert_ok!(TopUpModule::claim(Origin::signed(ALICE), campaign_id));
+		// rewarded by value, 2 * (200 + 100)
+		System::assert_last_event(Event::TopUpModule(crate::Event::RewardClaimed(campaign_id, ALICE, 600)));
+		assert_eq!(Tokens::free_balance(REWARD, &ALICE), 600);
+		assert_ok!(TopUpModule::claim(Origin::signed(BOB), campaign_id));
+		assert_eq!(Tokens::free_balance(REWARD, &BOB), 400);
+		assert_noop!(
+			TopUpModule::claim(Origin::signed(ALICE), campaign_id),
+			Error::<Runtime>::NoContribution
+		);
+	});
+}
+
+#[test]
+fn failed_campaign_should_refund_contributors() {
+	ExtBuilder::default().build().execute_with(|| {
+		let campaign_id = create_campaign(500);
+		// the amounts add up to the cap, but their value does not
+		assert_ok!(TopUpModule::contribute(Origin::signed(ALICE), campaign_id, AUSD, 200));
+		assert_ok!(TopUpModule::contribute(Origin::signed(ALICE), campaign_id, USDT, 400));
+
+		System::set_block_number(11);
+		assert_ok!(TopUpModule::finalize(Origin::signed(BOB), campaign_id));
+		System::assert_last_event(Event::TopUpModule(crate::Event::CampaignFailed(campaign_id, 400)));
+		assert_eq!(Tokens::free_balance(AUSD, &CHARLIE), 0);
+
+		assert_noop!(
+			TopUpModule::claim(Origin::signed(BOB), campaign_id),
+			Error::<Runtime>::NoContribution
+		);
+		assert_ok!(TopUpModule::claim(Origin::signed(ALICE), campaign_id));
+		System::assert_last_event(Event::TopUpModule(crate::Event::Refunded(campaign_id, ALICE)));
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 1_000);
+		assert_eq!(Tokens::free_balance(USDT, &ALICE), 1_000);
+		assert_eq!(Tokens::free_balance(REWARD, &ALICE), 0);
+	});
+}
@@ -10,6 +10,7 @@ use sp_std::{
 	cmp::{Eq, PartialEq},
 	fmt::Debug,
 	result,
+	vec::Vec,
 };
 
 /// Auction info.
@@ -42,6 +43,46 @@ pub trait Auction<AccountId, BlockNumber> {
 	fn remove_auction(id: Self::AuctionId);
 }
 
+/// Batch auction info.
+///
+/// A batch auction sells `lots` identical lots, won by the top `lots` bids.
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+#[derive(Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct BatchAuctionInfo<AccountId, Balance, BlockNumber> {
+	/// The number of lots.
+	pub lots: u32,
+	/// The current winning bids, one per lot, sorted by price in descending
+	/// order.
+	pub bids: Vec<(AccountId, Balance)>,
+	/// If true, all winners pay the lowest winning bid.
+	pub uniform_price: bool,
+	/// Define which block this auction will be started.
+	pub start: BlockNumber,
+	/// Define which block this auction will be ended.
+	pub end: Option<BlockNumber>,
+}
+
+/// Abstraction over a batch auction system.
+pub trait BatchAuction<AccountId, BlockNumber> {
+	/// The id of a BatchAuctionInfo
+	type AuctionId: FullCodec + Default + Copy + Eq + PartialEq + MaybeSerializeDeserialize + Bounded + Debug;
+	/// The price to bid.
+	type Balance: AtLeast32Bit + FullCodec + Copy + MaybeSerializeDeserialize + Debug + Default;
+
+	/// The batch auction info of `id`
+	fn batch_auction_info(id: Self::AuctionId) -> Option<BatchAuctionInfo<AccountId, Self::Balance, BlockNumber>>;
+	/// Create new batch auction of `lots` lots with specific startblock and
+	/// endblock, return the id of the auction
+	fn new_batch_auction(
+		lots: u32,
+		uniform_price: bool,
+		start: BlockNumber,
+		end: Option<BlockNumber>,
+	) -> result::Result<Self::AuctionId, DispatchError>;
+	/// Remove batch auction by `id`
+	fn remove_batch_auction(id: Self::AuctionId);
+}
+
 /// The result of bid handling.
 pub struct OnNewBidResult<BlockNumber> {
 	/// Indicates if the bid was accepted
@@ -65,3 +106,20 @@ pub trait AuctionHandler<AccountId, Balance, BlockNumber, AuctionId> {
 	/// End an auction with `winner`
 	fn on_auction_ended(id: AuctionId, winner: Option<(AccountId, Balance)>);
 }
+
+/// Hooks for batch auction to handle bids.
+pub trait BatchAuctionHandler<AccountId, Balance, BlockNumber, AuctionId> {
+	/// Called when new bid is received.
+	/// The return value determines if the bid should be accepted and update
+	/// auction end time. `outbid` is the lowest winning bid replaced by
+	/// the new bid, if all lots had a winner. Implementation should reserve
+	/// money from new bidder and refund the outbid bidder.
+	fn on_new_bid(
+		now: BlockNumber,
+		id: AuctionId,
+		new_bid: (AccountId, Balance),
+		outbid: Option<(AccountId, Balance)>,
+	) -> OnNewBidResult<BlockNumber>;
+	/// End a batch auction with `winners`, and the price each winner pays.
+	fn on_auction_ended(id: AuctionId, winners: Vec<(AccountId, Balance)>);
+}
@@ -1,7 +1,8 @@
 use crate::arithmetic;
 use codec::{Codec, FullCodec};
+use frame_support::weights::Weight;
 pub use frame_support::{
-	traits::{BalanceStatus, LockIdentifier},
+	traits::{BalanceStatus, ExistenceRequirement, LockIdentifier},
 	transactional,
 };
 use sp_runtime::{
@@ -57,6 +58,16 @@ pub trait MultiCurrency<AccountId> {
 		amount: Self::Balance,
 	) -> DispatchResult;
 
+	/// Transfer some amount from one account to another, `from` is only
+	/// reaped if `existence_requirement` is `AllowDeath`.
+	fn transfer_with_existence_requirement(
+		currency_id: Self::CurrencyId,
+		from: &AccountId,
+		to: &AccountId,
+		amount: Self::Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult;
+
 	/// Add `amount` to the balance of `who` under `currency_id` and increase
 	/// total issuance.
 	fn deposit(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> DispatchResult;
@@ -193,6 +204,111 @@ pub trait MultiReservableCurrency<AccountId>: MultiCurrency<AccountId> {
 	) -> result::Result<Self::Balance, DispatchError>;
 }
 
+/// A fungible multi-currency system where funds can be reserved from the
+/// user under named reserves.
+pub trait NamedMultiReservableCurrency<AccountId>: MultiReservableCurrency<AccountId> {
+	/// An identifier for a reserve. Used for disambiguating different reserves
+	/// so that they can be individually replaced or removed.
+	type ReserveIdentifier;
+
+	/// Deducts up to `value` from reserved balance of `who`. This function
+	/// cannot fail.
+	///
+	/// As much funds up to `value` will be deducted as possible. If the reserve
+	/// balance of `who` is less than `value`, then a non-zero second item will
+	/// be returned.
+	fn slash_reserved_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		value: Self::Balance,
+	) -> Self::Balance;
+
+	/// The amount of the balance of a given account that is externally
+	/// reserved under the identifier `id`.
+	fn reserved_balance_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+	) -> Self::Balance;
+
+	/// Moves `value` from balance to reserved balance under the identifier
+	/// `id`.
+	///
+	/// If the free balance is lower than `value`, then no funds will be moved
+	/// and an `Err` will be returned to notify of this. This is different
+	/// behavior than `unreserve_named`.
+	fn reserve_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		value: Self::Balance,
+	) -> DispatchResult;
+
+	/// Moves up to `value` from reserved balance under the identifier `id` to
+	/// free balance. This function cannot fail.
+	///
+	/// As much funds up to `value` will be moved as possible. If the reserve
+	/// balance of `who` is less than `value`, then the remaining amount will be
+	/// returned.
+	fn unreserve_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		value: Self::Balance,
+	) -> Self::Balance;
+
+	/// Moves up to `value` from reserved balance of account `slashed` under
+	/// the identifier `id` to balance of account `beneficiary`. Funds will be
+	/// placed in either the `free` balance or the `reserved` balance under the
+	/// same identifier, depending on the `status`.
+	///
+	/// As much funds up to `value` will be deducted as possible. If this is
+	/// less than `value`, then `Ok(non_zero)` will be returned.
+	fn repatriate_reserved_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		value: Self::Balance,
+		status: BalanceStatus,
+	) -> result::Result<Self::Balance, DispatchError>;
+
+	/// Ensure the reserved balance under the identifier `id` is equal to
+	/// `value`, reserving or unreserving the difference.
+	fn ensure_reserved_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		value: Self::Balance,
+	) -> DispatchResult {
+		let current = Self::reserved_balance_named(id, currency_id, who);
+		if current > value {
+			// we always have enough balance to unreserve here
+			Self::unreserve_named(id, currency_id, who, current - value);
+			Ok(())
+		} else if value > current {
+			// we checked value > current
+			Self::reserve_named(id, currency_id, who, value - current)
+		} else {
+			// current == value
+			Ok(())
+		}
+	}
+
+	/// Unreserve all the named reserved balances, returning the amount that
+	/// was unreserved.
+	fn unreserve_all_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+	) -> Self::Balance {
+		let value = Self::reserved_balance_named(id, currency_id, who);
+		Self::unreserve_named(id, currency_id, who, value);
+		value
+	}
+}
+
 /// Abstraction over a fungible (single) currency system.
 pub trait BasicCurrency<AccountId> {
 	/// The balance of an account.
@@ -221,6 +337,15 @@ pub trait BasicCurrency<AccountId> {
 	/// Transfer some amount from one account to another.
 	fn transfer(from: &AccountId, to: &AccountId, amount: Self::Balance) -> DispatchResult;
 
+	/// Transfer some amount from one account to another, `from` is only
+	/// reaped if `existence_requirement` is `AllowDeath`.
+	fn transfer_with_existence_requirement(
+		from: &AccountId,
+		to: &AccountId,
+		amount: Self::Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult;
+
 	/// Add `amount` to the balance of `who` and increase total issuance.
 	fn deposit(who: &AccountId, amount: Self::Balance) -> DispatchResult;
 
@@ -343,13 +468,104 @@ pub trait BasicReservableCurrency<AccountId>: BasicCurrency<AccountId> {
 	) -> result::Result<Self::Balance, DispatchError>;
 }
 
-/// Handler for account which has dust, need to burn or recycle it
+environmental::environmental!(hook_weight: Weight);
+
+/// The weight consumed by the `OnDust`, `OnSlash`, `OnDeposit` and
+/// `OnTransfer` hooks, which isn't known in advance.
+///
+/// The pallets calling the hooks `consume` their weight, so that the calls
+/// wrapped in `measure` can charge it in their actual weight.
+pub struct HookWeight;
+
+impl HookWeight {
+	/// Run `f`, returning the weight consumed by the hooks called in it.
+	pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Weight) {
+		let mut weight: Weight = 0;
+		let result = hook_weight::using(&mut weight, f);
+		(result, weight)
+	}
+
+	/// Add `weight` to the measurement in progress. Returns false if not
+	/// measuring, then the caller should register the weight to the block.
+	pub fn consume(weight: Weight) -> bool {
+		hook_weight::with(|total| *total = total.saturating_add(weight)).is_some()
+	}
+}
+
+/// Handler for account which has dust, need to burn or recycle it, returning
+/// the weight consumed.
 pub trait OnDust<AccountId, CurrencyId, Balance> {
-	fn on_dust(who: &AccountId, currency_id: CurrencyId, amount: Balance);
+	fn on_dust(who: &AccountId, currency_id: CurrencyId, amount: Balance) -> Weight;
 }
 
-impl<AccountId, CurrencyId, Balance> OnDust<AccountId, CurrencyId, Balance> for () {
-	fn on_dust(_: &AccountId, _: CurrencyId, _: Balance) {}
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<AccountId, CurrencyId: Copy, Balance: Copy> OnDust<AccountId, CurrencyId, Balance> for Tuple {
+	fn on_dust(who: &AccountId, currency_id: CurrencyId, amount: Balance) -> Weight {
+		let mut weight: Weight = 0;
+		for_tuples!( #( weight = weight.saturating_add(Tuple::on_dust(who, currency_id, amount)); )* );
+		weight
+	}
+}
+
+/// Handler for when `amount` is slashed from the balance of `who`, returning
+/// the weight consumed.
+pub trait OnSlash<AccountId, CurrencyId, Balance> {
+	fn on_slash(who: &AccountId, currency_id: CurrencyId, amount: Balance) -> Weight;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<AccountId, CurrencyId: Copy, Balance: Copy> OnSlash<AccountId, CurrencyId, Balance> for Tuple {
+	fn on_slash(who: &AccountId, currency_id: CurrencyId, amount: Balance) -> Weight {
+		let mut weight: Weight = 0;
+		for_tuples!( #( weight = weight.saturating_add(Tuple::on_slash(who, currency_id, amount)); )* );
+		weight
+	}
+}
+
+/// Handler for when `amount` is deposited into the balance of `who`,
+/// returning the weight consumed.
+pub trait OnDeposit<AccountId, CurrencyId, Balance> {
+	fn on_deposit(who: &AccountId, currency_id: CurrencyId, amount: Balance) -> Weight;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<AccountId, CurrencyId: Copy, Balance: Copy> OnDeposit<AccountId, CurrencyId, Balance> for Tuple {
+	fn on_deposit(who: &AccountId, currency_id: CurrencyId, amount: Balance) -> Weight {
+		let mut weight: Weight = 0;
+		for_tuples!( #( weight = weight.saturating_add(Tuple::on_deposit(who, currency_id, amount)); )* );
+		weight
+	}
+}
+
+/// Handler for when `amount` is transferred from `from` to `to`, returning
+/// the weight consumed.
+pub trait OnTransfer<AccountId, CurrencyId, Balance> {
+	fn on_transfer(from: &AccountId, to: &AccountId, currency_id: CurrencyId, amount: Balance) -> Weight;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<AccountId, CurrencyId: Copy, Balance: Copy> OnTransfer<AccountId, CurrencyId, Balance> for Tuple {
+	fn on_transfer(from: &AccountId, to: &AccountId, currency_id: CurrencyId, amount: Balance) -> Weight {
+		let mut weight: Weight = 0;
+		for_tuples!( #( weight = weight.saturating_add(Tuple::on_transfer(from, to, currency_id, amount)); )* );
+		weight
+	}
+}
+
+/// Handler for the changes of the locks on the balances of accounts, `amount`
+/// is the new amount of the lock, zero if it's removed. Returns the consumed
+/// weight.
+pub trait OnLockChanged<AccountId, CurrencyId, Balance> {
+	fn on_lock_changed(who: &AccountId, currency_id: CurrencyId, lock_id: LockIdentifier, amount: Balance) -> Weight;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(5)]
+impl<AccountId, CurrencyId: Copy, Balance: Copy> OnLockChanged<AccountId, CurrencyId, Balance> for Tuple {
+	fn on_lock_changed(who: &AccountId, currency_id: CurrencyId, lock_id: LockIdentifier, amount: Balance) -> Weight {
+		let mut weight: Weight = 0;
+		for_tuples!( #( weight = weight.saturating_add(Tuple::on_lock_changed(who, currency_id, lock_id, amount)); )* );
+		weight
+	}
 }
 
 pub trait TransferAll<AccountId> {
@@ -0,0 +1,21 @@
+use frame_support::weights::Weight;
+use sp_runtime::DispatchResult;
+
+/// A task to be executed after a delay.
+pub trait DelayedTask {
+	/// Validate the task. Called when the task is added, and again right
+	/// before it's dispatched.
+	fn pre_check(&self) -> DispatchResult;
+
+	/// The weight of dispatching the task.
+	fn weight(&self) -> Weight;
+
+	/// Dispatch the task.
+	fn dispatch(self) -> DispatchResult;
+}
+
+/// Manage tasks executed after a delay.
+pub trait DelayTasksManager<Task, BlockNumber> {
+	/// Add a task to be executed after `delay_blocks`.
+	fn add_delay_task(task: Task, delay_blocks: BlockNumber) -> DispatchResult;
+}
@@ -0,0 +1,343 @@
+//! Adapters between `MultiCurrency` and `frame_support::traits::fungibles`.
+//!
+//! `FungiblesAdapter` runs ORML pallets over `fungibles` implementations,
+//! e.g. `pallet-assets`, and `MultiCurrencyAsFungibles` runs FRAME pallets
+//! over `MultiCurrency` implementations, e.g. `orml-currencies`. Reserves are
+//! mapped to holds. `fungibles` has no freezes, locks only show in the
+//! balances which could be reduced.
+
+use crate::currency::{BalanceStatus, ExistenceRequirement, MultiCurrency, MultiReservableCurrency};
+use frame_support::{
+	storage::{with_transaction, TransactionOutcome},
+	traits::tokens::{
+		fungibles::{Inspect, InspectHold, Mutate, MutateHold, Transfer},
+		AssetId, Balance, DepositConsequence, WithdrawConsequence,
+	},
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{CheckedAdd, CheckedSub, MaybeSerializeDeserialize, Zero},
+	DispatchError, DispatchResult, TokenError,
+};
+use sp_std::{cmp, marker::PhantomData};
+
+/// `MultiCurrency` over the `fungibles` implementation `F`.
+///
+/// The free balance is the balance of `F` which could be reduced, i.e.
+/// without frozen and held funds.
+pub struct FungiblesAdapter<F>(PhantomData<F>);
+
+impl<AccountId, F> MultiCurrency<AccountId> for FungiblesAdapter<F>
+where
+	F: Inspect<AccountId> + Mutate<AccountId> + Transfer<AccountId>,
+	F::AssetId: MaybeSerializeDeserialize + TypeInfo,
+	F::Balance: MaybeSerializeDeserialize + TypeInfo,
+{
+	type CurrencyId = F::AssetId;
+	type Balance = F::Balance;
+
+	fn minimum_balance(currency_id: Self::CurrencyId) -> Self::Balance {
+		F::minimum_balance(currency_id)
+	}
+
+	fn total_issuance(currency_id: Self::CurrencyId) -> Self::Balance {
+		F::total_issuance(currency_id)
+	}
+
+	fn total_balance(currency_id: Self::CurrencyId, who: &AccountId) -> Self::Balance {
+		F::balance(currency_id, who)
+	}
+
+	fn free_balance(currency_id: Self::CurrencyId, who: &AccountId) -> Self::Balance {
+		F::reducible_balance(currency_id, who, false)
+	}
+
+	fn ensure_can_withdraw(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		F::can_withdraw(currency_id, who, amount).into_result().map(|_| ())
+	}
+
+	fn transfer(
+		currency_id: Self::CurrencyId,
+		from: &AccountId,
+		to: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		Self::transfer_with_existence_requirement(currency_id, from, to, amount, ExistenceRequirement::AllowDeath)
+	}
+
+	fn transfer_with_existence_requirement(
+		currency_id: Self::CurrencyId,
+		from: &AccountId,
+		to: &AccountId,
+		amount: Self::Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+		let keep_alive = existence_requirement == ExistenceRequirement::KeepAlive;
+		F::transfer(currency_id, from, to, amount, keep_alive).map(|_| ())
+	}
+
+	fn deposit(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+		F::mint_into(currency_id, who, amount)
+	}
+
+	fn withdraw(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+		F::burn_from(currency_id, who, amount).map(|_| ())
+	}
+
+	fn can_slash(currency_id: Self::CurrencyId, who: &AccountId, value: Self::Balance) -> bool {
+		value.is_zero() || F::reducible_balance(currency_id, who, false) >= value
+	}
+
+	fn slash(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> Self::Balance {
+		let slashable = cmp::min(amount, F::reducible_balance(currency_id, who, false));
+		if slashable.is_zero() {
+			return amount;
+		}
+		let slashed = F::burn_from(currency_id, who, slashable).unwrap_or_else(|_| Zero::zero());
+		amount.saturating_sub(slashed)
+	}
+}
+
+impl<AccountId, F> MultiReservableCurrency<AccountId> for FungiblesAdapter<F>
+where
+	F: Inspect<AccountId> + Mutate<AccountId> + Transfer<AccountId> + MutateHold<AccountId>,
+	F::AssetId: MaybeSerializeDeserialize + TypeInfo,
+	F::Balance: MaybeSerializeDeserialize + TypeInfo,
+{
+	fn can_reserve(currency_id: Self::CurrencyId, who: &AccountId, value: Self::Balance) -> bool {
+		value.is_zero() || F::can_hold(currency_id, who, value)
+	}
+
+	fn slash_reserved(currency_id: Self::CurrencyId, who: &AccountId, value: Self::Balance) -> Self::Balance {
+		if value.is_zero() {
+			return value;
+		}
+		// held funds could not be burned, so they are released first, and kept
+		// on hold if the released funds could not be burned, e.g. frozen
+		with_transaction(|| {
+			let released = match F::release(currency_id, who, value, true) {
+				Ok(released) => released,
+				Err(_) => return TransactionOutcome::Rollback(value),
+			};
+			match F::burn_from(currency_id, who, released) {
+				Ok(burned) if burned >= released => TransactionOutcome::Commit(value.saturating_sub(released)),
+				_ => TransactionOutcome::Rollback(value),
+			}
+		})
+	}
+
+	fn reserved_balance(currency_id: Self::CurrencyId, who: &AccountId) -> Self::Balance {
+		F::balance_on_hold(currency_id, who)
+	}
+
+	fn reserve(currency_id: Self::CurrencyId, who: &AccountId, value: Self::Balance) -> DispatchResult {
+		if value.is_zero() {
+			return Ok(());
+		}
+		F::hold(currency_id, who, value)
+	}
+
+	fn unreserve(currency_id: Self::CurrencyId, who: &AccountId, value: Self::Balance) -> Self::Balance {
+		if value.is_zero() {
+			return value;
+		}
+		let released = F::release(currency_id, who, value, true).unwrap_or_else(|_| Zero::zero());
+		value.saturating_sub(released)
+	}
+
+	fn repatriate_reserved(
+		currency_id: Self::CurrencyId,
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		value: Self::Balance,
+		status: BalanceStatus,
+	) -> Result<Self::Balance, DispatchError> {
+		if value.is_zero() {
+			return Ok(value);
+		}
+		let on_hold = status == BalanceStatus::Reserved;
+		let transferred = F::transfer_held(currency_id, slashed, beneficiary, value, true, on_hold)?;
+		Ok(value.saturating_sub(transferred))
+	}
+}
+
+/// `fungibles` over the `MultiCurrency` implementation `M`.
+///
+/// Locks are not visible through `MultiCurrency`, so the reducible balance
+/// only accounts for the free balance and the existential deposit,
+/// withdrawals of locked funds still fail.
+pub struct MultiCurrencyAsFungibles<M>(PhantomData<M>);
+
+impl<AccountId, M> Inspect<AccountId> for MultiCurrencyAsFungibles<M>
+where
+	M: MultiCurrency<AccountId>,
+	M::CurrencyId: AssetId,
+	M::Balance: Balance,
+{
+	type AssetId = M::CurrencyId;
+	type Balance = M::Balance;
+
+	fn total_issuance(asset: Self::AssetId) -> Self::Balance {
+		M::total_issuance(asset)
+	}
+
+	fn minimum_balance(asset: Self::AssetId) -> Self::Balance {
+		M::minimum_balance(asset)
+	}
+
+	fn balance(asset: Self::AssetId, who: &AccountId) -> Self::Balance {
+		M::total_balance(asset, who)
+	}
+
+	fn reducible_balance(asset: Self::AssetId, who: &AccountId, keep_alive: bool) -> Self::Balance {
+		let free = M::free_balance(asset, who);
+		if keep_alive {
+			// the reserved balance counts towards the existential deposit
+			let reserved = M::total_balance(asset, who).saturating_sub(free);
+			free.saturating_sub(M::minimum_balance(asset).saturating_sub(reserved))
+		} else {
+			free
+		}
+	}
+
+	fn can_deposit(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> DepositConsequence {
+		if amount.is_zero() {
+			return DepositConsequence::Success;
+		}
+		if M::total_issuance(asset).checked_add(&amount).is_none() {
+			return DepositConsequence::Overflow;
+		}
+		match M::total_balance(asset, who).checked_add(&amount) {
+			None => DepositConsequence::Overflow,
+			Some(total) if total < M::minimum_balance(asset) => DepositConsequence::BelowMinimum,
+			Some(_) => DepositConsequence::Success,
+		}
+	}
+
+	fn can_withdraw(
+		asset: Self::AssetId,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> WithdrawConsequence<Self::Balance> {
+		if amount.is_zero() {
+			return WithdrawConsequence::Success;
+		}
+		if M::total_issuance(asset).checked_sub(&amount).is_none() {
+			return WithdrawConsequence::Underflow;
+		}
+		if M::ensure_can_withdraw(asset, who, amount).is_err() {
+			return WithdrawConsequence::NoFunds;
+		}
+		let rest = M::total_balance(asset, who).saturating_sub(amount);
+		if !rest.is_zero() && rest < M::minimum_balance(asset) {
+			WithdrawConsequence::ReducedToZero(rest)
+		} else {
+			WithdrawConsequence::Success
+		}
+	}
+}
+
+impl<AccountId, M> Mutate<AccountId> for MultiCurrencyAsFungibles<M>
+where
+	M: MultiCurrency<AccountId>,
+	M::CurrencyId: AssetId,
+	M::Balance: Balance,
+{
+	fn mint_into(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		M::deposit(asset, who, amount)
+	}
+
+	fn burn_from(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> Result<Self::Balance, DispatchError> {
+		M::withdraw(asset, who, amount).map(|_| amount)
+	}
+}
+
+impl<AccountId, M> Transfer<AccountId> for MultiCurrencyAsFungibles<M>
+where
+	M: MultiCurrency<AccountId>,
+	M::CurrencyId: AssetId,
+	M::Balance: Balance,
+{
+	fn transfer(
+		asset: Self::AssetId,
+		source: &AccountId,
+		dest: &AccountId,
+		amount: Self::Balance,
+		keep_alive: bool,
+	) -> Result<Self::Balance, DispatchError> {
+		let existence_requirement = if keep_alive {
+			ExistenceRequirement::KeepAlive
+		} else {
+			ExistenceRequirement::AllowDeath
+		};
+		M::transfer_with_existence_requirement(asset, source, dest, amount, existence_requirement).map(|_| amount)
+	}
+}
+
+impl<AccountId, M> InspectHold<AccountId> for MultiCurrencyAsFungibles<M>
+where
+	M: MultiReservableCurrency<AccountId>,
+	M::CurrencyId: AssetId,
+	M::Balance: Balance,
+{
+	fn balance_on_hold(asset: Self::AssetId, who: &AccountId) -> Self::Balance {
+		M::reserved_balance(asset, who)
+	}
+
+	fn can_hold(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> bool {
+		M::can_reserve(asset, who, amount)
+	}
+}
+
+impl<AccountId, M> MutateHold<AccountId> for MultiCurrencyAsFungibles<M>
+where
+	M: MultiReservableCurrency<AccountId>,
+	M::CurrencyId: AssetId,
+	M::Balance: Balance,
+{
+	fn hold(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		M::reserve(asset, who, amount)
+	}
+
+	fn release(
+		asset: Self::AssetId,
+		who: &AccountId,
+		amount: Self::Balance,
+		best_effort: bool,
+	) -> Result<Self::Balance, DispatchError> {
+		if !best_effort && M::reserved_balance(asset, who) < amount {
+			return Err(TokenError::NoFunds.into());
+		}
+		let remaining = M::unreserve(asset, who, amount);
+		Ok(amount.saturating_sub(remaining))
+	}
+
+	fn transfer_held(
+		asset: Self::AssetId,
+		source: &AccountId,
+		dest: &AccountId,
+		amount: Self::Balance,
+		best_effort: bool,
+		on_hold: bool,
+	) -> Result<Self::Balance, DispatchError> {
+		if !best_effort && M::reserved_balance(asset, source) < amount {
+			return Err(TokenError::NoFunds.into());
+		}
+		let status = if on_hold {
+			BalanceStatus::Reserved
+		} else {
+			BalanceStatus::Free
+		};
+		let remaining = M::repatriate_reserved(asset, source, dest, amount, status)?;
+		Ok(amount.saturating_sub(remaining))
+	}
+}
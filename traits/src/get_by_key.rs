@@ -4,6 +4,13 @@ pub trait GetByKey<Key, Value> {
 	fn get(k: &Key) -> Value;
 }
 
+/// No value for any key.
+impl<Key, Value> GetByKey<Key, Option<Value>> for () {
+	fn get(_: &Key) -> Option<Value> {
+		None
+	}
+}
+
 /// Create new implementations of the `GetByKey` trait.
 ///
 /// The implementation is typically used like a map or set.
@@ -1,40 +1,56 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Decode, Encode};
+use frame_support::traits::Contains;
 use impl_trait_for_tuples::impl_for_tuples;
 use sp_runtime::{DispatchResult, RuntimeDebug};
 use sp_std::{
 	cmp::{Eq, PartialEq},
+	marker::PhantomData,
 	prelude::Vec,
 };
 
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
-pub use auction::{Auction, AuctionHandler, AuctionInfo, OnNewBidResult};
+pub use auction::{
+	Auction, AuctionHandler, AuctionInfo, BatchAuction, BatchAuctionHandler, BatchAuctionInfo, OnNewBidResult,
+};
 pub use currency::{
-	BalanceStatus, BasicCurrency, BasicCurrencyExtended, BasicLockableCurrency, BasicReservableCurrency,
-	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency, OnDust,
+	BalanceStatus, BasicCurrency, BasicCurrencyExtended, BasicLockableCurrency, BasicReservableCurrency, HookWeight,
+	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency,
+	NamedMultiReservableCurrency, OnDeposit, OnDust, OnLockChanged, OnSlash, OnTransfer,
 };
 pub use data_provider::{DataFeeder, DataProvider, DataProviderExtended};
+pub use delay_tasks::{DelayTasksManager, DelayedTask};
+pub use fungibles::{FungiblesAdapter, MultiCurrencyAsFungibles};
 pub use get_by_key::GetByKey;
 pub use multi_asset::ConcreteFungibleAsset;
 pub use nft::NFT;
+pub use orml_utilities::{Change, ChangeDelta};
 pub use price::{DefaultPriceProvider, PriceProvider};
-pub use rewards::RewardHandler;
-use scale_info::TypeInfo;
+pub use rate_limit::{RateLimiter, RateLimiterError};
+pub use rewards::{OnRewardDeduction, RewardHandler, ShareBoost};
+pub use storage_deposit::{NamedReserveStorageDeposit, StorageDepositHandler};
+pub use swap::Swap;
 pub use xcm_transfer::XcmTransfer;
 
 pub mod arithmetic;
 pub mod auction;
 pub mod currency;
 pub mod data_provider;
+pub mod delay_tasks;
+pub mod fungibles;
 pub mod get_by_key;
 pub mod location;
 pub mod multi_asset;
 pub mod nft;
+pub mod parameters;
 pub mod price;
+pub mod rate_limit;
 pub mod rewards;
+pub mod storage_deposit;
+pub mod swap;
 pub mod xcm_transfer;
 
 /// New data handler
@@ -44,6 +60,31 @@ pub trait OnNewData<AccountId, Key, Value> {
 	fn on_new_data(who: &AccountId, key: &Key, value: &Value);
 }
 
+/// New data handler subscribed to the keys in `Filter`, `Handler` is only
+/// called for the data of these keys. Subscriptions are composed as tuples,
+/// e.g. `(SubscribedOnNewData<DexKeys, Dex>, SubscribedOnNewData<LoansKeys,
+/// Loans>)`.
+pub struct SubscribedOnNewData<Filter, Handler>(PhantomData<(Filter, Handler)>);
+
+impl<AccountId, Key, Value, Filter, Handler> OnNewData<AccountId, Key, Value> for SubscribedOnNewData<Filter, Handler>
+where
+	Filter: Contains<Key>,
+	Handler: OnNewData<AccountId, Key, Value>,
+{
+	fn on_new_data(who: &AccountId, key: &Key, value: &Value) {
+		if Filter::contains(key) {
+			Handler::on_new_data(who, key, value);
+		}
+	}
+}
+
+/// Gradually updated value handler
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+pub trait OnGraduallyUpdated<Key, Value> {
+	/// The value stored at `key` has been updated from `old` to `new`
+	fn on_gradually_updated(key: &Key, old: &Value, new: &Value);
+}
+
 /// Combine data provided by operators
 pub trait CombineData<Key, TimestampedValue> {
 	/// Combine data provided by operators
@@ -54,15 +95,6 @@ pub trait CombineData<Key, TimestampedValue> {
 	) -> Option<TimestampedValue>;
 }
 
-/// Indicate if should change a value
-#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
-pub enum Change<Value> {
-	/// No change.
-	NoChange,
-	/// Changed to new value.
-	NewValue(Value),
-}
-
 #[derive(Encode, Decode, RuntimeDebug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct TimestampedValue<Value: Ord + PartialOrd, Moment> {
@@ -75,6 +107,20 @@ pub trait Happened<T> {
 	fn happened(t: &T);
 }
 
+/// Fallible handler of `T`, returning an error vetoes it. Tuples of handlers
+/// are called in order and stop at the first error.
+pub trait TryHappened<T> {
+	fn try_happened(t: &T) -> DispatchResult;
+}
+
+#[impl_for_tuples(30)]
+impl<T> TryHappened<T> for Tuple {
+	fn try_happened(t: &T) -> DispatchResult {
+		for_tuples!( #( Tuple::try_happened(t)?; )* );
+		Ok(())
+	}
+}
+
 pub trait Handler<T> {
 	fn handle(t: &T) -> DispatchResult;
 }
@@ -82,7 +128,7 @@ pub trait Handler<T> {
 #[impl_for_tuples(30)]
 impl<T> Handler<T> for Tuple {
 	fn handle(t: &T) -> DispatchResult {
-		for_tuples!( #( Tuple::handle(t); )* );
+		for_tuples!( #( Tuple::handle(t)?; )* );
 		Ok(())
 	}
 }
@@ -55,6 +55,40 @@ impl Reserve for MultiAsset {
 	}
 }
 
+/// Provides the reserve location of assets.
+pub trait ReserveProvider {
+	/// Returns the reserve location of `asset`.
+	fn reserve(asset: &MultiAsset) -> Option<MultiLocation>;
+}
+
+/// Reserve provider of assets located in the absolute view, the locations of
+/// self assets include the self chain.
+pub struct AbsoluteReserveProvider;
+
+impl ReserveProvider for AbsoluteReserveProvider {
+	fn reserve(asset: &MultiAsset) -> Option<MultiLocation> {
+		asset.reserve()
+	}
+}
+
+/// Reserve provider of assets located in the relative view, self assets are
+/// located under `Here` and reserved `Here`.
+pub struct RelativeReserveProvider;
+
+impl ReserveProvider for RelativeReserveProvider {
+	fn reserve(asset: &MultiAsset) -> Option<MultiLocation> {
+		if let Concrete(location) = &asset.id {
+			if location.parents == 0 && !is_chain_junction(location.first_interior()) {
+				Some(MultiLocation::here())
+			} else {
+				location.chain_part()
+			}
+		} else {
+			None
+		}
+	}
+}
+
 pub trait RelativeLocations {
 	fn sibling_parachain_general_key(para_id: u32, general_key: Vec<u8>) -> MultiLocation;
 }
@@ -108,6 +142,38 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn absolute_reserve_provider_works() {
+		assert_eq!(
+			AbsoluteReserveProvider::reserve(&concrete_fungible(MultiLocation::new(1, X2(PARACHAIN, GENERAL_INDEX)))),
+			Some(MultiLocation::new(1, X1(PARACHAIN)))
+		);
+		assert_eq!(
+			AbsoluteReserveProvider::reserve(&concrete_fungible(MultiLocation::new(0, X1(GENERAL_INDEX)))),
+			None
+		);
+	}
+
+	#[test]
+	fn relative_reserve_provider_works() {
+		assert_eq!(
+			RelativeReserveProvider::reserve(&concrete_fungible(MultiLocation::new(1, X2(PARACHAIN, GENERAL_INDEX)))),
+			Some(MultiLocation::new(1, X1(PARACHAIN)))
+		);
+		assert_eq!(
+			RelativeReserveProvider::reserve(&concrete_fungible(MultiLocation::new(0, X2(PARACHAIN, GENERAL_INDEX)))),
+			Some(PARACHAIN.into())
+		);
+		assert_eq!(
+			RelativeReserveProvider::reserve(&concrete_fungible(MultiLocation::new(0, X1(GENERAL_INDEX)))),
+			Some(MultiLocation::here())
+		);
+		assert_eq!(
+			RelativeReserveProvider::reserve(&concrete_fungible(MultiLocation::here())),
+			Some(MultiLocation::here())
+		);
+	}
+
 	#[test]
 	fn non_chain_part_works() {
 		assert_eq!(MultiLocation::parent().non_chain_part(), None);
@@ -0,0 +1,190 @@
+//! Typed runtime parameters, stored in aggregated key/value enums.
+
+use codec::{Decode, Encode};
+use frame_support::Parameter;
+use sp_runtime::traits::Member;
+
+#[doc(hidden)]
+pub use frame_support;
+
+/// A key/value pair of any of the parameters, which splits into an
+/// aggregated key and an optional aggregated value.
+pub trait AggregatedKeyValue: Parameter {
+	/// The aggregated key of all parameters.
+	type AggregatedKey: Parameter + Member + Encode + Decode;
+	/// The aggregated value of all parameters.
+	type AggregatedValue: Parameter + Member + Encode + Decode;
+
+	/// Split into the key and the value, `None` if the parameter is removed.
+	fn into_parts(self) -> (Self::AggregatedKey, Option<Self::AggregatedValue>);
+}
+
+/// The key of a single parameter, of `AggregatedValue`.
+pub trait Key<AggregatedValue> {
+	/// The value type of the parameter.
+	type Value;
+
+	/// Extract the value of this parameter from an aggregated value.
+	fn try_from_aggregated(value: AggregatedValue) -> Option<Self::Value>;
+}
+
+/// Ensure an origin is allowed to operate with `Argument`, like setting a
+/// given parameter.
+pub trait EnsureOriginWithArg<OuterOrigin, Argument> {
+	/// A return type.
+	type Success;
+
+	/// Perform the origin check.
+	fn try_origin(o: OuterOrigin, a: &Argument) -> Result<Self::Success, OuterOrigin>;
+
+	/// Perform the origin check.
+	fn ensure_origin(o: OuterOrigin, a: &Argument) -> Result<Self::Success, sp_runtime::traits::BadOrigin> {
+		Self::try_origin(o, a).map_err(|_| sp_runtime::traits::BadOrigin)
+	}
+}
+
+/// Define typed parameters.
+///
+/// Each parameter has a key struct and a value type. The aggregated
+/// key/value enum, key enum and value enum are generated with the given
+/// names, the aggregated key/value enum implements `AggregatedKeyValue`.
+///
+/// Example:
+/// ```ignore
+/// define_parameters! {
+///     pub RuntimeParameters, RuntimeParametersKey, RuntimeParametersValue = {
+///         MinBond: Balance = 0,
+///         RewardRate: Permill = 1,
+///     }
+/// }
+/// ```
+///
+/// The numbers are the codec indexes of the parameters and must stay the
+/// same once used on chain.
+#[macro_export]
+macro_rules! define_parameters {
+	(
+		$vis:vis $name:ident, $key_name:ident, $value_name:ident = {
+			$( $key:ident : $value:ty = $index:expr ),+ $(,)?
+		}
+	) => {
+		$(
+			#[derive(
+				Clone, Copy, PartialEq, Eq,
+				$crate::parameters::frame_support::codec::Encode,
+				$crate::parameters::frame_support::codec::Decode,
+				$crate::parameters::frame_support::RuntimeDebug,
+				$crate::parameters::frame_support::scale_info::TypeInfo,
+			)]
+			$vis struct $key;
+
+			impl From<$key> for $key_name {
+				fn from(key: $key) -> Self {
+					$key_name::$key(key)
+				}
+			}
+
+			impl $crate::parameters::Key<$value_name> for $key {
+				type Value = $value;
+
+				#[allow(unreachable_patterns)]
+				fn try_from_aggregated(value: $value_name) -> Option<$value> {
+					match value {
+						$value_name::$key(value) => Some(value),
+						_ => None,
+					}
+				}
+			}
+		)+
+
+		#[derive(
+			Clone, PartialEq, Eq,
+			$crate::parameters::frame_support::codec::Encode,
+			$crate::parameters::frame_support::codec::Decode,
+			$crate::parameters::frame_support::RuntimeDebug,
+			$crate::parameters::frame_support::scale_info::TypeInfo,
+		)]
+		$vis enum $name {
+			$(
+				#[codec(index = $index)]
+				$key($key, Option<$value>),
+			)+
+		}
+
+		#[derive(
+			Clone, PartialEq, Eq,
+			$crate::parameters::frame_support::codec::Encode,
+			$crate::parameters::frame_support::codec::Decode,
+			$crate::parameters::frame_support::RuntimeDebug,
+			$crate::parameters::frame_support::scale_info::TypeInfo,
+		)]
+		$vis enum $key_name {
+			$(
+				#[codec(index = $index)]
+				$key($key),
+			)+
+		}
+
+		#[derive(
+			Clone, PartialEq, Eq,
+			$crate::parameters::frame_support::codec::Encode,
+			$crate::parameters::frame_support::codec::Decode,
+			$crate::parameters::frame_support::RuntimeDebug,
+			$crate::parameters::frame_support::scale_info::TypeInfo,
+		)]
+		$vis enum $value_name {
+			$(
+				#[codec(index = $index)]
+				$key($value),
+			)+
+		}
+
+		impl $crate::parameters::AggregatedKeyValue for $name {
+			type AggregatedKey = $key_name;
+			type AggregatedValue = $value_name;
+
+			fn into_parts(self) -> (Self::AggregatedKey, Option<Self::AggregatedValue>) {
+				match self {
+					$(
+						$name::$key(key, value) => ($key_name::$key(key), value.map($value_name::$key)),
+					)+
+				}
+			}
+		}
+	};
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	define_parameters! {
+		pub TestParameters, TestParametersKey, TestParametersValue = {
+			MinBond: u128 = 0,
+			Enabled: bool = 3,
+		}
+	}
+
+	#[test]
+	fn define_parameters_works() {
+		assert_eq!(
+			TestParameters::MinBond(MinBond, Some(10)).into_parts(),
+			(
+				TestParametersKey::MinBond(MinBond),
+				Some(TestParametersValue::MinBond(10))
+			)
+		);
+		assert_eq!(
+			TestParameters::Enabled(Enabled, None).into_parts(),
+			(TestParametersKey::Enabled(Enabled), None)
+		);
+		assert_eq!(TestParametersKey::from(Enabled), TestParametersKey::Enabled(Enabled));
+
+		assert_eq!(MinBond::try_from_aggregated(TestParametersValue::MinBond(10)), Some(10));
+		assert_eq!(MinBond::try_from_aggregated(TestParametersValue::Enabled(true)), None);
+
+		// codec indexes
+		assert_eq!(TestParametersKey::Enabled(Enabled).encode(), vec![3]);
+		assert_eq!(TestParametersValue::MinBond(1).encode()[0], 0);
+	}
+}
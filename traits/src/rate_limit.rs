@@ -0,0 +1,163 @@
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, UniqueSaturatedInto, Zero},
+	RuntimeDebug,
+};
+
+/// Rate limiter errors.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum RateLimiterError {
+	/// The value exceeds the rate limit.
+	ExceedLimit,
+}
+
+/// Limit the rate of values consumed under keys.
+pub trait RateLimiter {
+	/// The id of a rate limiter, each id has its own limits.
+	type RateLimiterId: Clone;
+
+	/// Returns true if `key` bypasses the rate limits of `limiter_id`.
+	fn is_whitelist(limiter_id: Self::RateLimiterId, key: impl Encode) -> bool;
+
+	/// Check if `value` could be consumed under `key`.
+	fn can_consume(limiter_id: Self::RateLimiterId, key: impl Encode, value: u128) -> Result<(), RateLimiterError>;
+
+	/// Consume `value` under `key`.
+	fn consume(limiter_id: Self::RateLimiterId, key: impl Encode, value: u128);
+
+	/// Consume `value` under `key` if it's within the rate limit. Nothing is
+	/// checked or consumed if `whitelist_check` bypasses the rate limits.
+	fn try_consume(
+		limiter_id: Self::RateLimiterId,
+		key: impl Encode + Clone,
+		value: u128,
+		whitelist_check: Option<impl Encode>,
+	) -> Result<(), RateLimiterError> {
+		let need_consume = match whitelist_check {
+			Some(whitelist_key) => !Self::is_whitelist(limiter_id.clone(), whitelist_key),
+			None => true,
+		};
+
+		if need_consume {
+			Self::can_consume(limiter_id.clone(), key.clone(), value)?;
+			Self::consume(limiter_id, key, value);
+		}
+
+		Ok(())
+	}
+}
+
+impl RateLimiter for () {
+	type RateLimiterId = ();
+
+	fn is_whitelist(_: Self::RateLimiterId, _: impl Encode) -> bool {
+		true
+	}
+
+	fn can_consume(_: Self::RateLimiterId, _: impl Encode, _: u128) -> Result<(), RateLimiterError> {
+		Ok(())
+	}
+
+	fn consume(_: Self::RateLimiterId, _: impl Encode, _: u128) {}
+}
+
+/// A token bucket holding up to `capacity` tokens, refilled by
+/// `refill_amount` tokens every `refill_period`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct TokenBucket<Moment> {
+	/// The maximum tokens in the bucket.
+	pub capacity: u128,
+	/// The tokens refilled every `refill_period`.
+	pub refill_amount: u128,
+	/// The period of refills. Zero to refill the bucket fully on every
+	/// consumption.
+	pub refill_period: Moment,
+	/// The tokens currently in the bucket.
+	pub tokens: u128,
+	/// The last time the bucket was refilled.
+	pub last_refilled: Moment,
+}
+
+impl<Moment: AtLeast32BitUnsigned + Copy> TokenBucket<Moment> {
+	/// A full bucket.
+	pub fn new(capacity: u128, refill_amount: u128, refill_period: Moment, now: Moment) -> Self {
+		Self {
+			capacity,
+			refill_amount,
+			refill_period,
+			tokens: capacity,
+			last_refilled: now,
+		}
+	}
+
+	/// Refill the tokens for the periods elapsed since the last refill.
+	pub fn refill(&mut self, now: Moment) {
+		if self.refill_period.is_zero() {
+			self.tokens = self.capacity;
+			self.last_refilled = now;
+			return;
+		}
+
+		let elapsed = now.saturating_sub(self.last_refilled);
+		let periods: u128 = (elapsed / self.refill_period).unique_saturated_into();
+		if periods > 0 {
+			self.tokens = self
+				.tokens
+				.saturating_add(periods.saturating_mul(self.refill_amount))
+				.min(self.capacity);
+			self.last_refilled = now.saturating_sub(elapsed % self.refill_period);
+		}
+	}
+
+	/// Returns true if `value` tokens could be consumed.
+	pub fn can_consume(&self, value: u128) -> bool {
+		value <= self.tokens
+	}
+
+	/// Consume `value` tokens, saturating at zero.
+	pub fn consume(&mut self, value: u128) {
+		self.tokens = self.tokens.saturating_sub(value);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn token_bucket_should_work() {
+		let mut bucket = TokenBucket::new(100, 10, 5u32, 0);
+		assert!(bucket.can_consume(100));
+		assert!(!bucket.can_consume(101));
+
+		bucket.consume(80);
+		assert_eq!(bucket.tokens, 20);
+
+		// not a full period yet
+		bucket.refill(4);
+		assert_eq!(bucket.tokens, 20);
+
+		bucket.refill(12);
+		assert_eq!(bucket.tokens, 40);
+		assert_eq!(bucket.last_refilled, 10);
+
+		bucket.refill(15);
+		assert_eq!(bucket.tokens, 50);
+
+		// never exceeds capacity
+		bucket.refill(1000);
+		assert_eq!(bucket.tokens, 100);
+
+		bucket.consume(200);
+		assert_eq!(bucket.tokens, 0);
+	}
+
+	#[test]
+	fn token_bucket_without_refill_period_should_refill_fully() {
+		let mut bucket = TokenBucket::new(100, 0, 0u32, 0);
+		bucket.consume(100);
+		bucket.refill(0);
+		assert_eq!(bucket.tokens, 100);
+	}
+}
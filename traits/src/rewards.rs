@@ -1,5 +1,8 @@
 use codec::FullCodec;
-use sp_runtime::traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize};
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize, One},
+	FixedU128,
+};
 use sp_std::fmt::Debug;
 
 /// Hooks to manage reward pool
@@ -13,3 +16,27 @@ pub trait RewardHandler<AccountId, CurrencyId> {
 	/// Payout the reward to `who`
 	fn payout(who: &AccountId, pool: &Self::PoolId, currency_id: CurrencyId, amount: Self::Balance);
 }
+
+/// Handler for the rewards deducted at claim time
+pub trait OnRewardDeduction<AccountId, PoolId, CurrencyId, Balance> {
+	/// `amount` of `currency_id` reward is deducted from the claim of `who`
+	/// in `pool`
+	fn on_reward_deduction(who: &AccountId, pool: &PoolId, currency_id: CurrencyId, amount: Balance);
+}
+
+impl<AccountId, PoolId, CurrencyId, Balance> OnRewardDeduction<AccountId, PoolId, CurrencyId, Balance> for () {
+	fn on_reward_deduction(_who: &AccountId, _pool: &PoolId, _currency_id: CurrencyId, _amount: Balance) {}
+}
+
+/// Provide the share multipliers of accounts in reward pools, e.g. boosting
+/// the shares of longer lock-ups
+pub trait ShareBoost<AccountId, PoolId> {
+	/// The multiplier applied to the share of `who` in `pool`
+	fn share_multiplier(who: &AccountId, pool: &PoolId) -> FixedU128;
+}
+
+impl<AccountId, PoolId> ShareBoost<AccountId, PoolId> for () {
+	fn share_multiplier(_who: &AccountId, _pool: &PoolId) -> FixedU128 {
+		One::one()
+	}
+}
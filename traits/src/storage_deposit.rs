@@ -0,0 +1,45 @@
+use crate::currency::NamedMultiReservableCurrency;
+use frame_support::traits::Get;
+use sp_runtime::DispatchResult;
+use sp_std::marker::PhantomData;
+
+/// Take and refund deposits for the storage used by accounts.
+pub trait StorageDepositHandler<AccountId, CurrencyId, Balance> {
+	/// Take `amount` of `currency_id` from `who` as storage deposit.
+	fn take_deposit(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult;
+
+	/// Refund up to `amount` of the storage deposit of `who`, returns the
+	/// amount that could not be refunded.
+	fn refund_deposit(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> Balance;
+
+	/// The storage deposit of `who` in `currency_id`.
+	fn deposit_of(currency_id: CurrencyId, who: &AccountId) -> Balance;
+}
+
+/// Storage deposits held as named reserves of `Currency`, under the
+/// identifier given by `GetReserveId`. Each pallet should use its own
+/// identifier, so its deposits are kept apart from other reserves.
+pub struct NamedReserveStorageDeposit<Currency, GetReserveId>(PhantomData<(Currency, GetReserveId)>);
+
+impl<AccountId, Currency, GetReserveId> StorageDepositHandler<AccountId, Currency::CurrencyId, Currency::Balance>
+	for NamedReserveStorageDeposit<Currency, GetReserveId>
+where
+	Currency: NamedMultiReservableCurrency<AccountId>,
+	GetReserveId: Get<Currency::ReserveIdentifier>,
+{
+	fn take_deposit(currency_id: Currency::CurrencyId, who: &AccountId, amount: Currency::Balance) -> DispatchResult {
+		Currency::reserve_named(&GetReserveId::get(), currency_id, who, amount)
+	}
+
+	fn refund_deposit(
+		currency_id: Currency::CurrencyId,
+		who: &AccountId,
+		amount: Currency::Balance,
+	) -> Currency::Balance {
+		Currency::unreserve_named(&GetReserveId::get(), currency_id, who, amount)
+	}
+
+	fn deposit_of(currency_id: Currency::CurrencyId, who: &AccountId) -> Currency::Balance {
+		Currency::reserved_balance_named(&GetReserveId::get(), currency_id, who)
+	}
+}
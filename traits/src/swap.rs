@@ -0,0 +1,14 @@
+use sp_runtime::DispatchError;
+
+/// Swap between currencies.
+pub trait Swap<AccountId, CurrencyId, Balance> {
+	/// Swap `supply_amount` of `supply_currency_id` of `who` to
+	/// `target_currency_id`, returns the amount of `target_currency_id`
+	/// received by `who`.
+	fn swap(
+		who: &AccountId,
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		supply_amount: Balance,
+	) -> Result<Balance, DispatchError>;
+}
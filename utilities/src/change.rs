@@ -0,0 +1,157 @@
+//! Changes to values, e.g. the parameters of update calls.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedSub, Saturating},
+	ArithmeticError, PerThing, Percent, RuntimeDebug,
+};
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Indicate if should change a value
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Change<Value> {
+	/// No change.
+	NoChange,
+	/// Changed to new value.
+	NewValue(Value),
+}
+
+impl<Value> Change<Value> {
+	/// Apply the change to `value`.
+	pub fn apply(self, value: &mut Value) {
+		if let Change::NewValue(new_value) = self {
+			*value = new_value;
+		}
+	}
+}
+
+/// A change to a value, absolute or relative to the current value.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ChangeDelta<Value> {
+	/// No change.
+	NoChange,
+	/// Changed to new value.
+	NewValue(Value),
+	/// Increased by the given amount.
+	Increase(Value),
+	/// Decreased by the given amount.
+	Decrease(Value),
+	/// Changed to the given percent of the value, rounded down.
+	Percent(Percent),
+}
+
+impl<Value: AtLeast32BitUnsigned + Copy> ChangeDelta<Value> {
+	/// Apply the change to `value`, saturating at the numeric bounds.
+	pub fn apply(self, value: &mut Value) {
+		*value = match self {
+			ChangeDelta::NoChange => *value,
+			ChangeDelta::NewValue(new_value) => new_value,
+			ChangeDelta::Increase(amount) => value.saturating_add(amount),
+			ChangeDelta::Decrease(amount) => value.saturating_sub(amount),
+			ChangeDelta::Percent(percent) => percent.mul_floor(*value),
+		};
+	}
+
+	/// Apply the change to `value`, or return an error and leave `value`
+	/// unchanged on overflow or underflow.
+	pub fn checked_apply(self, value: &mut Value) -> Result<(), ArithmeticError> {
+		*value = match self {
+			ChangeDelta::NoChange => *value,
+			ChangeDelta::NewValue(new_value) => new_value,
+			ChangeDelta::Increase(amount) => value.checked_add(&amount).ok_or(ArithmeticError::Overflow)?,
+			ChangeDelta::Decrease(amount) => value.checked_sub(&amount).ok_or(ArithmeticError::Underflow)?,
+			ChangeDelta::Percent(percent) => percent.mul_floor(*value),
+		};
+		Ok(())
+	}
+}
+
+impl<Value> From<Change<Value>> for ChangeDelta<Value> {
+	fn from(change: Change<Value>) -> Self {
+		match change {
+			Change::NoChange => ChangeDelta::NoChange,
+			Change::NewValue(value) => ChangeDelta::NewValue(value),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn change_apply_should_work() {
+		let mut value = 10u32;
+		Change::NoChange.apply(&mut value);
+		assert_eq!(value, 10);
+		Change::NewValue(20).apply(&mut value);
+		assert_eq!(value, 20);
+	}
+
+	#[test]
+	fn change_delta_apply_should_work() {
+		let mut value = 10u32;
+		ChangeDelta::NoChange.apply(&mut value);
+		assert_eq!(value, 10);
+		ChangeDelta::Increase(5).apply(&mut value);
+		assert_eq!(value, 15);
+		ChangeDelta::Decrease(10).apply(&mut value);
+		assert_eq!(value, 5);
+		ChangeDelta::Decrease(10).apply(&mut value);
+		assert_eq!(value, 0);
+		ChangeDelta::Increase(u32::MAX).apply(&mut value);
+		ChangeDelta::Increase(1).apply(&mut value);
+		assert_eq!(value, u32::MAX);
+		ChangeDelta::NewValue(99).apply(&mut value);
+		assert_eq!(value, 99);
+		ChangeDelta::Percent(Percent::from_percent(50)).apply(&mut value);
+		assert_eq!(value, 49);
+		ChangeDelta::from(Change::NewValue(7)).apply(&mut value);
+		assert_eq!(value, 7);
+	}
+
+	#[test]
+	fn change_delta_checked_apply_should_work() {
+		let mut value = 10u32;
+		assert_eq!(ChangeDelta::Increase(5).checked_apply(&mut value), Ok(()));
+		assert_eq!(value, 15);
+		assert_eq!(
+			ChangeDelta::Decrease(16).checked_apply(&mut value),
+			Err(ArithmeticError::Underflow)
+		);
+		assert_eq!(
+			ChangeDelta::Increase(u32::MAX).checked_apply(&mut value),
+			Err(ArithmeticError::Overflow)
+		);
+		assert_eq!(value, 15);
+		assert_eq!(
+			ChangeDelta::Percent(Percent::from_percent(20)).checked_apply(&mut value),
+			Ok(())
+		);
+		assert_eq!(value, 3);
+	}
+
+	#[test]
+	fn change_serde_should_work() {
+		let change = ChangeDelta::Increase(5u32);
+		let json = serde_json::to_string(&change).unwrap();
+		assert_eq!(json, r#"{"Increase":5}"#);
+		assert_eq!(serde_json::from_str::<ChangeDelta<u32>>(&json).unwrap(), change);
+		assert_eq!(
+			serde_json::from_str::<Change<u32>>(r#"{"NewValue":1}"#).unwrap(),
+			Change::NewValue(1)
+		);
+	}
+
+	#[test]
+	fn change_max_encoded_len_should_work() {
+		assert_eq!(Change::<u32>::max_encoded_len(), 5);
+		assert_eq!(ChangeDelta::<u32>::max_encoded_len(), 5);
+		assert_eq!(ChangeDelta::<u32>::NewValue(1).encode().len(), 5);
+	}
+}
@@ -0,0 +1,133 @@
+use codec::FullCodec;
+use frame_support::{
+	storage::{generator::StorageMap as StorageMapT, IterableStorageMap, StorageValue},
+	weights::Weight,
+	ReversibleStorageHasher,
+};
+use sp_std::prelude::*;
+
+/// A storage map which can be iterated through in chunks bounded by weight,
+/// resuming from a cursor persisted in storage.
+pub trait IterableStorageMapChunked<K: FullCodec, V: FullCodec>: IterableStorageMap<K, V> {
+	/// Iterate through the next chunk of elements from the cursor kept in
+	/// `Cursor`, calling `f` with each of them, until `weight_limit` is
+	/// consumed at `weight_per_item` per element. The cursor is persisted so
+	/// the next call resumes after the last visited element, and killed once
+	/// the whole map is iterated. `f` may remove the visited element.
+	///
+	/// Returns the consumed weight.
+	fn iter_chunk<Cursor: StorageValue<Vec<u8>, Query = Option<Vec<u8>>>>(
+		weight_limit: Weight,
+		weight_per_item: Weight,
+		f: impl FnMut(K, V),
+	) -> Weight;
+}
+
+impl<K: FullCodec, V: FullCodec, G: StorageMapT<K, V>> IterableStorageMapChunked<K, V> for G
+where
+	G::Hasher: ReversibleStorageHasher,
+{
+	fn iter_chunk<Cursor: StorageValue<Vec<u8>, Query = Option<Vec<u8>>>>(
+		weight_limit: Weight,
+		weight_per_item: Weight,
+		mut f: impl FnMut(K, V),
+	) -> Weight {
+		// unbounded if `weight_per_item` is zero
+		let max_iterations = weight_limit.checked_div(weight_per_item);
+		let mut iterator = match Cursor::get() {
+			Some(cursor) => <G as IterableStorageMap<K, V>>::iter_from(cursor),
+			None => <G as IterableStorageMap<K, V>>::iter(),
+		};
+
+		let mut count: Weight = 0;
+		let mut finished = false;
+		while max_iterations.map_or(true, |max| count < max) {
+			match iterator.next() {
+				Some((key, value)) => {
+					f(key, value);
+					count += 1;
+				}
+				None => {
+					finished = true;
+					break;
+				}
+			}
+		}
+
+		if finished {
+			Cursor::kill();
+		} else {
+			Cursor::put(iterator.last_raw_key().to_vec());
+		}
+
+		weight_per_item.saturating_mul(count)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::{decl_module, decl_storage};
+	use sp_io::TestExternalities;
+
+	pub trait Config: frame_system::Config {}
+
+	decl_module! {
+		pub struct Module<T: Config> for enum Call where origin: T::Origin {}
+	}
+
+	decl_storage! {
+		trait Store for Module<T: Config> as ChunkTest {
+			pub TestCursor: Option<Vec<u8>>;
+			pub TestMap: map hasher(twox_64_concat) u32 => u32;
+		}
+	}
+
+	#[test]
+	fn iter_chunk_should_resume_from_cursor() {
+		TestExternalities::default().execute_with(|| {
+			for i in 0..5 {
+				TestMap::insert(i, i * 10);
+			}
+
+			let mut visited = vec![];
+			assert_eq!(
+				TestMap::iter_chunk::<TestCursor>(25, 10, |k, v| visited.push((k, v))),
+				20
+			);
+			assert_eq!(visited.len(), 2);
+			assert!(TestCursor::exists());
+
+			assert_eq!(
+				TestMap::iter_chunk::<TestCursor>(25, 10, |k, v| visited.push((k, v))),
+				20
+			);
+			assert_eq!(visited.len(), 4);
+			assert!(TestCursor::exists());
+
+			// the last chunk finishes the iteration and kills the cursor
+			assert_eq!(
+				TestMap::iter_chunk::<TestCursor>(25, 10, |k, v| visited.push((k, v))),
+				10
+			);
+			assert!(!TestCursor::exists());
+
+			visited.sort_unstable();
+			assert_eq!(visited, vec![(0, 0), (1, 10), (2, 20), (3, 30), (4, 40)]);
+		});
+	}
+
+	#[test]
+	fn iter_chunk_should_allow_removing_visited_elements() {
+		TestExternalities::default().execute_with(|| {
+			for i in 0..5 {
+				TestMap::insert(i, i * 10);
+			}
+
+			assert_eq!(TestMap::iter_chunk::<TestCursor>(30, 10, |k, _| TestMap::remove(k)), 30);
+			assert_eq!(TestMap::iter_chunk::<TestCursor>(30, 10, |k, _| TestMap::remove(k)), 20);
+			assert!(!TestCursor::exists());
+			assert_eq!(TestMap::iter().count(), 0);
+		});
+	}
+}
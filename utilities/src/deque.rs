@@ -0,0 +1,218 @@
+use codec::{Decode, Encode, FullCodec, MaxEncodedLen};
+use frame_support::storage::{StorageMap, StorageValue};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::marker::PhantomData;
+
+/// The index range `[head, tail)` of the items of a `StorageDeque`.
+///
+/// Indexes wrap around, so the deque can be pushed on both ends
+/// indefinitely.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct DequeBounds {
+	pub head: u32,
+	pub tail: u32,
+}
+
+impl DequeBounds {
+	/// Number of items in the deque.
+	pub fn len(&self) -> u32 {
+		self.tail.wrapping_sub(self.head)
+	}
+
+	/// Return if the deque is empty.
+	pub fn is_empty(&self) -> bool {
+		self.head == self.tail
+	}
+}
+
+/// A double-ended queue, stored in `Bounds` and `Items`.
+///
+/// Pushing and popping on both ends read and write a constant number of
+/// storage entries, so the queue never has to be loaded as a whole.
+pub struct StorageDeque<Bounds, Items, V>(PhantomData<(Bounds, Items, V)>);
+
+impl<Bounds, Items, V> StorageDeque<Bounds, Items, V>
+where
+	Bounds: StorageValue<DequeBounds, Query = DequeBounds>,
+	Items: StorageMap<u32, V, Query = Option<V>>,
+	V: FullCodec,
+{
+	/// Number of items in the deque.
+	pub fn len() -> u32 {
+		Bounds::get().len()
+	}
+
+	/// Return if the deque is empty.
+	pub fn is_empty() -> bool {
+		Bounds::get().is_empty()
+	}
+
+	/// Return the first item.
+	pub fn front() -> Option<V> {
+		let bounds = Bounds::get();
+		if bounds.is_empty() {
+			return None;
+		}
+		Items::get(bounds.head)
+	}
+
+	/// Return the last item.
+	pub fn back() -> Option<V> {
+		let bounds = Bounds::get();
+		if bounds.is_empty() {
+			return None;
+		}
+		Items::get(bounds.tail.wrapping_sub(1))
+	}
+
+	/// Append `value` to the back of the deque.
+	/// Return false if the deque is full.
+	pub fn push_back(value: V) -> bool {
+		let mut bounds = Bounds::get();
+		if bounds.len() == u32::MAX {
+			return false;
+		}
+		Items::insert(bounds.tail, value);
+		bounds.tail = bounds.tail.wrapping_add(1);
+		Bounds::put(bounds);
+		true
+	}
+
+	/// Prepend `value` to the front of the deque.
+	/// Return false if the deque is full.
+	pub fn push_front(value: V) -> bool {
+		let mut bounds = Bounds::get();
+		if bounds.len() == u32::MAX {
+			return false;
+		}
+		bounds.head = bounds.head.wrapping_sub(1);
+		Items::insert(bounds.head, value);
+		Bounds::put(bounds);
+		true
+	}
+
+	/// Remove and return the first item.
+	pub fn pop_front() -> Option<V> {
+		let mut bounds = Bounds::get();
+		if bounds.is_empty() {
+			return None;
+		}
+		let value = Items::take(bounds.head);
+		bounds.head = bounds.head.wrapping_add(1);
+		Bounds::put(bounds);
+		value
+	}
+
+	/// Remove and return the last item.
+	pub fn pop_back() -> Option<V> {
+		let mut bounds = Bounds::get();
+		if bounds.is_empty() {
+			return None;
+		}
+		bounds.tail = bounds.tail.wrapping_sub(1);
+		let value = Items::take(bounds.tail);
+		Bounds::put(bounds);
+		value
+	}
+
+	/// Iterate over the items from front to back.
+	///
+	/// Items are read lazily, so the iteration can be bounded with `take`.
+	pub fn iter() -> StorageDequeIterator<Items, V> {
+		StorageDequeIterator {
+			bounds: Bounds::get(),
+			_phantom: PhantomData,
+		}
+	}
+}
+
+/// Iterator over the items of a `StorageDeque`.
+pub struct StorageDequeIterator<Items, V> {
+	bounds: DequeBounds,
+	_phantom: PhantomData<(Items, V)>,
+}
+
+impl<Items, V> Iterator for StorageDequeIterator<Items, V>
+where
+	Items: StorageMap<u32, V, Query = Option<V>>,
+	V: FullCodec,
+{
+	type Item = V;
+
+	fn next(&mut self) -> Option<V> {
+		if self.bounds.is_empty() {
+			return None;
+		}
+		let value = Items::get(self.bounds.head);
+		self.bounds.head = self.bounds.head.wrapping_add(1);
+		value
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::{decl_module, decl_storage, storage::IterableStorageMap};
+	use sp_io::TestExternalities;
+	use sp_std::prelude::*;
+
+	pub trait Config: frame_system::Config {}
+
+	decl_module! {
+		pub struct Module<T: Config> for enum Call where origin: T::Origin {}
+	}
+
+	decl_storage! {
+		trait Store for Module<T: Config> as Deque {
+			pub TestBounds: DequeBounds;
+			pub TestItems: map hasher(twox_64_concat) u32 => Option<u32>;
+		}
+	}
+
+	type TestDeque = StorageDeque<TestBounds, TestItems, u32>;
+
+	fn items() -> Vec<u32> {
+		TestDeque::iter().collect()
+	}
+
+	#[test]
+	fn push_and_pop() {
+		TestExternalities::default().execute_with(|| {
+			assert!(TestDeque::is_empty());
+			assert_eq!(TestDeque::pop_front(), None);
+			assert_eq!(TestDeque::pop_back(), None);
+
+			assert!(TestDeque::push_back(2));
+			assert!(TestDeque::push_back(3));
+			// wraps around below index 0
+			assert!(TestDeque::push_front(1));
+			assert!(TestDeque::push_front(0));
+			assert_eq!(items(), vec![0, 1, 2, 3]);
+			assert_eq!(TestDeque::len(), 4);
+			assert_eq!(TestDeque::front(), Some(0));
+			assert_eq!(TestDeque::back(), Some(3));
+
+			assert_eq!(TestDeque::pop_front(), Some(0));
+			assert_eq!(TestDeque::pop_back(), Some(3));
+			assert_eq!(items(), vec![1, 2]);
+			assert_eq!(TestDeque::pop_front(), Some(1));
+			assert_eq!(TestDeque::pop_front(), Some(2));
+			assert!(TestDeque::is_empty());
+			assert_eq!(TestDeque::front(), None);
+			assert_eq!(TestItems::iter().count(), 0);
+		});
+	}
+
+	#[test]
+	fn bounded_iteration() {
+		TestExternalities::default().execute_with(|| {
+			for i in 1..=5 {
+				TestDeque::push_back(i);
+			}
+
+			assert_eq!(TestDeque::iter().take(2).collect::<Vec<_>>(), vec![1, 2]);
+			assert_eq!(TestDeque::len(), 5);
+		});
+	}
+}
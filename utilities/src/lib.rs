@@ -4,17 +4,26 @@ use frame_support::storage::{with_transaction, TransactionOutcome};
 use sp_runtime::DispatchError;
 use sp_std::result::Result;
 
+pub mod change;
+pub mod chunk;
+pub mod deque;
 #[deprecated(
 	since = "0.4.1",
 	note = "iterator module's functionality is now available in substrate's frame-support"
 )]
 pub mod iterator;
+pub mod linked_list;
+pub mod math;
 pub mod offchain_worker;
 pub mod ordered_set;
 
 #[allow(deprecated)]
 pub use iterator::{IterableStorageDoubleMapExtended, IterableStorageMapExtended};
 
+pub use change::{Change, ChangeDelta};
+pub use chunk::IterableStorageMapChunked;
+pub use deque::StorageDeque;
+pub use linked_list::StorageLinkedList;
 pub use offchain_worker::OffchainErr;
 pub use ordered_set::OrderedSet;
 
@@ -0,0 +1,253 @@
+use codec::{Decode, Encode, FullCodec, MaxEncodedLen};
+use frame_support::storage::{StorageMap, StorageValue};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::marker::PhantomData;
+
+/// The head and tail of a `StorageLinkedList`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct LinkedListEnds<K> {
+	pub head: Option<K>,
+	pub tail: Option<K>,
+}
+
+/// The neighbours of an item in a `StorageLinkedList`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct Link<K> {
+	pub prev: Option<K>,
+	pub next: Option<K>,
+}
+
+/// A doubly linked list of unique items, stored in `Ends` and `Links`.
+///
+/// Pushing and popping on both ends, and removing any item, read and write a
+/// constant number of storage entries, so the list never has to be loaded as
+/// a whole.
+pub struct StorageLinkedList<Ends, Links, K>(PhantomData<(Ends, Links, K)>);
+
+impl<Ends, Links, K> StorageLinkedList<Ends, Links, K>
+where
+	Ends: StorageValue<LinkedListEnds<K>, Query = LinkedListEnds<K>>,
+	Links: StorageMap<K, Link<K>, Query = Option<Link<K>>>,
+	K: FullCodec + Clone + PartialEq,
+{
+	/// Return if the list contains `item`.
+	pub fn contains(item: &K) -> bool {
+		Links::contains_key(item)
+	}
+
+	/// Return if the list is empty.
+	pub fn is_empty() -> bool {
+		Ends::get().head.is_none()
+	}
+
+	/// Return the first item.
+	pub fn front() -> Option<K> {
+		Ends::get().head
+	}
+
+	/// Return the last item.
+	pub fn back() -> Option<K> {
+		Ends::get().tail
+	}
+
+	/// Append `item` to the back of the list.
+	/// Return false if the list already contains `item`.
+	pub fn push_back(item: K) -> bool {
+		if Self::contains(&item) {
+			return false;
+		}
+
+		let mut ends = Ends::get();
+		let prev = ends.tail.replace(item.clone());
+		match &prev {
+			Some(tail) => Links::mutate(tail, |link| {
+				if let Some(link) = link {
+					link.next = Some(item.clone());
+				}
+			}),
+			None => ends.head = Some(item.clone()),
+		}
+		Links::insert(&item, Link { prev, next: None });
+		Ends::put(ends);
+		true
+	}
+
+	/// Prepend `item` to the front of the list.
+	/// Return false if the list already contains `item`.
+	pub fn push_front(item: K) -> bool {
+		if Self::contains(&item) {
+			return false;
+		}
+
+		let mut ends = Ends::get();
+		let next = ends.head.replace(item.clone());
+		match &next {
+			Some(head) => Links::mutate(head, |link| {
+				if let Some(link) = link {
+					link.prev = Some(item.clone());
+				}
+			}),
+			None => ends.tail = Some(item.clone()),
+		}
+		Links::insert(&item, Link { prev: None, next });
+		Ends::put(ends);
+		true
+	}
+
+	/// Remove and return the first item.
+	pub fn pop_front() -> Option<K> {
+		let head = Ends::get().head?;
+		Self::remove(&head);
+		Some(head)
+	}
+
+	/// Remove and return the last item.
+	pub fn pop_back() -> Option<K> {
+		let tail = Ends::get().tail?;
+		Self::remove(&tail);
+		Some(tail)
+	}
+
+	/// Remove `item` from the list.
+	/// Return true if removal happened.
+	pub fn remove(item: &K) -> bool {
+		let link = match Links::take(item) {
+			Some(link) => link,
+			None => return false,
+		};
+
+		let mut ends = Ends::get();
+		match &link.prev {
+			Some(prev) => Links::mutate(prev, |l| {
+				if let Some(l) = l {
+					l.next = link.next.clone();
+				}
+			}),
+			None => ends.head = link.next.clone(),
+		}
+		match &link.next {
+			Some(next) => Links::mutate(next, |l| {
+				if let Some(l) = l {
+					l.prev = link.prev.clone();
+				}
+			}),
+			None => ends.tail = link.prev,
+		}
+		Ends::put(ends);
+		true
+	}
+
+	/// Iterate over the items from front to back.
+	///
+	/// Items are read lazily, so the iteration can be bounded with `take`.
+	pub fn iter() -> StorageLinkedListIterator<Links, K> {
+		StorageLinkedListIterator {
+			next: Ends::get().head,
+			_phantom: PhantomData,
+		}
+	}
+}
+
+/// Iterator over the items of a `StorageLinkedList`.
+pub struct StorageLinkedListIterator<Links, K> {
+	next: Option<K>,
+	_phantom: PhantomData<Links>,
+}
+
+impl<Links, K> Iterator for StorageLinkedListIterator<Links, K>
+where
+	Links: StorageMap<K, Link<K>, Query = Option<Link<K>>>,
+	K: FullCodec,
+{
+	type Item = K;
+
+	fn next(&mut self) -> Option<K> {
+		let item = self.next.take()?;
+		self.next = Links::get(&item).and_then(|link| link.next);
+		Some(item)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::{decl_module, decl_storage};
+	use sp_io::TestExternalities;
+	use sp_std::prelude::*;
+
+	pub trait Config: frame_system::Config {}
+
+	decl_module! {
+		pub struct Module<T: Config> for enum Call where origin: T::Origin {}
+	}
+
+	decl_storage! {
+		trait Store for Module<T: Config> as LinkedList {
+			pub TestEnds: LinkedListEnds<u32>;
+			pub TestLinks: map hasher(twox_64_concat) u32 => Option<Link<u32>>;
+		}
+	}
+
+	type TestList = StorageLinkedList<TestEnds, TestLinks, u32>;
+
+	fn items() -> Vec<u32> {
+		TestList::iter().collect()
+	}
+
+	#[test]
+	fn push_and_pop() {
+		TestExternalities::default().execute_with(|| {
+			assert!(TestList::is_empty());
+			assert_eq!(TestList::pop_front(), None);
+			assert_eq!(TestList::pop_back(), None);
+
+			assert!(TestList::push_back(2));
+			assert!(TestList::push_back(3));
+			assert!(TestList::push_front(1));
+			assert!(!TestList::push_back(1));
+			assert!(!TestList::push_front(3));
+			assert_eq!(items(), vec![1, 2, 3]);
+			assert_eq!(TestList::front(), Some(1));
+			assert_eq!(TestList::back(), Some(3));
+
+			assert_eq!(TestList::pop_front(), Some(1));
+			assert_eq!(TestList::pop_back(), Some(3));
+			assert_eq!(items(), vec![2]);
+			assert_eq!(TestList::pop_back(), Some(2));
+			assert!(TestList::is_empty());
+			assert_eq!(TestEnds::get(), LinkedListEnds::default());
+		});
+	}
+
+	#[test]
+	fn remove() {
+		TestExternalities::default().execute_with(|| {
+			for i in 1..=5 {
+				TestList::push_back(i);
+			}
+
+			assert!(TestList::remove(&3));
+			assert!(!TestList::remove(&3));
+			assert_eq!(items(), vec![1, 2, 4, 5]);
+
+			assert!(TestList::remove(&1));
+			assert!(TestList::remove(&5));
+			assert_eq!(items(), vec![2, 4]);
+			assert_eq!(TestList::front(), Some(2));
+			assert_eq!(TestList::back(), Some(4));
+			assert!(!TestList::contains(&5));
+		});
+	}
+
+	#[test]
+	fn bounded_iteration() {
+		TestExternalities::default().execute_with(|| {
+			for i in 1..=5 {
+				TestList::push_back(i);
+			}
+
+			assert_eq!(TestList::iter().take(2).collect::<Vec<_>>(), vec![1, 2]);
+		});
+	}
+}
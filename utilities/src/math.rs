@@ -0,0 +1,160 @@
+//! Fixed-point math helpers for rate and share calculations.
+
+use sp_core::U256;
+use sp_runtime::{FixedPointNumber, FixedU128, PerThing, RuntimeDebug};
+
+/// Rounding mode of a division.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Rounding {
+	/// Round towards zero.
+	Down,
+	/// Round away from zero.
+	Up,
+	/// Round to the nearest value, half away from zero.
+	Nearest,
+}
+
+/// Calculate `a * b / c` with a `U256` intermediate, so `a * b` never
+/// overflows.
+///
+/// Return `None` if `c` is zero or the result doesn't fit in `u128`.
+pub fn checked_mul_div(a: u128, b: u128, c: u128, rounding: Rounding) -> Option<u128> {
+	if c == 0 {
+		return None;
+	}
+
+	// `a * b` can't overflow `U256`
+	let product = U256::from(a) * U256::from(b);
+	let c = U256::from(c);
+	let (quotient, remainder) = product.div_mod(c);
+	let round_up = match rounding {
+		Rounding::Down => false,
+		Rounding::Up => !remainder.is_zero(),
+		Rounding::Nearest => remainder >= c - (c >> 1),
+	};
+	let result = if round_up {
+		quotient.checked_add(U256::one())?
+	} else {
+		quotient
+	};
+
+	if result > u128::MAX.into() {
+		None
+	} else {
+		Some(result.low_u128())
+	}
+}
+
+/// A ratio `numerator / denominator` of two `u128`s.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct Ratio {
+	numerator: u128,
+	denominator: u128,
+}
+
+impl Ratio {
+	/// Create a ratio, return `None` if `denominator` is zero.
+	pub fn new(numerator: u128, denominator: u128) -> Option<Self> {
+		if denominator == 0 {
+			None
+		} else {
+			Some(Self { numerator, denominator })
+		}
+	}
+
+	pub fn numerator(&self) -> u128 {
+		self.numerator
+	}
+
+	pub fn denominator(&self) -> u128 {
+		self.denominator
+	}
+
+	/// Multiply `x` by the ratio, rounding down.
+	pub fn mul_floor(&self, x: u128) -> Option<u128> {
+		checked_mul_div(x, self.numerator, self.denominator, Rounding::Down)
+	}
+
+	/// Multiply `x` by the ratio, rounding up.
+	pub fn mul_ceil(&self, x: u128) -> Option<u128> {
+		checked_mul_div(x, self.numerator, self.denominator, Rounding::Up)
+	}
+
+	/// Multiply `x` by the ratio, rounding to the nearest value.
+	pub fn mul_round(&self, x: u128) -> Option<u128> {
+		checked_mul_div(x, self.numerator, self.denominator, Rounding::Nearest)
+	}
+
+	/// Convert into `FixedU128`, return `None` on overflow.
+	pub fn to_fixed(&self) -> Option<FixedU128> {
+		FixedU128::checked_from_rational(self.numerator, self.denominator)
+	}
+}
+
+/// Convert a `FixedU128` into a `PerThing` like `Permill` or `Perbill`,
+/// rounding down and saturating at one.
+pub fn fixed_to_per_thing<P: PerThing>(value: FixedU128) -> P {
+	P::from_rational(value.into_inner(), FixedU128::DIV)
+}
+
+/// Convert a `PerThing` like `Permill` or `Perbill` into a `FixedU128`.
+pub fn per_thing_to_fixed<P: PerThing>(value: P) -> FixedU128 {
+	FixedU128::saturating_from_rational(value.deconstruct().into(), P::ACCURACY.into())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::{Perbill, Permill};
+
+	#[test]
+	fn checked_mul_div_works() {
+		assert_eq!(checked_mul_div(10, 10, 3, Rounding::Down), Some(33));
+		assert_eq!(checked_mul_div(10, 10, 3, Rounding::Up), Some(34));
+		assert_eq!(checked_mul_div(10, 10, 3, Rounding::Nearest), Some(33));
+		assert_eq!(checked_mul_div(10, 5, 4, Rounding::Nearest), Some(13));
+		assert_eq!(checked_mul_div(10, 10, 5, Rounding::Up), Some(20));
+		assert_eq!(checked_mul_div(1, 1, 0, Rounding::Down), None);
+
+		// intermediate overflow of u128
+		assert_eq!(
+			checked_mul_div(u128::MAX, u128::MAX, u128::MAX, Rounding::Down),
+			Some(u128::MAX)
+		);
+		assert_eq!(checked_mul_div(u128::MAX, 3, 2, Rounding::Down), None);
+		assert_eq!(checked_mul_div(u128::MAX, 1, 1, Rounding::Up), Some(u128::MAX));
+	}
+
+	#[test]
+	fn ratio_works() {
+		assert_eq!(Ratio::new(1, 0), None);
+
+		let ratio = Ratio::new(2, 3).unwrap();
+		assert_eq!(ratio.mul_floor(100), Some(66));
+		assert_eq!(ratio.mul_ceil(100), Some(67));
+		assert_eq!(ratio.mul_round(100), Some(67));
+		assert_eq!(ratio.mul_round(10), Some(7));
+		assert_eq!(ratio.mul_floor(u128::MAX), Some(u128::MAX / 3 * 2));
+		assert_eq!(ratio.to_fixed(), Some(FixedU128::saturating_from_rational(2, 3)));
+	}
+
+	#[test]
+	fn per_thing_conversion_works() {
+		assert_eq!(
+			fixed_to_per_thing::<Permill>(FixedU128::saturating_from_rational(1, 4)),
+			Permill::from_percent(25)
+		);
+		assert_eq!(
+			fixed_to_per_thing::<Perbill>(FixedU128::saturating_from_integer(2)),
+			Perbill::one()
+		);
+		assert_eq!(
+			per_thing_to_fixed(Permill::from_percent(25)),
+			FixedU128::saturating_from_rational(1, 4)
+		);
+		assert_eq!(
+			per_thing_to_fixed(Perbill::one()),
+			FixedU128::saturating_from_integer(1)
+		);
+	}
+}
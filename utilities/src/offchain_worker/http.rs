@@ -0,0 +1,64 @@
+//! Helpers for offchain worker HTTP requests.
+
+use super::OffchainErr;
+use sp_runtime::offchain::{http, Duration};
+use sp_std::prelude::*;
+
+/// Fetch `url` with a HTTP GET request, returns the response body.
+///
+/// Each attempt times out after `timeout` milliseconds. A failed attempt,
+/// including a response with a status code other than 200, is retried up to
+/// `retries` times.
+pub fn http_get(url: &str, timeout: u64, retries: u32) -> Result<Vec<u8>, OffchainErr> {
+	let mut attempts = 0;
+	loop {
+		match fetch(url, timeout) {
+			Ok(body) => return Ok(body),
+			Err(e) if attempts >= retries => return Err(e),
+			Err(_) => attempts += 1,
+		}
+	}
+}
+
+fn fetch(url: &str, timeout: u64) -> Result<Vec<u8>, OffchainErr> {
+	let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(timeout));
+	let pending = http::Request::get(url)
+		.deadline(deadline)
+		.send()
+		.map_err(|_| OffchainErr::HttpFetching)?;
+	let response = pending
+		.try_wait(deadline)
+		.map_err(|_| OffchainErr::HttpFetching)?
+		.map_err(|_| OffchainErr::HttpFetching)?;
+	if response.code != 200 {
+		return Err(OffchainErr::HttpFetching);
+	}
+
+	Ok(response.body().collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::offchain::{testing, OffchainWorkerExt};
+	use sp_io::TestExternalities;
+
+	#[test]
+	fn http_get_works() {
+		let (offchain, state) = testing::TestOffchainExt::new();
+		let mut t = TestExternalities::default();
+		t.register_extension(OffchainWorkerExt::new(offchain));
+
+		state.write().expect_request(testing::PendingRequest {
+			method: "GET".into(),
+			uri: "http://localhost/price".into(),
+			response: Some(b"42".to_vec()),
+			sent: true,
+			..Default::default()
+		});
+
+		t.execute_with(|| {
+			assert_eq!(http_get("http://localhost/price", 1000, 2), Ok(b"42".to_vec()));
+		});
+	}
+}
@@ -1,3 +1,9 @@
+pub mod http;
+pub mod storage;
+
+pub use http::http_get;
+pub use storage::{iterate_map_with_cursor, with_lock};
+
 /// Error which may occur while executing the off-chain code.
 #[derive(PartialEq)]
 pub enum OffchainErr {
@@ -5,6 +11,7 @@ pub enum OffchainErr {
 	SubmitTransaction,
 	NotValidator,
 	OffchainLock,
+	HttpFetching,
 }
 
 impl sp_std::fmt::Debug for OffchainErr {
@@ -14,6 +21,7 @@ impl sp_std::fmt::Debug for OffchainErr {
 			OffchainErr::SubmitTransaction => write!(fmt, "Failed to submit transaction"),
 			OffchainErr::NotValidator => write!(fmt, "Is not validator"),
 			OffchainErr::OffchainLock => write!(fmt, "Failed to manipulate offchain lock"),
+			OffchainErr::HttpFetching => write!(fmt, "Failed to fetch via HTTP"),
 		}
 	}
 }
@@ -0,0 +1,136 @@
+//! Helpers for offchain worker storage: iteration cursors and locks.
+
+use super::OffchainErr;
+use frame_support::storage::{IterableStorageMap, PrefixIterator};
+use sp_runtime::offchain::{
+	storage::StorageValueRef,
+	storage_lock::{StorageLock, Time},
+	Duration,
+};
+use sp_std::prelude::*;
+
+/// Iterate over at most `max_iterations` entries of the storage map `M`,
+/// resuming from where the last run stopped.
+///
+/// The position is persisted in offchain storage under `cursor_key`, so a
+/// large map can be processed over several offchain worker runs. Returns
+/// true if the iteration reached the end of the map, in which case the
+/// cursor is reset and the next run starts over.
+pub fn iterate_map_with_cursor<M, K, V>(
+	cursor_key: &[u8],
+	max_iterations: u32,
+	mut f: impl FnMut(K, V),
+) -> Result<bool, OffchainErr>
+where
+	M: IterableStorageMap<K, V, Iterator = PrefixIterator<(K, V)>>,
+	K: codec::FullEncode,
+	V: codec::FullCodec,
+{
+	let mut cursor = StorageValueRef::persistent(cursor_key);
+	let start = cursor.get::<Vec<u8>>().map_err(|_| OffchainErr::OffchainStore)?;
+	let mut iter = match start {
+		Some(raw_key) => M::iter_from(raw_key),
+		None => M::iter(),
+	};
+
+	for _ in 0..max_iterations {
+		match iter.next() {
+			Some((key, value)) => f(key, value),
+			None => {
+				cursor.clear();
+				return Ok(true);
+			}
+		}
+	}
+
+	cursor.set(&iter.last_raw_key().to_vec());
+	Ok(false)
+}
+
+/// Execute `f` holding the offchain storage lock `lock_key`.
+///
+/// The lock expires after `deadline` milliseconds, so a worker that crashed
+/// while holding it doesn't block later runs forever. Returns
+/// `OffchainErr::OffchainLock` if the lock is held by someone else.
+pub fn with_lock<R>(lock_key: &[u8], deadline: u64, f: impl FnOnce() -> R) -> Result<R, OffchainErr> {
+	let mut lock = StorageLock::<Time>::with_deadline(lock_key, Duration::from_millis(deadline));
+	let _guard = lock.try_lock().map_err(|_| OffchainErr::OffchainLock)?;
+	Ok(f())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::{decl_module, decl_storage};
+	use sp_core::offchain::{testing::TestOffchainExt, OffchainDbExt, OffchainWorkerExt};
+	use sp_io::TestExternalities;
+
+	pub trait Config: frame_system::Config {}
+
+	decl_module! {
+		pub struct Module<T: Config> for enum Call where origin: T::Origin {}
+	}
+
+	decl_storage! {
+		trait Store for Module<T: Config> as OffchainStorage {
+			pub Map: map hasher(twox_64_concat) u32 => u32;
+		}
+	}
+
+	fn new_test_ext() -> TestExternalities {
+		let (offchain, _state) = TestOffchainExt::new();
+		let mut t = TestExternalities::default();
+		t.register_extension(OffchainDbExt::new(offchain.clone()));
+		t.register_extension(OffchainWorkerExt::new(offchain));
+		t
+	}
+
+	#[test]
+	fn iterate_map_with_cursor_works() {
+		new_test_ext().execute_with(|| {
+			for i in 0..5 {
+				Map::insert(i, i * 10);
+			}
+
+			let mut visited = vec![];
+			assert_eq!(
+				iterate_map_with_cursor::<Map, _, _>(b"cursor", 2, |k, v| visited.push((k, v))),
+				Ok(false)
+			);
+			assert_eq!(visited.len(), 2);
+			assert_eq!(
+				iterate_map_with_cursor::<Map, _, _>(b"cursor", 2, |k, v| visited.push((k, v))),
+				Ok(false)
+			);
+			assert_eq!(visited.len(), 4);
+			assert_eq!(
+				iterate_map_with_cursor::<Map, _, _>(b"cursor", 2, |k, v| visited.push((k, v))),
+				Ok(true)
+			);
+			visited.sort();
+			assert_eq!(visited, vec![(0, 0), (1, 10), (2, 20), (3, 30), (4, 40)]);
+
+			// starts over
+			let mut count = 0;
+			assert_eq!(
+				iterate_map_with_cursor::<Map, _, _>(b"cursor", 10, |_, _| count += 1),
+				Ok(true)
+			);
+			assert_eq!(count, 5);
+		});
+	}
+
+	#[test]
+	fn with_lock_works() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(with_lock(b"lock", 1000, || 42), Ok(42));
+			// released after the first run
+			assert_eq!(with_lock(b"lock", 1000, || 42), Ok(42));
+
+			assert_eq!(
+				with_lock(b"lock", 1000, || with_lock(b"lock", 1000, || 42)),
+				Ok(Err(OffchainErr::OffchainLock))
+			);
+		});
+	}
+}
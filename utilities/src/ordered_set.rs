@@ -1,9 +1,9 @@
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{traits::Get, BoundedVec, DefaultNoBound};
 use scale_info::TypeInfo;
-use sp_std::convert::TryInto;
 #[cfg(feature = "std")]
-use sp_std::{fmt, prelude::*};
+use sp_std::fmt;
+use sp_std::{cmp::Ordering, convert::TryInto, prelude::*};
 
 /// An ordered set backed by `BoundedVec`
 #[derive(PartialEq, Eq, Encode, Decode, DefaultNoBound, Clone, TypeInfo)]
@@ -62,6 +62,80 @@ impl<T: Ord, S: Get<u32>> OrderedSet<T, S> {
 	pub fn clear(&mut self) {
 		self.0 = BoundedVec::default();
 	}
+
+	/// Return the elements in range `[start, end)`.
+	pub fn range(&self, start: &T, end: &T) -> &[T] {
+		let lower = self.lower_bound(start);
+		let upper = self.lower_bound(end).max(lower);
+		&self.0[lower..upper]
+	}
+
+	/// Return the elements in range `[start, end]`.
+	pub fn range_inclusive(&self, start: &T, end: &T) -> &[T] {
+		let lower = self.lower_bound(start);
+		let upper = match self.0.binary_search(end) {
+			Ok(loc) => loc + 1,
+			Err(loc) => loc,
+		}
+		.max(lower);
+		&self.0[lower..upper]
+	}
+
+	/// Index of the first element not less than `value`.
+	fn lower_bound(&self, value: &T) -> usize {
+		match self.0.binary_search(value) {
+			Ok(loc) | Err(loc) => loc,
+		}
+	}
+}
+
+impl<T: Ord + Clone, S: Get<u32>> OrderedSet<T, S> {
+	/// Return the union of `self` and `other`.
+	/// Return `None` if the union exceeds the bound.
+	pub fn union(&self, other: &Self) -> Option<Self> {
+		let (mut a, mut b) = (self.0.iter().peekable(), other.0.iter().peekable());
+		let mut v = Vec::with_capacity(self.0.len().max(other.0.len()));
+		loop {
+			let next = match (a.peek(), b.peek()) {
+				(Some(x), Some(y)) => match x.cmp(y) {
+					Ordering::Less => a.next(),
+					Ordering::Greater => b.next(),
+					Ordering::Equal => {
+						b.next();
+						a.next()
+					}
+				},
+				(Some(_), None) => a.next(),
+				(None, Some(_)) => b.next(),
+				(None, None) => break,
+			};
+			v.extend(next.cloned());
+		}
+
+		v.try_into().ok().map(Self::from_sorted_set)
+	}
+
+	/// Return the elements in both `self` and `other`.
+	pub fn intersection(&self, other: &Self) -> Self {
+		self.filter(|value| other.contains(value))
+	}
+
+	/// Return the elements in `self` but not in `other`.
+	pub fn difference(&self, other: &Self) -> Self {
+		self.filter(|value| !other.contains(value))
+	}
+
+	fn filter(&self, f: impl Fn(&T) -> bool) -> Self {
+		let v: Vec<T> = self.0.iter().filter(|value| f(value)).cloned().collect();
+		// a subset of `self` never exceeds the bound
+		Self::from_sorted_set(v.try_into().expect("subset of a bounded set is bounded; qed"))
+	}
+}
+
+impl<T: MaxEncodedLen, S: Get<u32>> MaxEncodedLen for OrderedSet<T, S> {
+	fn max_encoded_len() -> usize {
+		BoundedVec::<T, S>::max_encoded_len()
+	}
 }
 
 impl<T: Ord, S: Get<u32>> From<BoundedVec<T, S>> for OrderedSet<T, S> {
@@ -169,6 +243,47 @@ mod tests {
 		assert_eq!(set, OrderedSet::new());
 	}
 
+	#[test]
+	fn range() {
+		let set: OrderedSet<i32, Eight> = OrderedSet::from(vec![1, 3, 5, 7].try_into().unwrap());
+
+		assert_eq!(set.range(&3, &7), &[3, 5]);
+		assert_eq!(set.range(&2, &8), &[3, 5, 7]);
+		assert_eq!(set.range(&4, &5), &[] as &[i32]);
+		assert_eq!(set.range(&7, &3), &[] as &[i32]);
+
+		assert_eq!(set.range_inclusive(&3, &7), &[3, 5, 7]);
+		assert_eq!(set.range_inclusive(&0, &1), &[1]);
+		assert_eq!(set.range_inclusive(&8, &9), &[] as &[i32]);
+	}
+
+	#[test]
+	fn set_operations() {
+		let a: OrderedSet<i32, Five> = OrderedSet::from(vec![1, 2, 3].try_into().unwrap());
+		let b: OrderedSet<i32, Five> = OrderedSet::from(vec![2, 3, 4].try_into().unwrap());
+		let c: OrderedSet<i32, Five> = OrderedSet::from(vec![5, 6, 7].try_into().unwrap());
+
+		assert_eq!(
+			a.union(&b),
+			Some(OrderedSet::from(vec![1, 2, 3, 4].try_into().unwrap()))
+		);
+		assert_eq!(a.union(&c), None);
+
+		assert_eq!(a.intersection(&b), OrderedSet::from(vec![2, 3].try_into().unwrap()));
+		assert_eq!(a.intersection(&c), OrderedSet::new());
+
+		assert_eq!(a.difference(&b), OrderedSet::from(vec![1].try_into().unwrap()));
+		assert_eq!(a.difference(&c), a);
+	}
+
+	#[test]
+	fn max_encoded_len() {
+		assert_eq!(
+			OrderedSet::<u32, Eight>::max_encoded_len(),
+			codec::Compact(8u32).encoded_size() + 8 * 4
+		);
+	}
+
 	#[test]
 	fn exceeding_max_size_should_fail() {
 		let mut set: OrderedSet<i32, Five> = OrderedSet::from(vec![1, 2, 3, 4, 5].try_into().unwrap());
@@ -0,0 +1,19 @@
+//! Runtime API definition for vesting module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	pub trait VestingApi<Balance> where
+		Balance: Codec,
+	{
+		/// The total scheduled amount of the active vesting schedules, vested
+		/// or not, and their number.
+		fn totals() -> (Balance, u32);
+	}
+}
@@ -0,0 +1,113 @@
+//! Benchmarks for the vesting module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+const SEED: u32 = 0;
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 4u32.into());
+	who
+}
+
+fn schedule<T: Config>() -> VestingScheduleOf<T> {
+	VestingSchedule {
+		start: 0u32.into(),
+		period: 10u32.into(),
+		period_count: 2,
+		per_period: T::MinVestedTransfer::get().max(1u32.into()),
+	}
+}
+
+/// Add `count` vesting schedules to `who`.
+fn add_schedules<T: Config>(who: &T::AccountId, count: u32) -> Result<(), &'static str> {
+	let funder = funded_account::<T>("funder", 0);
+	for _ in 0..count {
+		Pallet::<T>::do_vested_transfer(&funder, who, schedule::<T>())?;
+	}
+	Ok(())
+}
+
+benchmarks! {
+	// the recipient has the most schedules
+	vested_transfer {
+		let origin = T::VestedTransferOrigin::successful_origin();
+		let from = T::VestedTransferOrigin::ensure_origin(origin.clone()).map_err(|_| "bad origin")?;
+		T::Currency::make_free_balance_be(&from, BalanceOf::<T>::max_value() / 4u32.into());
+		let to = funded_account::<T>("to", 0);
+		add_schedules::<T>(&to, T::MaxVestingSchedules::get() - 1)?;
+	}: _<T::Origin>(origin, T::Lookup::unlookup(to.clone()), schedule::<T>())
+	verify {
+		assert_eq!(Pallet::<T>::vesting_schedules(&to).len() as u32, T::MaxVestingSchedules::get());
+	}
+
+	vested_transfer_batch {
+		let i in 1 .. T::MaxBatchSize::get();
+
+		let origin = T::VestedTransferOrigin::successful_origin();
+		let from = T::VestedTransferOrigin::ensure_origin(origin.clone()).map_err(|_| "bad origin")?;
+		T::Currency::make_free_balance_be(&from, BalanceOf::<T>::max_value() / 4u32.into());
+		let transfers = (0..i)
+			.map(|index| (account("to", index, SEED), schedule::<T>()))
+			.collect::<Vec<(T::AccountId, _)>>();
+	}: _<T::Origin>(origin, transfers.clone())
+	verify {
+		for (to, schedule) in transfers {
+			assert_eq!(Pallet::<T>::vesting_schedules(&to).first(), Some(&schedule));
+		}
+	}
+
+	claim {
+		let i in 1 .. T::MaxVestingSchedules::get();
+
+		let who = funded_account::<T>("who", 0);
+		add_schedules::<T>(&who, i)?;
+	}: _(RawOrigin::Signed(who.clone()))
+	verify {
+		assert_eq!(Pallet::<T>::vesting_schedules(&who).len() as u32, i);
+	}
+
+	update_vesting_schedules {
+		let i in 1 .. T::MaxVestingSchedules::get();
+
+		let who = funded_account::<T>("who", 0);
+		let schedules = (0..i).map(|_| schedule::<T>()).collect::<Vec<_>>();
+	}: _(RawOrigin::Root, T::Lookup::unlookup(who.clone()), schedules)
+	verify {
+		assert_eq!(Pallet::<T>::vesting_schedules(&who).len() as u32, i);
+	}
+
+	// the last schedule is extended, its locked balance computed over all of
+	// them
+	extend_schedule {
+		let i in 1 .. T::MaxVestingSchedules::get();
+
+		let who = funded_account::<T>("who", 0);
+		add_schedules::<T>(&who, i)?;
+		let per_period = schedule::<T>().per_period;
+	}: _(RawOrigin::Root, T::Lookup::unlookup(who.clone()), i - 1, 4, per_period)
+	verify {
+		assert_eq!(Pallet::<T>::vesting_schedules(&who)[(i - 1) as usize].period_count, 4);
+	}
+
+	transfer_schedule {
+		let i in 1 .. T::MaxVestingSchedules::get();
+
+		let from = funded_account::<T>("from", 0);
+		let to = funded_account::<T>("to", 0);
+		add_schedules::<T>(&from, 1)?;
+		add_schedules::<T>(&to, i - 1)?;
+		let origin = T::ScheduleTransferOrigin::successful_origin();
+	}: _<T::Origin>(origin, T::Lookup::unlookup(from.clone()), 0, T::Lookup::unlookup(to.clone()))
+	verify {
+		assert!(Pallet::<T>::vesting_schedules(&from).is_empty());
+		assert_eq!(Pallet::<T>::vesting_schedules(&to).len() as u32, i);
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::build(), crate::mock::Runtime);
@@ -20,9 +20,27 @@
 //! ### Dispatchable Functions
 //!
 //! - `vested_transfer` - Add a new vesting schedule for an account.
+//! - `vested_transfer_batch` - Add new vesting schedules for many accounts
+//!   at once, such as an airdrop.
 //! - `claim` - Claim unlocked balances.
 //! - `update_vesting_schedules` - Update all vesting schedules under an
 //!   account, `root` origin required.
+//! - `extend_schedule` - Change the period count and amount per period of a
+//!   vesting schedule without reducing the already vested amount, `root`
+//!   origin required.
+//! - `transfer_schedule` - Move a vesting schedule and its locked balance to
+//!   a new beneficiary, by `ScheduleTransferOrigin`, or once approved by both
+//!   the current and the new beneficiary.
+//!
+//! ### Totals
+//!
+//! The total scheduled amount of the active vesting schedules and their
+//! number are kept up to date in `TotalScheduled` and `ScheduleCount`, also
+//! available through the `VestingApi` runtime API, so they don't need to be
+//! summed over all accounts off-chain. A schedule is counted with its full
+//! amount until it completes and is removed on claim, so the total includes
+//! the amounts already vested, and is not the balance still locked. Existing chains should run
+//! `migration::InitializeTotals` to count the schedules created before.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -31,7 +49,10 @@ use codec::{HasCompact, MaxEncodedLen};
 use frame_support::{
 	ensure,
 	pallet_prelude::*,
-	traits::{Currency, EnsureOrigin, ExistenceRequirement, Get, LockIdentifier, LockableCurrency, WithdrawReasons},
+	traits::{
+		Currency, EnsureOrigin, ExistenceRequirement, Get, LockIdentifier, LockableCurrency, StorageVersion,
+		WithdrawReasons,
+	},
 	transactional, BoundedVec,
 };
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
@@ -46,6 +67,8 @@ use sp_std::{
 	vec::Vec,
 };
 
+mod benchmarking;
+pub mod migration;
 mod mock;
 mod tests;
 mod weights;
@@ -86,6 +109,12 @@ impl<BlockNumber: AtLeast32Bit + Copy, Balance: AtLeast32Bit + Copy> VestingSche
 		self.per_period.checked_mul(&self.period_count.into())
 	}
 
+	/// Returns vested amount for a given `time`, `None` if calculation
+	/// overflows.
+	pub fn vested_amount(&self, time: BlockNumber) -> Option<Balance> {
+		Some(self.total_amount()?.saturating_sub(self.locked_amount(time)))
+	}
+
 	/// Returns locked amount for a given `time`.
 	///
 	/// Note this func assumes schedule is a valid one(non-zero period and
@@ -105,6 +134,20 @@ impl<BlockNumber: AtLeast32Bit + Copy, Balance: AtLeast32Bit + Copy> VestingSche
 	}
 }
 
+/// The approvals of moving a vesting schedule to a new beneficiary.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ScheduleTransferApproval<AccountId, BlockNumber, Balance: HasCompact> {
+	/// The new beneficiary
+	pub new_beneficiary: AccountId,
+	/// The schedule approved to move, approvals are discarded if the schedule
+	/// at the index changed
+	pub schedule: VestingSchedule<BlockNumber, Balance>,
+	/// Approved by the current beneficiary
+	pub approved_by_beneficiary: bool,
+	/// Approved by the new beneficiary
+	pub approved_by_new_beneficiary: bool,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -112,6 +155,11 @@ pub mod module {
 	pub(crate) type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 	pub(crate) type VestingScheduleOf<T> = VestingSchedule<<T as frame_system::Config>::BlockNumber, BalanceOf<T>>;
+	pub(crate) type ScheduleTransferApprovalOf<T> = ScheduleTransferApproval<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+		BalanceOf<T>,
+	>;
 	pub type ScheduledItem<T> = (
 		<T as frame_system::Config>::AccountId,
 		<T as frame_system::Config>::BlockNumber,
@@ -133,6 +181,10 @@ pub mod module {
 		/// Required origin for vested transfer.
 		type VestedTransferOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
 
+		/// The origin which may move any vesting schedule to a new beneficiary
+		/// without approvals, e.g. governance.
+		type ScheduleTransferOrigin: EnsureOrigin<Self::Origin>;
+
 		/// Weight information for extrinsics in this module.
 		type WeightInfo: WeightInfo;
 
@@ -141,6 +193,10 @@ pub mod module {
 
 		// The block number provider
 		type BlockNumberProvider: BlockNumberProvider<BlockNumber = Self::BlockNumber>;
+
+		/// The maximum number of vested transfers in a batch.
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
 	}
 
 	#[pallet::error]
@@ -157,6 +213,16 @@ pub mod module {
 		AmountLow,
 		/// Failed because the maximum vesting schedules was exceeded
 		MaxVestingSchedulesExceeded,
+		/// Failed because the maximum batch size was exceeded
+		MaxBatchSizeExceeded,
+		/// The vesting schedule doesn't exist
+		VestingScheduleNotFound,
+		/// The new schedule would reduce the vested amount
+		VestedAmountReduced,
+		/// The new beneficiary is the current beneficiary
+		InvalidBeneficiary,
+		/// The caller is neither the current nor the new beneficiary
+		NotBeneficiary,
 	}
 
 	#[pallet::event]
@@ -168,6 +234,14 @@ pub mod module {
 		Claimed(T::AccountId, BalanceOf<T>),
 		/// Updated vesting schedules. \[who\]
 		VestingSchedulesUpdated(T::AccountId),
+		/// Extended a vesting schedule. \[who, index, vesting_schedule\]
+		VestingScheduleExtended(T::AccountId, u32, VestingScheduleOf<T>),
+		/// Approved moving a vesting schedule to a new beneficiary. \[who,
+		/// from, index, new_beneficiary\]
+		ScheduleTransferApproved(T::AccountId, T::AccountId, u32, T::AccountId),
+		/// Moved a vesting schedule to a new beneficiary. \[from, to,
+		/// vesting_schedule\]
+		VestingScheduleTransferred(T::AccountId, T::AccountId, VestingScheduleOf<T>),
 	}
 
 	/// Vesting schedules of an account.
@@ -183,6 +257,37 @@ pub mod module {
 		ValueQuery,
 	>;
 
+	/// The total scheduled amount of the active vesting schedules, including
+	/// the amounts already vested until the schedules complete.
+	///
+	/// TotalScheduled: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_scheduled)]
+	pub type TotalScheduled<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// The number of active vesting schedules.
+	///
+	/// ScheduleCount: u32
+	#[pallet::storage]
+	#[pallet::getter(fn schedule_count)]
+	pub type ScheduleCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Pending approvals of moving vesting schedules to new beneficiaries.
+	///
+	/// ScheduleTransferApprovals: double_map AccountId, u32 =>
+	/// Option<ScheduleTransferApproval>
+	#[pallet::storage]
+	#[pallet::getter(fn schedule_transfer_approvals)]
+	pub type ScheduleTransferApprovals<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		u32,
+		ScheduleTransferApprovalOf<T>,
+		OptionQuery,
+	>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub vesting: Vec<ScheduledItem<T>>,
@@ -202,13 +307,14 @@ pub mod module {
 				.iter()
 				.for_each(|(who, start, period, period_count, per_period)| {
 					let mut bounded_schedules = VestingSchedules::<T>::get(who);
+					let schedule = VestingSchedule {
+						start: *start,
+						period: *period,
+						period_count: *period_count,
+						per_period: *per_period,
+					};
 					bounded_schedules
-						.try_push(VestingSchedule {
-							start: *start,
-							period: *period,
-							period_count: *period_count,
-							per_period: *per_period,
-						})
+						.try_push(schedule.clone())
 						.expect("Max vesting schedules exceeded");
 					let total_amount = bounded_schedules
 						.iter()
@@ -225,11 +331,17 @@ pub mod module {
 
 					T::Currency::set_lock(VESTING_LOCK_ID, who, total_amount, WithdrawReasons::all());
 					VestingSchedules::<T>::insert(who, bounded_schedules);
+					Pallet::<T>::schedules_added(&[schedule]);
 				});
+			STORAGE_VERSION.put::<Pallet<T>>();
 		}
 	}
 
+	/// The current storage version, 1 since the totals are counted.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
@@ -260,6 +372,25 @@ pub mod module {
 			Ok(())
 		}
 
+		#[pallet::weight(T::WeightInfo::vested_transfer_batch(transfers.len() as u32))]
+		#[transactional]
+		pub fn vested_transfer_batch(
+			origin: OriginFor<T>,
+			transfers: Vec<(T::AccountId, VestingScheduleOf<T>)>,
+		) -> DispatchResult {
+			let from = T::VestedTransferOrigin::ensure_origin(origin)?;
+			ensure!(
+				transfers.len() <= T::MaxBatchSize::get() as usize,
+				Error::<T>::MaxBatchSizeExceeded
+			);
+
+			for (to, schedule) in transfers {
+				Self::do_vested_transfer(&from, &to, schedule.clone())?;
+				Self::deposit_event(Event::VestingScheduleAdded(from.clone(), to, schedule));
+			}
+			Ok(())
+		}
+
 		#[pallet::weight(T::WeightInfo::update_vesting_schedules(vesting_schedules.len() as u32))]
 		pub fn update_vesting_schedules(
 			origin: OriginFor<T>,
@@ -275,6 +406,55 @@ pub mod module {
 			Ok(())
 		}
 
+		/// Change the period count and the amount per period of the vesting
+		/// schedule at `index`, keeping its start and period. The amount
+		/// already vested at the current block must not be reduced.
+		#[pallet::weight(T::WeightInfo::extend_schedule(<T as Config>::MaxVestingSchedules::get()))]
+		pub fn extend_schedule(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			index: u32,
+			new_period_count: u32,
+			#[pallet::compact] new_per_period: BalanceOf<T>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let account = T::Lookup::lookup(who)?;
+			let schedule = Self::do_extend_schedule(&account, index, new_period_count, new_per_period)?;
+
+			Self::deposit_event(Event::VestingScheduleExtended(account, index, schedule));
+			Ok(())
+		}
+
+		/// Move the vesting schedule of `from` at `index`, and its locked
+		/// balance, to `new_beneficiary`.
+		///
+		/// The dispatch origin for this call must be `ScheduleTransferOrigin`,
+		/// or `Signed` by `from` or `new_beneficiary`, in which case the
+		/// schedule is moved once both approved.
+		#[pallet::weight(T::WeightInfo::transfer_schedule(<T as Config>::MaxVestingSchedules::get()))]
+		pub fn transfer_schedule(
+			origin: OriginFor<T>,
+			from: <T::Lookup as StaticLookup>::Source,
+			index: u32,
+			new_beneficiary: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let from = T::Lookup::lookup(from)?;
+			let to = T::Lookup::lookup(new_beneficiary)?;
+			ensure!(from != to, Error::<T>::InvalidBeneficiary);
+
+			if let Err(origin) = T::ScheduleTransferOrigin::try_origin(origin) {
+				let who = ensure_signed(origin)?;
+				if !Self::approve_schedule_transfer(&who, &from, index, &to)? {
+					return Ok(());
+				}
+			}
+
+			let schedule = Self::do_transfer_schedule(&from, index, &to)?;
+			Self::deposit_event(Event::VestingScheduleTransferred(from, to, schedule));
+			Ok(())
+		}
+
 		#[pallet::weight(T::WeightInfo::claim((<T as Config>::MaxVestingSchedules::get() / 2) as u32))]
 		pub fn claim_for(origin: OriginFor<T>, dest: <T::Lookup as StaticLookup>::Source) -> DispatchResult {
 			let _ = ensure_signed(origin)?;
@@ -300,15 +480,25 @@ impl<T: Config> Pallet<T> {
 		locked
 	}
 
+	/// The total scheduled amount of the active vesting schedules and their
+	/// number.
+	pub fn totals() -> (BalanceOf<T>, u32) {
+		(Self::total_scheduled(), Self::schedule_count())
+	}
+
 	/// Returns locked balance based on current block number.
 	fn locked_balance(who: &T::AccountId) -> BalanceOf<T> {
 		let now = T::BlockNumberProvider::current_block_number();
-		<VestingSchedules<T>>::mutate_exists(who, |maybe_schedules| {
+		let mut completed = Vec::new();
+		let locked = <VestingSchedules<T>>::mutate_exists(who, |maybe_schedules| {
 			let total = if let Some(schedules) = maybe_schedules.as_mut() {
 				let mut total: BalanceOf<T> = Zero::zero();
 				schedules.retain(|s| {
 					let amount = s.locked_amount(now);
 					total = total.saturating_add(amount);
+					if amount.is_zero() {
+						completed.push(s.clone());
+					}
 					!amount.is_zero()
 				});
 				total
@@ -319,7 +509,27 @@ impl<T: Config> Pallet<T> {
 				*maybe_schedules = None;
 			}
 			total
-		})
+		});
+		Self::schedules_removed(&completed);
+		locked
+	}
+
+	/// Add `schedules` to the totals.
+	fn schedules_added(schedules: &[VestingScheduleOf<T>]) {
+		if schedules.is_empty() {
+			return;
+		}
+		TotalScheduled::<T>::mutate(|total| *total = total.saturating_add(total_amount_of::<T>(schedules)));
+		ScheduleCount::<T>::mutate(|count| *count = count.saturating_add(schedules.len() as u32));
+	}
+
+	/// Remove `schedules` from the totals.
+	fn schedules_removed(schedules: &[VestingScheduleOf<T>]) {
+		if schedules.is_empty() {
+			return;
+		}
+		TotalScheduled::<T>::mutate(|total| *total = total.saturating_sub(total_amount_of::<T>(schedules)));
+		ScheduleCount::<T>::mutate(|count| *count = count.saturating_sub(schedules.len() as u32));
 	}
 
 	#[transactional]
@@ -332,6 +542,7 @@ impl<T: Config> Pallet<T> {
 
 		T::Currency::transfer(from, to, schedule_amount, ExistenceRequirement::AllowDeath)?;
 		T::Currency::set_lock(VESTING_LOCK_ID, to, total_amount, WithdrawReasons::all());
+		Self::schedules_added(sp_std::slice::from_ref(&schedule));
 		<VestingSchedules<T>>::try_append(to, schedule).map_err(|_| Error::<T>::MaxVestingSchedulesExceeded)?;
 		Ok(())
 	}
@@ -343,6 +554,7 @@ impl<T: Config> Pallet<T> {
 
 		// empty vesting schedules cleanup the storage and unlock the fund
 		if bounded_schedules.len().is_zero() {
+			Self::schedules_removed(&Self::vesting_schedules(who));
 			<VestingSchedules<T>>::remove(who);
 			T::Currency::remove_lock(VESTING_LOCK_ID, who);
 			return Ok(());
@@ -360,10 +572,128 @@ impl<T: Config> Pallet<T> {
 		);
 
 		T::Currency::set_lock(VESTING_LOCK_ID, who, total_amount, WithdrawReasons::all());
+		Self::schedules_removed(&Self::vesting_schedules(who));
+		Self::schedules_added(&bounded_schedules);
 		<VestingSchedules<T>>::insert(who, bounded_schedules);
 
 		Ok(())
 	}
+
+	fn do_extend_schedule(
+		who: &T::AccountId,
+		index: u32,
+		new_period_count: u32,
+		new_per_period: BalanceOf<T>,
+	) -> Result<VestingScheduleOf<T>, DispatchError> {
+		let now = T::BlockNumberProvider::current_block_number();
+		let mut schedules = Self::vesting_schedules(who);
+		let schedule = schedules
+			.get_mut(index as usize)
+			.ok_or(Error::<T>::VestingScheduleNotFound)?;
+
+		let new_schedule = VestingSchedule {
+			period_count: new_period_count,
+			per_period: new_per_period,
+			..schedule.clone()
+		};
+		ensure_valid_vesting_schedule::<T>(&new_schedule)?;
+		// valid schedules don't overflow
+		ensure!(
+			new_schedule.vested_amount(now) >= schedule.vested_amount(now),
+			Error::<T>::VestedAmountReduced
+		);
+		let old_schedule = sp_std::mem::replace(schedule, new_schedule.clone());
+
+		let locked = schedules.iter().fold(Zero::zero(), |acc: BalanceOf<T>, s| {
+			acc.saturating_add(s.locked_amount(now))
+		});
+		ensure!(
+			T::Currency::free_balance(who) >= locked,
+			Error::<T>::InsufficientBalanceToLock,
+		);
+
+		T::Currency::set_lock(VESTING_LOCK_ID, who, locked, WithdrawReasons::all());
+		<VestingSchedules<T>>::insert(who, schedules);
+		Self::schedules_removed(&[old_schedule]);
+		Self::schedules_added(sp_std::slice::from_ref(&new_schedule));
+		Ok(new_schedule)
+	}
+
+	/// Record the approval of `who` to move the schedule of `from` at `index`
+	/// to `to`. Returns `true` once both beneficiaries approved.
+	fn approve_schedule_transfer(
+		who: &T::AccountId,
+		from: &T::AccountId,
+		index: u32,
+		to: &T::AccountId,
+	) -> Result<bool, DispatchError> {
+		ensure!(who == from || who == to, Error::<T>::NotBeneficiary);
+		let schedule = Self::vesting_schedules(from)
+			.get(index as usize)
+			.cloned()
+			.ok_or(Error::<T>::VestingScheduleNotFound)?;
+
+		let mut approval = match Self::schedule_transfer_approvals(from, index) {
+			Some(approval) if approval.new_beneficiary == *to && approval.schedule == schedule => approval,
+			// approvals of another beneficiary or schedule are discarded
+			_ => ScheduleTransferApproval {
+				new_beneficiary: to.clone(),
+				schedule,
+				approved_by_beneficiary: false,
+				approved_by_new_beneficiary: false,
+			},
+		};
+		if who == from {
+			approval.approved_by_beneficiary = true;
+		} else {
+			approval.approved_by_new_beneficiary = true;
+		}
+
+		if approval.approved_by_beneficiary && approval.approved_by_new_beneficiary {
+			return Ok(true);
+		}
+		<ScheduleTransferApprovals<T>>::insert(from, index, approval);
+		Self::deposit_event(Event::ScheduleTransferApproved(
+			who.clone(),
+			from.clone(),
+			index,
+			to.clone(),
+		));
+		Ok(false)
+	}
+
+	#[transactional]
+	fn do_transfer_schedule(
+		from: &T::AccountId,
+		index: u32,
+		to: &T::AccountId,
+	) -> Result<VestingScheduleOf<T>, DispatchError> {
+		let now = T::BlockNumberProvider::current_block_number();
+		let mut schedules = Self::vesting_schedules(from);
+		ensure!((index as usize) < schedules.len(), Error::<T>::VestingScheduleNotFound);
+		let schedule = schedules.remove(index as usize);
+		let amount = schedule.locked_amount(now);
+		<ScheduleTransferApprovals<T>>::remove(from, index);
+
+		// unlock the balance of the schedule
+		<VestingSchedules<T>>::insert(from, schedules);
+		Self::do_claim(from);
+
+		let total_amount = Self::locked_balance(to)
+			.checked_add(&amount)
+			.ok_or(ArithmeticError::Overflow)?;
+		T::Currency::transfer(from, to, amount, ExistenceRequirement::AllowDeath)?;
+		T::Currency::set_lock(VESTING_LOCK_ID, to, total_amount, WithdrawReasons::all());
+		<VestingSchedules<T>>::try_append(to, schedule.clone()).map_err(|_| Error::<T>::MaxVestingSchedulesExceeded)?;
+		Ok(schedule)
+	}
+}
+
+/// The total amount of `schedules`, valid schedules don't overflow.
+fn total_amount_of<T: Config>(schedules: &[VestingScheduleOf<T>]) -> BalanceOf<T> {
+	schedules.iter().fold(Zero::zero(), |acc: BalanceOf<T>, s| {
+		acc.saturating_add(s.total_amount().unwrap_or_else(Zero::zero))
+	})
 }
 
 /// Returns `Ok(total_total)` if valid schedule, or error.
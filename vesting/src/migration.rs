@@ -0,0 +1,63 @@
+//! Migrations of orml-vesting.
+//!
+//! `InitializeTotals` counts the vesting schedules created before
+//! `TotalScheduled` and `ScheduleCount` were maintained.
+
+use crate::{
+	total_amount_of, BalanceOf, Config, Pallet, ScheduleCount, TotalScheduled, VestingSchedules, STORAGE_VERSION,
+};
+use frame_support::{
+	log,
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade},
+	weights::Weight,
+};
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::marker;
+
+/// Initialize `TotalScheduled` and `ScheduleCount` from the vesting schedules
+/// of all accounts.
+pub struct InitializeTotals<T>(marker::PhantomData<T>);
+
+impl<T: Config> InitializeTotals<T> {
+	/// Count the vesting schedules, returns the consumed weight.
+	pub fn migrate() -> Weight {
+		let mut accounts: Weight = 0;
+		let mut total: BalanceOf<T> = Zero::zero();
+		let mut count: u32 = 0;
+
+		for (_who, schedules) in VestingSchedules::<T>::iter() {
+			accounts += 1;
+			total = total.saturating_add(total_amount_of::<T>(&schedules));
+			count = count.saturating_add(schedules.len() as u32);
+		}
+		TotalScheduled::<T>::put(total);
+		ScheduleCount::<T>::put(count);
+		STORAGE_VERSION.put::<Pallet<T>>();
+
+		log::info!("vesting: counted {:?} schedules of {:?} accounts", count, accounts);
+		T::DbWeight::get().reads_writes(accounts.saturating_add(1), 3)
+	}
+}
+
+impl<T: Config> OnRuntimeUpgrade for InitializeTotals<T> {
+	fn on_runtime_upgrade() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() < STORAGE_VERSION {
+			Self::migrate()
+		} else {
+			T::DbWeight::get().reads(1)
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		if Pallet::<T>::on_chain_storage_version() != STORAGE_VERSION {
+			return Err("storage version not updated");
+		}
+		let count = VestingSchedules::<T>::iter_values()
+			.fold(0u32, |count, schedules| count.saturating_add(schedules.len() as u32));
+		if ScheduleCount::<T>::get() != count {
+			return Err("schedule count mismatch after migration");
+		}
+		Ok(())
+	}
+}
@@ -7,7 +7,7 @@ use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{EnsureOrigin, Everything},
 };
-use frame_system::RawOrigin;
+use frame_system::{EnsureRoot, RawOrigin};
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup};
 
@@ -76,13 +76,14 @@ impl EnsureOrigin<Origin> for EnsureAliceOrBob {
 
 	#[cfg(feature = "runtime-benchmarks")]
 	fn successful_origin() -> Origin {
-		Origin::from(RawOrigin::Signed(Default::default()))
+		Origin::from(RawOrigin::Signed(ALICE))
 	}
 }
 
 parameter_types! {
 	pub const MaxVestingSchedule: u32 = 2;
 	pub const MinVestedTransfer: u64 = 5;
+	pub const MaxBatchSize: u32 = 3;
 	pub static MockBlockNumberProvider: u64 = 0;
 }
 
@@ -99,9 +100,11 @@ impl Config for Runtime {
 	type Currency = PalletBalances;
 	type MinVestedTransfer = MinVestedTransfer;
 	type VestedTransferOrigin = EnsureAliceOrBob;
+	type ScheduleTransferOrigin = EnsureRoot<AccountId>;
 	type WeightInfo = ();
 	type MaxVestingSchedules = MaxVestingSchedule;
 	type BlockNumberProvider = MockBlockNumberProvider;
+	type MaxBatchSize = MaxBatchSize;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -122,6 +125,7 @@ construct_runtime!(
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
 pub const CHARLIE: AccountId = 3;
+pub const DAVE: AccountId = 4;
 
 #[derive(Default)]
 pub struct ExtBuilder;
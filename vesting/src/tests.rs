@@ -324,6 +324,57 @@ fn update_vesting_schedules_works() {
 	});
 }
 
+#[test]
+fn extend_schedule_works() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 4u32,
+			per_period: 10u64,
+		};
+		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule));
+
+		// 20 vested after two periods
+		MockBlockNumberProvider::set(21);
+		assert_noop!(Vesting::extend_schedule(Origin::signed(BOB), BOB, 0, 6, 10), BadOrigin);
+		assert_noop!(
+			Vesting::extend_schedule(Origin::root(), BOB, 1, 6, 10),
+			Error::<Runtime>::VestingScheduleNotFound
+		);
+		assert_noop!(
+			Vesting::extend_schedule(Origin::root(), BOB, 0, 2, 5),
+			Error::<Runtime>::VestedAmountReduced
+		);
+		assert_noop!(
+			Vesting::extend_schedule(Origin::root(), BOB, 0, 8, 10),
+			Error::<Runtime>::InsufficientBalanceToLock
+		);
+
+		assert_ok!(Vesting::extend_schedule(Origin::root(), BOB, 0, 6, 10));
+		let extended_schedule = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 6u32,
+			per_period: 10u64,
+		};
+		System::assert_last_event(Event::Vesting(crate::Event::VestingScheduleExtended(
+			BOB,
+			0,
+			extended_schedule.clone(),
+		)));
+		assert_eq!(Vesting::vesting_schedules(&BOB), vec![extended_schedule]);
+		assert_eq!(
+			PalletBalances::locks(&BOB).get(0),
+			Some(&BalanceLock {
+				id: VESTING_LOCK_ID,
+				amount: 40u64,
+				reasons: Reasons::All,
+			})
+		);
+	});
+}
+
 #[test]
 fn update_vesting_schedules_fails_if_unexpected_existing_locks() {
 	ExtBuilder::build().execute_with(|| {
@@ -409,3 +460,224 @@ fn exceeding_maximum_schedules_should_fail() {
 		);
 	});
 }
+
+#[test]
+fn vested_transfer_batch_works() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+
+		let schedule = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+		let another_schedule = VestingSchedule {
+			start: 5u64,
+			period: 10u64,
+			period_count: 1u32,
+			per_period: 10u64,
+		};
+		assert_ok!(Vesting::vested_transfer_batch(
+			Origin::signed(ALICE),
+			vec![(BOB, schedule.clone()), (BOB, another_schedule.clone())]
+		));
+		System::assert_has_event(Event::Vesting(crate::Event::VestingScheduleAdded(
+			ALICE,
+			BOB,
+			schedule.clone(),
+		)));
+		System::assert_last_event(Event::Vesting(crate::Event::VestingScheduleAdded(
+			ALICE,
+			BOB,
+			another_schedule.clone(),
+		)));
+
+		assert_eq!(Vesting::vesting_schedules(&BOB), vec![schedule, another_schedule]);
+		assert_eq!(PalletBalances::free_balance(ALICE), 70);
+		assert_eq!(
+			PalletBalances::locks(&BOB).get(0),
+			Some(&BalanceLock {
+				id: VESTING_LOCK_ID,
+				amount: 30u64,
+				reasons: Reasons::All,
+			})
+		);
+	});
+}
+
+#[test]
+fn vested_transfer_batch_fails_atomically() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+		let zero_period = VestingSchedule {
+			start: 0u64,
+			period: 0u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+
+		assert_noop!(
+			Vesting::vested_transfer_batch(Origin::signed(CHARLIE), vec![(BOB, schedule.clone())]),
+			BadOrigin
+		);
+		assert_noop!(
+			Vesting::vested_transfer_batch(Origin::signed(ALICE), vec![(BOB, schedule.clone()), (BOB, zero_period)]),
+			Error::<Runtime>::ZeroVestingPeriod
+		);
+		assert_noop!(
+			Vesting::vested_transfer_batch(Origin::signed(ALICE), vec![(BOB, schedule); 4]),
+			Error::<Runtime>::MaxBatchSizeExceeded
+		);
+		assert!(Vesting::vesting_schedules(&BOB).is_empty());
+		assert_eq!(PalletBalances::free_balance(ALICE), 100);
+	});
+}
+
+#[test]
+fn transfer_schedule_works() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule = VestingSchedule {
+			start: 5u64,
+			period: 3u64,
+			period_count: 3u32,
+			per_period: 5u64,
+		};
+
+		assert_noop!(
+			Vesting::transfer_schedule(Origin::signed(ALICE), CHARLIE, 0, CHARLIE),
+			Error::<Runtime>::InvalidBeneficiary
+		);
+		assert_noop!(
+			Vesting::transfer_schedule(Origin::root(), CHARLIE, 2, BOB),
+			Error::<Runtime>::VestingScheduleNotFound
+		);
+
+		// `VestedTransferOrigin` can't move the schedules of others
+		assert_noop!(
+			Vesting::transfer_schedule(Origin::signed(ALICE), CHARLIE, 1, BOB),
+			Error::<Runtime>::NotBeneficiary
+		);
+
+		// `ScheduleTransferOrigin` moves the schedule right away
+		assert_ok!(Vesting::transfer_schedule(Origin::root(), CHARLIE, 1, BOB));
+		System::assert_last_event(Event::Vesting(crate::Event::VestingScheduleTransferred(
+			CHARLIE,
+			BOB,
+			schedule.clone(),
+		)));
+		assert_eq!(Vesting::vesting_schedules(&BOB), vec![schedule]);
+		assert_eq!(PalletBalances::free_balance(BOB), 15);
+		assert_eq!(PalletBalances::free_balance(CHARLIE), 35);
+		assert_eq!(
+			PalletBalances::locks(&BOB).get(0),
+			Some(&BalanceLock {
+				id: VESTING_LOCK_ID,
+				amount: 15u64,
+				reasons: Reasons::All,
+			})
+		);
+		assert_eq!(
+			PalletBalances::locks(&CHARLIE).get(0),
+			Some(&BalanceLock {
+				id: VESTING_LOCK_ID,
+				amount: 5u64,
+				reasons: Reasons::All,
+			})
+		);
+
+		// otherwise both beneficiaries need to approve
+		let schedule = VestingSchedule {
+			start: 2u64,
+			period: 3u64,
+			period_count: 1u32,
+			per_period: 5u64,
+		};
+		assert_ok!(Vesting::transfer_schedule(Origin::signed(CHARLIE), CHARLIE, 0, DAVE));
+		System::assert_last_event(Event::Vesting(crate::Event::ScheduleTransferApproved(
+			CHARLIE, CHARLIE, 0, DAVE,
+		)));
+		assert_eq!(Vesting::vesting_schedules(&CHARLIE), vec![schedule.clone()]);
+		assert!(Vesting::schedule_transfer_approvals(&CHARLIE, 0).is_some());
+
+		MockBlockNumberProvider::set(3);
+		assert_ok!(Vesting::transfer_schedule(Origin::signed(DAVE), CHARLIE, 0, DAVE));
+		System::assert_last_event(Event::Vesting(crate::Event::VestingScheduleTransferred(
+			CHARLIE,
+			DAVE,
+			schedule.clone(),
+		)));
+		assert!(Vesting::schedule_transfer_approvals(&CHARLIE, 0).is_none());
+		assert!(Vesting::vesting_schedules(&CHARLIE).is_empty());
+		assert!(PalletBalances::locks(&CHARLIE).is_empty());
+		assert_eq!(PalletBalances::free_balance(CHARLIE), 30);
+		assert_eq!(Vesting::vesting_schedules(&DAVE), vec![schedule]);
+		assert_eq!(PalletBalances::free_balance(DAVE), 5);
+		assert_eq!(
+			PalletBalances::locks(&DAVE).get(0),
+			Some(&BalanceLock {
+				id: VESTING_LOCK_ID,
+				amount: 5u64,
+				reasons: Reasons::All,
+			})
+		);
+	});
+}
+
+#[test]
+fn totals_works() {
+	ExtBuilder::build().execute_with(|| {
+		assert_eq!(Vesting::totals(), (20, 2));
+
+		let schedule = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 4u32,
+			per_period: 10u64,
+		};
+		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, schedule));
+		assert_eq!(Vesting::totals(), (60, 3));
+
+		// completed schedules are removed on claim
+		MockBlockNumberProvider::set(21);
+		assert_ok!(Vesting::claim(Origin::signed(CHARLIE)));
+		assert_eq!(Vesting::totals(), (40, 1));
+
+		assert_noop!(
+			Vesting::extend_schedule(Origin::root(), BOB, 0, 8, 10),
+			Error::<Runtime>::InsufficientBalanceToLock
+		);
+		assert_ok!(Vesting::extend_schedule(Origin::root(), BOB, 0, 6, 10));
+		assert_eq!(Vesting::totals(), (60, 1));
+
+		assert_ok!(Vesting::update_vesting_schedules(Origin::root(), BOB, vec![]));
+		assert_eq!(Vesting::totals(), (0, 0));
+		assert_eq!(Vesting::total_scheduled(), 0);
+		assert_eq!(Vesting::schedule_count(), 0);
+	});
+}
+
+#[test]
+fn initialize_totals_migration_works() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	ExtBuilder::build().execute_with(|| {
+		TotalScheduled::<Runtime>::kill();
+		ScheduleCount::<Runtime>::kill();
+		StorageVersion::new(0).put::<Vesting>();
+
+		migration::InitializeTotals::<Runtime>::on_runtime_upgrade();
+		assert_eq!(Vesting::totals(), (20, 2));
+		assert_eq!(Vesting::on_chain_storage_version(), STORAGE_VERSION);
+
+		// only runs once
+		ScheduleCount::<Runtime>::put(1);
+		migration::InitializeTotals::<Runtime>::on_runtime_upgrade();
+		assert_eq!(Vesting::totals(), (20, 1));
+	});
+}
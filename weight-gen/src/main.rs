@@ -18,13 +18,26 @@ struct Opts {
 pub struct BenchData {
 	pub name: String,
 	pub base_weight: u64,
+	#[serde(default)]
+	pub base_weight_std_error: u64,
+	#[serde(default)]
+	pub runs: u32,
 	pub base_reads: u32,
 	pub base_writes: u32,
+	/// Written if measured by the bencher.
+	#[serde(default)]
+	pub proof_size: Option<u32>,
+	#[serde(default)]
+	pub events: Option<u32>,
 }
 
 #[derive(Serialize, Default, Debug, Clone)]
 struct TemplateData {
 	pub header: String,
+	pub date: String,
+	pub hostname: String,
+	/// The maximum number of runs of the benchmarks.
+	pub repeat: u32,
 	pub benchmarks: Vec<BenchData>,
 }
 
@@ -143,7 +156,17 @@ fn main() {
 		}
 	};
 
-	let hbs_data = TemplateData { header, benchmarks };
+	let hbs_data = TemplateData {
+		header,
+		date: chrono::Utc::now().format("%Y-%m-%d").to_string(),
+		hostname: gethostname::gethostname().to_string_lossy().to_string(),
+		repeat: benchmarks
+			.iter()
+			.map(|benchmark| benchmark.runs)
+			.max()
+			.unwrap_or_default(),
+		benchmarks,
+	};
 
 	const DEFAULT_TEMPLATE: &str = include_str!("./template.hbs");
 
@@ -1,5 +1,9 @@
 use codec::FullCodec;
-use sp_runtime::traits::{Convert, MaybeSerializeDeserialize, SaturatedConversion};
+use frame_support::traits::Get;
+use sp_runtime::{
+	traits::{Convert, MaybeSerializeDeserialize, SaturatedConversion},
+	DispatchError,
+};
 use sp_std::{
 	cmp::{Eq, PartialEq},
 	fmt::Debug,
@@ -36,11 +40,68 @@ impl From<Error> for XcmError {
 	}
 }
 
+/// Handle the failure of depositing a known currency.
+pub trait OnDepositFail<CurrencyId, AccountId, Balance> {
+	fn on_deposit_currency_fail(
+		err: DispatchError,
+		currency_id: CurrencyId,
+		who: &AccountId,
+		amount: Balance,
+	) -> Result;
+}
+
+/// Fail the deposit, the assets are trapped.
+impl<CurrencyId, AccountId, Balance> OnDepositFail<CurrencyId, AccountId, Balance> for () {
+	fn on_deposit_currency_fail(
+		err: DispatchError,
+		_currency_id: CurrencyId,
+		_who: &AccountId,
+		_amount: Balance,
+	) -> Result {
+		Err(XcmError::FailedToTransactAsset(err.into()))
+	}
+}
+
+/// `OnDepositFail` implementation, depositing the currency to the
+/// `Alternative` account, e.g. the treasury, instead.
+pub struct DepositToAlternative<Alternative, MultiCurrency, CurrencyId, AccountId, Balance>(
+	PhantomData<(Alternative, MultiCurrency, CurrencyId, AccountId, Balance)>,
+);
+impl<
+		Alternative: Get<AccountId>,
+		MultiCurrency: orml_traits::MultiCurrency<AccountId, CurrencyId = CurrencyId, Balance = Balance>,
+		CurrencyId: FullCodec + Eq + PartialEq + Copy + MaybeSerializeDeserialize + Debug,
+		AccountId: sp_std::fmt::Debug + Clone,
+		Balance: Debug,
+	> OnDepositFail<CurrencyId, AccountId, Balance>
+	for DepositToAlternative<Alternative, MultiCurrency, CurrencyId, AccountId, Balance>
+{
+	fn on_deposit_currency_fail(
+		err: DispatchError,
+		currency_id: CurrencyId,
+		who: &AccountId,
+		amount: Balance,
+	) -> Result {
+		let alternative = Alternative::get();
+		frame_support::log::warn!(
+			target: "xcm::currency-adapter",
+			"deposit {:?} {:?} to {:?} failed: {:?}, deposit to alternative {:?}",
+			amount,
+			currency_id,
+			who,
+			err,
+			alternative,
+		);
+		MultiCurrency::deposit(currency_id, &alternative, amount).map_err(|e| XcmError::FailedToTransactAsset(e.into()))
+	}
+}
+
 /// The `TransactAsset` implementation, to handle `MultiAsset` deposit/withdraw.
 /// Note that teleport related functions are unimplemented.
 ///
 /// If the asset is known, deposit/withdraw will be handled by `MultiCurrency`,
-/// else by `UnknownAsset` if unknown.
+/// else by `UnknownAsset` if unknown. Failed deposits of known assets are
+/// handled by `DepositFailureHandler`.
 pub struct MultiCurrencyAdapter<
 	MultiCurrency,
 	UnknownAsset,
@@ -49,6 +110,7 @@ pub struct MultiCurrencyAdapter<
 	AccountIdConvert,
 	CurrencyId,
 	CurrencyIdConvert,
+	DepositFailureHandler,
 >(
 	PhantomData<(
 		MultiCurrency,
@@ -58,6 +120,7 @@ pub struct MultiCurrencyAdapter<
 		AccountIdConvert,
 		CurrencyId,
 		CurrencyIdConvert,
+		DepositFailureHandler,
 	)>,
 );
 
@@ -69,8 +132,18 @@ impl<
 		AccountIdConvert: MoreConvert<MultiLocation, AccountId>,
 		CurrencyId: FullCodec + Eq + PartialEq + Copy + MaybeSerializeDeserialize + Debug,
 		CurrencyIdConvert: Convert<MultiAsset, Option<CurrencyId>>,
+		DepositFailureHandler: OnDepositFail<CurrencyId, AccountId, MultiCurrency::Balance>,
 	> TransactAsset
-	for MultiCurrencyAdapter<MultiCurrency, UnknownAsset, Match, AccountId, AccountIdConvert, CurrencyId, CurrencyIdConvert>
+	for MultiCurrencyAdapter<
+		MultiCurrency,
+		UnknownAsset,
+		Match,
+		AccountId,
+		AccountIdConvert,
+		CurrencyId,
+		CurrencyIdConvert,
+		DepositFailureHandler,
+	>
 {
 	fn deposit_asset(asset: &MultiAsset, location: &MultiLocation) -> Result {
 		match (
@@ -79,9 +152,8 @@ impl<
 			Match::matches_fungible(asset),
 		) {
 			// known asset
-			(Ok(who), Some(currency_id), Some(amount)) => {
-				MultiCurrency::deposit(currency_id, &who, amount).map_err(|e| XcmError::FailedToTransactAsset(e.into()))
-			}
+			(Ok(who), Some(currency_id), Some(amount)) => MultiCurrency::deposit(currency_id, &who, amount)
+				.or_else(|err| DepositFailureHandler::on_deposit_currency_fail(err, currency_id, &who, amount)),
 			// unknown asset
 			_ => UnknownAsset::deposit(asset, location).map_err(|e| XcmError::FailedToTransactAsset(e.into())),
 		}
@@ -0,0 +1,60 @@
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+use xcm::latest::{Error as XcmError, SendError};
+
+/// Cross-chain failures surfaced to callers of ORML XCM modules.
+///
+/// Low-level `XcmError`s and `SendError`s are mapped into it, so modules
+/// like xtokens and orml-xcm report why a message failed in the same terms.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum CrossChainError {
+	/// The message was rejected by the barrier.
+	BarrierRejected,
+	/// The weight of the message could not be determined, or exceeded the
+	/// weight limit.
+	WeigherFailed,
+	/// The destination could not be routed to.
+	Unroutable,
+	/// The fees paid were too low to buy execution.
+	FeeTooLow,
+	/// The message could not be delivered by the transport, or is not
+	/// supported by the destination.
+	TransportFailed,
+	/// Assets could not be withdrawn, deposited or transferred.
+	AssetTransactionFailed,
+	/// Any other failure.
+	Other,
+}
+
+impl From<XcmError> for CrossChainError {
+	fn from(e: XcmError) -> Self {
+		match e {
+			XcmError::Barrier => CrossChainError::BarrierRejected,
+			XcmError::WeightNotComputable | XcmError::WeightLimitReached(_) | XcmError::TooMuchWeightRequired => {
+				CrossChainError::WeigherFailed
+			}
+			XcmError::Unroutable => CrossChainError::Unroutable,
+			XcmError::TooExpensive | XcmError::NotHoldingFees => CrossChainError::FeeTooLow,
+			XcmError::Transport(_) | XcmError::DestinationUnsupported | XcmError::ExceedsMaxMessageSize => {
+				CrossChainError::TransportFailed
+			}
+			XcmError::FailedToTransactAsset(_) | XcmError::AssetNotFound | XcmError::NotWithdrawable => {
+				CrossChainError::AssetTransactionFailed
+			}
+			_ => CrossChainError::Other,
+		}
+	}
+}
+
+impl From<SendError> for CrossChainError {
+	fn from(e: SendError) -> Self {
+		match e {
+			SendError::CannotReachDestination(..) | SendError::Unroutable => CrossChainError::Unroutable,
+			SendError::Transport(_) | SendError::DestinationUnsupported | SendError::ExceedsMaxMessageSize => {
+				CrossChainError::TransportFailed
+			}
+		}
+	}
+}
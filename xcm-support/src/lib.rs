@@ -18,9 +18,11 @@ use xcm_executor::traits::{FilterAssetLocation, MatchesFungible};
 
 use orml_traits::location::Reserve;
 
-pub use currency_adapter::MultiCurrencyAdapter;
+pub use currency_adapter::{DepositToAlternative, MultiCurrencyAdapter, OnDepositFail};
+pub use error::CrossChainError;
 
 mod currency_adapter;
+mod error;
 
 mod tests;
 
@@ -56,6 +58,41 @@ impl FilterAssetLocation for MultiNativeAsset {
 	}
 }
 
+/// The trusted origins of concrete assets, e.g. kept by an on-chain asset
+/// registry, so the XCM trust configuration can be updated by governance
+/// instead of runtime upgrades.
+pub trait AssetTrustRegistry {
+	/// Whether `origin` is a trusted reserve of the asset at `location`.
+	fn is_reserve(location: &MultiLocation, origin: &MultiLocation) -> bool;
+
+	/// Whether `origin` is trusted to teleport the asset at `location`.
+	fn is_teleporter(location: &MultiLocation, origin: &MultiLocation) -> bool;
+}
+
+/// A `FilterAssetLocation` implementation, for `IsReserve`. Filters concrete
+/// assets whose reserve `origin` is trusted by `Registry`.
+pub struct ReserveAssetsFrom<Registry>(PhantomData<Registry>);
+impl<Registry: AssetTrustRegistry> FilterAssetLocation for ReserveAssetsFrom<Registry> {
+	fn filter_asset_location(asset: &MultiAsset, origin: &MultiLocation) -> bool {
+		match asset.id {
+			Concrete(ref location) => Registry::is_reserve(location, origin),
+			_ => false,
+		}
+	}
+}
+
+/// A `FilterAssetLocation` implementation, for `IsTeleporter`. Filters
+/// concrete assets `origin` is trusted by `Registry` to teleport.
+pub struct TeleportAssetsFrom<Registry>(PhantomData<Registry>);
+impl<Registry: AssetTrustRegistry> FilterAssetLocation for TeleportAssetsFrom<Registry> {
+	fn filter_asset_location(asset: &MultiAsset, origin: &MultiLocation) -> bool {
+		match asset.id {
+			Concrete(ref location) => Registry::is_teleporter(location, origin),
+			_ => false,
+		}
+	}
+}
+
 /// Handlers unknown asset deposit and withdraw.
 pub trait UnknownAsset {
 	/// Deposit unknown asset.
@@ -98,3 +98,93 @@ fn multi_native_asset() {
 		&MultiLocation::parent(),
 	));
 }
+
+/// Trusts the relay chain as the reserve of its token, and sibling parachain
+/// 1 to teleport its `TokenA`.
+pub struct MockRegistry;
+impl AssetTrustRegistry for MockRegistry {
+	fn is_reserve(location: &MultiLocation, origin: &MultiLocation) -> bool {
+		*location == MultiLocation::parent() && *origin == MultiLocation::parent()
+	}
+
+	fn is_teleporter(location: &MultiLocation, origin: &MultiLocation) -> bool {
+		*location == MultiLocation::sibling_parachain_general_key(1, "TokenA".into())
+			&& *origin == MultiLocation::new(1, X1(Parachain(1)))
+	}
+}
+
+#[test]
+fn reserve_and_teleport_assets_from_registry() {
+	let relay_token = MultiAsset {
+		fun: Fungible(10),
+		id: Concrete(MultiLocation::parent()),
+	};
+	let token_a = MultiAsset::sibling_parachain_asset(1, "TokenA".into(), 100);
+	let parachain_1 = MultiLocation::new(1, X1(Parachain(1)));
+
+	assert!(ReserveAssetsFrom::<MockRegistry>::filter_asset_location(
+		&relay_token,
+		&MultiLocation::parent()
+	));
+	assert!(!ReserveAssetsFrom::<MockRegistry>::filter_asset_location(
+		&relay_token,
+		&parachain_1
+	));
+	// reserve-trusted only, not trusted to teleport
+	assert!(!TeleportAssetsFrom::<MockRegistry>::filter_asset_location(
+		&relay_token,
+		&MultiLocation::parent()
+	));
+
+	assert!(TeleportAssetsFrom::<MockRegistry>::filter_asset_location(
+		&token_a,
+		&parachain_1
+	));
+	assert!(!ReserveAssetsFrom::<MockRegistry>::filter_asset_location(
+		&token_a,
+		&parachain_1
+	));
+
+	let abstract_asset = MultiAsset {
+		fun: Fungible(10),
+		id: Abstract(vec![1]),
+	};
+	assert!(!ReserveAssetsFrom::<MockRegistry>::filter_asset_location(
+		&abstract_asset,
+		&MultiLocation::parent()
+	));
+}
+
+#[test]
+fn cross_chain_error_from_xcm_error() {
+	assert_eq!(
+		CrossChainError::from(XcmError::Barrier),
+		CrossChainError::BarrierRejected
+	);
+	assert_eq!(
+		CrossChainError::from(XcmError::WeightLimitReached(100)),
+		CrossChainError::WeigherFailed
+	);
+	assert_eq!(CrossChainError::from(XcmError::Unroutable), CrossChainError::Unroutable);
+	assert_eq!(
+		CrossChainError::from(XcmError::TooExpensive),
+		CrossChainError::FeeTooLow
+	);
+	assert_eq!(
+		CrossChainError::from(XcmError::FailedToTransactAsset("")),
+		CrossChainError::AssetTransactionFailed
+	);
+	assert_eq!(CrossChainError::from(XcmError::Overflow), CrossChainError::Other);
+}
+
+#[test]
+fn cross_chain_error_from_send_error() {
+	assert_eq!(
+		CrossChainError::from(SendError::CannotReachDestination(MultiLocation::parent(), Xcm(vec![]))),
+		CrossChainError::Unroutable
+	);
+	assert_eq!(
+		CrossChainError::from(SendError::ExceedsMaxMessageSize),
+		CrossChainError::TransportFailed
+	);
+}
@@ -1,16 +1,48 @@
 //! # Xcm Module
+//!
+//! ## Overview
+//!
+//! The xcm module provides a way to send XCM messages as parachain sovereign.
+//!
+//! The latest `MaxSentMessages` sent messages are recorded in storage with
+//! their hash and destination, so they could be queried after sending.
+//! Messages are accepted in any supported XCM version and converted into the
+//! latest version, the XCM router would then wrap them into the version
+//! supported by each destination.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable functions
+//!
+//! - `send_as_sovereign`: Send an XCM message as parachain sovereign.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::large_enum_variant)]
+#![allow(clippy::unused_unit)]
 
 use frame_support::{pallet_prelude::*, traits::EnsureOrigin};
 use frame_system::pallet_prelude::*;
-use sp_std::boxed::Box;
+use scale_info::TypeInfo;
+use sp_runtime::traits::Hash;
+use sp_std::{boxed::Box, convert::TryInto};
 
-use xcm::latest::prelude::*;
+use xcm::{latest::prelude::*, VersionedMultiLocation, VersionedXcm};
+
+use orml_xcm_support::CrossChainError;
 
 pub use module::*;
 
+/// Record of a sent XCM message.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct SentMessage<Hash, BlockNumber> {
+	/// The hash of the sent message.
+	pub hash: Hash,
+	/// The destination of the sent message.
+	pub dest: MultiLocation,
+	/// The block number at which the message was sent.
+	pub sent_at: BlockNumber,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -23,6 +55,10 @@ pub mod module {
 		///
 		/// Typically root or the majority of collective.
 		type SovereignOrigin: EnsureOrigin<<Self as frame_system::Config>::Origin>;
+
+		/// The maximum number of sent messages kept in storage.
+		#[pallet::constant]
+		type MaxSentMessages: Get<u32>;
 	}
 
 	#[pallet::pallet]
@@ -31,8 +67,8 @@ pub mod module {
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// XCM message sent. \[to, message\]
-		Sent(MultiLocation, Xcm<()>),
+		/// XCM message sent. \[to, message, message_hash\]
+		Sent(MultiLocation, Xcm<()>, T::Hash),
 	}
 
 	#[pallet::error]
@@ -43,24 +79,77 @@ pub mod module {
 		/// The message and destination was recognized as being reachable but
 		/// the operation could not be completed.
 		SendFailure,
+		/// The version of the `Versioned` value used is not able to be
+		/// interpreted.
+		BadVersion,
+		/// The message could not be delivered by the transport, or is not
+		/// supported by the destination.
+		TransportFailed,
 	}
 
+	/// The latest sent messages, stored as a ring buffer of
+	/// `MaxSentMessages` slots.
+	///
+	/// SentMessages: map u32 => Option<SentMessage>
+	#[pallet::storage]
+	#[pallet::getter(fn sent_messages)]
+	pub type SentMessages<T: Config> =
+		StorageMap<_, Twox64Concat, u32, SentMessage<T::Hash, T::BlockNumber>, OptionQuery>;
+
+	/// The slot of `SentMessages` the next sent message would be stored at.
+	///
+	/// NextSentMessageIndex: u32
+	#[pallet::storage]
+	#[pallet::getter(fn next_sent_message_index)]
+	pub type NextSentMessageIndex<T: Config> = StorageValue<_, u32, ValueQuery>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Send an XCM message as parachain sovereign.
 		#[pallet::weight(100_000_000)]
 		pub fn send_as_sovereign(
 			origin: OriginFor<T>,
-			dest: Box<MultiLocation>,
-			message: Box<Xcm<()>>,
+			dest: Box<VersionedMultiLocation>,
+			message: Box<VersionedXcm<()>>,
 		) -> DispatchResult {
 			let _ = T::SovereignOrigin::ensure_origin(origin)?;
-			pallet_xcm::Pallet::<T>::send_xcm(Here, *dest.clone(), *message.clone()).map_err(|e| match e {
-				SendError::CannotReachDestination(..) => Error::<T>::Unreachable,
-				_ => Error::<T>::SendFailure,
+			let dest: MultiLocation = (*dest).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let message: Xcm<()> = (*message).try_into().map_err(|()| Error::<T>::BadVersion)?;
+
+			pallet_xcm::Pallet::<T>::send_xcm(Here, dest.clone(), message.clone()).map_err(|e| {
+				match CrossChainError::from(e) {
+					CrossChainError::Unroutable => Error::<T>::Unreachable,
+					CrossChainError::TransportFailed => Error::<T>::TransportFailed,
+					_ => Error::<T>::SendFailure,
+				}
 			})?;
-			Self::deposit_event(Event::Sent(*dest, *message));
+
+			let hash = T::Hashing::hash_of(&message);
+			Self::record_sent_message(hash, dest.clone());
+			Self::deposit_event(Event::Sent(dest, message, hash));
 			Ok(())
 		}
 	}
 }
+
+impl<T: Config> Pallet<T> {
+	/// Record a sent message, overriding the oldest record if there are
+	/// already `MaxSentMessages` records.
+	fn record_sent_message(hash: T::Hash, dest: MultiLocation) {
+		let max = T::MaxSentMessages::get();
+		if max == 0 {
+			return;
+		}
+
+		let index = Self::next_sent_message_index() % max;
+		SentMessages::<T>::insert(
+			index,
+			SentMessage {
+				hash,
+				dest,
+				sent_at: frame_system::Pallet::<T>::block_number(),
+			},
+		);
+		NextSentMessageIndex::<T>::put((index + 1) % max);
+	}
+}
@@ -0,0 +1,22 @@
+//! Runtime API definition for xtokens module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_std::prelude::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait XtokensApi<Asset, Dest, HopFee> where
+		Asset: Codec,
+		Dest: Codec,
+		HopFee: Codec,
+	{
+		/// Dry-run of a transfer, the fees expected to be deducted at each hop,
+		/// or `None` if the transfer is invalid.
+		fn hop_fees(asset: Asset, dest: Dest, dest_weight: u64) -> Option<Vec<HopFee>>;
+	}
+}
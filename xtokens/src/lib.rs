@@ -16,17 +16,54 @@
 //!
 //! - `transfer`: Transfer local assets with given `CurrencyId` and `Amount`.
 //! - `transfer_multiasset`: Transfer `MultiAsset` assets.
+//! - `transfer_with_memo`: Transfer local assets with a memo.
+//! - `transfer_multiasset_with_memo`: Transfer `MultiAsset` assets with a
+//!   memo.
+//!
+//! ### Memo
+//!
+//! The memo of a transfer is included in the `TransferredWithMemo` event, and
+//! `Config::MemoInstruction` converts it into an instruction appended to the
+//! message executed on the destination chain, after the assets are
+//! deposited, so off-chain systems could correlate the deposits. Memos are
+//! bounded by `Config::MaxMemoLen`, and weighed by their instruction and
+//! `Config::WeightPerMemoByte` per byte.
+//!
+//! ### Reserve
+//!
+//! The reserve of assets is given by `Config::ReserveProvider`. Assets
+//! reserved on the self chain are deposited to the destination as reserve
+//! transfers, other assets are withdrawn from their reserve.
+//! `AbsoluteReserveProvider` expects the locations of self assets to include
+//! the self chain, `RelativeReserveProvider` expects them to be under `Here`.
+//!
+//! ### Rate limiting
+//!
+//! Outbound transfers are rate limited by `Config::RateLimiter`, both per
+//! asset and per account and asset. Accounts whitelisted by the rate limiter
+//! bypass the limits.
+//!
+//! ### Fees
+//!
+//! The fee expected to be deducted from the transferred assets at each hop,
+//! the reserve and the destination chains, is computed from the fee per
+//! second of weight given by `Config::FeePerSecond`, e.g. from an asset
+//! registry, and included in the transfer events. `Pallet::hop_fees` computes
+//! it without transferring, for the `XtokensApi` runtime API.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::from_over_into)]
 #![allow(clippy::unused_unit)]
 #![allow(clippy::large_enum_variant)]
 
-use frame_support::{pallet_prelude::*, require_transactional, traits::Get, transactional, Parameter};
+use frame_support::{
+	pallet_prelude::*, require_transactional, traits::Get, transactional, weights::constants::WEIGHT_PER_SECOND,
+	BoundedVec, Parameter,
+};
 use frame_system::{ensure_signed, pallet_prelude::*};
 use sp_runtime::{
 	traits::{AtLeast32BitUnsigned, Convert, MaybeSerializeDeserialize, Member, Zero},
-	DispatchError,
+	DispatchError, RuntimeDebug,
 };
 use sp_std::{convert::TryInto, prelude::*, result::Result};
 
@@ -35,9 +72,10 @@ use xcm_executor::traits::{InvertLocation, WeightBounds};
 
 pub use module::*;
 use orml_traits::{
-	location::{Parse, Reserve},
-	XcmTransfer,
+	location::{Parse, ReserveProvider},
+	GetByKey, RateLimiter, XcmTransfer,
 };
+use orml_xcm_support::CrossChainError;
 
 mod mock;
 mod tests;
@@ -52,6 +90,25 @@ enum TransferKind {
 }
 use TransferKind::*;
 
+/// The fee expected to be deducted from a transfer at a hop.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct HopFee {
+	/// The chain executing the hop.
+	pub chain: MultiLocation,
+	/// The amount of the transferred asset deducted.
+	pub fee: u128,
+}
+
+/// Append the memo as the error handler of the destination message, which is
+/// set after the assets are deposited so it never runs. The memo is carried
+/// by the message without being executed.
+pub struct ErrorHandlerMemo;
+impl Convert<Vec<u8>, Option<Instruction<()>>> for ErrorHandlerMemo {
+	fn convert(memo: Vec<u8>) -> Option<Instruction<()>> {
+		Some(SetErrorHandler(Xcm(vec![DescendOrigin(X1(GeneralKey(memo)))])))
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -82,6 +139,11 @@ pub mod module {
 		#[pallet::constant]
 		type SelfLocation: Get<MultiLocation>;
 
+		/// The reserve locations of assets, `AbsoluteReserveProvider` or
+		/// `RelativeReserveProvider` depending on how `CurrencyIdConvert`
+		/// locates self assets.
+		type ReserveProvider: ReserveProvider;
+
 		/// XCM executor.
 		type XcmExecutor: ExecuteXcm<Self::Call>;
 
@@ -97,15 +159,42 @@ pub mod module {
 
 		/// Means of inverting a location.
 		type LocationInverter: InvertLocation;
+
+		/// The rate limiter of outbound transfers.
+		type RateLimiter: RateLimiter;
+
+		/// The rate limiter id of outbound transfers.
+		type RateLimiterId: Get<<Self::RateLimiter as RateLimiter>::RateLimiterId>;
+
+		/// The maximum length of a transfer memo.
+		#[pallet::constant]
+		type MaxMemoLen: Get<u32>;
+
+		/// The weight per byte of a transfer memo, on top of the weight of its
+		/// instruction.
+		#[pallet::constant]
+		type WeightPerMemoByte: Get<Weight>;
+
+		/// Convert a transfer memo into the instruction appended to the
+		/// message executed on the destination chain, `None` to only include
+		/// the memo in the event.
+		type MemoInstruction: Convert<Vec<u8>, Option<Instruction<()>>>;
+
+		/// The fee per second of weight charged by a chain in an asset, e.g.
+		/// from an asset registry. Hops of unknown fee are omitted from the
+		/// fee breakdown.
+		type FeePerSecond: GetByKey<(MultiLocation, AssetId), Option<u128>>;
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// Transferred. \[sender, currency_id, amount, dest\]
-		Transferred(T::AccountId, T::CurrencyId, T::Balance, MultiLocation),
-		/// Transferred `MultiAsset`. \[sender, asset, dest\]
-		TransferredMultiAsset(T::AccountId, MultiAsset, MultiLocation),
+		/// Transferred. \[sender, currency_id, amount, dest, hop_fees\]
+		Transferred(T::AccountId, T::CurrencyId, T::Balance, MultiLocation, Vec<HopFee>),
+		/// Transferred `MultiAsset`. \[sender, asset, dest, hop_fees\]
+		TransferredMultiAsset(T::AccountId, MultiAsset, MultiLocation, Vec<HopFee>),
+		/// Transferred with memo. \[sender, asset, dest, memo\]
+		TransferredWithMemo(T::AccountId, MultiAsset, MultiLocation, BoundedVec<u8, T::MaxMemoLen>),
 	}
 
 	#[pallet::error]
@@ -120,7 +209,7 @@ pub mod module {
 		NotCrossChainTransferableCurrency,
 		/// The message's weight could not be determined.
 		UnweighableMessage,
-		/// XCM execution failed.
+		/// XCM execution failed for a reason not covered by other errors.
 		XcmExecutionFailed,
 		/// Could not re-anchor the assets to declare the fees for the
 		/// destination chain.
@@ -134,6 +223,20 @@ pub mod module {
 		/// The version of the `Versioned` value used is not able to be
 		/// interpreted.
 		BadVersion,
+		/// The transfer exceeds the rate limit.
+		RateLimited,
+		/// XCM message was rejected by the barrier.
+		XcmBarrierRejected,
+		/// XCM message weight could not be determined or exceeded the limit.
+		XcmWeigherFailed,
+		/// XCM message destination could not be routed to.
+		XcmUnroutable,
+		/// Fees paid were too low to buy XCM execution.
+		XcmFeeTooLow,
+		/// XCM message could not be delivered by the transport.
+		XcmTransportFailed,
+		/// Assets in XCM message could not be withdrawn or deposited.
+		XcmAssetTransactionFailed,
 	}
 
 	#[pallet::hooks]
@@ -167,7 +270,7 @@ pub mod module {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			let dest: MultiLocation = (*dest).try_into().map_err(|()| Error::<T>::BadVersion)?;
-			Self::do_transfer(who, currency_id, amount, dest, dest_weight)
+			Self::do_transfer(who, currency_id, amount, dest, dest_weight, None)
 		}
 
 		/// Transfer `MultiAsset`.
@@ -193,7 +296,54 @@ pub mod module {
 			let who = ensure_signed(origin)?;
 			let asset: MultiAsset = (*asset).try_into().map_err(|()| Error::<T>::BadVersion)?;
 			let dest: MultiLocation = (*dest).try_into().map_err(|()| Error::<T>::BadVersion)?;
-			Self::do_transfer_multiasset(who, asset, dest, dest_weight, true)
+			Self::do_transfer_multiasset(who, asset, dest, dest_weight, true, None).map(|_| ())
+		}
+
+		/// Transfer native currencies with a memo.
+		///
+		/// The memo is included in the `TransferredWithMemo` event, instead of
+		/// `Transferred`, and appended to the message executed on the dest
+		/// chain by `MemoInstruction`. `dest_weight` should cover the appended
+		/// instruction.
+		#[pallet::weight(
+			Pallet::<T>::weight_of_transfer(currency_id.clone(), *amount, dest)
+				.saturating_add(Pallet::<T>::weight_of_memo(memo))
+		)]
+		#[transactional]
+		pub fn transfer_with_memo(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			amount: T::Balance,
+			dest: Box<VersionedMultiLocation>,
+			dest_weight: Weight,
+			memo: BoundedVec<u8, T::MaxMemoLen>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let dest: MultiLocation = (*dest).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			Self::do_transfer(who, currency_id, amount, dest, dest_weight, Some(memo))
+		}
+
+		/// Transfer `MultiAsset` with a memo.
+		///
+		/// The memo is included in the `TransferredWithMemo` event, instead of
+		/// `TransferredMultiAsset`, and appended to the message executed on
+		/// the dest chain by `MemoInstruction`. `dest_weight` should cover the
+		/// appended instruction.
+		#[pallet::weight(
+			Pallet::<T>::weight_of_transfer_multiasset(asset, dest).saturating_add(Pallet::<T>::weight_of_memo(memo))
+		)]
+		#[transactional]
+		pub fn transfer_multiasset_with_memo(
+			origin: OriginFor<T>,
+			asset: Box<VersionedMultiAsset>,
+			dest: Box<VersionedMultiLocation>,
+			dest_weight: Weight,
+			memo: BoundedVec<u8, T::MaxMemoLen>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let asset: MultiAsset = (*asset).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let dest: MultiLocation = (*dest).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			Self::do_transfer_multiasset(who, asset, dest, dest_weight, true, Some(memo)).map(|_| ())
 		}
 	}
 
@@ -204,14 +354,25 @@ pub mod module {
 			amount: T::Balance,
 			dest: MultiLocation,
 			dest_weight: Weight,
+			memo: Option<BoundedVec<u8, T::MaxMemoLen>>,
 		) -> DispatchResult {
 			let location: MultiLocation = T::CurrencyIdConvert::convert(currency_id.clone())
 				.ok_or(Error::<T>::NotCrossChainTransferableCurrency)?;
 
-			let asset = (location, amount.into()).into();
-			Self::do_transfer_multiasset(who.clone(), asset, dest.clone(), dest_weight, false)?;
-
-			Self::deposit_event(Event::<T>::Transferred(who, currency_id, amount, dest));
+			let asset: MultiAsset = (location, amount.into()).into();
+			let hop_fees = Self::do_transfer_multiasset(
+				who.clone(),
+				asset.clone(),
+				dest.clone(),
+				dest_weight,
+				false,
+				memo.clone(),
+			)?;
+
+			match memo {
+				Some(memo) => Self::deposit_event(Event::<T>::TransferredWithMemo(who, asset, dest, memo)),
+				None => Self::deposit_event(Event::<T>::Transferred(who, currency_id, amount, dest, hop_fees)),
+			}
 			Ok(())
 		}
 
@@ -221,23 +382,33 @@ pub mod module {
 			dest: MultiLocation,
 			dest_weight: Weight,
 			deposit_event: bool,
-		) -> DispatchResult {
+			memo: Option<BoundedVec<u8, T::MaxMemoLen>>,
+		) -> Result<Vec<HopFee>, DispatchError> {
 			if !asset.is_fungible(None) {
 				return Err(Error::<T>::NotFungible.into());
 			}
 
-			if fungible_amount(&asset).is_zero() {
-				return Ok(());
+			let amount = fungible_amount(&asset);
+			if amount.is_zero() {
+				return Ok(vec![]);
 			}
 
+			// per asset and per account limits, bypassed by whitelisted accounts
+			T::RateLimiter::try_consume(T::RateLimiterId::get(), asset.id.clone(), amount, Some(&who))
+				.map_err(|_| Error::<T>::RateLimited)?;
+			T::RateLimiter::try_consume(T::RateLimiterId::get(), (asset.id.clone(), &who), amount, Some(&who))
+				.map_err(|_| Error::<T>::RateLimited)?;
+
 			let (transfer_kind, dest, reserve, recipient) = Self::transfer_kind(&asset, &dest)?;
+			let hop_fees = Self::fees_of_hops(&transfer_kind, &asset, &reserve, &dest, dest_weight);
+			let deposit = Self::deposit_asset(recipient, memo.clone().map(|memo| memo.into_inner()));
 			let mut msg = match transfer_kind {
 				SelfReserveAsset => {
-					Self::transfer_self_reserve_asset(asset.clone(), dest.clone(), recipient, dest_weight)?
+					Self::transfer_self_reserve_asset(asset.clone(), dest.clone(), deposit, dest_weight)?
 				}
-				ToReserve => Self::transfer_to_reserve(asset.clone(), dest.clone(), recipient, dest_weight)?,
+				ToReserve => Self::transfer_to_reserve(asset.clone(), dest.clone(), deposit, dest_weight)?,
 				ToNonReserve => {
-					Self::transfer_to_non_reserve(asset.clone(), reserve, dest.clone(), recipient, dest_weight)?
+					Self::transfer_to_non_reserve(asset.clone(), reserve, dest.clone(), deposit, dest_weight)?
 				}
 			};
 
@@ -245,19 +416,64 @@ pub mod module {
 			let weight = T::Weigher::weight(&mut msg).map_err(|()| Error::<T>::UnweighableMessage)?;
 			T::XcmExecutor::execute_xcm_in_credit(origin_location, msg, weight, weight)
 				.ensure_complete()
-				.map_err(|_| Error::<T>::XcmExecutionFailed)?;
+				.map_err(|e| Error::<T>::from(CrossChainError::from(e)))?;
 
 			if deposit_event {
-				Self::deposit_event(Event::<T>::TransferredMultiAsset(who, asset, dest));
+				match memo {
+					Some(memo) => Self::deposit_event(Event::<T>::TransferredWithMemo(who, asset, dest, memo)),
+					None => Self::deposit_event(Event::<T>::TransferredMultiAsset(who, asset, dest, hop_fees.clone())),
+				}
 			}
 
-			Ok(())
+			Ok(hop_fees)
+		}
+
+		/// The fees expected to be deducted at each hop of a transfer of
+		/// `asset` to `dest`, buying `dest_weight` at each hop, without
+		/// transferring.
+		pub fn hop_fees(
+			asset: VersionedMultiAsset,
+			dest: VersionedMultiLocation,
+			dest_weight: Weight,
+		) -> Result<Vec<HopFee>, DispatchError> {
+			let asset: MultiAsset = asset.try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let dest: MultiLocation = dest.try_into().map_err(|()| Error::<T>::BadVersion)?;
+			ensure!(asset.is_fungible(None), Error::<T>::NotFungible);
+
+			let (transfer_kind, dest, reserve, _) = Self::transfer_kind(&asset, &dest)?;
+			Ok(Self::fees_of_hops(&transfer_kind, &asset, &reserve, &dest, dest_weight))
+		}
+
+		/// The fee of each hop, capped by the assets bought execution with at
+		/// the hop.
+		fn fees_of_hops(
+			transfer_kind: &TransferKind,
+			asset: &MultiAsset,
+			reserve: &MultiLocation,
+			dest: &MultiLocation,
+			dest_weight: Weight,
+		) -> Vec<HopFee> {
+			let hops = match transfer_kind {
+				SelfReserveAsset => vec![(dest, asset.clone())],
+				ToReserve => vec![(reserve, asset.clone())],
+				ToNonReserve => vec![(reserve, half(asset)), (dest, half(asset))],
+			};
+			hops.into_iter()
+				.filter_map(|(chain, fees)| {
+					let fee_per_second = T::FeePerSecond::get(&(chain.clone(), fees.id.clone()))?;
+					let fee = fee_per_second.saturating_mul(dest_weight as u128) / (WEIGHT_PER_SECOND as u128);
+					Some(HopFee {
+						chain: chain.clone(),
+						fee: fee.min(fungible_amount(&fees)),
+					})
+				})
+				.collect()
 		}
 
 		fn transfer_self_reserve_asset(
 			asset: MultiAsset,
 			dest: MultiLocation,
-			recipient: MultiLocation,
+			deposit: Vec<Instruction<()>>,
 			dest_weight: Weight,
 		) -> Result<Xcm<T::Call>, DispatchError> {
 			Ok(Xcm(vec![
@@ -266,10 +482,7 @@ pub mod module {
 					assets: All.into(),
 					max_assets: 1,
 					dest: dest.clone(),
-					xcm: Xcm(vec![
-						Self::buy_execution(asset, &dest, dest_weight)?,
-						Self::deposit_asset(recipient),
-					]),
+					xcm: Xcm([vec![Self::buy_execution(asset, &dest, dest_weight)?], deposit].concat()),
 				},
 			]))
 		}
@@ -277,7 +490,7 @@ pub mod module {
 		fn transfer_to_reserve(
 			asset: MultiAsset,
 			reserve: MultiLocation,
-			recipient: MultiLocation,
+			deposit: Vec<Instruction<()>>,
 			dest_weight: Weight,
 		) -> Result<Xcm<T::Call>, DispatchError> {
 			Ok(Xcm(vec![
@@ -285,10 +498,7 @@ pub mod module {
 				InitiateReserveWithdraw {
 					assets: All.into(),
 					reserve: reserve.clone(),
-					xcm: Xcm(vec![
-						Self::buy_execution(asset, &reserve, dest_weight)?,
-						Self::deposit_asset(recipient),
-					]),
+					xcm: Xcm([vec![Self::buy_execution(asset, &reserve, dest_weight)?], deposit].concat()),
 				},
 			]))
 		}
@@ -297,7 +507,7 @@ pub mod module {
 			asset: MultiAsset,
 			reserve: MultiLocation,
 			dest: MultiLocation,
-			recipient: MultiLocation,
+			deposit: Vec<Instruction<()>>,
 			dest_weight: Weight,
 		) -> Result<Xcm<T::Call>, DispatchError> {
 			let mut reanchored_dest = dest.clone();
@@ -324,22 +534,23 @@ pub mod module {
 							assets: All.into(),
 							max_assets: 1,
 							dest: reanchored_dest,
-							xcm: Xcm(vec![
-								Self::buy_execution(half(&asset), &dest, dest_weight)?,
-								Self::deposit_asset(recipient),
-							]),
+							xcm: Xcm([vec![Self::buy_execution(half(&asset), &dest, dest_weight)?], deposit].concat()),
 						},
 					]),
 				},
 			]))
 		}
 
-		fn deposit_asset(recipient: MultiLocation) -> Instruction<()> {
-			DepositAsset {
+		/// Deposit the assets to `recipient`, followed by the memo instruction
+		/// if any.
+		fn deposit_asset(recipient: MultiLocation, memo: Option<Vec<u8>>) -> Vec<Instruction<()>> {
+			let mut instructions = vec![DepositAsset {
 				assets: All.into(),
 				max_assets: 1,
 				beneficiary: recipient,
-			}
+			}];
+			instructions.extend(memo.and_then(T::MemoInstruction::convert));
+			instructions
 		}
 
 		fn buy_execution(
@@ -381,8 +592,9 @@ pub mod module {
 			let self_location = T::SelfLocation::get();
 			ensure!(dest != self_location, Error::<T>::NotCrossChainTransfer);
 
-			let reserve = asset.reserve().ok_or(Error::<T>::AssetHasNoReserve)?;
-			let transfer_kind = if reserve == self_location {
+			let reserve = T::ReserveProvider::reserve(asset).ok_or(Error::<T>::AssetHasNoReserve)?;
+			// self assets are reserved `Here` in the relative view
+			let transfer_kind = if reserve == self_location || reserve == MultiLocation::here() {
 				SelfReserveAsset
 			} else if reserve == dest {
 				ToReserve
@@ -428,6 +640,18 @@ pub mod module {
 			0
 		}
 
+		/// Returns weight of the `memo` of a transfer, the local weight of its
+		/// instruction and `WeightPerMemoByte` per byte.
+		fn weight_of_memo(memo: &[u8]) -> Weight {
+			let instruction_weight = T::MemoInstruction::convert(memo.to_vec()).map_or(0, |instruction| {
+				let mut msg: Xcm<T::Call> = Xcm(vec![instruction.into()]);
+				T::Weigher::weight(&mut msg).unwrap_or(Weight::max_value())
+			});
+			T::WeightPerMemoByte::get()
+				.saturating_mul(memo.len() as Weight)
+				.saturating_add(instruction_weight)
+		}
+
 		/// Returns weight of `transfer` call.
 		fn weight_of_transfer(currency_id: T::CurrencyId, amount: T::Balance, dest: &VersionedMultiLocation) -> Weight {
 			if let Some(location) = T::CurrencyIdConvert::convert(currency_id) {
@@ -448,7 +672,7 @@ pub mod module {
 			dest: MultiLocation,
 			dest_weight: Weight,
 		) -> DispatchResult {
-			Self::do_transfer(who, currency_id, amount, dest, dest_weight)
+			Self::do_transfer(who, currency_id, amount, dest, dest_weight, None)
 		}
 
 		#[require_transactional]
@@ -458,7 +682,21 @@ pub mod module {
 			dest: MultiLocation,
 			dest_weight: Weight,
 		) -> DispatchResult {
-			Self::do_transfer_multiasset(who, asset, dest, dest_weight, true)
+			Self::do_transfer_multiasset(who, asset, dest, dest_weight, true, None).map(|_| ())
+		}
+	}
+}
+
+impl<T: Config> From<CrossChainError> for Error<T> {
+	fn from(e: CrossChainError) -> Self {
+		match e {
+			CrossChainError::BarrierRejected => Error::<T>::XcmBarrierRejected,
+			CrossChainError::WeigherFailed => Error::<T>::XcmWeigherFailed,
+			CrossChainError::Unroutable => Error::<T>::XcmUnroutable,
+			CrossChainError::FeeTooLow => Error::<T>::XcmFeeTooLow,
+			CrossChainError::TransportFailed => Error::<T>::XcmTransportFailed,
+			CrossChainError::AssetTransactionFailed => Error::<T>::XcmAssetTransactionFailed,
+			CrossChainError::Other => Error::<T>::XcmExecutionFailed,
 		}
 	}
 }
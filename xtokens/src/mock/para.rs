@@ -1,4 +1,4 @@
-use super::{Amount, Balance, CurrencyId, CurrencyIdConvert, ParachainXcmRouter};
+use super::{Amount, Balance, CurrencyId, CurrencyIdConvert, ParachainXcmRouter, TREASURY};
 use crate as orml_xtokens;
 
 use frame_support::{
@@ -25,8 +25,10 @@ use xcm_builder::{
 };
 use xcm_executor::{traits::WeightTrader, Assets, Config, XcmExecutor};
 
-use orml_traits::parameter_type_with_key;
-use orml_xcm_support::{IsNativeConcrete, MultiCurrencyAdapter, MultiNativeAsset};
+use codec::Encode;
+use frame_support::storage::unhashed;
+use orml_traits::{location::AbsoluteReserveProvider, parameter_type_with_key, RateLimiter, RateLimiterError};
+use orml_xcm_support::{DepositToAlternative, IsNativeConcrete, MultiCurrencyAdapter, MultiNativeAsset};
 
 pub type AccountId = AccountId32;
 
@@ -92,8 +94,18 @@ impl orml_tokens::Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type OnDust = ();
+	type OnSlash = ();
+	type OnDeposit = ();
+	type OnTransfer = ();
 	type MaxLocks = MaxLocks;
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = [u8; 8];
 	type DustRemovalWhitelist = Everything;
+	type OnNewTokenAccount = ();
+	type OnKilledTokenAccount = ();
+	type TransferFee = ();
+	type TransferFeeExemptions = Nothing;
+	type OnLockChanged = ();
 }
 
 parameter_types! {
@@ -127,6 +139,7 @@ pub type XcmOriginToCallOrigin = (
 parameter_types! {
 	pub const UnitWeightCost: Weight = 10;
 	pub const MaxInstructions: u32 = 100;
+	pub const TreasuryAccount: AccountId = TREASURY;
 }
 
 pub type LocalAssetTransactor = MultiCurrencyAdapter<
@@ -137,6 +150,7 @@ pub type LocalAssetTransactor = MultiCurrencyAdapter<
 	LocationToAccountId,
 	CurrencyId,
 	CurrencyIdConvert,
+	DepositToAlternative<TreasuryAccount, Tokens, CurrencyId, AccountId, Balance>,
 >;
 
 pub type XcmRouter = ParachainXcmRouter<ParachainInfo>;
@@ -266,6 +280,50 @@ parameter_types! {
 	pub const BaseXcmWeight: Weight = 100_000_000;
 }
 
+pub const RATE_LIMIT: u128 = 600;
+
+/// Limit the total value consumed under each key to `RATE_LIMIT`, `BOB`
+/// is whitelisted.
+pub struct MockRateLimiter;
+impl RateLimiter for MockRateLimiter {
+	type RateLimiterId = u8;
+
+	fn is_whitelist(_: u8, key: impl Encode) -> bool {
+		key.encode() == super::BOB.encode()
+	}
+
+	fn can_consume(limiter_id: u8, key: impl Encode, value: u128) -> Result<(), RateLimiterError> {
+		let consumed: u128 = unhashed::get_or_default(&(limiter_id, key).encode());
+		if consumed.saturating_add(value) <= RATE_LIMIT {
+			Ok(())
+		} else {
+			Err(RateLimiterError::ExceedLimit)
+		}
+	}
+
+	fn consume(limiter_id: u8, key: impl Encode, value: u128) {
+		let storage_key = (limiter_id, key).encode();
+		let consumed: u128 = unhashed::get_or_default(&storage_key);
+		unhashed::put(&storage_key, &consumed.saturating_add(value));
+	}
+}
+
+parameter_types! {
+	pub const XtokensRateLimiterId: u8 = 1;
+	pub const MaxMemoLen: u32 = 32;
+	pub const WeightPerMemoByte: Weight = 1;
+}
+
+parameter_type_with_key! {
+	pub FeePerSecond: |key: (MultiLocation, AssetId)| -> Option<u128> {
+		// every chain charges one unit per weight, except para C
+		match key.0 {
+			MultiLocation { parents: 1, interior: X1(Parachain(3)) } => None,
+			_ => Some(WEIGHT_PER_SECOND as u128),
+		}
+	};
+}
+
 impl orml_xtokens::Config for Runtime {
 	type Event = Event;
 	type Balance = Balance;
@@ -273,15 +331,27 @@ impl orml_xtokens::Config for Runtime {
 	type CurrencyIdConvert = CurrencyIdConvert;
 	type AccountIdToMultiLocation = AccountIdToMultiLocation;
 	type SelfLocation = SelfLocation;
+	type ReserveProvider = AbsoluteReserveProvider;
 	type XcmExecutor = XcmExecutor<XcmConfig>;
 	type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
 	type BaseXcmWeight = BaseXcmWeight;
 	type LocationInverter = LocationInverter<Ancestry>;
+	type RateLimiter = MockRateLimiter;
+	type RateLimiterId = XtokensRateLimiterId;
+	type MaxMemoLen = MaxMemoLen;
+	type WeightPerMemoByte = WeightPerMemoByte;
+	type MemoInstruction = orml_xtokens::ErrorHandlerMemo;
+	type FeePerSecond = FeePerSecond;
+}
+
+parameter_types! {
+	pub const MaxSentMessages: u32 = 2;
 }
 
 impl orml_xcm::Config for Runtime {
 	type Event = Event;
 	type SovereignOrigin = EnsureRoot<AccountId>;
+	type MaxSentMessages = MaxSentMessages;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -3,7 +3,7 @@
 use super::*;
 use codec::Encode;
 use cumulus_primitives_core::ParaId;
-use frame_support::{assert_err, assert_noop, assert_ok, traits::Currency};
+use frame_support::{assert_err, assert_noop, assert_ok, traits::Currency, weights::GetDispatchInfo};
 use mock::*;
 use orml_traits::{ConcreteFungibleAsset, MultiCurrency};
 use polkadot_parachain::primitives::{AccountIdConversion, Sibling};
@@ -101,7 +101,7 @@ fn cannot_lost_fund_on_send_failed() {
 				),
 				40,
 			),
-			Error::<para::Runtime>::XcmExecutionFailed
+			Error::<para::Runtime>::XcmUnroutable
 		);
 
 		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &ALICE), 1_000);
@@ -149,6 +149,46 @@ fn send_relay_chain_asset_to_sibling() {
 	});
 }
 
+#[test]
+fn failed_deposit_goes_to_alternative_account() {
+	TestNet::reset();
+
+	Relay::execute_with(|| {
+		let _ = RelayBalances::deposit_creating(&para_a_account(), 1000);
+	});
+
+	ParaB::execute_with(|| {
+		orml_tokens::BlockedAccounts::<para::Runtime>::insert(BOB, orml_tokens::BlockedStatus::All);
+	});
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaXTokens::transfer(
+			Some(ALICE).into(),
+			CurrencyId::R,
+			500,
+			Box::new(
+				MultiLocation::new(
+					1,
+					X2(
+						Parachain(2),
+						Junction::AccountId32 {
+							network: NetworkId::Any,
+							id: BOB.into(),
+						}
+					)
+				)
+				.into()
+			),
+			40,
+		));
+	});
+
+	ParaB::execute_with(|| {
+		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &BOB), 0);
+		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &TREASURY), 420);
+	});
+}
+
 #[test]
 fn send_sibling_asset_to_reserve_sibling() {
 	TestNet::reset();
@@ -235,6 +275,78 @@ fn send_sibling_asset_to_non_reserve_sibling() {
 	});
 }
 
+#[test]
+fn transfer_should_include_hop_fees() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaTokens::deposit(CurrencyId::B, &ALICE, 1_000));
+	});
+
+	ParaB::execute_with(|| {
+		assert_ok!(ParaTokens::deposit(CurrencyId::B, &sibling_a_account(), 1_000));
+	});
+
+	ParaA::execute_with(|| {
+		let dest = MultiLocation::new(
+			1,
+			X2(
+				Parachain(3),
+				Junction::AccountId32 {
+					network: NetworkId::Any,
+					id: BOB.into(),
+				},
+			),
+		);
+		let reserve_fee = HopFee {
+			chain: (Parent, Parachain(2)).into(),
+			fee: 40,
+		};
+		let asset: MultiAsset = ((Parent, Parachain(2), GeneralKey("B".into())), 500).into();
+		assert_eq!(
+			ParaXTokens::hop_fees(asset.into(), dest.clone().into(), 40),
+			Ok(vec![reserve_fee.clone()])
+		);
+
+		assert_ok!(ParaXTokens::transfer(
+			Some(ALICE).into(),
+			CurrencyId::B,
+			500,
+			Box::new(dest.clone().into()),
+			40
+		));
+		para::System::assert_last_event(para::Event::XTokens(crate::Event::Transferred(
+			ALICE,
+			CurrencyId::B,
+			500,
+			dest,
+			vec![reserve_fee],
+		)));
+	});
+
+	ParaA::execute_with(|| {
+		let dest = MultiLocation::new(
+			1,
+			X2(
+				Parachain(2),
+				Junction::AccountId32 {
+					network: NetworkId::Any,
+					id: BOB.into(),
+				},
+			),
+		);
+		let asset: MultiAsset = ((Parent, Parachain(2), GeneralKey("B".into())), 30).into();
+		// capped by the assets bought execution with
+		assert_eq!(
+			ParaXTokens::hop_fees(asset.into(), dest.into(), 40),
+			Ok(vec![HopFee {
+				chain: (Parent, Parachain(2)).into(),
+				fee: 30,
+			}])
+		);
+	});
+}
+
 #[test]
 fn send_self_parachain_asset_to_sibling() {
 	TestNet::reset();
@@ -369,8 +481,8 @@ fn send_as_sovereign() {
 		let assets: MultiAsset = (Here, 1_000_000_000_000).into();
 		assert_ok!(para::OrmlXcm::send_as_sovereign(
 			para::Origin::root(),
-			Box::new(MultiLocation::parent()),
-			Box::new(Xcm(vec![
+			Box::new(MultiLocation::parent().into()),
+			Box::new(VersionedXcm::from(Xcm(vec![
 				WithdrawAsset(assets.clone().into()),
 				BuyExecution {
 					fees: assets,
@@ -381,8 +493,13 @@ fn send_as_sovereign() {
 					require_weight_at_most: 1_000_000_000,
 					call: call.encode().into(),
 				}
-			]))
+			])))
 		));
+		assert_eq!(para::OrmlXcm::next_sent_message_index(), 1);
+		assert_eq!(
+			para::OrmlXcm::sent_messages(0).map(|sent| sent.dest),
+			Some(MultiLocation::parent())
+		);
 	});
 
 	Relay::execute_with(|| {
@@ -412,8 +529,8 @@ fn send_as_sovereign_fails_if_bad_origin() {
 		assert_err!(
 			para::OrmlXcm::send_as_sovereign(
 				para::Origin::signed(ALICE),
-				Box::new(MultiLocation::parent()),
-				Box::new(Xcm(vec![
+				Box::new(MultiLocation::parent().into()),
+				Box::new(VersionedXcm::from(Xcm(vec![
 					WithdrawAsset(assets.clone().into()),
 					BuyExecution {
 						fees: assets,
@@ -424,10 +541,11 @@ fn send_as_sovereign_fails_if_bad_origin() {
 						require_weight_at_most: 1_000_000_000,
 						call: call.encode().into(),
 					}
-				]))
+				])))
 			),
 			DispatchError::BadOrigin,
 		);
+		assert_eq!(para::OrmlXcm::sent_messages(0), None);
 	});
 }
 
@@ -448,3 +566,120 @@ fn call_size_limit() {
 		If the limit is too strong, maybe consider increasing the limit",
 	);
 }
+
+#[test]
+fn transfer_exceeding_rate_limit_fails() {
+	TestNet::reset();
+
+	Relay::execute_with(|| {
+		let _ = RelayBalances::deposit_creating(&para_a_account(), 2_000);
+	});
+
+	ParaA::execute_with(|| {
+		let dest: Box<VersionedMultiLocation> = Box::new(
+			MultiLocation::new(
+				1,
+				X1(Junction::AccountId32 {
+					network: NetworkId::Any,
+					id: BOB.into(),
+				}),
+			)
+			.into(),
+		);
+
+		assert_ok!(ParaXTokens::transfer(
+			Some(ALICE).into(),
+			CurrencyId::R,
+			500,
+			dest.clone(),
+			40,
+		));
+		assert_noop!(
+			ParaXTokens::transfer(Some(ALICE).into(), CurrencyId::R, 200, dest.clone(), 40),
+			Error::<para::Runtime>::RateLimited
+		);
+
+		// whitelisted accounts bypass the limits
+		assert_ok!(ParaTokens::deposit(CurrencyId::R, &BOB, 1_000));
+		assert_ok!(ParaXTokens::transfer(Some(BOB).into(), CurrencyId::R, 1_000, dest, 40));
+		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &BOB), 0);
+	});
+
+	Relay::execute_with(|| {
+		assert_eq!(RelayBalances::free_balance(&para_a_account()), 500);
+		assert_eq!(RelayBalances::free_balance(&BOB), 1_420);
+	});
+}
+
+#[test]
+fn transfer_with_memo_works() {
+	TestNet::reset();
+
+	Relay::execute_with(|| {
+		let _ = RelayBalances::deposit_creating(&para_a_account(), 1_000);
+	});
+
+	ParaA::execute_with(|| {
+		let dest: Box<VersionedMultiLocation> = Box::new(
+			MultiLocation::new(
+				1,
+				X1(Junction::AccountId32 {
+					network: NetworkId::Any,
+					id: BOB.into(),
+				}),
+			)
+			.into(),
+		);
+
+		let memo: BoundedVec<u8, para::MaxMemoLen> = b"deposit-42".to_vec().try_into().unwrap();
+		let long_memo: Result<BoundedVec<u8, para::MaxMemoLen>, _> = vec![1; 33].try_into();
+		assert!(long_memo.is_err());
+
+		// the memo instruction and its bytes are weighed on top of the transfer
+		let transfer_weight = crate::Call::<para::Runtime>::transfer {
+			currency_id: CurrencyId::R,
+			amount: 500,
+			dest: dest.clone(),
+			dest_weight: 60,
+		}
+		.get_dispatch_info()
+		.weight;
+		let transfer_with_memo_weight = crate::Call::<para::Runtime>::transfer_with_memo {
+			currency_id: CurrencyId::R,
+			amount: 500,
+			dest: dest.clone(),
+			dest_weight: 60,
+			memo: memo.clone(),
+		}
+		.get_dispatch_info()
+		.weight;
+		// `SetErrorHandler` with one instruction, and 10 bytes
+		assert_eq!(transfer_with_memo_weight, transfer_weight + 20 + 10);
+
+		// the memo instruction and its handler are weighed on the dest chain
+		assert_ok!(ParaXTokens::transfer_with_memo(
+			Some(ALICE).into(),
+			CurrencyId::R,
+			500,
+			dest.clone(),
+			60,
+			memo.clone(),
+		));
+		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &ALICE), 500);
+		// only the event with the memo
+		para::System::assert_last_event(para::Event::XTokens(crate::Event::TransferredWithMemo(
+			ALICE,
+			(Parent, 500u128).into(),
+			(*dest).clone().try_into().unwrap(),
+			memo,
+		)));
+		assert!(!para::System::events()
+			.iter()
+			.any(|r| matches!(r.event, para::Event::XTokens(crate::Event::Transferred(..)))));
+	});
+
+	Relay::execute_with(|| {
+		assert_eq!(RelayBalances::free_balance(&para_a_account()), 500);
+		assert_eq!(RelayBalances::free_balance(&BOB), 440);
+	});
+}